@@ -0,0 +1,105 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 2D drawing export commands
+//!
+//! Storey plans and facade elevations as SVG or DXF, using the same
+//! writers as the server `/api/v1/drawing` route.
+
+use super::types::DrawingOptions;
+use ifc_lite_core::{build_entity_index, EntityDecoder, EntityScanner, IfcType};
+use ifc_lite_geometry::{
+    draw, DrawingFormat, DrawingRequest, DrawingView, ElevationDirection, GeometryRouter, Mesh,
+    StoreyLevel, DEFAULT_CUT_HEIGHT,
+};
+use rayon::prelude::*;
+
+/// Export a storey plan or elevation as SVG or DXF text
+#[tauri::command]
+pub async fn export_drawing(buffer: Vec<u8>, options: DrawingOptions) -> Result<String, String> {
+    let content = String::from_utf8(buffer).map_err(|e| format!("Invalid UTF-8: {}", e))?;
+
+    let format = match options.format.as_deref() {
+        None => DrawingFormat::default(),
+        Some(format) => {
+            DrawingFormat::parse(format).ok_or_else(|| format!("Unknown drawing format: {}", format))?
+        }
+    };
+
+    let entity_index = build_entity_index(&content);
+    let mut decoder = EntityDecoder::with_index(&content, entity_index.clone());
+    let router = GeometryRouter::with_units(&content, &mut decoder);
+
+    let view = match options.view.as_deref().map(str::trim) {
+        None | Some("") | Some("plan") => match options.elevation {
+            Some(cut_elevation) => DrawingView::Plan { cut_elevation },
+            None => {
+                let levels = StoreyLevel::collect(&content, &mut decoder, &router);
+                let level = match options.storey.as_deref() {
+                    Some(key) => StoreyLevel::find(&levels, key)
+                        .ok_or_else(|| format!("Storey not found: {}", key))?,
+                    None => levels
+                        .first()
+                        .ok_or_else(|| "Model has no building storeys".to_string())?,
+                };
+                DrawingView::plan(level, options.cut_height.unwrap_or(DEFAULT_CUT_HEIGHT))
+            }
+        },
+        Some(view) => DrawingView::Elevation(
+            ElevationDirection::parse(view).ok_or_else(|| format!("Unknown view: {}", view))?,
+        ),
+    };
+
+    // Collect element jobs, then mesh in parallel (Z-up, meters)
+    let mut scanner = EntityScanner::new(&content);
+    let mut void_index: rustc_hash::FxHashMap<u32, Vec<u32>> = rustc_hash::FxHashMap::default();
+    let mut jobs: Vec<(u32, usize, usize)> = Vec::new();
+    while let Some((id, type_name, start, end)) = scanner.next_entity() {
+        if type_name == "IFCRELVOIDSELEMENT" {
+            if let Ok(entity) = decoder.decode_at(start, end) {
+                if let (Some(host_id), Some(opening_id)) = (entity.get_ref(4), entity.get_ref(5)) {
+                    void_index.entry(host_id).or_default().push(opening_id);
+                }
+            }
+        }
+        if ifc_lite_core::has_geometry_by_name(type_name) {
+            jobs.push((id, start, end));
+        }
+    }
+
+    let unit_scale = router.unit_scale();
+    let plane_angle_scale = decoder.plane_angle_scale();
+    let elements: Vec<(u32, IfcType, Mesh)> = jobs
+        .into_par_iter()
+        .filter_map(|(id, start, end)| {
+            let mut local_decoder = EntityDecoder::with_index(&content, entity_index.clone());
+            local_decoder.set_plane_angle_scale(plane_angle_scale);
+            let entity = local_decoder.decode_at(start, end).ok()?;
+            if entity.get(6).map(|a| a.is_null()).unwrap_or(true) {
+                return None;
+            }
+            let local_router = GeometryRouter::with_scale(unit_scale);
+            let mesh = local_router
+                .process_element_with_voids(&entity, &mut local_decoder, &void_index)
+                .ok()?;
+            (!mesh.is_empty()).then_some((id, entity.ifc_type, mesh))
+        })
+        .collect();
+
+    let mut request = DrawingRequest::new(view);
+    request.format = format;
+    request.view_depth = options.view_depth;
+    if let Some(scale) = options.scale.filter(|s| *s > 0.0) {
+        request.scale = scale;
+    }
+    if let Some(hatch) = options.hatch {
+        request.hatch = hatch;
+    }
+
+    draw(
+        elements.iter().map(|(id, ifc_type, mesh)| (*id, *ifc_type, mesh)),
+        &request,
+    )
+    .map_err(|e| format!("Drawing failed: {}", e))
+}
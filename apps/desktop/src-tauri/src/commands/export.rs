@@ -0,0 +1,247 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Model export commands
+//!
+//! Standard 3D formats for other tools, using the same writers as the
+//! server `/api/v1/export/*` routes.
+
+use super::types::MeshFileOptions;
+use ifc_lite_core::{ElementMatcher, EntityDecoder, EntityScanner, RtcOffset};
+use ifc_lite_geometry::{
+    detect_rtc_offset, mesh_elements_local_with_rtc, write_mesh_files, ExportUnit, GlbBuilder,
+    Mesh, MeshExportOptions, MeshFormat, MeshPart, RepresentationSelection,
+};
+use rustc_hash::FxHashMap;
+use std::path::Path;
+
+/// Export the model as binary glTF (GLB)
+/// Repeated geometry uses EXT_mesh_gpu_instancing; node extras carry
+/// expressId, globalId and ifcType
+#[tauri::command]
+pub async fn export_glb(buffer: Vec<u8>) -> Result<tauri::ipc::Response, String> {
+    let content = String::from_utf8(buffer).map_err(|e| format!("Invalid UTF-8: {}", e))?;
+
+    let rtc = detect_rtc_offset(&content);
+    let elements = mesh_elements_local_with_rtc(
+        &content,
+        &RepresentationSelection::default(),
+        &ElementMatcher::all(),
+        &rtc,
+    );
+
+    let mut builder = GlbBuilder::new();
+    builder.set_rtc_offset(&rtc);
+    for element in elements {
+        builder.add(
+            element.info,
+            &element.mesh,
+            &element.transform,
+            element.color,
+        );
+    }
+
+    eprintln!(
+        "[Native] GLB export: {} elements, {} geometries",
+        builder.element_count(),
+        builder.geometry_count()
+    );
+
+    Ok(tauri::ipc::Response::new(builder.build()))
+}
+
+/// Save the model, a storey breakdown or a selection as STL, OBJ or PLY
+/// Returns the paths of the written files (OBJ also writes an MTL library
+/// next to it)
+#[tauri::command]
+pub async fn save_mesh_file(
+    buffer: Vec<u8>,
+    path: String,
+    options: MeshFileOptions,
+) -> Result<Vec<String>, String> {
+    let content = String::from_utf8(buffer).map_err(|e| format!("Invalid UTF-8: {}", e))?;
+
+    let format = match options.format.as_deref() {
+        None => MeshFormat::default(),
+        Some(format) => {
+            MeshFormat::parse(format).ok_or_else(|| format!("Unknown mesh format: {}", format))?
+        }
+    };
+    let unit = match options.unit.as_deref() {
+        None => ExportUnit::default(),
+        Some(unit) => ExportUnit::parse(unit).ok_or_else(|| format!("Unknown unit: {}", unit))?,
+    };
+    let group_by = options.group_by.as_deref().unwrap_or("element");
+    if !matches!(group_by, "element" | "storey" | "model") {
+        return Err(format!("Unknown grouping: {}", group_by));
+    }
+    let matcher = options
+        .express_ids
+        .as_ref()
+        .map_or_else(ElementMatcher::all, |ids| {
+            ElementMatcher::from_ids(ids.iter().copied())
+        });
+
+    let mut decoder = EntityDecoder::new(&content);
+    let mut scanner = EntityScanner::new(&content);
+    // Element -> spatial container, and part -> aggregate
+    let mut container: FxHashMap<u32, u32> = FxHashMap::default();
+    let mut aggregate: FxHashMap<u32, u32> = FxHashMap::default();
+    while let Some((_, type_name, start, end)) = scanner.next_entity() {
+        match type_name {
+            "IFCRELCONTAINEDINSPATIALSTRUCTURE" => {
+                if let Ok(entity) = decoder.decode_at(start, end) {
+                    if let (Some(elements), Some(structure)) =
+                        (entity.get_list(4), entity.get_ref(5))
+                    {
+                        for element in elements.iter().filter_map(|a| a.as_entity_ref()) {
+                            container.insert(element, structure);
+                        }
+                    }
+                }
+            }
+            "IFCRELAGGREGATES" => {
+                if let Ok(entity) = decoder.decode_at(start, end) {
+                    if let (Some(whole), Some(parts)) = (entity.get_ref(4), entity.get_list(5)) {
+                        for part in parts.iter().filter_map(|a| a.as_entity_ref()) {
+                            aggregate.insert(part, whole);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Far-away models are meshed around their own origin to keep f32 precision
+    let rtc = if options.remove_rtc.unwrap_or(true) {
+        detect_rtc_offset(&content)
+    } else {
+        RtcOffset::default()
+    };
+
+    // World-space meshes per element
+    let mut elements: Vec<(u32, String, Mesh, [f32; 4])> = mesh_elements_local_with_rtc(
+        &content,
+        &RepresentationSelection::default(),
+        &matcher,
+        &rtc,
+    )
+    .into_iter()
+    .map(|element| {
+        let id = element.info.express_id;
+        let name = match element.info.name.as_deref() {
+            Some(name) if !name.is_empty() => format!("{}_{}", name, id),
+            _ => format!("{}_{}", element.info.ifc_type.name(), id),
+        };
+        (id, name, element.world_mesh(), element.color)
+    })
+    .collect();
+    elements.sort_by_key(|(id, ..)| *id);
+
+    // Merge element meshes into storey or model parts
+    let groups: Vec<(String, Mesh, [f32; 4])> = match group_by {
+        "element" => elements
+            .into_iter()
+            .map(|(_, name, mesh, color)| (name, mesh, color))
+            .collect(),
+        _ => {
+            let mut groups: Vec<(String, Mesh, [f32; 4])> = Vec::new();
+            let mut lookup: FxHashMap<Option<u32>, usize> = FxHashMap::default();
+            for (id, _, mesh, color) in elements {
+                let key = if group_by == "storey" {
+                    let mut current = id;
+                    // Parts of an aggregate (e.g. stair flights) follow the whole
+                    for _ in 0..8 {
+                        match aggregate.get(&current) {
+                            Some(&whole) if !container.contains_key(&current) => current = whole,
+                            _ => break,
+                        }
+                    }
+                    container.get(&current).copied()
+                } else {
+                    None
+                };
+                let index = *lookup.entry(key).or_insert_with(|| {
+                    let name = match key {
+                        Some(storey) => decoder
+                            .decode_by_id(storey)
+                            .ok()
+                            .and_then(|e| e.get_string(2).map(str::to_string))
+                            .filter(|n| !n.is_empty())
+                            .unwrap_or_else(|| format!("Storey_{}", storey)),
+                        None if group_by == "storey" => "Unassigned".to_string(),
+                        None => "Model".to_string(),
+                    };
+                    groups.push((name, Mesh::new(), color));
+                    groups.len() - 1
+                });
+                groups[index].1.merge(&mesh);
+            }
+            groups
+        }
+    };
+
+    let parts: Vec<MeshPart> = groups
+        .iter()
+        .map(|(name, mesh, color)| MeshPart::new(name.clone(), mesh, *color))
+        .collect();
+    if parts.is_empty() {
+        return Err("No geometry to export".to_string());
+    }
+    let export_options = MeshExportOptions::new(unit).with_rtc(rtc);
+
+    // File name stem and target directory
+    let path = Path::new(&path);
+    let files: Vec<(String, Vec<u8>)> = if options.separate_files.unwrap_or(false) {
+        tokio::fs::create_dir_all(path)
+            .await
+            .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+        parts
+            .iter()
+            .flat_map(|part| {
+                let stem: String = part
+                    .name
+                    .chars()
+                    .map(|c| {
+                        if c.is_alphanumeric() || c == '-' || c == '_' {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect();
+                write_mesh_files(std::slice::from_ref(part), format, &export_options, &stem)
+            })
+            .map(|(name, data)| (path.join(name).to_string_lossy().into_owned(), data))
+            .collect()
+    } else {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "model".to_string());
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        write_mesh_files(&parts, format, &export_options, &stem)
+            .into_iter()
+            .map(|(name, data)| (dir.join(name).to_string_lossy().into_owned(), data))
+            .collect()
+    };
+
+    let mut written = Vec::with_capacity(files.len());
+    for (file, data) in files {
+        tokio::fs::write(&file, data)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", file, e))?;
+        written.push(file);
+    }
+
+    eprintln!(
+        "[Native] Mesh export: {} parts as {:?}, {} files",
+        parts.len(),
+        format,
+        written.len()
+    );
+
+    Ok(written)
+}
@@ -7,9 +7,12 @@
 //! These commands mirror the WASM API but use native Rust processing
 //! for maximum performance with parallel processing via rayon.
 
-use super::types::{CoordinateInfo, GeometryBatch, GeometryProgress, GeometryResult, GeometryStats, MeshData};
-use ifc_lite_core::{build_entity_index, EntityDecoder, EntityScanner, IfcType};
-use ifc_lite_geometry::{calculate_normals, GeometryRouter};
+use super::types::{
+    CoordinateInfo, GeometryBatch, GeometryFilter, GeometryProgress, GeometryResult, GeometryStats,
+    MeshData,
+};
+use ifc_lite_core::{build_entity_index, ElementFilter, EntityDecoder, EntityScanner};
+use ifc_lite_geometry::{calculate_normals, GeometryRouter, StyleIndex};
 use rayon::prelude::*;
 use std::sync::Arc;
 use std::time::Instant;
@@ -46,12 +49,16 @@ pub async fn parse_ifc_buffer(buffer: Vec<u8>) -> Result<serde_json::Value, Stri
     }))
 }
 
-/// Process IFC buffer and return all geometry meshes
+/// Process IFC buffer and return geometry meshes of the selected products
 #[tauri::command]
-pub async fn get_geometry(buffer: Vec<u8>) -> Result<GeometryResult, String> {
+pub async fn get_geometry(
+    buffer: Vec<u8>,
+    filter: Option<GeometryFilter>,
+) -> Result<GeometryResult, String> {
+    let filter = element_filter(filter)?;
     let content = String::from_utf8(buffer).map_err(|e| format!("Invalid UTF-8: {}", e))?;
 
-    let (meshes, _stats) = process_geometry(&content)?;
+    let (meshes, _stats) = process_geometry(&content, &filter)?;
 
     let total_vertices: usize = meshes.iter().map(|m| m.positions.len() / 3).sum();
     let total_triangles: usize = meshes.iter().map(|m| m.indices.len() / 3).sum();
@@ -69,7 +76,9 @@ pub async fn get_geometry(buffer: Vec<u8>) -> Result<GeometryResult, String> {
 pub async fn get_geometry_streaming(
     buffer: Vec<u8>,
     window: tauri::Window,
+    filter: Option<GeometryFilter>,
 ) -> Result<GeometryStats, String> {
+    let filter = element_filter(filter)?;
     let content = String::from_utf8(buffer).map_err(|e| format!("Invalid UTF-8: {}", e))?;
 
     let start = Instant::now();
@@ -80,8 +89,8 @@ pub async fn get_geometry_streaming(
     let mut decoder = EntityDecoder::with_index(&content, entity_index.clone());
 
     // Build style and void indices
-    let geometry_styles = build_geometry_style_index(&content, &mut decoder);
-    let style_index = build_element_style_index(&content, &geometry_styles, &mut decoder);
+    let style_index = StyleIndex::build(&content, &mut decoder);
+    let matcher = filter.compile(&content, &mut decoder);
 
     // Collect FacetedBrep IDs and void relationships
     let mut scanner = EntityScanner::new(&content);
@@ -99,7 +108,7 @@ pub async fn get_geometry_streaming(
                 }
             }
         }
-        if ifc_lite_core::has_geometry_by_name(type_name) {
+        if ifc_lite_core::has_geometry_by_name(type_name) && matcher.matches_name(id, type_name) {
             geometry_entity_count += 1;
         }
     }
@@ -107,8 +116,8 @@ pub async fn get_geometry_streaming(
     // Create geometry router with unit scale
     let router = GeometryRouter::with_units(&content, &mut decoder);
 
-    // Batch preprocess FacetedBrep
-    if !faceted_brep_ids.is_empty() {
+    // Batch preprocess FacetedBrep; filtered loads mesh them on demand
+    if !faceted_brep_ids.is_empty() && matcher.is_all() {
         router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
     }
 
@@ -125,7 +134,7 @@ pub async fn get_geometry_streaming(
     let mut processed = 0;
 
     while let Some((id, type_name, start, end)) = scanner.next_entity() {
-        if !ifc_lite_core::has_geometry_by_name(type_name) {
+        if !ifc_lite_core::has_geometry_by_name(type_name) || !matcher.matches_name(id, type_name) {
             continue;
         }
 
@@ -144,10 +153,7 @@ pub async fn get_geometry_streaming(
                         calculate_normals(&mut mesh);
                     }
 
-                    let color = style_index
-                        .get(&id)
-                        .copied()
-                        .unwrap_or_else(|| get_default_color_for_type(&entity.ifc_type));
+                    let color = style_index.element_color(id, &entity.ifc_type);
 
                     let mesh_data = convert_mesh_to_data(id, mesh, color);
                     total_vertices += mesh_data.positions.len() / 3;
@@ -205,6 +211,20 @@ pub async fn get_geometry_streaming(
     })
 }
 
+/// Element filter from command options
+fn element_filter(filter: Option<GeometryFilter>) -> Result<ElementFilter, String> {
+    let Some(filter) = filter else {
+        return Ok(ElementFilter::default());
+    };
+    Ok(ElementFilter::new()
+        .with_types(filter.types.unwrap_or_default())
+        .and_then(|f| f.with_excluded_types(filter.exclude_types.unwrap_or_default()))
+        .and_then(|f| f.with_properties(filter.properties.unwrap_or_default()))
+        .map_err(|e| e.to_string())?
+        .with_global_ids(filter.global_ids.unwrap_or_default())
+        .with_storeys(filter.storeys.unwrap_or_default()))
+}
+
 /// Entity data collected for parallel processing
 struct EntityJob {
     id: u32,
@@ -215,7 +235,10 @@ struct EntityJob {
 
 /// Internal function to process geometry (shared by sync and streaming)
 /// Uses PARALLEL processing via rayon for maximum performance
-fn process_geometry(content: &str) -> Result<(Vec<MeshData>, GeometryStats), String> {
+fn process_geometry(
+    content: &str,
+    filter: &ElementFilter,
+) -> Result<(Vec<MeshData>, GeometryStats), String> {
     let parse_start = Instant::now();
 
     // Build entity index (this is fast)
@@ -223,8 +246,8 @@ fn process_geometry(content: &str) -> Result<(Vec<MeshData>, GeometryStats), Str
     let mut decoder = EntityDecoder::with_index(content, entity_index.clone());
 
     // Build style index
-    let geometry_styles = build_geometry_style_index(content, &mut decoder);
-    let style_index = Arc::new(build_element_style_index(content, &geometry_styles, &mut decoder));
+    let style_index = Arc::new(StyleIndex::build(content, &mut decoder));
+    let matcher = filter.compile(content, &mut decoder);
 
     // PHASE 1: Collect all entities that need processing (sequential scan)
     let mut scanner = EntityScanner::new(content);
@@ -243,8 +266,8 @@ fn process_geometry(content: &str) -> Result<(Vec<MeshData>, GeometryStats), Str
             }
         }
 
-        // Collect geometry entities for parallel processing
-        if ifc_lite_core::has_geometry_by_name(type_name) {
+        // Collect selected geometry entities for parallel processing
+        if ifc_lite_core::has_geometry_by_name(type_name) && matcher.matches_name(id, type_name) {
             entity_jobs.push(EntityJob {
                 id,
                 type_name: type_name.to_string(),
@@ -257,8 +280,8 @@ fn process_geometry(content: &str) -> Result<(Vec<MeshData>, GeometryStats), Str
     // Create geometry router with unit scale
     let router = GeometryRouter::with_units(content, &mut decoder);
 
-    // Batch preprocess FacetedBrep
-    if !faceted_brep_ids.is_empty() {
+    // Batch preprocess FacetedBrep; filtered loads mesh them on demand
+    if !faceted_brep_ids.is_empty() && matcher.is_all() {
         router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
     }
 
@@ -294,10 +317,7 @@ fn process_geometry(content: &str) -> Result<(Vec<MeshData>, GeometryStats), Str
                             calculate_normals(&mut mesh);
                         }
 
-                        let color = style_index
-                            .get(&job.id)
-                            .copied()
-                            .unwrap_or_else(|| get_default_color_for_type(&entity.ifc_type));
+                        let color = style_index.element_color(job.id, &entity.ifc_type);
 
                         return Some(convert_mesh_to_data(job.id, mesh, color));
                     }
@@ -362,104 +382,3 @@ fn convert_mesh_to_data(express_id: u32, mesh: ifc_lite_geometry::Mesh, color: [
         color,
     }
 }
-
-/// Build geometry style index from IfcStyledItem entities
-fn build_geometry_style_index(
-    content: &str,
-    decoder: &mut EntityDecoder,
-) -> rustc_hash::FxHashMap<u32, [f32; 4]> {
-    use rustc_hash::FxHashMap;
-
-    let mut geometry_styles: FxHashMap<u32, [f32; 4]> = FxHashMap::default();
-    let mut scanner = EntityScanner::new(content);
-
-    while let Some((_, type_name, start, end)) = scanner.next_entity() {
-        if type_name == "IFCSTYLEDITEM" {
-            if let Ok(entity) = decoder.decode_at(start, end) {
-                // IfcStyledItem: Item (0), Styles (1), Name (2)
-                if let Some(item_ref) = entity.get_ref(0) {
-                    // Try to extract color from Styles
-                    if let Some(color) = extract_color_from_styled_item(&entity, decoder) {
-                        geometry_styles.insert(item_ref, color);
-                    }
-                }
-            }
-        }
-    }
-
-    geometry_styles
-}
-
-/// Build element style index by traversing from IfcProduct → Representation → Item
-fn build_element_style_index(
-    content: &str,
-    geometry_styles: &rustc_hash::FxHashMap<u32, [f32; 4]>,
-    decoder: &mut EntityDecoder,
-) -> rustc_hash::FxHashMap<u32, [f32; 4]> {
-    use rustc_hash::FxHashMap;
-
-    let mut element_styles: FxHashMap<u32, [f32; 4]> = FxHashMap::default();
-    let mut scanner = EntityScanner::new(content);
-
-    // Map representation items to their colors
-    while let Some((id, type_name, start, end)) = scanner.next_entity() {
-        if !ifc_lite_core::has_geometry_by_name(type_name) {
-            continue;
-        }
-
-        if let Ok(entity) = decoder.decode_at(start, end) {
-            // IfcProduct has Representation at index 6
-            if let Some(repr_ref) = entity.get_ref(6) {
-                // Try to find color through representation
-                if let Some(color) = find_color_for_representation(repr_ref, geometry_styles, decoder)
-                {
-                    element_styles.insert(id, color);
-                }
-            }
-        }
-    }
-
-    element_styles
-}
-
-/// Try to extract color from IfcStyledItem's Styles attribute
-fn extract_color_from_styled_item(
-    _entity: &ifc_lite_core::DecodedEntity,
-    _decoder: &mut EntityDecoder,
-) -> Option<[f32; 4]> {
-    // Simplified: return None for now, will use default colors
-    // Full implementation would traverse IfcPresentationStyleAssignment → IfcSurfaceStyle → IfcSurfaceStyleRendering
-    None
-}
-
-/// Find color for a representation by checking its items against geometry styles
-fn find_color_for_representation(
-    _repr_ref: u32,
-    geometry_styles: &rustc_hash::FxHashMap<u32, [f32; 4]>,
-    _decoder: &mut EntityDecoder,
-) -> Option<[f32; 4]> {
-    // Simplified: return first available color
-    geometry_styles.values().next().copied()
-}
-
-/// Get default color based on IFC type
-fn get_default_color_for_type(ifc_type: &IfcType) -> [f32; 4] {
-    match ifc_type {
-        IfcType::IfcWall | IfcType::IfcWallStandardCase => [0.9, 0.9, 0.85, 1.0],
-        IfcType::IfcSlab => [0.7, 0.7, 0.7, 1.0],
-        IfcType::IfcRoof => [0.6, 0.3, 0.2, 1.0],
-        IfcType::IfcColumn => [0.8, 0.8, 0.8, 1.0],
-        IfcType::IfcBeam => [0.75, 0.75, 0.75, 1.0],
-        IfcType::IfcDoor => [0.55, 0.35, 0.2, 1.0],
-        IfcType::IfcWindow => [0.6, 0.8, 0.95, 0.5],
-        IfcType::IfcStair | IfcType::IfcStairFlight => [0.65, 0.65, 0.65, 1.0],
-        IfcType::IfcRailing => [0.5, 0.5, 0.5, 1.0],
-        IfcType::IfcFurniture | IfcType::IfcFurnishingElement => [0.6, 0.5, 0.4, 1.0],
-        IfcType::IfcPlate => [0.7, 0.75, 0.8, 1.0],
-        IfcType::IfcMember => [0.7, 0.7, 0.7, 1.0],
-        IfcType::IfcCovering => [0.85, 0.85, 0.8, 1.0],
-        IfcType::IfcSpace => [0.5, 0.5, 0.8, 0.3],
-        IfcType::IfcOpeningElement => [0.9, 0.9, 0.9, 0.2],
-        _ => [0.7, 0.7, 0.7, 1.0], // Default gray
-    }
-}
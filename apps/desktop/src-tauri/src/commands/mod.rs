@@ -8,6 +8,8 @@
 //! and the native Rust IFC processing libraries.
 
 pub mod cache;
+pub mod drawing;
+pub mod export;
 pub mod file_dialog;
 pub mod ifc;
 mod types;
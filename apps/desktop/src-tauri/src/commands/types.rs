@@ -155,3 +155,59 @@ pub struct FileInfo {
     pub name: String,
     pub size: u64,
 }
+
+/// Options for 2D drawing export
+/// `view` is "plan" (default) or "north" / "south" / "east" / "west"
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawingOptions {
+    /// "svg" (default) or "dxf"
+    pub format: Option<String>,
+    pub view: Option<String>,
+    /// Storey name, GlobalId or express id (default: lowest storey)
+    pub storey: Option<String>,
+    /// Absolute cut elevation in meters, overrides `storey`
+    pub elevation: Option<f64>,
+    /// Cut height above the storey in meters (default: 1.2)
+    pub cut_height: Option<f64>,
+    /// Projection depth below the cut in meters (default: 1.2)
+    pub view_depth: Option<f64>,
+    /// SVG scale denominator (default: 100)
+    pub scale: Option<f64>,
+    /// Hatch DXF cut polygons (default: true)
+    pub hatch: Option<bool>,
+}
+
+/// Options for STL, OBJ and PLY export
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeshFileOptions {
+    /// "stl" (default), "stl-ascii", "obj", "ply" or "ply-ascii"
+    pub format: Option<String>,
+    /// Output unit: "m" (default), "cm", "mm", "ft" or "in"
+    pub unit: Option<String>,
+    /// Move far-away (georeferenced) models to the origin (default: true)
+    pub remove_rtc: Option<bool>,
+    /// "element" (default), "storey" or "model": one part per element, per storey or in total
+    pub group_by: Option<String>,
+    /// Only export these elements
+    pub express_ids: Option<Vec<u32>>,
+    /// Write one file per part into the directory `path` (default: false)
+    pub separate_files: Option<bool>,
+}
+
+/// Product selection for geometry commands; all given criteria must hold
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeometryFilter {
+    /// IFC types to mesh, subtypes included, e.g. "IfcWall"
+    pub types: Option<Vec<String>>,
+    /// IFC types to skip, subtypes included
+    pub exclude_types: Option<Vec<String>>,
+    /// GlobalIds to mesh
+    pub global_ids: Option<Vec<String>>,
+    /// Storeys to mesh, by name, GlobalId or "#id"
+    pub storeys: Option<Vec<String>>,
+    /// Property predicates, e.g. "Pset_WallCommon.IsExternal=true"
+    pub properties: Option<Vec<String>>,
+}
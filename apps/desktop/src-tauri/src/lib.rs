@@ -28,6 +28,9 @@ pub fn run() {
             commands::cache::delete_cache_entry,
             commands::cache::get_cache_stats,
             commands::file_dialog::open_ifc_file,
+            commands::drawing::export_drawing,
+            commands::export::export_glb,
+            commands::export::save_mesh_file,
         ])
         .setup(|app| {
             // Create cache directory on startup
@@ -25,6 +25,8 @@ pub struct Config {
     pub batch_size: usize,
     /// Maximum cache age in days.
     pub cache_max_age_days: u64,
+    /// Number of models whose query indexes are kept in memory.
+    pub query_cache_models: usize,
     /// Allowed CORS origins (comma-separated, or "*" for all in development).
     pub cors_origins: Vec<String>,
 }
@@ -79,6 +81,10 @@ impl Config {
                 .unwrap_or_else(|_| "7".into())
                 .parse()
                 .unwrap_or(7),
+            query_cache_models: std::env::var("QUERY_CACHE_MODELS")
+                .unwrap_or_else(|_| "4".into())
+                .parse()
+                .unwrap_or(4),
             cors_origins: std::env::var("CORS_ORIGINS")
                 .unwrap_or_else(|_| {
                     // Default: allow common development origins
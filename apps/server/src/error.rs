@@ -18,6 +18,9 @@ pub enum ApiError {
     #[error("Missing file in request")]
     MissingFile,
 
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
     #[error("File too large: maximum size is {max_mb} MB")]
     FileTooLarge { max_mb: usize },
 
@@ -57,6 +60,7 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let (status, code) = match &self {
             ApiError::MissingFile => (StatusCode::BAD_REQUEST, "MISSING_FILE"),
+            ApiError::InvalidRequest(_) => (StatusCode::BAD_REQUEST, "INVALID_REQUEST"),
             ApiError::FileTooLarge { .. } => (StatusCode::PAYLOAD_TOO_LARGE, "FILE_TOO_LARGE"),
             ApiError::InvalidUtf8(_) => (StatusCode::BAD_REQUEST, "INVALID_UTF8"),
             ApiError::Multipart(_) => (StatusCode::BAD_REQUEST, "MULTIPART_ERROR"),
@@ -112,3 +116,14 @@ impl From<crate::services::parquet_data_model::DataModelParquetError> for ApiErr
         ApiError::Parquet(err.to_string())
     }
 }
+
+impl From<crate::services::DrawingError> for ApiError {
+    fn from(err: crate::services::DrawingError) -> Self {
+        use crate::services::DrawingError;
+        match err {
+            DrawingError::StoreyNotFound(_) => ApiError::NotFound(err.to_string()),
+            DrawingError::Geometry(_) => ApiError::Processing(err.to_string()),
+            _ => ApiError::InvalidRequest(err.to_string()),
+        }
+    }
+}
@@ -19,6 +19,11 @@
 //! - `POST /api/v1/parse/metadata` - Quick metadata only
 //! - `POST /api/v1/parse/parquet` - Full parse with Parquet-encoded geometry (~15x smaller)
 //! - `POST /api/v1/parse/parquet/optimized` - ara3d BOS-optimized format (~50x smaller)
+//! - `POST /api/v1/drawing` - Storey plan or elevation as SVG or DXF
+//! - `POST /api/v1/export/glb` - Binary glTF with instancing and element metadata
+//! - `POST /api/v1/export/3dtiles` - OGC 3D Tiles tileset, tiles served from `GET /api/v1/tiles/:cache_key/:file`
+//! - `POST /api/v1/query?q=...` - Element query, e.g. `IfcDoor where storey = "Level 2"`
+//! - `GET /api/v1/query/:cache_key?q=...` - Element query against a model indexed by an earlier POST
 //! - `GET /api/v1/cache/:key` - Retrieve cached result
 
 use axum::{
@@ -46,6 +51,7 @@ mod types;
 
 use config::Config;
 use services::cache::DiskCache;
+use services::QueryModelCache;
 
 /// Build CORS layer based on configuration.
 ///
@@ -76,6 +82,7 @@ fn build_cors_layer(config: &Config) -> CorsLayer {
 pub struct AppState {
     pub cache: Arc<DiskCache>,
     pub config: Arc<Config>,
+    pub query_models: Arc<QueryModelCache>,
 }
 
 #[tokio::main]
@@ -111,6 +118,7 @@ async fn main() {
     let state = AppState {
         cache,
         config: Arc::new(config.clone()),
+        query_models: Arc::new(QueryModelCache::new(config.query_cache_models)),
     };
 
     // Build router
@@ -127,6 +135,15 @@ async fn main() {
         .route("/api/v1/parse/parquet", post(routes::parse::parse_parquet))
         .route("/api/v1/parse/parquet/optimized", post(routes::parse::parse_parquet_optimized))
         .route("/api/v1/parse/data-model/:cache_key", get(routes::parse::get_data_model))
+        // Drawing export
+        .route("/api/v1/drawing", post(routes::drawing::export_drawing))
+        // Model export
+        .route("/api/v1/export/glb", post(routes::export::export_glb))
+        .route("/api/v1/export/3dtiles", post(routes::export::export_tiles))
+        .route("/api/v1/tiles/:cache_key/:file", get(routes::export::get_tile))
+        // Element queries
+        .route("/api/v1/query", post(routes::query::query_model))
+        .route("/api/v1/query/:cache_key", get(routes::query::query_cached))
         // Cache endpoints
         .route("/api/v1/cache/{key}", get(routes::cache::get_cached))
         .route("/api/v1/cache/check/:hash", get(routes::parse::check_cache))
@@ -13,13 +13,15 @@ use axum::{
 };
 
 /// GET /api/v1/cache/:key - Retrieve cached result.
+///
+/// `key` is the `cache_key` of a `/api/v1/parse` response.
 pub async fn get_cached(
     State(state): State<AppState>,
     Path(key): Path<String>,
 ) -> Result<Json<ParseResponse>, ApiError> {
     tracing::debug!(key = %key, "Cache lookup");
 
-    match state.cache.get::<ParseResponse>(&key).await? {
+    match state.cache.get::<ParseResponse>(&format!("{}-json-v3", key)).await? {
         Some(mut response) => {
             response.stats.from_cache = true;
             tracing::info!(key = %key, "Cache HIT");
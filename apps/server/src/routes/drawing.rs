@@ -0,0 +1,63 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Drawing endpoint for 2D plan and elevation export.
+
+use crate::error::ApiError;
+use crate::routes::parse::extract_file;
+use crate::services::generate_drawing;
+use crate::types::DrawingOptions;
+use crate::AppState;
+use axum::{
+    body::Body,
+    extract::{Multipart, Query, State},
+    http::{header, StatusCode},
+    response::Response,
+};
+
+/// POST /api/v1/drawing - Storey plan or facade elevation as SVG or DXF.
+///
+/// Query options: `format` (svg|dxf), `view` (plan|north|south|east|west),
+/// `storey`, `elevation`, `cut_height`, `view_depth`, `scale`, `hatch`.
+pub async fn export_drawing(
+    State(state): State<AppState>,
+    Query(options): Query<DrawingOptions>,
+    mut multipart: Multipart,
+) -> Result<Response, ApiError> {
+    // Extract file from multipart
+    let data = extract_file(&mut multipart).await?;
+
+    // Check file size
+    if data.len() > state.config.max_file_size_mb * 1024 * 1024 {
+        return Err(ApiError::FileTooLarge {
+            max_mb: state.config.max_file_size_mb,
+        });
+    }
+
+    let content = String::from_utf8(data)?;
+
+    // Process on blocking thread pool (CPU-intensive)
+    let drawing =
+        tokio::task::spawn_blocking(move || generate_drawing(&content, &options)).await??;
+
+    let file_name: String = drawing
+        .name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, drawing.format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}.{}\"",
+                file_name,
+                drawing.format.extension()
+            ),
+        )
+        .body(Body::from(drawing.body))
+        .map_err(|e| ApiError::Internal(e.to_string()))
+}
@@ -0,0 +1,169 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Export endpoints for standard 3D file formats.
+
+use crate::error::ApiError;
+use crate::routes::parse::extract_file;
+use crate::services::{export_glb as export_glb_file, generate_tileset, DiskCache};
+use crate::types::{ParseOptions, TilesOptions, TilesetResponse};
+use crate::AppState;
+use axum::{
+    body::Body,
+    extract::{Multipart, Path, Query, State},
+    http::{header, StatusCode},
+    response::Response,
+    Json,
+};
+use ifc_lite_core::ElementFilter;
+
+/// POST /api/v1/export/glb - Binary glTF with instancing and element metadata.
+///
+/// Takes the representation and element filter options of `/api/v1/parse`.
+pub async fn export_glb(
+    State(state): State<AppState>,
+    Query(options): Query<ParseOptions>,
+    mut multipart: Multipart,
+) -> Result<Response, ApiError> {
+    let filter = export_filter(&options)?;
+
+    // Extract file from multipart
+    let data = extract_file(&mut multipart).await?;
+
+    // Check file size
+    if data.len() > state.config.max_file_size_mb * 1024 * 1024 {
+        return Err(ApiError::FileTooLarge {
+            max_mb: state.config.max_file_size_mb,
+        });
+    }
+
+    let content = String::from_utf8(data)?;
+
+    // Process on blocking thread pool (CPU-intensive)
+    let selection = options.representation_selection();
+    let glb =
+        tokio::task::spawn_blocking(move || export_glb_file(&content, &selection, &filter)).await?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "model/gltf-binary")
+        .header(header::CONTENT_DISPOSITION, "attachment; filename=\"model.glb\"")
+        .header(header::CONTENT_LENGTH, glb.len())
+        .body(Body::from(glb))
+        .map_err(|e| ApiError::Internal(e.to_string()))
+}
+
+/// POST /api/v1/export/3dtiles - OGC 3D Tiles tileset for streaming.
+///
+/// Query options: the representation and element filter options of
+/// `/api/v1/parse`, `max_elements`, plus `latitude` and `longitude` of the map
+/// conversion origin for CRSs other than WGS 84 UTM. Tiles are stored in the
+/// cache and served from `/api/v1/tiles/{key}/tileset.json`.
+pub async fn export_tiles(
+    State(state): State<AppState>,
+    Query(options): Query<ParseOptions>,
+    Query(tiles_options): Query<TilesOptions>,
+    mut multipart: Multipart,
+) -> Result<Json<TilesetResponse>, ApiError> {
+    let filter = export_filter(&options)?;
+
+    // Extract file from multipart
+    let data = extract_file(&mut multipart).await?;
+
+    // Check file size
+    if data.len() > state.config.max_file_size_mb * 1024 * 1024 {
+        return Err(ApiError::FileTooLarge {
+            max_mb: state.config.max_file_size_mb,
+        });
+    }
+
+    if tiles_options.latitude.is_some() != tiles_options.longitude.is_some() {
+        return Err(ApiError::InvalidRequest(
+            "latitude and longitude must be given together".to_string(),
+        ));
+    }
+
+    let options_key = format!(
+        "{}|{:?}|{:?}|{:?}",
+        options.cache_key(DiskCache::generate_key(&data)),
+        tiles_options.max_elements,
+        tiles_options.latitude,
+        tiles_options.longitude,
+    );
+    let cache_key = format!("{}-3dtiles-v3", &DiskCache::generate_key(options_key.as_bytes())[..32]);
+
+    if !options.skip_cache {
+        if let Some(cached) = state.cache.get::<TilesetResponse>(&cache_key).await? {
+            tracing::info!(cache_key = %cache_key, "3D Tiles cache HIT");
+            return Ok(Json(cached));
+        }
+    }
+
+    let content = String::from_utf8(data)?;
+
+    // Process on blocking thread pool (CPU-intensive)
+    let selection = options.representation_selection();
+    let generated = tokio::task::spawn_blocking(move || {
+        generate_tileset(&content, &selection, &filter, &tiles_options)
+    })
+    .await?;
+
+    let tileset = &generated.tileset;
+    state
+        .cache
+        .set_bytes(&format!("{}/tileset.json", cache_key), tileset.json.as_bytes())
+        .await?;
+    for tile in &tileset.contents {
+        state
+            .cache
+            .set_bytes(&format!("{}/{}", cache_key, tile.uri), &tile.glb)
+            .await?;
+    }
+
+    let response = TilesetResponse {
+        tileset_url: format!("/api/v1/tiles/{}/tileset.json", cache_key),
+        cache_key: cache_key.clone(),
+        tile_count: tileset.contents.len(),
+        element_count: generated.element_count,
+        georeferenced: generated.georeferenced,
+    };
+    state.cache.set(&cache_key, &response).await?;
+
+    Ok(Json(response))
+}
+
+/// Element filter for an export; per-layer meshes cannot be instanced.
+fn export_filter(options: &ParseOptions) -> Result<ElementFilter, ApiError> {
+    if options.split_layers {
+        return Err(ApiError::InvalidRequest(
+            "split_layers is not supported for export".to_string(),
+        ));
+    }
+    options.element_filter().map_err(ApiError::InvalidRequest)
+}
+
+/// GET /api/v1/tiles/:cache_key/:file - `tileset.json` or tile content.
+pub async fn get_tile(
+    State(state): State<AppState>,
+    Path((cache_key, file)): Path<(String, String)>,
+) -> Result<Response, ApiError> {
+    let data = state
+        .cache
+        .get_bytes(&format!("{}/{}", cache_key, file))
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("{}/{}", cache_key, file)))?;
+
+    let content_type = if file.ends_with(".json") {
+        "application/json"
+    } else {
+        "model/gltf-binary"
+    };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::CONTENT_LENGTH, data.len())
+        .body(Body::from(data))
+        .map_err(|e| ApiError::Internal(e.to_string()))
+}
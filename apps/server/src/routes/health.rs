@@ -68,6 +68,26 @@ pub async fn info() -> Json<ApiInfoResponse> {
                 path: "/api/v1/parse/metadata",
                 description: "Quick metadata extraction only",
             },
+            EndpointInfo {
+                method: "POST",
+                path: "/api/v1/drawing",
+                description: "Storey plan or elevation as SVG or DXF",
+            },
+            EndpointInfo {
+                method: "POST",
+                path: "/api/v1/export/glb",
+                description: "Binary glTF (GLB) with instancing and element metadata",
+            },
+            EndpointInfo {
+                method: "POST",
+                path: "/api/v1/export/3dtiles",
+                description: "OGC 3D Tiles tileset for streaming, placed by georeferencing",
+            },
+            EndpointInfo {
+                method: "GET",
+                path: "/api/v1/tiles/:cache_key/:file",
+                description: "Tileset JSON or tile content of a 3D Tiles export",
+            },
             EndpointInfo {
                 method: "GET",
                 path: "/api/v1/cache/:key",
@@ -5,5 +5,8 @@
 //! API routes for the IFC server.
 
 pub mod cache;
+pub mod drawing;
+pub mod export;
 pub mod health;
 pub mod parse;
+pub mod query;
@@ -10,11 +10,13 @@ use crate::services::{
     serialize_data_model_to_parquet, serialize_to_parquet,
     serialize_to_parquet_optimized_with_stats, OptimizedStats, VERTEX_MULTIPLIER,
 };
-use crate::types::{MetadataResponse, ModelMetadata, ParseResponse, ProcessingStats, StreamEvent};
+use crate::types::{
+    MetadataResponse, ModelMetadata, ParseOptions, ParseResponse, ProcessingStats, StreamEvent,
+};
 use crate::AppState;
 use axum::{
     body::Body,
-    extract::{Multipart, State},
+    extract::{Multipart, Query, State},
     http::{header, StatusCode},
     response::{sse::{Event, KeepAlive, Sse}, Response},
     Json,
@@ -28,7 +30,7 @@ use std::io::Read;
 
 /// Extract file data from multipart request.
 /// Automatically decompresses gzip-compressed files.
-async fn extract_file(multipart: &mut Multipart) -> Result<Vec<u8>, ApiError> {
+pub(crate) async fn extract_file(multipart: &mut Multipart) -> Result<Vec<u8>, ApiError> {
     while let Some(field) = multipart.next_field().await? {
         let field_name = field.name().unwrap_or_default();
         tracing::debug!(field_name = %field_name, "Processing multipart field");
@@ -67,8 +69,11 @@ async fn extract_file(multipart: &mut Multipart) -> Result<Vec<u8>, ApiError> {
 /// POST /api/v1/parse - Full synchronous parse.
 pub async fn parse_full(
     State(state): State<AppState>,
+    Query(options): Query<ParseOptions>,
     mut multipart: Multipart,
 ) -> Result<Json<ParseResponse>, ApiError> {
+    let filter = options.element_filter().map_err(ApiError::InvalidRequest)?;
+
     // Extract file from multipart
     let data = extract_file(&mut multipart).await?;
 
@@ -80,10 +85,11 @@ pub async fn parse_full(
     }
 
     // Generate cache key
-    let cache_key = DiskCache::generate_key(&data);
+    let cache_key = options.cache_key(DiskCache::generate_key(&data));
+    let json_cache_key = format!("{}-json-v3", cache_key);
 
     // Check cache first
-    if let Some(mut cached) = state.cache.get::<ParseResponse>(&cache_key).await? {
+    if let Some(mut cached) = state.cache.get::<ParseResponse>(&json_cache_key).await? {
         tracing::info!(cache_key = %cache_key, "Cache HIT");
         cached.stats.from_cache = true;
         return Ok(Json(cached));
@@ -95,7 +101,13 @@ pub async fn parse_full(
     let content = String::from_utf8(data)?;
 
     // Process on blocking thread pool (CPU-intensive)
-    let result = tokio::task::spawn_blocking(move || process_geometry(&content)).await?;
+    let selection = options.representation_selection();
+    let split_layers = options.split_layers;
+    let check_self_intersections = options.check_self_intersections;
+    let result = tokio::task::spawn_blocking(move || {
+        process_geometry(&content, &selection, &filter, split_layers, check_self_intersections)
+    })
+    .await?;
 
     let response = ParseResponse {
         cache_key: cache_key.clone(),
@@ -108,7 +120,7 @@ pub async fn parse_full(
     let cache = state.cache.clone();
     let response_clone = response.clone();
     tokio::spawn(async move {
-        if let Err(e) = cache.set(&cache_key, &response_clone).await {
+        if let Err(e) = cache.set(&json_cache_key, &response_clone).await {
             tracing::error!(error = %e, "Failed to cache result");
         }
     });
@@ -119,8 +131,11 @@ pub async fn parse_full(
 /// POST /api/v1/parse/stream - Streaming SSE parse.
 pub async fn parse_stream(
     State(state): State<AppState>,
+    Query(options): Query<ParseOptions>,
     mut multipart: Multipart,
 ) -> Result<Sse<impl futures::Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let filter = options.element_filter().map_err(ApiError::InvalidRequest)?;
+
     // Extract file
     let data = extract_file(&mut multipart).await?;
 
@@ -136,7 +151,14 @@ pub async fn parse_stream(
     let max_batch_size = state.config.max_batch_size;
 
     // Create streaming response with dynamic batch sizing
-    let stream = process_streaming(content, initial_batch_size, max_batch_size).map(|event: StreamEvent| {
+    let stream = process_streaming(
+        content,
+        initial_batch_size,
+        max_batch_size,
+        options.representation_selection(),
+        filter,
+        options.check_self_intersections,
+    ).map(|event: StreamEvent| {
         let json = serde_json::to_string(&event).unwrap_or_else(|e| {
             serde_json::to_string(&StreamEvent::Error {
                 message: e.to_string(),
@@ -198,8 +220,11 @@ pub enum ParquetStreamEvent {
 /// After `complete`, client should fetch data model via `/api/v1/data-model/{cache_key}`.
 pub async fn parse_parquet_stream(
     State(state): State<AppState>,
+    Query(options): Query<ParseOptions>,
     mut multipart: Multipart,
 ) -> Result<Sse<std::pin::Pin<Box<dyn futures::Stream<Item = Result<Event, Infallible>> + Send>>>, ApiError> {
+    let filter = options.element_filter().map_err(ApiError::InvalidRequest)?;
+
     use base64::{Engine, engine::general_purpose::STANDARD};
     use crate::services::serialize_to_parquet;
     use std::sync::{Arc, Mutex};
@@ -217,13 +242,13 @@ pub async fn parse_parquet_stream(
     }
 
     // Generate cache key before processing
-    let cache_key = DiskCache::generate_key(&data);
+    let cache_key = options.cache_key(DiskCache::generate_key(&data));
     let cache_key_clone = cache_key.clone();
 
     // OPTIMIZATION: Check cache first and fast-path return if available
     // This avoids re-processing files that are already cached
-    let parquet_cache_key = format!("{}-parquet-v2", cache_key);
-    let metadata_cache_key = format!("{}-parquet-metadata-v2", cache_key);
+    let parquet_cache_key = format!("{}-parquet-v3", cache_key);
+    let metadata_cache_key = format!("{}-parquet-metadata-v3", cache_key);
 
     if let (Some(cached_parquet), Some(cached_metadata_json)) = (
         state.cache.get_bytes(&parquet_cache_key).await?,
@@ -292,7 +317,14 @@ pub async fn parse_parquet_stream(
     let cache_key_for_geometry = cache_key.clone();
 
     // Create streaming response that yields Parquet batches
-    let stream = process_streaming(content.clone(), initial_batch_size, max_batch_size).map(move |event: StreamEvent| {
+    let stream = process_streaming(
+        content.clone(),
+        initial_batch_size,
+        max_batch_size,
+        options.representation_selection(),
+        filter,
+        options.check_self_intersections,
+    ).map(move |event: StreamEvent| {
         let sse_event = match event {
             StreamEvent::Start { total_estimate } => {
                 ParquetStreamEvent::Start {
@@ -371,7 +403,7 @@ pub async fn parse_parquet_stream(
                         combined_parquet.extend_from_slice(&0u32.to_le_bytes()); // data_model_len = 0
 
                         // Cache geometry (same format as non-streaming)
-                        let parquet_cache_key = format!("{}-parquet-v2", key);
+                        let parquet_cache_key = format!("{}-parquet-v3", key);
                         if let Err(e) = cache.set_bytes(&parquet_cache_key, &combined_parquet).await {
                             tracing::error!(error = %e, "Failed to cache geometry from stream");
                         } else {
@@ -390,7 +422,7 @@ pub async fn parse_parquet_stream(
                             data_model_stats: None, // Data model cached separately via data model endpoint
                         };
                         if let Ok(metadata_json) = serde_json::to_vec(&metadata_header) {
-                            let metadata_cache_key = format!("{}-parquet-metadata-v2", key);
+                            let metadata_cache_key = format!("{}-parquet-metadata-v3", key);
                             if let Err(e) = cache.set_bytes(&metadata_cache_key, &metadata_json).await {
                                 tracing::error!(error = %e, "Failed to cache metadata from stream");
                             } else {
@@ -422,10 +454,11 @@ pub async fn parse_parquet_stream(
     let content_for_cache = content.clone();
     let cache_key_for_dm = cache_key.clone();
     let cache_for_dm = cache.clone();
+    let computed_quantities = options.computed_quantities;
     tokio::spawn(async move {
         // Run data model extraction in blocking task
         let dm_result = tokio::task::spawn_blocking(move || {
-            extract_data_model(&content_for_cache)
+            extract_data_model(&content_for_cache, computed_quantities)
         }).await;
 
         if let Ok(data_model) = dm_result {
@@ -435,7 +468,7 @@ pub async fn parse_parquet_stream(
             }).await;
 
             if let Ok(Ok(parquet_data)) = serialize_result {
-                let dm_key = format!("{}-datamodel-v2", cache_key_for_dm);
+                let dm_key = format!("{}-datamodel-v3", cache_key_for_dm);
                 if let Err(e) = cache_for_dm.set_bytes(&dm_key, &parquet_data).await {
                     tracing::error!(error = %e, "Failed to cache data model from stream");
                 } else {
@@ -530,8 +563,11 @@ pub struct DataModelStats {
 /// - Body: Binary Parquet data (mesh_parquet + vertex_parquet + index_parquet)
 pub async fn parse_parquet(
     State(state): State<AppState>,
+    Query(options): Query<ParseOptions>,
     mut multipart: Multipart,
 ) -> Result<Response, ApiError> {
+    let filter = options.element_filter().map_err(ApiError::InvalidRequest)?;
+
     // Extract file from multipart
     let data = extract_file(&mut multipart).await?;
 
@@ -543,11 +579,11 @@ pub async fn parse_parquet(
     }
 
     // Generate cache key
-    let cache_key = DiskCache::generate_key(&data);
+    let cache_key = options.cache_key(DiskCache::generate_key(&data));
 
     // Check cache first (before any processing)
-    let parquet_cache_key = format!("{}-parquet-v2", cache_key);
-    let metadata_cache_key = format!("{}-parquet-metadata-v2", cache_key);
+    let parquet_cache_key = format!("{}-parquet-v3", cache_key);
+    let metadata_cache_key = format!("{}-parquet-metadata-v3", cache_key);
 
     if let (Some(cached_parquet), Some(cached_metadata_json)) = (
         state.cache.get_bytes(&parquet_cache_key).await?,
@@ -584,12 +620,24 @@ pub async fn parse_parquet(
     // rayon::join works correctly here because rayon has its own thread pool
     // that's independent of tokio's blocking thread pool
     let serialize_start = tokio::time::Instant::now();
+    let selection = options.representation_selection();
+    let split_layers = options.split_layers;
+    let computed_quantities = options.computed_quantities;
+    let check_self_intersections = options.check_self_intersections;
     let ((geometry_result, geometry_parquet), (data_model_stats, data_model_parquet)) =
         tokio::task::spawn_blocking(move || {
             // First: extract geometry and data model in parallel
             let (geometry_result, data_model) = rayon::join(
-                || process_geometry(&content),
-                || extract_data_model(&content),
+                || {
+                    process_geometry(
+                        &content,
+                        &selection,
+                        &filter,
+                        split_layers,
+                        check_self_intersections,
+                    )
+                },
+                || extract_data_model(&content, computed_quantities),
             );
 
             // Capture stats before moving data_model
@@ -625,7 +673,7 @@ pub async fn parse_parquet(
     );
 
     // Cache data model IMMEDIATELY (not in background) so it's ready when client polls
-    let data_model_cache_key = format!("{}-datamodel-v2", cache_key);
+    let data_model_cache_key = format!("{}-datamodel-v3", cache_key);
     if let Err(e) = state.cache.set_bytes(&data_model_cache_key, &data_model_parquet).await {
         tracing::error!(error = %e, "Failed to cache data model");
     } else {
@@ -655,8 +703,8 @@ pub async fn parse_parquet(
     let metadata_json = serde_json::to_string(&metadata_header)?;
 
     // Cache the results for future requests
-    let parquet_cache_key = format!("{}-parquet-v2", cache_key_clone);
-    let metadata_cache_key = format!("{}-parquet-metadata-v2", cache_key_clone);
+    let parquet_cache_key = format!("{}-parquet-v3", cache_key_clone);
+    let metadata_cache_key = format!("{}-parquet-metadata-v3", cache_key_clone);
     let combined_parquet_clone = combined_parquet.clone();
     let metadata_json_clone = metadata_json.clone();
     let cache = state.cache.clone();
@@ -713,8 +761,11 @@ pub struct OptimizedParquetMetadataHeader {
 /// Typical compression: 3-5x smaller than basic Parquet, 50-75x smaller than JSON.
 pub async fn parse_parquet_optimized(
     State(state): State<AppState>,
+    Query(options): Query<ParseOptions>,
     mut multipart: Multipart,
 ) -> Result<Response, ApiError> {
+    let filter = options.element_filter().map_err(ApiError::InvalidRequest)?;
+
     // Extract file from multipart
     let data = extract_file(&mut multipart).await?;
 
@@ -726,7 +777,7 @@ pub async fn parse_parquet_optimized(
     }
 
     // Generate cache key
-    let cache_key = DiskCache::generate_key(&data);
+    let cache_key = options.cache_key(DiskCache::generate_key(&data));
 
     tracing::info!(
         cache_key = %cache_key,
@@ -738,7 +789,13 @@ pub async fn parse_parquet_optimized(
     let content = String::from_utf8(data)?;
 
     // Process on blocking thread pool (CPU-intensive)
-    let result = tokio::task::spawn_blocking(move || process_geometry(&content)).await?;
+    let selection = options.representation_selection();
+    let split_layers = options.split_layers;
+    let check_self_intersections = options.check_self_intersections;
+    let result = tokio::task::spawn_blocking(move || {
+        process_geometry(&content, &selection, &filter, split_layers, check_self_intersections)
+    })
+    .await?;
 
     // Serialize to optimized Parquet (with deduplication, quantization, etc.)
     // Don't include normals by default - client can compute them
@@ -790,7 +847,7 @@ pub async fn get_data_model(
     State(state): State<AppState>,
     axum::extract::Path(cache_key): axum::extract::Path<String>,
 ) -> Result<Response, ApiError> {
-    let data_model_cache_key = format!("{}-datamodel-v2", cache_key);
+    let data_model_cache_key = format!("{}-datamodel-v3", cache_key);
     
     match state.cache.get_bytes(&data_model_cache_key).await? {
         Some(data_model_parquet) => {
@@ -836,7 +893,7 @@ pub async fn check_cache(
     State(state): State<AppState>,
     axum::extract::Path(hash): axum::extract::Path<String>,
 ) -> Result<Response, ApiError> {
-    let parquet_cache_key = format!("{}-parquet-v2", hash);
+    let parquet_cache_key = format!("{}-parquet-v3", hash);
     
     match state.cache.get_bytes(&parquet_cache_key).await? {
         Some(_) => {
@@ -870,8 +927,8 @@ pub async fn get_cached_geometry(
     State(state): State<AppState>,
     axum::extract::Path(hash): axum::extract::Path<String>,
 ) -> Result<Response, ApiError> {
-    let parquet_cache_key = format!("{}-parquet-v2", hash);
-    let metadata_cache_key = format!("{}-parquet-metadata-v2", hash);
+    let parquet_cache_key = format!("{}-parquet-v3", hash);
+    let metadata_cache_key = format!("{}-parquet-metadata-v3", hash);
     
     match (
         state.cache.get_bytes(&parquet_cache_key).await?,
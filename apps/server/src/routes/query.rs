@@ -0,0 +1,113 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Element query endpoints.
+
+use crate::error::ApiError;
+use crate::routes::parse::extract_file;
+use crate::services::{DiskCache, QueryModel};
+use crate::types::{QueryOptions, QueryResponse};
+use crate::AppState;
+use axum::{
+    extract::{Multipart, Path, Query, State},
+    Json,
+};
+use std::sync::Arc;
+
+/// Longest accepted query text in bytes.
+const MAX_QUERY_LENGTH: usize = 4096;
+
+/// POST /api/v1/query - Run an element query against an uploaded model.
+///
+/// The model is indexed once and kept in memory, so later queries can use
+/// `GET /api/v1/query/:cache_key` without uploading it again.
+pub async fn query_model(
+    State(state): State<AppState>,
+    Query(options): Query<QueryOptions>,
+    mut multipart: Multipart,
+) -> Result<Json<QueryResponse>, ApiError> {
+    let query = parse_query(&options)?;
+
+    // Extract file from multipart
+    let data = extract_file(&mut multipart).await?;
+
+    // Check file size
+    if data.len() > state.config.max_file_size_mb * 1024 * 1024 {
+        return Err(ApiError::FileTooLarge {
+            max_mb: state.config.max_file_size_mb,
+        });
+    }
+
+    let cache_key = DiskCache::generate_key(&data);
+    let model = match state.query_models.get(&cache_key) {
+        Some(model) => {
+            tracing::info!(cache_key = %cache_key, "Query index HIT");
+            model
+        }
+        None => {
+            tracing::info!(cache_key = %cache_key, size = data.len(), "Query index MISS - indexing");
+            let content = String::from_utf8(data)?;
+            let model =
+                Arc::new(tokio::task::spawn_blocking(move || QueryModel::build(content)).await?);
+            state.query_models.insert(cache_key.clone(), model.clone());
+            model
+        }
+    };
+
+    run_query(model, cache_key, options.q, query).await
+}
+
+/// GET /api/v1/query/:cache_key - Run an element query against an indexed model.
+pub async fn query_cached(
+    State(state): State<AppState>,
+    Path(cache_key): Path<String>,
+    Query(options): Query<QueryOptions>,
+) -> Result<Json<QueryResponse>, ApiError> {
+    let query = parse_query(&options)?;
+    let model = state.query_models.get(&cache_key).ok_or_else(|| {
+        ApiError::NotFound(format!(
+            "No query index for {}, upload the model again",
+            cache_key
+        ))
+    })?;
+
+    run_query(model, cache_key, options.q, query).await
+}
+
+fn parse_query(options: &QueryOptions) -> Result<ifc_lite_core::Query, ApiError> {
+    if options.q.len() > MAX_QUERY_LENGTH {
+        return Err(ApiError::InvalidRequest(format!(
+            "Query longer than {} bytes",
+            MAX_QUERY_LENGTH
+        )));
+    }
+    ifc_lite_core::Query::parse(&options.q).map_err(|e| ApiError::InvalidRequest(e.to_string()))
+}
+
+async fn run_query(
+    model: Arc<QueryModel>,
+    cache_key: String,
+    text: String,
+    query: ifc_lite_core::Query,
+) -> Result<Json<QueryResponse>, ApiError> {
+    let start = std::time::Instant::now();
+    let (ids, product_count) =
+        tokio::task::spawn_blocking(move || (model.execute(&query), model.product_count())).await?;
+    let ids = ids.map_err(|e| ApiError::InvalidRequest(e.to_string()))?;
+
+    tracing::info!(
+        query = %text,
+        count = ids.len(),
+        query_time_ms = start.elapsed().as_millis() as u64,
+        "Query complete"
+    );
+
+    Ok(Json(QueryResponse {
+        cache_key,
+        query: text,
+        count: ids.len(),
+        product_count,
+        ids,
+    }))
+}
@@ -4,9 +4,14 @@
 
 //! Data model extraction service - extracts properties, relationships, and spatial hierarchy.
 
-use ifc_lite_core::{build_entity_index, DecodedEntity, EntityDecoder, EntityScanner, extract_length_unit_scale};
+use ifc_lite_core::{
+    build_entity_index, extract_groups, quantity_unit_type, unit_conversion, unit_label, AttributeValue,
+    DecodedEntity, DistributionGraph, EntityDecoder, EntityScanner, IfcType, MaterialResolver, PropertyResolver, PropertyValue,
+    ReferenceResolver, UnitContext,
+};
+use ifc_lite_geometry::{ComputedQuantities, GeometryRouter, QuantitySource};
 use rayon::prelude::*;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -21,6 +26,20 @@ pub struct DataModel {
     pub quantity_sets: Vec<QuantitySet>,
     /// Relationships (type, relating, related[]).
     pub relationships: Vec<Relationship>,
+    /// Effective properties per element, including those inherited from type objects.
+    pub element_properties: Vec<ElementProperty>,
+    /// Materials per element, one row per layer, profile, constituent or material.
+    pub materials: Vec<ElementMaterial>,
+    /// Classification codes per element (Uniclass, OmniClass, NL-SfB, ...).
+    pub classifications: Vec<ElementClassification>,
+    /// Documents per element.
+    pub documents: Vec<ElementDocument>,
+    /// Groups and systems with their members.
+    pub groups: Vec<SystemGroup>,
+    /// Distribution ports with their owning elements.
+    pub ports: Vec<DistributionPort>,
+    /// Port connections, with flow direction between elements where known.
+    pub port_connections: Vec<PortConnection>,
     /// Spatial hierarchy data with nodes and lookup maps.
     pub spatial_hierarchy: SpatialHierarchyData,
 }
@@ -56,12 +75,133 @@ pub struct PropertySet {
 pub struct Property {
     /// Property name.
     pub property_name: String,
-    /// Property value (JSON-encoded).
+    /// JSON form of the whole value (see `property_kind` for the shape). The typed
+    /// fields below carry the same data for consumers that should not re-parse it.
     pub property_value: String,
-    /// Property value type.
+    /// Value kind (`PropertyValue::kind`, e.g. "measure" or "label"), or "mixed",
+    /// "reference", "object" or "null" when no single kind applies.
     pub property_type: String,
+    /// IfcProperty subtype ("single", "enumerated", "bounded", "list", "table",
+    /// "reference" or "complex").
+    #[serde(default = "default_property_kind")]
+    pub property_kind: String,
+    /// Nominal value (single) or set point (bounded).
+    #[serde(default)]
+    pub value: Option<PropertyValue>,
+    /// Enumerated or listed values, or the defined values of a table.
+    #[serde(default)]
+    pub values: Vec<PropertyValue>,
+    /// Lower bound (bounded).
+    #[serde(default)]
+    pub lower_bound: Option<PropertyValue>,
+    /// Upper bound (bounded).
+    #[serde(default)]
+    pub upper_bound: Option<PropertyValue>,
+    /// IFC measure type of the value (e.g. "IFCLENGTHMEASURE"); the usage name
+    /// for complex properties.
+    #[serde(default)]
+    pub measure_type: Option<String>,
+    /// Unit label: the property's own Unit, else the project unit of its measure type.
+    #[serde(default)]
+    pub unit: Option<String>,
 }
 
+impl Property {
+    fn new(property_name: String, kind: &str) -> Self {
+        Self {
+            property_name,
+            property_value: String::new(),
+            property_type: String::new(),
+            property_kind: kind.to_string(),
+            value: None,
+            values: Vec::new(),
+            lower_bound: None,
+            upper_bound: None,
+            measure_type: None,
+            unit: None,
+        }
+    }
+
+    /// Fill the JSON form, kind, measure type and unit left unset from the typed values.
+    fn finish(mut self) -> Self {
+        let typed: Vec<&PropertyValue> = self
+            .value
+            .iter()
+            .chain(&self.lower_bound)
+            .chain(&self.upper_bound)
+            .chain(&self.values)
+            .collect();
+
+        if self.property_type.is_empty() {
+            let mut kinds = typed.iter().map(|v| v.kind());
+            self.property_type = match kinds.next() {
+                Some(first) if kinds.all(|k| k == first) => first,
+                Some(_) => "mixed",
+                None => "null",
+            }
+            .to_string();
+        }
+        if self.measure_type.is_none() {
+            self.measure_type = typed.iter().find_map(|v| v.measure_type()).map(str::to_string);
+        }
+        if self.unit.is_none() {
+            self.unit = typed.iter().find_map(|v| v.unit()).map(str::to_string);
+        }
+
+        if self.property_value.is_empty() {
+            let json = match self.property_kind.as_str() {
+                PROPERTY_KIND_BOUNDED => {
+                    let mut object = serde_json::Map::new();
+                    let bounds = [
+                        ("lower", &self.lower_bound),
+                        ("upper", &self.upper_bound),
+                        ("set_point", &self.value),
+                    ];
+                    for (key, value) in bounds {
+                        if let Some(value) = value {
+                            object.insert(key.to_string(), value_to_json(value));
+                        }
+                    }
+                    serde_json::Value::Object(object)
+                }
+                PROPERTY_KIND_ENUMERATED | PROPERTY_KIND_LIST => values_to_json(&self.values),
+                _ => self.value.as_ref().map_or(serde_json::Value::Null, value_to_json),
+            };
+            self.property_value = json.to_string();
+        }
+        self
+    }
+
+    /// JSON object describing this property, used to nest it inside a complex property.
+    fn to_nested_json(&self) -> serde_json::Value {
+        let value = serde_json::from_str(&self.property_value).unwrap_or(serde_json::Value::Null);
+        let mut object = serde_json::Map::new();
+        object.insert("kind".to_string(), self.property_kind.as_str().into());
+        object.insert("type".to_string(), self.property_type.as_str().into());
+        object.insert("value".to_string(), value);
+        if let Some(measure_type) = &self.measure_type {
+            object.insert("measure_type".to_string(), measure_type.as_str().into());
+        }
+        if let Some(unit) = &self.unit {
+            object.insert("unit".to_string(), unit.as_str().into());
+        }
+        serde_json::Value::Object(object)
+    }
+}
+
+fn default_property_kind() -> String {
+    PROPERTY_KIND_SINGLE.to_string()
+}
+
+/// Property kinds, one per supported IfcProperty subtype.
+pub const PROPERTY_KIND_SINGLE: &str = "single";
+pub const PROPERTY_KIND_ENUMERATED: &str = "enumerated";
+pub const PROPERTY_KIND_BOUNDED: &str = "bounded";
+pub const PROPERTY_KIND_LIST: &str = "list";
+pub const PROPERTY_KIND_TABLE: &str = "table";
+pub const PROPERTY_KIND_REFERENCE: &str = "reference";
+pub const PROPERTY_KIND_COMPLEX: &str = "complex";
+
 /// Quantity set (IfcElementQuantity).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuantitySet {
@@ -73,6 +213,208 @@ pub struct QuantitySet {
     pub method_of_measurement: Option<String>,
     /// Quantities in this set.
     pub quantities: Vec<Quantity>,
+    /// Element the set was computed for (computed sets only; authored sets
+    /// are linked through IfcRelDefinesByProperties).
+    #[serde(default)]
+    pub element_id: Option<u32>,
+}
+
+/// Property of an element after type-object inheritance.
+///
+/// The value is the property named `property_name` in property set `pset_id`.
+/// Occurrence properties override type properties with the same set and name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementProperty {
+    /// Element (occurrence) entity ID.
+    pub element_id: u32,
+    /// Property set holding the effective value.
+    pub pset_id: u32,
+    /// Property name.
+    pub property_name: String,
+    /// "occurrence" or "type".
+    pub source: String,
+}
+
+/// One material of an element, resolved from IfcRelAssociatesMaterial.
+///
+/// An element has one row per part of each associated material definition
+/// (every layer of a layer set, every constituent, ...). Lengths are in metres.
+/// Quantity take-off by material multiplies an element quantity by `fraction`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementMaterial {
+    /// Element (occurrence) entity ID.
+    pub element_id: u32,
+    /// "occurrence" or "type".
+    pub source: String,
+    /// Associated IfcMaterialSelect entity ID.
+    pub association_id: u32,
+    /// Kind of association ("material", "layer_set_usage", "constituent_set", ...).
+    pub association_type: String,
+    /// Layer, profile or constituent set (the set behind a usage).
+    pub set_id: Option<u32>,
+    /// Set name.
+    pub set_name: Option<String>,
+    /// LayerSetDirection of a layer set usage (AXIS1, AXIS2, AXIS3).
+    pub layer_set_direction: Option<String>,
+    /// DirectionSense of a layer set usage (POSITIVE, NEGATIVE).
+    pub direction_sense: Option<String>,
+    /// OffsetFromReferenceLine of a layer set usage.
+    pub offset_from_reference_line: Option<f64>,
+    /// CardinalPoint of a profile set usage.
+    pub cardinal_point: Option<i64>,
+    /// Position of the part within the set.
+    pub part_index: u32,
+    /// "material", "layer", "profile" or "constituent".
+    pub part_kind: String,
+    /// Layer, profile or constituent entity ID.
+    pub part_id: Option<u32>,
+    /// Layer, profile or constituent name.
+    pub part_name: Option<String>,
+    /// IfcMaterial entity ID (absent for air gaps).
+    pub material_id: Option<u32>,
+    /// Material name.
+    pub material_name: Option<String>,
+    /// Material (or part) category.
+    pub material_category: Option<String>,
+    /// Layer thickness.
+    pub thickness: Option<f64>,
+    /// Signed distance of the layer's first face from the reference line.
+    pub layer_offset: Option<f64>,
+    /// Share of the element volume, if known.
+    pub fraction: Option<f64>,
+    /// Profile definition of a material profile.
+    pub profile_id: Option<u32>,
+    /// Whether a layer is ventilated.
+    pub is_ventilated: Option<bool>,
+    /// Layer or profile priority.
+    pub priority: Option<i64>,
+}
+
+/// Classification code of an element, resolved from IfcRelAssociatesClassification.
+///
+/// Own codes replace the type's code from the same classification system.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementClassification {
+    /// Element (occurrence) entity ID.
+    pub element_id: u32,
+    /// "occurrence" or "type".
+    pub source: String,
+    /// IfcClassificationReference (or IfcClassification) entity ID.
+    pub reference_id: u32,
+    /// Code, e.g. "Ss_25_10_30".
+    pub identification: Option<String>,
+    /// Title of the code.
+    pub name: Option<String>,
+    /// URI of the code.
+    pub location: Option<String>,
+    /// Description of the code.
+    pub description: Option<String>,
+    /// Codes of the parent references, from the top of the tree down.
+    pub parents: Vec<String>,
+    /// IfcClassification entity ID.
+    pub classification_id: Option<u32>,
+    /// Classification system name, e.g. "Uniclass 2015".
+    pub classification_name: Option<String>,
+    /// Publisher of the classification.
+    pub classification_source: Option<String>,
+    /// Edition of the classification.
+    pub edition: Option<String>,
+    /// Edition date.
+    pub edition_date: Option<String>,
+}
+
+/// Document of an element, resolved from IfcRelAssociatesDocument.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementDocument {
+    /// Element (occurrence) entity ID.
+    pub element_id: u32,
+    /// "occurrence" or "type".
+    pub source: String,
+    /// IfcDocumentReference (or IfcDocumentInformation) entity ID.
+    pub reference_id: u32,
+    /// Reference identifier.
+    pub identification: Option<String>,
+    /// Reference name.
+    pub name: Option<String>,
+    /// URI or path of the referenced document.
+    pub location: Option<String>,
+    /// Reference description.
+    pub description: Option<String>,
+    /// IfcDocumentInformation entity ID.
+    pub document_id: Option<u32>,
+    /// Document identifier.
+    pub document_identification: Option<String>,
+    /// Document title.
+    pub document_name: Option<String>,
+    /// Document description.
+    pub document_description: Option<String>,
+    /// Document location.
+    pub document_location: Option<String>,
+    /// Purpose of the document.
+    pub purpose: Option<String>,
+    /// Intended use of the document.
+    pub intended_use: Option<String>,
+    /// Document revision.
+    pub revision: Option<String>,
+    /// Document status (DRAFT, FINALDRAFT, FINAL, REVISION, NOTDEFINED).
+    pub status: Option<String>,
+}
+
+/// Group or system (IfcGroup subtype) with its members, from IfcRelAssignsToGroup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemGroup {
+    /// Group entity ID.
+    pub group_id: u32,
+    /// IFC type name (e.g., "IFCDISTRIBUTIONSYSTEM", "IFCZONE").
+    pub type_name: String,
+    /// Group name.
+    pub name: Option<String>,
+    /// Long name of a distribution system.
+    pub long_name: Option<String>,
+    /// Predefined type of a distribution system or circuit (e.g., "HEATING").
+    pub predefined_type: Option<String>,
+    /// Assigned objects (elements, ports, spaces or nested groups).
+    pub member_ids: Vec<u32>,
+    /// Buildings or other spatial structure serviced by the system (IfcRelServicesBuildings).
+    pub serviced_ids: Vec<u32>,
+}
+
+/// Distribution port of an element.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionPort {
+    /// Port entity ID.
+    pub port_id: u32,
+    /// Element owning the port (IfcRelNests, or IfcRelConnectsPortToElement in IFC2X3).
+    pub element_id: Option<u32>,
+    /// Port name.
+    pub name: Option<String>,
+    /// "SOURCE", "SINK", "SOURCEANDSINK" or "NOTDEFINED".
+    pub flow_direction: String,
+    /// Port type (e.g., "DUCT", "PIPE", "CABLE").
+    pub predefined_type: Option<String>,
+    /// Distribution system type of the port (e.g., "HEATING").
+    pub system_type: Option<String>,
+}
+
+/// Connection between two ports, from IfcRelConnectsPorts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortConnection {
+    /// IfcRelConnectsPorts entity ID.
+    pub connection_id: u32,
+    /// Relating port.
+    pub relating_port_id: u32,
+    /// Related port.
+    pub related_port_id: u32,
+    /// Element realizing the connection, if any.
+    pub realizing_element_id: Option<u32>,
+    /// Element owning the relating port.
+    pub relating_element_id: Option<u32>,
+    /// Element owning the related port.
+    pub related_element_id: Option<u32>,
+    /// Element the flow comes from, if the port directions tell.
+    pub upstream_element_id: Option<u32>,
+    /// Element the flow goes to, if the port directions tell.
+    pub downstream_element_id: Option<u32>,
 }
 
 /// Single quantity value.
@@ -80,12 +422,26 @@ pub struct QuantitySet {
 pub struct Quantity {
     /// Quantity name.
     pub quantity_name: String,
-    /// Quantity numeric value.
+    /// Quantity numeric value in SI units (metre, square metre, cubic metre,
+    /// kilogram, second); counts as authored.
     pub quantity_value: f64,
     /// Quantity type (length, area, volume, count, weight, time).
     pub quantity_type: String,
+    /// Where the value came from ("authored", "profile" or "mesh").
+    #[serde(default = "default_quantity_source")]
+    pub source: String,
+}
+
+fn default_quantity_source() -> String {
+    QUANTITY_SOURCE_AUTHORED.to_string()
 }
 
+/// Source flag for quantities read from IfcElementQuantity.
+pub const QUANTITY_SOURCE_AUTHORED: &str = "authored";
+
+/// Name of the quantity set holding geometry-derived quantities.
+pub const COMPUTED_QSET_NAME: &str = "Qto_Computed";
+
 /// Relationship between entities.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Relationship {
@@ -118,6 +474,9 @@ pub struct SpatialNode {
     pub children_ids: Vec<u32>,
     /// Contained elements (non-spatial entities like walls, doors, etc.).
     pub element_ids: Vec<u32>,
+    /// Elements referenced in this structure without being contained in it
+    /// (IfcRelReferencedInSpatialStructure), e.g. a column spanning several storeys.
+    pub referenced_element_ids: Vec<u32>,
 }
 
 /// Spatial hierarchy data with lookup maps.
@@ -135,6 +494,16 @@ pub struct SpatialHierarchyData {
     pub element_to_site: Vec<(u32, u32)>,
     /// Element to space mapping (element_id -> space_id).
     pub element_to_space: Vec<(u32, u32)>,
+    /// Element to facility mapping (element_id -> facility_id), for any IfcFacility
+    /// subtype including IfcBuilding, IfcBridge, IfcRoad, IfcRailway and IfcMarineFacility.
+    pub element_to_facility: Vec<(u32, u32)>,
+    /// Element to facility part mapping (element_id -> facility_part_id).
+    pub element_to_facility_part: Vec<(u32, u32)>,
+    /// Zone membership (element_id -> zone_id), from IfcZone groups and from
+    /// containment in or reference to an IfcSpatialZone. An element may be in several zones.
+    pub element_to_zone: Vec<(u32, u32)>,
+    /// Spatial references (element_id -> spatial_id) from IfcRelReferencedInSpatialStructure.
+    pub element_references: Vec<(u32, u32)>,
 }
 
 /// Job for processing an entity during data extraction.
@@ -146,7 +515,10 @@ struct EntityJob {
 }
 
 /// Extract complete data model from IFC content.
-pub fn extract_data_model(content: &str) -> DataModel {
+///
+/// With `compute_quantities`, every element with a body is meshed to add a
+/// `Qto_Computed` quantity set; authored data alone needs no geometry pass.
+pub fn extract_data_model(content: &str, compute_quantities: bool) -> DataModel {
     let extract_start = std::time::Instant::now();
     tracing::info!(content_size = content.len(), "Starting data model extraction");
 
@@ -209,32 +581,54 @@ pub fn extract_data_model(content: &str) -> DataModel {
     }).count();
     tracing::debug!(pset_count = pset_count, rel_count = rel_count, "Entity type counts before extraction");
 
+    // Project units for every measure type: label property values, convert
+    // quantities to SI and scale geometry (e.g., 0.001 for millimeters)
+    let mut unit_decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+    let project_id_for_units = all_entities
+        .iter()
+        .find(|j| j.type_name.eq_ignore_ascii_case("IFCPROJECT"))
+        .map(|j| j.id)
+        .unwrap_or(0);
+    let units = if project_id_for_units > 0 {
+        UnitContext::from_project(&mut unit_decoder, project_id_for_units).unwrap_or_default()
+    } else {
+        UnitContext::default()
+    };
+    let length_unit_scale = units.length_scale();
+    tracing::debug!(length_unit_scale = length_unit_scale, "Extracted length unit scale");
+
     // Parallel extraction using rayon::join
     let content_arc = Arc::new(content.to_string());
-    let (entities, ((property_sets, quantity_sets), relationships)) = rayon::join(
+    let (entities, (((property_sets, mut quantity_sets), mut relationships), computed)) = rayon::join(
         || extract_entity_metadata(&all_entities, &content_arc, &entity_index),
         || rayon::join(
             || rayon::join(
-                || extract_properties(&all_entities, &content_arc, &entity_index),
-                || extract_quantities(&all_entities, &content_arc, &entity_index),
+                || rayon::join(
+                    || extract_properties(&all_entities, &content_arc, &entity_index, &units),
+                    || extract_quantities(&all_entities, &content_arc, &entity_index, &units),
+                ),
+                || extract_relationships(&all_entities, &content_arc, &entity_index),
             ),
-            || extract_relationships(&all_entities, &content_arc, &entity_index),
+            || if compute_quantities {
+                extract_computed_quantities(&all_entities, &content_arc, &entity_index, &units, max_id)
+            } else {
+                Default::default()
+            },
         ),
     );
-
-    // Extract length unit scale (e.g., 0.001 for millimeters)
-    let mut unit_decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
-    let project_id_for_units = entities
-        .iter()
-        .find(|e| e.type_name.to_uppercase() == "IFCPROJECT")
-        .map(|e| e.entity_id)
-        .unwrap_or(0);
-    let length_unit_scale = if project_id_for_units > 0 {
-        extract_length_unit_scale(&mut unit_decoder, project_id_for_units).unwrap_or(1.0)
-    } else {
-        1.0
-    };
-    tracing::debug!(length_unit_scale = length_unit_scale, "Extracted length unit scale");
+    // Computed sets sit next to authored ones and are linked the same way
+    let (computed_sets, computed_rels) = computed;
+    quantity_sets.extend(computed_sets);
+    relationships.extend(computed_rels);
+
+    // Resolve type-object inheritance (depends on property sets)
+    let element_properties = resolve_element_properties(&content_arc, &entity_index, &property_sets);
+    let materials = resolve_element_materials(&content_arc, &entity_index, length_unit_scale);
+    let (classifications, documents) = resolve_element_references(&content_arc, &entity_index);
+    let (groups, (ports, port_connections)) = rayon::join(
+        || extract_system_groups(&content_arc, &entity_index),
+        || extract_distribution_graph(&content_arc, &entity_index),
+    );
 
     // Build spatial hierarchy (depends on relationships and entities)
     let spatial_hierarchy = build_spatial_hierarchy(&relationships, &entities, content, &entity_index, length_unit_scale);
@@ -245,6 +639,13 @@ pub fn extract_data_model(content: &str) -> DataModel {
         property_sets = property_sets.len(),
         quantity_sets = quantity_sets.len(),
         relationships = relationships.len(),
+        element_properties = element_properties.len(),
+        materials = materials.len(),
+        classifications = classifications.len(),
+        documents = documents.len(),
+        groups = groups.len(),
+        ports = ports.len(),
+        port_connections = port_connections.len(),
         spatial_nodes = spatial_hierarchy.nodes.len(),
         extract_time_ms = extract_time.as_millis(),
         "Data model extraction complete"
@@ -255,6 +656,13 @@ pub fn extract_data_model(content: &str) -> DataModel {
         property_sets,
         quantity_sets,
         relationships,
+        element_properties,
+        materials,
+        classifications,
+        documents,
+        groups,
+        ports,
+        port_connections,
         spatial_hierarchy,
     }
 }
@@ -290,6 +698,7 @@ fn extract_properties(
     jobs: &[EntityJob],
     content: &Arc<String>,
     entity_index: &Arc<ifc_lite_core::EntityIndex>,
+    units: &UnitContext,
 ) -> Vec<PropertySet> {
     // First, collect all PropertySet entities
     // PERF: Use eq_ignore_ascii_case to avoid string allocation per comparison
@@ -316,7 +725,7 @@ fn extract_properties(
             for prop_ref in has_properties.iter() {
                 if let Some(prop_id) = prop_ref.as_entity_ref() {
                     if let Ok(prop_entity) = local_decoder.decode_by_id(prop_id) {
-                        if let Some(prop) = extract_property(&prop_entity, &mut local_decoder) {
+                        if let Some(prop) = extract_property(&prop_entity, &mut local_decoder, units) {
                             properties.push(prop);
                         }
                     }
@@ -337,45 +746,445 @@ fn extract_properties(
 }
 
 /// Extract a single property from IfcProperty entity.
+///
+/// Handles every IfcSimpleProperty subtype plus IfcComplexProperty. Values are
+/// typed, with measure units resolved against the property's own Unit or the
+/// project's IfcUnitAssignment.
 fn extract_property(
     entity: &DecodedEntity,
-    _decoder: &mut EntityDecoder,
+    decoder: &mut EntityDecoder,
+    units: &UnitContext,
+) -> Option<Property> {
+    extract_property_depth(entity, decoder, units, 0)
+}
+
+/// Nesting limit for IfcComplexProperty (guards against cyclic references).
+const MAX_COMPLEX_PROPERTY_DEPTH: usize = 8;
+
+fn extract_property_depth(
+    entity: &DecodedEntity,
+    decoder: &mut EntityDecoder,
+    units: &UnitContext,
+    depth: usize,
 ) -> Option<Property> {
     // PERF: Use eq_ignore_ascii_case to avoid string allocation per comparison
     let ifc_type = entity.ifc_type.as_str();
+    let property_name = entity.get_string(0)?.to_string();
+
+    let property = if ifc_type.eq_ignore_ascii_case("IFCPROPERTYSINGLEVALUE") {
+        // IfcPropertySingleValue: [0]=Name, [1]=Description, [2]=NominalValue, [3]=Unit
+        let mut property = Property::new(property_name, PROPERTY_KIND_SINGLE);
+        property.unit = resolve_unit_attr(entity.get(3), decoder);
+        property.value = decode_value(entity.get(2), property.unit.as_deref(), units);
+        property
+    } else if ifc_type.eq_ignore_ascii_case("IFCPROPERTYENUMERATEDVALUE") {
+        // IfcPropertyEnumeratedValue: [0]=Name, [1]=Description, [2]=EnumerationValues,
+        // [3]=EnumerationReference (IfcPropertyEnumeration: [0]=Name, [1]=EnumerationValues, [2]=Unit)
+        let mut property = Property::new(property_name, PROPERTY_KIND_ENUMERATED);
+        property.unit = entity
+            .get(3)
+            .and_then(|attr| decoder.resolve_ref(attr).ok().flatten())
+            .and_then(|enumeration| resolve_unit_attr(enumeration.get(2), decoder));
+        property.values = decode_value_list(entity.get(2), property.unit.as_deref(), units);
+        property
+    } else if ifc_type.eq_ignore_ascii_case("IFCPROPERTYLISTVALUE") {
+        // IfcPropertyListValue: [0]=Name, [1]=Description, [2]=ListValues, [3]=Unit
+        let mut property = Property::new(property_name, PROPERTY_KIND_LIST);
+        property.unit = resolve_unit_attr(entity.get(3), decoder);
+        property.values = decode_value_list(entity.get(2), property.unit.as_deref(), units);
+        property
+    } else if ifc_type.eq_ignore_ascii_case("IFCPROPERTYBOUNDEDVALUE") {
+        // IfcPropertyBoundedValue: [0]=Name, [1]=Description, [2]=UpperBoundValue,
+        // [3]=LowerBoundValue, [4]=Unit, [5]=SetPointValue (IFC4)
+        let mut property = Property::new(property_name, PROPERTY_KIND_BOUNDED);
+        property.unit = resolve_unit_attr(entity.get(4), decoder);
+        let unit = property.unit.as_deref();
+        property.upper_bound = decode_value(entity.get(2), unit, units);
+        property.lower_bound = decode_value(entity.get(3), unit, units);
+        property.value = decode_value(entity.get(5), unit, units);
+        property
+    } else if ifc_type.eq_ignore_ascii_case("IFCPROPERTYTABLEVALUE") {
+        // IfcPropertyTableValue: [0]=Name, [1]=Description, [2]=DefiningValues,
+        // [3]=DefinedValues, [4]=Expression, [5]=DefiningUnit, [6]=DefinedUnit,
+        // [7]=CurveInterpolation (IFC4)
+        let mut property = Property::new(property_name, PROPERTY_KIND_TABLE);
+        let defining_unit = resolve_unit_attr(entity.get(5), decoder);
+        let defining = decode_value_list(entity.get(2), defining_unit.as_deref(), units);
+        property.unit = resolve_unit_attr(entity.get(6), decoder);
+        property.values = decode_value_list(entity.get(3), property.unit.as_deref(), units);
+
+        let mut object = serde_json::Map::new();
+        object.insert("defining".to_string(), values_to_json(&defining));
+        object.insert("defined".to_string(), values_to_json(&property.values));
+        if let Some(expression) = entity.get_string(4) {
+            object.insert("expression".to_string(), expression.into());
+        }
+        if let Some(interpolation) = entity.get(7).and_then(|v| v.as_enum()) {
+            object.insert("interpolation".to_string(), interpolation.into());
+        }
+        if let Some(unit) = defining.iter().find_map(|v| v.unit()) {
+            object.insert("defining_unit".to_string(), unit.into());
+        }
+        property.property_value = serde_json::Value::Object(object).to_string();
+        property
+    } else if ifc_type.eq_ignore_ascii_case("IFCPROPERTYREFERENCEVALUE") {
+        // IfcPropertyReferenceValue: [0]=Name, [1]=Description, [2]=UsageName,
+        // [3]=PropertyReference
+        let mut object = serde_json::Map::new();
+        if let Some(target) = entity.get_ref(3) {
+            object.insert("entity_id".to_string(), target.into());
+            if let Ok(referenced) = decoder.decode_by_id(target) {
+                object.insert("type_name".to_string(), referenced.ifc_type.as_str().into());
+                if let Some(name) = referenced_entity_name(&referenced) {
+                    object.insert("name".to_string(), name.into());
+                }
+            }
+        }
+        if let Some(usage) = entity.get_string(2) {
+            object.insert("usage".to_string(), usage.into());
+        }
 
-    // IfcPropertySingleValue: [0]=Name, [1]=Description, [2]=NominalValue, [3]=Unit
-    if ifc_type.eq_ignore_ascii_case("IFCPROPERTYSINGLEVALUE") {
-        let property_name = entity.get_string(0)?.to_string();
-        let nominal_value = entity.get(2)?;
-
-        // Extract value based on type
-        let (property_value, property_type) = if let Some(s) = nominal_value.as_string() {
-            (format!("\"{}\"", s), "string".to_string())
-        } else if let Some(f) = nominal_value.as_float() {
-            (f.to_string(), "number".to_string())
-        } else if let Some(i) = nominal_value.as_int() {
-            (i.to_string(), "integer".to_string())
-        } else {
-            // Fallback: serialize as string representation
-            (format!("{:?}", nominal_value), "unknown".to_string())
-        };
+        let mut property = Property::new(property_name, PROPERTY_KIND_REFERENCE);
+        property.property_value = serde_json::Value::Object(object).to_string();
+        property.property_type = "reference".to_string();
+        property
+    } else if ifc_type.eq_ignore_ascii_case("IFCCOMPLEXPROPERTY") {
+        // IfcComplexProperty: [0]=Name, [1]=Description, [2]=UsageName, [3]=HasProperties
+        if depth >= MAX_COMPLEX_PROPERTY_DEPTH {
+            return None;
+        }
 
-        Some(Property {
-            property_name,
-            property_value,
-            property_type,
-        })
+        let mut object = serde_json::Map::new();
+        for prop_ref in entity.get_list(3).unwrap_or(&[]) {
+            let Some(prop_id) = prop_ref.as_entity_ref() else {
+                continue;
+            };
+            let Ok(prop_entity) = decoder.decode_by_id(prop_id) else {
+                continue;
+            };
+            if let Some(prop) = extract_property_depth(&prop_entity, decoder, units, depth + 1) {
+                object.insert(prop.property_name.clone(), prop.to_nested_json());
+            }
+        }
+
+        let mut property = Property::new(property_name, PROPERTY_KIND_COMPLEX);
+        property.property_value = serde_json::Value::Object(object).to_string();
+        property.property_type = "object".to_string();
+        property.measure_type = entity.get_string(2).map(|s| s.to_string());
+        property
     } else {
-        None
+        return None;
+    };
+
+    Some(property.finish())
+}
+
+/// Decode an optional IfcValue attribute and resolve its unit.
+fn decode_value(
+    attr: Option<&AttributeValue>,
+    explicit_unit: Option<&str>,
+    units: &UnitContext,
+) -> Option<PropertyValue> {
+    let mut value = PropertyValue::from_attribute(attr?)?;
+    value.resolve_unit(explicit_unit, units);
+    Some(value)
+}
+
+/// Decode a list of IfcValue attributes, skipping unset entries.
+fn decode_value_list(
+    attr: Option<&AttributeValue>,
+    explicit_unit: Option<&str>,
+    units: &UnitContext,
+) -> Vec<PropertyValue> {
+    attr.and_then(|a| a.as_list())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| decode_value(Some(item), explicit_unit, units))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// JSON form of a typed value (numbers stay numbers, logical UNKNOWN is null).
+fn value_to_json(value: &PropertyValue) -> serde_json::Value {
+    match value {
+        PropertyValue::Integer(i) => (*i).into(),
+        PropertyValue::Boolean(b) => (*b).into(),
+        PropertyValue::Logical(b) => b.map_or(serde_json::Value::Null, Into::into),
+        PropertyValue::Real(_) | PropertyValue::Measure { .. } => value
+            .as_f64()
+            .and_then(serde_json::Number::from_f64)
+            .map_or(serde_json::Value::Null, Into::into),
+        _ => value.to_string().into(),
     }
 }
 
+fn values_to_json(values: &[PropertyValue]) -> serde_json::Value {
+    serde_json::Value::Array(values.iter().map(value_to_json).collect())
+}
+
+/// Name of an entity targeted by IfcPropertyReferenceValue, for the common
+/// IfcObjectReferenceSelect members.
+fn referenced_entity_name(entity: &DecodedEntity) -> Option<String> {
+    let ifc_type = entity.ifc_type.as_str();
+    let name_index = if ifc_type.eq_ignore_ascii_case("IFCMATERIAL") {
+        // IfcMaterial: [0]=Name
+        0
+    } else if ifc_type.eq_ignore_ascii_case("IFCORGANIZATION")
+        || ifc_type.eq_ignore_ascii_case("IFCPERSON")
+    {
+        // IfcOrganization: [1]=Name; IfcPerson: [1]=FamilyName
+        1
+    } else if ifc_type.eq_ignore_ascii_case("IFCCLASSIFICATIONREFERENCE")
+        || ifc_type.eq_ignore_ascii_case("IFCDOCUMENTREFERENCE")
+    {
+        // IfcExternalReference: [0]=Location, [1]=Identification, [2]=Name
+        2
+    } else {
+        return None;
+    };
+    entity.get_string(name_index).map(|s| s.to_string())
+}
+
+/// Resolve an optional IfcUnit attribute into a readable label.
+fn resolve_unit_attr(attr: Option<&AttributeValue>, decoder: &mut EntityDecoder) -> Option<String> {
+    let unit = decoder.resolve_ref(attr?).ok()??;
+    unit_label(&unit, decoder)
+}
+
+/// Resolve the effective properties of every element with direct or type property sets.
+///
+/// Only sets present in `property_sets` are referenced; inherited quantity sets
+/// stay in the quantities table.
+fn resolve_element_properties(
+    content: &Arc<String>,
+    entity_index: &Arc<ifc_lite_core::EntityIndex>,
+    property_sets: &[PropertySet],
+) -> Vec<ElementProperty> {
+    let mut decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+    let resolver = PropertyResolver::from_content(content, &mut decoder);
+    let pset_ids: FxHashSet<u32> = property_sets.iter().map(|pset| pset.pset_id).collect();
+    let elements: Vec<u32> = resolver.elements().collect();
+
+    tracing::debug!(count = elements.len(), "Resolving effective properties");
+
+    elements
+        .par_chunks(256)
+        .flat_map_iter(|chunk| {
+            // Shared psets (type sets in particular) stay cached within a chunk
+            let mut local_decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+            let mut rows = Vec::new();
+            for &element_id in chunk {
+                for property in resolver.effective_properties(element_id, &mut local_decoder) {
+                    if pset_ids.contains(&property.set_id) {
+                        rows.push(ElementProperty {
+                            element_id,
+                            pset_id: property.set_id,
+                            property_name: property.name,
+                            source: property.source.as_str().to_string(),
+                        });
+                    }
+                }
+            }
+            rows
+        })
+        .collect()
+}
+
+/// Resolve the materials of every element with a direct or type material association.
+fn resolve_element_materials(
+    content: &Arc<String>,
+    entity_index: &Arc<ifc_lite_core::EntityIndex>,
+    length_unit_scale: f64,
+) -> Vec<ElementMaterial> {
+    let mut decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+    let resolver = MaterialResolver::from_content(content, &mut decoder);
+    let elements: Vec<u32> = resolver.elements().collect();
+
+    tracing::debug!(count = elements.len(), "Resolving element materials");
+
+    let mut rows: Vec<ElementMaterial> = elements
+        .par_chunks(256)
+        .flat_map_iter(|chunk| {
+            // Shared layer sets and materials stay cached within a chunk
+            let mut local_decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+            let mut rows = Vec::new();
+            for &element_id in chunk {
+                for mut association in resolver.materials(element_id, &mut local_decoder) {
+                    association.scale_lengths(length_unit_scale);
+                    rows.extend(association.parts.iter().map(|part| ElementMaterial {
+                        element_id,
+                        source: association.source.as_str().to_string(),
+                        association_id: association.relating_id,
+                        association_type: association.kind.as_str().to_string(),
+                        set_id: association.set_id,
+                        set_name: association.set_name.clone(),
+                        layer_set_direction: association.layer_set_direction.clone(),
+                        direction_sense: association.direction_sense.clone(),
+                        offset_from_reference_line: association.offset_from_reference_line,
+                        cardinal_point: association.cardinal_point,
+                        part_index: part.index as u32,
+                        part_kind: part.kind.as_str().to_string(),
+                        part_id: part.part_id,
+                        part_name: part.name.clone(),
+                        material_id: part.material_id,
+                        material_name: part.material_name.clone(),
+                        material_category: part.category.clone(),
+                        thickness: part.thickness,
+                        layer_offset: part.offset,
+                        fraction: part.fraction,
+                        profile_id: part.profile_id,
+                        is_ventilated: part.is_ventilated,
+                        priority: part.priority,
+                    }));
+                }
+            }
+            rows
+        })
+        .collect();
+
+    rows.sort_by_key(|row| (row.element_id, row.association_id, row.part_index));
+    rows
+}
+
+/// Resolve the classification codes and documents of every element with a
+/// direct or type association.
+fn resolve_element_references(
+    content: &Arc<String>,
+    entity_index: &Arc<ifc_lite_core::EntityIndex>,
+) -> (Vec<ElementClassification>, Vec<ElementDocument>) {
+    let mut decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+    let resolver = ReferenceResolver::from_content(content, &mut decoder);
+    let elements = resolver.elements();
+
+    tracing::debug!(count = elements.len(), "Resolving element classifications and documents");
+
+    let (classifications, documents): (Vec<Vec<ElementClassification>>, Vec<Vec<ElementDocument>>) = elements
+        .par_chunks(256)
+        .map(|chunk| {
+            // Shared references and classifications stay cached within a chunk
+            let mut local_decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+            let mut classifications = Vec::new();
+            let mut documents = Vec::new();
+            for &element_id in chunk {
+                classifications.extend(resolver.classifications(element_id, &mut local_decoder).into_iter().map(|r| {
+                    ElementClassification {
+                        element_id,
+                        source: r.source.as_str().to_string(),
+                        reference_id: r.reference_id,
+                        identification: r.identification,
+                        name: r.name,
+                        location: r.location,
+                        description: r.description,
+                        parents: r.parents,
+                        classification_id: r.classification_id,
+                        classification_name: r.classification_name,
+                        classification_source: r.classification_source,
+                        edition: r.edition,
+                        edition_date: r.edition_date,
+                    }
+                }));
+                documents.extend(resolver.documents(element_id, &mut local_decoder).into_iter().map(|r| {
+                    ElementDocument {
+                        element_id,
+                        source: r.source.as_str().to_string(),
+                        reference_id: r.reference_id,
+                        identification: r.identification,
+                        name: r.name,
+                        location: r.location,
+                        description: r.description,
+                        document_id: r.document_id,
+                        document_identification: r.document_identification,
+                        document_name: r.document_name,
+                        document_description: r.document_description,
+                        document_location: r.document_location,
+                        purpose: r.purpose,
+                        intended_use: r.intended_use,
+                        revision: r.revision,
+                        status: r.status,
+                    }
+                }));
+            }
+            (classifications, documents)
+        })
+        .unzip();
+
+    // Chunks are in element order, so rows stay sorted by element
+    (
+        classifications.into_iter().flatten().collect(),
+        documents.into_iter().flatten().collect(),
+    )
+}
+
+/// Extract groups and systems with their members.
+fn extract_system_groups(
+    content: &Arc<String>,
+    entity_index: &Arc<ifc_lite_core::EntityIndex>,
+) -> Vec<SystemGroup> {
+    let mut decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+    extract_groups(content, &mut decoder)
+        .into_iter()
+        .map(|group| SystemGroup {
+            group_id: group.id,
+            type_name: group.ifc_type.as_str().to_string(),
+            name: group.name,
+            long_name: group.long_name,
+            predefined_type: group.predefined_type,
+            member_ids: group.members,
+            serviced_ids: group.serviced_structures,
+        })
+        .collect()
+}
+
+/// Extract distribution ports and port connections.
+fn extract_distribution_graph(
+    content: &Arc<String>,
+    entity_index: &Arc<ifc_lite_core::EntityIndex>,
+) -> (Vec<DistributionPort>, Vec<PortConnection>) {
+    let mut decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+    let graph = DistributionGraph::from_content(content, &mut decoder);
+
+    let mut ports: Vec<DistributionPort> = graph
+        .ports()
+        .map(|port| DistributionPort {
+            port_id: port.id,
+            element_id: port.element_id,
+            name: port.name.clone(),
+            flow_direction: port.flow_direction.as_str().to_string(),
+            predefined_type: port.predefined_type.clone(),
+            system_type: port.system_type.clone(),
+        })
+        .collect();
+    ports.sort_by_key(|port| port.port_id);
+
+    let connections = graph
+        .connections()
+        .iter()
+        .map(|connection| PortConnection {
+            connection_id: connection.relationship_id,
+            relating_port_id: connection.relating_port,
+            related_port_id: connection.related_port,
+            realizing_element_id: connection.realizing_element,
+            relating_element_id: connection.relating_element,
+            related_element_id: connection.related_element,
+            upstream_element_id: connection.flow.map(|(upstream, _)| upstream),
+            downstream_element_id: connection.flow.map(|(_, downstream)| downstream),
+        })
+        .collect();
+
+    (ports, connections)
+}
+
 /// Extract all quantity sets (IfcElementQuantity) and their quantities.
+///
+/// Values are converted from the quantity's own unit, or else the project
+/// unit, to SI.
 fn extract_quantities(
     jobs: &[EntityJob],
     content: &Arc<String>,
     entity_index: &Arc<ifc_lite_core::EntityIndex>,
+    units: &UnitContext,
 ) -> Vec<QuantitySet> {
     // First, collect all IfcElementQuantity entities
     // PERF: Use eq_ignore_ascii_case to avoid string allocation per comparison
@@ -403,7 +1212,7 @@ fn extract_quantities(
             for quant_ref in has_quantities.iter() {
                 if let Some(quant_id) = quant_ref.as_entity_ref() {
                     if let Ok(quant_entity) = local_decoder.decode_by_id(quant_id) {
-                        if let Some(quant) = extract_quantity_value(&quant_entity) {
+                        if let Some(quant) = extract_quantity_value(&quant_entity, &mut local_decoder, units) {
                             quantities.push(quant);
                         }
                     }
@@ -419,6 +1228,7 @@ fn extract_quantities(
                 qset_name,
                 method_of_measurement,
                 quantities,
+                element_id: None,
             })
         })
         .collect()
@@ -427,7 +1237,11 @@ fn extract_quantities(
 /// Extract a single quantity value from IfcPhysicalQuantity entity.
 /// Supports: IfcQuantityLength, IfcQuantityArea, IfcQuantityVolume,
 ///           IfcQuantityCount, IfcQuantityWeight, IfcQuantityTime
-fn extract_quantity_value(entity: &DecodedEntity) -> Option<Quantity> {
+fn extract_quantity_value(
+    entity: &DecodedEntity,
+    decoder: &mut EntityDecoder,
+    units: &UnitContext,
+) -> Option<Quantity> {
     // PERF: Use eq_ignore_ascii_case to avoid string allocation per comparison
     let ifc_type = entity.ifc_type.as_str();
 
@@ -453,15 +1267,141 @@ fn extract_quantity_value(entity: &DecodedEntity) -> Option<Quantity> {
     let quantity_name = entity.get_string(0)?.to_string();
 
     // Value is at index 3 for all quantity types
-    let quantity_value = entity.get_float(3)?;
+    let value = entity.get_float(3)?;
+    let explicit_unit = entity
+        .get(2)
+        .and_then(|attr| decoder.resolve_ref(attr).ok().flatten())
+        .and_then(|unit| unit_conversion(&unit, decoder));
+    let quantity_value = match (explicit_unit, quantity_unit_type(ifc_type)) {
+        (Some(conversion), _) => conversion.to_si(value),
+        (None, Some(unit_type)) => units.conversion(unit_type).to_si(value),
+        (None, None) => value, // Counts
+    };
 
     Some(Quantity {
         quantity_name,
         quantity_value,
         quantity_type: quantity_type.to_string(),
+        source: QUANTITY_SOURCE_AUTHORED.to_string(),
     })
 }
 
+/// Compute gross geometric quantities for every element with a body.
+///
+/// Returns one `Qto_Computed` set per element plus the IfcRelDefinesByProperties
+/// links to attach them. Set IDs are allocated above `max_id` so they cannot
+/// collide with entities in the file. Openings are not subtracted (gross values).
+/// Elements are meshed in their own coordinates and placed in f64, so values
+/// keep their precision on georeferenced models.
+fn extract_computed_quantities(
+    jobs: &[EntityJob],
+    content: &Arc<String>,
+    entity_index: &Arc<ifc_lite_core::EntityIndex>,
+    units: &UnitContext,
+    max_id: u32,
+) -> (Vec<QuantitySet>, Vec<Relationship>) {
+    let element_jobs: Vec<_> = jobs
+        .iter()
+        .filter(|job| {
+            ifc_lite_core::has_geometry_by_name(&job.type_name)
+                && !job.type_name.eq_ignore_ascii_case("IFCOPENINGELEMENT")
+                && !job.type_name.eq_ignore_ascii_case("IFCOPENINGSTANDARDCASE")
+        })
+        .collect();
+
+    tracing::debug!(count = element_jobs.len(), "Computing geometric quantities");
+
+    // Triangulate FacetedBreps once for all worker routers
+    let faceted_brep_ids: Vec<u32> = jobs
+        .iter()
+        .filter(|job| job.type_name.eq_ignore_ascii_case("IFCFACETEDBREP"))
+        .map(|job| job.id)
+        .collect();
+    let mut decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+    let faceted_breps = Arc::new(GeometryRouter::triangulate_faceted_breps(&faceted_brep_ids, &mut decoder));
+    // Gross values: no openings to subtract
+    let no_voids: FxHashMap<u32, Vec<u32>> = FxHashMap::default();
+
+    let mut sets: Vec<QuantitySet> = element_jobs
+        .par_iter()
+        .map_init(
+            || {
+                let mut router = GeometryRouter::with_scale(units.length_scale());
+                router.set_faceted_breps(faceted_breps.clone());
+                router
+            },
+            |router, job| {
+                let mut local_decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+                local_decoder.set_plane_angle_scale(units.plane_angle_scale());
+                let entity = local_decoder.decode_at(job.start, job.end).ok()?;
+                if entity.get(6).is_none_or(|a| a.is_null()) {
+                    return None;
+                }
+
+                let (mesh, placement) = router.process_element_local(&entity, &mut local_decoder, &no_voids).ok()?;
+                if mesh.is_empty() {
+                    return None;
+                }
+
+                let mut computed = ComputedQuantities::from_placed_mesh(&mesh, &placement);
+                if let Ok(Some(exact)) = router.extrusion_quantities(&entity, &mut local_decoder) {
+                    computed = computed.with_profile_quantities(&exact);
+                }
+
+                Some(QuantitySet {
+                    qset_id: 0, // Assigned below
+                    qset_name: COMPUTED_QSET_NAME.to_string(),
+                    method_of_measurement: None,
+                    quantities: computed_to_quantities(&computed),
+                    element_id: Some(job.id),
+                })
+            },
+        )
+        .flatten()
+        .collect();
+
+    // Stable, deterministic IDs regardless of rayon scheduling
+    sets.sort_unstable_by_key(|set| set.element_id);
+    let relationships = sets
+        .iter_mut()
+        .enumerate()
+        .map(|(i, set)| {
+            set.qset_id = max_id.saturating_add(1 + i as u32);
+            Relationship {
+                rel_type: "IFCRELDEFINESBYPROPERTIES".to_string(),
+                relating_id: set.qset_id,
+                related_id: set.element_id.unwrap_or(0),
+            }
+        })
+        .collect();
+
+    (sets, relationships)
+}
+
+/// Flatten computed quantities into named quantity values.
+fn computed_to_quantities(computed: &ComputedQuantities) -> Vec<Quantity> {
+    let quantity = |name: &str, value: f64, quantity_type: &str, source: QuantitySource| Quantity {
+        quantity_name: name.to_string(),
+        quantity_value: value,
+        quantity_type: quantity_type.to_string(),
+        source: source.as_str().to_string(),
+    };
+
+    let size = computed.bbox_size();
+    let mut quantities = Vec::with_capacity(8);
+    if let Some(volume) = computed.volume {
+        quantities.push(quantity("GrossVolume", volume, "volume", computed.source));
+    }
+    quantities.push(quantity("GrossSurfaceArea", computed.surface_area, "area", computed.source));
+    quantities.push(quantity("GrossFootprintArea", computed.footprint_area, "area", QuantitySource::Mesh));
+    quantities.push(quantity("GrossSideAreaX", computed.side_area_x, "area", QuantitySource::Mesh));
+    quantities.push(quantity("GrossSideAreaY", computed.side_area_y, "area", QuantitySource::Mesh));
+    quantities.push(quantity("BoundingBoxLength", size.x, "length", QuantitySource::Mesh));
+    quantities.push(quantity("BoundingBoxWidth", size.y, "length", QuantitySource::Mesh));
+    quantities.push(quantity("BoundingBoxHeight", size.z, "length", QuantitySource::Mesh));
+    quantities
+}
+
 /// Extract all relationships.
 fn extract_relationships(
     jobs: &[EntityJob],
@@ -471,6 +1411,8 @@ fn extract_relationships(
     // Filter for relationship entities
     let rel_types = [
         "IFCRELCONTAINEDINSPATIALSTRUCTURE",
+        "IFCRELREFERENCEDINSPATIALSTRUCTURE",
+        "IFCRELASSIGNSTOGROUP",
         "IFCRELAGGREGATES",
         "IFCRELDEFINESBYPROPERTIES",
         "IFCRELDEFINESBYTYPE",
@@ -507,7 +1449,11 @@ fn extract_relationship(entity: &DecodedEntity, type_name: &str) -> Option<Vec<R
 
     let (relating_idx, related_idx) = match type_upper.as_str() {
         "IFCRELDEFINESBYPROPERTIES" => (5, 4), // RelatingPropertyDefinition at 5, RelatedObjects at 4
+        "IFCRELDEFINESBYTYPE" => (5, 4), // RelatingType at 5, RelatedObjects at 4
+        "IFCRELASSOCIATESMATERIAL" => (5, 4), // RelatingMaterial at 5, RelatedObjects at 4
         "IFCRELCONTAINEDINSPATIALSTRUCTURE" => (5, 4), // RelatingStructure at 5, RelatedElements at 4
+        "IFCRELREFERENCEDINSPATIALSTRUCTURE" => (5, 4), // RelatingStructure at 5, RelatedElements at 4
+        "IFCRELASSIGNSTOGROUP" => (6, 4), // RelatingGroup at 6, RelatedObjects at 4
         _ => (4, 5), // Standard: RelatingObject at 4, RelatedObjects at 5
     };
 
@@ -535,7 +1481,30 @@ fn extract_relationship(entity: &DecodedEntity, type_name: &str) -> Option<Vec<R
     )
 }
 
+/// Whether an IFC type belongs to the spatial structure: IfcSpatialElement and its
+/// subtypes (sites, facilities and facility parts, storeys, spaces, spatial zones and
+/// external spatial elements).
+fn is_spatial_type(type_name: &str) -> bool {
+    IfcType::from_str(type_name).is_subtype_of(IfcType::IfcSpatialElement)
+}
+
+/// Entity lookup and relationship maps shared while building spatial nodes.
+struct SpatialContext<'a> {
+    entity_map: FxHashMap<u32, &'a EntityMetadata>,
+    /// Spatial parent -> spatial children.
+    children: FxHashMap<u32, Vec<u32>>,
+    /// Spatial container -> contained elements.
+    contained: FxHashMap<u32, Vec<u32>>,
+    /// Spatial structure -> referenced elements.
+    referenced: FxHashMap<u32, Vec<u32>>,
+    length_unit_scale: f64,
+}
+
 /// Build spatial hierarchy from relationships.
+///
+/// Spatial nodes are all IfcSpatialElement subtypes, so IFC4X3 facilities (bridges,
+/// roads, railways, marine facilities) and their parts are handled like buildings
+/// and storeys.
 fn build_spatial_hierarchy(
     relationships: &[Relationship],
     entities: &[EntityMetadata],
@@ -544,33 +1513,53 @@ fn build_spatial_hierarchy(
     length_unit_scale: f64,
 ) -> SpatialHierarchyData {
     let mut decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
-    
-    // Build entity map for quick lookup
-    let entity_map: FxHashMap<u32, &EntityMetadata> = entities
-        .iter()
-        .map(|e| (e.entity_id, e))
-        .collect();
+
+    let mut context = SpatialContext {
+        entity_map: entities.iter().map(|e| (e.entity_id, e)).collect(),
+        children: FxHashMap::default(),
+        contained: FxHashMap::default(),
+        referenced: FxHashMap::default(),
+        length_unit_scale,
+    };
+    let is_spatial = |entity_map: &FxHashMap<u32, &EntityMetadata>, id: u32| {
+        entity_map.get(&id).is_some_and(|e| is_spatial_type(&e.type_name))
+    };
 
     // Separate spatial relationships from element containment
-    // IFCRELAGGREGATES: spatial parent -> spatial child (Project -> Site -> Building -> Storey)
+    // IFCRELAGGREGATES: spatial parent -> spatial child (Project -> Site -> Facility -> Storey/Part -> Space)
     // IFCRELCONTAINEDINSPATIALSTRUCTURE: spatial container -> element (Storey -> Wall, Door, etc.)
-    let mut spatial_children_map: FxHashMap<u32, Vec<u32>> = FxHashMap::default();
-    let mut element_containment_map: FxHashMap<u32, Vec<u32>> = FxHashMap::default();
+    // IFCRELREFERENCEDINSPATIALSTRUCTURE: spatial structure -> referenced element or spatial zone
+    let mut placed: FxHashSet<u32> = FxHashSet::default();
+    let mut referenced_spatial: Vec<(u32, u32)> = Vec::new();
 
     for rel in relationships {
-        let rel_type_upper = rel.rel_type.to_uppercase();
-        if rel_type_upper == "IFCRELAGGREGATES" {
-            // Spatial hierarchy: parent -> child spatial nodes
-            spatial_children_map
-                .entry(rel.relating_id)
-                .or_default()
-                .push(rel.related_id);
-        } else if rel_type_upper == "IFCRELCONTAINEDINSPATIALSTRUCTURE" {
-            // Element containment: spatial container -> elements
-            element_containment_map
-                .entry(rel.relating_id)
-                .or_default()
-                .push(rel.related_id);
+        match rel.rel_type.to_uppercase().as_str() {
+            "IFCRELAGGREGATES" => {
+                // Element decomposition (curtain wall -> plates) is not spatial structure
+                if is_spatial(&context.entity_map, rel.related_id) {
+                    context.children.entry(rel.relating_id).or_default().push(rel.related_id);
+                    placed.insert(rel.related_id);
+                }
+            }
+            "IFCRELCONTAINEDINSPATIALSTRUCTURE" => {
+                context.contained.entry(rel.relating_id).or_default().push(rel.related_id);
+            }
+            "IFCRELREFERENCEDINSPATIALSTRUCTURE" => {
+                if is_spatial(&context.entity_map, rel.related_id) {
+                    referenced_spatial.push((rel.relating_id, rel.related_id));
+                } else {
+                    context.referenced.entry(rel.relating_id).or_default().push(rel.related_id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Spatial zones are usually only referenced in the structure; attach them below
+    // the first structure that references them
+    for (structure_id, spatial_id) in referenced_spatial {
+        if placed.insert(spatial_id) {
+            context.children.entry(structure_id).or_default().push(spatial_id);
         }
     }
 
@@ -583,78 +1572,71 @@ fn build_spatial_hierarchy(
 
     // Build all spatial nodes with full information
     let mut nodes_map: FxHashMap<u32, SpatialNode> = FxHashMap::default();
-    
-    // Collect all spatial entity IDs (Project, Site, Building, Storey, Space)
-    let spatial_types = ["IFCPROJECT", "IFCSITE", "IFCBUILDING", "IFCBUILDINGSTOREY", "IFCSPACE"];
-    let spatial_entity_ids: Vec<u32> = entities
-        .iter()
-        .filter(|e| {
-            let type_upper = e.type_name.to_uppercase();
-            spatial_types.iter().any(|&st| type_upper == st)
-        })
-        .map(|e| e.entity_id)
-        .collect();
 
     // Build nodes recursively starting from project
     if project_id != 0 {
-        build_spatial_nodes_recursive(
-            project_id,
-            0,
-            0,
-            "",
-            &spatial_children_map,
-            &element_containment_map,
-            &entity_map,
-            &mut decoder,
-            &mut nodes_map,
-            length_unit_scale,
-        );
+        build_spatial_nodes_recursive(project_id, 0, 0, "", &context, &mut decoder, &mut nodes_map);
     }
 
-    // Also process any spatial nodes not reachable from project (shouldn't happen, but be safe)
-    for &entity_id in &spatial_entity_ids {
-        if !nodes_map.contains_key(&entity_id) {
-            if let Some(entity) = entity_map.get(&entity_id) {
-                let name = entity.name.clone().unwrap_or_else(|| format!("{}#{}", entity.type_name, entity_id));
-
-                nodes_map.insert(entity_id, SpatialNode {
-                    entity_id,
-                    parent_id: 0,
-                    level: 0,
-                    path: name.clone(),
-                    type_name: entity.type_name.clone(),
-                    name: entity.name.clone(),
-                    elevation: extract_elevation_if_storey(&entity.type_name, entity_id, &mut decoder, length_unit_scale),
-                    children_ids: spatial_children_map.get(&entity_id).cloned().unwrap_or_default(),
-                    element_ids: element_containment_map.get(&entity_id).cloned().unwrap_or_default(),
-                });
-            }
+    // Also process any spatial nodes not reachable from project (shouldn't happen, but be safe),
+    // starting with those that have no spatial parent
+    let (roots, rest): (Vec<_>, Vec<_>) = entities
+        .iter()
+        .filter(|e| is_spatial_type(&e.type_name))
+        .partition(|e| !placed.contains(&e.entity_id));
+    for entity in roots.into_iter().chain(rest) {
+        if !nodes_map.contains_key(&entity.entity_id) {
+            build_spatial_nodes_recursive(entity.entity_id, 0, 0, "", &context, &mut decoder, &mut nodes_map);
         }
     }
 
-    // Build lookup maps for element containment
+    // Build lookup maps for element containment, references and zones
     let mut element_to_storey = Vec::new();
     let mut element_to_building = Vec::new();
     let mut element_to_site = Vec::new();
     let mut element_to_space = Vec::new();
+    let mut element_to_facility = Vec::new();
+    let mut element_to_facility_part = Vec::new();
+    let mut element_to_zone = Vec::new();
+    let mut element_references = Vec::new();
 
     for rel in relationships {
-        if rel.rel_type.to_uppercase() == "IFCRELCONTAINEDINSPATIALSTRUCTURE" {
-            let spatial_id = rel.relating_id;
-            let element_id = rel.related_id;
-            
-            if let Some(spatial_node) = nodes_map.get(&spatial_id) {
-                let type_upper = spatial_node.type_name.to_uppercase();
-                if type_upper == "IFCBUILDINGSTOREY" {
+        let Some(relating) = context.entity_map.get(&rel.relating_id) else {
+            continue;
+        };
+        let relating_type = IfcType::from_str(&relating.type_name);
+        let element_id = rel.related_id;
+
+        match rel.rel_type.to_uppercase().as_str() {
+            "IFCRELCONTAINEDINSPATIALSTRUCTURE" => {
+                let spatial_id = rel.relating_id;
+                if relating_type.is_subtype_of(IfcType::IfcBuildingStorey) {
                     element_to_storey.push((element_id, spatial_id));
-                } else if type_upper == "IFCBUILDING" {
+                } else if relating_type.is_subtype_of(IfcType::IfcBuilding) {
                     element_to_building.push((element_id, spatial_id));
-                } else if type_upper == "IFCSITE" {
+                } else if relating_type.is_subtype_of(IfcType::IfcSite) {
                     element_to_site.push((element_id, spatial_id));
-                } else if type_upper == "IFCSPACE" {
+                } else if relating_type.is_subtype_of(IfcType::IfcSpace) {
                     element_to_space.push((element_id, spatial_id));
                 }
+                if relating_type.is_subtype_of(IfcType::IfcFacility) {
+                    element_to_facility.push((element_id, spatial_id));
+                } else if relating_type.is_subtype_of(IfcType::IfcFacilityPart) {
+                    element_to_facility_part.push((element_id, spatial_id));
+                } else if relating_type.is_subtype_of(IfcType::IfcSpatialZone) {
+                    element_to_zone.push((element_id, spatial_id));
+                }
+            }
+            "IFCRELREFERENCEDINSPATIALSTRUCTURE" if !is_spatial(&context.entity_map, element_id) => {
+                element_references.push((element_id, rel.relating_id));
+                if relating_type.is_subtype_of(IfcType::IfcSpatialZone) {
+                    element_to_zone.push((element_id, rel.relating_id));
+                }
             }
+            "IFCRELASSIGNSTOGROUP" if relating_type.is_subtype_of(IfcType::IfcZone) => {
+                element_to_zone.push((element_id, rel.relating_id));
+            }
+            _ => {}
         }
     }
 
@@ -665,6 +1647,10 @@ fn build_spatial_hierarchy(
         element_to_building,
         element_to_site,
         element_to_space,
+        element_to_facility,
+        element_to_facility_part,
+        element_to_zone,
+        element_references,
     }
 }
 
@@ -674,14 +1660,15 @@ fn build_spatial_nodes_recursive(
     parent_id: u32,
     level: u16,
     parent_path: &str,
-    spatial_children_map: &FxHashMap<u32, Vec<u32>>,
-    element_containment_map: &FxHashMap<u32, Vec<u32>>,
-    entity_map: &FxHashMap<u32, &EntityMetadata>,
+    context: &SpatialContext,
     decoder: &mut EntityDecoder,
     nodes_map: &mut FxHashMap<u32, SpatialNode>,
-    length_unit_scale: f64,
 ) {
-    let entity = match entity_map.get(&entity_id) {
+    // Guard against aggregation cycles in malformed files
+    if nodes_map.contains_key(&entity_id) {
+        return;
+    }
+    let entity = match context.entity_map.get(&entity_id) {
         Some(e) => e,
         None => return,
     };
@@ -697,11 +1684,12 @@ fn build_spatial_nodes_recursive(
     };
 
     // Extract elevation for storeys (with unit scale applied)
-    let elevation = extract_elevation_if_storey(&entity.type_name, entity_id, decoder, length_unit_scale);
+    let elevation = extract_elevation_if_storey(&entity.type_name, entity_id, decoder, context.length_unit_scale);
 
     // Get children and elements
-    let children_ids = spatial_children_map.get(&entity_id).cloned().unwrap_or_default();
-    let element_ids = element_containment_map.get(&entity_id).cloned().unwrap_or_default();
+    let children_ids = context.children.get(&entity_id).cloned().unwrap_or_default();
+    let element_ids = context.contained.get(&entity_id).cloned().unwrap_or_default();
+    let referenced_element_ids = context.referenced.get(&entity_id).cloned().unwrap_or_default();
 
     let node = SpatialNode {
         entity_id,
@@ -713,24 +1701,14 @@ fn build_spatial_nodes_recursive(
         elevation,
         children_ids: children_ids.clone(),
         element_ids,
+        referenced_element_ids,
     };
 
     nodes_map.insert(entity_id, node);
 
     // Recursively process children
     for &child_id in &children_ids {
-        build_spatial_nodes_recursive(
-            child_id,
-            entity_id,
-            level + 1,
-            &path,
-            spatial_children_map,
-            element_containment_map,
-            entity_map,
-            decoder,
-            nodes_map,
-            length_unit_scale,
-        );
+        build_spatial_nodes_recursive(child_id, entity_id, level + 1, &path, context, decoder, nodes_map);
     }
 }
 
@@ -764,3 +1742,309 @@ fn extract_elevation_if_storey(
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One property set holding every supported property kind.
+    const PROPERTIES_IFC4: &str = r#"ISO-10303-21;
+HEADER;
+FILE_SCHEMA(('IFC4'));
+ENDSEC;
+DATA;
+#1=IFCSIUNIT(*,.LENGTHUNIT.,.MILLI.,.METRE.);
+#2=IFCSIUNIT(*,.THERMODYNAMICTEMPERATUREUNIT.,$,.DEGREE_CELSIUS.);
+#3=IFCUNITASSIGNMENT((#1));
+#4=IFCPROJECT('0YvctVUKr0kugbFTf53O9L',$,'Project',$,$,$,$,$,#3);
+#5=IFCPROPERTYENUMERATION('Finishes',(IFCLABEL('Matt'),IFCLABEL('Gloss')),$);
+#6=IFCPROPERTYENUMERATEDVALUE('Finish',$,(IFCLABEL('Gloss')),#5);
+#7=IFCPROPERTYBOUNDEDVALUE('Temperature',$,IFCTHERMODYNAMICTEMPERATUREMEASURE(30.),IFCTHERMODYNAMICTEMPERATUREMEASURE(5.),#2,IFCTHERMODYNAMICTEMPERATUREMEASURE(20.));
+#8=IFCPROPERTYLISTVALUE('Widths',$,(IFCLENGTHMEASURE(100.),IFCLENGTHMEASURE(200.)),$);
+#9=IFCPROPERTYTABLEVALUE('Deflection',$,(IFCREAL(0.),IFCREAL(1.)),(IFCLENGTHMEASURE(10.),IFCLENGTHMEASURE(20.)),'f(x)',$,$,.LINEAR.);
+#10=IFCMATERIAL('Oak',$,$);
+#11=IFCPROPERTYREFERENCEVALUE('Material',$,'Finish',#10);
+#12=IFCPROPERTYSINGLEVALUE('Depth',$,IFCLENGTHMEASURE(50.),$);
+#13=IFCCOMPLEXPROPERTY('Layer',$,'LayerDefinition',(#12,#8));
+#14=IFCCOMPLEXPROPERTY('Loop',$,'Loop',(#14));
+#15=IFCPROPERTYSET('1YvctVUKr0kugbFTf53O9L',$,'Pset_Test',$,(#6,#7,#8,#9,#11,#13,#14));
+ENDSEC;
+END-ISO-10303-21;
+"#;
+
+    /// IFC2X3 bounded and table values, without SetPointValue and CurveInterpolation.
+    const PROPERTIES_IFC2X3: &str = r#"ISO-10303-21;
+HEADER;
+FILE_SCHEMA(('IFC2X3'));
+ENDSEC;
+DATA;
+#1=IFCPROPERTYBOUNDEDVALUE('Temperature',$,IFCREAL(30.),IFCREAL(5.),$);
+#2=IFCPROPERTYTABLEVALUE('Deflection',$,(IFCREAL(0.),IFCREAL(1.)),(IFCREAL(10.),IFCREAL(20.)),$,$,$);
+ENDSEC;
+END-ISO-10303-21;
+"#;
+
+    /// IFC4X3 road and bridge with facility parts, a spatial zone and an IfcZone.
+    const INFRASTRUCTURE_IFC4X3: &str = r#"ISO-10303-21;
+HEADER;
+FILE_SCHEMA(('IFC4X3_ADD2'));
+ENDSEC;
+DATA;
+#1=IFCPROJECT('0YvctVUKr0kugbFTf53O9L',$,'Project',$,$,$,$,$,$);
+#2=IFCSITE('0YvctVUKr0kugbFTf53O9M',$,'Site',$,$,$,$,$,.ELEMENT.,$,$,$,$,$);
+#3=IFCROAD('0YvctVUKr0kugbFTf53O9N',$,'Road',$,$,$,$,$,.ELEMENT.,$);
+#4=IFCBRIDGE('0YvctVUKr0kugbFTf53O9O',$,'Bridge',$,$,$,$,$,.ELEMENT.,.GIRDER.);
+#5=IFCBRIDGEPART('0YvctVUKr0kugbFTf53O9P',$,'Deck',$,$,$,$,$,.ELEMENT.,.LONGITUDINAL.,.DECK.);
+#6=IFCROADPART('0YvctVUKr0kugbFTf53O9Q',$,'Carriageway',$,$,$,$,$,.ELEMENT.,.LONGITUDINAL.,.CARRIAGEWAY.);
+#7=IFCSPATIALZONE('0YvctVUKr0kugbFTf53O9R',$,'Lane',$,$,$,$,$,.LANE.);
+#10=IFCRELAGGREGATES('1YvctVUKr0kugbFTf53O9L',$,$,$,#1,(#2));
+#11=IFCRELAGGREGATES('1YvctVUKr0kugbFTf53O9M',$,$,$,#2,(#3,#4));
+#12=IFCRELAGGREGATES('1YvctVUKr0kugbFTf53O9N',$,$,$,#3,(#6));
+#13=IFCRELAGGREGATES('1YvctVUKr0kugbFTf53O9O',$,$,$,#4,(#5));
+#20=IFCBEAM('2YvctVUKr0kugbFTf53O9L',$,'Girder',$,$,$,$,$,$);
+#21=IFCCOLUMN('2YvctVUKr0kugbFTf53O9M',$,'Pier',$,$,$,$,$,$);
+#22=IFCSLAB('2YvctVUKr0kugbFTf53O9N',$,'Pavement',$,$,$,$,$,$);
+#30=IFCRELCONTAINEDINSPATIALSTRUCTURE('3YvctVUKr0kugbFTf53O9L',$,$,$,(#20),#5);
+#31=IFCRELCONTAINEDINSPATIALSTRUCTURE('3YvctVUKr0kugbFTf53O9M',$,$,$,(#21),#4);
+#32=IFCRELCONTAINEDINSPATIALSTRUCTURE('3YvctVUKr0kugbFTf53O9N',$,$,$,(#22),#6);
+#33=IFCRELREFERENCEDINSPATIALSTRUCTURE('3YvctVUKr0kugbFTf53O9O',$,$,$,(#21),#6);
+#34=IFCRELREFERENCEDINSPATIALSTRUCTURE('3YvctVUKr0kugbFTf53O9P',$,$,$,(#7),#6);
+#35=IFCRELREFERENCEDINSPATIALSTRUCTURE('3YvctVUKr0kugbFTf53O9Q',$,$,$,(#22),#7);
+#40=IFCZONE('4YvctVUKr0kugbFTf53O9L',$,'Maintenance',$,$,$);
+#41=IFCRELASSIGNSTOGROUP('4YvctVUKr0kugbFTf53O9M',$,$,$,(#20,#21),$,#40);
+ENDSEC;
+END-ISO-10303-21;
+"#;
+
+    fn property(content: &str, id: u32) -> Option<Property> {
+        let mut decoder = EntityDecoder::new(content);
+        let units = UnitContext::from_content(content, &mut decoder);
+        let entity = decoder.decode_by_id(id).unwrap();
+        extract_property(&entity, &mut decoder, &units)
+    }
+
+    fn json(property: &Property) -> serde_json::Value {
+        serde_json::from_str(&property.property_value).unwrap()
+    }
+
+    #[test]
+    fn test_enumerated_property() {
+        let finish = property(PROPERTIES_IFC4, 6).unwrap();
+        assert_eq!(finish.property_kind, PROPERTY_KIND_ENUMERATED);
+        assert_eq!(finish.property_type, "label");
+        assert_eq!(finish.values, vec![PropertyValue::Label("Gloss".to_string())]);
+        assert_eq!(json(&finish), serde_json::json!(["Gloss"]));
+    }
+
+    #[test]
+    fn test_bounded_property() {
+        let temperature = property(PROPERTIES_IFC4, 7).unwrap();
+        assert_eq!(temperature.property_kind, PROPERTY_KIND_BOUNDED);
+        assert_eq!(temperature.unit.as_deref(), Some("degree_celsius"));
+        assert_eq!(temperature.measure_type.as_deref(), Some("IFCTHERMODYNAMICTEMPERATUREMEASURE"));
+        assert_eq!(temperature.lower_bound.as_ref().and_then(PropertyValue::as_f64), Some(5.0));
+        assert_eq!(temperature.upper_bound.as_ref().and_then(PropertyValue::as_f64), Some(30.0));
+        // IFC4 SetPointValue at [5]
+        assert_eq!(temperature.value.as_ref().and_then(PropertyValue::as_f64), Some(20.0));
+        assert_eq!(
+            json(&temperature),
+            serde_json::json!({ "lower": 5.0, "upper": 30.0, "set_point": 20.0 })
+        );
+
+        // IFC2X3 has no SetPointValue
+        let temperature = property(PROPERTIES_IFC2X3, 1).unwrap();
+        assert_eq!(temperature.value, None);
+        assert_eq!(temperature.property_type, "real");
+        assert_eq!(json(&temperature), serde_json::json!({ "lower": 5.0, "upper": 30.0 }));
+    }
+
+    #[test]
+    fn test_list_property() {
+        let widths = property(PROPERTIES_IFC4, 8).unwrap();
+        assert_eq!(widths.property_kind, PROPERTY_KIND_LIST);
+        assert_eq!(widths.property_type, "measure");
+        // Unit from the project's IfcUnitAssignment
+        assert_eq!(widths.unit.as_deref(), Some("millimetre"));
+        assert_eq!(json(&widths), serde_json::json!([100.0, 200.0]));
+    }
+
+    #[test]
+    fn test_table_property() {
+        let deflection = property(PROPERTIES_IFC4, 9).unwrap();
+        assert_eq!(deflection.property_kind, PROPERTY_KIND_TABLE);
+        assert_eq!(deflection.unit.as_deref(), Some("millimetre"));
+        assert_eq!(deflection.values.len(), 2);
+        // IFC4 CurveInterpolation at [7]
+        assert_eq!(
+            json(&deflection),
+            serde_json::json!({
+                "defining": [0.0, 1.0],
+                "defined": [10.0, 20.0],
+                "expression": "f(x)",
+                "interpolation": "LINEAR",
+            })
+        );
+
+        // IFC2X3 ends at DefinedUnit
+        let deflection = property(PROPERTIES_IFC2X3, 2).unwrap();
+        assert_eq!(
+            json(&deflection),
+            serde_json::json!({ "defining": [0.0, 1.0], "defined": [10.0, 20.0] })
+        );
+    }
+
+    #[test]
+    fn test_reference_property() {
+        let material = property(PROPERTIES_IFC4, 11).unwrap();
+        assert_eq!(material.property_kind, PROPERTY_KIND_REFERENCE);
+        assert_eq!(material.property_type, "reference");
+        assert_eq!(
+            json(&material),
+            serde_json::json!({
+                "entity_id": 10,
+                "type_name": "IFCMATERIAL",
+                "name": "Oak",
+                "usage": "Finish",
+            })
+        );
+    }
+
+    #[test]
+    fn test_complex_property() {
+        let layer = property(PROPERTIES_IFC4, 13).unwrap();
+        assert_eq!(layer.property_kind, PROPERTY_KIND_COMPLEX);
+        assert_eq!(layer.measure_type.as_deref(), Some("LayerDefinition"));
+        let value = json(&layer);
+        assert_eq!(value["Depth"]["kind"], "single");
+        assert_eq!(value["Depth"]["value"], 50.0);
+        assert_eq!(value["Depth"]["unit"], "millimetre");
+        assert_eq!(value["Widths"]["kind"], "list");
+        assert_eq!(value["Widths"]["value"], serde_json::json!([100.0, 200.0]));
+
+        // A property containing itself stops at the nesting limit
+        let looped = property(PROPERTIES_IFC4, 14).unwrap();
+        let mut depth = 0;
+        let mut value = json(&looped);
+        while let Some(nested) = value.get("Loop") {
+            value = nested["value"].clone();
+            depth += 1;
+        }
+        assert_eq!(depth, MAX_COMPLEX_PROPERTY_DEPTH - 1);
+    }
+
+    #[test]
+    fn test_properties_parquet_round_trip() {
+        use arrow::array::{Array, Float64Array, ListArray, StringArray};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let data_model = extract_data_model(PROPERTIES_IFC4, false);
+        assert_eq!(data_model.property_sets.len(), 1);
+        let parquet = crate::services::serialize_data_model_to_parquet(&data_model).unwrap();
+
+        // Properties are the second length-prefixed table
+        let entities_len = u32::from_le_bytes(parquet[0..4].try_into().unwrap()) as usize;
+        let start = 4 + entities_len;
+        let len = u32::from_le_bytes(parquet[start..start + 4].try_into().unwrap()) as usize;
+        let table = bytes::Bytes::copy_from_slice(&parquet[start + 4..start + 4 + len]);
+        let batch = ParquetRecordBatchReaderBuilder::try_new(table)
+            .unwrap()
+            .build()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+        let names = column("property_name");
+        let names = names.as_any().downcast_ref::<StringArray>().unwrap();
+        let row = |name: &str| (0..names.len()).find(|&i| names.value(i) == name).unwrap();
+
+        let kinds = column("property_kind");
+        let kinds = kinds.as_any().downcast_ref::<StringArray>().unwrap();
+        let expected = [
+            ("Finish", PROPERTY_KIND_ENUMERATED),
+            ("Temperature", PROPERTY_KIND_BOUNDED),
+            ("Widths", PROPERTY_KIND_LIST),
+            ("Deflection", PROPERTY_KIND_TABLE),
+            ("Material", PROPERTY_KIND_REFERENCE),
+            ("Layer", PROPERTY_KIND_COMPLEX),
+        ];
+        for (name, kind) in expected {
+            assert_eq!(kinds.value(row(name)), kind, "{}", name);
+        }
+
+        let bound = |name: &str| {
+            let array = column(name);
+            array.as_any().downcast_ref::<Float64Array>().unwrap().value(row("Temperature"))
+        };
+        assert_eq!((bound("lower_bound"), bound("upper_bound"), bound("value_real")), (5.0, 30.0, 20.0));
+
+        let units = column("unit");
+        let units = units.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(units.value(row("Widths")), "millimetre");
+
+        let values_real = column("values_real");
+        let values_real = values_real.as_any().downcast_ref::<ListArray>().unwrap();
+        let widths = values_real.value(row("Widths"));
+        assert_eq!(widths.as_any().downcast_ref::<Float64Array>().unwrap().values(), &[100.0, 200.0]);
+
+        let values_text = column("values_text");
+        let values_text = values_text.as_any().downcast_ref::<ListArray>().unwrap();
+        let finish = values_text.value(row("Finish"));
+        assert_eq!(finish.as_any().downcast_ref::<StringArray>().unwrap().value(0), "Gloss");
+        assert!(values_text.is_null(row("Widths")));
+    }
+
+    #[test]
+    fn test_facility_hierarchy() {
+        let hierarchy = extract_data_model(INFRASTRUCTURE_IFC4X3, false).spatial_hierarchy;
+        assert_eq!(hierarchy.project_id, 1);
+        let node = |id: u32| hierarchy.nodes.iter().find(|n| n.entity_id == id).unwrap();
+
+        let site = node(2);
+        assert_eq!((site.parent_id, site.level), (1, 1));
+        assert_eq!(site.children_ids, vec![3, 4]);
+
+        let deck = node(5);
+        assert_eq!((deck.parent_id, deck.level), (4, 3));
+        assert_eq!(deck.path, "Project/Site/Bridge/Deck");
+        assert_eq!(deck.element_ids, vec![20]);
+        assert_eq!(node(4).element_ids, vec![21]);
+
+        let mut facilities = hierarchy.element_to_facility.clone();
+        facilities.sort();
+        assert_eq!(facilities, vec![(21, 4)]);
+        let mut parts = hierarchy.element_to_facility_part.clone();
+        parts.sort();
+        assert_eq!(parts, vec![(20, 5), (22, 6)]);
+        // Facilities are not buildings or storeys
+        assert!(hierarchy.element_to_building.is_empty());
+        assert!(hierarchy.element_to_storey.is_empty());
+    }
+
+    #[test]
+    fn test_spatial_references_and_zones() {
+        let hierarchy = extract_data_model(INFRASTRUCTURE_IFC4X3, false).spatial_hierarchy;
+        let node = |id: u32| hierarchy.nodes.iter().find(|n| n.entity_id == id).unwrap();
+
+        // The pier is contained in the bridge and referenced by the road part
+        let carriageway = node(6);
+        assert_eq!(carriageway.element_ids, vec![22]);
+        assert_eq!(carriageway.referenced_element_ids, vec![21]);
+
+        // A referenced spatial zone hangs below the structure referencing it
+        assert_eq!(carriageway.children_ids, vec![7]);
+        let lane = node(7);
+        assert_eq!((lane.parent_id, lane.level), (6, 4));
+        assert_eq!(lane.referenced_element_ids, vec![22]);
+
+        let mut references = hierarchy.element_references.clone();
+        references.sort();
+        assert_eq!(references, vec![(21, 6), (22, 7)]);
+
+        // Zone membership from the spatial zone and the IfcZone group (RelatingGroup at [6])
+        let mut zones = hierarchy.element_to_zone.clone();
+        zones.sort();
+        assert_eq!(zones, vec![(20, 40), (21, 40), (22, 7)]);
+    }
+}
@@ -0,0 +1,121 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 2D drawing service - storey plans and facade elevations as SVG or DXF.
+
+use crate::types::DrawingOptions;
+use ifc_lite_core::{ElementMatcher, EntityDecoder};
+use ifc_lite_geometry::{
+    draw, mesh_elements_local, DrawingFormat, DrawingRequest, DrawingView, ElevationDirection,
+    GeometryRouter, RepresentationSelection, StoreyLevel, DEFAULT_CUT_HEIGHT,
+};
+use thiserror::Error;
+
+/// Errors during drawing generation.
+#[derive(Debug, Error)]
+pub enum DrawingError {
+    #[error("Unknown drawing format '{0}' (expected svg or dxf)")]
+    InvalidFormat(String),
+
+    #[error("Unknown view '{0}' (expected plan, north, south, east or west)")]
+    InvalidView(String),
+
+    #[error("Storey '{0}' not found")]
+    StoreyNotFound(String),
+
+    #[error("Model has no building storeys; pass an elevation instead")]
+    NoStoreys,
+
+    #[error("Geometry error: {0}")]
+    Geometry(#[from] ifc_lite_geometry::Error),
+}
+
+/// A generated drawing file.
+pub struct Drawing {
+    /// File contents.
+    pub body: String,
+    /// Output format.
+    pub format: DrawingFormat,
+    /// Suggested file name without extension.
+    pub name: String,
+}
+
+/// Generate a plan or elevation drawing of an IFC model.
+pub fn generate_drawing(content: &str, options: &DrawingOptions) -> Result<Drawing, DrawingError> {
+    let format = match options.format.as_deref() {
+        None => DrawingFormat::default(),
+        Some(format) => DrawingFormat::parse(format)
+            .ok_or_else(|| DrawingError::InvalidFormat(format.to_string()))?,
+    };
+
+    let (view, name) = match options.view.as_deref().map(str::trim) {
+        None | Some("") | Some("plan") => plan_view(content, options)?,
+        Some(view) => {
+            let direction = ElevationDirection::parse(view)
+                .ok_or_else(|| DrawingError::InvalidView(view.to_string()))?;
+            (
+                DrawingView::Elevation(direction),
+                format!("{:?} elevation", direction),
+            )
+        }
+    };
+
+    // Drawings use body geometry in the same Z-up meter frame as the storeys
+    let elements: Vec<_> = mesh_elements_local(
+        content,
+        &RepresentationSelection::default(),
+        &ElementMatcher::all(),
+    )
+    .into_iter()
+    .map(|element| (element.info.express_id, element.info.ifc_type, element.world_mesh()))
+    .collect();
+
+    let mut request = DrawingRequest::new(view);
+    request.format = format;
+    request.view_depth = options.view_depth;
+    request.title = Some(name.clone());
+    if let Some(scale) = options.scale.filter(|s| *s > 0.0) {
+        request.scale = scale;
+    }
+    if let Some(hatch) = options.hatch {
+        request.hatch = hatch;
+    }
+
+    let body = draw(
+        elements.iter().map(|(id, ifc_type, mesh)| (*id, *ifc_type, mesh)),
+        &request,
+    )?;
+
+    tracing::info!(view = %name, format = format.extension(), size = body.len(), "Generated drawing");
+
+    Ok(Drawing { body, format, name })
+}
+
+/// Resolve the plan cut elevation from the options and the model's storeys.
+fn plan_view(content: &str, options: &DrawingOptions) -> Result<(DrawingView, String), DrawingError> {
+    if let Some(cut_elevation) = options.elevation {
+        return Ok((
+            DrawingView::Plan { cut_elevation },
+            format!("Plan at {:.2} m", cut_elevation),
+        ));
+    }
+
+    let mut decoder = EntityDecoder::new(content);
+    let router = GeometryRouter::with_units(content, &mut decoder);
+    let levels = StoreyLevel::collect(content, &mut decoder, &router);
+
+    let level = match options.storey.as_deref() {
+        Some(key) => StoreyLevel::find(&levels, key)
+            .ok_or_else(|| DrawingError::StoreyNotFound(key.to_string()))?,
+        None => levels.first().ok_or(DrawingError::NoStoreys)?,
+    };
+    let cut_height = options.cut_height.unwrap_or(DEFAULT_CUT_HEIGHT);
+    let name = if level.name.is_empty() {
+        format!("Storey #{}", level.express_id)
+    } else {
+        level.name.clone()
+    };
+
+    Ok((DrawingView::plan(level, cut_height), name))
+}
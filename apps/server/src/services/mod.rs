@@ -6,16 +6,24 @@
 
 pub mod cache;
 pub mod data_model;
+pub mod drawing;
+pub mod glb;
 pub mod parquet;
 pub mod parquet_data_model;
 pub mod parquet_optimized;
 pub mod processor;
+pub mod query;
 pub mod streaming;
+pub mod tiles;
 
 pub use cache::DiskCache;
 pub use data_model::{extract_data_model, DataModel};
+pub use drawing::{generate_drawing, DrawingError};
+pub use glb::export_glb;
 pub use parquet::{serialize_to_parquet, ParquetError};
 pub use parquet_data_model::serialize_data_model_to_parquet;
 pub use parquet_optimized::{serialize_to_parquet_optimized_with_stats, OptimizedStats, VERTEX_MULTIPLIER};
 pub use processor::process_geometry;
+pub use query::{QueryModel, QueryModelCache};
 pub use streaming::process_streaming;
+pub use tiles::generate_tileset;
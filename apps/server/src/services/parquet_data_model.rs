@@ -4,7 +4,7 @@
 
 //! Parquet serialization for IFC data model (entities, properties, relationships, spatial hierarchy).
 
-use crate::services::data_model::{DataModel, EntityMetadata, PropertySet, QuantitySet, Relationship, SpatialHierarchyData, SpatialNode};
+use crate::services::data_model::{DataModel, DistributionPort, ElementClassification, ElementDocument, ElementMaterial, ElementProperty, EntityMetadata, PortConnection, Property, PropertySet, QuantitySet, Relationship, SpatialHierarchyData, SpatialNode, SystemGroup};
 use arrow::array::{BooleanArray, ListArray, StringArray, UInt16Array, UInt32Array};
 use arrow::array::builder::ListBuilder;
 use arrow::array::UInt32Builder;
@@ -31,13 +31,32 @@ pub enum DataModelParquetError {
 
 /// Serialize data model to Parquet format.
 ///
-/// Creates 5 Parquet tables:
+/// Creates 12 Parquet tables:
 /// 1. Entities (entity_id, type_name, global_id, name, has_geometry)
-/// 2. Properties (pset_id, pset_name, property_name, property_value, property_type)
-/// 3. Quantities (qset_id, qset_name, method_of_measurement, quantity_name, quantity_value, quantity_type)
+/// 2. Properties (pset_id, pset_name, property_name, property_value, property_type, property_kind, measure_type, unit,
+///    value_real, value_integer, value_boolean, value_text, values_real, values_text, lower_bound, upper_bound)
+/// 3. Quantities (qset_id, qset_name, method_of_measurement, quantity_name, quantity_value, quantity_type, element_id, source)
 /// 4. Relationships (rel_type, relating_id, related_id)
-/// 5. Spatial (entity_id, parent_id, level, path, type_name, name, elevation, children_ids, element_ids)
-///    Plus lookup tables: element_to_storey, element_to_building, element_to_site, element_to_space
+/// 5. Spatial (entity_id, parent_id, level, path, type_name, name, elevation, children_ids, element_ids,
+///    referenced_element_ids)
+///    Plus lookup tables: element_to_storey, element_to_building, element_to_site, element_to_space,
+///    then after the project id: element_to_facility, element_to_facility_part, element_to_zone,
+///    element_references
+/// 6. Element properties (element_id, pset_id, property_name, source), appended after the
+///    original five tables so older readers keep working
+/// 7. Materials (element_id, source, association_id, association_type, set_id, set_name,
+///    layer_set_direction, direction_sense, offset_from_reference_line, cardinal_point, part_index,
+///    part_kind, part_id, part_name, material_id, material_name, material_category, thickness,
+///    layer_offset, fraction, profile_id, is_ventilated, priority)
+/// 8. Classifications (element_id, source, reference_id, identification, name, location, description,
+///    parents, classification_id, classification_name, classification_source, edition, edition_date)
+/// 9. Documents (element_id, source, reference_id, identification, name, location, description,
+///    document_id, document_identification, document_name, document_description, document_location,
+///    purpose, intended_use, revision, status)
+/// 10. Groups (group_id, type_name, name, long_name, predefined_type, member_ids, serviced_ids)
+/// 11. Ports (port_id, element_id, name, flow_direction, predefined_type, system_type)
+/// 12. Port connections (connection_id, relating_port_id, related_port_id, realizing_element_id,
+///     relating_element_id, related_element_id, upstream_element_id, downstream_element_id)
 pub fn serialize_data_model_to_parquet(data_model: &DataModel) -> Result<Vec<u8>, DataModelParquetError> {
     // Serialize all tables in parallel using rayon
     let (entities_data, ((properties_data, quantities_data), (relationships_data, spatial_data))) = rayon::join(
@@ -59,8 +78,15 @@ pub fn serialize_data_model_to_parquet(data_model: &DataModel) -> Result<Vec<u8>
     let quantities_data = quantities_data?;
     let relationships_data = relationships_data?;
     let spatial_data = spatial_data?;
-
-    // Write format: [entities_len][entities_data][properties_len][properties_data][quantities_len][quantities_data][relationships_len][relationships_data][spatial_len][spatial_data]
+    let element_properties_data = serialize_element_properties_table(&data_model.element_properties)?;
+    let materials_data = serialize_materials_table(&data_model.materials)?;
+    let classifications_data = serialize_classifications_table(&data_model.classifications)?;
+    let documents_data = serialize_documents_table(&data_model.documents)?;
+    let groups_data = serialize_groups_table(&data_model.groups)?;
+    let ports_data = serialize_ports_table(&data_model.ports)?;
+    let port_connections_data = serialize_port_connections_table(&data_model.port_connections)?;
+
+    // Write format: [entities_len][entities_data][properties_len][properties_data][quantities_len][quantities_data][relationships_len][relationships_data][spatial_len][spatial_data][element_properties_len][element_properties_data][materials_len][materials_data][classifications_len][classifications_data][documents_len][documents_data][groups_len][groups_data][ports_len][ports_data][port_connections_len][port_connections_data]
     let mut result = Vec::new();
     result.extend_from_slice(&(entities_data.len() as u32).to_le_bytes());
     result.extend_from_slice(&entities_data);
@@ -72,6 +98,20 @@ pub fn serialize_data_model_to_parquet(data_model: &DataModel) -> Result<Vec<u8>
     result.extend_from_slice(&relationships_data);
     result.extend_from_slice(&(spatial_data.len() as u32).to_le_bytes());
     result.extend_from_slice(&spatial_data);
+    result.extend_from_slice(&(element_properties_data.len() as u32).to_le_bytes());
+    result.extend_from_slice(&element_properties_data);
+    result.extend_from_slice(&(materials_data.len() as u32).to_le_bytes());
+    result.extend_from_slice(&materials_data);
+    result.extend_from_slice(&(classifications_data.len() as u32).to_le_bytes());
+    result.extend_from_slice(&classifications_data);
+    result.extend_from_slice(&(documents_data.len() as u32).to_le_bytes());
+    result.extend_from_slice(&documents_data);
+    result.extend_from_slice(&(groups_data.len() as u32).to_le_bytes());
+    result.extend_from_slice(&groups_data);
+    result.extend_from_slice(&(ports_data.len() as u32).to_le_bytes());
+    result.extend_from_slice(&ports_data);
+    result.extend_from_slice(&(port_connections_data.len() as u32).to_le_bytes());
+    result.extend_from_slice(&port_connections_data);
 
     Ok(result)
 }
@@ -132,36 +172,87 @@ fn serialize_entities_table(entities: &[EntityMetadata]) -> Result<Vec<u8>, Data
 }
 
 /// Serialize properties table.
+///
+/// Typed columns hold the value without JSON: `value_*` for the nominal value
+/// (single) or set point (bounded), `values_real`/`values_text` for enumerated,
+/// list and table values (numeric lists as reals, anything else as text), and
+/// numeric bounds.
 fn serialize_properties_table(property_sets: &[PropertySet]) -> Result<Vec<u8>, DataModelParquetError> {
+    use arrow::array::builder::{Float64Builder, StringBuilder};
+    use arrow::array::{Float64Array, Int64Array};
+    use ifc_lite_core::PropertyValue;
+
     // Flatten property sets into rows using parallel iteration
-    let rows: Vec<(u32, String, String, String, String)> = property_sets
+    let rows: Vec<(u32, &str, &Property)> = property_sets
         .par_iter()
         .flat_map_iter(|pset| {
-            pset.properties.iter().map(move |prop| {
-                (
-                    pset.pset_id,
-                    pset.pset_name.clone(),
-                    prop.property_name.clone(),
-                    prop.property_value.clone(),
-                    prop.property_type.clone(),
-                )
-            })
+            pset.properties
+                .iter()
+                .map(move |prop| (pset.pset_id, pset.pset_name.as_str(), prop))
         })
         .collect();
-    
-    // Split into separate vectors
-    let mut pset_ids = Vec::with_capacity(rows.len());
-    let mut pset_names = Vec::with_capacity(rows.len());
-    let mut property_names = Vec::with_capacity(rows.len());
-    let mut property_values = Vec::with_capacity(rows.len());
-    let mut property_types = Vec::with_capacity(rows.len());
-    
-    for (pset_id, pset_name, prop_name, prop_value, prop_type) in rows {
+
+    let count = rows.len();
+    let mut pset_ids = Vec::with_capacity(count);
+    let mut pset_names = Vec::with_capacity(count);
+    let mut property_names = Vec::with_capacity(count);
+    let mut property_values = Vec::with_capacity(count);
+    let mut property_types = Vec::with_capacity(count);
+    let mut property_kinds = Vec::with_capacity(count);
+    let mut measure_types = Vec::with_capacity(count);
+    let mut units = Vec::with_capacity(count);
+    let mut value_reals = Vec::with_capacity(count);
+    let mut value_integers = Vec::with_capacity(count);
+    let mut value_booleans = Vec::with_capacity(count);
+    let mut value_texts = Vec::with_capacity(count);
+    let mut lower_bounds = Vec::with_capacity(count);
+    let mut upper_bounds = Vec::with_capacity(count);
+    let mut values_real = ListBuilder::new(Float64Builder::new());
+    let mut values_text = ListBuilder::new(StringBuilder::new());
+
+    for (pset_id, pset_name, prop) in rows {
         pset_ids.push(pset_id);
         pset_names.push(pset_name);
-        property_names.push(prop_name);
-        property_values.push(prop_value);
-        property_types.push(prop_type);
+        property_names.push(prop.property_name.as_str());
+        property_values.push(prop.property_value.as_str());
+        property_types.push(prop.property_type.as_str());
+        property_kinds.push(prop.property_kind.as_str());
+        measure_types.push(prop.measure_type.as_deref());
+        units.push(prop.unit.as_deref());
+
+        let value = prop.value.as_ref();
+        value_reals.push(match value {
+            Some(PropertyValue::Integer(_)) => None,
+            other => other.and_then(PropertyValue::as_f64),
+        });
+        value_integers.push(match value {
+            Some(PropertyValue::Integer(i)) => Some(*i),
+            _ => None,
+        });
+        value_booleans.push(value.and_then(PropertyValue::as_bool));
+        value_texts.push(value.and_then(PropertyValue::as_text));
+        lower_bounds.push(prop.lower_bound.as_ref().and_then(PropertyValue::as_f64));
+        upper_bounds.push(prop.upper_bound.as_ref().and_then(PropertyValue::as_f64));
+
+        let numeric: Option<Vec<f64>> = prop.values.iter().map(PropertyValue::as_f64).collect();
+        match numeric {
+            _ if prop.values.is_empty() => {
+                values_real.append(false);
+                values_text.append(false);
+            }
+            Some(numbers) => {
+                values_real.values().append_slice(&numbers);
+                values_real.append(true);
+                values_text.append(false);
+            }
+            None => {
+                for value in &prop.values {
+                    values_text.values().append_value(value.to_string());
+                }
+                values_text.append(true);
+                values_real.append(false);
+            }
+        }
     }
 
     let schema = Schema::new(vec![
@@ -170,6 +261,17 @@ fn serialize_properties_table(property_sets: &[PropertySet]) -> Result<Vec<u8>,
         Field::new("property_name", DataType::Utf8, false),
         Field::new("property_value", DataType::Utf8, false),
         Field::new("property_type", DataType::Utf8, false),
+        Field::new("property_kind", DataType::Utf8, false),
+        Field::new("measure_type", DataType::Utf8, true),
+        Field::new("unit", DataType::Utf8, true),
+        Field::new("value_real", DataType::Float64, true),
+        Field::new("value_integer", DataType::Int64, true),
+        Field::new("value_boolean", DataType::Boolean, true),
+        Field::new("value_text", DataType::Utf8, true),
+        Field::new("values_real", DataType::new_list(DataType::Float64, true), true),
+        Field::new("values_text", DataType::new_list(DataType::Utf8, true), true),
+        Field::new("lower_bound", DataType::Float64, true),
+        Field::new("upper_bound", DataType::Float64, true),
     ]);
 
     let batch = RecordBatch::try_new(
@@ -180,6 +282,299 @@ fn serialize_properties_table(property_sets: &[PropertySet]) -> Result<Vec<u8>,
             Arc::new(StringArray::from(property_names)),
             Arc::new(StringArray::from(property_values)),
             Arc::new(StringArray::from(property_types)),
+            Arc::new(StringArray::from(property_kinds)),
+            Arc::new(StringArray::from(measure_types)),
+            Arc::new(StringArray::from(units)),
+            Arc::new(Float64Array::from(value_reals)),
+            Arc::new(Int64Array::from(value_integers)),
+            Arc::new(BooleanArray::from(value_booleans)),
+            Arc::new(StringArray::from(value_texts)),
+            Arc::new(values_real.finish()),
+            Arc::new(values_text.finish()),
+            Arc::new(Float64Array::from(lower_bounds)),
+            Arc::new(Float64Array::from(upper_bounds)),
+        ],
+    )?;
+
+    write_parquet_batch(batch)
+}
+
+/// Serialize effective element properties table.
+fn serialize_element_properties_table(
+    element_properties: &[ElementProperty],
+) -> Result<Vec<u8>, DataModelParquetError> {
+    let element_ids: Vec<u32> = element_properties.iter().map(|p| p.element_id).collect();
+    let pset_ids: Vec<u32> = element_properties.iter().map(|p| p.pset_id).collect();
+    let property_names: Vec<&str> = element_properties.iter().map(|p| p.property_name.as_str()).collect();
+    let sources: Vec<&str> = element_properties.iter().map(|p| p.source.as_str()).collect();
+
+    let schema = Schema::new(vec![
+        Field::new("element_id", DataType::UInt32, false),
+        Field::new("pset_id", DataType::UInt32, false),
+        Field::new("property_name", DataType::Utf8, false),
+        Field::new("source", DataType::Utf8, false),
+    ]);
+
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(UInt32Array::from(element_ids)),
+            Arc::new(UInt32Array::from(pset_ids)),
+            Arc::new(StringArray::from(property_names)),
+            Arc::new(StringArray::from(sources)),
+        ],
+    )?;
+
+    write_parquet_batch(batch)
+}
+
+/// Serialize element materials table.
+fn serialize_materials_table(materials: &[ElementMaterial]) -> Result<Vec<u8>, DataModelParquetError> {
+    use arrow::array::{Float64Array, Int64Array};
+
+    let schema = Schema::new(vec![
+        Field::new("element_id", DataType::UInt32, false),
+        Field::new("source", DataType::Utf8, false),
+        Field::new("association_id", DataType::UInt32, false),
+        Field::new("association_type", DataType::Utf8, false),
+        Field::new("set_id", DataType::UInt32, true),
+        Field::new("set_name", DataType::Utf8, true),
+        Field::new("layer_set_direction", DataType::Utf8, true),
+        Field::new("direction_sense", DataType::Utf8, true),
+        Field::new("offset_from_reference_line", DataType::Float64, true),
+        Field::new("cardinal_point", DataType::Int64, true),
+        Field::new("part_index", DataType::UInt32, false),
+        Field::new("part_kind", DataType::Utf8, false),
+        Field::new("part_id", DataType::UInt32, true),
+        Field::new("part_name", DataType::Utf8, true),
+        Field::new("material_id", DataType::UInt32, true),
+        Field::new("material_name", DataType::Utf8, true),
+        Field::new("material_category", DataType::Utf8, true),
+        Field::new("thickness", DataType::Float64, true),
+        Field::new("layer_offset", DataType::Float64, true),
+        Field::new("fraction", DataType::Float64, true),
+        Field::new("profile_id", DataType::UInt32, true),
+        Field::new("is_ventilated", DataType::Boolean, true),
+        Field::new("priority", DataType::Int64, true),
+    ]);
+
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(UInt32Array::from_iter_values(materials.iter().map(|m| m.element_id))),
+            Arc::new(StringArray::from_iter_values(materials.iter().map(|m| m.source.as_str()))),
+            Arc::new(UInt32Array::from_iter_values(materials.iter().map(|m| m.association_id))),
+            Arc::new(StringArray::from_iter_values(materials.iter().map(|m| m.association_type.as_str()))),
+            Arc::new(UInt32Array::from_iter(materials.iter().map(|m| m.set_id))),
+            Arc::new(StringArray::from_iter(materials.iter().map(|m| m.set_name.as_deref()))),
+            Arc::new(StringArray::from_iter(materials.iter().map(|m| m.layer_set_direction.as_deref()))),
+            Arc::new(StringArray::from_iter(materials.iter().map(|m| m.direction_sense.as_deref()))),
+            Arc::new(Float64Array::from_iter(materials.iter().map(|m| m.offset_from_reference_line))),
+            Arc::new(Int64Array::from_iter(materials.iter().map(|m| m.cardinal_point))),
+            Arc::new(UInt32Array::from_iter_values(materials.iter().map(|m| m.part_index))),
+            Arc::new(StringArray::from_iter_values(materials.iter().map(|m| m.part_kind.as_str()))),
+            Arc::new(UInt32Array::from_iter(materials.iter().map(|m| m.part_id))),
+            Arc::new(StringArray::from_iter(materials.iter().map(|m| m.part_name.as_deref()))),
+            Arc::new(UInt32Array::from_iter(materials.iter().map(|m| m.material_id))),
+            Arc::new(StringArray::from_iter(materials.iter().map(|m| m.material_name.as_deref()))),
+            Arc::new(StringArray::from_iter(materials.iter().map(|m| m.material_category.as_deref()))),
+            Arc::new(Float64Array::from_iter(materials.iter().map(|m| m.thickness))),
+            Arc::new(Float64Array::from_iter(materials.iter().map(|m| m.layer_offset))),
+            Arc::new(Float64Array::from_iter(materials.iter().map(|m| m.fraction))),
+            Arc::new(UInt32Array::from_iter(materials.iter().map(|m| m.profile_id))),
+            Arc::new(BooleanArray::from_iter(materials.iter().map(|m| m.is_ventilated))),
+            Arc::new(Int64Array::from_iter(materials.iter().map(|m| m.priority))),
+        ],
+    )?;
+
+    write_parquet_batch(batch)
+}
+
+/// Serialize element classifications table.
+fn serialize_classifications_table(
+    classifications: &[ElementClassification],
+) -> Result<Vec<u8>, DataModelParquetError> {
+    use arrow::array::builder::StringBuilder;
+
+    let mut parents = ListBuilder::new(StringBuilder::new());
+    for classification in classifications {
+        for parent in &classification.parents {
+            parents.values().append_value(parent);
+        }
+        parents.append(true);
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("element_id", DataType::UInt32, false),
+        Field::new("source", DataType::Utf8, false),
+        Field::new("reference_id", DataType::UInt32, false),
+        Field::new("identification", DataType::Utf8, true),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("location", DataType::Utf8, true),
+        Field::new("description", DataType::Utf8, true),
+        Field::new("parents", DataType::new_list(DataType::Utf8, true), true),
+        Field::new("classification_id", DataType::UInt32, true),
+        Field::new("classification_name", DataType::Utf8, true),
+        Field::new("classification_source", DataType::Utf8, true),
+        Field::new("edition", DataType::Utf8, true),
+        Field::new("edition_date", DataType::Utf8, true),
+    ]);
+
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(UInt32Array::from_iter_values(classifications.iter().map(|c| c.element_id))),
+            Arc::new(StringArray::from_iter_values(classifications.iter().map(|c| c.source.as_str()))),
+            Arc::new(UInt32Array::from_iter_values(classifications.iter().map(|c| c.reference_id))),
+            Arc::new(StringArray::from_iter(classifications.iter().map(|c| c.identification.as_deref()))),
+            Arc::new(StringArray::from_iter(classifications.iter().map(|c| c.name.as_deref()))),
+            Arc::new(StringArray::from_iter(classifications.iter().map(|c| c.location.as_deref()))),
+            Arc::new(StringArray::from_iter(classifications.iter().map(|c| c.description.as_deref()))),
+            Arc::new(parents.finish()),
+            Arc::new(UInt32Array::from_iter(classifications.iter().map(|c| c.classification_id))),
+            Arc::new(StringArray::from_iter(classifications.iter().map(|c| c.classification_name.as_deref()))),
+            Arc::new(StringArray::from_iter(classifications.iter().map(|c| c.classification_source.as_deref()))),
+            Arc::new(StringArray::from_iter(classifications.iter().map(|c| c.edition.as_deref()))),
+            Arc::new(StringArray::from_iter(classifications.iter().map(|c| c.edition_date.as_deref()))),
+        ],
+    )?;
+
+    write_parquet_batch(batch)
+}
+
+/// Serialize element documents table.
+fn serialize_documents_table(documents: &[ElementDocument]) -> Result<Vec<u8>, DataModelParquetError> {
+    let schema = Schema::new(vec![
+        Field::new("element_id", DataType::UInt32, false),
+        Field::new("source", DataType::Utf8, false),
+        Field::new("reference_id", DataType::UInt32, false),
+        Field::new("identification", DataType::Utf8, true),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("location", DataType::Utf8, true),
+        Field::new("description", DataType::Utf8, true),
+        Field::new("document_id", DataType::UInt32, true),
+        Field::new("document_identification", DataType::Utf8, true),
+        Field::new("document_name", DataType::Utf8, true),
+        Field::new("document_description", DataType::Utf8, true),
+        Field::new("document_location", DataType::Utf8, true),
+        Field::new("purpose", DataType::Utf8, true),
+        Field::new("intended_use", DataType::Utf8, true),
+        Field::new("revision", DataType::Utf8, true),
+        Field::new("status", DataType::Utf8, true),
+    ]);
+
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(UInt32Array::from_iter_values(documents.iter().map(|d| d.element_id))),
+            Arc::new(StringArray::from_iter_values(documents.iter().map(|d| d.source.as_str()))),
+            Arc::new(UInt32Array::from_iter_values(documents.iter().map(|d| d.reference_id))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.identification.as_deref()))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.name.as_deref()))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.location.as_deref()))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.description.as_deref()))),
+            Arc::new(UInt32Array::from_iter(documents.iter().map(|d| d.document_id))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.document_identification.as_deref()))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.document_name.as_deref()))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.document_description.as_deref()))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.document_location.as_deref()))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.purpose.as_deref()))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.intended_use.as_deref()))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.revision.as_deref()))),
+            Arc::new(StringArray::from_iter(documents.iter().map(|d| d.status.as_deref()))),
+        ],
+    )?;
+
+    write_parquet_batch(batch)
+}
+
+/// Serialize groups table.
+fn serialize_groups_table(groups: &[SystemGroup]) -> Result<Vec<u8>, DataModelParquetError> {
+    let mut member_builder = ListBuilder::new(UInt32Builder::new());
+    let mut serviced_builder = ListBuilder::new(UInt32Builder::new());
+    for group in groups {
+        member_builder.values().append_slice(&group.member_ids);
+        member_builder.append(true);
+        serviced_builder.values().append_slice(&group.serviced_ids);
+        serviced_builder.append(true);
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("group_id", DataType::UInt32, false),
+        Field::new("type_name", DataType::Utf8, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("long_name", DataType::Utf8, true),
+        Field::new("predefined_type", DataType::Utf8, true),
+        Field::new("member_ids", DataType::new_list(DataType::UInt32, true), false),
+        Field::new("serviced_ids", DataType::new_list(DataType::UInt32, true), false),
+    ]);
+
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(UInt32Array::from_iter_values(groups.iter().map(|g| g.group_id))),
+            Arc::new(StringArray::from_iter_values(groups.iter().map(|g| g.type_name.as_str()))),
+            Arc::new(StringArray::from_iter(groups.iter().map(|g| g.name.as_deref()))),
+            Arc::new(StringArray::from_iter(groups.iter().map(|g| g.long_name.as_deref()))),
+            Arc::new(StringArray::from_iter(groups.iter().map(|g| g.predefined_type.as_deref()))),
+            Arc::new(member_builder.finish()),
+            Arc::new(serviced_builder.finish()),
+        ],
+    )?;
+
+    write_parquet_batch(batch)
+}
+
+/// Serialize distribution ports table.
+fn serialize_ports_table(ports: &[DistributionPort]) -> Result<Vec<u8>, DataModelParquetError> {
+    let schema = Schema::new(vec![
+        Field::new("port_id", DataType::UInt32, false),
+        Field::new("element_id", DataType::UInt32, true),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("flow_direction", DataType::Utf8, false),
+        Field::new("predefined_type", DataType::Utf8, true),
+        Field::new("system_type", DataType::Utf8, true),
+    ]);
+
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(UInt32Array::from_iter_values(ports.iter().map(|p| p.port_id))),
+            Arc::new(UInt32Array::from_iter(ports.iter().map(|p| p.element_id))),
+            Arc::new(StringArray::from_iter(ports.iter().map(|p| p.name.as_deref()))),
+            Arc::new(StringArray::from_iter_values(ports.iter().map(|p| p.flow_direction.as_str()))),
+            Arc::new(StringArray::from_iter(ports.iter().map(|p| p.predefined_type.as_deref()))),
+            Arc::new(StringArray::from_iter(ports.iter().map(|p| p.system_type.as_deref()))),
+        ],
+    )?;
+
+    write_parquet_batch(batch)
+}
+
+/// Serialize port connections table.
+fn serialize_port_connections_table(connections: &[PortConnection]) -> Result<Vec<u8>, DataModelParquetError> {
+    let schema = Schema::new(vec![
+        Field::new("connection_id", DataType::UInt32, false),
+        Field::new("relating_port_id", DataType::UInt32, false),
+        Field::new("related_port_id", DataType::UInt32, false),
+        Field::new("realizing_element_id", DataType::UInt32, true),
+        Field::new("relating_element_id", DataType::UInt32, true),
+        Field::new("related_element_id", DataType::UInt32, true),
+        Field::new("upstream_element_id", DataType::UInt32, true),
+        Field::new("downstream_element_id", DataType::UInt32, true),
+    ]);
+
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(UInt32Array::from_iter_values(connections.iter().map(|c| c.connection_id))),
+            Arc::new(UInt32Array::from_iter_values(connections.iter().map(|c| c.relating_port_id))),
+            Arc::new(UInt32Array::from_iter_values(connections.iter().map(|c| c.related_port_id))),
+            Arc::new(UInt32Array::from_iter(connections.iter().map(|c| c.realizing_element_id))),
+            Arc::new(UInt32Array::from_iter(connections.iter().map(|c| c.relating_element_id))),
+            Arc::new(UInt32Array::from_iter(connections.iter().map(|c| c.related_element_id))),
+            Arc::new(UInt32Array::from_iter(connections.iter().map(|c| c.upstream_element_id))),
+            Arc::new(UInt32Array::from_iter(connections.iter().map(|c| c.downstream_element_id))),
         ],
     )?;
 
@@ -190,19 +585,31 @@ fn serialize_properties_table(property_sets: &[PropertySet]) -> Result<Vec<u8>,
 fn serialize_quantities_table(quantity_sets: &[QuantitySet]) -> Result<Vec<u8>, DataModelParquetError> {
     use arrow::array::Float64Array;
 
+    /// One flattened quantity row.
+    struct QuantityRow {
+        qset_id: u32,
+        qset_name: String,
+        method: String,
+        quantity_name: String,
+        quantity_value: f64,
+        quantity_type: String,
+        element_id: Option<u32>,
+        source: String,
+    }
+
     // Flatten quantity sets into rows using parallel iteration
-    let rows: Vec<(u32, String, String, String, f64, String)> = quantity_sets
+    let rows: Vec<QuantityRow> = quantity_sets
         .par_iter()
         .flat_map_iter(|qset| {
-            qset.quantities.iter().map(move |quant| {
-                (
-                    qset.qset_id,
-                    qset.qset_name.clone(),
-                    qset.method_of_measurement.clone().unwrap_or_default(),
-                    quant.quantity_name.clone(),
-                    quant.quantity_value,
-                    quant.quantity_type.clone(),
-                )
+            qset.quantities.iter().map(move |quant| QuantityRow {
+                qset_id: qset.qset_id,
+                qset_name: qset.qset_name.clone(),
+                method: qset.method_of_measurement.clone().unwrap_or_default(),
+                quantity_name: quant.quantity_name.clone(),
+                quantity_value: quant.quantity_value,
+                quantity_type: quant.quantity_type.clone(),
+                element_id: qset.element_id,
+                source: quant.source.clone(),
             })
         })
         .collect();
@@ -214,14 +621,18 @@ fn serialize_quantities_table(quantity_sets: &[QuantitySet]) -> Result<Vec<u8>,
     let mut quantity_names = Vec::with_capacity(rows.len());
     let mut quantity_values = Vec::with_capacity(rows.len());
     let mut quantity_types = Vec::with_capacity(rows.len());
-
-    for (qset_id, qset_name, method, quant_name, quant_value, quant_type) in rows {
-        qset_ids.push(qset_id);
-        qset_names.push(qset_name);
-        methods.push(method);
-        quantity_names.push(quant_name);
-        quantity_values.push(quant_value);
-        quantity_types.push(quant_type);
+    let mut element_ids = Vec::with_capacity(rows.len());
+    let mut sources = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        qset_ids.push(row.qset_id);
+        qset_names.push(row.qset_name);
+        methods.push(row.method);
+        quantity_names.push(row.quantity_name);
+        quantity_values.push(row.quantity_value);
+        quantity_types.push(row.quantity_type);
+        element_ids.push(row.element_id);
+        sources.push(row.source);
     }
 
     let schema = Schema::new(vec![
@@ -231,6 +642,8 @@ fn serialize_quantities_table(quantity_sets: &[QuantitySet]) -> Result<Vec<u8>,
         Field::new("quantity_name", DataType::Utf8, false),
         Field::new("quantity_value", DataType::Float64, false),
         Field::new("quantity_type", DataType::Utf8, false),
+        Field::new("element_id", DataType::UInt32, true),
+        Field::new("source", DataType::Utf8, false),
     ]);
 
     let batch = RecordBatch::try_new(
@@ -242,6 +655,8 @@ fn serialize_quantities_table(quantity_sets: &[QuantitySet]) -> Result<Vec<u8>,
             Arc::new(StringArray::from(quantity_names)),
             Arc::new(Float64Array::from(quantity_values)),
             Arc::new(StringArray::from(quantity_types)),
+            Arc::new(UInt32Array::from(element_ids)),
+            Arc::new(StringArray::from(sources)),
         ],
     )?;
 
@@ -313,9 +728,21 @@ fn serialize_spatial_hierarchy(hierarchy: &SpatialHierarchyData) -> Result<Vec<u
     result.extend_from_slice(&(element_to_space_data.len() as u32).to_le_bytes());
     result.extend_from_slice(&element_to_space_data);
 
-    // Add project_id as final u32
+    // Add project_id as final u32 of the original layout
     result.extend_from_slice(&hierarchy.project_id.to_le_bytes());
 
+    // Facility, zone and reference lookups follow, so older readers keep working
+    for (pairs, table_name) in [
+        (&hierarchy.element_to_facility, "element_to_facility"),
+        (&hierarchy.element_to_facility_part, "element_to_facility_part"),
+        (&hierarchy.element_to_zone, "element_to_zone"),
+        (&hierarchy.element_references, "element_references"),
+    ] {
+        let data = serialize_lookup_table(pairs, table_name)?;
+        result.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        result.extend_from_slice(&data);
+    }
+
     Ok(result)
 }
 
@@ -333,6 +760,7 @@ fn serialize_spatial_nodes_table(spatial_nodes: &[SpatialNode]) -> Result<Vec<u8
     let mut elevations: Vec<Option<f64>> = Vec::with_capacity(count);
     let mut children_ids_list = Vec::with_capacity(count);
     let mut element_ids_list = Vec::with_capacity(count);
+    let mut referenced_builder = ListBuilder::new(UInt32Builder::new());
 
     for node in spatial_nodes {
         entity_ids.push(node.entity_id);
@@ -344,6 +772,8 @@ fn serialize_spatial_nodes_table(spatial_nodes: &[SpatialNode]) -> Result<Vec<u8
         elevations.push(node.elevation);
         children_ids_list.push(node.children_ids.clone());
         element_ids_list.push(node.element_ids.clone());
+        referenced_builder.values().append_slice(&node.referenced_element_ids);
+        referenced_builder.append(true);
     }
     
 
@@ -390,6 +820,7 @@ fn serialize_spatial_nodes_table(spatial_nodes: &[SpatialNode]) -> Result<Vec<u8
         Field::new("elevation", DataType::Float64, true), // Nullable
         Field::new("children_ids", DataType::new_list(DataType::UInt32, true), false), // Inner items nullable
         Field::new("element_ids", DataType::new_list(DataType::UInt32, true), false),  // Inner items nullable
+        Field::new("referenced_element_ids", DataType::new_list(DataType::UInt32, true), false),
     ]);
 
     let batch = RecordBatch::try_new(
@@ -404,6 +835,7 @@ fn serialize_spatial_nodes_table(spatial_nodes: &[SpatialNode]) -> Result<Vec<u8
             Arc::new(Float64Array::from(elevations)),
             Arc::new(children_list_array),
             Arc::new(element_list_array),
+            Arc::new(referenced_builder.finish()),
         ],
     )?;
 
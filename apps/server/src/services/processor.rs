@@ -5,8 +5,12 @@
 //! IFC processing service with parallel geometry extraction.
 
 use crate::types::{CoordinateInfo, MeshData, ModelMetadata, ProcessingStats};
-use ifc_lite_core::{build_entity_index, DecodedEntity, EntityDecoder, EntityScanner, IfcType};
-use ifc_lite_geometry::{calculate_normals, GeometryRouter};
+use ifc_lite_core::{build_entity_index, ElementFilter, EntityDecoder, EntityScanner, IfcType};
+use ifc_lite_geometry::{
+    build_material_layer_index, build_texture_map_index, calculate_crease_normals, default_color,
+    validate_mesh_with_intersections, GeometryRouter, RepresentationSelection, StyleIndex,
+    SurfaceMaterial, DEFAULT_CREASE_ANGLE, DEFAULT_WELD_TOLERANCE,
+};
 use rayon::prelude::*;
 use rustc_hash::FxHashMap;
 use std::sync::Arc;
@@ -27,19 +31,20 @@ struct EntityJob {
     end: usize,
 }
 
-/// Extract entity references from a list attribute.
-fn get_refs_from_list(entity: &DecodedEntity, index: usize) -> Option<Vec<u32>> {
-    let list = entity.get_list(index)?;
-    let refs: Vec<u32> = list.iter().filter_map(|v| v.as_entity_ref()).collect();
-    if refs.is_empty() {
-        None
-    } else {
-        Some(refs)
-    }
-}
-
 /// Process IFC content with parallel geometry extraction.
-pub fn process_geometry(content: &str) -> ProcessingResult {
+///
+/// `selection` chooses which shape representations are meshed (body by default)
+/// and `filter` which products; unselected products are skipped before any
+/// geometry work. With `split_layers`, walls and slabs with a material layer set
+/// usage produce one mesh per layer, coloured by the layer's material. With
+/// `check_self_intersections`, meshes crossing themselves are not counted as solid.
+pub fn process_geometry(
+    content: &str,
+    selection: &RepresentationSelection,
+    filter: &ElementFilter,
+    split_layers: bool,
+    check_self_intersections: bool,
+) -> ProcessingResult {
     let total_start = std::time::Instant::now();
     let parse_start = std::time::Instant::now();
 
@@ -50,8 +55,10 @@ pub fn process_geometry(content: &str) -> ProcessingResult {
     let mut decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
     tracing::debug!("Built entity index");
 
-    // OPTIMIZATION: Build style indices in a single pass (previously two separate scans)
-    let style_index = Arc::new(build_style_indices(content, &mut decoder));
+    // Resolve element styles in a single pass (styled item → material → type → default)
+    let style_index = Arc::new(StyleIndex::build(content, &mut decoder));
+    let texture_maps = Arc::new(build_texture_map_index(content, &mut decoder));
+    let matcher = filter.compile(content, &mut decoder);
 
     // Collect geometry entities and build void index
     let mut scanner = EntityScanner::new(content);
@@ -74,7 +81,9 @@ pub fn process_geometry(content: &str) -> ProcessingResult {
             }
         }
 
-        if ifc_lite_core::has_geometry_by_name(type_name) {
+        if ifc_lite_core::has_geometry_by_name(type_name)
+            && matcher.matches_name(id, type_name)
+        {
             if let Ok(entity) = decoder.decode_at(start, end) {
                 entity_jobs.push(EntityJob {
                     id,
@@ -106,7 +115,8 @@ pub fn process_geometry(content: &str) -> ProcessingResult {
 
     // Preprocess complex geometry
     let router = GeometryRouter::with_units(content, &mut decoder);
-    if !faceted_brep_ids.is_empty() {
+    // Filtered loads mesh their FacetedBreps on demand instead of all up front
+    if !faceted_brep_ids.is_empty() && matcher.is_all() {
         tracing::debug!(count = faceted_brep_ids.len(), "Preprocessing FacetedBreps");
         router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
     }
@@ -119,50 +129,92 @@ pub fn process_geometry(content: &str) -> ProcessingResult {
     let content_arc = Arc::new(content.to_string());
     let entity_index_arc = entity_index; // Already Arc from above
     let unit_scale = router.unit_scale();
+    let plane_angle_scale = decoder.plane_angle_scale();
     let void_index_arc = Arc::new(void_index);
+    let material_layers = Arc::new(if split_layers {
+        build_material_layer_index(content, &mut decoder, unit_scale)
+    } else {
+        FxHashMap::default()
+    });
 
     let meshes: Vec<MeshData> = entity_jobs
         .into_par_iter()
-        .filter_map(|job| {
+        .flat_map_iter(|job| {
+            let mut job_meshes = Vec::new();
             let mut local_decoder =
                 EntityDecoder::with_arc_index(&content_arc, entity_index_arc.clone());
+            local_decoder.set_plane_angle_scale(plane_angle_scale);
 
             if let Ok(entity) = local_decoder.decode_at(job.start, job.end) {
                 // Check if entity has representation
                 let has_representation = entity.get(6).is_some_and(|a| !a.is_null());
                 if !has_representation {
-                    return None;
+                    return job_meshes;
                 }
 
-                let local_router = GeometryRouter::with_scale(unit_scale);
+                let mut local_router = GeometryRouter::with_scale(unit_scale);
+                local_router.set_texture_maps(texture_maps.clone());
+                local_router.set_material_layers(material_layers.clone());
+                local_router.set_representation_selection(selection.clone());
 
-                if let Ok(mut mesh) = local_router.process_element_with_voids(
+                if let Ok(sub_meshes) = local_router.process_element_with_layers(
                     &entity,
                     &mut local_decoder,
                     void_index_arc.as_ref(),
                 ) {
-                    if !mesh.is_empty() {
-                        if mesh.normals.is_empty() {
-                            calculate_normals(&mut mesh);
+                    for sub in sub_meshes.sub_meshes {
+                        let mut mesh = sub.mesh;
+                        if mesh.is_empty() {
+                            continue;
                         }
-
-                        let color = style_index
-                            .get(&job.id)
-                            .copied()
-                            .unwrap_or_else(|| get_default_color(&job.ifc_type));
-
-                        return Some(MeshData::new(
-                            job.id,
-                            job.ifc_type.name().to_string(),
-                            mesh.positions,
-                            mesh.normals,
-                            mesh.indices,
-                            color,
-                        ));
+                        // Weld split vertices and regenerate crease-aware normals so
+                        // extrusions, B-reps and CSG results shade consistently
+                        mesh.weld(DEFAULT_WELD_TOLERANCE);
+                        calculate_crease_normals(&mut mesh, DEFAULT_CREASE_ANGLE);
+
+                        // Layers use their own material's presentation, falling back to
+                        // the type default rather than another layer's style
+                        let material_layer_id = sub.layer_index.map(|_| sub.geometry_id);
+                        let (material, color) = if material_layer_id.is_some() {
+                            let material =
+                                sub.material_id.and_then(|id| style_index.material_presentation(id));
+                            let color = material
+                                .map(SurfaceMaterial::color)
+                                .unwrap_or_else(|| default_color(&job.ifc_type));
+                            (material, color)
+                        } else {
+                            (
+                                style_index.element_material(job.id),
+                                style_index.element_color(job.id, &job.ifc_type),
+                            )
+                        };
+
+                        let is_solid = if check_self_intersections {
+                            validate_mesh_with_intersections(&mesh).is_solid
+                        } else {
+                            mesh.is_solid()
+                        };
+                        let obb = mesh.oriented_bounding_box();
+
+                        job_meshes.push(
+                            MeshData::new(
+                                job.id,
+                                job.ifc_type.name().to_string(),
+                                mesh.positions,
+                                mesh.normals,
+                                mesh.indices,
+                                color,
+                            )
+                            .with_is_solid(is_solid)
+                            .with_obb(obb.as_ref())
+                            .with_material(material)
+                            .with_material_layer(material_layer_id)
+                            .with_uvs(mesh.uvs),
+                        );
                     }
                 }
             }
-            None
+            job_meshes
         })
         .collect();
 
@@ -172,11 +224,13 @@ pub fn process_geometry(content: &str) -> ProcessingResult {
     // Calculate stats
     let total_vertices: usize = meshes.iter().map(|m| m.vertex_count()).sum();
     let total_triangles: usize = meshes.iter().map(|m| m.triangle_count()).sum();
+    let solid_meshes = meshes.iter().filter(|m| m.is_solid).count();
 
     tracing::info!(
         meshes = meshes.len(),
         vertices = total_vertices,
         triangles = total_triangles,
+        solid_meshes = solid_meshes,
         geometry_time_ms = geometry_time.as_millis(),
         total_time_ms = total_time.as_millis(),
         "Geometry processing complete"
@@ -194,6 +248,8 @@ pub fn process_geometry(content: &str) -> ProcessingResult {
             total_meshes: meshes.len(),
             total_vertices,
             total_triangles,
+            solid_meshes,
+            non_solid_meshes: meshes.len() - solid_meshes,
             parse_time_ms: parse_time.as_millis() as u64,
             geometry_time_ms: geometry_time.as_millis() as u64,
             total_time_ms: total_time.as_millis() as u64,
@@ -201,225 +257,3 @@ pub fn process_geometry(content: &str) -> ProcessingResult {
         },
     }
 }
-
-/// OPTIMIZATION: Build both style indices in a single pass through entities.
-/// Previously, build_geometry_style_index and build_element_style_index each scanned all entities.
-/// This combined function scans once and builds both maps together, reducing I/O overhead.
-fn build_style_indices(
-    content: &str,
-    decoder: &mut EntityDecoder,
-) -> FxHashMap<u32, [f32; 4]> {
-    // Phase 1: Single scan to collect styled items and geometry-bearing elements
-    let mut geometry_styles: FxHashMap<u32, [f32; 4]> = FxHashMap::default();
-    let mut element_repr_ids: Vec<(u32, u32)> = Vec::with_capacity(2000); // (element_id, repr_id)
-    let mut scanner = EntityScanner::new(content);
-
-    while let Some((id, type_name, start, end)) = scanner.next_entity() {
-        // Collect IfcStyledItem data
-        if type_name == "IFCSTYLEDITEM" {
-            if let Ok(styled_item) = decoder.decode_at(start, end) {
-                if let Some(geometry_id) = styled_item.get_ref(0) {
-                    if !geometry_styles.contains_key(&geometry_id) {
-                        if let Some(color) = extract_color_from_styled_item(&styled_item, decoder) {
-                            geometry_styles.insert(geometry_id, color);
-                        }
-                    }
-                }
-            }
-        }
-        // Collect geometry-bearing element representation IDs
-        else if ifc_lite_core::has_geometry_by_name(type_name) {
-            if let Ok(element) = decoder.decode_at(start, end) {
-                if let Some(repr_id) = element.get_ref(6) {
-                    element_repr_ids.push((id, repr_id));
-                }
-            }
-        }
-    }
-
-    // Phase 2: Build element style index using collected data (no re-scan needed)
-    let mut element_styles: FxHashMap<u32, [f32; 4]> = FxHashMap::default();
-    for (element_id, repr_id) in element_repr_ids {
-        if let Some(color) = find_color_in_representation(repr_id, &geometry_styles, decoder) {
-            element_styles.insert(element_id, color);
-        }
-    }
-
-    element_styles
-}
-
-/// Find a color in a representation by traversing its items.
-fn find_color_in_representation(
-    repr_id: u32,
-    geometry_styles: &FxHashMap<u32, [f32; 4]>,
-    decoder: &mut EntityDecoder,
-) -> Option<[f32; 4]> {
-    // Decode the IfcProductDefinitionShape
-    let repr = decoder.decode_by_id(repr_id).ok()?;
-
-    // Attribute 2: Representations (list of IfcRepresentation)
-    let repr_list = get_refs_from_list(&repr, 2)?;
-
-    for shape_repr_id in repr_list {
-        if let Ok(shape_repr) = decoder.decode_by_id(shape_repr_id) {
-            // Attribute 3: Items (list of IfcRepresentationItem)
-            if let Some(items) = get_refs_from_list(&shape_repr, 3) {
-                for item_id in items {
-                    // Check direct style
-                    if let Some(color) = geometry_styles.get(&item_id) {
-                        return Some(*color);
-                    }
-
-                    // Check mapped items
-                    if let Ok(item) = decoder.decode_by_id(item_id) {
-                        if item.ifc_type == IfcType::IfcMappedItem {
-                            if let Some(source_id) = item.get_ref(0) {
-                                if let Ok(source) = decoder.decode_by_id(source_id) {
-                                    if let Some(mapped_repr_id) = source.get_ref(1) {
-                                        if let Some(color) = find_color_in_shape_representation(
-                                            mapped_repr_id,
-                                            geometry_styles,
-                                            decoder,
-                                        ) {
-                                            return Some(color);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    None
-}
-
-/// Find color in a shape representation.
-fn find_color_in_shape_representation(
-    repr_id: u32,
-    geometry_styles: &FxHashMap<u32, [f32; 4]>,
-    decoder: &mut EntityDecoder,
-) -> Option<[f32; 4]> {
-    let repr = decoder.decode_by_id(repr_id).ok()?;
-    let items = get_refs_from_list(&repr, 3)?;
-
-    for item_id in items {
-        if let Some(color) = geometry_styles.get(&item_id) {
-            return Some(*color);
-        }
-    }
-
-    None
-}
-
-/// Extract color from an IfcStyledItem by traversing style references.
-fn extract_color_from_styled_item(
-    styled_item: &DecodedEntity,
-    decoder: &mut EntityDecoder,
-) -> Option<[f32; 4]> {
-    // Attribute 1: Styles (list of style refs)
-    let style_refs = get_refs_from_list(styled_item, 1)?;
-
-    for style_id in style_refs {
-        if let Ok(style) = decoder.decode_by_id(style_id) {
-            // IfcPresentationStyleAssignment has Styles at attr 0
-            if let Some(inner_refs) = get_refs_from_list(&style, 0) {
-                for inner_id in inner_refs {
-                    if let Some(color) = extract_surface_style_color(inner_id, decoder) {
-                        return Some(color);
-                    }
-                }
-            }
-            // Or it might be IfcSurfaceStyle directly
-            if let Some(color) = extract_surface_style_color(style_id, decoder) {
-                return Some(color);
-            }
-        }
-    }
-
-    None
-}
-
-/// Extract color from an IfcSurfaceStyle.
-fn extract_surface_style_color(style_id: u32, decoder: &mut EntityDecoder) -> Option<[f32; 4]> {
-    let style = decoder.decode_by_id(style_id).ok()?;
-
-    // IfcSurfaceStyle: Attr 2 = Styles (list of rendering styles)
-    let rendering_refs = get_refs_from_list(&style, 2)?;
-
-    for rendering_id in rendering_refs {
-        if let Ok(rendering) = decoder.decode_by_id(rendering_id) {
-            // IfcSurfaceStyleRendering: Attr 0 = SurfaceColour
-            if let Some(color_id) = rendering.get_ref(0) {
-                if let Ok(color) = decoder.decode_by_id(color_id) {
-                    // IfcColourRgb: Attr 1 = Red, Attr 2 = Green, Attr 3 = Blue
-                    let r = color.get_float(1).unwrap_or(0.8) as f32;
-                    let g = color.get_float(2).unwrap_or(0.8) as f32;
-                    let b = color.get_float(3).unwrap_or(0.8) as f32;
-
-                    // Check for transparency (Attr 1 in IfcSurfaceStyleShading, inherited by IfcSurfaceStyleRendering)
-                    // Transparency: 0.0 = opaque, 1.0 = fully transparent
-                    // Alpha: 1.0 = opaque, 0.0 = fully transparent
-                    // So: alpha = 1.0 - transparency
-                    let alpha: f32 = 1.0 - rendering.get_float(1).unwrap_or(0.0) as f32;
-
-                    return Some([r, g, b, alpha.max(0.0).min(1.0)]);
-                }
-            }
-        }
-    }
-
-    None
-}
-
-/// Get default color based on IFC type.
-fn get_default_color(ifc_type: &IfcType) -> [f32; 4] {
-    match ifc_type {
-        // Walls - light gray
-        IfcType::IfcWall | IfcType::IfcWallStandardCase => [0.85, 0.85, 0.85, 1.0],
-
-        // Slabs - darker gray
-        IfcType::IfcSlab => [0.7, 0.7, 0.7, 1.0],
-
-        // Roofs - brown-ish
-        IfcType::IfcRoof => [0.6, 0.5, 0.4, 1.0],
-
-        // Columns/Beams - steel gray
-        IfcType::IfcColumn | IfcType::IfcBeam | IfcType::IfcMember => [0.6, 0.65, 0.7, 1.0],
-
-        // Windows - light blue transparent
-        IfcType::IfcWindow => [0.6, 0.8, 1.0, 0.4],
-
-        // Doors - wood brown
-        IfcType::IfcDoor => [0.6, 0.45, 0.3, 1.0],
-
-        // Stairs
-        IfcType::IfcStair | IfcType::IfcStairFlight => [0.75, 0.75, 0.75, 1.0],
-
-        // Railings
-        IfcType::IfcRailing => [0.4, 0.4, 0.45, 1.0],
-
-        // Plates/Coverings
-        IfcType::IfcPlate | IfcType::IfcCovering => [0.8, 0.8, 0.8, 1.0],
-
-        // Furniture
-        IfcType::IfcFurnishingElement => [0.5, 0.35, 0.2, 1.0],
-
-        // Space - cyan transparent (matches MainToolbar)
-        IfcType::IfcSpace => [0.2, 0.85, 1.0, 0.3],
-
-        // Opening elements - red-orange transparent
-        IfcType::IfcOpeningElement => [1.0, 0.42, 0.29, 0.4],
-
-        // Site - green
-        IfcType::IfcSite => [0.4, 0.8, 0.3, 1.0],
-
-        // Building element proxy - generic gray
-        IfcType::IfcBuildingElementProxy => [0.6, 0.6, 0.6, 1.0],
-
-        // Default - neutral gray
-        _ => [0.8, 0.8, 0.8, 1.0],
-    }
-}
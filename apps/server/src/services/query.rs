@@ -0,0 +1,75 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Element query indexes kept in memory per model.
+
+use ifc_lite_core::{build_entity_index, EntityDecoder, EntityIndex, ModelIndex, Query};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Model content with its query indexes.
+pub struct QueryModel {
+    content: String,
+    entity_index: Arc<EntityIndex>,
+    index: ModelIndex,
+}
+
+impl QueryModel {
+    /// Index a model for querying.
+    pub fn build(content: String) -> Self {
+        let entity_index = Arc::new(build_entity_index(&content));
+        let mut decoder = EntityDecoder::with_arc_index(&content, entity_index.clone());
+        let index = ModelIndex::build(&content, &mut decoder);
+        Self {
+            content,
+            entity_index,
+            index,
+        }
+    }
+
+    /// Run a query, returning matching express ids in ascending order.
+    pub fn execute(&self, query: &Query) -> ifc_lite_core::Result<Vec<u32>> {
+        let mut decoder = EntityDecoder::with_arc_index(&self.content, self.entity_index.clone());
+        self.index.execute(query, &mut decoder)
+    }
+
+    /// Number of indexed products.
+    pub fn product_count(&self) -> usize {
+        self.index.product_count()
+    }
+}
+
+/// Recently queried models by cache key, least recently used evicted first.
+pub struct QueryModelCache {
+    capacity: usize,
+    models: Mutex<VecDeque<(String, Arc<QueryModel>)>>,
+}
+
+impl QueryModelCache {
+    /// Create a cache holding up to `capacity` models.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            models: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Get a model, marking it as most recently used.
+    pub fn get(&self, key: &str) -> Option<Arc<QueryModel>> {
+        let mut models = self.models.lock().unwrap_or_else(|e| e.into_inner());
+        let position = models.iter().position(|(k, _)| k == key)?;
+        let entry = models.remove(position)?;
+        let model = entry.1.clone();
+        models.push_front(entry);
+        Some(model)
+    }
+
+    /// Add a model, evicting the least recently used beyond capacity.
+    pub fn insert(&self, key: String, model: Arc<QueryModel>) {
+        let mut models = self.models.lock().unwrap_or_else(|e| e.into_inner());
+        models.retain(|(k, _)| *k != key);
+        models.push_front((key, model));
+        models.truncate(self.capacity);
+    }
+}
@@ -8,8 +8,14 @@ use crate::services::cache::DiskCache;
 use crate::types::{CoordinateInfo, MeshData, ModelMetadata, ProcessingStats, StreamEvent};
 use async_stream::stream;
 use futures::Stream;
-use ifc_lite_core::{build_entity_index, DecodedEntity, EntityDecoder, EntityIndex, EntityScanner, IfcType};
-use ifc_lite_geometry::{calculate_normals, GeometryRouter};
+use ifc_lite_core::{
+    build_entity_index, ElementFilter, EntityDecoder, EntityIndex, EntityScanner, IfcType,
+};
+use ifc_lite_geometry::{
+    build_texture_map_index, calculate_crease_normals, validate_mesh_with_intersections,
+    GeometryRouter, IndexedTextureMap, RepresentationSelection, StyleIndex, DEFAULT_CREASE_ANGLE,
+    DEFAULT_WELD_TOLERANCE,
+};
 use rayon::prelude::*;
 use rustc_hash::FxHashMap;
 use std::pin::Pin;
@@ -30,37 +36,56 @@ struct EntityJob {
 struct PreparedData {
     content: Arc<String>,
     entity_index: Arc<EntityIndex>,
-    style_index: Arc<FxHashMap<u32, [f32; 4]>>,
+    style_index: Arc<StyleIndex>,
+    router_settings: Arc<RouterSettings>,
     void_index: Arc<FxHashMap<u32, Vec<u32>>>,
     jobs: Vec<EntityJob>,
     schema_version: String,
     total_entities: usize,
     parse_time_ms: u64,
+}
+
+/// Settings shared by the per-thread geometry routers (all Send-safe).
+struct RouterSettings {
     /// OPTIMIZATION: Precomputed unit scale to avoid parsing content per mesh
     unit_scale: f64,
+    /// Radians per plane angle unit, set on each per-thread decoder
+    plane_angle_scale: f64,
+    texture_maps: Arc<FxHashMap<u32, IndexedTextureMap>>,
+    representation_selection: RepresentationSelection,
+    /// Count self-intersecting meshes as non-solid
+    check_self_intersections: bool,
 }
 
-/// Extract entity references from a list attribute.
-fn get_refs_from_list(entity: &DecodedEntity, index: usize) -> Option<Vec<u32>> {
-    let list = entity.get_list(index)?;
-    let refs: Vec<u32> = list.iter().filter_map(|v| v.as_entity_ref()).collect();
-    if refs.is_empty() {
-        None
-    } else {
-        Some(refs)
+impl RouterSettings {
+    /// Create a router for one job.
+    fn router(&self) -> GeometryRouter {
+        // OPTIMIZATION: Use with_scale() instead of with_units()
+        // unit_scale is precomputed once, avoiding content parsing per mesh
+        let mut router = GeometryRouter::with_scale(self.unit_scale);
+        router.set_texture_maps(self.texture_maps.clone());
+        router.set_representation_selection(self.representation_selection.clone());
+        router
     }
 }
 
 /// Prepare all data needed for streaming (runs synchronously).
-fn prepare_streaming_data(content: String) -> PreparedData {
+fn prepare_streaming_data(
+    content: String,
+    representation_selection: RepresentationSelection,
+    filter: &ElementFilter,
+    check_self_intersections: bool,
+) -> PreparedData {
     let parse_start = std::time::Instant::now();
 
     // Build entity index
     let entity_index = Arc::new(build_entity_index(&content));
     let mut decoder = EntityDecoder::with_arc_index(&content, entity_index.clone());
 
-    // OPTIMIZATION: Build style indices in a single pass (previously two separate scans)
-    let style_index = build_style_indices(&content, &mut decoder);
+    // Resolve element styles in a single pass (styled item → material → type → default)
+    let style_index = StyleIndex::build(&content, &mut decoder);
+    let texture_maps = build_texture_map_index(&content, &mut decoder);
+    let matcher = filter.compile(&content, &mut decoder);
 
     // Collect jobs and build void index
     let mut scanner = EntityScanner::new(&content);
@@ -83,7 +108,9 @@ fn prepare_streaming_data(content: String) -> PreparedData {
             }
         }
 
-        if ifc_lite_core::has_geometry_by_name(type_name) {
+        if ifc_lite_core::has_geometry_by_name(type_name)
+            && matcher.matches_name(id, type_name)
+        {
             if let Ok(entity) = decoder.decode_at(start, end) {
                 jobs.push(EntityJob {
                     id,
@@ -105,12 +132,13 @@ fn prepare_streaming_data(content: String) -> PreparedData {
 
     // Preprocess FacetedBreps and extract unit_scale
     let router = GeometryRouter::with_units(&content, &mut decoder);
-    if !faceted_brep_ids.is_empty() {
+    if !faceted_brep_ids.is_empty() && matcher.is_all() {
         router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
     }
     // OPTIMIZATION: Extract unit_scale before dropping router
     // This allows process_batch to use with_scale() instead of with_units() per mesh
     let unit_scale = router.unit_scale();
+    let plane_angle_scale = decoder.plane_angle_scale();
     drop(router); // Explicitly drop non-Send router
 
     let parse_time_ms = parse_start.elapsed().as_millis() as u64;
@@ -119,12 +147,18 @@ fn prepare_streaming_data(content: String) -> PreparedData {
         content: Arc::new(content),
         entity_index, // Already Arc
         style_index: Arc::new(style_index),
+        router_settings: Arc::new(RouterSettings {
+            unit_scale,
+            plane_angle_scale,
+            texture_maps: Arc::new(texture_maps),
+            representation_selection,
+            check_self_intersections,
+        }),
         void_index: Arc::new(void_index),
         jobs,
         schema_version,
         total_entities,
         parse_time_ms,
-        unit_scale,
     }
 }
 
@@ -133,14 +167,15 @@ fn process_batch(
     jobs: Vec<EntityJob>,
     content: Arc<String>,
     entity_index: Arc<EntityIndex>,
-    style_index: Arc<FxHashMap<u32, [f32; 4]>>,
+    style_index: Arc<StyleIndex>,
     void_index: Arc<FxHashMap<u32, Vec<u32>>>,
-    unit_scale: f64,
+    router_settings: Arc<RouterSettings>,
 ) -> Vec<MeshData> {
     jobs.par_iter()
         .filter_map(|job| {
             let mut local_decoder =
                 EntityDecoder::with_arc_index(&content, entity_index.clone());
+            local_decoder.set_plane_angle_scale(router_settings.plane_angle_scale);
 
             if let Ok(entity) = local_decoder.decode_at(job.start, job.end) {
                 let has_representation = entity.get(6).is_some_and(|a| !a.is_null());
@@ -148,9 +183,7 @@ fn process_batch(
                     return None;
                 }
 
-                // OPTIMIZATION: Use with_scale() instead of with_units()
-                // unit_scale is precomputed once, avoiding content parsing per mesh
-                let local_router = GeometryRouter::with_scale(unit_scale);
+                let local_router = router_settings.router();
 
                 if let Ok(mut mesh) = local_router.process_element_with_voids(
                     &entity,
@@ -158,23 +191,35 @@ fn process_batch(
                     void_index.as_ref(),
                 ) {
                     if !mesh.is_empty() {
-                        if mesh.normals.is_empty() {
-                            calculate_normals(&mut mesh);
-                        }
-
-                        let color = style_index
-                            .get(&job.id)
-                            .copied()
-                            .unwrap_or_else(|| get_default_color(&job.ifc_type));
-
-                        return Some(MeshData::new(
-                            job.id,
-                            job.ifc_type.name().to_string(),
-                            mesh.positions,
-                            mesh.normals,
-                            mesh.indices,
-                            color,
-                        ));
+                        // Weld split vertices and regenerate crease-aware normals so
+                        // extrusions, B-reps and CSG results shade consistently
+                        mesh.weld(DEFAULT_WELD_TOLERANCE);
+                        calculate_crease_normals(&mut mesh, DEFAULT_CREASE_ANGLE);
+
+                        let material = style_index.element_material(job.id);
+                        let color = style_index.element_color(job.id, &job.ifc_type);
+
+                        let is_solid = if router_settings.check_self_intersections {
+                            validate_mesh_with_intersections(&mesh).is_solid
+                        } else {
+                            mesh.is_solid()
+                        };
+                        let obb = mesh.oriented_bounding_box();
+
+                        return Some(
+                            MeshData::new(
+                                job.id,
+                                job.ifc_type.name().to_string(),
+                                mesh.positions,
+                                mesh.normals,
+                                mesh.indices,
+                                color,
+                            )
+                            .with_is_solid(is_solid)
+                            .with_obb(obb.as_ref())
+                            .with_material(material)
+                            .with_uvs(mesh.uvs),
+                        );
                     }
                 }
             }
@@ -217,13 +262,21 @@ pub fn process_streaming(
     content: String,
     initial_batch_size: usize,
     max_batch_size: usize,
+    representation_selection: RepresentationSelection,
+    filter: ElementFilter,
+    check_self_intersections: bool,
 ) -> Pin<Box<dyn Stream<Item = StreamEvent> + Send>> {
     Box::pin(stream! {
         let total_start = std::time::Instant::now();
 
         // Prepare data in blocking task (all CPU-intensive work)
         let prepared = tokio::task::spawn_blocking(move || {
-            prepare_streaming_data(content)
+            prepare_streaming_data(
+                content,
+                representation_selection,
+                &filter,
+                check_self_intersections,
+            )
         }).await;
 
         let prepared = match prepared {
@@ -254,6 +307,7 @@ pub fn process_streaming(
         let mut all_meshes: Vec<MeshData> = Vec::new();
         let mut total_vertices = 0usize;
         let mut total_triangles = 0usize;
+        let mut solid_meshes = 0usize;
 
         // PIPELINED BATCH PROCESSING: Process multiple batches concurrently
         // Pipeline depth: more batches in flight = better CPU utilization
@@ -292,13 +346,15 @@ pub fn process_streaming(
                 let index_bg = prepared.entity_index.clone();
                 let void_bg = prepared.void_index.clone();
                 let style_bg = prepared.style_index.clone();
-                let unit_scale = prepared.unit_scale;
+                let settings_bg = prepared.router_settings.clone();
                 let tx_clone = tx.clone();
 
                 // Spawn batch processing task
                 tokio::spawn(async move {
                     let result = tokio::task::spawn_blocking(move || {
-                        process_batch(chunk_vec, content_bg, index_bg, style_bg, void_bg, unit_scale)
+                        process_batch(
+                            chunk_vec, content_bg, index_bg, style_bg, void_bg, settings_bg,
+                        )
                     }).await;
                     
                     let batch_result = match result {
@@ -334,6 +390,9 @@ pub fn process_streaming(
                 for mesh in &meshes {
                     total_vertices += mesh.vertex_count();
                     total_triangles += mesh.triangle_count();
+                    if mesh.is_solid {
+                        solid_meshes += 1;
+                    }
                 }
 
                 if !meshes.is_empty() {
@@ -372,6 +431,8 @@ pub fn process_streaming(
                 total_meshes: all_meshes.len(),
                 total_vertices,
                 total_triangles,
+                solid_meshes,
+                non_solid_meshes: all_meshes.len() - solid_meshes,
                 parse_time_ms: prepared.parse_time_ms,
                 geometry_time_ms: total_time.as_millis() as u64 - prepared.parse_time_ms,
                 total_time_ms: total_time.as_millis() as u64,
@@ -387,178 +448,3 @@ pub fn process_streaming(
         };
     })
 }
-
-// Helper functions for style extraction
-
-/// OPTIMIZATION: Build both style indices in a single pass through entities.
-/// Previously, build_geometry_style_index and build_element_style_index each scanned all entities.
-/// This combined function scans once and builds both maps together, reducing I/O overhead.
-fn build_style_indices(
-    content: &str,
-    decoder: &mut EntityDecoder,
-) -> FxHashMap<u32, [f32; 4]> {
-    // Phase 1: Single scan to collect styled items and geometry-bearing elements
-    let mut geometry_styles: FxHashMap<u32, [f32; 4]> = FxHashMap::default();
-    let mut element_repr_ids: Vec<(u32, u32)> = Vec::with_capacity(2000); // (element_id, repr_id)
-    let mut scanner = EntityScanner::new(content);
-
-    while let Some((id, type_name, start, end)) = scanner.next_entity() {
-        // Collect IfcStyledItem data
-        if type_name == "IFCSTYLEDITEM" {
-            if let Ok(styled_item) = decoder.decode_at(start, end) {
-                if let Some(geometry_id) = styled_item.get_ref(0) {
-                    if !geometry_styles.contains_key(&geometry_id) {
-                        if let Some(color) = extract_color_from_styled_item(&styled_item, decoder) {
-                            geometry_styles.insert(geometry_id, color);
-                        }
-                    }
-                }
-            }
-        }
-        // Collect geometry-bearing element representation IDs
-        else if ifc_lite_core::has_geometry_by_name(type_name) {
-            if let Ok(element) = decoder.decode_at(start, end) {
-                if let Some(repr_id) = element.get_ref(6) {
-                    element_repr_ids.push((id, repr_id));
-                }
-            }
-        }
-    }
-
-    // Phase 2: Build element style index using collected data (no re-scan needed)
-    let mut element_styles: FxHashMap<u32, [f32; 4]> = FxHashMap::default();
-    for (element_id, repr_id) in element_repr_ids {
-        if let Some(color) = find_color_in_representation(repr_id, &geometry_styles, decoder) {
-            element_styles.insert(element_id, color);
-        }
-    }
-
-    element_styles
-}
-
-fn find_color_in_representation(
-    repr_id: u32,
-    geometry_styles: &FxHashMap<u32, [f32; 4]>,
-    decoder: &mut EntityDecoder,
-) -> Option<[f32; 4]> {
-    let repr = decoder.decode_by_id(repr_id).ok()?;
-    let repr_list = get_refs_from_list(&repr, 2)?;
-
-    for shape_repr_id in repr_list {
-        if let Ok(shape_repr) = decoder.decode_by_id(shape_repr_id) {
-            if let Some(items) = get_refs_from_list(&shape_repr, 3) {
-                for item_id in items {
-                    if let Some(color) = geometry_styles.get(&item_id) {
-                        return Some(*color);
-                    }
-
-                    if let Ok(item) = decoder.decode_by_id(item_id) {
-                        if item.ifc_type == IfcType::IfcMappedItem {
-                            if let Some(source_id) = item.get_ref(0) {
-                                if let Ok(source) = decoder.decode_by_id(source_id) {
-                                    if let Some(mapped_repr_id) = source.get_ref(1) {
-                                        if let Some(color) = find_color_in_shape_representation(
-                                            mapped_repr_id,
-                                            geometry_styles,
-                                            decoder,
-                                        ) {
-                                            return Some(color);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    None
-}
-
-fn find_color_in_shape_representation(
-    repr_id: u32,
-    geometry_styles: &FxHashMap<u32, [f32; 4]>,
-    decoder: &mut EntityDecoder,
-) -> Option<[f32; 4]> {
-    let repr = decoder.decode_by_id(repr_id).ok()?;
-    let items = get_refs_from_list(&repr, 3)?;
-
-    for item_id in items {
-        if let Some(color) = geometry_styles.get(&item_id) {
-            return Some(*color);
-        }
-    }
-
-    None
-}
-
-fn extract_color_from_styled_item(
-    styled_item: &DecodedEntity,
-    decoder: &mut EntityDecoder,
-) -> Option<[f32; 4]> {
-    let style_refs = get_refs_from_list(styled_item, 1)?;
-
-    for style_id in style_refs {
-        if let Ok(style) = decoder.decode_by_id(style_id) {
-            if let Some(inner_refs) = get_refs_from_list(&style, 0) {
-                for inner_id in inner_refs {
-                    if let Some(color) = extract_surface_style_color(inner_id, decoder) {
-                        return Some(color);
-                    }
-                }
-            }
-            if let Some(color) = extract_surface_style_color(style_id, decoder) {
-                return Some(color);
-            }
-        }
-    }
-
-    None
-}
-
-fn extract_surface_style_color(style_id: u32, decoder: &mut EntityDecoder) -> Option<[f32; 4]> {
-    let style = decoder.decode_by_id(style_id).ok()?;
-    let rendering_refs = get_refs_from_list(&style, 2)?;
-
-    for rendering_id in rendering_refs {
-        if let Ok(rendering) = decoder.decode_by_id(rendering_id) {
-            if let Some(color_id) = rendering.get_ref(0) {
-                if let Ok(color) = decoder.decode_by_id(color_id) {
-                    let r = color.get_float(1).unwrap_or(0.8) as f32;
-                    let g = color.get_float(2).unwrap_or(0.8) as f32;
-                    let b = color.get_float(3).unwrap_or(0.8) as f32;
-                    let alpha: f32 = 1.0 - rendering.get_float(8).unwrap_or(0.0) as f32;
-
-                    return Some([r, g, b, alpha.max(0.0).min(1.0)]);
-                }
-            }
-        }
-    }
-
-    None
-}
-
-fn get_default_color(ifc_type: &IfcType) -> [f32; 4] {
-    match ifc_type {
-        IfcType::IfcWall | IfcType::IfcWallStandardCase => [0.85, 0.85, 0.85, 1.0],
-        IfcType::IfcSlab => [0.7, 0.7, 0.7, 1.0],
-        IfcType::IfcRoof => [0.6, 0.5, 0.4, 1.0],
-        IfcType::IfcColumn | IfcType::IfcBeam | IfcType::IfcMember => [0.6, 0.65, 0.7, 1.0],
-        IfcType::IfcWindow => [0.6, 0.8, 1.0, 0.4],
-        IfcType::IfcDoor => [0.6, 0.45, 0.3, 1.0],
-        IfcType::IfcStair | IfcType::IfcStairFlight => [0.75, 0.75, 0.75, 1.0],
-        IfcType::IfcRailing => [0.4, 0.4, 0.45, 1.0],
-        IfcType::IfcPlate | IfcType::IfcCovering => [0.8, 0.8, 0.8, 1.0],
-        IfcType::IfcFurnishingElement => [0.5, 0.35, 0.2, 1.0],
-        // Space - cyan transparent (matches MainToolbar)
-        IfcType::IfcSpace => [0.2, 0.85, 1.0, 0.3],
-        // Opening elements - red-orange transparent
-        IfcType::IfcOpeningElement => [1.0, 0.42, 0.29, 0.4],
-        // Site - green
-        IfcType::IfcSite => [0.4, 0.8, 0.3, 1.0],
-        IfcType::IfcBuildingElementProxy => [0.6, 0.6, 0.6, 1.0],
-        _ => [0.8, 0.8, 0.8, 1.0],
-    }
-}
@@ -0,0 +1,158 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! 3D Tiles export service - spatially tiled glTF for city-scale streaming.
+
+use crate::types::TilesOptions;
+use ifc_lite_core::{
+    ElementFilter, EntityDecoder, EntityScanner, GeoRefExtractor, GeoReference, IfcType,
+    UnitContext,
+};
+use ifc_lite_geometry::{
+    detect_rtc_offset, georeferenced_transform, mesh_elements_local_with_rtc, Matrix4,
+    RepresentationSelection, Tileset, TilesetBuilder, TilesetOptions, Vector3,
+};
+
+/// Generated tileset with export details.
+pub struct GeneratedTileset {
+    pub tileset: Tileset,
+    pub element_count: usize,
+    /// Whether a root transform onto the globe was written.
+    pub georeferenced: bool,
+}
+
+/// Partition an IFC model into an OGC 3D Tiles tileset.
+///
+/// The root transform comes from the model's `IfcMapConversion`; for CRSs
+/// other than WGS 84 UTM the origin's latitude and longitude must be given.
+/// Only elements passing `filter` are tiled. Far-away models are meshed
+/// relative to their RTC offset, which is added back in f64 before tiling.
+pub fn generate_tileset(
+    content: &str,
+    selection: &RepresentationSelection,
+    filter: &ElementFilter,
+    options: &TilesOptions,
+) -> GeneratedTileset {
+    let start = std::time::Instant::now();
+
+    let mut decoder = EntityDecoder::new(content);
+    let matcher = filter.compile(content, &mut decoder);
+    let rtc = detect_rtc_offset(content);
+    let elements = mesh_elements_local_with_rtc(content, selection, &matcher, &rtc);
+    let rtc_translation = Matrix4::new_translation(&Vector3::new(rtc.x, rtc.y, rtc.z));
+
+    // Georeferencing entities
+    let mut scanner = EntityScanner::new(content);
+    let mut georef_entities: Vec<(u32, IfcType)> = Vec::new();
+    while let Some((id, type_name, _, _)) = scanner.next_entity() {
+        if matches!(
+            type_name,
+            "IFCMAPCONVERSION" | "IFCPROJECTEDCRS" | "IFCPROPERTYSET"
+        ) {
+            georef_entities.push((id, IfcType::from_str(type_name)));
+        }
+    }
+    let unit_scale = UnitContext::from_content(content, &mut decoder).length_scale();
+
+    let mut builder = TilesetBuilder::with_options(TilesetOptions {
+        max_elements: options.max_elements.unwrap_or(250),
+        ..Default::default()
+    });
+
+    let origin = options.latitude.zip(options.longitude);
+    let georef = GeoRefExtractor::extract(&mut decoder, &georef_entities)
+        .ok()
+        .flatten()
+        // Without a map conversion, model units map to meters unscaled
+        .or_else(|| {
+            origin.map(|_| GeoReference {
+                scale: unit_scale,
+                ..GeoReference::new()
+            })
+        });
+    let transform = georef.and_then(|georef| georeferenced_transform(&georef, unit_scale, origin));
+    if let Some(transform) = transform {
+        builder.set_transform(transform);
+    }
+
+    for element in elements {
+        builder.add(
+            element.info,
+            &element.mesh,
+            &(rtc_translation * element.transform),
+            element.color,
+        );
+    }
+    let tileset = builder.build();
+
+    tracing::info!(
+        elements = builder.element_count(),
+        tiles = tileset.contents.len(),
+        georeferenced = transform.is_some(),
+        time_ms = start.elapsed().as_millis(),
+        "3D Tiles export complete"
+    );
+
+    GeneratedTileset {
+        tileset,
+        element_count: builder.element_count(),
+        georeferenced: transform.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wall with an opening on Swiss LV95 coordinates.
+    const FAR_WALL: &str = r#"ISO-10303-21;
+HEADER;
+FILE_SCHEMA(('IFC4'));
+ENDSEC;
+DATA;
+#1=IFCCARTESIANPOINT((0.,0.,0.));
+#2=IFCDIRECTION((0.,0.,1.));
+#3=IFCDIRECTION((1.,0.,0.));
+#4=IFCAXIS2PLACEMENT3D(#1,#2,#3);
+#5=IFCCARTESIANPOINT((2600000.3,1200000.7,0.));
+#6=IFCAXIS2PLACEMENT3D(#5,#2,#3);
+#7=IFCLOCALPLACEMENT($,#6);
+#8=IFCRECTANGLEPROFILEDEF(.AREA.,$,$,4.,0.2);
+#9=IFCEXTRUDEDAREASOLID(#8,#4,#2,3.);
+#10=IFCSHAPEREPRESENTATION($,'Body','SweptSolid',(#9));
+#11=IFCPRODUCTDEFINITIONSHAPE($,$,(#10));
+#12=IFCWALL('2O2Fr$t4X7Zf8NOew3FLOH',$,'W1',$,$,#7,#11,$,$);
+#13=IFCLOCALPLACEMENT(#7,#4);
+#14=IFCRECTANGLEPROFILEDEF(.AREA.,$,$,1.,1.);
+#15=IFCEXTRUDEDAREASOLID(#14,#4,#2,2.);
+#16=IFCSHAPEREPRESENTATION($,'Body','SweptSolid',(#15));
+#17=IFCPRODUCTDEFINITIONSHAPE($,$,(#16));
+#18=IFCOPENINGELEMENT('2O2Fr$t4X7Zf8NOew3FLOI',$,'O1',$,$,#13,#17,$,$);
+#19=IFCRELVOIDSELEMENT('2O2Fr$t4X7Zf8NOew3FLOJ',$,$,$,#12,#18);
+ENDSEC;
+END-ISO-10303-21;
+"#;
+
+    #[test]
+    fn test_far_wall_with_opening_keeps_precision() {
+        let generated = generate_tileset(
+            FAR_WALL,
+            &RepresentationSelection::default(),
+            &ElementFilter::default(),
+            &TilesOptions::default(),
+        );
+        // The opening element is tiled as well
+        assert_eq!(generated.element_count, 2);
+
+        // Elements with openings are meshed in model coordinates; without the
+        // RTC offset the wall's x extent would snap to the 0.25 m f32 grid
+        let json: serde_json::Value = serde_json::from_str(&generated.tileset.json).unwrap();
+        let bbox = json["root"]["boundingVolume"]["box"].as_array().unwrap();
+        let bbox: Vec<f64> = bbox.iter().map(|v| v.as_f64().unwrap()).collect();
+        assert!((bbox[0] - 2_600_000.3).abs() < 1e-3, "{}", bbox[0]);
+        assert!((bbox[1] - 1_200_000.7).abs() < 1e-3, "{}", bbox[1]);
+        assert!((bbox[2] - 1.5).abs() < 1e-3, "{}", bbox[2]);
+        assert!((bbox[3] - 2.0).abs() < 1e-3, "{}", bbox[3]);
+    }
+}
@@ -4,6 +4,7 @@
 
 //! Mesh data types for serialization.
 
+use ifc_lite_geometry::{OrientedBoundingBox, Point3, SurfaceMaterial, SurfaceSide, Vector3};
 use serde::{Deserialize, Serialize};
 
 /// Individual mesh data with geometry and metadata.
@@ -21,6 +22,106 @@ pub struct MeshData {
     pub indices: Vec<u32>,
     /// RGBA color [r, g, b, a] in 0-1 range.
     pub color: [f32; 4],
+    /// Whether the mesh is a closed, manifold, consistently wound solid.
+    #[serde(default)]
+    pub is_solid: bool,
+    /// Oriented bounding box in the same (Z-up) frame as `positions`.
+    #[serde(default)]
+    pub obb: Option<MeshObb>,
+    /// Surface material from the element's IfcSurfaceStyle, if styled.
+    #[serde(default)]
+    pub material: Option<MeshMaterial>,
+    /// Texture coordinates (u, v pairs), empty for untextured meshes.
+    #[serde(default)]
+    pub uvs: Vec<f32>,
+    /// IfcMaterialLayer this mesh was cut from, when layers are split.
+    #[serde(default)]
+    pub material_layer_id: Option<u32>,
+}
+
+/// Surface material properties beyond the base color.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeshMaterial {
+    /// IfcSurfaceStyle name.
+    pub name: Option<String>,
+    /// Specular color [r, g, b] in 0-1 range, if authored.
+    pub specular_color: Option<[f32; 3]>,
+    /// PBR roughness approximation in 0-1 range.
+    pub roughness: f32,
+    /// PBR metallic approximation in 0-1 range.
+    pub metallic: f32,
+    /// IFC reflectance method in lowercase (e.g. "metal", "notdefined").
+    pub reflectance_method: String,
+    /// Whether both sides of the surface should be rendered.
+    pub double_sided: bool,
+    /// URL of the first image texture, if any.
+    pub texture_url: Option<String>,
+}
+
+impl From<&SurfaceMaterial> for MeshMaterial {
+    fn from(material: &SurfaceMaterial) -> Self {
+        Self {
+            name: material.name.clone(),
+            specular_color: material.specular_color,
+            roughness: material.roughness(),
+            metallic: material.metallic(),
+            reflectance_method: material.reflectance_method.as_str().to_string(),
+            double_sided: material.side == SurfaceSide::Both,
+            texture_url: material.texture_url().map(str::to_string),
+        }
+    }
+}
+
+/// Oriented bounding box of a mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MeshObb {
+    /// Box center.
+    pub center: [f32; 3],
+    /// Half the box size along each axis, longest first.
+    pub half_extents: [f32; 3],
+    /// Orthonormal, right-handed box axes.
+    pub axes: [[f32; 3]; 3],
+}
+
+impl MeshObb {
+    /// Convert to Y-up (new Y = old Z, new Z = -old Y) as
+    /// `[cx, cy, cz, hx, hy, hz, qx, qy, qz, qw]` with a rotation quaternion.
+    pub fn to_y_up(self) -> [f32; 10] {
+        let y_up = |v: [f32; 3]| Vector3::new(v[0] as f64, v[2] as f64, -v[1] as f64);
+        let obb = OrientedBoundingBox {
+            center: Point3::from(y_up(self.center)),
+            axes: [y_up(self.axes[0]), y_up(self.axes[1]), y_up(self.axes[2])],
+            half_extents: Vector3::new(
+                self.half_extents[0] as f64,
+                self.half_extents[1] as f64,
+                self.half_extents[2] as f64,
+            ),
+        };
+        let q = obb.rotation();
+        [
+            self.center[0],
+            self.center[2],
+            -self.center[1],
+            self.half_extents[0],
+            self.half_extents[1],
+            self.half_extents[2],
+            q.i as f32,
+            q.j as f32,
+            q.k as f32,
+            q.w as f32,
+        ]
+    }
+}
+
+impl From<&OrientedBoundingBox> for MeshObb {
+    fn from(obb: &OrientedBoundingBox) -> Self {
+        let v = |v: &Vector3<f64>| [v.x as f32, v.y as f32, v.z as f32];
+        Self {
+            center: [obb.center.x as f32, obb.center.y as f32, obb.center.z as f32],
+            half_extents: v(&obb.half_extents),
+            axes: [v(&obb.axes[0]), v(&obb.axes[1]), v(&obb.axes[2])],
+        }
+    }
 }
 
 impl MeshData {
@@ -40,9 +141,51 @@ impl MeshData {
             normals,
             indices,
             color,
+            is_solid: false,
+            obb: None,
+            material: None,
+            uvs: Vec::new(),
+            material_layer_id: None,
         }
     }
 
+    /// Set the solid flag from a mesh validation result.
+    pub fn with_is_solid(mut self, is_solid: bool) -> Self {
+        self.is_solid = is_solid;
+        self
+    }
+
+    /// Set the oriented bounding box.
+    pub fn with_obb(mut self, obb: Option<&OrientedBoundingBox>) -> Self {
+        self.obb = obb.map(MeshObb::from);
+        self
+    }
+
+    /// Set the surface material.
+    pub fn with_material(mut self, material: Option<&SurfaceMaterial>) -> Self {
+        self.material = material.map(MeshMaterial::from);
+        self
+    }
+
+    /// Set texture coordinates; ignored unless there is one pair per vertex.
+    pub fn with_uvs(mut self, uvs: Vec<f32>) -> Self {
+        if !uvs.is_empty() && uvs.len() / 2 == self.vertex_count() {
+            self.uvs = uvs;
+        }
+        self
+    }
+
+    /// Set the material layer the mesh belongs to.
+    pub fn with_material_layer(mut self, material_layer_id: Option<u32>) -> Self {
+        self.material_layer_id = material_layer_id;
+        self
+    }
+
+    /// Check if the mesh has texture coordinates.
+    pub fn has_uvs(&self) -> bool {
+        !self.uvs.is_empty()
+    }
+
     /// Get the number of vertices.
     pub fn vertex_count(&self) -> usize {
         self.positions.len() / 3
@@ -8,8 +8,9 @@ mod mesh;
 mod request;
 mod response;
 
-pub use mesh::MeshData;
-pub use request::ParseOptions;
+pub use mesh::{MeshData, MeshMaterial, MeshObb};
+pub use request::{DrawingOptions, ParseOptions, QueryOptions, TilesOptions};
 pub use response::{
-    CoordinateInfo, MetadataResponse, ModelMetadata, ParseResponse, ProcessingStats, StreamEvent,
+    CoordinateInfo, MetadataResponse, ModelMetadata, ParseResponse, ProcessingStats, QueryResponse,
+    StreamEvent, TilesetResponse,
 };
@@ -4,7 +4,10 @@
 
 //! Request types for the API.
 
+use ifc_lite_core::ElementFilter;
+use ifc_lite_geometry::RepresentationSelection;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 
 /// Options for parsing requests.
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -16,4 +19,183 @@ pub struct ParseOptions {
     /// Batch size for streaming responses.
     #[serde(default)]
     pub batch_size: Option<usize>,
+
+    /// Representation identifiers to mesh in preference order,
+    /// e.g. `Axis,Body` (default: solid body geometry). An entry can require a
+    /// sub-context TargetView after `@`, e.g. `FootPrint@PLAN_VIEW,Body`.
+    #[serde(default)]
+    pub representations: Option<String>,
+
+    /// Comma-separated IFC types to mesh, subtypes included, e.g. `IfcWall,IfcSlab`.
+    #[serde(default)]
+    pub types: Option<String>,
+
+    /// Comma-separated IFC types to skip, subtypes included.
+    #[serde(default)]
+    pub exclude_types: Option<String>,
+
+    /// Comma-separated GlobalIds to mesh.
+    #[serde(default)]
+    pub global_ids: Option<String>,
+
+    /// Comma-separated storeys to mesh, by name, GlobalId or `#id`.
+    #[serde(default)]
+    pub storey: Option<String>,
+
+    /// Semicolon-separated property predicates, e.g. `Pset_WallCommon.IsExternal=true`.
+    #[serde(default)]
+    pub property: Option<String>,
+
+    /// Split walls and slabs with a material layer set usage into one mesh per layer.
+    #[serde(default)]
+    pub split_layers: bool,
+
+    /// Add computed volume, area and bounding box quantities (`Qto_Computed`)
+    /// to the data model. Meshes every element, so off by default.
+    #[serde(default)]
+    pub computed_quantities: bool,
+
+    /// Also count self-intersecting meshes as non-solid. Tests nearby triangle
+    /// pairs of every mesh, so off by default.
+    #[serde(default)]
+    pub check_self_intersections: bool,
+}
+
+impl ParseOptions {
+    /// Representation selection requested by the client.
+    pub fn representation_selection(&self) -> RepresentationSelection {
+        self.representations
+            .as_deref()
+            .map(RepresentationSelection::parse)
+            .unwrap_or_default()
+    }
+
+    /// Cache key for a file processed with these options.
+    ///
+    /// The default selection without a filter keeps the plain content hash, so
+    /// clients can look it up from the file hash alone. Routes append a
+    /// versioned suffix (`-parquet-v3`, `-datamodel-v3`, ...) that is bumped
+    /// whenever their output changes, which invalidates older entries.
+    pub fn cache_key(&self, content_key: String) -> String {
+        let selection = self.representation_selection();
+        let mut key = if selection.is_default() {
+            content_key
+        } else {
+            format!("{}-repr-{}", content_key, selection)
+        };
+        if let Some(filter) = self.filter_description() {
+            let digest = hex::encode(Sha256::digest(filter.as_bytes()));
+            key = format!("{}-filter-{}", key, &digest[..16]);
+        }
+        if self.split_layers {
+            key.push_str("-layers");
+        }
+        if self.computed_quantities {
+            key.push_str("-qto");
+        }
+        if self.check_self_intersections {
+            key.push_str("-selfint");
+        }
+        key
+    }
+
+    /// Element filter requested by the client.
+    pub fn element_filter(&self) -> Result<ElementFilter, String> {
+        let filter = ElementFilter::new()
+            .with_types(split_list(&self.types, ','))
+            .and_then(|f| f.with_excluded_types(split_list(&self.exclude_types, ',')))
+            .and_then(|f| f.with_properties(split_list(&self.property, ';')))
+            .map_err(|e| e.to_string())?;
+        Ok(filter
+            .with_global_ids(split_list(&self.global_ids, ','))
+            .with_storeys(split_list(&self.storey, ',')))
+    }
+
+    /// Filter parameters as given, or `None` if no filter was requested.
+    fn filter_description(&self) -> Option<String> {
+        let parts = [
+            &self.types,
+            &self.exclude_types,
+            &self.global_ids,
+            &self.storey,
+            &self.property,
+        ];
+        parts
+            .iter()
+            .any(|p| p.as_deref().is_some_and(|p| !p.trim().is_empty()))
+            .then(|| {
+                parts
+                    .iter()
+                    .map(|p| p.as_deref().unwrap_or_default().trim())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+    }
+}
+
+fn split_list(value: &Option<String>, separator: char) -> Vec<&str> {
+    value
+        .as_deref()
+        .map(|v| v.split(separator).map(str::trim).filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// Options for 2D drawing export.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DrawingOptions {
+    /// Output format: `svg` (default) or `dxf`.
+    #[serde(default)]
+    pub format: Option<String>,
+
+    /// `plan` (default) or a facade elevation: `north`, `south`, `east`, `west`.
+    #[serde(default)]
+    pub view: Option<String>,
+
+    /// Storey to draw, by name, GlobalId or express id (default: lowest storey).
+    #[serde(default)]
+    pub storey: Option<String>,
+
+    /// Absolute cut elevation in meters, overriding `storey`.
+    #[serde(default)]
+    pub elevation: Option<f64>,
+
+    /// Cut height above the storey elevation in meters (default: 1.2).
+    #[serde(default)]
+    pub cut_height: Option<f64>,
+
+    /// How far below the cut plane elements are projected, in meters (default: 1.2).
+    #[serde(default)]
+    pub view_depth: Option<f64>,
+
+    /// SVG scale denominator (default: 100 for 1:100).
+    #[serde(default)]
+    pub scale: Option<f64>,
+
+    /// Hatch cut polygons in DXF output (default: true).
+    #[serde(default)]
+    pub hatch: Option<bool>,
+}
+
+/// Options for element queries.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct QueryOptions {
+    /// Query text, e.g. `IfcDoor where storey = "Level 2" and Pset_DoorCommon.FireRating = EI30`.
+    #[serde(default)]
+    pub q: String,
+}
+
+/// Options for 3D Tiles export.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TilesOptions {
+    /// Maximum number of elements per tile (default: 250).
+    #[serde(default)]
+    pub max_elements: Option<usize>,
+
+    /// Latitude of the map conversion origin in degrees, for CRSs other than WGS 84 UTM.
+    #[serde(default)]
+    pub latitude: Option<f64>,
+
+    /// Longitude of the map conversion origin in degrees, for CRSs other than WGS 84 UTM.
+    #[serde(default)]
+    pub longitude: Option<f64>,
 }
@@ -51,6 +51,13 @@ pub struct ProcessingStats {
     pub total_vertices: usize,
     /// Total number of triangles.
     pub total_triangles: usize,
+    /// Number of meshes that are closed, manifold solids.
+    #[serde(default)]
+    pub solid_meshes: usize,
+    /// Number of meshes with open edges, non-manifold edges or flipped faces,
+    /// or crossing themselves when `check_self_intersections` is set.
+    #[serde(default)]
+    pub non_solid_meshes: usize,
     /// Time spent parsing entities (ms).
     pub parse_time_ms: u64,
     /// Time spent processing geometry (ms).
@@ -118,3 +125,33 @@ pub enum StreamEvent {
         message: String,
     },
 }
+
+/// 3D Tiles export result; tiles are served from the cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TilesetResponse {
+    /// Cache key of the tileset.
+    pub cache_key: String,
+    /// URL of `tileset.json`, relative to the server root.
+    pub tileset_url: String,
+    /// Number of tile content files.
+    pub tile_count: usize,
+    /// Number of elements in the tileset.
+    pub element_count: usize,
+    /// Whether the root transform places the model on the globe.
+    pub georeferenced: bool,
+}
+
+/// Element query result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryResponse {
+    /// Cache key of the indexed model, for `GET /api/v1/query/:cache_key`.
+    pub cache_key: String,
+    /// Query text as received.
+    pub query: String,
+    /// Number of matching elements.
+    pub count: usize,
+    /// Number of products in the model.
+    pub product_count: usize,
+    /// Express ids of matching elements, ascending.
+    pub ids: Vec<u32>,
+}
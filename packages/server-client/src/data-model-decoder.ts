@@ -6,6 +6,7 @@
  * Decode Parquet-encoded data model from server.
  */
 
+import type { Table } from 'apache-arrow';
 import { ensureParquetInit } from './parquet-decoder';
 
 export interface EntityMetadata {
@@ -20,8 +21,22 @@ export interface EntityMetadata {
 
 export interface Property {
   property_name: string;
+  /** JSON-encoded value; scalar, array or object depending on property_kind */
   property_value: string;
+  /** Value kind: 'real', 'integer', 'boolean', 'logical', 'label', 'identifier', 'measure', 'date_time', 'mixed', 'reference', 'object' or 'null' */
   property_type: string;
+  /** 'single', 'enumerated', 'bounded', 'list', 'table', 'reference' or 'complex' */
+  property_kind: string;
+  /** IFC measure type of the value (e.g. 'IFCLENGTHMEASURE') */
+  measure_type?: string;
+  /** Unit label: the property's own Unit, else the project unit for the measure type */
+  unit?: string;
+  /** Typed nominal value (single) or set point (bounded) */
+  value?: number | boolean | string;
+  /** Typed enumerated, list or table values */
+  values?: number[] | string[];
+  lower_bound?: number;
+  upper_bound?: number;
 }
 
 export interface PropertySet {
@@ -34,12 +49,16 @@ export interface Quantity {
   quantity_name: string;
   quantity_value: number;
   quantity_type: string;
+  /** 'authored' (IfcElementQuantity), 'profile' (exact extrusion) or 'mesh' (tessellated geometry) */
+  source: string;
 }
 
 export interface QuantitySet {
   qset_id: number;
   qset_name: string;
   method_of_measurement?: string;
+  /** Element the set was computed for (computed sets only) */
+  element_id?: number;
   quantities: Quantity[];
 }
 
@@ -49,6 +68,135 @@ export interface Relationship {
   related_id: number;
 }
 
+/** Property of an element after type-object inheritance; the value is in propertySets */
+export interface ElementProperty {
+  element_id: number;
+  pset_id: number;
+  property_name: string;
+  /** 'occurrence' or 'type' (inherited through IfcRelDefinesByType) */
+  source: string;
+}
+
+/** One material part (layer, profile, constituent or material) of an element */
+export interface ElementMaterial {
+  element_id: number;
+  /** 'occurrence' or 'type' (inherited through IfcRelDefinesByType) */
+  source: string;
+  /** Associated IfcMaterialSelect entity */
+  association_id: number;
+  /** 'material', 'material_list', 'layer_set', 'layer_set_usage', 'profile_set', 'profile_set_usage', 'constituent_set', ... */
+  association_type: string;
+  set_id?: number;
+  set_name?: string;
+  layer_set_direction?: string;
+  direction_sense?: string;
+  /** Metres */
+  offset_from_reference_line?: number;
+  cardinal_point?: number;
+  part_index: number;
+  /** 'material', 'layer', 'profile' or 'constituent' */
+  part_kind: string;
+  part_id?: number;
+  part_name?: string;
+  /** Absent for air gaps */
+  material_id?: number;
+  material_name?: string;
+  material_category?: string;
+  /** Layer thickness in metres */
+  thickness?: number;
+  /** Signed distance of the layer's first face from the reference line, in metres */
+  layer_offset?: number;
+  /** Share of the element volume, for quantity take-off by material */
+  fraction?: number;
+  profile_id?: number;
+  is_ventilated?: boolean;
+  priority?: number;
+}
+
+/** Classification code of an element (Uniclass, OmniClass, NL-SfB, ...) */
+export interface ElementClassification {
+  element_id: number;
+  /** 'occurrence' or 'type'; own codes replace the type's code from the same system */
+  source: string;
+  /** IfcClassificationReference (or IfcClassification) entity */
+  reference_id: number;
+  /** Code, e.g. 'Ss_25_10_30' */
+  identification?: string;
+  name?: string;
+  location?: string;
+  description?: string;
+  /** Codes of the parent references, from the top of the tree down */
+  parents: string[];
+  classification_id?: number;
+  /** Classification system name, e.g. 'Uniclass 2015' */
+  classification_name?: string;
+  classification_source?: string;
+  edition?: string;
+  edition_date?: string;
+}
+
+/** Document associated with an element */
+export interface ElementDocument {
+  element_id: number;
+  /** 'occurrence' or 'type' (inherited through IfcRelDefinesByType) */
+  source: string;
+  /** IfcDocumentReference (or IfcDocumentInformation) entity */
+  reference_id: number;
+  identification?: string;
+  name?: string;
+  location?: string;
+  description?: string;
+  /** IfcDocumentInformation entity */
+  document_id?: number;
+  document_identification?: string;
+  document_name?: string;
+  document_description?: string;
+  document_location?: string;
+  purpose?: string;
+  intended_use?: string;
+  revision?: string;
+  /** 'DRAFT', 'FINALDRAFT', 'FINAL', 'REVISION' or 'NOTDEFINED' */
+  status?: string;
+}
+
+/** Group or system (IfcGroup subtype) with its members */
+export interface SystemGroup {
+  group_id: number;
+  /** e.g. 'IFCDISTRIBUTIONSYSTEM', 'IFCBUILDINGSYSTEM', 'IFCZONE' */
+  type_name: string;
+  name?: string;
+  long_name?: string;
+  /** e.g. 'HEATING', 'VENTILATION' */
+  predefined_type?: string;
+  /** Elements, ports, spaces or nested groups */
+  member_ids: number[];
+  /** Buildings or other spatial structure serviced by the system */
+  serviced_ids: number[];
+}
+
+/** Distribution port of an element */
+export interface DistributionPort {
+  port_id: number;
+  element_id?: number;
+  name?: string;
+  /** 'SOURCE', 'SINK', 'SOURCEANDSINK' or 'NOTDEFINED' */
+  flow_direction: string;
+  predefined_type?: string;
+  system_type?: string;
+}
+
+/** Connection between two ports, with the flow direction between their elements where known */
+export interface PortConnection {
+  connection_id: number;
+  relating_port_id: number;
+  related_port_id: number;
+  realizing_element_id?: number;
+  relating_element_id?: number;
+  related_element_id?: number;
+  upstream_element_id?: number;
+  downstream_element_id?: number;
+}
+
 export interface SpatialNode {
   entity_id: number;
   parent_id: number;
@@ -59,6 +207,8 @@ export interface SpatialNode {
   elevation?: number;
   children_ids: number[];
   element_ids: number[];
+  /** Elements referenced in (not contained in) this structure, e.g. columns spanning storeys */
+  referenced_element_ids: number[];
 }
 
 export interface SpatialHierarchy {
@@ -68,6 +218,13 @@ export interface SpatialHierarchy {
   element_to_building: Map<number, number>;
   element_to_site: Map<number, number>;
   element_to_space: Map<number, number>;
+  /** Any IfcFacility subtype: buildings, bridges, roads, railways, marine facilities */
+  element_to_facility: Map<number, number>;
+  element_to_facility_part: Map<number, number>;
+  /** IfcZone groups and IfcSpatialZone containment/references; an element may be in several zones */
+  element_to_zones: Map<number, number[]>;
+  /** Spatial structures an element is referenced in (IfcRelReferencedInSpatialStructure) */
+  element_references: Map<number, number[]>;
 }
 
 export interface DataModel {
@@ -76,6 +233,19 @@ export interface DataModel {
   quantitySets: Map<number, QuantitySet>;
   relationships: Relationship[];
   spatialHierarchy: SpatialHierarchy;
+  /** Effective properties per element (occurrence overrides type) */
+  elementProperties: Map<number, ElementProperty[]>;
+  /** Materials per element (own association, else the type's) */
+  materials: Map<number, ElementMaterial[]>;
+  /** Classification codes per element */
+  classifications: Map<number, ElementClassification[]>;
+  /** Documents per element */
+  documents: Map<number, ElementDocument[]>;
+  /** Groups and systems by group id */
+  groups: Map<number, SystemGroup>;
+  /** Distribution ports by port id */
+  ports: Map<number, DistributionPort>;
+  portConnections: PortConnection[];
 }
 
 /**
@@ -85,7 +255,8 @@ export interface DataModel {
  * Arrow's .get(i) is slow for strings (offset lookup + UTF-8 decode per call).
  * toArray() decodes all strings in one pass which is 10-20x faster for large datasets.
  *
- * Format: [entities_len][entities_data][properties_len][properties_data][quantities_len][quantities_data][relationships_len][relationships_data][spatial_len][spatial_data]
+ * Format: [entities_len][entities_data][properties_len][properties_data][quantities_len][quantities_data][relationships_len][relationships_data][spatial_len][spatial_data][element_properties_len][element_properties_data][materials_len][materials_data][classifications_len][classifications_data][documents_len][documents_data][groups_len][groups_data][ports_len][ports_data][port_connections_len][port_connections_data]
+ * The sections after the spatial hierarchy are optional (absent in older cached responses).
  */
 export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
   // Initialize WASM module (only runs once)
@@ -124,6 +295,65 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
   const spatialLen = view.getUint32(offset, true);
   offset += 4;
   const spatialData = new Uint8Array(data, offset, spatialLen);
+  offset += spatialLen;
+
+  // Read element properties Parquet section (optional)
+  let elementPropertiesData: Uint8Array | undefined;
+  if (offset + 4 <= data.byteLength) {
+    const elementPropertiesLen = view.getUint32(offset, true);
+    offset += 4;
+    elementPropertiesData = new Uint8Array(data, offset, elementPropertiesLen);
+    offset += elementPropertiesLen;
+  }
+
+  // Read materials Parquet section (optional)
+  let materialsData: Uint8Array | undefined;
+  if (offset + 4 <= data.byteLength) {
+    const materialsLen = view.getUint32(offset, true);
+    offset += 4;
+    materialsData = new Uint8Array(data, offset, materialsLen);
+    offset += materialsLen;
+  }
+
+  // Read classifications Parquet section (optional)
+  let classificationsData: Uint8Array | undefined;
+  if (offset + 4 <= data.byteLength) {
+    const classificationsLen = view.getUint32(offset, true);
+    offset += 4;
+    classificationsData = new Uint8Array(data, offset, classificationsLen);
+    offset += classificationsLen;
+  }
+
+  // Read documents Parquet section (optional)
+  let documentsData: Uint8Array | undefined;
+  if (offset + 4 <= data.byteLength) {
+    const documentsLen = view.getUint32(offset, true);
+    offset += 4;
+    documentsData = new Uint8Array(data, offset, documentsLen);
+    offset += documentsLen;
+  }
+
+  // Read groups, ports and port connections Parquet sections (optional)
+  let groupsData: Uint8Array | undefined;
+  if (offset + 4 <= data.byteLength) {
+    const groupsLen = view.getUint32(offset, true);
+    offset += 4;
+    groupsData = new Uint8Array(data, offset, groupsLen);
+    offset += groupsLen;
+  }
+  let portsData: Uint8Array | undefined;
+  if (offset + 4 <= data.byteLength) {
+    const portsLen = view.getUint32(offset, true);
+    offset += 4;
+    portsData = new Uint8Array(data, offset, portsLen);
+    offset += portsLen;
+  }
+  let portConnectionsData: Uint8Array | undefined;
+  if (offset + 4 <= data.byteLength) {
+    const portConnectionsLen = view.getUint32(offset, true);
+    offset += 4;
+    portConnectionsData = new Uint8Array(data, offset, portConnectionsLen);
+  }
 
   // Parse Parquet tables
   // @ts-ignore - parquet-wasm API
@@ -174,6 +404,24 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
   const propertyNamesArr = propertiesArrow.getChild('property_name')?.toArray() as string[];
   const propertyValuesArr = propertiesArrow.getChild('property_value')?.toArray() as string[];
   const propertyTypesArr = propertiesArrow.getChild('property_type')?.toArray() as string[];
+  const propertyKindsArr = propertiesArrow.getChild('property_kind')?.toArray() as string[] | undefined;
+  const measureTypesArr = propertiesArrow.getChild('measure_type')?.toArray() as (string | null)[] | undefined;
+  const unitsArr = propertiesArrow.getChild('unit')?.toArray() as (string | null)[] | undefined;
+  const valueRealCol = propertiesArrow.getChild('value_real');
+  const valueIntegerCol = propertiesArrow.getChild('value_integer');
+  const valueBooleanCol = propertiesArrow.getChild('value_boolean');
+  const valueTextCol = propertiesArrow.getChild('value_text');
+  const valuesRealCol = propertiesArrow.getChild('values_real');
+  const valuesTextCol = propertiesArrow.getChild('values_text');
+  const lowerBoundCol = propertiesArrow.getChild('lower_bound');
+  const upperBoundCol = propertiesArrow.getChild('upper_bound');
+
+  // Int64 columns decode as BigInt
+  const typedPropertyValue = (i: number): number | boolean | string | undefined => {
+    const integer = valueIntegerCol?.get(i);
+    if (integer !== null && integer !== undefined) return Number(integer);
+    return valueRealCol?.get(i) ?? valueBooleanCol?.get(i) ?? valueTextCol?.get(i) ?? undefined;
+  };
 
   const propertySets = new Map<number, PropertySet>();
   for (let i = 0; i < psetIds.length; i++) {
@@ -190,6 +438,17 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
       property_name: propertyNamesArr[i] ?? '',
       property_value: propertyValuesArr[i] ?? '',
       property_type: propertyTypesArr[i] ?? '',
+      property_kind: propertyKindsArr?.[i] ?? 'single',
+      measure_type: measureTypesArr?.[i] || undefined,
+      unit: unitsArr?.[i] || undefined,
+      value: typedPropertyValue(i),
+      values: valuesRealCol?.get(i)
+        ? [...(valuesRealCol.get(i).toArray() as Float64Array)]
+        : valuesTextCol?.get(i)
+          ? [...(valuesTextCol.get(i).toArray() as string[])]
+          : undefined,
+      lower_bound: lowerBoundCol?.get(i) ?? undefined,
+      upper_bound: upperBoundCol?.get(i) ?? undefined,
     });
   }
 
@@ -206,6 +465,8 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
   const quantityNamesArr = quantitiesArrow.getChild('quantity_name')?.toArray() as string[];
   const quantityValuesArr = quantitiesArrow.getChild('quantity_value')?.toArray() as Float64Array;
   const quantityTypesArr = quantitiesArrow.getChild('quantity_type')?.toArray() as string[];
+  const elementIdsCol = quantitiesArrow.getChild('element_id');
+  const sourcesArr = quantitiesArrow.getChild('source')?.toArray() as string[] | undefined;
 
   const quantitySets = new Map<number, QuantitySet>();
   for (let i = 0; i < qsetIds.length; i++) {
@@ -215,6 +476,7 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
         qset_id: qsetId,
         qset_name: qsetNamesArr[i] ?? '',
         method_of_measurement: methodsArr[i] || undefined,
+        element_id: elementIdsCol?.get(i) ?? undefined,
         quantities: [],
       });
     }
@@ -223,6 +485,7 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
       quantity_name: quantityNamesArr[i] ?? '',
       quantity_value: quantityValuesArr[i] ?? 0,
       quantity_type: quantityTypesArr[i] ?? '',
+      source: sourcesArr?.[i] ?? 'authored',
     });
   }
 
@@ -272,8 +535,18 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
   const elementToSpaceData = new Uint8Array(spatialData.buffer, spatialData.byteOffset + spatialOffset, elementToSpaceLen);
   spatialOffset += elementToSpaceLen;
 
-  // Read project_id (final u32)
+  // Read project_id (final u32 of the original layout)
   const projectId = spatialView.getUint32(spatialOffset, true);
+  spatialOffset += 4;
+
+  // Read facility, zone and reference lookup tables (optional, absent in older cached responses)
+  const optionalLookups: Uint8Array[] = [];
+  while (spatialOffset + 4 <= spatialData.byteLength) {
+    const lookupLen = spatialView.getUint32(spatialOffset, true);
+    spatialOffset += 4;
+    optionalLookups.push(new Uint8Array(spatialData.buffer, spatialData.byteOffset + spatialOffset, lookupLen));
+    spatialOffset += lookupLen;
+  }
 
   // OPTIMIZED: Parse nodes Parquet table with bulk array extraction
   // @ts-ignore
@@ -291,6 +564,7 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
   const elevationsArr = nodesArrow.getChild('elevation')?.toArray() as (number | null)[];
   const childrenIdsList = nodesArrow.getChild('children_ids');
   const elementIdsList = nodesArrow.getChild('element_ids');
+  const referencedIdsList = nodesArrow.getChild('referenced_element_ids');
 
   // Pre-allocate array for spatial nodes
   const nodeCount = spatialEntityIds.length;
@@ -316,6 +590,9 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
       }
     }
 
+    const referencedVector = referencedIdsList?.get(i);
+    const referencedIds: number[] = referencedVector ? [...(referencedVector.toArray() as Uint32Array)] : [];
+
     spatialNodes[i] = {
       entity_id: spatialEntityIds[i],
       parent_id: parentIdsArr[i] ?? 0,
@@ -326,6 +603,7 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
       elevation: elevationsArr[i] ?? undefined,
       children_ids: childrenIds,
       element_ids: elementIds,
+      referenced_element_ids: referencedIds,
     };
   }
 
@@ -353,11 +631,246 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
     parseLookupTable(elementToSpaceData),
   ];
 
+  // Zone and reference tables can hold several rows per element
+  const parseMultiLookupTable = (tableData: Uint8Array | undefined): Map<number, number[]> => {
+    const map = new Map<number, number[]>();
+    if (!tableData) return map;
+    // @ts-ignore
+    const table = parquet.readParquet(tableData);
+    // @ts-ignore
+    const arrowTable = arrow.tableFromIPC(table.intoIPCStream());
+    const elemIds = arrowTable.getChild('element_id')?.toArray() as Uint32Array;
+    const spatIds = arrowTable.getChild('spatial_id')?.toArray() as Uint32Array;
+    for (let i = 0; i < elemIds.length; i++) {
+      const list = map.get(elemIds[i]);
+      if (list) {
+        list.push(spatIds[i]);
+      } else {
+        map.set(elemIds[i], [spatIds[i]]);
+      }
+    }
+    return map;
+  };
+
+  const [elementToFacilityData, elementToFacilityPartData, elementToZoneData, elementReferencesData] = optionalLookups;
+  const elementToFacility = elementToFacilityData ? parseLookupTable(elementToFacilityData) : new Map<number, number>();
+  const elementToFacilityPart = elementToFacilityPartData ? parseLookupTable(elementToFacilityPartData) : new Map<number, number>();
+  const elementToZones = parseMultiLookupTable(elementToZoneData);
+  const elementReferences = parseMultiLookupTable(elementReferencesData);
+
+  const elementProperties = new Map<number, ElementProperty[]>();
+  if (elementPropertiesData) {
+    // @ts-ignore
+    const elementPropertiesTable = parquet.readParquet(elementPropertiesData);
+    // @ts-ignore
+    const elementPropertiesArrow = arrow.tableFromIPC(elementPropertiesTable.intoIPCStream());
+    const elemIds = elementPropertiesArrow.getChild('element_id')?.toArray() as Uint32Array;
+    const elemPsetIds = elementPropertiesArrow.getChild('pset_id')?.toArray() as Uint32Array;
+    const elemPropertyNames = elementPropertiesArrow.getChild('property_name')?.toArray() as string[];
+    const elemSources = elementPropertiesArrow.getChild('source')?.toArray() as string[];
+    for (let i = 0; i < elemIds.length; i++) {
+      let list = elementProperties.get(elemIds[i]);
+      if (!list) {
+        list = [];
+        elementProperties.set(elemIds[i], list);
+      }
+      list.push({
+        element_id: elemIds[i],
+        pset_id: elemPsetIds[i],
+        property_name: elemPropertyNames[i] ?? '',
+        source: elemSources[i] ?? 'occurrence',
+      });
+    }
+  }
+
+  const materials = new Map<number, ElementMaterial[]>();
+  if (materialsData) {
+    // @ts-ignore
+    const materialsTable = parquet.readParquet(materialsData);
+    // @ts-ignore
+    const materialsArrow = arrow.tableFromIPC(materialsTable.intoIPCStream());
+    const { column, optionalNumber, optionalString } = nullableColumns(materialsArrow);
+    const materialElementIds = column('element_id')?.toArray() as Uint32Array;
+    for (let i = 0; i < materialElementIds.length; i++) {
+      let list = materials.get(materialElementIds[i]);
+      if (!list) {
+        list = [];
+        materials.set(materialElementIds[i], list);
+      }
+      list.push({
+        element_id: materialElementIds[i],
+        source: optionalString('source', i) ?? 'occurrence',
+        association_id: optionalNumber('association_id', i) ?? 0,
+        association_type: optionalString('association_type', i) ?? '',
+        set_id: optionalNumber('set_id', i),
+        set_name: optionalString('set_name', i),
+        layer_set_direction: optionalString('layer_set_direction', i),
+        direction_sense: optionalString('direction_sense', i),
+        offset_from_reference_line: optionalNumber('offset_from_reference_line', i),
+        cardinal_point: optionalNumber('cardinal_point', i),
+        part_index: optionalNumber('part_index', i) ?? 0,
+        part_kind: optionalString('part_kind', i) ?? 'material',
+        part_id: optionalNumber('part_id', i),
+        part_name: optionalString('part_name', i),
+        material_id: optionalNumber('material_id', i),
+        material_name: optionalString('material_name', i),
+        material_category: optionalString('material_category', i),
+        thickness: optionalNumber('thickness', i),
+        layer_offset: optionalNumber('layer_offset', i),
+        fraction: optionalNumber('fraction', i),
+        profile_id: optionalNumber('profile_id', i),
+        is_ventilated: column('is_ventilated')?.get(i) ?? undefined,
+        priority: optionalNumber('priority', i),
+      });
+    }
+  }
+
+  const classifications = new Map<number, ElementClassification[]>();
+  if (classificationsData) {
+    // @ts-ignore
+    const classificationsTable = parquet.readParquet(classificationsData);
+    // @ts-ignore
+    const classificationsArrow = arrow.tableFromIPC(classificationsTable.intoIPCStream());
+    const { column, optionalNumber, optionalString } = nullableColumns(classificationsArrow);
+    const classificationElementIds = column('element_id')?.toArray() as Uint32Array;
+    for (let i = 0; i < classificationElementIds.length; i++) {
+      let list = classifications.get(classificationElementIds[i]);
+      if (!list) {
+        list = [];
+        classifications.set(classificationElementIds[i], list);
+      }
+      const parents = column('parents')?.get(i);
+      list.push({
+        element_id: classificationElementIds[i],
+        source: optionalString('source', i) ?? 'occurrence',
+        reference_id: optionalNumber('reference_id', i) ?? 0,
+        identification: optionalString('identification', i),
+        name: optionalString('name', i),
+        location: optionalString('location', i),
+        description: optionalString('description', i),
+        parents: parents ? [...(parents.toArray() as string[])] : [],
+        classification_id: optionalNumber('classification_id', i),
+        classification_name: optionalString('classification_name', i),
+        classification_source: optionalString('classification_source', i),
+        edition: optionalString('edition', i),
+        edition_date: optionalString('edition_date', i),
+      });
+    }
+  }
+
+  const documents = new Map<number, ElementDocument[]>();
+  if (documentsData) {
+    // @ts-ignore
+    const documentsTable = parquet.readParquet(documentsData);
+    // @ts-ignore
+    const documentsArrow = arrow.tableFromIPC(documentsTable.intoIPCStream());
+    const { column, optionalNumber, optionalString } = nullableColumns(documentsArrow);
+    const documentElementIds = column('element_id')?.toArray() as Uint32Array;
+    for (let i = 0; i < documentElementIds.length; i++) {
+      let list = documents.get(documentElementIds[i]);
+      if (!list) {
+        list = [];
+        documents.set(documentElementIds[i], list);
+      }
+      list.push({
+        element_id: documentElementIds[i],
+        source: optionalString('source', i) ?? 'occurrence',
+        reference_id: optionalNumber('reference_id', i) ?? 0,
+        identification: optionalString('identification', i),
+        name: optionalString('name', i),
+        location: optionalString('location', i),
+        description: optionalString('description', i),
+        document_id: optionalNumber('document_id', i),
+        document_identification: optionalString('document_identification', i),
+        document_name: optionalString('document_name', i),
+        document_description: optionalString('document_description', i),
+        document_location: optionalString('document_location', i),
+        purpose: optionalString('purpose', i),
+        intended_use: optionalString('intended_use', i),
+        revision: optionalString('revision', i),
+        status: optionalString('status', i),
+      });
+    }
+  }
+
+  const idList = (vector: { toArray(): Uint32Array } | null | undefined): number[] =>
+    vector ? [...vector.toArray()] : [];
+
+  const groups = new Map<number, SystemGroup>();
+  if (groupsData) {
+    // @ts-ignore
+    const groupsTable = parquet.readParquet(groupsData);
+    // @ts-ignore
+    const groupsArrow = arrow.tableFromIPC(groupsTable.intoIPCStream());
+    const { column, optionalString } = nullableColumns(groupsArrow);
+    const groupIds = column('group_id')?.toArray() as Uint32Array;
+    for (let i = 0; i < groupIds.length; i++) {
+      groups.set(groupIds[i], {
+        group_id: groupIds[i],
+        type_name: optionalString('type_name', i) ?? '',
+        name: optionalString('name', i),
+        long_name: optionalString('long_name', i),
+        predefined_type: optionalString('predefined_type', i),
+        member_ids: idList(column('member_ids')?.get(i)),
+        serviced_ids: idList(column('serviced_ids')?.get(i)),
+      });
+    }
+  }
+
+  const ports = new Map<number, DistributionPort>();
+  if (portsData) {
+    // @ts-ignore
+    const portsTable = parquet.readParquet(portsData);
+    // @ts-ignore
+    const portsArrow = arrow.tableFromIPC(portsTable.intoIPCStream());
+    const { column, optionalNumber, optionalString } = nullableColumns(portsArrow);
+    const portIds = column('port_id')?.toArray() as Uint32Array;
+    for (let i = 0; i < portIds.length; i++) {
+      ports.set(portIds[i], {
+        port_id: portIds[i],
+        element_id: optionalNumber('element_id', i),
+        name: optionalString('name', i),
+        flow_direction: optionalString('flow_direction', i) ?? 'NOTDEFINED',
+        predefined_type: optionalString('predefined_type', i),
+        system_type: optionalString('system_type', i),
+      });
+    }
+  }
+
+  const portConnections: PortConnection[] = [];
+  if (portConnectionsData) {
+    // @ts-ignore
+    const portConnectionsTable = parquet.readParquet(portConnectionsData);
+    // @ts-ignore
+    const portConnectionsArrow = arrow.tableFromIPC(portConnectionsTable.intoIPCStream());
+    const { column, optionalNumber } = nullableColumns(portConnectionsArrow);
+    const connectionIds = column('connection_id')?.toArray() as Uint32Array;
+    for (let i = 0; i < connectionIds.length; i++) {
+      portConnections.push({
+        connection_id: connectionIds[i],
+        relating_port_id: optionalNumber('relating_port_id', i) ?? 0,
+        related_port_id: optionalNumber('related_port_id', i) ?? 0,
+        realizing_element_id: optionalNumber('realizing_element_id', i),
+        relating_element_id: optionalNumber('relating_element_id', i),
+        related_element_id: optionalNumber('related_element_id', i),
+        upstream_element_id: optionalNumber('upstream_element_id', i),
+        downstream_element_id: optionalNumber('downstream_element_id', i),
+      });
+    }
+  }
+
   return {
     entities,
     propertySets,
     quantitySets,
     relationships,
+    elementProperties,
+    materials,
+    classifications,
+    documents,
+    groups,
+    ports,
+    portConnections,
     spatialHierarchy: {
       nodes: spatialNodes,
       project_id: projectId,
@@ -365,6 +878,61 @@ export async function decodeDataModel(data: ArrayBuffer): Promise<DataModel> {
       element_to_building: elementToBuilding,
       element_to_site: elementToSite,
       element_to_space: elementToSpace,
+      element_to_facility: elementToFacility,
+      element_to_facility_part: elementToFacilityPart,
+      element_to_zones: elementToZones,
+      element_references: elementReferences,
     },
   };
 }
+
+/**
+ * Elements reached from an element by following port connections in flow direction.
+ *
+ * Only connections with a known direction (a SOURCE port connected to a SINK port) are followed.
+ * Results are in breadth-first order and exclude the start element.
+ */
+export function traceFlow(model: DataModel, elementId: number, direction: 'upstream' | 'downstream'): number[] {
+  const edges = new Map<number, number[]>();
+  for (const connection of model.portConnections) {
+    const { upstream_element_id: upstream, downstream_element_id: downstream } = connection;
+    if (upstream === undefined || downstream === undefined) continue;
+    const [from, to] = direction === 'downstream' ? [upstream, downstream] : [downstream, upstream];
+    const list = edges.get(from);
+    if (list) {
+      list.push(to);
+    } else {
+      edges.set(from, [to]);
+    }
+  }
+
+  const visited = new Set<number>([elementId]);
+  const queue = [elementId];
+  const reached: number[] = [];
+  while (queue.length > 0) {
+    const current = queue.shift()!;
+    for (const next of edges.get(current) ?? []) {
+      if (!visited.has(next)) {
+        visited.add(next);
+        reached.push(next);
+        queue.push(next);
+      }
+    }
+  }
+  return reached;
+}
+
+/**
+ * Per-row readers for a table with nullable columns.
+ *
+ * Nullable numeric columns are read with .get(i) so that nulls stay distinguishable.
+ */
+function nullableColumns(table: Table) {
+  const column = (name: string) => table.getChild(name);
+  const optionalNumber = (name: string, i: number): number | undefined => {
+    const value = column(name)?.get(i);
+    return value === null || value === undefined ? undefined : Number(value);
+  };
+  const optionalString = (name: string, i: number): string | undefined => column(name)?.get(i) ?? undefined;
+  return { column, optionalNumber, optionalString };
+}
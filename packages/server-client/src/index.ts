@@ -31,4 +31,4 @@
 export * from './client';
 export * from './types';
 export { decodeParquetGeometry, decodeOptimizedParquetGeometry, isParquetAvailable } from './parquet-decoder';
-export { decodeDataModel, type DataModel, type Quantity, type QuantitySet } from './data-model-decoder';
+export { decodeDataModel, traceFlow, type DataModel, type DistributionPort, type ElementClassification, type ElementDocument, type ElementMaterial, type ElementProperty, type PortConnection, type Quantity, type QuantitySet, type SystemGroup } from './data-model-decoder';
@@ -184,6 +184,20 @@ export async function decodeParquetGeometry(data: ArrayBuffer): Promise<MeshData
   const colorG = meshArrow.getChild('color_g')?.toArray() as Float32Array;
   const colorB = meshArrow.getChild('color_b')?.toArray() as Float32Array;
   const colorA = meshArrow.getChild('color_a')?.toArray() as Float32Array;
+  const obbColumns = ['obb_cx', 'obb_cy', 'obb_cz', 'obb_hx', 'obb_hy', 'obb_hz', 'obb_qx', 'obb_qy', 'obb_qz', 'obb_qw']
+    .map((name) => meshArrow.getChild(name)?.toArray() as Float32Array | undefined);
+  const hasObb = obbColumns.every((column) => column !== undefined);
+  // Material columns are nullable (unstyled meshes), so read them with get()
+  const roughness = meshArrow.getChild('roughness');
+  const metallic = meshArrow.getChild('metallic');
+  const specularR = meshArrow.getChild('specular_r');
+  const specularG = meshArrow.getChild('specular_g');
+  const specularB = meshArrow.getChild('specular_b');
+  const reflectanceMethods = meshArrow.getChild('reflectance_method');
+  const doubleSided = meshArrow.getChild('double_sided');
+  const textureUrls = meshArrow.getChild('texture_url');
+  // Only set on per-layer meshes (split_layers)
+  const materialLayerIds = meshArrow.getChild('material_layer_id');
 
   // Extract columns from vertex table
   const posX = vertexArrow.getChild('x')?.toArray() as Float32Array;
@@ -192,6 +206,9 @@ export async function decodeParquetGeometry(data: ArrayBuffer): Promise<MeshData
   const normX = vertexArrow.getChild('nx')?.toArray() as Float32Array;
   const normY = vertexArrow.getChild('ny')?.toArray() as Float32Array;
   const normZ = vertexArrow.getChild('nz')?.toArray() as Float32Array;
+  // Texture coordinates are only present when at least one mesh is textured
+  const texU = vertexArrow.getChild('u')?.toArray() as Float32Array | undefined;
+  const texV = vertexArrow.getChild('v')?.toArray() as Float32Array | undefined;
 
   // Extract columns from index table
   const idx0 = indexArrow.getChild('i0')?.toArray() as Uint32Array;
@@ -248,6 +265,42 @@ export async function decodeParquetGeometry(data: ArrayBuffer): Promise<MeshData
       indices: indices as any,
       color: [colorR[i], colorG[i], colorB[i], colorA[i]],
     };
+
+    if (hasObb) {
+      const [cx, cy, cz, hx, hy, hz, qx, qy, qz, qw] = obbColumns.map((column) => column![i]);
+      meshes[i].obb = {
+        center: [cx, cy, cz],
+        half_extents: [hx, hy, hz],
+        rotation: [qx, qy, qz, qw],
+      };
+    }
+
+    if (roughness && roughness.get(i) != null) {
+      const specular = specularR?.get(i);
+      meshes[i].material = {
+        roughness: roughness.get(i),
+        metallic: metallic?.get(i) ?? 0,
+        specular_color: specular != null ? [specular, specularG!.get(i), specularB!.get(i)] : null,
+        reflectance_method: reflectanceMethods?.get(i) ?? 'notdefined',
+        double_sided: doubleSided?.get(i) ?? true,
+        texture_url: textureUrls?.get(i) ?? null,
+      };
+    }
+
+    const materialLayerId = materialLayerIds?.get(i);
+    if (materialLayerId != null) {
+      meshes[i].material_layer_id = materialLayerId;
+    }
+
+    if (texU && texV && meshes[i].material?.texture_url) {
+      const uvs = new Float32Array(vertexCount * 2);
+      for (let v = 0; v < vertexCount; v++) {
+        const srcIdx = vertexStart + v;
+        uvs[v * 2] = texU[srcIdx];
+        uvs[v * 2 + 1] = texV[srcIdx];
+      }
+      meshes[i].uvs = uvs as any;
+    }
   }
 
   return meshes;
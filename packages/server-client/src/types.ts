@@ -28,6 +28,46 @@ export interface MeshData {
   indices: number[];
   /** RGBA color [r, g, b, a] in 0-1 range */
   color: [number, number, number, number];
+  /** Oriented bounding box (Y-up), when provided by the server */
+  obb?: OrientedBoundingBox;
+  /** Surface material from the element's IfcSurfaceStyle, if styled */
+  material?: MeshMaterial;
+  /** Texture coordinates as flat array (u, v pairs), if textured */
+  uvs?: number[];
+  /** IfcMaterialLayer this mesh was cut from, when parsed with split_layers */
+  material_layer_id?: number | null;
+}
+
+/**
+ * Surface material properties beyond the base color.
+ */
+export interface MeshMaterial {
+  /** IfcSurfaceStyle name */
+  name?: string | null;
+  /** Specular color [r, g, b] in 0-1 range, if authored */
+  specular_color?: [number, number, number] | null;
+  /** PBR roughness approximation in 0-1 range */
+  roughness: number;
+  /** PBR metallic approximation in 0-1 range */
+  metallic: number;
+  /** IFC reflectance method in lowercase (e.g. "metal", "notdefined") */
+  reflectance_method: string;
+  /** Whether both sides of the surface should be rendered */
+  double_sided: boolean;
+  /** URL of the first image texture, if any */
+  texture_url?: string | null;
+}
+
+/**
+ * Oriented bounding box of a mesh.
+ */
+export interface OrientedBoundingBox {
+  /** Box center [x, y, z] */
+  center: [number, number, number];
+  /** Half the box size along each local axis, longest first */
+  half_extents: [number, number, number];
+  /** Rotation from box-local to world as a quaternion [x, y, z, w] */
+  rotation: [number, number, number, number];
 }
 
 /**
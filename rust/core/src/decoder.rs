@@ -93,6 +93,8 @@ pub struct EntityDecoder<'a> {
     /// Cache of cartesian point coordinates for FacetedBrep optimization
     /// Only populated when using get_polyloop_coords_cached
     point_cache: FxHashMap<u32, (f64, f64, f64)>,
+    /// Radians per plane angle unit of the file (1.0 unless set from the project units)
+    plane_angle_scale: f64,
 }
 
 impl<'a> EntityDecoder<'a> {
@@ -103,6 +105,7 @@ impl<'a> EntityDecoder<'a> {
             cache: FxHashMap::default(),
             entity_index: None,
             point_cache: FxHashMap::default(),
+            plane_angle_scale: 1.0,
         }
     }
 
@@ -113,6 +116,7 @@ impl<'a> EntityDecoder<'a> {
             cache: FxHashMap::default(),
             entity_index: Some(Arc::new(index)),
             point_cache: FxHashMap::default(),
+            plane_angle_scale: 1.0,
         }
     }
 
@@ -123,6 +127,7 @@ impl<'a> EntityDecoder<'a> {
             cache: FxHashMap::default(),
             entity_index: Some(index),
             point_cache: FxHashMap::default(),
+            plane_angle_scale: 1.0,
         }
     }
 
@@ -243,6 +248,20 @@ impl<'a> EntityDecoder<'a> {
         self.cache.len()
     }
 
+    /// Radians per unit of the file's plane angle measures
+    ///
+    /// Geometry processors multiply angles (revolution angles, conic trim
+    /// parameters) by this. Defaults to 1.0 (radians, the IFC default unit).
+    #[inline]
+    pub fn plane_angle_scale(&self) -> f64 {
+        self.plane_angle_scale
+    }
+
+    /// Set the plane angle unit, usually from [`crate::UnitContext::plane_angle_scale`]
+    pub fn set_plane_angle_scale(&mut self, scale: f64) {
+        self.plane_angle_scale = scale;
+    }
+
     /// Get raw bytes for an entity (for direct/fast parsing)
     /// Returns the full entity line including type and attributes
     #[inline]
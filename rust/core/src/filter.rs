@@ -0,0 +1,663 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Element Selection Filters
+//!
+//! Declarative filters that pick which products get geometry. A filter
+//! combines IFC types (subtypes included), GlobalIds, containing storeys and
+//! property predicates; all given criteria must hold. Compiling a filter
+//! resolves storeys and properties with a single relationship scan so
+//! geometry processing only needs a cheap per-element check.
+//!
+//! ```rust,ignore
+//! let filter = ElementFilter::new()
+//!     .with_types(["IfcWall", "IfcSlab"])?
+//!     .with_storeys(["Level 1"])
+//!     .with_properties(["Pset_WallCommon.IsExternal=true"])?;
+//! let matcher = filter.compile(content, &mut decoder);
+//! if matcher.matches(id, ifc_type) { /* mesh it */ }
+//! ```
+
+use crate::decoder::EntityDecoder;
+use crate::error::{Error, Result};
+use crate::generated::{has_geometry_by_name, IfcType};
+use crate::parser::EntityScanner;
+use crate::properties::PropertyResolver;
+use crate::schema_gen::{AttributeValue, DecodedEntity};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Comparison of a property predicate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyOp {
+    /// Property is present
+    Exists,
+    /// `=`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `~`: text contains, case-insensitive
+    Contains,
+}
+
+/// Condition on a property or quantity value
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyPredicate {
+    /// Property set or quantity set name; any set if `None`
+    pub pset: Option<String>,
+    /// Property or quantity name
+    pub property: String,
+    /// Comparison
+    pub op: PropertyOp,
+    /// Value to compare with (unused for `Exists`)
+    pub value: String,
+}
+
+impl PropertyPredicate {
+    /// Parse `[Pset.]Property[op value]` with op one of `= != < <= > >= ~`
+    ///
+    /// Names and text values compare case-insensitively; values may be quoted.
+    pub fn parse(s: &str) -> Result<Self> {
+        let s = s.trim();
+        // Two-character operators first
+        let ops = [
+            ("!=", PropertyOp::Ne),
+            ("<=", PropertyOp::Le),
+            (">=", PropertyOp::Ge),
+            ("=", PropertyOp::Eq),
+            ("<", PropertyOp::Lt),
+            (">", PropertyOp::Gt),
+            ("~", PropertyOp::Contains),
+        ];
+        let found = ops
+            .iter()
+            .filter_map(|(token, op)| s.find(token).map(|pos| (pos, *token, *op)))
+            .min_by_key(|(pos, token, _)| (*pos, std::cmp::Reverse(token.len())));
+
+        let (name, op, value) = match found {
+            Some((pos, token, op)) => (&s[..pos], op, s[pos + token.len()..].trim()),
+            None => (s, PropertyOp::Exists, ""),
+        };
+        let value = value
+            .strip_prefix(['\'', '"'])
+            .and_then(|v| v.strip_suffix(['\'', '"']))
+            .unwrap_or(value);
+
+        let name = name.trim();
+        let (pset, property) = match name.split_once('.') {
+            Some((pset, property)) => (Some(pset.trim().to_string()), property.trim()),
+            None => (None, name),
+        };
+        if property.is_empty() || pset.as_deref() == Some("") {
+            return Err(Error::parse(
+                0,
+                format!("Invalid property predicate: {}", s),
+            ));
+        }
+
+        Ok(Self {
+            pset,
+            property: property.to_string(),
+            op,
+            value: value.to_string(),
+        })
+    }
+
+    /// Check a property value
+    pub(crate) fn test(&self, value: &PropertyValue) -> bool {
+        compare(self.op, &self.value, value)
+    }
+
+    pub(crate) fn names_match(&self, pset: &str, property: &str) -> bool {
+        self.property.eq_ignore_ascii_case(property)
+            && self
+                .pset
+                .as_deref()
+                .is_none_or(|p| p.eq_ignore_ascii_case(pset))
+    }
+}
+
+/// Compare a value with the text of a predicate
+pub(crate) fn compare(op: PropertyOp, expected: &str, value: &PropertyValue) -> bool {
+    use std::cmp::Ordering;
+
+    let ordering = match (value, expected.trim().parse::<f64>()) {
+        (PropertyValue::Number(n), Ok(expected)) => n.partial_cmp(&expected),
+        (PropertyValue::Bool(b), _) => parse_bool(expected).map(|expected| b.cmp(&expected)),
+        (PropertyValue::Text(t), _) => Some(t.to_lowercase().cmp(&expected.to_lowercase())),
+        _ => None,
+    };
+
+    match op {
+        PropertyOp::Exists => true,
+        PropertyOp::Contains => value
+            .text()
+            .to_lowercase()
+            .contains(&expected.to_lowercase()),
+        PropertyOp::Eq => ordering == Some(Ordering::Equal),
+        PropertyOp::Ne => ordering != Some(Ordering::Equal),
+        PropertyOp::Lt => ordering == Some(Ordering::Less),
+        PropertyOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        PropertyOp::Gt => ordering == Some(Ordering::Greater),
+        PropertyOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+    }
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "true" | "t" | "yes" | "1" => Some(true),
+        "false" | "f" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Property value as seen by predicates
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PropertyValue {
+    Number(f64),
+    Bool(bool),
+    Text(String),
+}
+
+impl PropertyValue {
+    fn from_attribute(value: &AttributeValue) -> Option<Self> {
+        // Typed values like IFCLABEL('x') are lists of type name and value
+        let value = match value {
+            AttributeValue::List(items)
+                if matches!(items.first(), Some(AttributeValue::String(_))) =>
+            {
+                items.get(1)?
+            }
+            other => other,
+        };
+        match value {
+            AttributeValue::Float(f) => Some(Self::Number(*f)),
+            AttributeValue::Integer(i) => Some(Self::Number(*i as f64)),
+            AttributeValue::String(s) => Some(Self::Text(s.clone())),
+            AttributeValue::Enum(e) => match e.as_str() {
+                "T" | "TRUE" => Some(Self::Bool(true)),
+                "F" | "FALSE" => Some(Self::Bool(false)),
+                other => Some(Self::Text(other.to_string())),
+            },
+            _ => None,
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Self::Number(n) => n.to_string(),
+            Self::Bool(b) => b.to_string(),
+            Self::Text(t) => t.clone(),
+        }
+    }
+}
+
+/// Declarative selection of products for geometry processing
+///
+/// Empty criteria select everything; non-empty criteria must all hold.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ElementFilter {
+    /// Include these types and their subtypes
+    pub types: Vec<IfcType>,
+    /// Exclude these types and their subtypes
+    pub exclude_types: Vec<IfcType>,
+    /// Include only these GlobalIds
+    pub global_ids: Vec<String>,
+    /// Include only elements in these storeys, by name, GlobalId or `#id`
+    pub storeys: Vec<String>,
+    /// Property and quantity conditions
+    pub properties: Vec<PropertyPredicate>,
+}
+
+impl ElementFilter {
+    /// Filter that selects everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add types to include, by IFC name (e.g. `IfcWall`)
+    pub fn with_types<I, S>(mut self, names: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.types.extend(parse_types(names)?);
+        Ok(self)
+    }
+
+    /// Add types to exclude, by IFC name
+    pub fn with_excluded_types<I, S>(mut self, names: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.exclude_types.extend(parse_types(names)?);
+        Ok(self)
+    }
+
+    /// Add GlobalIds to include
+    pub fn with_global_ids<I, S>(mut self, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.global_ids.extend(
+            ids.into_iter()
+                .map(|id| id.as_ref().trim().to_string())
+                .filter(|id| !id.is_empty()),
+        );
+        self
+    }
+
+    /// Add storeys to include, by name, GlobalId or `#id`
+    pub fn with_storeys<I, S>(mut self, storeys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.storeys.extend(
+            storeys
+                .into_iter()
+                .map(|s| s.as_ref().trim().to_string())
+                .filter(|s| !s.is_empty()),
+        );
+        self
+    }
+
+    /// Add property predicates, see `PropertyPredicate::parse`
+    pub fn with_properties<I, S>(mut self, predicates: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for predicate in predicates {
+            if !predicate.as_ref().trim().is_empty() {
+                self.properties
+                    .push(PropertyPredicate::parse(predicate.as_ref())?);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Check if the filter selects everything
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+            && self.exclude_types.is_empty()
+            && self.global_ids.is_empty()
+            && self.storeys.is_empty()
+            && self.properties.is_empty()
+    }
+
+    /// Check the type criteria alone
+    pub fn matches_type(&self, ifc_type: IfcType) -> bool {
+        (self.types.is_empty() || self.types.iter().any(|t| ifc_type.is_subtype_of(*t)))
+            && !self
+                .exclude_types
+                .iter()
+                .any(|t| ifc_type.is_subtype_of(*t))
+    }
+
+    /// Resolve GlobalId, storey and property criteria against a file
+    ///
+    /// Only these need the scan; type-only filters compile without reading
+    /// the content.
+    pub fn compile(&self, content: &str, decoder: &mut EntityDecoder) -> ElementMatcher {
+        let mut matcher = ElementMatcher {
+            filter: self.clone(),
+            ids: None,
+        };
+        if self.global_ids.is_empty() && self.storeys.is_empty() && self.properties.is_empty() {
+            return matcher;
+        }
+
+        let mut candidates: Vec<(u32, usize, usize)> = Vec::new();
+        let mut storeys: Vec<u32> = Vec::new();
+        // Element -> spatial structure, part -> aggregate
+        let mut parent: FxHashMap<u32, u32> = FxHashMap::default();
+        let mut properties = PropertyResolver::default();
+
+        let mut scanner = EntityScanner::new(content);
+        while let Some((id, type_name, start, end)) = scanner.next_entity() {
+            match type_name {
+                "IFCBUILDINGSTOREY" => storeys.push(id),
+                "IFCRELCONTAINEDINSPATIALSTRUCTURE" if !self.storeys.is_empty() => {
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        if let (Some(elements), Some(structure)) =
+                            (entity.get_list(4), entity.get_ref(5))
+                        {
+                            for element in elements.iter().filter_map(AttributeValue::as_entity_ref)
+                            {
+                                parent.insert(element, structure);
+                            }
+                        }
+                    }
+                }
+                "IFCRELAGGREGATES" if !self.storeys.is_empty() => {
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        if let (Some(whole), Some(parts)) = (entity.get_ref(4), entity.get_list(5))
+                        {
+                            for part in parts.iter().filter_map(AttributeValue::as_entity_ref) {
+                                // Containment wins over aggregation
+                                parent.entry(part).or_insert(whole);
+                            }
+                        }
+                    }
+                }
+                _ if !self.properties.is_empty() && PropertyResolver::is_assignment(type_name) => {
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        properties.add_assignment(&entity);
+                    }
+                }
+                _ => {}
+            }
+            if has_geometry_by_name(type_name) && self.matches_type(IfcType::from_str(type_name)) {
+                candidates.push((id, start, end));
+            }
+        }
+
+        // Storeys named by the filter
+        let storey_ids: FxHashSet<u32> = storeys
+            .into_iter()
+            .filter(|&id| {
+                let Ok(storey) = decoder.decode_by_id(id) else {
+                    return false;
+                };
+                self.storeys.iter().any(|key| {
+                    let key = key.trim_start_matches('#');
+                    key.parse::<u32>() == Ok(id)
+                        || storey.get_string(0) == Some(key)
+                        || storey
+                            .get_string(2)
+                            .is_some_and(|name| name.eq_ignore_ascii_case(key))
+                })
+            })
+            .collect();
+
+        if !self.properties.is_empty() {
+            properties.load_type_sets(decoder);
+        }
+
+        let mut ids = FxHashSet::default();
+        for (id, start, end) in candidates {
+            if !self.global_ids.is_empty() {
+                let Ok(entity) = decoder.decode_at_with_id(id, start, end) else {
+                    continue;
+                };
+                let global_id = entity.get_string(0).unwrap_or_default();
+                if !self.global_ids.iter().any(|g| g == global_id) {
+                    continue;
+                }
+            }
+
+            if !self.storeys.is_empty() {
+                let mut current = id;
+                let mut in_storey = false;
+                for _ in 0..32 {
+                    match parent.get(&current) {
+                        Some(&next) if storey_ids.contains(&next) => {
+                            in_storey = true;
+                            break;
+                        }
+                        Some(&next) => current = next,
+                        None => break,
+                    }
+                }
+                if !in_storey {
+                    continue;
+                }
+            }
+
+            if !self.properties.is_empty() {
+                // Type-object properties count unless the occurrence overrides them
+                let values: Vec<(String, String, PropertyValue)> = properties
+                    .effective_properties(id, decoder)
+                    .into_iter()
+                    .filter_map(|property| {
+                        let member = decoder.decode_by_id(property.property_id).ok()?;
+                        let value = member_value(&member)?;
+                        Some((property.set_name, property.name, value))
+                    })
+                    .collect();
+                let all = self.properties.iter().all(|predicate| {
+                    values.iter().any(|(pset, name, value)| {
+                        predicate.names_match(pset, name) && predicate.test(value)
+                    })
+                });
+                if !all {
+                    continue;
+                }
+            }
+
+            ids.insert(id);
+        }
+
+        matcher.ids = Some(ids);
+        matcher
+    }
+}
+
+/// Compiled filter for per-element checks
+#[derive(Debug, Clone, Default)]
+pub struct ElementMatcher {
+    filter: ElementFilter,
+    /// Elements passing the GlobalId, storey and property criteria
+    ids: Option<FxHashSet<u32>>,
+}
+
+impl ElementMatcher {
+    /// Matcher that accepts everything
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Matcher that accepts only the given elements
+    pub fn from_ids<I>(ids: I) -> Self
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        Self {
+            filter: ElementFilter::default(),
+            ids: Some(ids.into_iter().collect()),
+        }
+    }
+
+    /// Check if everything is accepted
+    pub fn is_all(&self) -> bool {
+        self.filter.is_empty() && self.ids.is_none()
+    }
+
+    /// Check an element
+    #[inline]
+    pub fn matches(&self, id: u32, ifc_type: IfcType) -> bool {
+        self.filter.matches_type(ifc_type) && self.ids.as_ref().is_none_or(|ids| ids.contains(&id))
+    }
+
+    /// Check an element by its upper-case STEP type name, as yielded by `EntityScanner`
+    #[inline]
+    pub fn matches_name(&self, id: u32, type_name: &str) -> bool {
+        self.is_all() || self.matches(id, IfcType::from_str(type_name))
+    }
+}
+
+fn parse_types<I, S>(names: I) -> Result<Vec<IfcType>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    names
+        .into_iter()
+        .map(|name| name.as_ref().trim().to_string())
+        .filter(|name| !name.is_empty())
+        .map(|name| match IfcType::from_str(&name) {
+            IfcType::Unknown(_) => Err(Error::InvalidIfcType(name)),
+            ifc_type => Ok(ifc_type),
+        })
+        .collect()
+}
+
+/// Value of an IfcPropertySingleValue or IfcQuantity* set member
+pub(crate) fn member_value(member: &DecodedEntity) -> Option<PropertyValue> {
+    // IfcPropertySingleValue.NominalValue, IfcQuantity*.XxxValue
+    let value = match member.ifc_type {
+        IfcType::IfcPropertySingleValue => member.get(2),
+        IfcType::IfcQuantityLength
+        | IfcType::IfcQuantityArea
+        | IfcType::IfcQuantityVolume
+        | IfcType::IfcQuantityCount
+        | IfcType::IfcQuantityWeight
+        | IfcType::IfcQuantityTime => member.get(3),
+        _ => None,
+    };
+    value.and_then(PropertyValue::from_attribute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::build_entity_index;
+
+    const MODEL: &str = r#"
+#1=IFCBUILDINGSTOREY('S0',$,'Ground',$,$,$,$,$,.ELEMENT.,0.);
+#2=IFCBUILDINGSTOREY('S1',$,'Level 1',$,$,$,$,$,.ELEMENT.,3.);
+#10=IFCWALL('W10',$,'Outer',$,$,#100,#101,$,$);
+#11=IFCWALLSTANDARDCASE('W11',$,'Inner',$,$,#100,#101,$,$);
+#12=IFCSLAB('S12',$,'Floor',$,$,#100,#101,$,$);
+#13=IFCSTAIR('ST13',$,'Stair',$,$,#100,$,$,$);
+#14=IFCSTAIRFLIGHT('SF14',$,'Flight',$,$,#100,#101,$,$);
+#20=IFCRELCONTAINEDINSPATIALSTRUCTURE('R20',$,$,$,(#10,#12),#1);
+#21=IFCRELCONTAINEDINSPATIALSTRUCTURE('R21',$,$,$,(#11,#13),#2);
+#22=IFCRELAGGREGATES('R22',$,$,$,#13,(#14));
+#30=IFCPROPERTYSINGLEVALUE('IsExternal',$,IFCBOOLEAN(.T.),$);
+#31=IFCPROPERTYSINGLEVALUE('IsExternal',$,IFCBOOLEAN(.F.),$);
+#32=IFCPROPERTYSINGLEVALUE('Reference',$,IFCIDENTIFIER('Basic Wall 300'),$);
+#33=IFCPROPERTYSET('P33',$,'Pset_WallCommon',$,(#30,#32));
+#34=IFCPROPERTYSET('P34',$,'Pset_WallCommon',$,(#31));
+#35=IFCQUANTITYLENGTH('Width',$,$,0.3,$);
+#36=IFCELEMENTQUANTITY('Q36',$,'Qto_WallBaseQuantities',$,$,(#35));
+#40=IFCRELDEFINESBYPROPERTIES('R40',$,$,$,(#10),#33);
+#41=IFCRELDEFINESBYPROPERTIES('R41',$,$,$,(#11),#34);
+#42=IFCRELDEFINESBYPROPERTIES('R42',$,$,$,(#10),#36);
+#50=IFCPROPERTYSINGLEVALUE('IsExternal',$,IFCBOOLEAN(.T.),$);
+#51=IFCPROPERTYSINGLEVALUE('LoadBearing',$,IFCBOOLEAN(.T.),$);
+#52=IFCPROPERTYSET('P52',$,'Pset_WallCommon',$,(#50,#51));
+#53=IFCWALLTYPE('T53',$,'Basic Wall 200',$,$,(#52),$,$,$,.STANDARD.);
+#54=IFCRELDEFINESBYTYPE('R54',$,$,$,(#11),#53);
+"#;
+
+    fn selected(filter: &ElementFilter) -> Vec<u32> {
+        let mut decoder = EntityDecoder::with_index(MODEL, build_entity_index(MODEL));
+        let matcher = filter.compile(MODEL, &mut decoder);
+        let mut scanner = EntityScanner::new(MODEL);
+        let mut ids = Vec::new();
+        while let Some((id, type_name, _, _)) = scanner.next_entity() {
+            let ifc_type = IfcType::from_str(type_name);
+            if ifc_type.is_subtype_of(IfcType::IfcElement) && matcher.matches(id, ifc_type) {
+                ids.push(id);
+            }
+        }
+        ids
+    }
+
+    #[test]
+    fn test_predicate_parse() {
+        let p = PropertyPredicate::parse("Pset_WallCommon.IsExternal = true").unwrap();
+        assert_eq!(p.pset.as_deref(), Some("Pset_WallCommon"));
+        assert_eq!(p.property, "IsExternal");
+        assert_eq!(p.op, PropertyOp::Eq);
+        assert_eq!(p.value, "true");
+
+        let p = PropertyPredicate::parse("Width>='0.25'").unwrap();
+        assert_eq!(
+            (p.pset, p.op, p.value.as_str()),
+            (None, PropertyOp::Ge, "0.25")
+        );
+
+        let p = PropertyPredicate::parse("FireRating").unwrap();
+        assert_eq!(p.op, PropertyOp::Exists);
+        assert!(PropertyPredicate::parse(".X=1").is_err());
+    }
+
+    #[test]
+    fn test_type_filter_with_subtypes() {
+        let filter = ElementFilter::new().with_types(["IfcWall"]).unwrap();
+        assert!(filter.matches_type(IfcType::IfcWallStandardCase));
+        assert_eq!(selected(&filter), vec![10, 11]);
+
+        let filter = ElementFilter::new()
+            .with_excluded_types(["IfcWall", "IfcSlab"])
+            .unwrap();
+        assert_eq!(selected(&filter), vec![13, 14]);
+
+        assert!(ElementFilter::new().with_types(["IfcNotAType"]).is_err());
+        assert_eq!(selected(&ElementFilter::new()), vec![10, 11, 12, 13, 14]);
+    }
+
+    #[test]
+    fn test_global_id_and_storey_filter() {
+        let filter = ElementFilter::new().with_global_ids(["W11", "S12"]);
+        assert_eq!(selected(&filter), vec![11, 12]);
+
+        // Aggregated parts follow their whole into the storey
+        let filter = ElementFilter::new().with_storeys(["level 1"]);
+        assert_eq!(selected(&filter), vec![11, 13, 14]);
+        let filter = ElementFilter::new().with_storeys(["S0", "#2"]);
+        assert_eq!(selected(&filter), vec![10, 11, 12, 13, 14]);
+
+        let filter = ElementFilter::new()
+            .with_storeys(["Ground"])
+            .with_types(["IfcSlab"])
+            .unwrap();
+        assert_eq!(selected(&filter), vec![12]);
+
+        let matcher = ElementMatcher::from_ids([11]);
+        assert!(!matcher.is_all());
+        assert!(matcher.matches_name(11, "IFCWALLSTANDARDCASE"));
+        assert!(!matcher.matches_name(10, "IFCWALL"));
+    }
+
+    #[test]
+    fn test_property_filter() {
+        let filter = ElementFilter::new()
+            .with_properties(["Pset_WallCommon.IsExternal=true"])
+            .unwrap();
+        assert_eq!(selected(&filter), vec![10]);
+
+        let filter = ElementFilter::new()
+            .with_properties(["IsExternal!=T"])
+            .unwrap();
+        assert_eq!(selected(&filter), vec![11]);
+
+        let filter = ElementFilter::new()
+            .with_properties(["Reference~basic wall", "Qto_WallBaseQuantities.Width>0.25"])
+            .unwrap();
+        assert_eq!(selected(&filter), vec![10]);
+
+        let filter = ElementFilter::new()
+            .with_properties(["Width<0.25"])
+            .unwrap();
+        assert!(selected(&filter).is_empty());
+    }
+
+    #[test]
+    fn test_property_filter_with_type_properties() {
+        // Inherited from the wall type
+        let filter = ElementFilter::new()
+            .with_properties(["Pset_WallCommon.LoadBearing=true"])
+            .unwrap();
+        assert_eq!(selected(&filter), vec![11]);
+
+        // The occurrence value overrides the type's IsExternal=true
+        let filter = ElementFilter::new()
+            .with_properties(["IsExternal=true"])
+            .unwrap();
+        assert_eq!(selected(&filter), vec![10]);
+    }
+}
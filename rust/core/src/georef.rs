@@ -138,6 +138,86 @@ impl GeoReference {
             1.0,
         ]
     }
+
+    /// UTM zone number and hemisphere (`true` for north) of a WGS 84 UTM CRS
+    ///
+    /// Recognises EPSG:32601-32660 / EPSG:32701-32760 in the CRS name and
+    /// "UTM zone 32N"-style map projections.
+    pub fn utm_zone(&self) -> Option<(u8, bool)> {
+        if let Some(name) = &self.crs_name {
+            let code = name.trim().to_ascii_uppercase();
+            let code = code.strip_prefix("EPSG:").unwrap_or(&code);
+            if let Ok(code) = code.parse::<u32>() {
+                let zone = (code % 100) as u8;
+                if (1..=60).contains(&zone) {
+                    match code / 100 {
+                        326 => return Some((zone, true)),
+                        327 => return Some((zone, false)),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let projection = self.map_projection.as_ref()?.to_ascii_uppercase();
+        let rest = projection.split("UTM").nth(1)?;
+        let rest = rest.trim_start_matches(|c: char| !c.is_ascii_digit());
+        let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        let zone: u8 = digits.parse().ok()?;
+        let north = !rest[digits.len()..].trim_start().starts_with('S');
+        (1..=60).contains(&zone).then_some((zone, north))
+    }
+
+    /// Convert map coordinates of a WGS 84 UTM CRS to latitude and longitude
+    ///
+    /// Returns `(latitude, longitude, convergence)` in radians, where
+    /// convergence is the angle from true north to grid north (clockwise).
+    /// Uses the Krüger series, accurate to well below a millimetre in-zone.
+    pub fn map_to_geographic(&self, e: f64, n: f64) -> Option<(f64, f64, f64)> {
+        let (zone, north) = self.utm_zone()?;
+
+        const A: f64 = 6_378_137.0;
+        const F: f64 = 1.0 / 298.257_223_563;
+        const K0: f64 = 0.9996;
+
+        let n3 = F / (2.0 - F);
+        let n2 = n3 * n3;
+        let rect = A / (1.0 + n3) * (1.0 + n2 / 4.0 + n2 * n2 / 64.0);
+        let beta = [
+            n3 / 2.0 - 2.0 * n2 / 3.0 + 37.0 * n2 * n3 / 96.0,
+            n2 / 48.0 + n2 * n3 / 15.0,
+            17.0 * n2 * n3 / 480.0,
+        ];
+        let delta = [
+            2.0 * n3 - 2.0 * n2 / 3.0 - 2.0 * n2 * n3,
+            7.0 * n2 / 3.0 - 8.0 * n2 * n3 / 5.0,
+            56.0 * n2 * n3 / 15.0,
+        ];
+
+        let false_northing = if north { 0.0 } else { 10_000_000.0 };
+        let xi = (n - false_northing) / (K0 * rect);
+        let eta = (e - 500_000.0) / (K0 * rect);
+
+        let mut xi_p = xi;
+        let mut eta_p = eta;
+        for (j, b) in beta.iter().enumerate() {
+            let k = 2.0 * (j + 1) as f64;
+            xi_p -= b * (k * xi).sin() * (k * eta).cosh();
+            eta_p -= b * (k * xi).cos() * (k * eta).sinh();
+        }
+
+        let chi = (xi_p.sin() / eta_p.cosh()).asin();
+        let mut lat = chi;
+        for (j, d) in delta.iter().enumerate() {
+            lat += d * (2.0 * (j + 1) as f64 * chi).sin();
+        }
+
+        let central_meridian = (zone as f64 * 6.0 - 183.0).to_radians();
+        let dlon = eta_p.sinh().atan2(xi_p.cos());
+        let convergence = (dlon.tan() * lat.sin()).atan();
+
+        Some((lat, central_meridian + dlon, convergence))
+    }
 }
 
 /// Extract georeferencing from IFC content
@@ -419,6 +499,40 @@ mod tests {
         assert!((n - 10.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_utm_zone() {
+        let mut georef = GeoReference::new();
+        georef.crs_name = Some("EPSG:32632".to_string());
+        assert_eq!(georef.utm_zone(), Some((32, true)));
+
+        georef.crs_name = Some("EPSG:32756".to_string());
+        assert_eq!(georef.utm_zone(), Some((56, false)));
+
+        georef.crs_name = Some("EPSG:25832".to_string());
+        assert_eq!(georef.utm_zone(), None);
+
+        georef.map_projection = Some("UTM Zone 33S".to_string());
+        assert_eq!(georef.utm_zone(), Some((33, false)));
+    }
+
+    #[test]
+    fn test_map_to_geographic() {
+        let mut georef = GeoReference::new();
+        georef.crs_name = Some("EPSG:32632".to_string());
+
+        // Central meridian of zone 32 on the equator
+        let (lat, lon, gamma) = georef.map_to_geographic(500000.0, 0.0).unwrap();
+        assert!(lat.abs() < 1e-12);
+        assert!((lon.to_degrees() - 9.0).abs() < 1e-12);
+        assert!(gamma.abs() < 1e-12);
+
+        // Munich Frauenkirche: 48.138576N 11.573497E
+        let (lat, lon, gamma) = georef.map_to_geographic(691_449.656, 5_334_905.738).unwrap();
+        assert!((lat.to_degrees() - 48.138576).abs() < 1e-7);
+        assert!((lon.to_degrees() - 11.573497).abs() < 1e-7);
+        assert!(gamma > 0.0);
+    }
+
     #[test]
     fn test_rtc_offset() {
         let positions = vec![
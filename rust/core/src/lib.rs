@@ -15,6 +15,8 @@
 //! - **Entity Scanning**: SIMD-accelerated entity discovery using [memchr](https://docs.rs/memchr)
 //! - **Lazy Decoding**: On-demand attribute parsing for memory efficiency
 //! - **Streaming Parser**: Event-based parsing for large files
+//! - **Element Filters**: Select products by type, GlobalId, storey or property
+//! - **Property Inheritance**: Effective element properties including type-object sets
 //!
 //! ## Quick Start
 //!
@@ -69,13 +71,21 @@
 pub mod decoder;
 pub mod error;
 pub mod fast_parse;
+pub mod filter;
 pub mod generated;
 pub mod georef;
 pub mod legacy_entities;
+pub mod materials;
 pub mod model_bounds;
 pub mod parser;
+pub mod properties;
+pub mod query;
+pub mod references;
 pub mod schema_gen;
 pub mod streaming;
+pub mod systems;
+#[cfg(test)]
+mod test_fixtures;
 pub mod units;
 
 pub use decoder::{build_entity_index, EntityDecoder, EntityIndex};
@@ -85,11 +95,28 @@ pub use fast_parse::{
     extract_face_indices_from_entity, extract_first_entity_ref, parse_coordinates_direct,
     parse_indices_direct, process_triangulated_faceset_direct, should_use_fast_path, FastMeshData,
 };
+pub use filter::{ElementFilter, ElementMatcher, PropertyOp, PropertyPredicate};
 pub use generated::{has_geometry_by_name, IfcType};
 pub use georef::{GeoRefExtractor, GeoReference, RtcOffset};
 pub use legacy_entities::{get_legacy_entity_info, is_legacy_entity, map_legacy_to_base_type, LegacyEntityInfo};
+pub use materials::{
+    MaterialAssociation, MaterialAssociationKind, MaterialPart, MaterialPartKind, MaterialResolver,
+};
 pub use model_bounds::{scan_model_bounds, scan_placement_bounds, ModelBounds};
 pub use parser::{parse_entity, EntityScanner, Token};
+pub use properties::{EffectiveProperty, PropertyResolver, PropertySource, PropertyValue};
+pub use query::{
+    Attribute, Condition, ModelIndex, Predicate, Query, SpatialLevel, MAX_CONDITION_DEPTH,
+};
+pub use references::{
+    resolve_classification, ClassificationReference, DocumentReference, ReferenceResolver,
+};
 pub use schema_gen::{AttributeValue, DecodedEntity, GeometryCategory, IfcSchema, ProfileCategory};
 pub use streaming::{parse_stream, ParseEvent, StreamConfig};
-pub use units::{extract_length_unit_scale, get_si_prefix_multiplier};
+pub use systems::{
+    extract_groups, DistributionGraph, FlowDirection, Group, Port, PortConnection,
+};
+pub use units::{
+    extract_length_unit_scale, get_si_prefix_multiplier, measure_unit_type, quantity_unit_type,
+    unit_conversion, unit_label, UnitContext, UnitConversion,
+};
@@ -0,0 +1,646 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Material association resolution
+//!
+//! IfcRelAssociatesMaterial attaches an IfcMaterialSelect to elements and to
+//! type objects. An element without its own association inherits the one of
+//! its type (IfcRelDefinesByType); an own association replaces the type's.
+//!
+//! ```text
+//! IfcMaterial                         → one part
+//! IfcMaterialList                     → one part per material
+//! IfcMaterialLayerSet(Usage)          → one part per layer, with thickness and offset
+//! IfcMaterialProfileSet(Usage)        → one part per profile
+//! IfcMaterialConstituentSet           → one part per constituent, with fraction
+//! ```
+//!
+//! Every part carries the share of the element volume it takes where that is
+//! known, so that quantity take-off by material is `volume × fraction`.
+//! Lengths are in project length units.
+
+use crate::decoder::EntityDecoder;
+use crate::generated::IfcType;
+use crate::parser::EntityScanner;
+use crate::properties::PropertySource;
+use crate::schema_gen::{AttributeValue, DecodedEntity};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Kind of IfcMaterialSelect associated with an element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MaterialAssociationKind {
+    Material,
+    MaterialList,
+    Layer,
+    LayerSet,
+    LayerSetUsage,
+    Profile,
+    ProfileSet,
+    ProfileSetUsage,
+    Constituent,
+    ConstituentSet,
+}
+
+impl MaterialAssociationKind {
+    /// Kind of a material entity, if it can be associated with an element
+    pub fn from_type(ifc_type: &IfcType) -> Option<Self> {
+        let kind = match ifc_type {
+            IfcType::IfcMaterial => Self::Material,
+            IfcType::IfcMaterialList => Self::MaterialList,
+            IfcType::IfcMaterialLayer | IfcType::IfcMaterialLayerWithOffsets => Self::Layer,
+            IfcType::IfcMaterialLayerSet => Self::LayerSet,
+            IfcType::IfcMaterialLayerSetUsage => Self::LayerSetUsage,
+            IfcType::IfcMaterialProfile | IfcType::IfcMaterialProfileWithOffsets => Self::Profile,
+            IfcType::IfcMaterialProfileSet => Self::ProfileSet,
+            IfcType::IfcMaterialProfileSetUsage | IfcType::IfcMaterialProfileSetUsageTapering => {
+                Self::ProfileSetUsage
+            }
+            IfcType::IfcMaterialConstituent => Self::Constituent,
+            IfcType::IfcMaterialConstituentSet => Self::ConstituentSet,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Snake-case label ("material", "layer_set_usage", ...)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Material => "material",
+            Self::MaterialList => "material_list",
+            Self::Layer => "layer",
+            Self::LayerSet => "layer_set",
+            Self::LayerSetUsage => "layer_set_usage",
+            Self::Profile => "profile",
+            Self::ProfileSet => "profile_set",
+            Self::ProfileSetUsage => "profile_set_usage",
+            Self::Constituent => "constituent",
+            Self::ConstituentSet => "constituent_set",
+        }
+    }
+}
+
+/// Role of one material within an association
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MaterialPartKind {
+    /// Plain IfcMaterial (alone or in a list)
+    Material,
+    /// IfcMaterialLayer
+    Layer,
+    /// IfcMaterialProfile
+    Profile,
+    /// IfcMaterialConstituent
+    Constituent,
+}
+
+impl MaterialPartKind {
+    /// Lower-case label ("material", "layer", "profile" or "constituent")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Material => "material",
+            Self::Layer => "layer",
+            Self::Profile => "profile",
+            Self::Constituent => "constituent",
+        }
+    }
+}
+
+/// One material of an association: a layer, profile, constituent or list entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaterialPart {
+    /// Role within the association
+    pub kind: MaterialPartKind,
+    /// Position within the set (0 for single materials)
+    pub index: usize,
+    /// IfcMaterialLayer / IfcMaterialProfile / IfcMaterialConstituent entity
+    pub part_id: Option<u32>,
+    /// Name of the layer, profile or constituent
+    pub name: Option<String>,
+    /// IfcMaterial entity; layers without one are air gaps
+    pub material_id: Option<u32>,
+    /// IfcMaterial.Name
+    pub material_name: Option<String>,
+    /// Category of the material, or of the part when it has its own
+    pub category: Option<String>,
+    /// IfcMaterialLayer.LayerThickness
+    pub thickness: Option<f64>,
+    /// Signed distance of the layer's first face from the reference line along
+    /// the layer set direction; the layer extends `thickness` further in its
+    /// DirectionSense
+    pub offset: Option<f64>,
+    /// Share of the element volume (layer thickness over total thickness,
+    /// constituent fraction, 1.0 for a single material) if known
+    pub fraction: Option<f64>,
+    /// IfcMaterialProfile.Profile
+    pub profile_id: Option<u32>,
+    /// IfcMaterialLayer.IsVentilated (`None` if unknown)
+    pub is_ventilated: Option<bool>,
+    /// IfcMaterialLayer/IfcMaterialProfile.Priority
+    pub priority: Option<i64>,
+}
+
+impl MaterialPart {
+    fn new(kind: MaterialPartKind, index: usize) -> Self {
+        Self {
+            kind,
+            index,
+            part_id: None,
+            name: None,
+            material_id: None,
+            material_name: None,
+            category: None,
+            thickness: None,
+            offset: None,
+            fraction: None,
+            profile_id: None,
+            is_ventilated: None,
+            priority: None,
+        }
+    }
+}
+
+/// Material associated with an element, resolved into its parts
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaterialAssociation {
+    /// IfcMaterialSelect entity named by IfcRelAssociatesMaterial
+    pub relating_id: u32,
+    /// Kind of that entity
+    pub kind: MaterialAssociationKind,
+    /// Set behind a usage (ForLayerSet / ForProfileSet); the set itself otherwise
+    pub set_id: Option<u32>,
+    /// Name of the set or list
+    pub set_name: Option<String>,
+    /// Whether the association is the element's own or its type's
+    pub source: PropertySource,
+    /// IfcMaterialLayerSetUsage.LayerSetDirection (AXIS1, AXIS2 or AXIS3)
+    pub layer_set_direction: Option<String>,
+    /// IfcMaterialLayerSetUsage.DirectionSense (POSITIVE or NEGATIVE)
+    pub direction_sense: Option<String>,
+    /// IfcMaterialLayerSetUsage.OffsetFromReferenceLine
+    pub offset_from_reference_line: Option<f64>,
+    /// IfcMaterialProfileSetUsage.CardinalPoint
+    pub cardinal_point: Option<i64>,
+    /// Sum of layer thicknesses for layer sets
+    pub total_thickness: Option<f64>,
+    /// Materials in set order
+    pub parts: Vec<MaterialPart>,
+}
+
+impl MaterialAssociation {
+    /// Multiply thicknesses and offsets, e.g. by the length unit scale to get metres
+    pub fn scale_lengths(&mut self, scale: f64) {
+        let scale_opt = |value: &mut Option<f64>| {
+            if let Some(v) = value {
+                *v *= scale;
+            }
+        };
+        scale_opt(&mut self.offset_from_reference_line);
+        scale_opt(&mut self.total_thickness);
+        for part in &mut self.parts {
+            scale_opt(&mut part.thickness);
+            scale_opt(&mut part.offset);
+        }
+    }
+}
+
+/// Material associations of elements and type objects in a file
+#[derive(Debug, Clone, Default)]
+pub struct MaterialResolver {
+    /// Object (element or type) → associated IfcMaterialSelect entities
+    associations: FxHashMap<u32, Vec<u32>>,
+    /// Element → type object (IfcRelDefinesByType)
+    element_types: FxHashMap<u32, u32>,
+    /// Type objects seen in the file, which are not elements themselves
+    type_objects: FxHashSet<u32>,
+}
+
+impl MaterialResolver {
+    /// Collect material associations and type objects from a file
+    pub fn from_content(content: &str, decoder: &mut EntityDecoder) -> Self {
+        let mut resolver = Self::default();
+        let mut scanner = EntityScanner::new(content);
+        while let Some((id, type_name, start, end)) = scanner.next_entity() {
+            match type_name {
+                "IFCRELASSOCIATESMATERIAL" | "IFCRELDEFINESBYTYPE" => {
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        resolver.add_relationship(&entity);
+                    }
+                }
+                _ if (type_name.ends_with("TYPE") || type_name.ends_with("STYLE"))
+                    && IfcType::from_str(type_name).is_subtype_of(IfcType::IfcTypeObject) =>
+                {
+                    resolver.type_objects.insert(id);
+                }
+                _ => {}
+            }
+        }
+        resolver
+    }
+
+    /// Record an IfcRelAssociatesMaterial or IfcRelDefinesByType
+    fn add_relationship(&mut self, relationship: &DecodedEntity) {
+        // Both: [4]=RelatedObjects, [5]=RelatingMaterial/RelatingType
+        let (Some(objects), Some(relating)) = (relationship.get_list(4), relationship.get_ref(5))
+        else {
+            return;
+        };
+        let is_type = relationship.ifc_type == IfcType::IfcRelDefinesByType;
+        for object in objects.iter().filter_map(AttributeValue::as_entity_ref) {
+            if is_type {
+                self.element_types.insert(object, relating);
+                self.type_objects.insert(relating);
+            } else {
+                self.associations.entry(object).or_default().push(relating);
+            }
+        }
+    }
+
+    /// Type object of an element
+    pub fn element_type(&self, element: u32) -> Option<u32> {
+        self.element_types.get(&element).copied()
+    }
+
+    /// Elements with a direct or inherited material association
+    pub fn elements(&self) -> impl Iterator<Item = u32> + '_ {
+        let own = self
+            .associations
+            .keys()
+            .copied()
+            .filter(|object| !self.type_objects.contains(object));
+        let typed = self
+            .element_types
+            .iter()
+            .filter(|(element, type_id)| {
+                self.associations.contains_key(type_id) && !self.associations.contains_key(element)
+            })
+            .map(|(&element, _)| element);
+        own.chain(typed)
+    }
+
+    /// Associated IfcMaterialSelect entities of an element, own or else its type's
+    pub fn material_ids(&self, element: u32) -> Vec<(u32, PropertySource)> {
+        if let Some(own) = self.associations.get(&element) {
+            return own
+                .iter()
+                .map(|&id| (id, PropertySource::Occurrence))
+                .collect();
+        }
+        self.element_type(element)
+            .and_then(|type_id| self.associations.get(&type_id))
+            .into_iter()
+            .flatten()
+            .map(|&id| (id, PropertySource::Type))
+            .collect()
+    }
+
+    /// Resolve the materials of an element
+    pub fn materials(&self, element: u32, decoder: &mut EntityDecoder) -> Vec<MaterialAssociation> {
+        self.material_ids(element)
+            .into_iter()
+            .filter_map(|(id, source)| resolve_material(id, source, decoder))
+            .collect()
+    }
+}
+
+/// Resolve an IfcMaterialSelect entity into its parts
+pub fn resolve_material(
+    relating_id: u32,
+    source: PropertySource,
+    decoder: &mut EntityDecoder,
+) -> Option<MaterialAssociation> {
+    let entity = decoder.decode_by_id(relating_id).ok()?;
+    let kind = MaterialAssociationKind::from_type(&entity.ifc_type)?;
+    let mut association = MaterialAssociation {
+        relating_id,
+        kind,
+        set_id: None,
+        set_name: None,
+        source,
+        layer_set_direction: None,
+        direction_sense: None,
+        offset_from_reference_line: None,
+        cardinal_point: None,
+        total_thickness: None,
+        parts: Vec::new(),
+    };
+
+    match kind {
+        MaterialAssociationKind::Material => {
+            let mut part = MaterialPart::new(MaterialPartKind::Material, 0);
+            read_material(&mut part, Some(relating_id), decoder);
+            part.fraction = Some(1.0);
+            association.parts.push(part);
+        }
+        MaterialAssociationKind::MaterialList => {
+            // IfcMaterialList: [0]=Materials
+            let ids = entity_refs(&entity, 0);
+            let single = ids.len() == 1;
+            for (index, id) in ids.into_iter().enumerate() {
+                let mut part = MaterialPart::new(MaterialPartKind::Material, index);
+                read_material(&mut part, Some(id), decoder);
+                part.fraction = single.then_some(1.0);
+                association.parts.push(part);
+            }
+            association.set_id = Some(relating_id);
+        }
+        MaterialAssociationKind::Layer => {
+            let mut part = read_layer(&entity, relating_id, 0, decoder);
+            part.offset = Some(0.0);
+            part.fraction = Some(1.0);
+            association.total_thickness = part.thickness;
+            association.parts.push(part);
+        }
+        MaterialAssociationKind::LayerSet => {
+            read_layer_set(&mut association, &entity, relating_id, decoder);
+        }
+        MaterialAssociationKind::LayerSetUsage => {
+            // IfcMaterialLayerSetUsage: [0]=ForLayerSet, [1]=LayerSetDirection,
+            // [2]=DirectionSense, [3]=OffsetFromReferenceLine
+            association.layer_set_direction = enum_value(&entity, 1);
+            association.direction_sense = enum_value(&entity, 2);
+            association.offset_from_reference_line = Some(entity.get_float(3).unwrap_or(0.0));
+            let set_id = entity.get_ref(0)?;
+            let set = decoder.decode_by_id(set_id).ok()?;
+            read_layer_set(&mut association, &set, set_id, decoder);
+        }
+        MaterialAssociationKind::Profile => {
+            let mut part = read_profile(&entity, relating_id, 0, decoder);
+            part.fraction = Some(1.0);
+            association.parts.push(part);
+        }
+        MaterialAssociationKind::ProfileSet => {
+            read_profile_set(&mut association, &entity, relating_id, decoder);
+        }
+        MaterialAssociationKind::ProfileSetUsage => {
+            // IfcMaterialProfileSetUsage: [0]=ForProfileSet, [1]=CardinalPoint
+            association.cardinal_point = entity.get(1).and_then(AttributeValue::as_int);
+            let set_id = entity.get_ref(0)?;
+            let set = decoder.decode_by_id(set_id).ok()?;
+            read_profile_set(&mut association, &set, set_id, decoder);
+        }
+        MaterialAssociationKind::Constituent => {
+            let mut part = read_constituent(&entity, relating_id, 0, decoder);
+            part.fraction = Some(part.fraction.unwrap_or(1.0));
+            association.parts.push(part);
+        }
+        MaterialAssociationKind::ConstituentSet => {
+            // IfcMaterialConstituentSet: [0]=Name, [1]=Description, [2]=MaterialConstituents
+            association.set_id = Some(relating_id);
+            association.set_name = entity.get_string(0).map(str::to_string);
+            let ids = entity_refs(&entity, 2);
+            let single = ids.len() == 1;
+            for (index, id) in ids.into_iter().enumerate() {
+                let Ok(constituent) = decoder.decode_by_id(id) else {
+                    continue;
+                };
+                let mut part = read_constituent(&constituent, id, index, decoder);
+                if single && part.fraction.is_none() {
+                    part.fraction = Some(1.0);
+                }
+                association.parts.push(part);
+            }
+        }
+    }
+
+    Some(association)
+}
+
+/// Fill the parts of an IfcMaterialLayerSet, stacking layers from the reference line
+fn read_layer_set(
+    association: &mut MaterialAssociation,
+    set: &DecodedEntity,
+    set_id: u32,
+    decoder: &mut EntityDecoder,
+) {
+    // IfcMaterialLayerSet: [0]=MaterialLayers, [1]=LayerSetName
+    association.set_id = Some(set_id);
+    association.set_name = set.get_string(1).map(str::to_string);
+
+    let sign = match association.direction_sense.as_deref() {
+        Some("NEGATIVE") => -1.0,
+        _ => 1.0,
+    };
+    let mut position = 0.0;
+    for (index, layer_id) in entity_refs(set, 0).into_iter().enumerate() {
+        let Ok(layer) = decoder.decode_by_id(layer_id) else {
+            continue;
+        };
+        let mut part = read_layer(&layer, layer_id, index, decoder);
+        let thickness = part.thickness.unwrap_or(0.0);
+        part.offset = Some(association.offset_from_reference_line.unwrap_or(0.0) + sign * position);
+        position += thickness;
+        association.parts.push(part);
+    }
+
+    association.total_thickness = Some(position);
+    if position > 0.0 {
+        for part in &mut association.parts {
+            part.fraction = part.thickness.map(|t| t / position);
+        }
+    }
+}
+
+/// Fill the parts of an IfcMaterialProfileSet
+fn read_profile_set(
+    association: &mut MaterialAssociation,
+    set: &DecodedEntity,
+    set_id: u32,
+    decoder: &mut EntityDecoder,
+) {
+    // IfcMaterialProfileSet: [0]=Name, [1]=Description, [2]=MaterialProfiles
+    association.set_id = Some(set_id);
+    association.set_name = set.get_string(0).map(str::to_string);
+    let ids = entity_refs(set, 2);
+    let single = ids.len() == 1;
+    for (index, profile_id) in ids.into_iter().enumerate() {
+        let Ok(profile) = decoder.decode_by_id(profile_id) else {
+            continue;
+        };
+        let mut part = read_profile(&profile, profile_id, index, decoder);
+        part.fraction = single.then_some(1.0);
+        association.parts.push(part);
+    }
+}
+
+/// IfcMaterialLayer: [0]=Material, [1]=LayerThickness, [2]=IsVentilated,
+/// [3]=Name, [4]=Description, [5]=Category, [6]=Priority
+fn read_layer(
+    layer: &DecodedEntity,
+    layer_id: u32,
+    index: usize,
+    decoder: &mut EntityDecoder,
+) -> MaterialPart {
+    let mut part = MaterialPart::new(MaterialPartKind::Layer, index);
+    part.part_id = Some(layer_id);
+    read_material(&mut part, layer.get_ref(0), decoder);
+    part.thickness = layer.get_float(1);
+    part.is_ventilated = layer
+        .get(2)
+        .and_then(AttributeValue::as_enum)
+        .and_then(|v| match v {
+            "T" => Some(true),
+            "F" => Some(false),
+            _ => None,
+        });
+    part.name = layer.get_string(3).map(str::to_string);
+    if let Some(category) = layer.get_string(5) {
+        part.category = Some(category.to_string());
+    }
+    part.priority = layer.get(6).and_then(AttributeValue::as_int);
+    part
+}
+
+/// IfcMaterialProfile: [0]=Name, [1]=Description, [2]=Material, [3]=Profile,
+/// [4]=Priority, [5]=Category
+fn read_profile(
+    profile: &DecodedEntity,
+    profile_id: u32,
+    index: usize,
+    decoder: &mut EntityDecoder,
+) -> MaterialPart {
+    let mut part = MaterialPart::new(MaterialPartKind::Profile, index);
+    part.part_id = Some(profile_id);
+    part.name = profile.get_string(0).map(str::to_string);
+    read_material(&mut part, profile.get_ref(2), decoder);
+    part.profile_id = profile.get_ref(3);
+    part.priority = profile.get(4).and_then(AttributeValue::as_int);
+    if let Some(category) = profile.get_string(5) {
+        part.category = Some(category.to_string());
+    }
+    part
+}
+
+/// IfcMaterialConstituent: [0]=Name, [1]=Description, [2]=Material,
+/// [3]=Fraction, [4]=Category
+fn read_constituent(
+    constituent: &DecodedEntity,
+    constituent_id: u32,
+    index: usize,
+    decoder: &mut EntityDecoder,
+) -> MaterialPart {
+    let mut part = MaterialPart::new(MaterialPartKind::Constituent, index);
+    part.part_id = Some(constituent_id);
+    part.name = constituent.get_string(0).map(str::to_string);
+    read_material(&mut part, constituent.get_ref(2), decoder);
+    part.fraction = constituent.get_float(3);
+    if let Some(category) = constituent.get_string(4) {
+        part.category = Some(category.to_string());
+    }
+    part
+}
+
+/// IfcMaterial: [0]=Name, [1]=Description, [2]=Category (IFC4)
+fn read_material(part: &mut MaterialPart, material_id: Option<u32>, decoder: &mut EntityDecoder) {
+    let Some(material_id) = material_id else {
+        return;
+    };
+    part.material_id = Some(material_id);
+    if let Ok(material) = decoder.decode_by_id(material_id) {
+        part.material_name = material.get_string(0).map(str::to_string);
+        part.category = material.get_string(2).map(str::to_string);
+    }
+}
+
+fn entity_refs(entity: &DecodedEntity, index: usize) -> Vec<u32> {
+    entity
+        .get_list(index)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(AttributeValue::as_entity_ref)
+        .collect()
+}
+
+fn enum_value(entity: &DecodedEntity, index: usize) -> Option<String> {
+    entity
+        .get(index)
+        .and_then(AttributeValue::as_enum)
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{decoder, MODEL};
+
+    fn resolver() -> (MaterialResolver, EntityDecoder<'static>) {
+        let mut decoder = decoder();
+        (MaterialResolver::from_content(MODEL, &mut decoder), decoder)
+    }
+
+    fn names(association: &MaterialAssociation) -> Vec<Option<&str>> {
+        association
+            .parts
+            .iter()
+            .map(|p| p.material_name.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn test_layer_set_inherited_from_type() {
+        let (resolver, mut decoder) = resolver();
+        let materials = resolver.materials(23, &mut decoder);
+        assert_eq!(materials.len(), 1);
+
+        let set = &materials[0];
+        assert_eq!(set.kind, MaterialAssociationKind::LayerSet);
+        assert_eq!(set.source, PropertySource::Type);
+        assert_eq!(set.set_name.as_deref(), Some("Cavity wall"));
+        assert_eq!(set.total_thickness, Some(400.0));
+        assert_eq!(
+            names(set),
+            vec![Some("Concrete"), Some("Insulation"), None, Some("Brick")]
+        );
+        let offsets: Vec<_> = set.parts.iter().map(|p| p.offset.unwrap()).collect();
+        assert_eq!(offsets, vec![0.0, 200.0, 300.0, 320.0]);
+        assert_eq!(set.parts[0].fraction, Some(0.5));
+        assert_eq!(set.parts[2].is_ventilated, Some(true));
+        assert_eq!(set.parts[3].category.as_deref(), Some("Finish"));
+    }
+
+    #[test]
+    fn test_own_usage_replaces_type_set() {
+        let (resolver, mut decoder) = resolver();
+        let mut materials = resolver.materials(24, &mut decoder);
+        assert_eq!(materials.len(), 1);
+
+        let usage = &mut materials[0];
+        assert_eq!(usage.kind, MaterialAssociationKind::LayerSetUsage);
+        assert_eq!(usage.source, PropertySource::Occurrence);
+        assert_eq!(usage.set_id, Some(81));
+        assert_eq!(usage.layer_set_direction.as_deref(), Some("AXIS2"));
+        assert_eq!(usage.direction_sense.as_deref(), Some("NEGATIVE"));
+
+        // Negative sense stacks layers from the reference offset downwards
+        usage.scale_lengths(0.001);
+        let offsets: Vec<_> = usage.parts.iter().map(|p| p.offset.unwrap()).collect();
+        assert_eq!(offsets, vec![0.2, 0.0, -0.1, -0.12]);
+        assert_eq!(usage.parts[1].thickness, Some(0.1));
+    }
+
+    #[test]
+    fn test_profile_constituent_and_list() {
+        let (resolver, mut decoder) = resolver();
+
+        let column = &resolver.materials(25, &mut decoder)[0];
+        assert_eq!(column.kind, MaterialAssociationKind::ProfileSetUsage);
+        assert_eq!(column.cardinal_point, Some(5));
+        assert_eq!(column.parts[0].profile_id, Some(87));
+        assert_eq!(column.parts[0].material_name.as_deref(), Some("Steel"));
+        assert_eq!(column.parts[0].fraction, Some(1.0));
+
+        let window = &resolver.materials(27, &mut decoder)[0];
+        assert_eq!(window.kind, MaterialAssociationKind::ConstituentSet);
+        let fractions: Vec<_> = window.parts.iter().map(|p| p.fraction).collect();
+        assert_eq!(fractions, vec![Some(0.8), Some(0.2)]);
+        assert_eq!(window.parts[1].name.as_deref(), Some("Frame"));
+
+        let slab = &resolver.materials(26, &mut decoder)[0];
+        assert_eq!(slab.kind, MaterialAssociationKind::MaterialList);
+        assert_eq!(names(slab), vec![Some("Concrete"), Some("Steel")]);
+        assert_eq!(slab.parts[0].fraction, None);
+
+        let mut elements: Vec<u32> = resolver.elements().collect();
+        elements.sort_unstable();
+        assert_eq!(elements, vec![20, 21, 23, 24, 25, 26, 27]);
+    }
+}
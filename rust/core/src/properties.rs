@@ -0,0 +1,531 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Effective property resolution with type-object inheritance
+//!
+//! Elements get property sets directly through IfcRelDefinesByProperties and
+//! indirectly through IfcRelDefinesByType, whose type object lists its own sets
+//! in HasPropertySets. A property defined on both wins on the occurrence: the
+//! override is per property, keyed by set name and property name.
+//!
+//! Values decode into the typed `PropertyValue`, with measure units taken from
+//! the property itself or the project's IfcUnitAssignment.
+
+use crate::decoder::EntityDecoder;
+use crate::generated::IfcType;
+use crate::parser::EntityScanner;
+use crate::schema_gen::{AttributeValue, DecodedEntity};
+use crate::units::UnitContext;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Where an effective property or material association comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertySource {
+    /// Set assigned to the element itself
+    Occurrence,
+    /// Set inherited from the element's type object
+    Type,
+}
+
+impl PropertySource {
+    /// Lower-case label ("occurrence" or "type")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Occurrence => "occurrence",
+            Self::Type => "type",
+        }
+    }
+}
+
+/// One property of an element after inheritance is applied
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveProperty {
+    /// IfcPropertySet or IfcElementQuantity holding the property
+    pub set_id: u32,
+    /// Name of that set
+    pub set_name: String,
+    /// IfcProperty or IfcPhysicalQuantity entity
+    pub property_id: u32,
+    /// Property name
+    pub name: String,
+    /// Whether the set is the element's own or its type's
+    pub source: PropertySource,
+}
+
+/// Typed value of a property, decoded from an IfcValue
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum PropertyValue {
+    /// IfcReal or an untyped number
+    Real(f64),
+    /// IfcInteger or an untyped integer
+    Integer(i64),
+    /// IfcBoolean
+    Boolean(bool),
+    /// IfcLogical; `None` is UNKNOWN
+    Logical(Option<bool>),
+    /// IfcLabel, IfcText and other free text
+    Label(String),
+    /// IfcIdentifier
+    Identifier(String),
+    /// Numeric IfcMeasureValue with its unit, if any
+    Measure {
+        /// Value in the unit below
+        value: f64,
+        /// IFC measure type (e.g. "IFCLENGTHMEASURE")
+        measure_type: String,
+        /// Unit label; see `resolve_unit`
+        unit: Option<String>,
+    },
+    /// IfcDate, IfcDateTime, IfcTime or IfcDuration as written (ISO 8601);
+    /// IfcTimeStamp converted to UTC
+    DateTime(String),
+}
+
+impl PropertyValue {
+    /// Decode an IfcValue attribute, unwrapping typed values like `IFCLABEL('x')`
+    ///
+    /// Measures come back without a unit; call `resolve_unit` to add it.
+    pub fn from_attribute(value: &AttributeValue) -> Option<Self> {
+        // Typed values are lists of type name and value
+        let (type_name, inner) = match value {
+            AttributeValue::List(items) if items.len() == 2 => match &items[0] {
+                AttributeValue::String(type_name) => (Some(type_name.as_str()), &items[1]),
+                _ => return None,
+            },
+            other => (None, other),
+        };
+
+        let Some(type_name) = type_name else {
+            return match inner {
+                AttributeValue::Float(f) => Some(Self::Real(*f)),
+                AttributeValue::Integer(i) => Some(Self::Integer(*i)),
+                AttributeValue::String(s) | AttributeValue::Enum(s) => Some(Self::Label(s.clone())),
+                _ => None,
+            };
+        };
+
+        let upper = type_name.to_ascii_uppercase();
+        let value = match (upper.as_str(), inner) {
+            ("IFCBOOLEAN", AttributeValue::Enum(e)) => Self::Boolean(e == "T"),
+            ("IFCLOGICAL", AttributeValue::Enum(e)) => Self::Logical(match e.as_str() {
+                "T" => Some(true),
+                "F" => Some(false),
+                _ => None,
+            }),
+            ("IFCIDENTIFIER", AttributeValue::String(s)) => Self::Identifier(s.clone()),
+            ("IFCDATE" | "IFCDATETIME" | "IFCTIME" | "IFCDURATION", AttributeValue::String(s)) => {
+                Self::DateTime(s.clone())
+            }
+            ("IFCTIMESTAMP", AttributeValue::Integer(seconds)) => {
+                Self::DateTime(format_timestamp(*seconds))
+            }
+            ("IFCINTEGER", _) => Self::Integer(inner.as_int()?),
+            ("IFCREAL", _) => Self::Real(inner.as_float()?),
+            (measure, AttributeValue::Float(_) | AttributeValue::Integer(_))
+                if measure.ends_with("MEASURE") || measure == "IFCPARAMETERVALUE" =>
+            {
+                Self::Measure {
+                    value: inner.as_float()?,
+                    measure_type: upper.clone(),
+                    unit: None,
+                }
+            }
+            (_, AttributeValue::String(s) | AttributeValue::Enum(s)) => Self::Label(s.clone()),
+            (_, AttributeValue::Float(f)) => Self::Real(*f),
+            (_, AttributeValue::Integer(i)) => Self::Integer(*i),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Set the unit of a measure: the property's own unit if given, otherwise
+    /// the project unit for the measure type
+    pub fn resolve_unit(&mut self, explicit: Option<&str>, units: &UnitContext) {
+        if let Self::Measure {
+            measure_type, unit, ..
+        } = self
+        {
+            *unit = explicit
+                .or_else(|| units.measure_label(measure_type))
+                .map(|s| s.to_string());
+        }
+    }
+
+    /// Variant name ("real", "integer", "boolean", "logical", "label",
+    /// "identifier", "measure" or "date_time")
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Real(_) => "real",
+            Self::Integer(_) => "integer",
+            Self::Boolean(_) => "boolean",
+            Self::Logical(_) => "logical",
+            Self::Label(_) => "label",
+            Self::Identifier(_) => "identifier",
+            Self::Measure { .. } => "measure",
+            Self::DateTime(_) => "date_time",
+        }
+    }
+
+    /// Numeric value of reals, integers and measures
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Real(v) | Self::Measure { value: v, .. } => Some(*v),
+            Self::Integer(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// Value of booleans and known logicals
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Boolean(b) | Self::Logical(Some(b)) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Text of labels, identifiers and dates
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Label(s) | Self::Identifier(s) | Self::DateTime(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Measure type of a measure
+    pub fn measure_type(&self) -> Option<&str> {
+        match self {
+            Self::Measure { measure_type, .. } => Some(measure_type),
+            _ => None,
+        }
+    }
+
+    /// Unit of a measure
+    pub fn unit(&self) -> Option<&str> {
+        match self {
+            Self::Measure { unit, .. } => unit.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Real(v) => write!(f, "{}", v),
+            Self::Integer(i) => write!(f, "{}", i),
+            Self::Boolean(b) | Self::Logical(Some(b)) => write!(f, "{}", b),
+            Self::Logical(None) => f.write_str("unknown"),
+            Self::Label(s) | Self::Identifier(s) | Self::DateTime(s) => f.write_str(s),
+            Self::Measure {
+                value,
+                unit: Some(unit),
+                ..
+            } => write!(f, "{} {}", value, unit),
+            Self::Measure { value, .. } => write!(f, "{}", value),
+        }
+    }
+}
+
+/// ISO 8601 UTC date-time of an IfcTimeStamp (seconds since 1970-01-01)
+fn format_timestamp(seconds: i64) -> String {
+    let days = seconds.div_euclid(86_400);
+    let secs = seconds.rem_euclid(86_400);
+    // Civil date from days since epoch (proleptic Gregorian)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/// Property set assignments of a model, including type objects
+#[derive(Debug, Clone, Default)]
+pub struct PropertyResolver {
+    /// Element -> sets from IfcRelDefinesByProperties
+    occurrence_sets: FxHashMap<u32, Vec<u32>>,
+    /// Element -> type object from IfcRelDefinesByType
+    element_types: FxHashMap<u32, u32>,
+    /// Type object -> HasPropertySets
+    type_sets: FxHashMap<u32, Vec<u32>>,
+}
+
+impl PropertyResolver {
+    /// Collect set assignments and type objects from a file
+    pub fn from_content(content: &str, decoder: &mut EntityDecoder) -> Self {
+        let mut resolver = Self::default();
+        let mut scanner = EntityScanner::new(content);
+        while let Some((_, type_name, start, end)) = scanner.next_entity() {
+            if Self::is_assignment(type_name) {
+                if let Ok(entity) = decoder.decode_at(start, end) {
+                    resolver.add_assignment(&entity);
+                }
+            }
+        }
+        resolver.load_type_sets(decoder);
+        resolver
+    }
+
+    /// Check if an upper-case STEP type name is a relationship read by `add_assignment`
+    pub(crate) fn is_assignment(type_name: &str) -> bool {
+        matches!(
+            type_name,
+            "IFCRELDEFINESBYPROPERTIES" | "IFCRELDEFINESBYTYPE"
+        )
+    }
+
+    /// Record an IfcRelDefinesByProperties or IfcRelDefinesByType
+    pub(crate) fn add_assignment(&mut self, relationship: &DecodedEntity) {
+        // Both: [4]=RelatedObjects, [5]=RelatingPropertyDefinition/RelatingType
+        let (Some(objects), Some(relating)) = (relationship.get_list(4), relationship.get_ref(5))
+        else {
+            return;
+        };
+        let is_type = relationship.ifc_type == IfcType::IfcRelDefinesByType;
+        for object in objects.iter().filter_map(AttributeValue::as_entity_ref) {
+            if is_type {
+                self.element_types.insert(object, relating);
+            } else {
+                self.occurrence_sets
+                    .entry(object)
+                    .or_default()
+                    .push(relating);
+            }
+        }
+    }
+
+    /// Read HasPropertySets of every recorded type object
+    pub(crate) fn load_type_sets(&mut self, decoder: &mut EntityDecoder) {
+        let types: FxHashSet<u32> = self.element_types.values().copied().collect();
+        for type_id in types {
+            let Ok(type_object) = decoder.decode_by_id(type_id) else {
+                continue;
+            };
+            // IfcTypeObject: [5]=HasPropertySets
+            let sets: Vec<u32> = type_object
+                .get_list(5)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(AttributeValue::as_entity_ref)
+                .collect();
+            if !sets.is_empty() {
+                self.type_sets.insert(type_id, sets);
+            }
+        }
+    }
+
+    /// Type object of an element
+    pub fn element_type(&self, element: u32) -> Option<u32> {
+        self.element_types.get(&element).copied()
+    }
+
+    /// Elements with at least one direct or inherited set
+    pub fn elements(&self) -> impl Iterator<Item = u32> + '_ {
+        let typed = self
+            .element_types
+            .iter()
+            .filter(|(element, type_id)| {
+                self.type_sets.contains_key(type_id) && !self.occurrence_sets.contains_key(element)
+            })
+            .map(|(&element, _)| element);
+        self.occurrence_sets.keys().copied().chain(typed)
+    }
+
+    /// Sets of an element, type sets first so that later entries take precedence
+    pub fn set_ids(&self, element: u32) -> Vec<(u32, PropertySource)> {
+        let inherited = self
+            .element_type(element)
+            .and_then(|type_id| self.type_sets.get(&type_id))
+            .into_iter()
+            .flatten()
+            .map(|&set| (set, PropertySource::Type));
+        let own = self
+            .occurrence_sets
+            .get(&element)
+            .into_iter()
+            .flatten()
+            .map(|&set| (set, PropertySource::Occurrence));
+        inherited.chain(own).collect()
+    }
+
+    /// Properties of an element with occurrence values overriding type values
+    ///
+    /// Members of IfcPropertySet and IfcElementQuantity sets are returned in
+    /// set order; an overridden type property is replaced in place.
+    pub fn effective_properties(
+        &self,
+        element: u32,
+        decoder: &mut EntityDecoder,
+    ) -> Vec<EffectiveProperty> {
+        let mut properties: Vec<EffectiveProperty> = Vec::new();
+        let mut positions: FxHashMap<(String, String), usize> = FxHashMap::default();
+
+        for (set_id, source) in self.set_ids(element) {
+            let Ok(set) = decoder.decode_by_id(set_id) else {
+                continue;
+            };
+            // IfcPropertySet.HasProperties / IfcElementQuantity.Quantities
+            let members = match set.ifc_type {
+                IfcType::IfcPropertySet => set.get_list(4),
+                IfcType::IfcElementQuantity => set.get_list(5),
+                _ => None,
+            };
+            let set_name = set.get_string(2).unwrap_or_default().to_string();
+
+            for member in members
+                .unwrap_or_default()
+                .iter()
+                .filter_map(AttributeValue::as_entity_ref)
+            {
+                let Ok(property) = decoder.decode_by_id(member) else {
+                    continue;
+                };
+                let Some(name) = property.get_string(0) else {
+                    continue;
+                };
+                let effective = EffectiveProperty {
+                    set_id,
+                    set_name: set_name.clone(),
+                    property_id: member,
+                    name: name.to_string(),
+                    source,
+                };
+                match positions.get(&(set_name.clone(), effective.name.clone())) {
+                    // Occurrence replaces type; within one source the first set wins
+                    Some(&index) => {
+                        if source == PropertySource::Occurrence
+                            && properties[index].source == PropertySource::Type
+                        {
+                            properties[index] = effective;
+                        }
+                    }
+                    None => {
+                        positions
+                            .insert((set_name.clone(), effective.name.clone()), properties.len());
+                        properties.push(effective);
+                    }
+                }
+            }
+        }
+
+        properties
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{decoder, MODEL};
+
+    fn resolver() -> (PropertyResolver, EntityDecoder<'static>) {
+        let mut decoder = decoder();
+        (PropertyResolver::from_content(MODEL, &mut decoder), decoder)
+    }
+
+    fn summary(properties: &[EffectiveProperty]) -> Vec<(&str, u32, PropertySource)> {
+        properties
+            .iter()
+            .map(|p| (p.name.as_str(), p.property_id, p.source))
+            .collect()
+    }
+
+    #[test]
+    fn test_type_properties_are_inherited() {
+        let (resolver, mut decoder) = resolver();
+        assert_eq!(resolver.element_type(20), Some(45));
+        assert_eq!(
+            summary(&resolver.effective_properties(20, &mut decoder)),
+            vec![
+                ("FireRating", 40, PropertySource::Type),
+                ("IsExternal", 41, PropertySource::Type),
+                ("Width", 43, PropertySource::Type),
+                ("Area", 62, PropertySource::Occurrence),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_occurrence_overrides_type_per_property() {
+        let (resolver, mut decoder) = resolver();
+        let properties = resolver.effective_properties(21, &mut decoder);
+        assert_eq!(
+            summary(&properties),
+            vec![
+                ("FireRating", 47, PropertySource::Occurrence),
+                ("IsExternal", 41, PropertySource::Type),
+                ("Width", 43, PropertySource::Type),
+            ]
+        );
+        assert_eq!(properties[0].set_id, 48);
+        assert_eq!(properties[0].set_name, "Pset_DoorCommon");
+    }
+
+    #[test]
+    fn test_untyped_element_and_listing() {
+        let (resolver, mut decoder) = resolver();
+        assert_eq!(resolver.element_type(22), None);
+        assert_eq!(
+            summary(&resolver.effective_properties(22, &mut decoder)),
+            vec![("FireRating", 47, PropertySource::Occurrence)]
+        );
+
+        let mut elements: Vec<u32> = resolver.elements().collect();
+        elements.sort_unstable();
+        assert_eq!(elements, vec![20, 21, 22, 23]);
+        assert!(resolver.effective_properties(99, &mut decoder).is_empty());
+    }
+
+    #[test]
+    fn test_property_value_decoding() {
+        let mut decoder = decoder();
+        let units = UnitContext::from_content(MODEL, &mut decoder);
+        let mut value = |id: u32| {
+            let property = decoder.decode_by_id(id).unwrap();
+            let mut value = PropertyValue::from_attribute(property.get(2).unwrap()).unwrap();
+            let explicit = property
+                .get_ref(3)
+                .and_then(|unit| decoder.decode_by_id(unit).ok())
+                .and_then(|unit| crate::units::unit_label(&unit, &mut decoder));
+            value.resolve_unit(explicit.as_deref(), &units);
+            value
+        };
+
+        let length = value(50);
+        assert_eq!(length.as_f64(), Some(200.0));
+        assert_eq!(length.measure_type(), Some("IFCLENGTHMEASURE"));
+        assert_eq!(length.unit(), Some("millimetre"));
+        assert_eq!(length.to_string(), "200 millimetre");
+        assert_eq!(value(51).unit(), Some("square_metre"));
+        // The property's own unit wins over the project unit
+        assert_eq!(value(52).unit(), Some("centimetre"));
+        assert_eq!(value(53), PropertyValue::Boolean(true));
+        assert_eq!(value(54), PropertyValue::Logical(None));
+        assert_eq!(value(55), PropertyValue::Identifier("W-01".to_string()));
+        assert_eq!(value(56).as_text(), Some("Oak"));
+        assert_eq!(value(57), PropertyValue::Integer(3));
+        assert_eq!(
+            value(58),
+            PropertyValue::DateTime("2000-02-29T00:00:00Z".to_string())
+        );
+        let count = value(59);
+        assert_eq!((count.kind(), count.unit()), ("measure", None));
+    }
+}
@@ -0,0 +1,1097 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Element Queries
+//!
+//! A small text syntax for selecting products of a decoded model, answered
+//! from a `ModelIndex` that is built once per model:
+//!
+//! ```text
+//! IfcDoor where storey = "Level 2" and Pset_DoorCommon.FireRating = EI30
+//! IfcWall, IfcSlab where material ~ concrete and not classification = Ss_25_10
+//! * where (zone = "Fire Zone 1" or space = 101) and Qto_SpaceBaseQuantities.NetFloorArea > 20
+//! ```
+//!
+//! A query is a comma-separated list of IFC types (subtypes included, `*`
+//! or nothing for all products), optionally followed by `where` and a
+//! condition. Conditions combine predicates with `and`, `or`, `not` and
+//! parentheses. A predicate is a key, optionally followed by an operator
+//! (`= != < <= > >= ~`) and a value; without an operator it checks that the
+//! key is present. Keywords and names compare case-insensitively; values
+//! are bare words, numbers, `#id`s or quoted strings.
+//!
+//! | Key | Matches |
+//! |-----|---------|
+//! | `type` | IFC type, subtypes included |
+//! | `storey`, `building`, `site`, `space`, `facility`, `zone`, `spatial` | Containing spatial element or zone by name, LongName, GlobalId or `#id` |
+//! | `name`, `description`, `objecttype`, `tag`, `globalid` | Element attribute |
+//! | `material` | Material, layer, constituent or set name, or material category |
+//! | `classification` | Reference code or name, parent codes or classification name |
+//! | `Pset.Property`, `*.Property`, `Property` | Property or quantity value, type objects included |
+//!
+//! ```rust,ignore
+//! let mut decoder = EntityDecoder::new(content);
+//! let index = ModelIndex::build(content, &mut decoder);
+//! let doors = index.query("IfcDoor where storey = 'Level 2'", &mut decoder)?;
+//! ```
+
+use crate::decoder::EntityDecoder;
+use crate::error::{Error, Result};
+use crate::filter::{compare, member_value, PropertyOp, PropertyPredicate, PropertyValue};
+use crate::generated::IfcType;
+use crate::materials::MaterialResolver;
+use crate::parser::EntityScanner;
+use crate::properties::PropertyResolver;
+use crate::references::ReferenceResolver;
+use crate::schema_gen::{AttributeValue, DecodedEntity};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Parsed element query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    /// Product types, subtypes included; all products if empty
+    pub types: Vec<IfcType>,
+    /// Condition after `where`
+    pub condition: Option<Condition>,
+}
+
+/// Deepest condition nesting accepted by `Query::parse` and `ModelIndex::execute`
+pub const MAX_CONDITION_DEPTH: usize = 64;
+
+/// Boolean combination of predicates
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// All must hold
+    And(Vec<Condition>),
+    /// Any must hold
+    Or(Vec<Condition>),
+    /// Must not hold
+    Not(Box<Condition>),
+    /// Single predicate
+    Predicate(Predicate),
+}
+
+impl Condition {
+    /// Nesting depth, a single predicate being 1
+    ///
+    /// Walks the tree with an explicit stack, so it is safe on conditions of
+    /// any depth.
+    pub fn depth(&self) -> usize {
+        let mut deepest = 0;
+        let mut pending = vec![(self, 1)];
+        while let Some((condition, depth)) = pending.pop() {
+            deepest = deepest.max(depth);
+            match condition {
+                Self::And(items) | Self::Or(items) => {
+                    pending.extend(items.iter().map(|item| (item, depth + 1)))
+                }
+                Self::Not(inner) => pending.push((inner, depth + 1)),
+                Self::Predicate(_) => {}
+            }
+        }
+        deepest
+    }
+}
+
+/// Spatial element or zone level of a spatial predicate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialLevel {
+    /// IfcSite
+    Site,
+    /// IfcFacility, including IfcBuilding
+    Facility,
+    /// IfcBuilding
+    Building,
+    /// IfcBuildingStorey
+    Storey,
+    /// IfcSpace
+    Space,
+    /// IfcZone, through group assignment
+    Zone,
+    /// Any spatial element
+    Any,
+}
+
+impl SpatialLevel {
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "site" => Some(Self::Site),
+            "facility" => Some(Self::Facility),
+            "building" => Some(Self::Building),
+            "storey" => Some(Self::Storey),
+            "space" => Some(Self::Space),
+            "zone" => Some(Self::Zone),
+            "spatial" => Some(Self::Any),
+            _ => None,
+        }
+    }
+
+    /// IFC type of the level, subtypes included
+    pub fn ifc_type(&self) -> IfcType {
+        match self {
+            Self::Site => IfcType::IfcSite,
+            Self::Facility => IfcType::IfcFacility,
+            Self::Building => IfcType::IfcBuilding,
+            Self::Storey => IfcType::IfcBuildingStorey,
+            Self::Space => IfcType::IfcSpace,
+            Self::Zone => IfcType::IfcZone,
+            Self::Any => IfcType::IfcSpatialElement,
+        }
+    }
+}
+
+/// Element attribute of an attribute predicate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attribute {
+    /// IfcRoot.GlobalId
+    GlobalId,
+    /// IfcRoot.Name
+    Name,
+    /// IfcRoot.Description
+    Description,
+    /// IfcObject.ObjectType
+    ObjectType,
+    /// IfcElement.Tag
+    Tag,
+}
+
+impl Attribute {
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "globalid" => Some(Self::GlobalId),
+            "name" => Some(Self::Name),
+            "description" => Some(Self::Description),
+            "objecttype" => Some(Self::ObjectType),
+            "tag" => Some(Self::Tag),
+            _ => None,
+        }
+    }
+
+    /// Attribute index on an entity of the given type
+    fn index(&self, ifc_type: IfcType) -> Option<usize> {
+        match self {
+            Self::GlobalId => Some(0),
+            Self::Name => Some(2),
+            Self::Description => Some(3),
+            Self::ObjectType => Some(4),
+            Self::Tag => ifc_type.is_subtype_of(IfcType::IfcElement).then_some(7),
+        }
+    }
+}
+
+/// Single condition on an element
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    /// `type = IfcWall`: type check, subtypes included (`=` or `!=`)
+    Type { ifc_type: IfcType, op: PropertyOp },
+    /// `storey = "Level 2"`: containing spatial element or zone
+    Spatial {
+        level: SpatialLevel,
+        op: PropertyOp,
+        value: String,
+    },
+    /// `name ~ door`: element attribute
+    Attribute {
+        attribute: Attribute,
+        op: PropertyOp,
+        value: String,
+    },
+    /// `material = concrete`: associated material names
+    Material { op: PropertyOp, value: String },
+    /// `classification = Ss_25_10`: associated classification references
+    Classification { op: PropertyOp, value: String },
+    /// `Pset_DoorCommon.FireRating = EI30`: property or quantity value
+    Property(PropertyPredicate),
+}
+
+impl Query {
+    /// Parse a query, see the module documentation for the syntax
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut parser = Parser::new(text)?;
+        let mut types = Vec::new();
+        let mut all = false;
+        if !parser.at_end() && !parser.at_keyword("where") {
+            loop {
+                let (position, name) = parser.value()?;
+                if name == "*" {
+                    all = true;
+                } else {
+                    types.push(parse_type(&name, position)?);
+                }
+                if !parser.eat(&Token::Comma) {
+                    break;
+                }
+            }
+        }
+        if all {
+            types.clear();
+        }
+
+        let condition = if parser.eat_keyword("where") {
+            Some(parser.condition(0)?)
+        } else {
+            None
+        };
+        if !parser.at_end() {
+            return Err(parser.unexpected());
+        }
+
+        Ok(Self { types, condition })
+    }
+}
+
+fn parse_type(name: &str, position: usize) -> Result<IfcType> {
+    if name.is_empty() {
+        return Err(Error::parse(position, "Expected an IFC type"));
+    }
+    match IfcType::from_str(name) {
+        IfcType::Unknown(_) => Err(Error::InvalidIfcType(name.to_string())),
+        ifc_type => Ok(ifc_type),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Name or value, split on unquoted dots; `quoted` if any part was quoted
+    Term {
+        parts: Vec<String>,
+        quoted: bool,
+    },
+    Op(PropertyOp),
+    Comma,
+    Open,
+    Close,
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(position, c)) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '(' | ')' | ',' => {
+                chars.next();
+                match c {
+                    '(' => Token::Open,
+                    ')' => Token::Close,
+                    _ => Token::Comma,
+                }
+            }
+            '=' | '!' | '<' | '>' | '~' => {
+                chars.next();
+                let with_eq = chars.next_if(|&(_, next)| next == '=').is_some();
+                Token::Op(match (c, with_eq) {
+                    ('=', false) => PropertyOp::Eq,
+                    ('!', true) => PropertyOp::Ne,
+                    ('<', false) => PropertyOp::Lt,
+                    ('<', true) => PropertyOp::Le,
+                    ('>', false) => PropertyOp::Gt,
+                    ('>', true) => PropertyOp::Ge,
+                    ('~', false) => PropertyOp::Contains,
+                    _ => return Err(Error::parse(position, "Invalid operator")),
+                })
+            }
+            _ => {
+                let mut parts = vec![String::new()];
+                let mut quoted = false;
+                while let Some(&(start, c)) = chars.peek() {
+                    match c {
+                        '\'' | '"' => {
+                            chars.next();
+                            quoted = true;
+                            loop {
+                                match chars.next() {
+                                    Some((_, q)) if q == c => break,
+                                    Some((_, other)) => parts.last_mut().unwrap().push(other),
+                                    None => return Err(Error::parse(start, "Unterminated string")),
+                                }
+                            }
+                        }
+                        '.' => {
+                            chars.next();
+                            parts.push(String::new());
+                        }
+                        c if c.is_whitespace() || "(),=!<>~".contains(c) => break,
+                        c => {
+                            chars.next();
+                            parts.last_mut().unwrap().push(c);
+                        }
+                    }
+                }
+                Token::Term { parts, quoted }
+            }
+        };
+        tokens.push((position, token));
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens of a query
+///
+/// `depth` counts the condition levels above the current token, which bounds
+/// both the recursion here and the depth of the resulting `Condition`.
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    len: usize,
+}
+
+impl Parser {
+    fn new(text: &str) -> Result<Self> {
+        Ok(Self {
+            tokens: tokenize(text)?,
+            next: 0,
+            len: text.len(),
+        })
+    }
+
+    fn at_end(&self) -> bool {
+        self.next >= self.tokens.len()
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, token)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.next)
+            .map_or(self.len, |(position, _)| *position)
+    }
+
+    fn unexpected(&self) -> Error {
+        match self.peek() {
+            Some(_) => Error::parse(self.position(), "Unexpected token"),
+            None => Error::parse(self.len, "Unexpected end of query"),
+        }
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.peek() == Some(token);
+        if found {
+            self.next += 1;
+        }
+        found
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Term { parts, quoted: false })
+            if parts.len() == 1 && parts[0].eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.at_keyword(keyword);
+        if found {
+            self.next += 1;
+        }
+        found
+    }
+
+    /// Next term with its dot-separated parts
+    fn term(&mut self) -> Result<(usize, Vec<String>, bool)> {
+        let position = self.position();
+        match self.peek() {
+            Some(Token::Term { parts, quoted }) => {
+                let term = (position, parts.clone(), *quoted);
+                self.next += 1;
+                Ok(term)
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// Next term as a value, dots kept
+    fn value(&mut self) -> Result<(usize, String)> {
+        let (position, parts, _) = self.term()?;
+        Ok((position, parts.join(".")))
+    }
+
+    fn condition(&mut self, depth: usize) -> Result<Condition> {
+        let mut any = vec![self.conjunction(depth + 1)?];
+        while self.eat_keyword("or") {
+            any.push(self.conjunction(depth + 1)?);
+        }
+        Ok(if any.len() == 1 {
+            any.pop().unwrap()
+        } else {
+            Condition::Or(any)
+        })
+    }
+
+    fn conjunction(&mut self, depth: usize) -> Result<Condition> {
+        let mut all = vec![self.unary(depth + 1)?];
+        while self.eat_keyword("and") {
+            all.push(self.unary(depth + 1)?);
+        }
+        Ok(if all.len() == 1 {
+            all.pop().unwrap()
+        } else {
+            Condition::And(all)
+        })
+    }
+
+    fn unary(&mut self, depth: usize) -> Result<Condition> {
+        if depth > MAX_CONDITION_DEPTH {
+            return Err(Error::parse(
+                self.position(),
+                format!("Query nested deeper than {} levels", MAX_CONDITION_DEPTH),
+            ));
+        }
+        if self.eat_keyword("not") {
+            return Ok(Condition::Not(Box::new(self.unary(depth + 1)?)));
+        }
+        if self.eat(&Token::Open) {
+            let condition = self.condition(depth)?;
+            if !self.eat(&Token::Close) {
+                return Err(self.unexpected());
+            }
+            return Ok(condition);
+        }
+        self.predicate().map(Condition::Predicate)
+    }
+
+    fn predicate(&mut self) -> Result<Predicate> {
+        let (position, parts, quoted) = self.term()?;
+        let op = match self.peek() {
+            Some(Token::Op(op)) => {
+                let op = *op;
+                self.next += 1;
+                op
+            }
+            _ => PropertyOp::Exists,
+        };
+        let (value_position, value) = match op {
+            PropertyOp::Exists => (position, String::new()),
+            _ => self.value()?,
+        };
+
+        let key = (!quoted && parts.len() == 1).then(|| parts[0].to_ascii_lowercase());
+        let text_op = |op: PropertyOp| match op {
+            PropertyOp::Exists | PropertyOp::Eq | PropertyOp::Ne | PropertyOp::Contains => Ok(op),
+            _ => Err(Error::parse(
+                position,
+                format!("'{}' only supports =, != and ~", parts[0]),
+            )),
+        };
+
+        if let Some(key) = key.as_deref() {
+            if key == "type" {
+                if !matches!(op, PropertyOp::Eq | PropertyOp::Ne) {
+                    return Err(Error::parse(position, "'type' only supports = and !="));
+                }
+                let ifc_type = parse_type(&value, value_position)?;
+                return Ok(Predicate::Type { ifc_type, op });
+            }
+            if let Some(level) = SpatialLevel::from_key(key) {
+                let op = text_op(op)?;
+                return Ok(Predicate::Spatial { level, op, value });
+            }
+            if let Some(attribute) = Attribute::from_key(key) {
+                return Ok(Predicate::Attribute {
+                    attribute,
+                    op,
+                    value,
+                });
+            }
+            match key {
+                "material" => {
+                    let op = text_op(op)?;
+                    return Ok(Predicate::Material { op, value });
+                }
+                "classification" => {
+                    let op = text_op(op)?;
+                    return Ok(Predicate::Classification { op, value });
+                }
+                _ => {}
+            }
+        }
+
+        // `Pset.Property`, `*.Property` or `Property`
+        let (pset, property) = match parts.split_first() {
+            Some((pset, rest)) if !rest.is_empty() => {
+                (Some(pset.clone()).filter(|p| p != "*"), rest.join("."))
+            }
+            _ => (None, parts.join(".")),
+        };
+        if property.is_empty() || pset.as_deref() == Some("") {
+            return Err(Error::parse(position, "Invalid property name"));
+        }
+        Ok(Predicate::Property(PropertyPredicate {
+            pset,
+            property,
+            op,
+            value,
+        }))
+    }
+}
+
+/// Spatial element or zone, as matched by spatial predicates
+#[derive(Debug, Clone)]
+struct Structure {
+    ifc_type: IfcType,
+    global_id: String,
+    name: Option<String>,
+    long_name: Option<String>,
+}
+
+impl Structure {
+    fn matches(&self, id: u32, op: PropertyOp, value: &str) -> bool {
+        let names = self.name.iter().chain(&self.long_name);
+        match op {
+            PropertyOp::Exists => true,
+            PropertyOp::Contains => {
+                let value = value.to_lowercase();
+                names
+                    .into_iter()
+                    .any(|name| name.to_lowercase().contains(&value))
+            }
+            _ => {
+                value.strip_prefix('#').and_then(|v| v.parse::<u32>().ok()) == Some(id)
+                    || self.global_id == value
+                    || names
+                        .into_iter()
+                        .any(|name| name.eq_ignore_ascii_case(value))
+            }
+        }
+    }
+}
+
+/// Indexes of a model for answering queries
+///
+/// Types, spatial structure and zones are read with one scan; properties,
+/// materials and classifications through their resolvers. Per-element values
+/// are decoded lazily while a query runs, so the index itself stays small.
+#[derive(Debug, Clone, Default)]
+pub struct ModelIndex {
+    /// Product type -> products
+    products: FxHashMap<IfcType, Vec<u32>>,
+    /// Element -> spatial structure, part -> whole
+    parents: FxHashMap<u32, u32>,
+    /// Element -> spatial structures referencing it
+    references: FxHashMap<u32, Vec<u32>>,
+    /// Object -> zones it is assigned to
+    zones: FxHashMap<u32, Vec<u32>>,
+    /// Spatial elements and zones
+    structures: FxHashMap<u32, Structure>,
+    properties: PropertyResolver,
+    materials: MaterialResolver,
+    classifications: ReferenceResolver,
+}
+
+impl ModelIndex {
+    /// Build the indexes of a file
+    pub fn build(content: &str, decoder: &mut EntityDecoder) -> Self {
+        let mut index = Self::default();
+        let mut types: FxHashMap<&str, IfcType> = FxHashMap::default();
+        let mut assignments: Vec<(u32, Vec<u32>)> = Vec::new();
+
+        let mut scanner = EntityScanner::new(content);
+        while let Some((id, type_name, start, end)) = scanner.next_entity() {
+            match type_name {
+                "IFCRELCONTAINEDINSPATIALSTRUCTURE" | "IFCRELREFERENCEDINSPATIALSTRUCTURE" => {
+                    // [4]=RelatedElements, [5]=RelatingStructure
+                    let Ok(entity) = decoder.decode_at(start, end) else {
+                        continue;
+                    };
+                    let Some(structure) = entity.get_ref(5) else {
+                        continue;
+                    };
+                    for element in entity_refs(&entity, 4) {
+                        if entity.ifc_type == IfcType::IfcRelContainedInSpatialStructure {
+                            // Containment wins over aggregation
+                            index.parents.insert(element, structure);
+                        } else {
+                            index.references.entry(element).or_default().push(structure);
+                        }
+                    }
+                }
+                "IFCRELAGGREGATES" => {
+                    // [4]=RelatingObject, [5]=RelatedObjects
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        if let Some(whole) = entity.get_ref(4) {
+                            for part in entity_refs(&entity, 5) {
+                                index.parents.entry(part).or_insert(whole);
+                            }
+                        }
+                    }
+                }
+                "IFCRELASSIGNSTOGROUP" => {
+                    // [4]=RelatedObjects, [6]=RelatingGroup
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        if let Some(group) = entity.get_ref(6) {
+                            assignments.push((group, entity_refs(&entity, 4)));
+                        }
+                    }
+                }
+                _ if PropertyResolver::is_assignment(type_name) => {
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        index.properties.add_assignment(&entity);
+                    }
+                }
+                _ => {
+                    let ifc_type = *types
+                        .entry(type_name)
+                        .or_insert_with(|| IfcType::from_str(type_name));
+                    if ifc_type == IfcType::IfcZone
+                        || ifc_type.is_subtype_of(IfcType::IfcSpatialElement)
+                    {
+                        if let Ok(entity) = decoder.decode_at_with_id(id, start, end) {
+                            index.structures.insert(id, read_structure(&entity));
+                        }
+                    }
+                    if ifc_type.is_subtype_of(IfcType::IfcProduct) {
+                        index.products.entry(ifc_type).or_default().push(id);
+                    }
+                }
+            }
+        }
+
+        for (group, objects) in assignments {
+            let is_zone = index
+                .structures
+                .get(&group)
+                .is_some_and(|s| s.ifc_type == IfcType::IfcZone);
+            if is_zone {
+                for object in objects {
+                    index.zones.entry(object).or_default().push(group);
+                }
+            }
+        }
+
+        index.properties.load_type_sets(decoder);
+        index.materials = MaterialResolver::from_content(content, decoder);
+        index.classifications = ReferenceResolver::from_content(content, decoder);
+        index
+    }
+
+    /// Number of indexed products
+    pub fn product_count(&self) -> usize {
+        self.products.values().map(Vec::len).sum()
+    }
+
+    /// Parse and run a query, returning matching express ids in ascending order
+    pub fn query(&self, text: &str, decoder: &mut EntityDecoder) -> Result<Vec<u32>> {
+        self.execute(&Query::parse(text)?, decoder)
+    }
+
+    /// Run a parsed query, returning matching express ids in ascending order
+    ///
+    /// Conditions nested deeper than `MAX_CONDITION_DEPTH` are rejected, which
+    /// keeps compiling and evaluating them within a bounded recursion depth.
+    pub fn execute(&self, query: &Query, decoder: &mut EntityDecoder) -> Result<Vec<u32>> {
+        if let Some(depth) = query.condition.as_ref().map(Condition::depth) {
+            if depth > MAX_CONDITION_DEPTH {
+                return Err(Error::parse(
+                    0,
+                    format!("Query nested deeper than {} levels", MAX_CONDITION_DEPTH),
+                ));
+            }
+        }
+        let test = query.condition.as_ref().map(|c| self.compile(c));
+        let mut ids: Vec<u32> = Vec::new();
+        for (&ifc_type, products) in &self.products {
+            if !query.types.is_empty() && !query.types.iter().any(|t| ifc_type.is_subtype_of(*t)) {
+                continue;
+            }
+            for &id in products {
+                let mut element = Candidate::new(id, ifc_type);
+                if test
+                    .as_ref()
+                    .is_none_or(|test| self.eval(test, &mut element, decoder))
+                {
+                    ids.push(id);
+                }
+            }
+        }
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    /// Resolve spatial predicates to the structures they accept
+    fn compile<'q>(&self, condition: &'q Condition) -> Test<'q> {
+        match condition {
+            Condition::And(all) => Test::All(all.iter().map(|c| self.compile(c)).collect()),
+            Condition::Or(any) => Test::Any(any.iter().map(|c| self.compile(c)).collect()),
+            Condition::Not(inner) => Test::Not(Box::new(self.compile(inner))),
+            Condition::Predicate(Predicate::Spatial { level, op, value }) => {
+                let level_type = level.ifc_type();
+                // `!=` is the negation of `=`
+                let match_op = match op {
+                    PropertyOp::Ne => PropertyOp::Eq,
+                    op => *op,
+                };
+                let structures = self
+                    .structures
+                    .iter()
+                    .filter(|(&id, structure)| {
+                        structure.ifc_type.is_subtype_of(level_type)
+                            && structure.matches(id, match_op, value)
+                    })
+                    .map(|(&id, _)| id)
+                    .collect();
+                Test::Structures {
+                    structures,
+                    negate: *op == PropertyOp::Ne,
+                }
+            }
+            Condition::Predicate(predicate) => Test::Predicate(predicate),
+        }
+    }
+
+    fn eval(&self, test: &Test, element: &mut Candidate, decoder: &mut EntityDecoder) -> bool {
+        match test {
+            Test::All(all) => all.iter().all(|t| self.eval(t, element, decoder)),
+            Test::Any(any) => any.iter().any(|t| self.eval(t, element, decoder)),
+            Test::Not(inner) => !self.eval(inner, element, decoder),
+            Test::Structures { structures, negate } => {
+                let locations = element
+                    .locations
+                    .get_or_insert_with(|| self.locations(element.id));
+                locations.iter().any(|id| structures.contains(id)) != *negate
+            }
+            Test::Predicate(predicate) => self.test_predicate(predicate, element, decoder),
+        }
+    }
+
+    fn test_predicate(
+        &self,
+        predicate: &Predicate,
+        element: &mut Candidate,
+        decoder: &mut EntityDecoder,
+    ) -> bool {
+        match predicate {
+            Predicate::Type { ifc_type, op } => {
+                element.ifc_type.is_subtype_of(*ifc_type) == (*op == PropertyOp::Eq)
+            }
+            Predicate::Spatial { .. } => unreachable!("spatial predicates are compiled"),
+            Predicate::Attribute {
+                attribute,
+                op,
+                value,
+            } => {
+                let id = element.id;
+                let entity = element
+                    .entity
+                    .get_or_insert_with(|| decoder.decode_by_id(id).ok());
+                let text = attribute
+                    .index(element.ifc_type)
+                    .and_then(|index| entity.as_ref()?.get_string(index));
+                match text {
+                    Some(text) => compare(*op, value, &PropertyValue::Text(text.to_string())),
+                    None => *op == PropertyOp::Ne,
+                }
+            }
+            Predicate::Material { op, value } => {
+                let names = element
+                    .materials
+                    .get_or_insert_with(|| self.material_names(element.id, decoder));
+                match_names(names, *op, value)
+            }
+            Predicate::Classification { op, value } => {
+                let names = element
+                    .classifications
+                    .get_or_insert_with(|| self.classification_names(element.id, decoder));
+                match_names(names, *op, value)
+            }
+            Predicate::Property(predicate) => {
+                let id = element.id;
+                // Type-object properties count unless the occurrence overrides them
+                let values = element.properties.get_or_insert_with(|| {
+                    self.properties
+                        .effective_properties(id, decoder)
+                        .into_iter()
+                        .filter_map(|property| {
+                            let member = decoder.decode_by_id(property.property_id).ok()?;
+                            let value = member_value(&member)?;
+                            Some((property.set_name, property.name, value))
+                        })
+                        .collect()
+                });
+                values.iter().any(|(pset, name, value)| {
+                    predicate.names_match(pset, name) && predicate.test(value)
+                })
+            }
+        }
+    }
+
+    /// Spatial elements and zones an element is located in
+    ///
+    /// Follows containment and aggregation upwards, including structures
+    /// referencing the element and zones of the element or its containers.
+    fn locations(&self, id: u32) -> FxHashSet<u32> {
+        let mut locations = FxHashSet::default();
+        let mut pending: Vec<u32> = self.references.get(&id).cloned().unwrap_or_default();
+        pending.extend(self.parents.get(&id));
+        pending.extend(self.zones.get(&id).into_iter().flatten());
+        while let Some(next) = pending.pop() {
+            if next == id || !locations.insert(next) {
+                continue;
+            }
+            pending.extend(self.parents.get(&next));
+            pending.extend(self.zones.get(&next).into_iter().flatten());
+        }
+        locations
+    }
+
+    fn material_names(&self, id: u32, decoder: &mut EntityDecoder) -> Vec<String> {
+        let mut names = Vec::new();
+        for association in self.materials.materials(id, decoder) {
+            names.extend(association.set_name);
+            for part in association.parts {
+                names.extend(part.material_name);
+                names.extend(part.name);
+                names.extend(part.category);
+            }
+        }
+        names
+    }
+
+    fn classification_names(&self, id: u32, decoder: &mut EntityDecoder) -> Vec<String> {
+        let mut names = Vec::new();
+        for reference in self.classifications.classifications(id, decoder) {
+            names.extend(reference.identification);
+            names.extend(reference.name);
+            names.extend(reference.parents);
+            names.extend(reference.classification_name);
+        }
+        names
+    }
+}
+
+/// Condition with spatial predicates resolved against a `ModelIndex`
+enum Test<'q> {
+    All(Vec<Test<'q>>),
+    Any(Vec<Test<'q>>),
+    Not(Box<Test<'q>>),
+    Structures {
+        structures: FxHashSet<u32>,
+        negate: bool,
+    },
+    Predicate(&'q Predicate),
+}
+
+/// Element being tested, with values decoded on first use
+struct Candidate {
+    id: u32,
+    ifc_type: IfcType,
+    entity: Option<Option<DecodedEntity>>,
+    locations: Option<FxHashSet<u32>>,
+    materials: Option<Vec<String>>,
+    classifications: Option<Vec<String>>,
+    properties: Option<Vec<(String, String, PropertyValue)>>,
+}
+
+impl Candidate {
+    fn new(id: u32, ifc_type: IfcType) -> Self {
+        Self {
+            id,
+            ifc_type,
+            entity: None,
+            locations: None,
+            materials: None,
+            classifications: None,
+            properties: None,
+        }
+    }
+}
+
+/// Text predicate over a list of names: `!=` holds when none is equal
+fn match_names(names: &[String], op: PropertyOp, value: &str) -> bool {
+    match op {
+        PropertyOp::Exists => !names.is_empty(),
+        PropertyOp::Ne => !names.iter().any(|n| n.eq_ignore_ascii_case(value)),
+        PropertyOp::Contains => {
+            let value = value.to_lowercase();
+            names.iter().any(|n| n.to_lowercase().contains(&value))
+        }
+        _ => names.iter().any(|n| n.eq_ignore_ascii_case(value)),
+    }
+}
+
+fn read_structure(entity: &DecodedEntity) -> Structure {
+    // IfcSpatialElement: [7]=LongName; IfcZone (IFC4): [5]=LongName
+    let long_name = match entity.ifc_type {
+        IfcType::IfcZone => 5,
+        _ => 7,
+    };
+    Structure {
+        ifc_type: entity.ifc_type,
+        global_id: entity.get_string(0).unwrap_or_default().to_string(),
+        name: entity.get_string(2).map(str::to_string),
+        long_name: entity.get_string(long_name).map(str::to_string),
+    }
+}
+
+fn entity_refs(entity: &DecodedEntity, index: usize) -> Vec<u32> {
+    entity
+        .get_list(index)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(AttributeValue::as_entity_ref)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{decoder, MODEL};
+
+    fn run(text: &str) -> Vec<u32> {
+        let mut decoder = decoder();
+        let index = ModelIndex::build(MODEL, &mut decoder);
+        index.query(text, &mut decoder).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let query = Query::parse("IfcDoor, IfcWindow where storey = 'Level 2' and not (Pset_DoorCommon.FireRating ~ EI or *.Width >= 0.9)").unwrap();
+        assert_eq!(query.types, vec![IfcType::IfcDoor, IfcType::IfcWindow]);
+        let Some(Condition::And(all)) = query.condition else {
+            panic!("expected a conjunction");
+        };
+        assert_eq!(
+            all[0],
+            Condition::Predicate(Predicate::Spatial {
+                level: SpatialLevel::Storey,
+                op: PropertyOp::Eq,
+                value: "Level 2".to_string(),
+            })
+        );
+        let Condition::Not(inner) = &all[1] else {
+            panic!("expected a negation");
+        };
+        let Condition::Or(any) = inner.as_ref() else {
+            panic!("expected a disjunction");
+        };
+        assert_eq!(
+            any[1],
+            Condition::Predicate(Predicate::Property(PropertyPredicate {
+                pset: None,
+                property: "Width".to_string(),
+                op: PropertyOp::Ge,
+                value: "0.9".to_string(),
+            }))
+        );
+
+        assert_eq!(Query::parse("*").unwrap().types, vec![]);
+        assert_eq!(Query::parse("").unwrap(), Query::parse("*").unwrap());
+        assert!(Query::parse("WHERE name").unwrap().condition.is_some());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Query::parse("IfcNotAType"),
+            Err(Error::InvalidIfcType(_))
+        ));
+        for text in [
+            "IfcDoor where",
+            "IfcDoor where (name = a",
+            "IfcDoor where storey < 3",
+            "IfcDoor where name = 'open",
+            "IfcDoor where type ~ IfcWall",
+            "IfcDoor where name == a",
+            "IfcDoor IfcWall",
+            "IfcDoor where .Width > 1",
+        ] {
+            assert!(Query::parse(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = |levels: usize| {
+            format!(
+                "* where {}name = x{}",
+                "(not ".repeat(levels),
+                ")".repeat(levels)
+            )
+        };
+        let query = Query::parse(&nested(20)).unwrap();
+        assert!(query.condition.unwrap().depth() <= MAX_CONDITION_DEPTH);
+        assert!(Query::parse(&nested(40)).is_err());
+
+        // Fails fast instead of overflowing the stack
+        let text = format!("* where {}name = x", "not ".repeat(20000));
+        assert!(Query::parse(&text).is_err());
+
+        // Hand-built conditions are checked before they are evaluated
+        let mut condition = Condition::Predicate(Predicate::Material {
+            op: PropertyOp::Exists,
+            value: String::new(),
+        });
+        for _ in 0..MAX_CONDITION_DEPTH {
+            condition = Condition::Not(Box::new(condition));
+        }
+        let query = Query {
+            types: Vec::new(),
+            condition: Some(condition),
+        };
+        let mut decoder = decoder();
+        let index = ModelIndex::build(MODEL, &mut decoder);
+        assert!(index.execute(&query, &mut decoder).is_err());
+    }
+
+    #[test]
+    fn test_type_spatial_and_property() {
+        assert_eq!(
+            run("IfcDoor where storey = \"Level 1\" and Pset_DoorCommon.FireRating = EI30"),
+            vec![20]
+        );
+        // Door B overrides the rating of its type
+        assert_eq!(run("IfcDoor where FireRating = ei60"), vec![21, 22]);
+        // Contained in a space on the storey
+        assert_eq!(run("IfcDoor where storey = 'Level 2'"), vec![21, 22]);
+        // Spatial references and aggregated spaces count too
+        assert_eq!(run("* where storey = S8"), vec![9, 21, 22, 24, 25, 27]);
+        assert_eq!(run("IfcDoor where space and storey != #7"), vec![22]);
+        assert_eq!(run("IfcDoor where zone = 'fire zone 1'"), vec![22]);
+        assert_eq!(
+            run("IfcSpace where zone ~ fire and storey = 'Level 2'"),
+            vec![9]
+        );
+        assert_eq!(run("IfcWall"), vec![23, 24]);
+        assert_eq!(
+            run("IfcDoor where Qto_DoorBaseQuantities.Area > 1"),
+            vec![20]
+        );
+        assert_eq!(
+            run("IfcDoor where Qto_DoorBaseQuantities.Width >= 900"),
+            vec![20, 21]
+        );
+    }
+
+    #[test]
+    fn test_attributes_materials_and_classifications() {
+        assert_eq!(
+            run("IfcDoor, IfcColumn where name ~ 'door b' or tag = T20 or type = IfcColumn"),
+            vec![20, 21, 25]
+        );
+        assert_eq!(
+            run("IfcElement where material = concrete"),
+            vec![23, 24, 26]
+        );
+        assert_eq!(run("IfcElement where material = wood"), vec![20, 21]);
+        assert_eq!(
+            run("IfcDoor, IfcWindow where material != oak"),
+            vec![22, 27]
+        );
+        assert_eq!(run("IfcDoor where classification ~ uniclass"), vec![20, 22]);
+        assert_eq!(run("IfcElement where classification = Ss_25"), vec![23, 24]);
+        assert_eq!(
+            run("IfcDoor where classification = Pr_30_59_24 and not Qto_DoorBaseQuantities.Area > 1"),
+            vec![22]
+        );
+        assert_eq!(
+            run("IfcDoor where globalid = D21 or not material"),
+            vec![21, 22]
+        );
+    }
+}
@@ -0,0 +1,536 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Classification and document references
+//!
+//! IfcRelAssociatesClassification and IfcRelAssociatesDocument attach external
+//! references to elements and type objects:
+//!
+//! ```text
+//! IfcClassificationReference ─ReferencedSource→ IfcClassificationReference … → IfcClassification
+//!   (Identification, Name)                          (parent codes)                 (Name, Edition)
+//! IfcDocumentReference ─ReferencedDocument→ IfcDocumentInformation
+//! ```
+//!
+//! An element also carries the references of its type (IfcRelDefinesByType).
+//! An own classification replaces the type's reference from the same
+//! classification system; documents of both are kept.
+//!
+//! Both IFC2X3 (ItemReference, IfcCalendarDate editions,
+//! IfcDocumentInformation.DocumentReferences) and IFC4 layouts are read.
+
+use crate::decoder::EntityDecoder;
+use crate::generated::IfcType;
+use crate::parser::EntityScanner;
+use crate::properties::PropertySource;
+use crate::schema_gen::{AttributeValue, DecodedEntity};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Maximum number of ReferencedSource links followed up to the classification
+const MAX_REFERENCE_DEPTH: usize = 16;
+
+/// Classification code of an element, with the system it belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassificationReference {
+    /// Associated IfcClassificationReference, or IfcClassification when the
+    /// system itself is associated
+    pub reference_id: u32,
+    /// Whether the reference is the element's own or its type's
+    pub source: PropertySource,
+    /// Code, e.g. "Ss_25_10_30" (IFC4 Identification, IFC2X3 ItemReference)
+    pub identification: Option<String>,
+    /// Title of the code
+    pub name: Option<String>,
+    /// URI of the code
+    pub location: Option<String>,
+    /// IFC4 Description
+    pub description: Option<String>,
+    /// Codes (or names) of the parent references, from the top of the tree down
+    pub parents: Vec<String>,
+    /// IfcClassification at the end of the ReferencedSource chain
+    pub classification_id: Option<u32>,
+    /// Classification system name, e.g. "Uniclass 2015"
+    pub classification_name: Option<String>,
+    /// Publisher of the classification (IfcClassification.Source)
+    pub classification_source: Option<String>,
+    /// Edition of the classification
+    pub edition: Option<String>,
+    /// Edition date as written (IFC4) or as YYYY-MM-DD (IFC2X3 IfcCalendarDate)
+    pub edition_date: Option<String>,
+}
+
+/// Document associated with an element
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentReference {
+    /// Associated IfcDocumentReference, or IfcDocumentInformation when the
+    /// document itself is associated
+    pub reference_id: u32,
+    /// Whether the reference is the element's own or its type's
+    pub source: PropertySource,
+    /// Reference identifier (IFC4 Identification, IFC2X3 ItemReference)
+    pub identification: Option<String>,
+    /// Reference name
+    pub name: Option<String>,
+    /// URI or path of the referenced document or part
+    pub location: Option<String>,
+    /// IFC4 Description
+    pub description: Option<String>,
+    /// IfcDocumentInformation of the reference
+    pub document_id: Option<u32>,
+    /// Document identifier (IFC4 Identification, IFC2X3 DocumentId)
+    pub document_identification: Option<String>,
+    /// Document title
+    pub document_name: Option<String>,
+    /// Document description
+    pub document_description: Option<String>,
+    /// IFC4 document location
+    pub document_location: Option<String>,
+    /// Purpose of the document
+    pub purpose: Option<String>,
+    /// Intended use of the document
+    pub intended_use: Option<String>,
+    /// Document revision
+    pub revision: Option<String>,
+    /// IfcDocumentStatusEnum (DRAFT, FINALDRAFT, FINAL, REVISION, NOTDEFINED)
+    pub status: Option<String>,
+}
+
+/// Classification and document associations of elements and type objects in a file
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceResolver {
+    /// Object (element or type) → associated IfcClassificationSelect entities
+    classifications: FxHashMap<u32, Vec<u32>>,
+    /// Object (element or type) → associated IfcDocumentSelect entities
+    documents: FxHashMap<u32, Vec<u32>>,
+    /// Element → type object (IfcRelDefinesByType)
+    element_types: FxHashMap<u32, u32>,
+    /// Type objects seen in the file, which are not elements themselves
+    type_objects: FxHashSet<u32>,
+    /// IfcDocumentReference → IfcDocumentInformation listing it (IFC2X3)
+    reference_documents: FxHashMap<u32, u32>,
+}
+
+impl ReferenceResolver {
+    /// Collect classification and document associations from a file
+    pub fn from_content(content: &str, decoder: &mut EntityDecoder) -> Self {
+        let mut resolver = Self::default();
+        let mut scanner = EntityScanner::new(content);
+        while let Some((id, type_name, start, end)) = scanner.next_entity() {
+            match type_name {
+                "IFCRELASSOCIATESCLASSIFICATION"
+                | "IFCRELASSOCIATESDOCUMENT"
+                | "IFCRELDEFINESBYTYPE" => {
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        resolver.add_relationship(&entity);
+                    }
+                }
+                "IFCDOCUMENTINFORMATION" => {
+                    // IFC2X3: [3]=DocumentReferences (IFC4 has Location there)
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        for reference in entity_refs(&entity, 3) {
+                            resolver.reference_documents.insert(reference, id);
+                        }
+                    }
+                }
+                _ if (type_name.ends_with("TYPE") || type_name.ends_with("STYLE"))
+                    && IfcType::from_str(type_name).is_subtype_of(IfcType::IfcTypeObject) =>
+                {
+                    resolver.type_objects.insert(id);
+                }
+                _ => {}
+            }
+        }
+        resolver
+    }
+
+    /// Record an IfcRelAssociatesClassification, IfcRelAssociatesDocument or IfcRelDefinesByType
+    fn add_relationship(&mut self, relationship: &DecodedEntity) {
+        // All: [4]=RelatedObjects, [5]=RelatingClassification/RelatingDocument/RelatingType
+        let (Some(objects), Some(relating)) = (relationship.get_list(4), relationship.get_ref(5))
+        else {
+            return;
+        };
+        for object in objects.iter().filter_map(AttributeValue::as_entity_ref) {
+            match relationship.ifc_type {
+                IfcType::IfcRelDefinesByType => {
+                    self.element_types.insert(object, relating);
+                    self.type_objects.insert(relating);
+                }
+                IfcType::IfcRelAssociatesClassification => {
+                    self.classifications
+                        .entry(object)
+                        .or_default()
+                        .push(relating);
+                }
+                _ => self.documents.entry(object).or_default().push(relating),
+            }
+        }
+    }
+
+    /// Type object of an element
+    pub fn element_type(&self, element: u32) -> Option<u32> {
+        self.element_types.get(&element).copied()
+    }
+
+    /// Elements with a direct or inherited classification or document
+    pub fn elements(&self) -> Vec<u32> {
+        let has_references = |object: &u32| {
+            self.classifications.contains_key(object) || self.documents.contains_key(object)
+        };
+        let mut elements: FxHashSet<u32> = self
+            .classifications
+            .keys()
+            .chain(self.documents.keys())
+            .copied()
+            .filter(|object| !self.type_objects.contains(object))
+            .collect();
+        elements.extend(
+            self.element_types
+                .iter()
+                .filter(|(_, type_id)| has_references(type_id))
+                .map(|(&element, _)| element),
+        );
+        let mut elements: Vec<u32> = elements.into_iter().collect();
+        elements.sort_unstable();
+        elements
+    }
+
+    /// Associated entities of an element from `map`, own first, then its type's
+    fn associated(
+        &self,
+        map: &FxHashMap<u32, Vec<u32>>,
+        element: u32,
+    ) -> Vec<(u32, PropertySource)> {
+        let own = map
+            .get(&element)
+            .into_iter()
+            .flatten()
+            .map(|&id| (id, PropertySource::Occurrence));
+        let inherited = self
+            .element_type(element)
+            .and_then(|type_id| map.get(&type_id))
+            .into_iter()
+            .flatten()
+            .map(|&id| (id, PropertySource::Type));
+        own.chain(inherited).collect()
+    }
+
+    /// Resolve the classification references of an element
+    ///
+    /// Type references are dropped when the element has its own reference in
+    /// the same classification system.
+    pub fn classifications(
+        &self,
+        element: u32,
+        decoder: &mut EntityDecoder,
+    ) -> Vec<ClassificationReference> {
+        let mut references: Vec<ClassificationReference> = Vec::new();
+        for (id, source) in self.associated(&self.classifications, element) {
+            let Some(reference) = resolve_classification(id, source, decoder) else {
+                continue;
+            };
+            let replaced = source == PropertySource::Type
+                && references.iter().any(|own| {
+                    own.source == PropertySource::Occurrence
+                        && (own.reference_id == reference.reference_id
+                            || (own.classification_id.is_some()
+                                && own.classification_id == reference.classification_id))
+                });
+            if !replaced {
+                references.push(reference);
+            }
+        }
+        references
+    }
+
+    /// Resolve the document references of an element, own and inherited
+    pub fn documents(&self, element: u32, decoder: &mut EntityDecoder) -> Vec<DocumentReference> {
+        let mut references: Vec<DocumentReference> = Vec::new();
+        for (id, source) in self.associated(&self.documents, element) {
+            if references.iter().any(|r| r.reference_id == id) {
+                continue;
+            }
+            if let Some(reference) = self.resolve_document(id, source, decoder) {
+                references.push(reference);
+            }
+        }
+        references
+    }
+
+    /// Resolve an IfcDocumentSelect entity
+    fn resolve_document(
+        &self,
+        id: u32,
+        source: PropertySource,
+        decoder: &mut EntityDecoder,
+    ) -> Option<DocumentReference> {
+        let entity = decoder.decode_by_id(id).ok()?;
+        let mut reference = DocumentReference {
+            reference_id: id,
+            source,
+            identification: None,
+            name: None,
+            location: None,
+            description: None,
+            document_id: None,
+            document_identification: None,
+            document_name: None,
+            document_description: None,
+            document_location: None,
+            purpose: None,
+            intended_use: None,
+            revision: None,
+            status: None,
+        };
+
+        let information = match entity.ifc_type {
+            IfcType::IfcDocumentInformation => Some(entity),
+            IfcType::IfcDocumentReference => {
+                // [0]=Location, [1]=Identification/ItemReference, [2]=Name,
+                // IFC4: [3]=Description, [4]=ReferencedDocument
+                reference.location = string(&entity, 0);
+                reference.identification = string(&entity, 1);
+                reference.name = string(&entity, 2);
+                reference.description = string(&entity, 3);
+                entity
+                    .get_ref(4)
+                    .or_else(|| self.reference_documents.get(&id).copied())
+                    .and_then(|info| decoder.decode_by_id(info).ok())
+            }
+            _ => return None,
+        };
+
+        if let Some(info) = information {
+            // [0]=Identification/DocumentId, [1]=Name, [2]=Description,
+            // [3]=Location (IFC4), [4]=Purpose, [5]=IntendedUse, [7]=Revision, [16]=Status
+            reference.document_id = Some(info.id);
+            reference.document_identification = string(&info, 0);
+            reference.document_name = string(&info, 1);
+            reference.document_description = string(&info, 2);
+            reference.document_location = string(&info, 3);
+            reference.purpose = string(&info, 4);
+            reference.intended_use = string(&info, 5);
+            reference.revision = string(&info, 7);
+            reference.status = info
+                .get(16)
+                .and_then(AttributeValue::as_enum)
+                .map(str::to_string);
+        }
+
+        Some(reference)
+    }
+}
+
+/// Resolve an IfcClassificationSelect entity, following ReferencedSource up
+/// to the classification
+pub fn resolve_classification(
+    id: u32,
+    source: PropertySource,
+    decoder: &mut EntityDecoder,
+) -> Option<ClassificationReference> {
+    let entity = decoder.decode_by_id(id).ok()?;
+    let mut reference = ClassificationReference {
+        reference_id: id,
+        source,
+        identification: None,
+        name: None,
+        location: None,
+        description: None,
+        parents: Vec::new(),
+        classification_id: None,
+        classification_name: None,
+        classification_source: None,
+        edition: None,
+        edition_date: None,
+    };
+
+    let mut current = match entity.ifc_type {
+        IfcType::IfcClassification => Some(entity),
+        IfcType::IfcClassificationReference => {
+            // [0]=Location, [1]=Identification/ItemReference, [2]=Name,
+            // [3]=ReferencedSource, IFC4: [4]=Description
+            reference.location = string(&entity, 0);
+            reference.identification = string(&entity, 1);
+            reference.name = string(&entity, 2);
+            reference.description = string(&entity, 4);
+            entity
+                .get_ref(3)
+                .and_then(|parent| decoder.decode_by_id(parent).ok())
+        }
+        _ => return None,
+    };
+
+    for _ in 0..MAX_REFERENCE_DEPTH {
+        let Some(parent) = current.take() else {
+            break;
+        };
+        match parent.ifc_type {
+            IfcType::IfcClassificationReference => {
+                if let Some(code) = string(&parent, 1).or_else(|| string(&parent, 2)) {
+                    reference.parents.push(code);
+                }
+                current = parent
+                    .get_ref(3)
+                    .and_then(|next| decoder.decode_by_id(next).ok());
+            }
+            IfcType::IfcClassification => {
+                // [0]=Source, [1]=Edition, [2]=EditionDate, [3]=Name
+                reference.classification_id = Some(parent.id);
+                reference.classification_source = string(&parent, 0);
+                reference.edition = string(&parent, 1);
+                reference.edition_date = edition_date(&parent, decoder);
+                reference.classification_name = string(&parent, 3);
+            }
+            _ => {}
+        }
+    }
+    reference.parents.reverse();
+
+    Some(reference)
+}
+
+/// EditionDate of an IfcClassification: an IfcDate string (IFC4) or an
+/// IfcCalendarDate (IFC2X3)
+fn edition_date(classification: &DecodedEntity, decoder: &mut EntityDecoder) -> Option<String> {
+    if let Some(date) = string(classification, 2) {
+        return Some(date);
+    }
+    // IfcCalendarDate: [0]=DayComponent, [1]=MonthComponent, [2]=YearComponent
+    let date = decoder.decode_by_id(classification.get_ref(2)?).ok()?;
+    let component = |index| date.get(index).and_then(AttributeValue::as_int);
+    Some(format!(
+        "{:04}-{:02}-{:02}",
+        component(2)?,
+        component(1)?,
+        component(0)?
+    ))
+}
+
+fn string(entity: &DecodedEntity, index: usize) -> Option<String> {
+    entity
+        .get_string(index)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+fn entity_refs(entity: &DecodedEntity, index: usize) -> Vec<u32> {
+    entity
+        .get_list(index)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(AttributeValue::as_entity_ref)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::build_entity_index;
+    use crate::test_fixtures::MODEL;
+
+    fn resolver(content: &'static str) -> (ReferenceResolver, EntityDecoder<'static>) {
+        let mut decoder = EntityDecoder::with_index(content, build_entity_index(content));
+        (
+            ReferenceResolver::from_content(content, &mut decoder),
+            decoder,
+        )
+    }
+
+    #[test]
+    fn test_classification_chain_and_type_inheritance() {
+        let (resolver, mut decoder) = resolver(MODEL);
+        assert_eq!(resolver.elements(), vec![20, 22, 23, 24]);
+
+        let inherited = resolver.classifications(23, &mut decoder);
+        assert_eq!(inherited.len(), 2);
+        let uniclass = &inherited[0];
+        assert_eq!(uniclass.source, PropertySource::Type);
+        assert_eq!(uniclass.identification.as_deref(), Some("Ss_25_10"));
+        assert_eq!(uniclass.name.as_deref(), Some("Framed panel systems"));
+        assert_eq!(uniclass.description.as_deref(), Some("Framed"));
+        assert_eq!(
+            uniclass.parents,
+            vec!["Ss".to_string(), "Ss_25".to_string()]
+        );
+        assert_eq!(uniclass.classification_id, Some(100));
+        assert_eq!(
+            uniclass.classification_name.as_deref(),
+            Some("Uniclass 2015")
+        );
+        assert_eq!(uniclass.classification_source.as_deref(), Some("NBS"));
+        assert_eq!(uniclass.edition.as_deref(), Some("2015"));
+        assert_eq!(uniclass.edition_date.as_deref(), Some("2023-01-01"));
+        assert_eq!(
+            inherited[1].classification_name.as_deref(),
+            Some("OmniClass")
+        );
+
+        // An own Uniclass code replaces the type's, OmniClass is still inherited
+        let own = resolver.classifications(24, &mut decoder);
+        let codes: Vec<_> = own
+            .iter()
+            .map(|r| (r.identification.as_deref(), r.source))
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                (Some("Ss_25_11"), PropertySource::Occurrence),
+                (Some("21-02 10 10"), PropertySource::Type),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_document_references() {
+        let (resolver, mut decoder) = resolver(MODEL);
+
+        let documents = resolver.documents(23, &mut decoder);
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].source, PropertySource::Occurrence);
+        assert_eq!(documents[0].location.as_deref(), Some("fm/wall.pdf"));
+        assert_eq!(documents[0].document_id, None);
+
+        let spec = &documents[1];
+        assert_eq!(spec.source, PropertySource::Type);
+        assert_eq!(spec.identification.as_deref(), Some("3.1"));
+        assert_eq!(spec.document_id, Some(112));
+        assert_eq!(spec.document_identification.as_deref(), Some("DOC-1"));
+        assert_eq!(spec.document_name.as_deref(), Some("Wall specification"));
+        assert_eq!(spec.purpose.as_deref(), Some("Tender"));
+        assert_eq!(spec.revision.as_deref(), Some("C02"));
+        assert_eq!(spec.status.as_deref(), Some("FINAL"));
+    }
+
+    #[test]
+    fn test_ifc2x3_references() {
+        const IFC2X3: &str = r#"
+#1=IFCWALL('W1',$,'Wall',$,$,$,$,$);
+#2=IFCCALENDARDATE(1,7,2005);
+#3=IFCCLASSIFICATION('NL-SfB','2005',#2,'NL-SfB');
+#4=IFCCLASSIFICATIONREFERENCE($,'21.12','Buitenwanden',#3);
+#5=IFCRELASSOCIATESCLASSIFICATION('R1',$,$,$,(#1),#4);
+#6=IFCDOCUMENTREFERENCE('drawings/A-101.dwg','A-101',$);
+#7=IFCDOCUMENTINFORMATION('D-7','Floor plan',$,(#6),$,$,$,'B',$,$,$,$,$,$,$,$,.DRAFT.);
+#8=IFCRELASSOCIATESDOCUMENT('R2',$,$,$,(#1),#6);
+"#;
+        let (resolver, mut decoder) = resolver(IFC2X3);
+
+        let classification = &resolver.classifications(1, &mut decoder)[0];
+        assert_eq!(classification.identification.as_deref(), Some("21.12"));
+        assert_eq!(
+            classification.classification_name.as_deref(),
+            Some("NL-SfB")
+        );
+        assert_eq!(classification.edition_date.as_deref(), Some("2005-07-01"));
+        assert!(classification.parents.is_empty());
+
+        // IfcDocumentInformation.DocumentReferences links the reference to its document
+        let document = &resolver.documents(1, &mut decoder)[0];
+        assert_eq!(document.identification.as_deref(), Some("A-101"));
+        assert_eq!(document.document_id, Some(7));
+        assert_eq!(document.document_name.as_deref(), Some("Floor plan"));
+        assert_eq!(document.document_location, None);
+        assert_eq!(document.status.as_deref(), Some("DRAFT"));
+    }
+}
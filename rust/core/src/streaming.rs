@@ -81,7 +81,8 @@ pub struct StreamConfig {
     pub progress_interval: usize,
     /// Skip these entity types during scanning
     pub skip_types: Vec<IfcType>,
-    /// Only process these entity types (if specified)
+    /// Only process these entity types (if specified); geometry processing
+    /// selects products with `ElementFilter` instead
     pub only_types: Option<Vec<IfcType>>,
 }
 
@@ -0,0 +1,459 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Systems, groups and distribution port connectivity
+//!
+//! Groups (IfcGroup and subtypes such as IfcSystem, IfcDistributionSystem,
+//! IfcDistributionCircuit, IfcBuildingSystem and IfcZone) collect their members
+//! through IfcRelAssignsToGroup. Systems serve buildings and other spatial
+//! structure through IfcRelServicesBuildings.
+//!
+//! Distribution elements are connected through their ports:
+//!
+//! ```text
+//! element ─IfcRelNests (IFC4) / IfcRelConnectsPortToElement (IFC2X3)→ IfcDistributionPort
+//! port ─IfcRelConnectsPorts→ port
+//! ```
+//!
+//! A connection between a SOURCE port and a SINK port carries flow from the
+//! element owning the source to the element owning the sink. Other connections
+//! link elements without a known direction.
+
+use crate::decoder::EntityDecoder;
+use crate::generated::IfcType;
+use crate::parser::EntityScanner;
+use crate::schema_gen::{AttributeValue, DecodedEntity};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+
+/// IfcFlowDirectionEnum of a distribution port
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlowDirection {
+    /// Flow leaves the element through the port
+    Source,
+    /// Flow enters the element through the port
+    Sink,
+    /// Flow in both directions
+    SourceAndSink,
+    NotDefined,
+}
+
+impl FlowDirection {
+    /// Parse an IfcFlowDirectionEnum value, without dots
+    pub fn from_enum(value: &str) -> Self {
+        match value {
+            "SOURCE" => Self::Source,
+            "SINK" => Self::Sink,
+            "SOURCEANDSINK" => Self::SourceAndSink,
+            _ => Self::NotDefined,
+        }
+    }
+
+    /// IFC enumeration value ("SOURCE", "SINK", ...)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Source => "SOURCE",
+            Self::Sink => "SINK",
+            Self::SourceAndSink => "SOURCEANDSINK",
+            Self::NotDefined => "NOTDEFINED",
+        }
+    }
+}
+
+/// Group or system with its members
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    pub id: u32,
+    pub ifc_type: IfcType,
+    pub name: Option<String>,
+    /// IFC4 LongName of a distribution system
+    pub long_name: Option<String>,
+    /// PredefinedType of a distribution system or circuit, without dots
+    pub predefined_type: Option<String>,
+    /// Assigned objects, in file order (elements, ports, spaces or nested groups)
+    pub members: Vec<u32>,
+    /// Buildings or other spatial structure serviced by the system
+    pub serviced_structures: Vec<u32>,
+}
+
+/// Collect all groups with members or serviced structure
+pub fn extract_groups(content: &str, decoder: &mut EntityDecoder) -> Vec<Group> {
+    let mut members: FxHashMap<u32, Vec<u32>> = FxHashMap::default();
+    let mut serviced: FxHashMap<u32, Vec<u32>> = FxHashMap::default();
+
+    let mut scanner = EntityScanner::new(content);
+    while let Some((_, type_name, start, end)) = scanner.next_entity() {
+        match type_name {
+            "IFCRELASSIGNSTOGROUP" | "IFCRELASSIGNSTOGROUPBYFACTOR" => {
+                // [4]=RelatedObjects, [6]=RelatingGroup
+                if let Ok(entity) = decoder.decode_at(start, end) {
+                    if let Some(group) = entity.get_ref(6) {
+                        members
+                            .entry(group)
+                            .or_default()
+                            .extend(entity_refs(&entity, 4));
+                    }
+                }
+            }
+            "IFCRELSERVICESBUILDINGS" => {
+                // [4]=RelatingSystem, [5]=RelatedBuildings
+                if let Ok(entity) = decoder.decode_at(start, end) {
+                    if let Some(system) = entity.get_ref(4) {
+                        serviced
+                            .entry(system)
+                            .or_default()
+                            .extend(entity_refs(&entity, 5));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut ids: Vec<u32> = members.keys().chain(serviced.keys()).copied().collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    ids.into_iter()
+        .filter_map(|id| {
+            let entity = decoder.decode_by_id(id).ok()?;
+            if !entity.ifc_type.is_subtype_of(IfcType::IfcGroup) {
+                return None;
+            }
+            // IfcDistributionSystem: [5]=LongName, [6]=PredefinedType
+            // IfcDistributionCircuit is a subtype with the same layout
+            let (long_name, predefined_type) = if entity
+                .ifc_type
+                .is_subtype_of(IfcType::IfcDistributionSystem)
+            {
+                (string(&entity, 5), enum_value(&entity, 6))
+            } else {
+                (None, None)
+            };
+            Some(Group {
+                id,
+                ifc_type: entity.ifc_type,
+                name: string(&entity, 2),
+                long_name,
+                predefined_type,
+                members: members.remove(&id).unwrap_or_default(),
+                serviced_structures: serviced.remove(&id).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Distribution port of an element
+#[derive(Debug, Clone, PartialEq)]
+pub struct Port {
+    pub id: u32,
+    /// Element the port belongs to
+    pub element_id: Option<u32>,
+    pub name: Option<String>,
+    pub flow_direction: FlowDirection,
+    /// IFC4 IfcDistributionPortTypeEnum (DUCT, PIPE, CABLE, ...)
+    pub predefined_type: Option<String>,
+    /// IFC4 IfcDistributionSystemEnum of the port
+    pub system_type: Option<String>,
+}
+
+/// Connection between two ports (IfcRelConnectsPorts)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortConnection {
+    pub relationship_id: u32,
+    pub relating_port: u32,
+    pub related_port: u32,
+    /// Element realizing the connection, e.g. a fitting not modelled with ports
+    pub realizing_element: Option<u32>,
+    pub relating_element: Option<u32>,
+    pub related_element: Option<u32>,
+    /// Elements in flow order (upstream, downstream), if the port directions tell
+    pub flow: Option<(u32, u32)>,
+}
+
+/// Element connectivity through distribution ports
+#[derive(Debug, Clone, Default)]
+pub struct DistributionGraph {
+    ports: FxHashMap<u32, Port>,
+    connections: Vec<PortConnection>,
+    /// Element → elements receiving flow from it
+    downstream: FxHashMap<u32, Vec<u32>>,
+    /// Element → elements sending flow to it
+    upstream: FxHashMap<u32, Vec<u32>>,
+    /// Element → connected elements, regardless of direction
+    neighbours: FxHashMap<u32, Vec<u32>>,
+}
+
+impl DistributionGraph {
+    /// Collect ports and port connections from a file
+    pub fn from_content(content: &str, decoder: &mut EntityDecoder) -> Self {
+        let mut graph = Self::default();
+        let mut port_elements: Vec<(u32, u32)> = Vec::new();
+        let mut connections: Vec<(u32, u32, u32, Option<u32>)> = Vec::new();
+
+        let mut scanner = EntityScanner::new(content);
+        while let Some((id, type_name, start, end)) = scanner.next_entity() {
+            match type_name {
+                "IFCDISTRIBUTIONPORT" => {
+                    // [2]=Name, [7]=FlowDirection, IFC4: [8]=PredefinedType, [9]=SystemType
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        graph.ports.insert(
+                            id,
+                            Port {
+                                id,
+                                element_id: None,
+                                name: string(&entity, 2),
+                                flow_direction: enum_value(&entity, 7)
+                                    .map(|value| FlowDirection::from_enum(&value))
+                                    .unwrap_or(FlowDirection::NotDefined),
+                                predefined_type: enum_value(&entity, 8),
+                                system_type: enum_value(&entity, 9),
+                            },
+                        );
+                    }
+                }
+                "IFCRELNESTS" => {
+                    // [4]=RelatingObject, [5]=RelatedObjects (ports are told apart below)
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        if let Some(element) = entity.get_ref(4) {
+                            port_elements.extend(
+                                entity_refs(&entity, 5)
+                                    .into_iter()
+                                    .map(|port| (port, element)),
+                            );
+                        }
+                    }
+                }
+                "IFCRELCONNECTSPORTTOELEMENT" => {
+                    // [4]=RelatingPort, [5]=RelatedElement
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        if let (Some(port), Some(element)) = (entity.get_ref(4), entity.get_ref(5))
+                        {
+                            port_elements.push((port, element));
+                        }
+                    }
+                }
+                "IFCRELCONNECTSPORTS" => {
+                    // [4]=RelatingPort, [5]=RelatedPort, [6]=RealizingElement
+                    if let Ok(entity) = decoder.decode_at(start, end) {
+                        if let (Some(relating), Some(related)) =
+                            (entity.get_ref(4), entity.get_ref(5))
+                        {
+                            connections.push((id, relating, related, entity.get_ref(6)));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (port_id, element_id) in port_elements {
+            if let Some(port) = graph.ports.get_mut(&port_id) {
+                port.element_id = Some(element_id);
+            }
+        }
+
+        for (relationship_id, relating_port, related_port, realizing_element) in connections {
+            graph.add_connection(PortConnection {
+                relationship_id,
+                relating_port,
+                related_port,
+                realizing_element,
+                relating_element: None,
+                related_element: None,
+                flow: None,
+            });
+        }
+
+        graph
+    }
+
+    /// Record a port connection, resolving port owners and flow direction
+    fn add_connection(&mut self, mut connection: PortConnection) {
+        let relating = self.ports.get(&connection.relating_port);
+        let related = self.ports.get(&connection.related_port);
+        connection.relating_element = relating.and_then(|port| port.element_id);
+        connection.related_element = related.and_then(|port| port.element_id);
+
+        if let (Some(from), Some(to)) = (connection.relating_element, connection.related_element) {
+            let directions = (
+                relating.map(|port| port.flow_direction),
+                related.map(|port| port.flow_direction),
+            );
+            connection.flow = match directions {
+                (Some(FlowDirection::Source), _) | (_, Some(FlowDirection::Sink)) => {
+                    Some((from, to))
+                }
+                (Some(FlowDirection::Sink), _) | (_, Some(FlowDirection::Source)) => {
+                    Some((to, from))
+                }
+                _ => None,
+            };
+            if let Some((upstream, downstream)) = connection.flow {
+                self.downstream
+                    .entry(upstream)
+                    .or_default()
+                    .push(downstream);
+                self.upstream.entry(downstream).or_default().push(upstream);
+            }
+            if from != to {
+                self.neighbours.entry(from).or_default().push(to);
+                self.neighbours.entry(to).or_default().push(from);
+            }
+        }
+
+        self.connections.push(connection);
+    }
+
+    /// All distribution ports, in no particular order
+    pub fn ports(&self) -> impl Iterator<Item = &Port> {
+        self.ports.values()
+    }
+
+    /// Port by entity ID
+    pub fn port(&self, id: u32) -> Option<&Port> {
+        self.ports.get(&id)
+    }
+
+    /// Ports of an element
+    pub fn element_ports(&self, element: u32) -> Vec<&Port> {
+        let mut ports: Vec<&Port> = self
+            .ports
+            .values()
+            .filter(|port| port.element_id == Some(element))
+            .collect();
+        ports.sort_by_key(|port| port.id);
+        ports
+    }
+
+    /// Port connections, in file order
+    pub fn connections(&self) -> &[PortConnection] {
+        &self.connections
+    }
+
+    /// Elements directly connected to an element
+    pub fn neighbours(&self, element: u32) -> &[u32] {
+        self.neighbours
+            .get(&element)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Elements receiving flow from an element, directly or through other elements
+    pub fn downstream(&self, element: u32) -> Vec<u32> {
+        traverse(&self.downstream, element)
+    }
+
+    /// Elements sending flow to an element, directly or through other elements
+    pub fn upstream(&self, element: u32) -> Vec<u32> {
+        traverse(&self.upstream, element)
+    }
+
+    /// Elements in the same connected network as an element, regardless of flow direction
+    pub fn connected(&self, element: u32) -> Vec<u32> {
+        traverse(&self.neighbours, element)
+    }
+}
+
+/// Breadth-first walk from `start`, excluding it, in visiting order
+fn traverse(edges: &FxHashMap<u32, Vec<u32>>, start: u32) -> Vec<u32> {
+    let mut visited: FxHashSet<u32> = FxHashSet::default();
+    visited.insert(start);
+    let mut queue = VecDeque::from([start]);
+    let mut reached = Vec::new();
+    while let Some(element) = queue.pop_front() {
+        for &next in edges.get(&element).into_iter().flatten() {
+            if visited.insert(next) {
+                reached.push(next);
+                queue.push_back(next);
+            }
+        }
+    }
+    reached
+}
+
+fn string(entity: &DecodedEntity, index: usize) -> Option<String> {
+    entity
+        .get_string(index)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+fn enum_value(entity: &DecodedEntity, index: usize) -> Option<String> {
+    entity
+        .get(index)
+        .and_then(AttributeValue::as_enum)
+        .map(str::to_string)
+}
+
+fn entity_refs(entity: &DecodedEntity, index: usize) -> Vec<u32> {
+    entity
+        .get_list(index)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(AttributeValue::as_entity_ref)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{decoder, MODEL};
+
+    #[test]
+    fn test_groups() {
+        let mut decoder = decoder();
+        let groups = extract_groups(MODEL, &mut decoder);
+        assert_eq!(groups.len(), 2);
+
+        let system = &groups[0];
+        assert_eq!(system.id, 140);
+        assert_eq!(system.ifc_type, IfcType::IfcDistributionSystem);
+        assert_eq!(system.name.as_deref(), Some("Heating"));
+        assert_eq!(system.long_name.as_deref(), Some("LTHW circuit"));
+        assert_eq!(system.predefined_type.as_deref(), Some("HEATING"));
+        assert_eq!(system.members, vec![30, 31, 32, 33, 34]);
+        assert_eq!(system.serviced_structures, vec![6]);
+
+        let zone = &groups[1];
+        assert_eq!(zone.ifc_type, IfcType::IfcZone);
+        assert_eq!(zone.members, vec![9]);
+        assert!(zone.serviced_structures.is_empty());
+    }
+
+    #[test]
+    fn test_port_flow_direction() {
+        // Pump → pipe → tee → two radiators; the ports to radiator B have no direction
+        let mut decoder = decoder();
+        let graph = DistributionGraph::from_content(MODEL, &mut decoder);
+
+        let ports = graph.element_ports(32);
+        assert_eq!(
+            ports.iter().map(|p| p.id).collect::<Vec<_>>(),
+            vec![123, 124, 125]
+        );
+        assert_eq!(ports[0].flow_direction, FlowDirection::Sink);
+        assert_eq!(ports[0].system_type.as_deref(), Some("HEATING"));
+
+        let flows: Vec<_> = graph.connections().iter().map(|c| c.flow).collect();
+        // C2 is written sink-to-source and still flows from the pipe to the tee
+        assert_eq!(
+            flows,
+            vec![Some((30, 31)), Some((31, 32)), Some((32, 33)), None]
+        );
+        assert_eq!(graph.connections()[1].relating_element, Some(32));
+    }
+
+    #[test]
+    fn test_upstream_downstream() {
+        let mut decoder = decoder();
+        let graph = DistributionGraph::from_content(MODEL, &mut decoder);
+
+        assert_eq!(graph.downstream(30), vec![31, 32, 33]);
+        assert_eq!(graph.upstream(33), vec![32, 31, 30]);
+        assert!(graph.upstream(34).is_empty());
+        assert_eq!(graph.neighbours(32), &[31, 33, 34]);
+        assert_eq!(graph.connected(34), vec![32, 31, 33, 30]);
+    }
+}
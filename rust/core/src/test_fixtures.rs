@@ -0,0 +1,153 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Shared test model for the property, material, reference, system and
+//! query tests
+//!
+//! A small house in millimetres: two storeys with a kitchen, doors with and
+//! without a type, layered walls, a steel column, a slab, a window and a
+//! heating circuit. Ids are grouped by topic:
+//!
+//! | Ids     | Content                                             |
+//! |---------|-----------------------------------------------------|
+//! | 1-19    | Project, units and spatial structure                |
+//! | 20-27   | Doors A-C, walls A-B, column, slab, window          |
+//! | 30-34   | Pump, pipe, tee, radiators A-B                      |
+//! | 40-64   | Door type, property sets and quantities             |
+//! | 70-98   | Materials                                           |
+//! | 100-116 | Classifications and documents                       |
+//! | 120-145 | Ports, connections, heating system and fire zone    |
+
+use crate::decoder::{build_entity_index, EntityDecoder};
+
+pub const MODEL: &str = r#"
+#1=IFCPROJECT('P1',$,'Project',$,$,$,$,$,#2);
+#2=IFCUNITASSIGNMENT((#3,#4));
+#3=IFCSIUNIT(*,.LENGTHUNIT.,.MILLI.,.METRE.);
+#4=IFCSIUNIT(*,.AREAUNIT.,$,.SQUARE_METRE.);
+#5=IFCSIUNIT(*,.LENGTHUNIT.,.CENTI.,.METRE.);
+#6=IFCBUILDING('B6',$,'House',$,$,$,$,$,.ELEMENT.,$,$,$);
+#7=IFCBUILDINGSTOREY('S7',$,'Level 1',$,$,$,$,$,.ELEMENT.,0.);
+#8=IFCBUILDINGSTOREY('S8',$,'Level 2',$,$,$,$,$,.ELEMENT.,3000.);
+#9=IFCSPACE('SP9',$,'101',$,$,$,$,'Kitchen',.ELEMENT.,.INTERNAL.,$);
+#10=IFCRELAGGREGATES('R10',$,$,$,#1,(#6));
+#11=IFCRELAGGREGATES('R11',$,$,$,#6,(#7,#8));
+#12=IFCRELAGGREGATES('R12',$,$,$,#8,(#9));
+#15=IFCRELCONTAINEDINSPATIALSTRUCTURE('R15',$,$,$,(#20,#23,#25,#26),#7);
+#16=IFCRELCONTAINEDINSPATIALSTRUCTURE('R16',$,$,$,(#21,#24,#27),#8);
+#17=IFCRELCONTAINEDINSPATIALSTRUCTURE('R17',$,$,$,(#22),#9);
+#18=IFCRELREFERENCEDINSPATIALSTRUCTURE('R18',$,$,$,(#25),#8);
+#20=IFCDOOR('D20',$,'Door A',$,$,$,$,'T20',2100.,900.,.DOOR.,.SINGLE_SWING_LEFT.,$);
+#21=IFCDOOR('D21',$,'Door B',$,$,$,$,$,2100.,900.,.DOOR.,$,$);
+#22=IFCDOOR('D22',$,'Door C',$,$,$,$,$,2100.,1200.,.DOOR.,$,$);
+#23=IFCWALL('W23',$,'Wall A',$,$,$,$,$,$);
+#24=IFCWALL('W24',$,'Wall B',$,$,$,$,$,$);
+#25=IFCCOLUMN('C25',$,'Column',$,$,$,$,$,$);
+#26=IFCSLAB('S26',$,'Slab',$,$,$,$,$,$);
+#27=IFCWINDOW('WI27',$,'Window',$,$,$,$,$,$,$,$,$,$);
+#30=IFCPUMP('E30',$,'Pump',$,$,$,$,$,$);
+#31=IFCPIPESEGMENT('E31',$,'Pipe',$,$,$,$,$,$);
+#32=IFCPIPEFITTING('E32',$,'Tee',$,$,$,$,$,$);
+#33=IFCSPACEHEATER('E33',$,'Radiator A',$,$,$,$,$,$);
+#34=IFCSPACEHEATER('E34',$,'Radiator B',$,$,$,$,$,$);
+#40=IFCPROPERTYSINGLEVALUE('FireRating',$,IFCLABEL('EI30'),$);
+#41=IFCPROPERTYSINGLEVALUE('IsExternal',$,IFCBOOLEAN(.F.),$);
+#42=IFCPROPERTYSET('P42',$,'Pset_DoorCommon',$,(#40,#41));
+#43=IFCQUANTITYLENGTH('Width',$,$,900.,$);
+#44=IFCELEMENTQUANTITY('Q44',$,'Qto_DoorBaseQuantities',$,$,(#43));
+#45=IFCDOORTYPE('T45',$,'Single Flush',$,$,(#42,#44),$,$,$,.DOOR.,.SINGLE_SWING_LEFT.,$,$);
+#46=IFCRELDEFINESBYTYPE('R46',$,$,$,(#20,#21),#45);
+#47=IFCPROPERTYSINGLEVALUE('FireRating',$,IFCLABEL('EI60'),$);
+#48=IFCPROPERTYSET('P48',$,'Pset_DoorCommon',$,(#47));
+#49=IFCRELDEFINESBYPROPERTIES('R49',$,$,$,(#21,#22),#48);
+#50=IFCPROPERTYSINGLEVALUE('Width',$,IFCLENGTHMEASURE(200.),$);
+#51=IFCPROPERTYSINGLEVALUE('NetSideArea',$,IFCAREAMEASURE(4.5),$);
+#52=IFCPROPERTYSINGLEVALUE('Finish',$,IFCPOSITIVELENGTHMEASURE(2.),#5);
+#53=IFCPROPERTYSINGLEVALUE('LoadBearing',$,IFCBOOLEAN(.T.),$);
+#54=IFCPROPERTYSINGLEVALUE('IsExternal',$,IFCLOGICAL(.U.),$);
+#55=IFCPROPERTYSINGLEVALUE('Reference',$,IFCIDENTIFIER('W-01'),$);
+#56=IFCPROPERTYSINGLEVALUE('Cladding',$,IFCLABEL('Oak'),$);
+#57=IFCPROPERTYSINGLEVALUE('Layers',$,IFCINTEGER(3),$);
+#58=IFCPROPERTYSINGLEVALUE('Inspected',$,IFCTIMESTAMP(951782400),$);
+#59=IFCPROPERTYSINGLEVALUE('Openings',$,IFCCOUNTMEASURE(2),$);
+#60=IFCPROPERTYSET('P60',$,'Pset_WallCommon',$,(#50,#51,#52,#53,#54,#55,#56,#57,#58,#59));
+#61=IFCRELDEFINESBYPROPERTIES('R61',$,$,$,(#23),#60);
+#62=IFCQUANTITYAREA('Area',$,$,1.89,$);
+#63=IFCELEMENTQUANTITY('Q63',$,'Qto_DoorBaseQuantities',$,$,(#62));
+#64=IFCRELDEFINESBYPROPERTIES('R64',$,$,$,(#20),#63);
+#70=IFCMATERIAL('Concrete',$,'Concrete');
+#71=IFCMATERIAL('Insulation',$,'Insulation');
+#72=IFCMATERIAL('Brick',$,$);
+#73=IFCMATERIAL('Steel',$,'Steel');
+#74=IFCMATERIAL('Glass',$,$);
+#75=IFCMATERIAL('Aluminium',$,$);
+#76=IFCMATERIAL('Oak',$,'Wood');
+#77=IFCMATERIALLAYER(#70,200.,.F.,'Core',$,$,$);
+#78=IFCMATERIALLAYER(#71,100.,.F.,'Insulation',$,$,$);
+#79=IFCMATERIALLAYER($,20.,.T.,'Air',$,$,$);
+#80=IFCMATERIALLAYER(#72,80.,.F.,'Facing',$,'Finish',$);
+#81=IFCMATERIALLAYERSET((#77,#78,#79,#80),'Cavity wall',$);
+#82=IFCMATERIALLAYERSETUSAGE(#81,.AXIS2.,.NEGATIVE.,200.,$);
+#83=IFCWALLTYPE('T83',$,'Cavity',$,$,$,$,$,$,.STANDARD.);
+#84=IFCRELDEFINESBYTYPE('R84',$,$,$,(#23,#24),#83);
+#85=IFCRELASSOCIATESMATERIAL('R85',$,$,$,(#83),#81);
+#86=IFCRELASSOCIATESMATERIAL('R86',$,$,$,(#24),#82);
+#87=IFCISHAPEPROFILEDEF(.AREA.,'HEA200',$,200.,190.,6.5,10.,18.,$,$);
+#88=IFCMATERIALPROFILE('HEA200',$,#73,#87,$,$);
+#89=IFCMATERIALPROFILESET('HEA200',$,(#88),$);
+#90=IFCMATERIALPROFILESETUSAGE(#89,5,$);
+#91=IFCRELASSOCIATESMATERIAL('R91',$,$,$,(#25),#90);
+#92=IFCMATERIALCONSTITUENT('Glazing',$,#74,0.8,$);
+#93=IFCMATERIALCONSTITUENT('Frame',$,#75,0.2,$);
+#94=IFCMATERIALCONSTITUENTSET('Window',$,(#92,#93));
+#95=IFCRELASSOCIATESMATERIAL('R95',$,$,$,(#27),#94);
+#96=IFCMATERIALLIST((#70,#73));
+#97=IFCRELASSOCIATESMATERIAL('R97',$,$,$,(#26),#96);
+#98=IFCRELASSOCIATESMATERIAL('R98',$,$,$,(#20,#21),#76);
+#100=IFCCLASSIFICATION('NBS','2015','2023-01-01','Uniclass 2015',$,'https://uniclass.thenbs.com',$);
+#101=IFCCLASSIFICATIONREFERENCE($,'Ss','Systems',#100,$,$);
+#102=IFCCLASSIFICATIONREFERENCE($,'Ss_25','Wall and barrier systems',#101,$,$);
+#103=IFCCLASSIFICATIONREFERENCE('https://uniclass.thenbs.com/Ss_25_10','Ss_25_10','Framed panel systems',#102,'Framed',$);
+#104=IFCCLASSIFICATIONREFERENCE($,'Ss_25_11','Monolithic wall systems',#102,$,$);
+#105=IFCCLASSIFICATION('CSI',$,$,'OmniClass',$,$,$);
+#106=IFCCLASSIFICATIONREFERENCE($,'21-02 10 10','Exterior Walls',#105,$,$);
+#107=IFCRELASSOCIATESCLASSIFICATION('R107',$,$,$,(#83),#103);
+#108=IFCRELASSOCIATESCLASSIFICATION('R108',$,$,$,(#83),#106);
+#109=IFCRELASSOCIATESCLASSIFICATION('R109',$,$,$,(#24),#104);
+#110=IFCCLASSIFICATIONREFERENCE($,'Pr_30_59_24','Doorsets',#100,$,$);
+#111=IFCRELASSOCIATESCLASSIFICATION('R111',$,$,$,(#20,#22),#110);
+#112=IFCDOCUMENTINFORMATION('DOC-1','Wall specification','Spec',$,'Tender','Construction',$,'C02',$,$,$,$,$,$,$,$,.FINAL.);
+#113=IFCDOCUMENTREFERENCE('specs/wall.pdf','3.1','Section 3.1',$,#112);
+#114=IFCDOCUMENTREFERENCE('fm/wall.pdf',$,'Maintenance',$,$);
+#115=IFCRELASSOCIATESDOCUMENT('R115',$,$,$,(#83),#113);
+#116=IFCRELASSOCIATESDOCUMENT('R116',$,$,$,(#23),#114);
+#120=IFCDISTRIBUTIONPORT('P120',$,'Out',$,$,$,$,.SOURCE.,.PIPE.,.HEATING.);
+#121=IFCDISTRIBUTIONPORT('P121',$,'In',$,$,$,$,.SINK.,.PIPE.,.HEATING.);
+#122=IFCDISTRIBUTIONPORT('P122',$,'Out',$,$,$,$,.SOURCE.,.PIPE.,.HEATING.);
+#123=IFCDISTRIBUTIONPORT('P123',$,'In',$,$,$,$,.SINK.,.PIPE.,.HEATING.);
+#124=IFCDISTRIBUTIONPORT('P124',$,'Out A',$,$,$,$,.SOURCE.,.PIPE.,.HEATING.);
+#125=IFCDISTRIBUTIONPORT('P125',$,'Out B',$,$,$,$,.NOTDEFINED.,.PIPE.,.HEATING.);
+#126=IFCDISTRIBUTIONPORT('P126',$,'In',$,$,$,$,.SINK.,.PIPE.,.HEATING.);
+#127=IFCDISTRIBUTIONPORT('P127',$,'In',$,$,$,$,.NOTDEFINED.,.PIPE.,.HEATING.);
+#130=IFCRELNESTS('N130',$,$,$,#30,(#120));
+#131=IFCRELNESTS('N131',$,$,$,#31,(#121,#122));
+#132=IFCRELNESTS('N132',$,$,$,#32,(#123,#124,#125));
+#133=IFCRELNESTS('N133',$,$,$,#33,(#126));
+#134=IFCRELNESTS('N134',$,$,$,#34,(#127));
+#135=IFCRELCONNECTSPORTS('C135',$,$,$,#120,#121,$);
+#136=IFCRELCONNECTSPORTS('C136',$,$,$,#123,#122,$);
+#137=IFCRELCONNECTSPORTS('C137',$,$,$,#124,#126,$);
+#138=IFCRELCONNECTSPORTS('C138',$,$,$,#125,#127,$);
+#140=IFCDISTRIBUTIONSYSTEM('S140',$,'Heating',$,$,'LTHW circuit',.HEATING.);
+#141=IFCRELASSIGNSTOGROUP('G141',$,$,$,(#30,#31,#32,#33,#34),$,#140);
+#142=IFCRELSERVICESBUILDINGS('SB142',$,$,$,#140,(#6));
+#143=IFCGROUP('G143',$,'Unused',$,$);
+#144=IFCZONE('Z144',$,'Fire Zone 1',$,$,$);
+#145=IFCRELASSIGNSTOGROUP('G145',$,$,$,(#9),$,#144);
+"#;
+
+/// Decoder over [`MODEL`]
+pub fn decoder() -> EntityDecoder<'static> {
+    EntityDecoder::with_index(MODEL, build_entity_index(MODEL))
+}
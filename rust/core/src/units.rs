@@ -5,10 +5,14 @@
 //! Unit extraction and conversion for IFC files
 //!
 //! Handles parsing of IFCSIUNIT and IFCCONVERSIONBASEDUNIT (imperial units)
-//! and applying appropriate multipliers to geometry coordinates.
+//! and applying appropriate multipliers to geometry coordinates. `UnitContext`
+//! labels the project unit of every measure type and converts its values to SI.
 
 use crate::decoder::EntityDecoder;
 use crate::error::Result;
+use crate::parser::EntityScanner;
+use crate::schema_gen::{AttributeValue, DecodedEntity};
+use rustc_hash::FxHashMap;
 
 /// SI Prefix multipliers as defined in IFC specification
 /// Maps IfcSIPrefix enum values to their numeric multipliers
@@ -35,16 +39,56 @@ pub fn get_si_prefix_multiplier(prefix: &str) -> f64 {
     }
 }
 
-/// Known conversion factors for imperial/conversion-based units to meters
-/// These are the standard conversions defined in IFC specification
+/// Known conversion factors for conversion-based units to SI base units
+///
+/// Covers the common units listed for IfcConversionBasedUnit (length, area,
+/// volume, mass, force, pressure, time, energy and the degree). Names are
+/// matched case-insensitively, with or without quotes and underscores.
 #[inline]
 pub fn get_conversion_based_unit_factor(name: &str) -> Option<f64> {
-    match name.to_uppercase().as_str() {
+    let name = name.trim_matches('\'').to_uppercase().replace('_', " ");
+    match name.as_str() {
         // Length units to meters
-        "FOOT" | "FEET" | "'FOOT'" => Some(0.3048),
-        "INCH" | "'INCH'" => Some(0.0254),
-        "YARD" | "'YARD'" => Some(0.9144),
-        "MILE" | "'MILE'" => Some(1609.344),
+        "FOOT" | "FEET" => Some(0.3048),
+        "INCH" => Some(0.0254),
+        "YARD" => Some(0.9144),
+        "MILE" => Some(1609.344),
+        "US SURVEY FOOT" => Some(1200.0 / 3937.0),
+        // Area units to square meters
+        "SQUARE FOOT" | "SQUARE FEET" => Some(0.09290304),
+        "SQUARE INCH" => Some(0.00064516),
+        "SQUARE YARD" => Some(0.83612736),
+        "SQUARE MILE" => Some(2_589_988.110336),
+        "ACRE" => Some(4046.8564224),
+        // Volume units to cubic meters
+        "CUBIC FOOT" | "CUBIC FEET" => Some(0.028316846592),
+        "CUBIC INCH" => Some(1.6387064e-5),
+        "CUBIC YARD" => Some(0.764554857984),
+        "LITRE" | "LITER" => Some(1e-3),
+        "FLUID OUNCE UK" => Some(28.4130625e-6),
+        "FLUID OUNCE US" => Some(29.5735295625e-6),
+        "PINT UK" => Some(568.26125e-6),
+        "PINT US" => Some(473.176473e-6),
+        "GALLON UK" => Some(4.54609e-3),
+        "GALLON US" => Some(3.785411784e-3),
+        // Plane angle to radians
+        "DEGREE" => Some(std::f64::consts::PI / 180.0),
+        // Mass units to kilograms
+        "OUNCE" => Some(0.028349523125),
+        "POUND" => Some(0.45359237),
+        "TON UK" => Some(1016.0469088),
+        "TON US" => Some(907.18474),
+        // Force and pressure units to newtons and pascals
+        "LBF" => Some(4.4482216152605),
+        "KIP" => Some(4448.2216152605),
+        "PSI" => Some(6894.757293168),
+        "KSI" => Some(6_894_757.293168),
+        // Time units to seconds
+        "MINUTE" => Some(60.0),
+        "HOUR" => Some(3600.0),
+        "DAY" => Some(86400.0),
+        // Energy units to joules
+        "BTU" => Some(1055.05585262),
         _ => None,
     }
 }
@@ -260,6 +304,311 @@ pub fn extract_length_unit_scale(decoder: &mut EntityDecoder, project_id: u32) -
     Ok(1.0)
 }
 
+/// Conversion of a unit to its SI base unit: `si = (value - offset) * scale`
+///
+/// The offset is only non-zero for temperatures (degree Celsius,
+/// IfcConversionBasedUnitWithOffset such as degree Fahrenheit).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitConversion {
+    /// SI base units per unit
+    pub scale: f64,
+    /// Unit value of the SI zero point
+    pub offset: f64,
+}
+
+impl UnitConversion {
+    /// Conversion of a unit that already is the SI base unit
+    pub const IDENTITY: Self = Self {
+        scale: 1.0,
+        offset: 0.0,
+    };
+
+    /// Convert a value in this unit to SI
+    #[inline]
+    pub fn to_si(&self, value: f64) -> f64 {
+        (value - self.offset) * self.scale
+    }
+}
+
+impl Default for UnitConversion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Maximum nesting of conversion-based and derived units
+const MAX_UNIT_DEPTH: usize = 8;
+
+/// Project units from IfcUnitAssignment, keyed by unit type
+///
+/// Keys are IfcUnitEnum / IfcDerivedUnitEnum values as written in the file
+/// (e.g. `LENGTHUNIT`, `THERMALTRANSMITTANCEUNIT`) plus `MONETARYUNIT`.
+/// Unit types without an assignment are taken to be in SI base units.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnitContext {
+    labels: FxHashMap<String, String>,
+    conversions: FxHashMap<String, UnitConversion>,
+}
+
+impl UnitContext {
+    /// Read the units assigned to a project
+    ///
+    /// Follows IFCPROJECT.UnitsInContext → IFCUNITASSIGNMENT.Units.
+    pub fn from_project(decoder: &mut EntityDecoder, project_id: u32) -> Result<Self> {
+        let mut context = Self::default();
+        let project = decoder.decode_by_id(project_id)?;
+        // IfcProject: [8]=UnitsInContext
+        let Some(assignment_id) = project.get_ref(8) else {
+            return Ok(context);
+        };
+        let assignment = decoder.decode_by_id(assignment_id)?;
+        let unit_ids: Vec<u32> = assignment
+            .get_list(0)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(AttributeValue::as_entity_ref)
+            .collect();
+
+        for unit_id in unit_ids {
+            let Ok(unit) = decoder.decode_by_id(unit_id) else {
+                continue;
+            };
+            let unit_type = if unit.ifc_type.as_str() == "IFCMONETARYUNIT" {
+                "MONETARYUNIT"
+            } else {
+                // IfcNamedUnit / IfcDerivedUnit: [1]=UnitType
+                match unit.get(1).and_then(AttributeValue::as_enum) {
+                    Some(unit_type) => unit_type,
+                    None => continue,
+                }
+            };
+            if let Some(label) = unit_label(&unit, decoder) {
+                context.labels.insert(unit_type.to_string(), label);
+            }
+            if let Some(conversion) = unit_conversion(&unit, decoder) {
+                context.conversions.insert(unit_type.to_string(), conversion);
+            }
+        }
+        Ok(context)
+    }
+
+    /// Read the units of the first IFCPROJECT in a file
+    pub fn from_content(content: &str, decoder: &mut EntityDecoder) -> Self {
+        let mut scanner = EntityScanner::new(content);
+        while let Some((id, type_name, _, _)) = scanner.next_entity() {
+            if type_name == "IFCPROJECT" {
+                return Self::from_project(decoder, id).unwrap_or_default();
+            }
+        }
+        Self::default()
+    }
+
+    /// Label of the project unit for a unit type (e.g. `LENGTHUNIT` → "millimetre")
+    pub fn label(&self, unit_type: &str) -> Option<&str> {
+        self.labels.get(unit_type).map(String::as_str)
+    }
+
+    /// Label of the project unit that applies to an IFC measure type
+    pub fn measure_label(&self, measure_type: &str) -> Option<&str> {
+        self.label(&measure_unit_type(measure_type)?)
+    }
+
+    /// SI conversion of the project unit for a unit type (identity if unassigned)
+    pub fn conversion(&self, unit_type: &str) -> UnitConversion {
+        self.conversions.get(unit_type).copied().unwrap_or_default()
+    }
+
+    /// SI base units per project unit for a unit type (1.0 if unassigned)
+    pub fn scale(&self, unit_type: &str) -> f64 {
+        self.conversion(unit_type).scale
+    }
+
+    /// Metres per project length unit
+    pub fn length_scale(&self) -> f64 {
+        self.scale("LENGTHUNIT")
+    }
+
+    /// Radians per project plane angle unit
+    pub fn plane_angle_scale(&self) -> f64 {
+        self.scale("PLANEANGLEUNIT")
+    }
+
+    /// Convert a value of an IFC measure type from project units to SI
+    ///
+    /// Dimensionless measures and measures without a unit type are returned unchanged.
+    pub fn to_si(&self, value: f64, measure_type: &str) -> f64 {
+        match measure_unit_type(measure_type) {
+            Some(unit_type) => self.conversion(&unit_type).to_si(value),
+            None => value,
+        }
+    }
+}
+
+/// Unit type of an IfcPhysicalSimpleQuantity's value
+///
+/// `IFCQUANTITYAREA` → `AREAUNIT`. Counts and unknown types return `None`.
+pub fn quantity_unit_type(quantity_type: &str) -> Option<&'static str> {
+    let upper = quantity_type.to_ascii_uppercase();
+    match upper.as_str() {
+        "IFCQUANTITYLENGTH" => Some("LENGTHUNIT"),
+        "IFCQUANTITYAREA" => Some("AREAUNIT"),
+        "IFCQUANTITYVOLUME" => Some("VOLUMEUNIT"),
+        "IFCQUANTITYWEIGHT" => Some("MASSUNIT"),
+        "IFCQUANTITYTIME" => Some("TIMEUNIT"),
+        _ => None,
+    }
+}
+
+/// Unit type that a measure type is expressed in
+///
+/// `IFCLENGTHMEASURE` → `LENGTHUNIT`, `IFCTHERMALTRANSMITTANCEMEASURE` →
+/// `THERMALTRANSMITTANCEUNIT`. Returns `None` for dimensionless measures.
+pub fn measure_unit_type(measure_type: &str) -> Option<String> {
+    let upper = measure_type.to_ascii_uppercase();
+    let quantity = upper.strip_prefix("IFC")?.strip_suffix("MEASURE")?;
+    let unit = match quantity {
+        "POSITIVELENGTH" | "NONNEGATIVELENGTH" => "LENGTH",
+        "POSITIVEPLANEANGLE" | "COMPOUNDPLANEANGLE" => "PLANEANGLE",
+        "THERMALCONDUCTIVITY" => "THERMALCONDUCTANCE",
+        "COUNT" | "RATIO" | "NORMALISEDRATIO" | "POSITIVERATIO" | "NUMERIC" | "DESCRIPTIVE"
+        | "CONTEXTDEPENDENT" => return None,
+        other => other,
+    };
+    Some(format!("{}UNIT", unit))
+}
+
+/// Readable label for an IfcNamedUnit, IfcDerivedUnit or IfcMonetaryUnit
+///
+/// SI units read as lower-case prefix and name ("millimetre", "square_metre"),
+/// conversion-based units by their name, derived units by their user-defined
+/// type or their elements ("watt·square_metre^-1·kelvin^-1").
+pub fn unit_label(unit: &DecodedEntity, decoder: &mut EntityDecoder) -> Option<String> {
+    match unit.ifc_type.as_str() {
+        "IFCSIUNIT" => {
+            // IfcSIUnit: [0]=Dimensions, [1]=UnitType, [2]=Prefix, [3]=Name
+            let name = unit.get(3)?.as_enum()?;
+            let prefix = unit.get(2).and_then(AttributeValue::as_enum).unwrap_or("");
+            Some(format!("{}{}", prefix, name).to_ascii_lowercase())
+        }
+        "IFCCONVERSIONBASEDUNIT"
+        | "IFCCONVERSIONBASEDUNITWITHOFFSET"
+        | "IFCCONTEXTDEPENDENTUNIT" => {
+            // [0]=Dimensions, [1]=UnitType, [2]=Name
+            unit.get_string(2).map(|s| s.to_string())
+        }
+        "IFCDERIVEDUNIT" => {
+            // IfcDerivedUnit: [0]=Elements, [1]=UnitType, [2]=UserDefinedType
+            if let Some(name) = unit.get_string(2) {
+                return Some(name.to_string());
+            }
+            let mut parts = Vec::new();
+            for element_id in unit
+                .get_list(0)?
+                .iter()
+                .filter_map(AttributeValue::as_entity_ref)
+            {
+                // IfcDerivedUnitElement: [0]=Unit, [1]=Exponent
+                let element = decoder.decode_by_id(element_id).ok()?;
+                let named = decoder.decode_by_id(element.get_ref(0)?).ok()?;
+                let label = unit_label(&named, decoder)?;
+                match element.get(1).and_then(AttributeValue::as_int) {
+                    Some(1) | None => parts.push(label),
+                    Some(exponent) => parts.push(format!("{}^{}", label, exponent)),
+                }
+            }
+            (!parts.is_empty()).then(|| parts.join("·"))
+        }
+        "IFCMONETARYUNIT" => {
+            // IfcMonetaryUnit: [0]=Currency (label in IFC4, enum in IFC2X3)
+            let currency = unit.get(0)?;
+            currency
+                .as_string()
+                .or_else(|| currency.as_enum())
+                .map(|s| s.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// SI conversion of an IfcNamedUnit or IfcDerivedUnit
+///
+/// SI units apply their prefix (squared for `SQUARE_METRE`, cubed for
+/// `CUBIC_METRE`; grams are scaled to kilograms). Conversion-based units use
+/// the known factor for their name, else their ConversionFactor. Derived
+/// units multiply their elements raised to their exponents.
+pub fn unit_conversion(unit: &DecodedEntity, decoder: &mut EntityDecoder) -> Option<UnitConversion> {
+    unit_conversion_depth(unit, decoder, 0)
+}
+
+fn unit_conversion_depth(
+    unit: &DecodedEntity,
+    decoder: &mut EntityDecoder,
+    depth: usize,
+) -> Option<UnitConversion> {
+    if depth > MAX_UNIT_DEPTH {
+        return None;
+    }
+    match unit.ifc_type.as_str() {
+        "IFCSIUNIT" => {
+            // IfcSIUnit: [0]=Dimensions, [1]=UnitType, [2]=Prefix, [3]=Name
+            let name = unit.get(3)?.as_enum()?;
+            let prefix = unit
+                .get(2)
+                .and_then(AttributeValue::as_enum)
+                .map_or(1.0, get_si_prefix_multiplier);
+            let (scale, offset) = match name {
+                "SQUARE_METRE" => (prefix.powi(2), 0.0),
+                "CUBIC_METRE" => (prefix.powi(3), 0.0),
+                "GRAM" => (prefix * 1e-3, 0.0),
+                "DEGREE_CELSIUS" => (prefix, -273.15 / prefix),
+                _ => (prefix, 0.0),
+            };
+            Some(UnitConversion { scale, offset })
+        }
+        "IFCCONVERSIONBASEDUNIT" | "IFCCONVERSIONBASEDUNITWITHOFFSET" => {
+            // [0]=Dimensions, [1]=UnitType, [2]=Name, [3]=ConversionFactor,
+            // [4]=ConversionOffset (with offset only)
+            let known = unit.get_string(2).and_then(get_conversion_based_unit_factor);
+            let scale = match known {
+                Some(scale) => scale,
+                None => {
+                    // IfcMeasureWithUnit: [0]=ValueComponent, [1]=UnitComponent
+                    let factor = decoder.decode_by_id(unit.get_ref(3)?).ok()?;
+                    let value = factor.get_float(0).filter(|v| *v > 0.0)?;
+                    let component = match factor.get_ref(1) {
+                        Some(id) => {
+                            let component = decoder.decode_by_id(id).ok()?;
+                            unit_conversion_depth(&component, decoder, depth + 1)?
+                        }
+                        None => UnitConversion::IDENTITY,
+                    };
+                    value * component.scale
+                }
+            };
+            let offset = unit.get_float(4).unwrap_or(0.0);
+            Some(UnitConversion { scale, offset })
+        }
+        "IFCDERIVEDUNIT" => {
+            // IfcDerivedUnit: [0]=Elements; IfcDerivedUnitElement: [0]=Unit, [1]=Exponent
+            let element_ids: Vec<u32> = unit
+                .get_list(0)?
+                .iter()
+                .filter_map(AttributeValue::as_entity_ref)
+                .collect();
+            let mut scale = 1.0;
+            for element_id in element_ids {
+                let element = decoder.decode_by_id(element_id).ok()?;
+                let exponent = element.get(1).and_then(AttributeValue::as_int).unwrap_or(1);
+                let named = decoder.decode_by_id(element.get_ref(0)?).ok()?;
+                let conversion = unit_conversion_depth(&named, decoder, depth + 1)?;
+                scale *= conversion.scale.powi(exponent as i32);
+            }
+            Some(UnitConversion { scale, offset: 0.0 })
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -382,4 +731,93 @@ END-ISO-10303-21;
             scale
         );
     }
+
+    #[test]
+    fn test_unit_context_labels() {
+        let ifc_content = r#"
+#1=IFCPROJECT('guid',$,'Test',$,$,$,$,$,#2);
+#2=IFCUNITASSIGNMENT((#3,#4,#5,#6,#7));
+#3=IFCSIUNIT(*,.LENGTHUNIT.,.MILLI.,.METRE.);
+#4=IFCCONVERSIONBASEDUNIT(#8,.PLANEANGLEUNIT.,'DEGREE',#9);
+#5=IFCDERIVEDUNIT((#11,#12,#13),.THERMALTRANSMITTANCEUNIT.,$);
+#6=IFCMONETARYUNIT('EUR');
+#7=IFCSIUNIT(*,.THERMODYNAMICTEMPERATUREUNIT.,$,.KELVIN.);
+#8=IFCDIMENSIONALEXPONENTS(0,0,0,0,0,0,0);
+#9=IFCMEASUREWITHUNIT(IFCPLANEANGLEMEASURE(0.0174532925199433),#10);
+#10=IFCSIUNIT(*,.PLANEANGLEUNIT.,$,.RADIAN.);
+#11=IFCDERIVEDUNITELEMENT(#14,1);
+#12=IFCDERIVEDUNITELEMENT(#15,-2);
+#13=IFCDERIVEDUNITELEMENT(#7,-1);
+#14=IFCSIUNIT(*,.POWERUNIT.,$,.WATT.);
+#15=IFCSIUNIT(*,.LENGTHUNIT.,$,.METRE.);
+"#;
+        let mut decoder = EntityDecoder::new(ifc_content);
+        let units = UnitContext::from_content(ifc_content, &mut decoder);
+
+        assert_eq!(units.measure_label("IFCLENGTHMEASURE"), Some("millimetre"));
+        assert_eq!(
+            units.measure_label("IfcPositiveLengthMeasure"),
+            Some("millimetre")
+        );
+        assert_eq!(units.measure_label("IFCPLANEANGLEMEASURE"), Some("DEGREE"));
+        assert_eq!(
+            units.measure_label("IFCTHERMALTRANSMITTANCEMEASURE"),
+            Some("watt·metre^-2·kelvin^-1")
+        );
+        assert_eq!(units.measure_label("IFCMONETARYMEASURE"), Some("EUR"));
+        assert_eq!(units.measure_label("IFCAREAMEASURE"), None);
+        assert_eq!(measure_unit_type("IFCRATIOMEASURE"), None);
+        assert_eq!(measure_unit_type("IFCLABEL"), None);
+    }
+
+    #[test]
+    fn test_unit_context_si_conversion() {
+        let ifc_content = r#"
+#1=IFCPROJECT('guid',$,'Test',$,$,$,$,$,#2);
+#2=IFCUNITASSIGNMENT((#3,#4,#5,#6,#7,#8,#9,#10));
+#3=IFCSIUNIT(*,.LENGTHUNIT.,.MILLI.,.METRE.);
+#4=IFCCONVERSIONBASEDUNIT(#20,.AREAUNIT.,'square foot',$);
+#5=IFCSIUNIT(*,.VOLUMEUNIT.,.CENTI.,.CUBIC_METRE.);
+#6=IFCSIUNIT(*,.MASSUNIT.,$,.GRAM.);
+#7=IFCCONVERSIONBASEDUNIT(#20,.PLANEANGLEUNIT.,'GON',#21);
+#8=IFCCONVERSIONBASEDUNITWITHOFFSET(#20,.THERMODYNAMICTEMPERATUREUNIT.,'Fahrenheit',#23,-459.67);
+#9=IFCDERIVEDUNIT((#25,#26),.LINEARFORCEUNIT.,$);
+#10=IFCCONVERSIONBASEDUNIT(#20,.TIMEUNIT.,'hour',$);
+#20=IFCDIMENSIONALEXPONENTS(0,0,0,0,0,0,0);
+#21=IFCMEASUREWITHUNIT(IFCPLANEANGLEMEASURE(0.9),#22);
+#22=IFCCONVERSIONBASEDUNIT(#20,.PLANEANGLEUNIT.,'DEGREE',$);
+#23=IFCMEASUREWITHUNIT(IFCREAL(0.5555555555555556),#24);
+#24=IFCSIUNIT(*,.THERMODYNAMICTEMPERATUREUNIT.,$,.KELVIN.);
+#25=IFCDERIVEDUNITELEMENT(#27,1);
+#26=IFCDERIVEDUNITELEMENT(#3,-1);
+#27=IFCSIUNIT(*,.FORCEUNIT.,.KILO.,.NEWTON.);
+#30=IFCSIUNIT(*,.THERMODYNAMICTEMPERATUREUNIT.,$,.DEGREE_CELSIUS.);
+"#;
+        let mut decoder = EntityDecoder::new(ifc_content);
+        let units = UnitContext::from_content(ifc_content, &mut decoder);
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9 * b.abs().max(1.0);
+
+        assert!(close(units.length_scale(), 0.001));
+        assert!(close(units.to_si(2.0, "IFCAREAMEASURE"), 0.18580608));
+        assert!(close(units.to_si(1.0, "IFCVOLUMEMEASURE"), 1e-6));
+        assert!(close(units.to_si(500.0, "IFCMASSMEASURE"), 0.5));
+        // Gon defined as 0.9 degree
+        assert!(close(units.plane_angle_scale(), std::f64::consts::PI / 200.0));
+        assert!(close(
+            units.to_si(32.0, "IFCTHERMODYNAMICTEMPERATUREMEASURE"),
+            273.15
+        ));
+        // kN/mm → N/m
+        assert!(close(units.to_si(1.0, "IFCLINEARFORCEMEASURE"), 1e6));
+        assert!(close(units.to_si(1.5, "IFCTIMEMEASURE"), 5400.0));
+        // Unassigned unit types and dimensionless measures are left as they are
+        assert!(close(units.to_si(3.0, "IFCELECTRICCURRENTMEASURE"), 3.0));
+        assert!(close(units.to_si(0.5, "IFCRATIOMEASURE"), 0.5));
+        assert_eq!(quantity_unit_type("IfcQuantityWeight"), Some("MASSUNIT"));
+        assert_eq!(quantity_unit_type("IFCQUANTITYCOUNT"), None);
+
+        let celsius = decoder.decode_by_id(30).unwrap();
+        let celsius = unit_conversion(&celsius, &mut decoder).unwrap();
+        assert!(close(celsius.to_si(20.0), 293.15));
+    }
 }
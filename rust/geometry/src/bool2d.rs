@@ -155,6 +155,39 @@ pub fn union_contours(contours: &[Vec<Point2<f64>>]) -> Result<Vec<Vec<Point2<f6
     Ok(all_contours)
 }
 
+/// Perform 2D boolean difference between two sets of profiles
+///
+/// Unlike [`subtract_multiple_2d`], every resulting shape is kept, so a void
+/// that splits the subject in two yields two profiles. Overlapping clip
+/// profiles are unioned (non-zero fill rule). An empty result means the
+/// subject is entirely covered by the clip.
+pub fn difference_2d(subject: &[Profile2D], clip: &[Profile2D]) -> Vec<Profile2D> {
+    let subject_paths: Vec<Vec<[f64; 2]>> = subject
+        .iter()
+        .filter(|p| p.outer.len() >= 3)
+        .flat_map(profile_to_paths)
+        .collect();
+
+    if subject_paths.is_empty() {
+        return Vec::new();
+    }
+
+    let clip_paths: Vec<Vec<[f64; 2]>> = clip
+        .iter()
+        .filter(|p| p.outer.len() >= 3)
+        .flat_map(profile_to_paths)
+        .collect();
+
+    let rule = if clip_paths.is_empty() {
+        OverlayRule::Subject
+    } else {
+        OverlayRule::Difference
+    };
+    let result = subject_paths.overlay(&clip_paths, rule, FillRule::NonZero);
+
+    shapes_to_profiles(&result)
+}
+
 /// Check if a contour is valid (has area, not degenerate)
 pub fn is_valid_contour(contour: &[Point2<f64>]) -> bool {
     if contour.len() < 3 {
@@ -385,6 +418,30 @@ fn shapes_to_profile(shapes: &[Vec<Vec<[f64; 2]>>]) -> Result<Profile2D> {
     Ok(Profile2D { outer, holes })
 }
 
+/// Convert all i_overlay result shapes to profiles, dropping degenerate ones
+fn shapes_to_profiles(shapes: &[Vec<Vec<[f64; 2]>>]) -> Vec<Profile2D> {
+    shapes
+        .iter()
+        .filter_map(|shape| {
+            let (outer, holes) = shape.split_first()?;
+            let outer: Vec<Point2<f64>> = outer.iter().map(|p| Point2::new(p[0], p[1])).collect();
+            if !is_valid_contour(&outer) {
+                return None;
+            }
+            let holes = holes
+                .iter()
+                .map(|c| c.iter().map(|p| Point2::new(p[0], p[1])).collect::<Vec<_>>())
+                .filter(|h| is_valid_contour(h))
+                .map(|h| ensure_cw(&h))
+                .collect();
+            Some(Profile2D {
+                outer: ensure_ccw(&outer),
+                holes,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
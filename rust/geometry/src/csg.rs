@@ -746,33 +746,22 @@ impl ClippingProcessor {
     /// Remove degenerate triangles from CSG result
     /// 
     /// CSG operations can create thin "sliver" triangles at intersection boundaries
-    /// due to numerical precision issues. This function removes triangles that:
-    /// 1. Have very small area (thin slivers)
-    /// 2. Are located inside the original host mesh bounds (not on the surface)
+    /// due to numerical precision issues. This function removes triangles with very
+    /// small area. Faces inside the host bounds are kept: they are the walls of the
+    /// subtracted openings.
     fn remove_degenerate_triangles(mesh: &Mesh, host_mesh: &Mesh) -> Mesh {
         let (host_min, host_max) = host_mesh.bounds();
         
-        // Convert host bounds to f64 for calculations
-        let host_min_x = host_min.x as f64;
-        let host_min_y = host_min.y as f64;
-        let host_min_z = host_min.z as f64;
-        let host_max_x = host_max.x as f64;
-        let host_max_y = host_max.y as f64;
-        let host_max_z = host_max.z as f64;
-        
         // Calculate host dimensions to determine appropriate thresholds
-        let host_size_x = (host_max_x - host_min_x).abs();
-        let host_size_y = (host_max_y - host_min_y).abs();
-        let host_size_z = (host_max_z - host_min_z).abs();
+        let host_size_x = (host_max.x as f64 - host_min.x as f64).abs();
+        let host_size_y = (host_max.y as f64 - host_min.y as f64).abs();
+        let host_size_z = (host_max.z as f64 - host_min.z as f64).abs();
         let min_dim = host_size_x.min(host_size_y).min(host_size_z);
         
         // Minimum area threshold - triangles smaller than this are likely artifacts
         // Use 0.1% of the smallest host dimension squared
         let min_area = (min_dim * 0.001).powi(2);
         
-        // Distance threshold for "inside" detection
-        let epsilon = min_dim * 0.01;
-        
         let mut cleaned = Mesh::new();
         
         // Process each triangle
@@ -809,26 +798,6 @@ impl ClippingProcessor {
                 continue;
             }
             
-            // Check if triangle center is strictly inside the host bounds
-            // (not on the surface) - these are likely CSG artifacts
-            let center = Point3::new(
-                (v0.x + v1.x + v2.x) / 3.0,
-                (v0.y + v1.y + v2.y) / 3.0,
-                (v0.z + v1.z + v2.z) / 3.0,
-            );
-            
-            // Check if center is inside host bounds (with epsilon margin)
-            let inside_x = center.x > (host_min_x + epsilon) && center.x < (host_max_x - epsilon);
-            let inside_y = center.y > (host_min_y + epsilon) && center.y < (host_max_y - epsilon);
-            let inside_z = center.z > (host_min_z + epsilon) && center.z < (host_max_z - epsilon);
-            
-            // If triangle is strictly inside the host in ALL dimensions, it's likely an artifact
-            // Only remove if it's also relatively small
-            let max_area = min_dim * min_dim * 0.1; // 10% of smallest dimension squared
-            if inside_x && inside_y && inside_z && area < max_area {
-                continue;
-            }
-            
             // Get normals
             let n0 = Vector3::new(
                 mesh.normals[i0 * 3] as f64,
@@ -1285,6 +1254,120 @@ pub fn calculate_normals(mesh: &mut Mesh) {
     }
 }
 
+/// Default crease angle for [`calculate_crease_normals`] (30 degrees)
+pub const DEFAULT_CREASE_ANGLE: f64 = std::f64::consts::PI / 6.0;
+
+/// Calculate normals that stay smooth across shallow edges and split at sharp ones
+///
+/// Faces sharing a vertex are grouped while the angle between their normals
+/// stays below `crease_angle` (radians). Each group gets its own copy of the
+/// vertex with an area-weighted normal, so a box keeps flat faces while a
+/// tessellated cylinder shades smoothly. Works best on a welded mesh
+/// (see [`Mesh::weld`]).
+pub fn calculate_crease_normals(mesh: &mut Mesh, crease_angle: f64) {
+    let vertex_count = mesh.vertex_count();
+    if vertex_count == 0 || mesh.indices.is_empty() {
+        return;
+    }
+
+    let cos_crease = crease_angle.cos();
+
+    // Area-weighted face normals (raw cross product) plus unit normals for grouping
+    let triangle_count = mesh.indices.len() / 3;
+    let mut face_normals: Vec<Vector3<f64>> = Vec::with_capacity(triangle_count);
+    let mut face_units: Vec<Option<Vector3<f64>>> = Vec::with_capacity(triangle_count);
+    let mut vertex_faces: Vec<SmallVec<[u32; 8]>> = vec![SmallVec::new(); vertex_count];
+
+    for (face, tri) in mesh.indices.chunks_exact(3).enumerate() {
+        let (i0, i1, i2) = (tri[0] as usize, tri[1] as usize, tri[2] as usize);
+        if i0 >= vertex_count || i1 >= vertex_count || i2 >= vertex_count {
+            face_normals.push(Vector3::zeros());
+            face_units.push(None);
+            continue;
+        }
+
+        let p = |i: usize| {
+            Point3::new(
+                mesh.positions[i * 3] as f64,
+                mesh.positions[i * 3 + 1] as f64,
+                mesh.positions[i * 3 + 2] as f64,
+            )
+        };
+        let normal = Triangle::new(p(i0), p(i1), p(i2)).cross_product();
+        face_normals.push(normal);
+        face_units.push(normal.try_normalize(1e-12));
+
+        vertex_faces[i0].push(face as u32);
+        vertex_faces[i1].push(face as u32);
+        vertex_faces[i2].push(face as u32);
+    }
+
+    let mut positions: Vec<f32> = Vec::with_capacity(mesh.positions.len());
+    let mut normals: Vec<f32> = Vec::with_capacity(mesh.positions.len());
+    let mut indices = mesh.indices.clone();
+    let has_uvs = mesh.has_uvs();
+    let mut uvs: Vec<f32> = Vec::new();
+
+    // Per vertex: (seed unit normal, accumulated normal, output index) for each smoothing group
+    type SmoothingGroup = (Vector3<f64>, Vector3<f64>, u32);
+    let mut groups: SmallVec<[SmoothingGroup; 4]> = SmallVec::new();
+    let mut face_group: SmallVec<[usize; 8]> = SmallVec::new();
+
+    for (vertex, faces) in vertex_faces.iter().enumerate() {
+        if faces.is_empty() {
+            continue;
+        }
+
+        groups.clear();
+        face_group.clear();
+
+        for &face in faces {
+            let unit = face_units[face as usize].unwrap_or_else(Vector3::zeros);
+            let group = groups
+                .iter()
+                .position(|(seed, _, _)| seed.dot(&unit) >= cos_crease);
+            let group = match group {
+                Some(g) => g,
+                None => {
+                    groups.push((unit, Vector3::zeros(), 0));
+                    groups.len() - 1
+                }
+            };
+            groups[group].1 += face_normals[face as usize];
+            face_group.push(group);
+        }
+
+        for group in groups.iter_mut() {
+            let out_index = (positions.len() / 3) as u32;
+            positions.extend_from_slice(&mesh.positions[vertex * 3..vertex * 3 + 3]);
+            if has_uvs {
+                uvs.extend_from_slice(&mesh.uvs[vertex * 2..vertex * 2 + 2]);
+            }
+            let n = group
+                .1
+                .try_normalize(1e-12)
+                .unwrap_or_else(|| Vector3::new(0.0, 0.0, 1.0));
+            normals.extend_from_slice(&[n.x as f32, n.y as f32, n.z as f32]);
+            group.2 = out_index;
+        }
+
+        for (&face, &group) in faces.iter().zip(face_group.iter()) {
+            // Match against the original indices - `indices` already holds remapped values
+            let base = face as usize * 3;
+            for corner in base..base + 3 {
+                if mesh.indices[corner] as usize == vertex {
+                    indices[corner] = groups[group].2;
+                }
+            }
+        }
+    }
+
+    mesh.positions = positions;
+    mesh.normals = normals;
+    mesh.indices = indices;
+    mesh.uvs = uvs;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1389,4 +1472,87 @@ mod tests {
         let area = triangle.area();
         assert!((area - 0.5).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_crease_normals_keep_box_faces_flat() {
+        let mut mesh = aabb_to_mesh(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+        mesh.weld(crate::mesh::DEFAULT_WELD_TOLERANCE);
+        assert_eq!(mesh.vertex_count(), 8);
+
+        calculate_crease_normals(&mut mesh, DEFAULT_CREASE_ANGLE);
+
+        // Every corner is split into three vertices, one per adjacent face
+        assert_eq!(mesh.vertex_count(), 24);
+        assert_eq!(mesh.triangle_count(), 12);
+        for n in mesh.normals.chunks_exact(3) {
+            let axis_aligned = n.iter().filter(|c| c.abs() > 0.999).count();
+            assert_eq!(axis_aligned, 1, "normal {:?} should be axis aligned", n);
+        }
+    }
+
+    /// Two triangles sharing the edge 1-2, with vertex 3 lifted by `lift`
+    fn folded_pair(lift: f32) -> Mesh {
+        let mut mesh = Mesh::new();
+        mesh.positions = vec![
+            0.0, 0.0, 0.0,
+            1.0, 0.0, 0.0,
+            0.0, 1.0, 0.0,
+            1.0, 1.0, lift,
+        ];
+        mesh.indices = vec![0, 1, 2, 1, 3, 2];
+        mesh
+    }
+
+    fn vertex_normal(mesh: &Mesh, i: u32) -> Vector3<f64> {
+        let n = &mesh.normals[i as usize * 3..i as usize * 3 + 3];
+        Vector3::new(n[0] as f64, n[1] as f64, n[2] as f64)
+    }
+
+    #[test]
+    fn test_crease_normals_smooth_shallow_edges() {
+        // Face normals about 14 degrees apart, below the 30 degree crease angle
+        let lift = 10f64.to_radians().tan();
+        let mut mesh = folded_pair(lift as f32);
+        let flat = Vector3::new(0.0, 0.0, 1.0);
+        let tilted = Vector3::new(-lift, -lift, 1.0).normalize();
+
+        calculate_crease_normals(&mut mesh, DEFAULT_CREASE_ANGLE);
+        assert_eq!(mesh.vertex_count(), 4);
+        assert_eq!(mesh.indices, vec![0, 1, 2, 1, 3, 2]);
+
+        // Unshared corners keep their face normal
+        assert!((vertex_normal(&mesh, 0) - flat).norm() < 1e-6);
+        assert!((vertex_normal(&mesh, 3) - tilted).norm() < 1e-6);
+
+        // The shared edge gets one averaged normal between the two faces
+        let shared = vertex_normal(&mesh, 1);
+        assert!((vertex_normal(&mesh, 2) - shared).norm() < 1e-6);
+        assert!((shared.norm() - 1.0).abs() < 1e-6);
+        let between = flat.dot(&tilted);
+        assert!(shared.dot(&flat) > between + 1e-3);
+        assert!(shared.dot(&tilted) > between + 1e-3);
+        assert!(shared.dot(&flat.cross(&tilted)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_crease_normals_split_steep_edges() {
+        // Face normals about 55 degrees apart, above the crease angle
+        let mut mesh = folded_pair(1.0);
+        let flat = Vector3::new(0.0, 0.0, 1.0);
+        let tilted = Vector3::new(-1.0, -1.0, 1.0).normalize();
+
+        calculate_crease_normals(&mut mesh, DEFAULT_CREASE_ANGLE);
+
+        // The shared vertices are split, one copy per face
+        assert_eq!(mesh.vertex_count(), 6);
+        assert_eq!(mesh.triangle_count(), 2);
+        let (first, second) = mesh.indices.split_at(3);
+        assert!(first.iter().all(|i| !second.contains(i)));
+        for &i in first {
+            assert!((vertex_normal(&mesh, i) - flat).norm() < 1e-6);
+        }
+        for &i in second {
+            assert!((vertex_normal(&mesh, i) - tilted).norm() < 1e-6);
+        }
+    }
 }
@@ -0,0 +1,317 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! DXF Writer
+//!
+//! ASCII DXF (AutoCAD 2000, `AC1015`) with meters as drawing units. Layers
+//! follow the IFC class: cut fills go on `IfcWall`, `IfcSlab`, ... with the
+//! default type colour, projected edges on `IfcWall_Projection` in grey.
+//! Cut contours are closed `LWPOLYLINE`s with an optional `SOLID` `HATCH`
+//! behind them; projected edges are `LINE`s.
+//!
+//! Only the HEADER, TABLES and ENTITIES sections are written, which CAD
+//! viewers accept for plain 2D drawings.
+
+use super::format_number;
+use crate::plan_cut::PlanCut;
+use crate::profile::Profile2D;
+use crate::style::default_color;
+use ifc_lite_core::IfcType;
+use nalgebra::Point2;
+use std::fmt::Write;
+
+/// AutoCAD colour index for projected edges (grey)
+const PROJECTION_COLOR: i32 = 8;
+
+/// DXF output options
+#[derive(Debug, Clone)]
+pub struct DxfOptions {
+    /// Add a solid hatch to cut polygons
+    pub hatch: bool,
+}
+
+impl Default for DxfOptions {
+    fn default() -> Self {
+        Self { hatch: true }
+    }
+}
+
+/// Write a plan cut as a DXF document
+pub fn write_dxf(plan: &PlanCut, options: &DxfOptions) -> String {
+    let mut writer = DxfWriter::default();
+
+    // Layers in first-seen order: cut layers, then projection layers
+    let mut layers: Vec<(String, i32, Option<u32>)> = vec![("0".to_string(), 7, None)];
+    for element in &plan.cut {
+        let name = element.ifc_type.name().to_string();
+        if !layers.iter().any(|(n, _, _)| *n == name) {
+            layers.push((name, 7, Some(true_color(&element.ifc_type))));
+        }
+    }
+    for element in &plan.projected {
+        let name = projection_layer(&element.ifc_type);
+        if !layers.iter().any(|(n, _, _)| *n == name) {
+            layers.push((name, PROJECTION_COLOR, None));
+        }
+    }
+
+    // Entities are written to a separate buffer first so $HANDSEED is known
+    let mut entities = DxfWriter {
+        next_handle: 0x100,
+        ..Default::default()
+    };
+    for element in &plan.cut {
+        let layer = element.ifc_type.name();
+        for polygon in &element.polygons {
+            if options.hatch {
+                entities.hatch(layer, polygon);
+            }
+            for contour in std::iter::once(&polygon.outer).chain(polygon.holes.iter()) {
+                entities.lwpolyline(layer, contour);
+            }
+        }
+    }
+    for element in &plan.projected {
+        let layer = projection_layer(&element.ifc_type);
+        for [a, b] in &element.lines {
+            entities.line(&layer, a, b);
+        }
+    }
+
+    writer.section("HEADER");
+    writer.pair(9, "$ACADVER");
+    writer.pair(1, "AC1015");
+    writer.pair(9, "$INSUNITS");
+    // 6 = meters
+    writer.pair(70, 6);
+    writer.pair(9, "$HANDSEED");
+    writer.pair(5, format!("{:X}", entities.next_handle + 1));
+    writer.pair(0, "ENDSEC");
+
+    writer.section("TABLES");
+    writer.table("LTYPE", 1);
+    writer.pair(0, "LTYPE");
+    writer.handle();
+    writer.pair(100, "AcDbSymbolTableRecord");
+    writer.pair(100, "AcDbLinetypeTableRecord");
+    writer.pair(2, "CONTINUOUS");
+    writer.pair(70, 0);
+    writer.pair(3, "Solid line");
+    writer.pair(72, 65);
+    writer.pair(73, 0);
+    writer.pair(40, "0.0");
+    writer.pair(0, "ENDTAB");
+
+    writer.table("LAYER", layers.len());
+    for (name, color, rgb) in &layers {
+        writer.pair(0, "LAYER");
+        writer.handle();
+        writer.pair(100, "AcDbSymbolTableRecord");
+        writer.pair(100, "AcDbLayerTableRecord");
+        writer.pair(2, name);
+        writer.pair(70, 0);
+        writer.pair(62, color);
+        if let Some(rgb) = rgb {
+            writer.pair(420, rgb);
+        }
+        writer.pair(6, "CONTINUOUS");
+    }
+    writer.pair(0, "ENDTAB");
+    writer.pair(0, "ENDSEC");
+
+    writer.section("ENTITIES");
+    writer.out.push_str(&entities.out);
+    writer.pair(0, "ENDSEC");
+    writer.pair(0, "EOF");
+
+    writer.out
+}
+
+/// Layer for projected edges of an IFC class
+fn projection_layer(ifc_type: &IfcType) -> String {
+    format!("{}_Projection", ifc_type.name())
+}
+
+/// 24-bit true colour (group code 420) of the default type colour
+fn true_color(ifc_type: &IfcType) -> u32 {
+    let color = default_color(ifc_type);
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u32;
+    (channel(color[0]) << 16) | (channel(color[1]) << 8) | channel(color[2])
+}
+
+/// Group code / value pair writer with a running entity handle
+#[derive(Default)]
+struct DxfWriter {
+    out: String,
+    next_handle: u32,
+}
+
+impl DxfWriter {
+    fn pair(&mut self, code: i32, value: impl std::fmt::Display) {
+        let _ = write!(self.out, "{:>3}\n{}\n", code, value);
+    }
+
+    fn handle(&mut self) {
+        self.next_handle += 1;
+        let handle = format!("{:X}", self.next_handle);
+        self.pair(5, handle);
+    }
+
+    fn section(&mut self, name: &str) {
+        self.pair(0, "SECTION");
+        self.pair(2, name);
+    }
+
+    fn table(&mut self, name: &str, count: usize) {
+        self.pair(0, "TABLE");
+        self.pair(2, name);
+        self.handle();
+        self.pair(100, "AcDbSymbolTable");
+        self.pair(70, count);
+    }
+
+    fn entity(&mut self, kind: &str, layer: &str, subclass: &str) {
+        self.pair(0, kind);
+        self.handle();
+        self.pair(100, "AcDbEntity");
+        self.pair(8, layer);
+        self.pair(100, subclass);
+    }
+
+    fn point(&mut self, code: i32, p: &Point2<f64>) {
+        self.pair(code, format_number(p.x));
+        self.pair(code + 10, format_number(p.y));
+    }
+
+    fn line(&mut self, layer: &str, a: &Point2<f64>, b: &Point2<f64>) {
+        self.entity("LINE", layer, "AcDbLine");
+        self.point(10, a);
+        self.pair(30, 0);
+        self.point(11, b);
+        self.pair(31, 0);
+    }
+
+    fn lwpolyline(&mut self, layer: &str, contour: &[Point2<f64>]) {
+        if contour.len() < 2 {
+            return;
+        }
+        self.entity("LWPOLYLINE", layer, "AcDbPolyline");
+        self.pair(90, contour.len());
+        // Closed
+        self.pair(70, 1);
+        for p in contour {
+            self.point(10, p);
+        }
+    }
+
+    fn hatch(&mut self, layer: &str, polygon: &Profile2D) {
+        let contours: Vec<&Vec<Point2<f64>>> = std::iter::once(&polygon.outer)
+            .chain(polygon.holes.iter())
+            .filter(|c| c.len() >= 3)
+            .collect();
+        if contours.is_empty() {
+            return;
+        }
+
+        self.entity("HATCH", layer, "AcDbHatch");
+        self.pair(10, 0);
+        self.pair(20, 0);
+        self.pair(30, 0);
+        self.pair(210, 0);
+        self.pair(220, 0);
+        self.pair(230, 1);
+        self.pair(2, "SOLID");
+        // Solid fill, not associative
+        self.pair(70, 1);
+        self.pair(71, 0);
+        self.pair(91, contours.len());
+        for (i, contour) in contours.iter().enumerate() {
+            // Polyline boundary, outermost for the first loop
+            self.pair(92, if i == 0 { 2 | 1 | 16 } else { 2 });
+            self.pair(72, 0);
+            self.pair(73, 1);
+            self.pair(93, contour.len());
+            for p in contour.iter() {
+                self.point(10, p);
+            }
+            self.pair(97, 0);
+        }
+        // Odd parity, predefined pattern, no seed points
+        self.pair(75, 0);
+        self.pair(76, 1);
+        self.pair(98, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan_cut::{CutElement, ProjectedElement};
+
+    fn plan() -> PlanCut {
+        let mut profile = Profile2D::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(4.0, 0.0),
+            Point2::new(4.0, 4.0),
+            Point2::new(0.0, 4.0),
+        ]);
+        profile.add_hole(vec![
+            Point2::new(1.0, 1.0),
+            Point2::new(1.0, 3.0),
+            Point2::new(3.0, 3.0),
+            Point2::new(3.0, 1.0),
+        ]);
+        PlanCut {
+            cut: vec![CutElement {
+                express_id: 1,
+                ifc_type: IfcType::IfcColumn,
+                polygons: vec![profile],
+            }],
+            projected: vec![ProjectedElement {
+                express_id: 2,
+                ifc_type: IfcType::IfcSlab,
+                lines: vec![[Point2::new(0.0, 0.0), Point2::new(10.0, 0.0)]],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_write_dxf() {
+        let dxf = write_dxf(&plan(), &DxfOptions::default());
+        let lines: Vec<&str> = dxf.lines().collect();
+
+        assert_eq!(lines[..4], ["  0", "SECTION", "  2", "HEADER"]);
+        assert_eq!(lines[lines.len() - 2..], ["  0", "EOF"]);
+        assert!(dxf.contains("  2\nIfcColumn\n 70\n0\n 62\n7\n420\n10069683\n"));
+        assert!(dxf.contains("  2\nIfcSlab_Projection\n 70\n0\n 62\n8\n"));
+
+        // Two closed polylines and one hatch with two boundary paths
+        let count = |kind: &str| {
+            lines
+                .windows(2)
+                .filter(|w| w[0] == "  0" && w[1] == kind)
+                .count()
+        };
+        assert_eq!(count("LWPOLYLINE"), 2);
+        assert_eq!(count("HATCH"), 1);
+        assert_eq!(count("LINE"), 1);
+        assert!(dxf.contains(" 91\n2\n 92\n19\n"));
+
+        // Handles are unique
+        let handles: Vec<&str> = lines
+            .chunks(2)
+            .filter(|pair| pair[0] == "  5")
+            .map(|pair| pair[1])
+            .collect();
+        let unique: std::collections::HashSet<_> = handles.iter().collect();
+        assert_eq!(handles.len(), unique.len());
+    }
+
+    #[test]
+    fn test_write_dxf_without_hatch() {
+        let dxf = write_dxf(&plan(), &DxfOptions { hatch: false });
+        assert!(!dxf.contains("HATCH"));
+        assert!(dxf.contains("LWPOLYLINE"));
+    }
+}
@@ -0,0 +1,262 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Element Meshing for Export
+//!
+//! Shared front end of the file exporters: scan the model, index openings,
+//! batch-triangulate FacetedBreps and mesh every matching element in
+//! parallel. Meshes stay in the element's own coordinates with the placement
+//! alongside, so GLB and 3D Tiles can instance repeated geometry; writers that
+//! need world coordinates call [`ElementMesh::world_mesh`].
+//!
+//! Georeferenced models are meshed relative to an `RtcOffset` from
+//! [`detect_rtc_offset`]: placements are shifted in f64 before anything is
+//! stored as f32, so the offset can be handed to the writers without losing
+//! precision.
+
+use super::gltf::ElementInfo;
+use crate::csg::calculate_crease_normals;
+use crate::mesh::{Mesh, DEFAULT_WELD_TOLERANCE};
+use crate::representation::RepresentationSelection;
+use crate::router::GeometryRouter;
+use crate::style::StyleIndex;
+use crate::DEFAULT_CREASE_ANGLE;
+use ifc_lite_core::{build_entity_index, ElementMatcher, EntityDecoder, EntityScanner, RtcOffset};
+use nalgebra::{Matrix4, Point3, Vector3};
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+use std::sync::Arc;
+
+/// Meshed element ready for export
+#[derive(Debug, Clone)]
+pub struct ElementMesh {
+    /// Express ID, GlobalId, type and name
+    pub info: ElementInfo,
+    /// Triangles in the element's coordinates (meters)
+    pub mesh: Mesh,
+    /// Placement into model coordinates (meters) relative to the RTC offset;
+    /// identity for elements with openings, which are meshed in model
+    /// coordinates
+    pub transform: Matrix4<f64>,
+    /// RGBA colour from the element's style
+    pub color: [f32; 4],
+}
+
+impl ElementMesh {
+    /// Mesh with the placement applied, transformed in f64
+    pub fn world_mesh(&self) -> Mesh {
+        let mut mesh = self.mesh.clone();
+        if self.transform == Matrix4::identity() {
+            return mesh;
+        }
+        for p in mesh.positions.chunks_exact_mut(3) {
+            let point = Point3::new(p[0] as f64, p[1] as f64, p[2] as f64);
+            let t = self.transform.transform_point(&point);
+            p.copy_from_slice(&[t.x as f32, t.y as f32, t.z as f32]);
+        }
+        for n in mesh.normals.chunks_exact_mut(3) {
+            let normal = Vector3::new(n[0] as f64, n[1] as f64, n[2] as f64);
+            let t = self.transform.transform_vector(&normal).normalize();
+            n.copy_from_slice(&[t.x as f32, t.y as f32, t.z as f32]);
+        }
+        mesh
+    }
+}
+
+/// Offset bringing a model far (>10 km) from the origin back to it
+///
+/// Sampled from element placements before meshing; zero for models near the
+/// origin.
+pub fn detect_rtc_offset(content: &str) -> RtcOffset {
+    let mut decoder = EntityDecoder::new(content);
+    let router = GeometryRouter::with_units(content, &mut decoder);
+    let (x, y, z) = router.detect_rtc_offset_from_first_element(content, &mut decoder);
+    RtcOffset { x, y, z }
+}
+
+/// Mesh every element with geometry that `matcher` accepts
+///
+/// Meshes are welded and get crease-aware normals, as in the viewer.
+/// Elements come back in file order.
+pub fn mesh_elements_local(
+    content: &str,
+    selection: &RepresentationSelection,
+    matcher: &ElementMatcher,
+) -> Vec<ElementMesh> {
+    mesh_elements_local_with_rtc(content, selection, matcher, &RtcOffset::default())
+}
+
+/// Mesh every element that `matcher` accepts relative to `rtc`
+pub fn mesh_elements_local_with_rtc(
+    content: &str,
+    selection: &RepresentationSelection,
+    matcher: &ElementMatcher,
+    rtc: &RtcOffset,
+) -> Vec<ElementMesh> {
+    let entity_index = Arc::new(build_entity_index(content));
+    let mut decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+    let style_index = StyleIndex::build(content, &mut decoder);
+
+    // Collect geometry entities and openings
+    let mut scanner = EntityScanner::new(content);
+    let mut faceted_brep_ids: Vec<u32> = Vec::new();
+    let mut void_index: FxHashMap<u32, Vec<u32>> = FxHashMap::default();
+    let mut jobs: Vec<(u32, usize, usize)> = Vec::new();
+
+    while let Some((id, type_name, start, end)) = scanner.next_entity() {
+        if type_name == "IFCFACETEDBREP" {
+            faceted_brep_ids.push(id);
+        } else if type_name == "IFCRELVOIDSELEMENT" {
+            if let Ok(entity) = decoder.decode_at(start, end) {
+                if let (Some(host), Some(opening)) = (entity.get_ref(4), entity.get_ref(5)) {
+                    void_index.entry(host).or_default().push(opening);
+                }
+            }
+        }
+
+        if ifc_lite_core::has_geometry_by_name(type_name) && matcher.matches_name(id, type_name) {
+            jobs.push((id, start, end));
+        }
+    }
+
+    let unit_scale = GeometryRouter::with_units(content, &mut decoder).unit_scale();
+    let plane_angle_scale = decoder.plane_angle_scale();
+    // Filtered exports mesh their FacetedBreps on demand instead of all up front
+    let faceted_breps = Arc::new(if matcher.is_all() {
+        GeometryRouter::triangulate_faceted_breps(&faceted_brep_ids, &mut decoder)
+    } else {
+        FxHashMap::default()
+    });
+
+    let new_router = || {
+        let mut router = GeometryRouter::with_scale_and_rtc(unit_scale, (rtc.x, rtc.y, rtc.z));
+        router.set_representation_selection(selection.clone());
+        router.set_faceted_breps(faceted_breps.clone());
+        router
+    };
+    let mesh_element = |router: &mut GeometryRouter, (id, start, end): (u32, usize, usize)| {
+        let mut local_decoder = EntityDecoder::with_arc_index(content, entity_index.clone());
+        local_decoder.set_plane_angle_scale(plane_angle_scale);
+        let entity = local_decoder.decode_at_with_id(id, start, end).ok()?;
+        if !entity.get(6).is_some_and(|a| !a.is_null()) {
+            return None;
+        }
+
+        let (mut mesh, transform) = router
+            .process_element_local(&entity, &mut local_decoder, &void_index)
+            .ok()?;
+        if mesh.is_empty() {
+            return None;
+        }
+        mesh.weld(DEFAULT_WELD_TOLERANCE);
+        calculate_crease_normals(&mut mesh, DEFAULT_CREASE_ANGLE);
+
+        Some(ElementMesh {
+            info: ElementInfo {
+                express_id: id,
+                global_id: entity.get_string(0).map(str::to_string),
+                ifc_type: entity.ifc_type,
+                name: entity.get_string(2).map(str::to_string),
+            },
+            mesh,
+            transform,
+            color: style_index.element_color(id, &entity.ifc_type),
+        })
+    };
+
+    // One router per worker thread, so mapped items are meshed once per thread
+    #[cfg(not(target_arch = "wasm32"))]
+    let elements = jobs
+        .into_par_iter()
+        .map_init(new_router, mesh_element)
+        .flatten()
+        .collect();
+
+    // On WASM: no threads, one router for the whole model
+    #[cfg(target_arch = "wasm32")]
+    let elements = {
+        let mut router = new_router();
+        jobs.into_iter()
+            .filter_map(|job| mesh_element(&mut router, job))
+            .collect()
+    };
+
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODEL: &str = r#"ISO-10303-21;
+HEADER;
+FILE_SCHEMA(('IFC4'));
+ENDSEC;
+DATA;
+#1=IFCCARTESIANPOINT((0.,0.,0.));
+#2=IFCDIRECTION((0.,0.,1.));
+#3=IFCDIRECTION((1.,0.,0.));
+#4=IFCAXIS2PLACEMENT3D(#1,#2,#3);
+#5=IFCLOCALPLACEMENT($,#4);
+#6=IFCCARTESIANPOINT((10.,20.,0.));
+#7=IFCAXIS2PLACEMENT3D(#6,#2,#3);
+#8=IFCLOCALPLACEMENT(#5,#7);
+#9=IFCRECTANGLEPROFILEDEF(.AREA.,$,$,2.,1.);
+#10=IFCEXTRUDEDAREASOLID(#9,#4,#2,3.);
+#11=IFCSHAPEREPRESENTATION($,'Body','SweptSolid',(#10));
+#12=IFCPRODUCTDEFINITIONSHAPE($,$,(#11));
+#13=IFCCOLUMN('2O2Fr$t4X7Zf8NOew3FLOH',$,'C1',$,$,#8,#12,$,$);
+#14=IFCWALL('2O2Fr$t4X7Zf8NOew3FLOI',$,'W1',$,$,#5,#12,$,$);
+#15=IFCBEAM('2O2Fr$t4X7Zf8NOew3FLOJ',$,'B1',$,$,#5,$,$,$);
+ENDSEC;
+END-ISO-10303-21;
+"#;
+
+    #[test]
+    fn test_mesh_elements_local() {
+        let elements = mesh_elements_local(
+            MODEL,
+            &RepresentationSelection::default(),
+            &ElementMatcher::all(),
+        );
+        // The beam has no representation
+        let ids: Vec<u32> = elements.iter().map(|e| e.info.express_id).collect();
+        assert_eq!(ids, vec![13, 14]);
+
+        let column = &elements[0];
+        assert_eq!(column.info.name.as_deref(), Some("C1"));
+        assert_eq!(column.transform[(0, 3)], 10.0);
+        assert_eq!(column.transform[(1, 3)], 20.0);
+        assert_eq!(column.mesh.normals.len(), column.mesh.positions.len());
+
+        let (min, max) = column.world_mesh().bounds();
+        assert!((min.x - 9.0).abs() < 1e-5 && (max.x - 11.0).abs() < 1e-5);
+        assert!((min.y - 19.5).abs() < 1e-5 && (max.z - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_mesh_elements_with_rtc() {
+        // Column on Swiss LV95 coordinates, wall at the origin
+        let model = MODEL.replace("(10.,20.,0.)", "(2600010.,1200020.,0.)");
+        assert!(!detect_rtc_offset(MODEL).is_significant());
+        let rtc = detect_rtc_offset(&model);
+        assert_eq!((rtc.x, rtc.y, rtc.z), (2_600_010.0, 1_200_020.0, 0.0));
+
+        let elements = mesh_elements_local_with_rtc(
+            &model,
+            &RepresentationSelection::default(),
+            &ElementMatcher::all(),
+            &rtc,
+        );
+        let column = &elements[0];
+        assert_eq!(column.transform[(0, 3)], 0.0);
+        assert_eq!(column.transform[(1, 3)], 0.0);
+
+        // Sub-millimeter detail survives the f32 positions
+        let (min, max) = column.world_mesh().bounds();
+        assert_eq!((min.x, max.x), (-1.0, 1.0));
+        assert_eq!((min.y, max.y), (-0.5, 0.5));
+    }
+}
@@ -0,0 +1,714 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! glTF 2.0 / GLB Writer
+//!
+//! Binary glTF for game engines, AR viewers and DCC tools. Meshes are added
+//! in IFC coordinates (Z-up, meters) together with their placement; a root
+//! node rotates the model into glTF's Y-up frame.
+//!
+//! Identical geometry with the same colour is written once. When it is used
+//! by several elements and every placement is a plain translation, rotation
+//! and scale, the elements become one node with `EXT_mesh_gpu_instancing`;
+//! otherwise each element gets its own node. Element metadata is stored in
+//! node `extras`:
+//!
+//! ```json
+//! { "expressId": 42, "globalId": "2hF2b$7X5Dy8Y9tY0OK2ez", "ifcType": "IfcWall" }
+//! ```
+//!
+//! Instanced nodes carry the same objects as `extras.instances`, in instance
+//! order.
+
+use crate::mesh::Mesh;
+use ifc_lite_core::{IfcType, RtcOffset};
+use nalgebra::{Matrix3, Matrix4, Rotation3, UnitQuaternion, Vector3};
+use rustc_hash::{FxHashMap, FxHasher};
+use serde_json::{json, Map, Value};
+use std::hash::{Hash, Hasher};
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+pub(crate) const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const INSTANCING: &str = "EXT_mesh_gpu_instancing";
+
+/// Metadata written to a node's extras
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementInfo {
+    /// Express ID
+    pub express_id: u32,
+    /// IFC GlobalId, if known
+    pub global_id: Option<String>,
+    /// IFC type
+    pub ifc_type: IfcType,
+    /// Element name, used as node name when present
+    pub name: Option<String>,
+}
+
+impl ElementInfo {
+    /// Element without GlobalId or name
+    pub fn new(express_id: u32, ifc_type: IfcType) -> Self {
+        Self {
+            express_id,
+            global_id: None,
+            ifc_type,
+            name: None,
+        }
+    }
+
+    fn extras(&self) -> Value {
+        let mut extras = Map::new();
+        extras.insert("expressId".into(), json!(self.express_id));
+        if let Some(global_id) = &self.global_id {
+            extras.insert("globalId".into(), json!(global_id));
+        }
+        extras.insert("ifcType".into(), json!(self.ifc_type.name()));
+        if let Some(name) = &self.name {
+            extras.insert("name".into(), json!(name));
+        }
+        Value::Object(extras)
+    }
+
+    fn node_name(&self) -> String {
+        match &self.name {
+            Some(name) if !name.is_empty() => name.clone(),
+            _ => format!("{} #{}", self.ifc_type.name(), self.express_id),
+        }
+    }
+}
+
+/// GLB output options
+#[derive(Debug, Clone)]
+pub struct GlbOptions {
+    /// Use `EXT_mesh_gpu_instancing` for repeated geometry
+    pub instancing: bool,
+    /// Minimum number of elements sharing a geometry before it is instanced
+    pub min_instances: usize,
+}
+
+impl Default for GlbOptions {
+    fn default() -> Self {
+        Self {
+            instancing: true,
+            min_instances: 2,
+        }
+    }
+}
+
+/// One shared geometry and the elements using it
+struct Geometry {
+    mesh: Mesh,
+    color: [f32; 4],
+    instances: Vec<(ElementInfo, Matrix4<f64>)>,
+}
+
+/// Collects element meshes and writes a GLB file
+#[derive(Default)]
+pub struct GlbBuilder {
+    options: GlbOptions,
+    geometries: Vec<Geometry>,
+    /// (geometry hash, colour bits) -> index into `geometries`
+    lookup: FxHashMap<(u64, [u32; 4]), usize>,
+    /// Offset of the element transforms from model coordinates (Z-up meters)
+    rtc_offset: Option<RtcOffset>,
+}
+
+impl GlbBuilder {
+    /// Create a builder with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a builder with the given options
+    pub fn with_options(options: GlbOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    /// Mark element transforms as relative to `rtc`
+    ///
+    /// The offset is written as the root node's translation, in f64, so the
+    /// model keeps its position without large coordinates in the buffers.
+    pub fn set_rtc_offset(&mut self, rtc: &RtcOffset) {
+        self.rtc_offset = rtc.is_significant().then(|| rtc.clone());
+    }
+
+    /// Add an element mesh placed by `transform`
+    ///
+    /// Meshes identical to an earlier one (same positions, indices and
+    /// colour) are shared. Pass the identity for world-space meshes.
+    pub fn add(
+        &mut self,
+        element: ElementInfo,
+        mesh: &Mesh,
+        transform: &Matrix4<f64>,
+        color: [f32; 4],
+    ) {
+        if mesh.is_empty() {
+            return;
+        }
+        let key = (geometry_hash(mesh), color.map(f32::to_bits));
+        let index = *self.lookup.entry(key).or_insert_with(|| {
+            self.geometries.push(Geometry {
+                mesh: mesh.clone(),
+                color,
+                instances: Vec::new(),
+            });
+            self.geometries.len() - 1
+        });
+        self.geometries[index].instances.push((element, *transform));
+    }
+
+    /// Add a geometry that is already shared by several elements
+    pub fn add_instanced<I>(&mut self, mesh: Mesh, color: [f32; 4], instances: I)
+    where
+        I: IntoIterator<Item = (ElementInfo, Matrix4<f64>)>,
+    {
+        let instances: Vec<_> = instances.into_iter().collect();
+        if mesh.is_empty() || instances.is_empty() {
+            return;
+        }
+        self.geometries.push(Geometry {
+            mesh,
+            color,
+            instances,
+        });
+    }
+
+    /// Number of distinct geometries
+    pub fn geometry_count(&self) -> usize {
+        self.geometries.len()
+    }
+
+    /// Number of elements
+    pub fn element_count(&self) -> usize {
+        self.geometries.iter().map(|g| g.instances.len()).sum()
+    }
+
+    /// Check if nothing was added
+    pub fn is_empty(&self) -> bool {
+        self.geometries.is_empty()
+    }
+
+    /// Write the GLB file
+    pub fn build(&self) -> Vec<u8> {
+        let mut doc = GltfDocument::default();
+        let mut children = Vec::new();
+        let mut materials: FxHashMap<[u32; 4], usize> = FxHashMap::default();
+
+        for geometry in &self.geometries {
+            let material = *materials
+                .entry(geometry.color.map(f32::to_bits))
+                .or_insert_with(|| doc.add_material(geometry.color));
+            let mesh = doc.add_mesh(&geometry.mesh, material);
+
+            let trs: Option<Vec<Trs>> = if self.options.instancing
+                && geometry.instances.len() >= self.options.min_instances.max(2)
+            {
+                geometry
+                    .instances
+                    .iter()
+                    .map(|(_, transform)| Trs::decompose(transform))
+                    .collect()
+            } else {
+                None
+            };
+
+            match trs {
+                Some(trs) => children.push(doc.add_instanced_node(mesh, geometry, &trs)),
+                None => {
+                    for (element, transform) in &geometry.instances {
+                        children.push(doc.add_node(mesh, element, transform));
+                    }
+                }
+            }
+        }
+
+        if let Some(rtc) = &self.rtc_offset {
+            // The root node turns Z-up into glTF's Y-up, so the offset is given in Y-up too
+            doc.root_translation = Some([rtc.x, rtc.z, -rtc.y]);
+        }
+        doc.finish(children)
+    }
+}
+
+/// Translation, rotation and scale of an affine transform without shear
+struct Trs {
+    translation: [f32; 3],
+    rotation: [f32; 4],
+    scale: [f32; 3],
+}
+
+impl Trs {
+    /// Split a transform, or `None` if it shears or projects
+    fn decompose(transform: &Matrix4<f64>) -> Option<Self> {
+        if transform.row(3).transpose() != nalgebra::Vector4::new(0.0, 0.0, 0.0, 1.0) {
+            return None;
+        }
+        let linear: Matrix3<f64> = transform.fixed_view::<3, 3>(0, 0).into();
+        let mut scale = Vector3::new(
+            linear.column(0).norm(),
+            linear.column(1).norm(),
+            linear.column(2).norm(),
+        );
+        if scale.min() < 1e-12 {
+            return None;
+        }
+        // Mirror through X for left-handed placements
+        if linear.determinant() < 0.0 {
+            scale.x = -scale.x;
+        }
+        let rotation = linear * Matrix3::from_diagonal(&scale.map(|s| 1.0 / s));
+        if (rotation.transpose() * rotation - Matrix3::identity())
+            .abs()
+            .max()
+            > 1e-5
+        {
+            return None;
+        }
+        let q = UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(rotation));
+        Some(Self {
+            translation: [
+                transform[(0, 3)] as f32,
+                transform[(1, 3)] as f32,
+                transform[(2, 3)] as f32,
+            ],
+            rotation: [q.i as f32, q.j as f32, q.k as f32, q.w as f32],
+            scale: [scale.x as f32, scale.y as f32, scale.z as f32],
+        })
+    }
+}
+
+/// glTF JSON arrays and the binary buffer being assembled
+#[derive(Default)]
+pub(crate) struct GltfDocument {
+    buffer: Vec<u8>,
+    buffer_views: Vec<Value>,
+    accessors: Vec<Value>,
+    materials: Vec<Value>,
+    pub(crate) meshes: Vec<Value>,
+    nodes: Vec<Value>,
+    /// Top-level extensions object
+    pub(crate) extensions: Map<String, Value>,
+    pub(crate) extensions_used: Vec<&'static str>,
+    /// Translation of the root node, in glTF (Y-up) coordinates
+    pub(crate) root_translation: Option<[f64; 3]>,
+}
+
+impl GltfDocument {
+    /// Append raw data as a buffer view and return its index
+    pub(crate) fn add_view(&mut self, bytes: &[u8], target: Option<u32>) -> usize {
+        // Accessor data must be 4-byte aligned
+        self.add_aligned_view(bytes, target, 4)
+    }
+
+    /// Append raw data starting at a multiple of `alignment`
+    pub(crate) fn add_aligned_view(
+        &mut self,
+        bytes: &[u8],
+        target: Option<u32>,
+        alignment: usize,
+    ) -> usize {
+        while !self.buffer.len().is_multiple_of(alignment) {
+            self.buffer.push(0);
+        }
+        let mut view = json!({
+            "buffer": 0,
+            "byteOffset": self.buffer.len(),
+            "byteLength": bytes.len(),
+        });
+        if let Some(target) = target {
+            view["target"] = json!(target);
+        }
+        self.buffer.extend_from_slice(bytes);
+        self.buffer_views.push(view);
+        self.buffer_views.len() - 1
+    }
+
+    pub(crate) fn add_floats(
+        &mut self,
+        data: &[f32],
+        kind: &str,
+        target: Option<u32>,
+        bounds: bool,
+    ) -> usize {
+        let components = match kind {
+            "VEC4" => 4,
+            "VEC3" => 3,
+            _ => 1,
+        };
+        let bytes: Vec<u8> = data.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let view = self.add_view(&bytes, target);
+        let mut accessor = json!({
+            "bufferView": view,
+            "componentType": FLOAT,
+            "count": data.len() / components,
+            "type": kind,
+        });
+        if bounds {
+            let mut min = vec![f32::MAX; components];
+            let mut max = vec![f32::MIN; components];
+            for chunk in data.chunks_exact(components) {
+                for (i, v) in chunk.iter().enumerate() {
+                    min[i] = min[i].min(*v);
+                    max[i] = max[i].max(*v);
+                }
+            }
+            accessor["min"] = json!(min);
+            accessor["max"] = json!(max);
+        }
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    pub(crate) fn add_indices(&mut self, indices: &[u32]) -> usize {
+        let bytes: Vec<u8> = indices.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let view = self.add_view(&bytes, Some(ELEMENT_ARRAY_BUFFER));
+        self.accessors.push(json!({
+            "bufferView": view,
+            "componentType": UNSIGNED_INT,
+            "count": indices.len(),
+            "type": "SCALAR",
+        }));
+        self.accessors.len() - 1
+    }
+
+    pub(crate) fn add_material(&mut self, color: [f32; 4]) -> usize {
+        let mut material = json!({
+            "pbrMetallicRoughness": {
+                "baseColorFactor": color,
+                "metallicFactor": 0.0,
+                "roughnessFactor": 0.8,
+            },
+            "doubleSided": true,
+        });
+        if color[3] < 1.0 {
+            material["alphaMode"] = json!("BLEND");
+        }
+        self.materials.push(material);
+        self.materials.len() - 1
+    }
+
+    fn add_mesh(&mut self, mesh: &Mesh, material: usize) -> usize {
+        let position = self.add_floats(&mesh.positions, "VEC3", Some(ARRAY_BUFFER), true);
+        let mut attributes = json!({ "POSITION": position });
+        if mesh.normals.len() == mesh.positions.len() {
+            let normal = self.add_floats(&mesh.normals, "VEC3", Some(ARRAY_BUFFER), false);
+            attributes["NORMAL"] = json!(normal);
+        }
+        let indices = self.add_indices(&mesh.indices);
+        self.meshes.push(json!({
+            "primitives": [{
+                "attributes": attributes,
+                "indices": indices,
+                "material": material,
+                "mode": 4,
+            }],
+        }));
+        self.meshes.len() - 1
+    }
+
+    fn add_node(&mut self, mesh: usize, element: &ElementInfo, transform: &Matrix4<f64>) -> usize {
+        let mut node = json!({
+            "name": element.node_name(),
+            "mesh": mesh,
+            "extras": element.extras(),
+        });
+        if *transform != Matrix4::identity() {
+            // Column-major, as stored by nalgebra
+            node["matrix"] = json!(transform.as_slice());
+        }
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    fn add_instanced_node(&mut self, mesh: usize, geometry: &Geometry, trs: &[Trs]) -> usize {
+        let translations: Vec<f32> = trs.iter().flat_map(|t| t.translation).collect();
+        let rotations: Vec<f32> = trs.iter().flat_map(|t| t.rotation).collect();
+        let scales: Vec<f32> = trs.iter().flat_map(|t| t.scale).collect();
+        let translation = self.add_floats(&translations, "VEC3", None, false);
+        let rotation = self.add_floats(&rotations, "VEC4", None, false);
+        let scale = self.add_floats(&scales, "VEC3", None, false);
+
+        let (first, _) = &geometry.instances[0];
+        let instances: Vec<Value> = geometry
+            .instances
+            .iter()
+            .map(|(element, _)| element.extras())
+            .collect();
+
+        if !self.extensions_used.contains(&INSTANCING) {
+            self.extensions_used.push(INSTANCING);
+        }
+        self.nodes.push(json!({
+            "name": format!("{} x{}", first.ifc_type.name(), trs.len()),
+            "mesh": mesh,
+            "extensions": {
+                INSTANCING: {
+                    "attributes": {
+                        "TRANSLATION": translation,
+                        "ROTATION": rotation,
+                        "SCALE": scale,
+                    },
+                },
+            },
+            "extras": { "instances": instances },
+        }));
+        self.nodes.len() - 1
+    }
+
+    /// Add a node with a mesh and no transform
+    pub(crate) fn add_mesh_node(&mut self, mesh: usize, name: &str) -> usize {
+        self.nodes.push(json!({ "name": name, "mesh": mesh }));
+        self.nodes.len() - 1
+    }
+
+    /// Add the Z-up to Y-up root node and serialize to GLB
+    pub(crate) fn finish(mut self, children: Vec<usize>) -> Vec<u8> {
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        let mut root = json!({
+            "name": "IFC Model",
+            "rotation": [-half, 0.0, 0.0, half],
+        });
+        if let Some(translation) = self.root_translation {
+            root["translation"] = json!(translation);
+        }
+        if !children.is_empty() {
+            root["children"] = json!(children);
+        }
+        self.nodes.push(root);
+        let root = self.nodes.len() - 1;
+
+        while self.buffer.len() % 4 != 0 {
+            self.buffer.push(0);
+        }
+
+        let mut gltf = json!({
+            "asset": { "version": "2.0", "generator": "IFC-Lite" },
+            "scene": 0,
+            "scenes": [{ "nodes": [root] }],
+            "nodes": self.nodes,
+        });
+        for (key, values) in [
+            ("meshes", self.meshes),
+            ("materials", self.materials),
+            ("accessors", self.accessors),
+            ("bufferViews", self.buffer_views),
+        ] {
+            if !values.is_empty() {
+                gltf[key] = Value::Array(values);
+            }
+        }
+        if !self.buffer.is_empty() {
+            gltf["buffers"] = json!([{ "byteLength": self.buffer.len() }]);
+        }
+        if !self.extensions_used.is_empty() {
+            gltf["extensionsUsed"] = json!(self.extensions_used);
+        }
+        if !self.extensions.is_empty() {
+            gltf["extensions"] = Value::Object(self.extensions);
+        }
+
+        let mut json_chunk = serde_json::to_vec(&gltf).unwrap_or_default();
+        while json_chunk.len() % 4 != 0 {
+            json_chunk.push(b' ');
+        }
+
+        let mut total = 12 + 8 + json_chunk.len();
+        if !self.buffer.is_empty() {
+            total += 8 + self.buffer.len();
+        }
+
+        let mut glb = Vec::with_capacity(total);
+        glb.extend_from_slice(&GLB_MAGIC.to_le_bytes());
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&(total as u32).to_le_bytes());
+        glb.extend_from_slice(&(json_chunk.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&CHUNK_JSON.to_le_bytes());
+        glb.extend_from_slice(&json_chunk);
+        if !self.buffer.is_empty() {
+            glb.extend_from_slice(&(self.buffer.len() as u32).to_le_bytes());
+            glb.extend_from_slice(&CHUNK_BIN.to_le_bytes());
+            glb.extend_from_slice(&self.buffer);
+        }
+        glb
+    }
+}
+
+/// Hash of positions and indices, for sharing identical geometry
+fn geometry_hash(mesh: &Mesh) -> u64 {
+    let mut hasher = FxHasher::default();
+    mesh.positions.len().hash(&mut hasher);
+    mesh.indices.len().hash(&mut hasher);
+    for pos in &mesh.positions {
+        pos.to_bits().hash(&mut hasher);
+    }
+    for idx in &mesh.indices {
+        idx.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extrusion::extrude_profile;
+    use crate::profile::Profile2D;
+    use nalgebra::Point2;
+
+    fn column() -> Mesh {
+        let profile = Profile2D::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(0.3, 0.0),
+            Point2::new(0.3, 0.3),
+            Point2::new(0.0, 0.3),
+        ]);
+        extrude_profile(&profile, 3.0, None).unwrap()
+    }
+
+    fn parse_glb(glb: &[u8]) -> (Value, &[u8]) {
+        let u32_at = |i: usize| u32::from_le_bytes(glb[i..i + 4].try_into().unwrap());
+        assert_eq!(u32_at(0), GLB_MAGIC);
+        assert_eq!(u32_at(4), 2);
+        assert_eq!(u32_at(8) as usize, glb.len());
+        let json_len = u32_at(12) as usize;
+        assert_eq!(u32_at(16), CHUNK_JSON);
+        let json: Value = serde_json::from_slice(&glb[20..20 + json_len]).unwrap();
+        let bin_start = 20 + json_len;
+        assert_eq!(u32_at(bin_start + 4), CHUNK_BIN);
+        let bin_len = u32_at(bin_start) as usize;
+        (json, &glb[bin_start + 8..bin_start + 8 + bin_len])
+    }
+
+    #[test]
+    fn test_glb_instancing_and_extras() {
+        let column = column();
+        let gray = [0.6, 0.65, 0.7, 1.0];
+        let mut builder = GlbBuilder::new();
+        for i in 0..3 {
+            let mut info = ElementInfo::new(10 + i, IfcType::IfcColumn);
+            info.global_id = Some(format!("GUID{}", i));
+            let transform = Matrix4::new_translation(&Vector3::new(i as f64 * 5.0, 0.0, 0.0));
+            builder.add(info, &column, &transform, gray);
+        }
+        // Sheared placement cannot be instanced
+        let mut wall_info = ElementInfo::new(20, IfcType::IfcWall);
+        wall_info.name = Some("Wall 1".to_string());
+        let mut shear = Matrix4::identity();
+        shear[(0, 1)] = 0.5;
+        builder.add(wall_info, &column, &shear, [0.85, 0.85, 0.85, 1.0]);
+
+        assert_eq!(builder.geometry_count(), 2);
+        assert_eq!(builder.element_count(), 4);
+
+        let glb = builder.build();
+        let (json, bin) = parse_glb(&glb);
+
+        assert_eq!(json["extensionsUsed"], json!([INSTANCING]));
+        assert_eq!(
+            json["buffers"][0]["byteLength"].as_u64().unwrap() as usize,
+            bin.len()
+        );
+        assert_eq!(json["materials"].as_array().unwrap().len(), 2);
+
+        let nodes = json["nodes"].as_array().unwrap();
+        let root = &nodes[json["scenes"][0]["nodes"][0].as_u64().unwrap() as usize];
+        assert_eq!(root["children"].as_array().unwrap().len(), 2);
+
+        let instanced = &nodes[0];
+        let attributes = &instanced["extensions"][INSTANCING]["attributes"];
+        let translation = &json["accessors"][attributes["TRANSLATION"].as_u64().unwrap() as usize];
+        assert_eq!(translation["count"], 3);
+        assert_eq!(instanced["extras"]["instances"][2]["expressId"], 12);
+        assert_eq!(instanced["extras"]["instances"][1]["globalId"], "GUID1");
+        assert_eq!(instanced["extras"]["instances"][0]["ifcType"], "IfcColumn");
+
+        let single = &nodes[1];
+        assert_eq!(single["name"], "Wall 1");
+        assert_eq!(single["extras"]["expressId"], 20);
+        assert_eq!(single["matrix"].as_array().unwrap().len(), 16);
+        assert!(single.get("extensions").is_none());
+    }
+
+    #[test]
+    fn test_glb_without_instancing() {
+        let column = column();
+        let mut builder = GlbBuilder::with_options(GlbOptions {
+            instancing: false,
+            ..Default::default()
+        });
+        for i in 0..2 {
+            builder.add(
+                ElementInfo::new(i, IfcType::IfcColumn),
+                &column,
+                &Matrix4::identity(),
+                [0.5, 0.5, 0.5, 0.5],
+            );
+        }
+        let (json, _) = parse_glb(&builder.build());
+
+        assert!(json.get("extensionsUsed").is_none());
+        assert_eq!(json["meshes"].as_array().unwrap().len(), 1);
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(json["nodes"][0]["mesh"], 0);
+        assert!(json["nodes"][0].get("matrix").is_none());
+        assert_eq!(json["materials"][0]["alphaMode"], "BLEND");
+    }
+
+    #[test]
+    fn test_glb_rtc_offset() {
+        let root_of = |rtc: RtcOffset| {
+            let mut builder = GlbBuilder::new();
+            builder.set_rtc_offset(&rtc);
+            builder.add(
+                ElementInfo::new(1, IfcType::IfcColumn),
+                &column(),
+                &Matrix4::identity(),
+                [0.5, 0.5, 0.5, 1.0],
+            );
+            let (json, _) = parse_glb(&builder.build());
+            let root = json["scenes"][0]["nodes"][0].as_u64().unwrap() as usize;
+            json["nodes"][root].clone()
+        };
+
+        let root = root_of(RtcOffset {
+            x: 2_600_010.25,
+            y: 1_200_020.5,
+            z: 400.0,
+        });
+        assert_eq!(root["translation"], json!([2_600_010.25, 400.0, -1_200_020.5]));
+
+        // Offsets near the origin are not written
+        let root = root_of(RtcOffset {
+            x: 12.0,
+            y: 0.0,
+            z: 0.0,
+        });
+        assert!(root.get("translation").is_none());
+    }
+
+    #[test]
+    fn test_trs_decompose() {
+        let rotation = Rotation3::from_axis_angle(&Vector3::z_axis(), 0.7);
+        let transform = Matrix4::new_translation(&Vector3::new(1.0, 2.0, 3.0))
+            * rotation.to_homogeneous()
+            * Matrix4::new_nonuniform_scaling(&Vector3::new(2.0, 1.0, 1.0));
+        let trs = Trs::decompose(&transform).unwrap();
+        assert_eq!(trs.translation, [1.0, 2.0, 3.0]);
+        assert!((trs.scale[0] - 2.0).abs() < 1e-6);
+        let q = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.7);
+        assert!((trs.rotation[2] - q.k as f32).abs() < 1e-6);
+        assert!((trs.rotation[3] - q.w as f32).abs() < 1e-6);
+
+        let mirrored = Matrix4::new_nonuniform_scaling(&Vector3::new(1.0, -1.0, 1.0));
+        let trs = Trs::decompose(&mirrored).unwrap();
+        assert_eq!(trs.scale[0], -1.0);
+    }
+}
@@ -0,0 +1,275 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Mesh File Export
+//!
+//! Shared input and options for the plain mesh writers (STL, OBJ, PLY) used
+//! by fabrication and analysis tools. Callers pass named `MeshPart`s - one
+//! per element, per storey or per selection - in IFC coordinates (Z-up,
+//! meters) and convert them to the output unit. Georeferenced models should
+//! be meshed relative to an `RtcOffset` so positions keep their precision in
+//! f32; the offset is then recorded in the OBJ and PLY headers.
+
+use super::obj::write_obj;
+use super::ply::{write_ply, write_ply_ascii};
+use super::stl::{write_stl, write_stl_ascii};
+use crate::mesh::{Mesh, SubMeshCollection};
+use ifc_lite_core::RtcOffset;
+use nalgebra::Point3;
+
+/// Length unit of an exported mesh file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportUnit {
+    #[default]
+    Meters,
+    Centimeters,
+    Millimeters,
+    Feet,
+    Inches,
+}
+
+impl ExportUnit {
+    /// Parse a unit name or symbol (`m`, `cm`, `mm`, `ft`, `in`), case-insensitive
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "m" | "meter" | "meters" | "metre" | "metres" => Some(Self::Meters),
+            "cm" | "centimeter" | "centimeters" | "centimetre" | "centimetres" => {
+                Some(Self::Centimeters)
+            }
+            "mm" | "millimeter" | "millimeters" | "millimetre" | "millimetres" => {
+                Some(Self::Millimeters)
+            }
+            "ft" | "foot" | "feet" => Some(Self::Feet),
+            "in" | "inch" | "inches" => Some(Self::Inches),
+            _ => None,
+        }
+    }
+
+    /// Number of units in one meter
+    pub fn per_meter(&self) -> f64 {
+        match self {
+            Self::Meters => 1.0,
+            Self::Centimeters => 100.0,
+            Self::Millimeters => 1000.0,
+            Self::Feet => 1.0 / 0.3048,
+            Self::Inches => 1.0 / 0.0254,
+        }
+    }
+}
+
+/// Coordinate handling shared by the mesh writers
+#[derive(Debug, Clone, Default)]
+pub struct MeshExportOptions {
+    /// Output length unit
+    pub unit: ExportUnit,
+    /// Offset (meters) already subtracted from the part positions
+    pub rtc: Option<RtcOffset>,
+}
+
+impl MeshExportOptions {
+    /// Options in the given unit, for parts in IFC coordinates
+    pub fn new(unit: ExportUnit) -> Self {
+        Self { unit, rtc: None }
+    }
+
+    /// Parts are relative to `offset`, e.g. from `detect_rtc_offset`
+    ///
+    /// Offsets close to the origin are ignored.
+    pub fn with_rtc(mut self, offset: RtcOffset) -> Self {
+        self.rtc = offset.is_significant().then_some(offset);
+        self
+    }
+
+    /// Output position of a vertex
+    pub(crate) fn position(&self, p: &[f32]) -> Point3<f64> {
+        Point3::new(p[0] as f64, p[1] as f64, p[2] as f64) * self.unit.per_meter()
+    }
+
+    /// Header line recording the RTC offset, if any
+    pub(crate) fn rtc_comment(&self) -> Option<String> {
+        self.rtc
+            .as_ref()
+            .map(|rtc| format!("RTC offset (m): {} {} {}", rtc.x, rtc.y, rtc.z))
+    }
+}
+
+/// Named mesh with a colour; one solid, object or group in the output
+#[derive(Debug, Clone)]
+pub struct MeshPart<'a> {
+    /// Part name, e.g. element name or storey
+    pub name: String,
+    /// Triangles in IFC coordinates (Z-up, meters)
+    pub mesh: &'a Mesh,
+    /// RGBA colour, used for OBJ materials and PLY vertex colours
+    pub color: [f32; 4],
+}
+
+impl<'a> MeshPart<'a> {
+    /// Create a part
+    pub fn new(name: impl Into<String>, mesh: &'a Mesh, color: [f32; 4]) -> Self {
+        Self {
+            name: name.into(),
+            mesh,
+            color,
+        }
+    }
+
+    /// One part per sub-mesh, named `<name> #<geometry id>`
+    pub fn from_sub_meshes<F>(name: &str, collection: &'a SubMeshCollection, color: F) -> Vec<Self>
+    where
+        F: Fn(u32) -> [f32; 4],
+    {
+        collection
+            .iter()
+            .map(|sub| {
+                Self::new(
+                    format!("{} #{}", name, sub.geometry_id),
+                    &sub.mesh,
+                    color(sub.geometry_id),
+                )
+            })
+            .collect()
+    }
+
+    /// Triangles as index triples
+    pub(crate) fn triangles(&self) -> impl Iterator<Item = [usize; 3]> + '_ {
+        self.mesh
+            .indices
+            .chunks_exact(3)
+            .map(|t| [t[0] as usize, t[1] as usize, t[2] as usize])
+    }
+}
+
+/// Mesh file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MeshFormat {
+    /// Binary STL
+    #[default]
+    Stl,
+    /// ASCII STL, one solid per part
+    StlAscii,
+    /// Wavefront OBJ with an MTL material library
+    Obj,
+    /// Binary little-endian PLY with vertex colours
+    Ply,
+    /// ASCII PLY with vertex colours
+    PlyAscii,
+}
+
+impl MeshFormat {
+    /// Parse `stl`, `stl-ascii`, `obj`, `ply` or `ply-ascii`, case-insensitive
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "stl" => Some(Self::Stl),
+            "stl-ascii" => Some(Self::StlAscii),
+            "obj" => Some(Self::Obj),
+            "ply" => Some(Self::Ply),
+            "ply-ascii" => Some(Self::PlyAscii),
+            _ => None,
+        }
+    }
+
+    /// File extension of the main output file
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Stl | Self::StlAscii => "stl",
+            Self::Obj => "obj",
+            Self::Ply | Self::PlyAscii => "ply",
+        }
+    }
+
+    /// MIME type of the main output file
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Stl | Self::StlAscii => "model/stl",
+            Self::Obj => "model/obj",
+            Self::Ply | Self::PlyAscii => "application/ply",
+        }
+    }
+}
+
+/// Write parts in the given format
+///
+/// Returns `(file name, data)` pairs named after `stem`: one file, or the
+/// OBJ file followed by its MTL library.
+pub fn write_mesh_files(
+    parts: &[MeshPart],
+    format: MeshFormat,
+    options: &MeshExportOptions,
+    stem: &str,
+) -> Vec<(String, Vec<u8>)> {
+    let file_name = format!("{}.{}", stem, format.extension());
+    match format {
+        MeshFormat::Stl => vec![(file_name, write_stl(parts, options))],
+        MeshFormat::StlAscii => vec![(file_name, write_stl_ascii(parts, options).into_bytes())],
+        MeshFormat::Obj => {
+            let mtl_name = format!("{}.mtl", stem);
+            let obj = write_obj(parts, options, &mtl_name);
+            vec![
+                (file_name, obj.obj.into_bytes()),
+                (mtl_name, obj.mtl.into_bytes()),
+            ]
+        }
+        MeshFormat::Ply => vec![(file_name, write_ply(parts, options))],
+        MeshFormat::PlyAscii => vec![(file_name, write_ply_ascii(parts, options).into_bytes())],
+    }
+}
+
+/// Name usable as an STL solid, OBJ object or material name
+pub(crate) fn sanitize_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_whitespace() || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    if name.is_empty() {
+        "part".to_string()
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_units_and_rtc() {
+        assert_eq!(ExportUnit::parse("MM"), Some(ExportUnit::Millimeters));
+        assert_eq!(ExportUnit::parse("yd"), None);
+        assert!((ExportUnit::Inches.per_meter() - 39.370_078_740_157_48).abs() < 1e-9);
+
+        let offset = RtcOffset {
+            x: 500_000.0,
+            y: 5_000_000.0,
+            z: 0.0,
+        };
+        let options = MeshExportOptions::new(ExportUnit::Millimeters).with_rtc(offset);
+        assert_eq!(
+            options.rtc_comment().as_deref(),
+            Some("RTC offset (m): 500000 5000000 0")
+        );
+        assert_eq!(
+            options.position(&[1.0, 0.0, 0.5]),
+            Point3::new(1000.0, 0.0, 500.0)
+        );
+
+        // Offsets near the origin are not recorded
+        let offset = RtcOffset {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+        assert!(MeshExportOptions::default().with_rtc(offset).rtc.is_none());
+
+        assert_eq!(MeshFormat::parse("PLY_ASCII"), Some(MeshFormat::PlyAscii));
+        assert_eq!(sanitize_name(" Basic Wall:200mm "), "Basic_Wall:200mm");
+    }
+}
@@ -0,0 +1,257 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Export
+//!
+//! Writers for standard file formats other tools can open, fed by
+//! `elements`, which meshes a model's elements for them. `gltf` writes
+//! element meshes as binary glTF (GLB) with instancing and per-element
+//! metadata; `tiles` partitions them into an OGC 3D Tiles tileset for
+//! streaming. `stl`, `obj` and `ply` write plain meshes for fabrication and
+//! analysis tools (see `mesh_file`). The 2D writers turn a `PlanCut` into drawing files:
+//! - **SVG**: one `<path>` per element with per-IFC-type CSS classes and
+//!   `data-express-id` attributes for linking back to the model
+//! - **DXF**: one layer per IFC class, closed polylines and solid hatches for
+//!   cut fills, lines for projected edges
+//!
+//! Both writers take coordinates in meters as produced by `PlanCutter`.
+//! `draw` runs the whole pipeline on element meshes for callers that just
+//! want a file:
+//!
+//! ```rust,ignore
+//! let level = StoreyLevel::find(&levels, "Level 1").unwrap();
+//! let request = DrawingRequest::new(DrawingView::plan(level, DEFAULT_CUT_HEIGHT));
+//! let svg = draw(elements.iter().map(|e| (e.id, e.ifc_type, &e.mesh)), &request)?;
+//! ```
+
+pub mod dxf;
+pub mod elements;
+pub mod gltf;
+pub mod mesh_file;
+pub mod obj;
+pub mod ply;
+pub mod stl;
+pub mod svg;
+pub mod tiles;
+
+pub use dxf::{write_dxf, DxfOptions};
+pub use elements::{
+    detect_rtc_offset, mesh_elements_local, mesh_elements_local_with_rtc, ElementMesh,
+};
+pub use gltf::{ElementInfo, GlbBuilder, GlbOptions};
+pub use mesh_file::{write_mesh_files, ExportUnit, MeshExportOptions, MeshFormat, MeshPart};
+pub use obj::{write_obj, ObjFile};
+pub use ply::{write_ply, write_ply_ascii};
+pub use stl::{write_stl, write_stl_ascii};
+pub use svg::{write_svg, SvgOptions};
+pub use tiles::{georeferenced_transform, TileContent, Tileset, TilesetBuilder, TilesetOptions};
+
+use crate::error::Result;
+use crate::mesh::Mesh;
+use crate::plan_cut::{is_drawn, ElevationDirection, PlanCutter, StoreyLevel, DEFAULT_VIEW_DEPTH};
+use ifc_lite_core::IfcType;
+use nalgebra::Point3;
+
+/// What a drawing shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DrawingView {
+    /// Floor plan cut at an absolute elevation (meters)
+    Plan { cut_elevation: f64 },
+    /// Facade elevation of the whole model
+    Elevation(ElevationDirection),
+}
+
+impl DrawingView {
+    /// Floor plan of a storey, cut at a height above its elevation
+    pub fn plan(level: &StoreyLevel, cut_height: f64) -> Self {
+        Self::Plan {
+            cut_elevation: level.elevation + cut_height,
+        }
+    }
+}
+
+/// Everything needed to draw element meshes
+#[derive(Debug, Clone)]
+pub struct DrawingRequest {
+    /// Plan or elevation
+    pub view: DrawingView,
+    /// Output format
+    pub format: DrawingFormat,
+    /// Projection depth for plans (meters, default `DEFAULT_VIEW_DEPTH`)
+    pub view_depth: Option<f64>,
+    /// SVG scale denominator
+    pub scale: f64,
+    /// Hatch DXF cut polygons
+    pub hatch: bool,
+    /// SVG title
+    pub title: Option<String>,
+}
+
+impl DrawingRequest {
+    /// SVG drawing at 1:100 with the given view
+    pub fn new(view: DrawingView) -> Self {
+        Self {
+            view,
+            format: DrawingFormat::Svg,
+            view_depth: None,
+            scale: SvgOptions::default().scale,
+            hatch: true,
+            title: None,
+        }
+    }
+}
+
+/// Cut or project element meshes and write the drawing file
+///
+/// Meshes must be Z-up in meters; openings, spaces and virtual elements are
+/// skipped.
+pub fn draw<'a, I>(elements: I, request: &DrawingRequest) -> Result<String>
+where
+    I: IntoIterator<Item = (u32, IfcType, &'a Mesh)>,
+{
+    let elements: Vec<(u32, IfcType, &Mesh)> = elements
+        .into_iter()
+        .filter(|(_, ifc_type, mesh)| is_drawn(ifc_type) && !mesh.is_empty())
+        .collect();
+
+    let cutter = match request.view {
+        DrawingView::Plan { cut_elevation } => PlanCutter::at_elevation(cut_elevation)
+            .with_view_depth(request.view_depth.unwrap_or(DEFAULT_VIEW_DEPTH)),
+        DrawingView::Elevation(direction) => {
+            let (min, max) = elements
+                .iter()
+                .map(|(_, _, mesh)| mesh.bounds())
+                .reduce(|(a_min, a_max), (b_min, b_max)| (a_min.inf(&b_min), a_max.sup(&b_max)))
+                .unwrap_or((Point3::origin(), Point3::origin()));
+            PlanCutter::elevation(direction, min.cast(), max.cast())
+        }
+    };
+    let plan = cutter.cut(elements)?;
+
+    Ok(match request.format {
+        DrawingFormat::Svg => write_svg(
+            &plan,
+            &SvgOptions {
+                scale: request.scale,
+                title: request.title.clone(),
+                ..Default::default()
+            },
+        ),
+        DrawingFormat::Dxf => write_dxf(
+            &plan,
+            &DxfOptions {
+                hatch: request.hatch,
+            },
+        ),
+    })
+}
+
+/// Output format of a 2D drawing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DrawingFormat {
+    /// Scalable Vector Graphics
+    #[default]
+    Svg,
+    /// AutoCAD Drawing Exchange Format (ASCII)
+    Dxf,
+}
+
+impl DrawingFormat {
+    /// Parse `svg` or `dxf`, case-insensitive
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "svg" => Some(Self::Svg),
+            "dxf" => Some(Self::Dxf),
+            _ => None,
+        }
+    }
+
+    /// MIME type of the output
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Svg => "image/svg+xml",
+            Self::Dxf => "application/dxf",
+        }
+    }
+
+    /// File extension of the output
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Dxf => "dxf",
+        }
+    }
+}
+
+/// Format a coordinate with up to 4 decimals (0.1 mm) and no trailing zeros
+pub(crate) fn format_number(value: f64) -> String {
+    let rounded = (value * 10_000.0).round() / 10_000.0;
+    // Avoid "-0"
+    let rounded = if rounded == 0.0 { 0.0 } else { rounded };
+    let mut s = format!("{:.4}", rounded);
+    if s.contains('.') {
+        while s.ends_with('0') {
+            s.pop();
+        }
+        if s.ends_with('.') {
+            s.pop();
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(1.0), "1");
+        assert_eq!(format_number(-0.00001), "0");
+        assert_eq!(format_number(2.50), "2.5");
+        assert_eq!(format_number(3.123456), "3.1235");
+        assert_eq!(DrawingFormat::parse("DXF"), Some(DrawingFormat::Dxf));
+        assert_eq!(DrawingFormat::parse("pdf"), None);
+    }
+
+    #[test]
+    fn test_draw_plan_and_elevation() {
+        use crate::extrusion::extrude_profile;
+        use crate::profile::Profile2D;
+        use nalgebra::Point2;
+
+        let profile = Profile2D::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(5.0, 0.0),
+            Point2::new(5.0, 0.2),
+            Point2::new(0.0, 0.2),
+        ]);
+        let wall = extrude_profile(&profile, 3.0, None).unwrap();
+        let opening = extrude_profile(&profile, 3.0, None).unwrap();
+        let elements = [
+            (1, IfcType::IfcWall, &wall),
+            (2, IfcType::IfcOpeningElement, &opening),
+        ];
+
+        let level = StoreyLevel {
+            express_id: 10,
+            global_id: String::new(),
+            name: "Ground".to_string(),
+            elevation: 0.0,
+        };
+        let plan = draw(
+            elements,
+            &DrawingRequest::new(DrawingView::plan(&level, 1.2)),
+        )
+        .unwrap();
+        assert!(plan.contains("class=\"cut ifcwall\" data-express-id=\"1\""));
+        assert!(!plan.contains("data-express-id=\"2\""));
+
+        let mut request = DrawingRequest::new(DrawingView::Elevation(ElevationDirection::South));
+        request.format = DrawingFormat::Dxf;
+        let elevation = draw(elements, &request).unwrap();
+        assert!(elevation.contains("IfcWall_Projection"));
+        assert!(!elevation.contains("HATCH"));
+    }
+}
@@ -0,0 +1,133 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! OBJ Writer
+//!
+//! Wavefront OBJ with an MTL material library. Each part becomes an `o`
+//! object; parts with the same colour share a material whose diffuse colour
+//! and dissolve come from the surface style. Normals are written when the
+//! mesh has them.
+
+use super::mesh_file::{sanitize_name, MeshExportOptions, MeshPart};
+use rustc_hash::FxHashMap;
+use std::fmt::Write;
+
+/// OBJ geometry and its material library
+#[derive(Debug, Clone)]
+pub struct ObjFile {
+    /// `.obj` content
+    pub obj: String,
+    /// `.mtl` content
+    pub mtl: String,
+}
+
+/// Write parts as OBJ, referencing the material library `mtl_name`
+pub fn write_obj(parts: &[MeshPart], options: &MeshExportOptions, mtl_name: &str) -> ObjFile {
+    let mut obj = String::from("# IFC-Lite OBJ export\n");
+    let mut mtl = String::from("# IFC-Lite MTL export\n");
+    if let Some(comment) = options.rtc_comment() {
+        let _ = writeln!(obj, "# {}", comment);
+    }
+    let _ = writeln!(obj, "mtllib {}", mtl_name);
+
+    let mut materials: FxHashMap<[u32; 4], String> = FxHashMap::default();
+    let mut vertex_base = 1usize;
+    let mut normal_base = 1usize;
+
+    for part in parts {
+        let mesh = part.mesh;
+        if mesh.is_empty() {
+            continue;
+        }
+
+        let next = materials.len();
+        let material = materials
+            .entry(part.color.map(f32::to_bits))
+            .or_insert_with(|| {
+                let name = format!("material_{}", next);
+                let [r, g, b, a] = part.color;
+                let _ = writeln!(mtl, "\nnewmtl {}", name);
+                let _ = writeln!(mtl, "Ka 0 0 0");
+                let _ = writeln!(mtl, "Kd {} {} {}", r, g, b);
+                let _ = writeln!(mtl, "Ks 0 0 0");
+                let _ = writeln!(mtl, "d {}", a);
+                let _ = writeln!(mtl, "illum 1");
+                name
+            });
+
+        let _ = writeln!(obj, "o {}", sanitize_name(&part.name));
+        let _ = writeln!(obj, "usemtl {}", material);
+        let vertex_count = mesh.positions.len() / 3;
+        for p in mesh.positions.chunks_exact(3) {
+            let p = options.position(p);
+            let _ = writeln!(obj, "v {} {} {}", p.x as f32, p.y as f32, p.z as f32);
+        }
+        let has_normals = mesh.normals.len() == mesh.positions.len();
+        if has_normals {
+            for n in mesh.normals.chunks_exact(3) {
+                let _ = writeln!(obj, "vn {} {} {}", n[0], n[1], n[2]);
+            }
+        }
+
+        for t in part.triangles() {
+            if t.iter().any(|&i| i >= vertex_count) {
+                continue;
+            }
+            obj.push('f');
+            for i in t {
+                if has_normals {
+                    let _ = write!(obj, " {}//{}", vertex_base + i, normal_base + i);
+                } else {
+                    let _ = write!(obj, " {}", vertex_base + i);
+                }
+            }
+            obj.push('\n');
+        }
+
+        vertex_base += vertex_count;
+        if has_normals {
+            normal_base += vertex_count;
+        }
+    }
+
+    ObjFile { obj, mtl }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::Mesh;
+
+    #[test]
+    fn test_obj_with_materials() {
+        let mut mesh = Mesh::new();
+        mesh.positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        mesh.normals = vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+        mesh.indices = vec![0, 1, 2];
+        let mut bare = mesh.clone();
+        bare.normals.clear();
+
+        let glass = [0.6, 0.8, 0.9, 0.3];
+        let parts = [
+            MeshPart::new("Window 1", &mesh, glass),
+            MeshPart::new("Window 2", &bare, glass),
+            MeshPart::new("Wall", &mesh, [0.85, 0.85, 0.85, 1.0]),
+        ];
+        let file = write_obj(&parts, &MeshExportOptions::default(), "model.mtl");
+
+        assert!(file.obj.contains("mtllib model.mtl\n"));
+        assert!(file.obj.contains("o Window_1\nusemtl material_0\n"));
+        assert!(file.obj.contains("f 1//1 2//2 3//3\n"));
+        // Second part continues vertex numbering, has no normals
+        assert!(file.obj.contains("o Window_2\nusemtl material_0\n"));
+        assert!(file.obj.contains("f 4 5 6\n"));
+        assert!(file.obj.contains("f 7//4 8//5 9//6\n"));
+
+        assert_eq!(file.mtl.matches("newmtl").count(), 2);
+        assert!(file
+            .mtl
+            .contains("newmtl material_0\nKa 0 0 0\nKd 0.6 0.8 0.9\n"));
+        assert!(file.mtl.contains("d 0.3\n"));
+    }
+}
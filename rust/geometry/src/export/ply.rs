@@ -0,0 +1,176 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! PLY Writer
+//!
+//! Stanford polygon files for point-cloud and analysis tools, in binary
+//! little-endian or ASCII encoding. Parts are merged into one vertex list;
+//! each vertex carries its part's RGBA colour, and normals are included when
+//! every part has them.
+
+use super::mesh_file::{MeshExportOptions, MeshPart};
+use std::fmt::Write;
+
+/// Header shared by both encodings
+fn header(
+    parts: &[MeshPart],
+    options: &MeshExportOptions,
+    encoding: &str,
+    normals: bool,
+) -> String {
+    let vertices: usize = parts.iter().map(|p| p.mesh.positions.len() / 3).sum();
+    let faces: usize = parts.iter().map(|p| valid_triangles(p).count()).sum();
+
+    let mut header = format!(
+        "ply\nformat {} 1.0\ncomment IFC-Lite PLY export\n",
+        encoding
+    );
+    if let Some(comment) = options.rtc_comment() {
+        let _ = writeln!(header, "comment {}", comment);
+    }
+    let _ = writeln!(header, "element vertex {}", vertices);
+    header.push_str("property float x\nproperty float y\nproperty float z\n");
+    if normals {
+        header.push_str("property float nx\nproperty float ny\nproperty float nz\n");
+    }
+    header.push_str(
+        "property uchar red\nproperty uchar green\nproperty uchar blue\nproperty uchar alpha\n",
+    );
+    let _ = writeln!(header, "element face {}", faces);
+    header.push_str("property list uchar int vertex_indices\nend_header\n");
+    header
+}
+
+fn has_normals(parts: &[MeshPart]) -> bool {
+    parts
+        .iter()
+        .all(|p| p.mesh.normals.len() == p.mesh.positions.len())
+}
+
+fn valid_triangles<'a>(part: &'a MeshPart) -> impl Iterator<Item = [usize; 3]> + 'a {
+    let count = part.mesh.positions.len() / 3;
+    part.triangles()
+        .filter(move |t| t.iter().all(|&i| i < count))
+}
+
+fn color_bytes(color: [f32; 4]) -> [u8; 4] {
+    color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+/// Write parts as binary little-endian PLY
+pub fn write_ply(parts: &[MeshPart], options: &MeshExportOptions) -> Vec<u8> {
+    let normals = has_normals(parts);
+    let mut ply = header(parts, options, "binary_little_endian", normals).into_bytes();
+
+    for part in parts {
+        let color = color_bytes(part.color);
+        let mesh = part.mesh;
+        for (i, p) in mesh.positions.chunks_exact(3).enumerate() {
+            let p = options.position(p);
+            for c in [p.x, p.y, p.z] {
+                ply.extend_from_slice(&(c as f32).to_le_bytes());
+            }
+            if normals {
+                for c in &mesh.normals[i * 3..i * 3 + 3] {
+                    ply.extend_from_slice(&c.to_le_bytes());
+                }
+            }
+            ply.extend_from_slice(&color);
+        }
+    }
+
+    let mut base = 0usize;
+    for part in parts {
+        for t in valid_triangles(part) {
+            ply.push(3);
+            for i in t {
+                ply.extend_from_slice(&((base + i) as i32).to_le_bytes());
+            }
+        }
+        base += part.mesh.positions.len() / 3;
+    }
+    ply
+}
+
+/// Write parts as ASCII PLY
+pub fn write_ply_ascii(parts: &[MeshPart], options: &MeshExportOptions) -> String {
+    let normals = has_normals(parts);
+    let mut ply = header(parts, options, "ascii", normals);
+
+    for part in parts {
+        let [r, g, b, a] = color_bytes(part.color);
+        let mesh = part.mesh;
+        for (i, p) in mesh.positions.chunks_exact(3).enumerate() {
+            let p = options.position(p);
+            let _ = write!(ply, "{} {} {}", p.x as f32, p.y as f32, p.z as f32);
+            if normals {
+                let n = &mesh.normals[i * 3..i * 3 + 3];
+                let _ = write!(ply, " {} {} {}", n[0], n[1], n[2]);
+            }
+            let _ = writeln!(ply, " {} {} {} {}", r, g, b, a);
+        }
+    }
+
+    let mut base = 0usize;
+    for part in parts {
+        for [i0, i1, i2] in valid_triangles(part) {
+            let _ = writeln!(ply, "3 {} {} {}", base + i0, base + i1, base + i2);
+        }
+        base += part.mesh.positions.len() / 3;
+    }
+    ply
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::Mesh;
+    use ifc_lite_core::RtcOffset;
+
+    fn triangle() -> Mesh {
+        let mut mesh = Mesh::new();
+        mesh.positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        mesh.indices = vec![0, 1, 2];
+        mesh
+    }
+
+    #[test]
+    fn test_ply_ascii_and_binary() {
+        let mesh = triangle();
+        let parts = [
+            MeshPart::new("a", &mesh, [1.0, 0.0, 0.0, 1.0]),
+            MeshPart::new("b", &mesh, [0.0, 0.0, 1.0, 0.5]),
+        ];
+        let options = MeshExportOptions::default();
+
+        let ascii = write_ply_ascii(&parts, &options);
+        assert!(ascii.starts_with("ply\nformat ascii 1.0\n"));
+        assert!(ascii.contains("element vertex 6\n"));
+        assert!(ascii.contains("element face 2\n"));
+        assert!(!ascii.contains("property float nx"));
+        assert!(ascii.contains("\n1 0 0 255 0 0 255\n"));
+        assert!(ascii.contains("\n0 1 0 0 0 255 128\n"));
+        assert!(ascii.trim_end().ends_with("3 3 4 5"));
+
+        let binary = write_ply(&parts, &options);
+        let header_end = b"end_header\n";
+        let start = binary
+            .windows(header_end.len())
+            .position(|w| w == header_end)
+            .unwrap()
+            + header_end.len();
+        // 6 vertices of 3 floats and 4 colour bytes, 2 faces of 1 + 3 * 4 bytes
+        assert_eq!(binary.len() - start, 6 * 16 + 2 * 13);
+        let last = &binary[binary.len() - 4..];
+        assert_eq!(i32::from_le_bytes(last.try_into().unwrap()), 5);
+
+        let options = MeshExportOptions::default().with_rtc(RtcOffset {
+            x: 2_600_000.0,
+            y: 1_200_000.0,
+            z: 400.0,
+        });
+        let ascii = write_ply_ascii(&parts, &options);
+        assert!(ascii.contains("comment RTC offset (m): 2600000 1200000 400\n"));
+    }
+}
@@ -0,0 +1,128 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! STL Writer
+//!
+//! Binary and ASCII stereolithography files for 3D printing and CNC
+//! workflows. Binary STL has no notion of parts, so all parts end up in one
+//! solid; ASCII STL writes one `solid` block per part. Facet normals are
+//! recomputed from the triangle winding.
+
+use super::mesh_file::{sanitize_name, MeshExportOptions, MeshPart};
+use nalgebra::{Point3, Vector3};
+use std::fmt::Write;
+
+/// Triangles of a part in output coordinates, with facet normals
+fn facets<'a>(
+    part: &'a MeshPart,
+    options: &'a MeshExportOptions,
+) -> impl Iterator<Item = (Vector3<f64>, [Point3<f64>; 3])> + 'a {
+    let positions = &part.mesh.positions;
+    part.triangles()
+        .filter(move |t| t.iter().all(|&i| i * 3 + 2 < positions.len()))
+        .map(move |t| {
+            let v = t.map(|i| options.position(&positions[i * 3..i * 3 + 3]));
+            let normal = (v[1] - v[0])
+                .cross(&(v[2] - v[0]))
+                .try_normalize(1e-20)
+                .unwrap_or_else(Vector3::zeros);
+            (normal, v)
+        })
+}
+
+/// Write parts as binary STL
+pub fn write_stl(parts: &[MeshPart], options: &MeshExportOptions) -> Vec<u8> {
+    let mut header = [0u8; 80];
+    let title = b"IFC-Lite binary STL";
+    header[..title.len()].copy_from_slice(title);
+
+    let mut body = Vec::new();
+    let mut count: u32 = 0;
+    for part in parts {
+        for (normal, vertices) in facets(part, options) {
+            for v in std::iter::once(normal).chain(vertices.iter().map(|p| p.coords)) {
+                for c in v.iter() {
+                    body.extend_from_slice(&(*c as f32).to_le_bytes());
+                }
+            }
+            // Attribute byte count
+            body.extend_from_slice(&0u16.to_le_bytes());
+            count += 1;
+        }
+    }
+
+    let mut stl = Vec::with_capacity(84 + body.len());
+    stl.extend_from_slice(&header);
+    stl.extend_from_slice(&count.to_le_bytes());
+    stl.extend_from_slice(&body);
+    stl
+}
+
+/// Write parts as ASCII STL, one solid per part
+pub fn write_stl_ascii(parts: &[MeshPart], options: &MeshExportOptions) -> String {
+    let mut out = String::new();
+    for part in parts {
+        let name = sanitize_name(&part.name);
+        let _ = writeln!(out, "solid {}", name);
+        for (n, vertices) in facets(part, options) {
+            let _ = writeln!(out, "  facet normal {:e} {:e} {:e}", n.x, n.y, n.z);
+            out.push_str("    outer loop\n");
+            for v in &vertices {
+                let _ = writeln!(
+                    out,
+                    "      vertex {:e} {:e} {:e}",
+                    v.x as f32, v.y as f32, v.z as f32
+                );
+            }
+            out.push_str("    endloop\n  endfacet\n");
+        }
+        let _ = writeln!(out, "endsolid {}", name);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::mesh_file::ExportUnit;
+    use crate::mesh::Mesh;
+
+    fn triangle() -> Mesh {
+        let mut mesh = Mesh::new();
+        mesh.positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        mesh.indices = vec![0, 1, 2];
+        mesh
+    }
+
+    #[test]
+    fn test_binary_stl() {
+        let mesh = triangle();
+        let parts = [
+            MeshPart::new("a", &mesh, [1.0; 4]),
+            MeshPart::new("b", &mesh, [1.0; 4]),
+        ];
+        let stl = write_stl(&parts, &MeshExportOptions::new(ExportUnit::Millimeters));
+        assert_eq!(stl.len(), 84 + 2 * 50);
+        assert_eq!(u32::from_le_bytes(stl[80..84].try_into().unwrap()), 2);
+
+        let floats: Vec<f32> = stl[84..84 + 48]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        // Normal, then three vertices in millimeters
+        assert_eq!(&floats[..3], &[0.0, 0.0, 1.0]);
+        assert_eq!(&floats[6..9], &[1000.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_ascii_stl() {
+        let mesh = triangle();
+        let parts = [MeshPart::new("Basic Wall", &mesh, [1.0; 4])];
+        let stl = write_stl_ascii(&parts, &MeshExportOptions::default());
+        assert!(stl.starts_with("solid Basic_Wall\n"));
+        assert!(stl.contains("facet normal 0e0 0e0 1e0"));
+        assert!(stl.contains("vertex 1e0 0e0 0e0"));
+        assert!(stl.trim_end().ends_with("endsolid Basic_Wall"));
+    }
+}
@@ -0,0 +1,269 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! SVG Writer
+//!
+//! The viewBox is in meters with the Y axis flipped, so plan north points up.
+//! The physical size follows the drawing scale (1:100 by default), letting
+//! the file print at scale. Each element becomes one `<path>`:
+//!
+//! ```xml
+//! <path class="cut ifcwall" data-express-id="42" data-ifc-type="IfcWall" d="M0 0L5 0..."/>
+//! ```
+//!
+//! Cut fills use the default type colour; projected edges are thin lines.
+
+use super::format_number;
+use crate::plan_cut::PlanCut;
+use crate::style::default_color;
+use ifc_lite_core::IfcType;
+use nalgebra::Point2;
+use rustc_hash::FxHashSet;
+use std::fmt::Write;
+
+/// SVG output options
+#[derive(Debug, Clone)]
+pub struct SvgOptions {
+    /// Drawing scale denominator (100 = 1:100)
+    pub scale: f64,
+    /// Margin around the drawing (meters)
+    pub margin: f64,
+    /// Optional `<title>`
+    pub title: Option<String>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            scale: 100.0,
+            margin: 0.5,
+            title: None,
+        }
+    }
+}
+
+/// Write a plan cut as an SVG document
+pub fn write_svg(plan: &PlanCut, options: &SvgOptions) -> String {
+    let (min, max) = plan
+        .bounds()
+        .unwrap_or((Point2::new(0.0, 0.0), Point2::new(1.0, 1.0)));
+    let margin = options.margin.max(0.0);
+    let x = min.x - margin;
+    // Flipped Y: SVG y = -plan y
+    let y = -max.y - margin;
+    let width = (max.x - min.x) + 2.0 * margin;
+    let height = (max.y - min.y) + 2.0 * margin;
+    // Paper size in millimeters
+    let mm_per_meter = 1000.0 / options.scale.max(f64::EPSILON);
+
+    let mut svg = String::new();
+    svg.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}mm\" height=\"{}mm\" viewBox=\"{} {} {} {}\">",
+        format_number(width * mm_per_meter),
+        format_number(height * mm_per_meter),
+        format_number(x),
+        format_number(y),
+        format_number(width),
+        format_number(height),
+    );
+    if let Some(title) = &options.title {
+        let _ = writeln!(svg, "<title>{}</title>", escape_xml(title));
+    }
+
+    write_style(&mut svg, plan, options);
+
+    svg.push_str("<g id=\"projected\">\n");
+    for element in &plan.projected {
+        if element.lines.is_empty() {
+            continue;
+        }
+        let mut d = String::new();
+        for [a, b] in &element.lines {
+            let _ = write!(
+                d,
+                "M{} {}L{} {}",
+                format_number(a.x),
+                format_number(-a.y),
+                format_number(b.x),
+                format_number(-b.y)
+            );
+        }
+        write_path(
+            &mut svg,
+            "projected",
+            element.express_id,
+            &element.ifc_type,
+            &d,
+            false,
+        );
+    }
+    svg.push_str("</g>\n");
+
+    svg.push_str("<g id=\"cut\">\n");
+    for element in &plan.cut {
+        let mut d = String::new();
+        for polygon in &element.polygons {
+            for contour in std::iter::once(&polygon.outer).chain(polygon.holes.iter()) {
+                append_contour(&mut d, contour);
+            }
+        }
+        if !d.is_empty() {
+            write_path(
+                &mut svg,
+                "cut",
+                element.express_id,
+                &element.ifc_type,
+                &d,
+                true,
+            );
+        }
+    }
+    svg.push_str("</g>\n");
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Write the stylesheet with one fill rule per cut IFC type
+fn write_style(svg: &mut String, plan: &PlanCut, options: &SvgOptions) {
+    // Line widths in meters that print as 0.35 mm / 0.18 mm at scale
+    let cut_stroke = 0.35 * options.scale / 1000.0;
+    let projected_stroke = 0.18 * options.scale / 1000.0;
+
+    svg.push_str("<style>\n");
+    let _ = writeln!(
+        svg,
+        ".cut{{stroke:#000;stroke-width:{};stroke-linejoin:round;fill:#ccc}}",
+        format_number(cut_stroke)
+    );
+    let _ = writeln!(
+        svg,
+        ".projected{{stroke:#666;stroke-width:{};fill:none}}",
+        format_number(projected_stroke)
+    );
+
+    let mut seen = FxHashSet::default();
+    for element in &plan.cut {
+        if seen.insert(element.ifc_type) {
+            let _ = writeln!(
+                svg,
+                ".cut.{}{{fill:{}}}",
+                css_class(&element.ifc_type),
+                hex_color(default_color(&element.ifc_type))
+            );
+        }
+    }
+    svg.push_str("</style>\n");
+}
+
+fn write_path(
+    svg: &mut String,
+    kind: &str,
+    express_id: u32,
+    ifc_type: &IfcType,
+    d: &str,
+    fill: bool,
+) {
+    let _ = writeln!(
+        svg,
+        "<path class=\"{} {}\" data-express-id=\"{}\" data-ifc-type=\"{}\"{} d=\"{}\"/>",
+        kind,
+        css_class(ifc_type),
+        express_id,
+        ifc_type.name(),
+        if fill { " fill-rule=\"evenodd\"" } else { "" },
+        d
+    );
+}
+
+fn append_contour(d: &mut String, contour: &[Point2<f64>]) {
+    if contour.len() < 3 {
+        return;
+    }
+    for (i, p) in contour.iter().enumerate() {
+        let _ = write!(
+            d,
+            "{}{} {}",
+            if i == 0 { 'M' } else { 'L' },
+            format_number(p.x),
+            format_number(-p.y)
+        );
+    }
+    d.push('Z');
+}
+
+/// CSS class of an IFC type (`IfcWall` -> `ifcwall`)
+pub fn css_class(ifc_type: &IfcType) -> String {
+    ifc_type.name().to_ascii_lowercase()
+}
+
+fn hex_color(color: [f32; 4]) -> String {
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(color[0]),
+        channel(color[1]),
+        channel(color[2])
+    )
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan_cut::{CutElement, ProjectedElement};
+    use crate::profile::Profile2D;
+
+    #[test]
+    fn test_write_svg() {
+        let plan = PlanCut {
+            cut: vec![CutElement {
+                express_id: 42,
+                ifc_type: IfcType::IfcWall,
+                polygons: vec![Profile2D::new(vec![
+                    Point2::new(0.0, 0.0),
+                    Point2::new(5.0, 0.0),
+                    Point2::new(5.0, 0.2),
+                    Point2::new(0.0, 0.2),
+                ])],
+            }],
+            projected: vec![ProjectedElement {
+                express_id: 7,
+                ifc_type: IfcType::IfcSlab,
+                lines: vec![[Point2::new(0.0, 0.0), Point2::new(0.0, 4.0)]],
+            }],
+        };
+        let options = SvgOptions {
+            title: Some("Level 1 <plan>".to_string()),
+            ..Default::default()
+        };
+        let svg = write_svg(&plan, &options);
+
+        assert!(svg.contains("width=\"60mm\" height=\"50mm\""));
+        assert!(svg.contains("viewBox=\"-0.5 -4.5 6 5\""));
+        assert!(svg.contains("<title>Level 1 &lt;plan&gt;</title>"));
+        assert!(svg.contains(".cut.ifcwall{fill:#d9d9d9}"));
+        assert!(svg.contains(
+            "<path class=\"cut ifcwall\" data-express-id=\"42\" data-ifc-type=\"IfcWall\" fill-rule=\"evenodd\" d=\"M0 0L5 0L5 -0.2L0 -0.2Z\"/>"
+        ));
+        assert!(svg.contains("class=\"projected ifcslab\" data-express-id=\"7\""));
+        assert!(svg.contains("d=\"M0 0L0 -4\""));
+    }
+}
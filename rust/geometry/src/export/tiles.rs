@@ -0,0 +1,713 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! OGC 3D Tiles Writer
+//!
+//! 3D Tiles 1.1 tilesets for streaming large models into CesiumJS and other
+//! tile viewers. Elements are partitioned with a KD-tree over their bounding
+//! boxes, splitting at the median of element centres along the longest axis.
+//! Elements that are large compared to a tile stay in that tile, so coarse
+//! tiles show walls and slabs and finer tiles add the small parts (`ADD`
+//! refinement). A tile's geometric error is the size of the largest element
+//! below it.
+//!
+//! Tile content is glTF (GLB) with one primitive per colour. Every vertex
+//! carries a `_FEATURE_ID_0` attribute (`EXT_mesh_features`) indexing an
+//! `EXT_structural_metadata` property table with the element's express id,
+//! GlobalId, IFC type and name, so viewers can pick and style elements.
+//!
+//! `georeferenced_transform` turns an `IfcMapConversion` into the tileset's
+//! root transform, placing the model on the WGS 84 ellipsoid.
+
+use super::gltf::{ElementInfo, GltfDocument, ARRAY_BUFFER};
+use crate::mesh::Mesh;
+use ifc_lite_core::GeoReference;
+use nalgebra::{Matrix3, Matrix4, Point3, Rotation3, Vector3};
+use rustc_hash::FxHashMap;
+use serde_json::{json, Value};
+
+const MESH_FEATURES: &str = "EXT_mesh_features";
+const STRUCTURAL_METADATA: &str = "EXT_structural_metadata";
+/// WGS 84 semi-major axis
+const WGS84_A: f64 = 6_378_137.0;
+/// WGS 84 first eccentricity squared
+const WGS84_E2: f64 = 6.694_379_990_141_316e-3;
+
+/// Tiling options
+#[derive(Debug, Clone)]
+pub struct TilesetOptions {
+    /// Split tiles holding more elements than this
+    pub max_elements: usize,
+    /// Maximum tree depth
+    pub max_depth: usize,
+    /// Elements at least this fraction of a tile's diagonal stay in that tile
+    pub large_element_ratio: f64,
+}
+
+impl Default for TilesetOptions {
+    fn default() -> Self {
+        Self {
+            max_elements: 250,
+            max_depth: 16,
+            large_element_ratio: 0.25,
+        }
+    }
+}
+
+/// One tile content file
+#[derive(Debug, Clone)]
+pub struct TileContent {
+    /// Path relative to `tileset.json`
+    pub uri: String,
+    /// GLB data
+    pub glb: Vec<u8>,
+}
+
+/// `tileset.json` and the tile contents it references
+#[derive(Debug, Clone)]
+pub struct Tileset {
+    /// `tileset.json` document
+    pub json: String,
+    /// Tile contents
+    pub contents: Vec<TileContent>,
+}
+
+/// Element geometry in model coordinates
+struct TileElement {
+    info: ElementInfo,
+    color: [f32; 4],
+    positions: Vec<Point3<f64>>,
+    normals: Vec<f32>,
+    indices: Vec<u32>,
+    min: Point3<f64>,
+    max: Point3<f64>,
+}
+
+impl TileElement {
+    fn size(&self) -> f64 {
+        (self.max - self.min).norm()
+    }
+
+    fn center(&self) -> Point3<f64> {
+        nalgebra::center(&self.min, &self.max)
+    }
+}
+
+/// KD-tree node
+struct TileNode {
+    min: Point3<f64>,
+    max: Point3<f64>,
+    content: Vec<usize>,
+    children: Vec<TileNode>,
+    /// Size of the largest element in this subtree
+    largest: f64,
+}
+
+impl TileNode {
+    /// Error of showing this tile without its children
+    fn geometric_error(&self) -> f64 {
+        self.children.iter().map(|c| c.largest).fold(0.0, f64::max)
+    }
+}
+
+/// Collects element meshes and writes a 3D Tiles tileset
+#[derive(Default)]
+pub struct TilesetBuilder {
+    options: TilesetOptions,
+    elements: Vec<TileElement>,
+    transform: Option<Matrix4<f64>>,
+}
+
+impl TilesetBuilder {
+    /// Create a builder with default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a builder with the given options
+    pub fn with_options(options: TilesetOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    /// Set the root tile transform (model meters to ECEF)
+    pub fn set_transform(&mut self, transform: Matrix4<f64>) {
+        self.transform = Some(transform);
+    }
+
+    /// Add an element mesh placed by `transform`
+    ///
+    /// Pass the identity for world-space meshes. Normals are recomputed if
+    /// the mesh has none.
+    pub fn add(
+        &mut self,
+        element: ElementInfo,
+        mesh: &Mesh,
+        transform: &Matrix4<f64>,
+        color: [f32; 4],
+    ) {
+        if mesh.is_empty() {
+            return;
+        }
+
+        let positions: Vec<Point3<f64>> = mesh
+            .positions
+            .chunks_exact(3)
+            .map(|p| transform.transform_point(&Point3::new(p[0] as f64, p[1] as f64, p[2] as f64)))
+            .collect();
+
+        let mut source = None;
+        if mesh.normals.len() != mesh.positions.len() {
+            let mut with_normals = mesh.clone();
+            crate::calculate_normals(&mut with_normals);
+            source = Some(with_normals);
+        }
+        let source_normals = source.as_ref().map_or(&mesh.normals, |m| &m.normals);
+
+        let linear: Matrix3<f64> = transform.fixed_view::<3, 3>(0, 0).into();
+        let normal_matrix = linear.try_inverse().map_or(linear, |m| m.transpose());
+        let normals = source_normals
+            .chunks_exact(3)
+            .flat_map(|n| {
+                let n = normal_matrix * Vector3::new(n[0] as f64, n[1] as f64, n[2] as f64);
+                let n = n.try_normalize(1e-12).unwrap_or(n);
+                [n.x as f32, n.y as f32, n.z as f32]
+            })
+            .collect();
+
+        let mut min = Point3::new(f64::MAX, f64::MAX, f64::MAX);
+        let mut max = Point3::new(f64::MIN, f64::MIN, f64::MIN);
+        for p in &positions {
+            min = min.inf(p);
+            max = max.sup(p);
+        }
+
+        self.elements.push(TileElement {
+            info: element,
+            color,
+            positions,
+            normals,
+            indices: mesh.indices.clone(),
+            min,
+            max,
+        });
+    }
+
+    /// Number of elements
+    pub fn element_count(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Check if nothing was added
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Partition the elements and write the tileset
+    pub fn build(&self) -> Tileset {
+        let mut contents = Vec::new();
+        if self.elements.is_empty() {
+            let json = json!({
+                "asset": { "version": "1.1", "generator": "IFC-Lite" },
+                "geometricError": 0.0,
+                "root": {
+                    "boundingVolume": { "box": [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0] },
+                    "geometricError": 0.0,
+                    "refine": "ADD",
+                },
+            });
+            return Tileset {
+                json: json.to_string(),
+                contents,
+            };
+        }
+
+        let root = self.split((0..self.elements.len()).collect(), 0);
+        let mut root_json = self.tile_json(&root, &mut contents);
+        if let Some(transform) = &self.transform {
+            // Column-major, as stored by nalgebra
+            root_json["transform"] = json!(transform.as_slice());
+        }
+
+        let json = json!({
+            "asset": { "version": "1.1", "generator": "IFC-Lite" },
+            "geometricError": (root.max - root.min).norm(),
+            "root": root_json,
+        });
+        Tileset {
+            json: serde_json::to_string_pretty(&json).unwrap_or_default(),
+            contents,
+        }
+    }
+
+    fn bounds(&self, items: &[usize]) -> (Point3<f64>, Point3<f64>) {
+        let mut min = Point3::new(f64::MAX, f64::MAX, f64::MAX);
+        let mut max = Point3::new(f64::MIN, f64::MIN, f64::MIN);
+        for &i in items {
+            min = min.inf(&self.elements[i].min);
+            max = max.sup(&self.elements[i].max);
+        }
+        (min, max)
+    }
+
+    fn split(&self, items: Vec<usize>, depth: usize) -> TileNode {
+        let (min, max) = self.bounds(&items);
+        let largest = items
+            .iter()
+            .map(|&i| self.elements[i].size())
+            .fold(0.0, f64::max);
+
+        if items.len() <= self.options.max_elements.max(1) || depth >= self.options.max_depth {
+            return TileNode {
+                min,
+                max,
+                content: items,
+                children: Vec::new(),
+                largest,
+            };
+        }
+
+        // Large elements are shown at this level, the rest goes further down
+        let threshold = (max - min).norm() * self.options.large_element_ratio;
+        let (content, mut rest): (Vec<usize>, Vec<usize>) = items
+            .into_iter()
+            .partition(|&i| self.elements[i].size() >= threshold);
+
+        let mut children = Vec::new();
+        if rest.len() <= self.options.max_elements.max(1) {
+            if !rest.is_empty() {
+                children.push(self.split(rest, depth + 1));
+            }
+        } else {
+            // Median split along the longest axis of the element centres
+            let mut lo = Point3::new(f64::MAX, f64::MAX, f64::MAX);
+            let mut hi = Point3::new(f64::MIN, f64::MIN, f64::MIN);
+            for &i in &rest {
+                let c = self.elements[i].center();
+                lo = lo.inf(&c);
+                hi = hi.sup(&c);
+            }
+            let axis = (hi - lo).imax();
+            rest.sort_by(|&a, &b| {
+                let ca = self.elements[a].center()[axis];
+                let cb = self.elements[b].center()[axis];
+                ca.total_cmp(&cb)
+            });
+            let upper = rest.split_off(rest.len() / 2);
+            children.push(self.split(rest, depth + 1));
+            children.push(self.split(upper, depth + 1));
+        }
+
+        TileNode {
+            min,
+            max,
+            content,
+            children,
+            largest,
+        }
+    }
+
+    fn tile_json(&self, node: &TileNode, contents: &mut Vec<TileContent>) -> Value {
+        let center = nalgebra::center(&node.min, &node.max);
+        let half = (node.max - node.min) / 2.0;
+        let mut tile = json!({
+            "boundingVolume": {
+                "box": [
+                    center.x, center.y, center.z,
+                    half.x, 0.0, 0.0,
+                    0.0, half.y, 0.0,
+                    0.0, 0.0, half.z,
+                ],
+            },
+            "geometricError": node.geometric_error(),
+            "refine": "ADD",
+        });
+
+        if !node.content.is_empty() {
+            let uri = format!("{}.glb", contents.len());
+            tile["content"] = json!({ "uri": uri });
+            contents.push(TileContent {
+                uri,
+                glb: self.write_content(&node.content, center),
+            });
+        }
+        if !node.children.is_empty() {
+            let children: Vec<Value> = node
+                .children
+                .iter()
+                .map(|child| self.tile_json(child, contents))
+                .collect();
+            tile["children"] = Value::Array(children);
+        }
+        tile
+    }
+
+    /// Write one tile's elements as GLB, relative to the tile centre
+    fn write_content(&self, items: &[usize], center: Point3<f64>) -> Vec<u8> {
+        let mut doc = GltfDocument::default();
+        let feature_count = items.len();
+
+        // One primitive per colour; feature ids index `items`
+        let mut groups: Vec<([f32; 4], Vec<usize>)> = Vec::new();
+        let mut lookup: FxHashMap<[u32; 4], usize> = FxHashMap::default();
+        for (feature, &i) in items.iter().enumerate() {
+            let color = self.elements[i].color;
+            let group = *lookup.entry(color.map(f32::to_bits)).or_insert_with(|| {
+                groups.push((color, Vec::new()));
+                groups.len() - 1
+            });
+            groups[group].1.push(feature);
+        }
+
+        let mut children = Vec::new();
+        for (color, features) in &groups {
+            let mut positions = Vec::new();
+            let mut normals = Vec::new();
+            let mut feature_ids = Vec::new();
+            let mut indices = Vec::new();
+            for &feature in features {
+                let element = &self.elements[items[feature]];
+                let base = (positions.len() / 3) as u32;
+                for p in &element.positions {
+                    let local = p - center;
+                    positions.extend([local.x as f32, local.y as f32, local.z as f32]);
+                }
+                normals.extend_from_slice(&element.normals);
+                feature_ids.extend(std::iter::repeat_n(feature as f32, element.positions.len()));
+                indices.extend(element.indices.iter().map(|i| i + base));
+            }
+
+            let material = doc.add_material(*color);
+            let position = doc.add_floats(&positions, "VEC3", Some(ARRAY_BUFFER), true);
+            let normal = doc.add_floats(&normals, "VEC3", Some(ARRAY_BUFFER), false);
+            let feature_id = doc.add_floats(&feature_ids, "SCALAR", Some(ARRAY_BUFFER), false);
+            let indices = doc.add_indices(&indices);
+            doc.meshes.push(json!({
+                "primitives": [{
+                    "attributes": {
+                        "POSITION": position,
+                        "NORMAL": normal,
+                        "_FEATURE_ID_0": feature_id,
+                    },
+                    "indices": indices,
+                    "material": material,
+                    "mode": 4,
+                    "extensions": {
+                        MESH_FEATURES: {
+                            "featureIds": [{
+                                "featureCount": feature_count,
+                                "attribute": 0,
+                                "propertyTable": 0,
+                            }],
+                        },
+                    },
+                }],
+            }));
+            let mesh = doc.meshes.len() - 1;
+            children.push(doc.add_mesh_node(mesh, &format!("Batch {}", mesh)));
+        }
+
+        let elements: Vec<&ElementInfo> = items.iter().map(|&i| &self.elements[i].info).collect();
+        let express_ids: Vec<u8> = elements
+            .iter()
+            .flat_map(|e| e.express_id.to_le_bytes())
+            .collect();
+        // Property values must start on 8-byte boundaries
+        let express_id = doc.add_aligned_view(&express_ids, None, 8);
+        let string_property = |doc: &mut GltfDocument, value: &dyn Fn(&ElementInfo) -> &str| {
+            let mut values = Vec::new();
+            let mut offsets = vec![0u32];
+            for element in &elements {
+                values.extend_from_slice(value(element).as_bytes());
+                offsets.push(values.len() as u32);
+            }
+            let offsets: Vec<u8> = offsets.iter().flat_map(|o| o.to_le_bytes()).collect();
+            json!({
+                "values": doc.add_aligned_view(&values, None, 8),
+                "stringOffsets": doc.add_aligned_view(&offsets, None, 8),
+                "stringOffsetType": "UINT32",
+            })
+        };
+        let global_id = string_property(&mut doc, &|e| e.global_id.as_deref().unwrap_or(""));
+        let ifc_type = string_property(&mut doc, &|e| e.ifc_type.name());
+        let name = string_property(&mut doc, &|e| e.name.as_deref().unwrap_or(""));
+
+        doc.extensions.insert(
+            STRUCTURAL_METADATA.into(),
+            json!({
+                "schema": {
+                    "id": "ifc",
+                    "classes": {
+                        "element": {
+                            "properties": {
+                                "expressId": { "type": "SCALAR", "componentType": "UINT32" },
+                                "globalId": { "type": "STRING" },
+                                "ifcType": { "type": "STRING" },
+                                "name": { "type": "STRING" },
+                            },
+                        },
+                    },
+                },
+                "propertyTables": [{
+                    "name": "elements",
+                    "class": "element",
+                    "count": feature_count,
+                    "properties": {
+                        "expressId": { "values": express_id },
+                        "globalId": global_id,
+                        "ifcType": ifc_type,
+                        "name": name,
+                    },
+                }],
+            }),
+        );
+        doc.extensions_used = vec![MESH_FEATURES, STRUCTURAL_METADATA];
+        // The root node turns Z-up into glTF's Y-up, so the centre is given in Y-up too
+        doc.root_translation = Some([center.x, center.z, -center.y]);
+        doc.finish(children)
+    }
+}
+
+/// Root transform placing model coordinates (meters) on the WGS 84 ellipsoid
+///
+/// Applies `GeoReference::to_matrix` to reach map coordinates, then sets the
+/// map conversion origin on the ellipsoid in an east-north-up frame rotated by
+/// the grid convergence. The origin is computed for WGS 84 UTM CRSs; for
+/// other CRSs pass its latitude and longitude in degrees. Heights are taken
+/// as ellipsoidal. Returns `None` if the origin cannot be located.
+///
+/// `IfcMapConversion.Scale` converts project length units to map units, so it
+/// includes the project unit scale; `unit_scale` (meters per project unit) is
+/// divided out since model coordinates are already in meters.
+pub fn georeferenced_transform(
+    georef: &GeoReference,
+    unit_scale: f64,
+    origin: Option<(f64, f64)>,
+) -> Option<Matrix4<f64>> {
+    let (lat, lon, convergence) = match origin {
+        Some((lat, lon)) => (lat.to_radians(), lon.to_radians(), 0.0),
+        None => georef.map_to_geographic(georef.eastings, georef.northings)?,
+    };
+
+    let mut in_meters = georef.clone();
+    if unit_scale > 0.0 {
+        in_meters.scale /= unit_scale;
+    }
+    let map = Matrix4::from_column_slice(&in_meters.to_matrix());
+    let to_origin =
+        Matrix4::new_translation(&Vector3::new(-georef.eastings, -georef.northings, 0.0));
+    // Grid north is `convergence` clockwise from true north
+    let grid_to_enu = Rotation3::from_axis_angle(&Vector3::z_axis(), -convergence).to_homogeneous();
+
+    Some(enu_to_ecef(lat, lon) * grid_to_enu * to_origin * map)
+}
+
+/// East-north-up frame on the WGS 84 ellipsoid at a latitude and longitude (radians)
+fn enu_to_ecef(lat: f64, lon: f64) -> Matrix4<f64> {
+    let (sin_lat, cos_lat) = lat.sin_cos();
+    let (sin_lon, cos_lon) = lon.sin_cos();
+    let n = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
+
+    #[rustfmt::skip]
+    let frame = Matrix4::new(
+        -sin_lon, -sin_lat * cos_lon, cos_lat * cos_lon, n * cos_lat * cos_lon,
+        cos_lon,  -sin_lat * sin_lon, cos_lat * sin_lon, n * cos_lat * sin_lon,
+        0.0,      cos_lat,            sin_lat,           n * (1.0 - WGS84_E2) * sin_lat,
+        0.0,      0.0,                0.0,               1.0,
+    );
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extrusion::extrude_profile;
+    use crate::profile::Profile2D;
+    use ifc_lite_core::IfcType;
+    use nalgebra::Point2;
+
+    fn cube(size: f64) -> Mesh {
+        let profile = Profile2D::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(size, 0.0),
+            Point2::new(size, size),
+            Point2::new(0.0, size),
+        ]);
+        extrude_profile(&profile, size, None).unwrap()
+    }
+
+    fn glb_json(glb: &[u8]) -> Value {
+        let json_len = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+        serde_json::from_slice(&glb[20..20 + json_len]).unwrap()
+    }
+
+    fn collect_tiles<'a>(tile: &'a Value, out: &mut Vec<&'a Value>) {
+        out.push(tile);
+        if let Some(children) = tile["children"].as_array() {
+            for child in children {
+                collect_tiles(child, out);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tileset_partitioning() {
+        let small = cube(0.5);
+        let slab = cube(40.0);
+        let mut builder = TilesetBuilder::with_options(TilesetOptions {
+            max_elements: 10,
+            ..Default::default()
+        });
+        builder.add(
+            ElementInfo::new(1, IfcType::IfcSlab),
+            &slab,
+            &Matrix4::identity(),
+            [0.7, 0.7, 0.7, 1.0],
+        );
+        for i in 0..100u32 {
+            let offset = Vector3::new((i % 10) as f64 * 4.0, (i / 10) as f64 * 4.0, 0.0);
+            builder.add(
+                ElementInfo::new(100 + i, IfcType::IfcFurnishingElement),
+                &small,
+                &Matrix4::new_translation(&offset),
+                [0.5, 0.3, 0.2, 1.0],
+            );
+        }
+        assert_eq!(builder.element_count(), 101);
+
+        let tileset = builder.build();
+        let json: Value = serde_json::from_str(&tileset.json).unwrap();
+        assert_eq!(json["asset"]["version"], "1.1");
+        assert!(json["root"].get("transform").is_none());
+
+        let mut tiles = Vec::new();
+        collect_tiles(&json["root"], &mut tiles);
+
+        // The slab stays in the root, furniture is split into small leaves
+        let root_glb = &tileset.contents[0];
+        assert_eq!(json["root"]["content"]["uri"], root_glb.uri.as_str());
+        let root_gltf = glb_json(&root_glb.glb);
+        let table = &root_gltf["extensions"][STRUCTURAL_METADATA]["propertyTables"][0];
+        assert_eq!(table["count"], 1);
+        assert!(json["root"]["geometricError"].as_f64().unwrap() > 0.8);
+
+        let mut features = 0;
+        for tile in &tiles {
+            let Some(uri) = tile["content"]["uri"].as_str() else {
+                continue;
+            };
+            let content = tileset.contents.iter().find(|c| c.uri == uri).unwrap();
+            let gltf = glb_json(&content.glb);
+            let count = gltf["extensions"][STRUCTURAL_METADATA]["propertyTables"][0]["count"]
+                .as_u64()
+                .unwrap();
+            assert!(count <= 10);
+            features += count;
+
+            let primitive = &gltf["meshes"][0]["primitives"][0];
+            assert!(primitive["attributes"].get("_FEATURE_ID_0").is_some());
+            assert_eq!(
+                primitive["extensions"][MESH_FEATURES]["featureIds"][0]["featureCount"],
+                count
+            );
+            if tile.get("children").is_none() {
+                assert_eq!(tile["geometricError"], 0.0);
+            }
+        }
+        assert_eq!(features, 101);
+        assert_eq!(
+            tileset.contents.len(),
+            tiles.iter().filter(|t| t.get("content").is_some()).count()
+        );
+    }
+
+    #[test]
+    fn test_tile_content_is_relative_to_center() {
+        let mut builder = TilesetBuilder::new();
+        let mut info = ElementInfo::new(7, IfcType::IfcWall);
+        info.global_id = Some("0abc".to_string());
+        let far = Matrix4::new_translation(&Vector3::new(1000.0, 2000.0, 10.0));
+        builder.add(info, &cube(2.0), &far, [0.8, 0.8, 0.8, 1.0]);
+
+        let tileset = builder.build();
+        let json: Value = serde_json::from_str(&tileset.json).unwrap();
+        let bbox = json["root"]["boundingVolume"]["box"].as_array().unwrap();
+        assert_eq!(bbox[0], 1001.0);
+        assert_eq!(bbox[1], 2001.0);
+        assert_eq!(bbox[2], 11.0);
+
+        let gltf = glb_json(&tileset.contents[0].glb);
+        let root = gltf["nodes"].as_array().unwrap().last().unwrap().clone();
+        assert_eq!(root["translation"], json!([1001.0, 11.0, -2001.0]));
+        let position = &gltf["accessors"][gltf["meshes"][0]["primitives"][0]["attributes"]
+            ["POSITION"]
+            .as_u64()
+            .unwrap() as usize];
+        assert_eq!(position["min"], json!([-1.0, -1.0, -1.0]));
+        assert_eq!(
+            gltf["extensionsUsed"],
+            json!([MESH_FEATURES, STRUCTURAL_METADATA])
+        );
+    }
+
+    #[test]
+    fn test_georeferenced_transform() {
+        let mut georef = GeoReference::new();
+        georef.crs_name = Some("EPSG:32632".to_string());
+        georef.eastings = 691_449.656;
+        georef.northings = 5_334_905.738;
+        georef.orthogonal_height = 520.0;
+
+        let transform = georeferenced_transform(&georef, 1.0, None).unwrap();
+        let origin = transform.transform_point(&Point3::origin());
+        let lon = origin.y.atan2(origin.x).to_degrees();
+        assert!((lon - 11.573497).abs() < 1e-6);
+        // 520 m above the ellipsoid at 48.14 N
+        let frame = enu_to_ecef(48.138576f64.to_radians(), lon.to_radians());
+        let expected = frame.transform_point(&Point3::new(0.0, 0.0, 520.0));
+        assert!((origin - expected).norm() < 0.05);
+
+        // Local +Y is grid north, east of true north east of the central meridian
+        let north = transform.transform_vector(&Vector3::y());
+        let east: Vector3<f64> = frame.fixed_view::<3, 1>(0, 0).into();
+        let up: Vector3<f64> = frame.fixed_view::<3, 1>(0, 2).into();
+        assert!((north.norm() - 1.0).abs() < 1e-9);
+        assert!(north.dot(&up).abs() < 1e-9);
+        assert!(north.dot(&east) > 0.01);
+
+        // Without a UTM CRS an explicit origin is needed
+        georef.crs_name = None;
+        assert!(georeferenced_transform(&georef, 1.0, None).is_none());
+        let transform = georeferenced_transform(&georef, 1.0, Some((0.0, 0.0))).unwrap();
+        let origin = transform.transform_point(&Point3::origin());
+        assert!((origin.x - (WGS84_A + 520.0)).abs() < 1e-6);
+        // East is +Y in ECEF on the prime meridian
+        assert!((transform.transform_vector(&Vector3::x()) - Vector3::y()).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_georeferenced_transform_scale() {
+        // Millimetre project with a combined unit and grid scale factor
+        let mut georef = GeoReference::new();
+        georef.crs_name = Some("EPSG:32632".to_string());
+        georef.eastings = 691_449.656;
+        georef.northings = 5_334_905.738;
+        georef.scale = 0.0009996;
+
+        // Meshes are in meters: only the grid scale factor remains
+        let transform = georeferenced_transform(&georef, 0.001, None).unwrap();
+        let east = transform.transform_vector(&Vector3::x());
+        assert!((east.norm() - 0.9996).abs() < 1e-9);
+        // Heights are not scaled
+        let up = transform.transform_vector(&Vector3::z());
+        assert!((up.norm() - 1.0).abs() < 1e-9);
+
+        // A scale that is only the unit conversion leaves meters unchanged
+        georef.scale = 0.001;
+        let transform = georeferenced_transform(&georef, 0.001, None).unwrap();
+        assert!((transform.transform_vector(&Vector3::y()).norm() - 1.0).abs() < 1e-9);
+    }
+}
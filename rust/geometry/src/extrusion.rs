@@ -4,9 +4,10 @@
 
 //! Extrusion operations - converting 2D profiles to 3D meshes
 
+use crate::bool2d::{difference_2d, ensure_ccw, ensure_cw};
 use crate::error::{Error, Result};
 use crate::mesh::Mesh;
-use crate::profile::{Profile2D, Profile2DWithVoids, Triangulation, VoidInfo};
+use crate::profile::{Profile2D, Profile2DWithVoids, Triangulation};
 use nalgebra::{Matrix4, Point2, Point3, Vector3};
 
 /// Extrude a 2D profile along the Z axis
@@ -62,9 +63,11 @@ pub fn extrude_profile(
 
 /// Extrude a 2D profile with void awareness
 ///
-/// This function handles both through-voids and partial-depth voids:
-/// - Through voids: Added as holes to the profile before extrusion
-/// - Partial-depth voids: Generate internal caps at depth boundaries
+/// The extrusion is split into slabs at every void start and end depth. Each
+/// slab is the profile minus the voids active over its depth range, so both
+/// through voids and partial-depth voids (including ones that cut the
+/// profile boundary) remove exactly their prism from the solid. Horizontal
+/// faces are only generated where consecutive slabs differ.
 ///
 /// # Arguments
 /// * `profile_with_voids` - Profile with classified void information
@@ -85,46 +88,92 @@ pub fn extrude_profile_with_voids(
         ));
     }
 
-    // Create profile with through-voids as holes
-    let profile_with_holes = profile_with_voids.profile_with_through_holes();
-
-    // Triangulate the combined profile
-    let triangulation = profile_with_holes.triangulate()?;
-
-    // Estimate capacity
-    let partial_void_count = profile_with_voids.partial_voids().count();
+    let epsilon = depth * 1e-9;
+    let base = normalized_profile(&profile_with_voids.profile);
+
+    // Void prisms clamped to the extrusion range
+    let voids: Vec<(Profile2D, f64, f64)> = profile_with_voids
+        .voids
+        .iter()
+        .filter(|v| v.contour.len() >= 3)
+        .map(|v| {
+            let (start, end) = if v.is_through {
+                (0.0, depth)
+            } else {
+                (v.depth_start.max(0.0), v.depth_end.min(depth))
+            };
+            (Profile2D::new(v.contour.clone()), start, end)
+        })
+        .filter(|(_, start, end)| end - start > epsilon)
+        .collect();
+
+    let mut levels = vec![0.0, depth];
+    for (_, start, end) in &voids {
+        levels.push(*start);
+        levels.push(*end);
+    }
+    levels.sort_by(f64::total_cmp);
+    levels.dedup_by(|a, b| (*a - *b).abs() <= epsilon);
+    if let Some(last) = levels.last_mut() {
+        *last = depth;
+    }
 
-    let vertex_count = triangulation.points.len() * 2;
-    let side_vertex_count = profile_with_holes.outer.len() * 2
-        + profile_with_holes.holes.iter().map(|h| h.len() * 2).sum::<usize>();
-    let partial_void_vertices = partial_void_count * 100; // Estimate
-    let total_vertices = vertex_count + side_vertex_count + partial_void_vertices;
+    // Merge consecutive depth ranges with the same active voids into slabs
+    let mut slabs: Vec<Slab> = Vec::new();
+    for range in levels.windows(2) {
+        let mid = (range[0] + range[1]) * 0.5;
+        let active: Vec<usize> = voids
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, start, end))| *start < mid && mid < *end)
+            .map(|(i, _)| i)
+            .collect();
+
+        match slabs.last_mut() {
+            Some(slab) if slab.active == active => slab.z_end = range[1],
+            _ => {
+                let profiles = if active.is_empty() {
+                    vec![base.clone()]
+                } else {
+                    let clip: Vec<Profile2D> =
+                        active.iter().map(|&i| voids[i].0.clone()).collect();
+                    difference_2d(std::slice::from_ref(&base), &clip)
+                };
+                slabs.push(Slab {
+                    z_start: range[0],
+                    z_end: range[1],
+                    active,
+                    profiles,
+                });
+            }
+        }
+    }
 
-    let mut mesh = Mesh::with_capacity(
-        total_vertices,
-        triangulation.indices.len() * 2 + profile_with_holes.outer.len() * 6,
-    );
+    let mut mesh = Mesh::new();
 
-    // Create top and bottom caps (with through-void holes included)
-    create_cap_mesh(&triangulation, 0.0, Vector3::new(0.0, 0.0, -1.0), &mut mesh);
-    create_cap_mesh(
-        &triangulation,
-        depth,
-        Vector3::new(0.0, 0.0, 1.0),
-        &mut mesh,
-    );
-
-    // Create side walls for outer boundary
-    create_side_walls(&profile_with_holes.outer, depth, &mut mesh);
+    for slab in &slabs {
+        for profile in &slab.profiles {
+            create_side_walls_between(&profile.outer, slab.z_start, slab.z_end, &mut mesh);
+            for hole in &profile.holes {
+                create_side_walls_between(hole, slab.z_start, slab.z_end, &mut mesh);
+            }
+        }
+    }
 
-    // Create side walls for holes (including through-voids)
-    for hole in &profile_with_holes.holes {
-        create_side_walls(hole, depth, &mut mesh);
+    if let (Some(first), Some(last)) = (slabs.first(), slabs.last()) {
+        create_oriented_caps(&first.profiles, 0.0, false, &mut mesh)?;
+        create_oriented_caps(&last.profiles, depth, true, &mut mesh)?;
     }
 
-    // Handle partial-depth voids
-    for void in profile_with_voids.partial_voids() {
-        create_partial_void_geometry(void, depth, &mut mesh)?;
+    // Steps between slabs: material starting above faces down, material
+    // ending below faces up
+    for pair in slabs.windows(2) {
+        let (lower, upper) = (&pair[0], &pair[1]);
+        let z = lower.z_end;
+        let starting = difference_2d(&upper.profiles, &lower.profiles);
+        let ending = difference_2d(&lower.profiles, &upper.profiles);
+        create_oriented_caps(&starting, z, false, &mut mesh)?;
+        create_oriented_caps(&ending, z, true, &mut mesh)?;
     }
 
     // Apply transformation if provided
@@ -135,100 +184,82 @@ pub fn extrude_profile_with_voids(
     Ok(mesh)
 }
 
-/// Create geometry for a partial-depth void
-///
-/// Generates:
-/// - Internal cap at void start depth (if not at bottom)
-/// - Internal cap at void end depth (if not at top)
-/// - Side walls for the void opening
-fn create_partial_void_geometry(void: &VoidInfo, total_depth: f64, mesh: &mut Mesh) -> Result<()> {
-    if void.contour.len() < 3 {
-        return Ok(());
-    }
+/// Depth range of a voided extrusion with a constant cross-section
+struct Slab {
+    z_start: f64,
+    z_end: f64,
+    active: Vec<usize>,
+    profiles: Vec<Profile2D>,
+}
 
-    let epsilon = 0.001;
-
-    // Create triangulation for void contour
-    let void_profile = Profile2D::new(void.contour.clone());
-    let void_triangulation = match void_profile.triangulate() {
-        Ok(t) => t,
-        Err(_) => return Ok(()), // Skip if triangulation fails
-    };
-
-    // Create internal cap at void start (if not at bottom)
-    if void.depth_start > epsilon {
-        create_cap_mesh(
-            &void_triangulation,
-            void.depth_start,
-            Vector3::new(0.0, 0.0, -1.0), // Facing down into the void
-            mesh,
-        );
+/// Profile with a counter-clockwise outer boundary and clockwise holes
+fn normalized_profile(profile: &Profile2D) -> Profile2D {
+    Profile2D {
+        outer: ensure_ccw(&profile.outer),
+        holes: profile.holes.iter().map(|h| ensure_cw(h)).collect(),
     }
+}
 
-    // Create internal cap at void end (if not at top)
-    if void.depth_end < total_depth - epsilon {
-        create_cap_mesh(
-            &void_triangulation,
-            void.depth_end,
-            Vector3::new(0.0, 0.0, 1.0), // Facing up into the void
-            mesh,
-        );
-    }
+/// Create horizontal faces for profiles at height `z`, facing up or down
+///
+/// Triangle winding is derived from the triangulation itself, so it is
+/// correct regardless of the contour orientation earcut returns.
+fn create_oriented_caps(profiles: &[Profile2D], z: f64, up: bool, mesh: &mut Mesh) -> Result<()> {
+    let normal = Vector3::new(0.0, 0.0, if up { 1.0 } else { -1.0 });
+
+    for profile in profiles {
+        let triangulation = profile.triangulate()?;
+        let base_index = mesh.vertex_count() as u32;
 
-    // Create side walls for the void (from depth_start to depth_end)
-    let void_depth = void.depth_end - void.depth_start;
-    if void_depth > epsilon {
-        create_void_side_walls(&void.contour, void.depth_start, void.depth_end, mesh);
+        for point in &triangulation.points {
+            mesh.add_vertex(Point3::new(point.x, point.y, z), normal);
+        }
+
+        for tri in triangulation.indices.chunks_exact(3) {
+            let (a, b, c) = (
+                triangulation.points[tri[0]],
+                triangulation.points[tri[1]],
+                triangulation.points[tri[2]],
+            );
+            let ccw = (b - a).perp(&(c - a)) > 0.0;
+            let (i0, i1, i2) = (
+                base_index + tri[0] as u32,
+                base_index + tri[1] as u32,
+                base_index + tri[2] as u32,
+            );
+            if ccw == up {
+                mesh.add_triangle(i0, i1, i2);
+            } else {
+                mesh.add_triangle(i0, i2, i1);
+            }
+        }
     }
 
     Ok(())
 }
 
-/// Create side walls for a void opening between two depths
-fn create_void_side_walls(
-    contour: &[Point2<f64>],
-    z_start: f64,
-    z_end: f64,
-    mesh: &mut Mesh,
-) {
-    let base_index = mesh.vertex_count() as u32;
-    let mut quad_count = 0u32;
-
-    for i in 0..contour.len() {
-        let j = (i + 1) % contour.len();
-
-        let p0 = &contour[i];
-        let p1 = &contour[j];
+/// Create outward-facing side walls between two depths
+///
+/// Expects outer boundaries counter-clockwise and holes clockwise.
+fn create_side_walls_between(boundary: &[Point2<f64>], z_start: f64, z_end: f64, mesh: &mut Mesh) {
+    for i in 0..boundary.len() {
+        let p0 = &boundary[i];
+        let p1 = &boundary[(i + 1) % boundary.len()];
 
-        // Calculate normal for this edge (pointing inward for voids)
-        // Use try_normalize to handle degenerate edges (duplicate consecutive points)
-        let edge = Vector3::new(p1.x - p0.x, p1.y - p0.y, 0.0);
-        // Reverse normal direction for holes (pointing inward)
-        let normal = match Vector3::new(edge.y, -edge.x, 0.0).try_normalize(1e-10) {
+        // Skip degenerate edges (duplicate consecutive points)
+        let normal = match Vector3::new(p1.y - p0.y, p0.x - p1.x, 0.0).try_normalize(1e-10) {
             Some(n) => n,
-            None => continue, // Skip degenerate edge (duplicate points in contour)
+            None => continue,
         };
 
-        // Bottom vertices (at z_start)
-        let v0_bottom = Point3::new(p0.x, p0.y, z_start);
-        let v1_bottom = Point3::new(p1.x, p1.y, z_start);
-
-        // Top vertices (at z_end)
-        let v0_top = Point3::new(p0.x, p0.y, z_end);
-        let v1_top = Point3::new(p1.x, p1.y, z_end);
+        let idx = mesh.vertex_count() as u32;
+        mesh.add_vertex(Point3::new(p0.x, p0.y, z_start), normal);
+        mesh.add_vertex(Point3::new(p1.x, p1.y, z_start), normal);
+        mesh.add_vertex(Point3::new(p1.x, p1.y, z_end), normal);
+        mesh.add_vertex(Point3::new(p0.x, p0.y, z_end), normal);
 
-        // Add 4 vertices for this quad
-        let idx = base_index + (quad_count * 4);
-        mesh.add_vertex(v0_bottom, normal);
-        mesh.add_vertex(v1_bottom, normal);
-        mesh.add_vertex(v1_top, normal);
-        mesh.add_vertex(v0_top, normal);
-
-        // Add 2 triangles for the quad (reversed winding for inward-facing)
-        mesh.add_triangle(idx, idx + 2, idx + 1);
-        mesh.add_triangle(idx, idx + 3, idx + 2);
-
-        quad_count += 1;
+        mesh.add_triangle(idx, idx + 1, idx + 2);
+        mesh.add_triangle(idx, idx + 2, idx + 3);
     }
 }
 
@@ -370,7 +401,7 @@ pub fn apply_transform_with_rtc(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::profile::create_rectangle;
+    use crate::profile::{create_rectangle, VoidInfo};
 
     #[test]
     fn test_extrude_rectangle() {
@@ -445,4 +476,60 @@ mod tests {
         let result = extrude_profile(&profile, -1.0, None);
         assert!(result.is_err());
     }
+
+    fn square(min: f64, max: f64) -> Vec<Point2<f64>> {
+        vec![
+            Point2::new(min, min),
+            Point2::new(max, min),
+            Point2::new(max, max),
+            Point2::new(min, max),
+        ]
+    }
+
+    fn void(
+        contour: Vec<Point2<f64>>,
+        depth_start: f64,
+        depth_end: f64,
+        is_through: bool,
+    ) -> VoidInfo {
+        VoidInfo {
+            contour,
+            depth_start,
+            depth_end,
+            is_through,
+        }
+    }
+
+    #[test]
+    fn test_extrude_with_through_and_partial_voids() {
+        use crate::quantities::mesh_volume;
+
+        // 10 x 8 x 2 block with a 2 x 2 through hole and a 2 x 2 pocket 0.5 deep
+        let profile = Profile2DWithVoids::new(
+            create_rectangle(10.0, 8.0),
+            vec![
+                void(square(-3.0, -1.0), 0.0, 2.0, true),
+                void(square(1.0, 3.0), 1.5, 2.0, false),
+            ],
+        );
+        let mesh = extrude_profile_with_voids(&profile, 2.0, None).unwrap();
+
+        let expected = 160.0 - 8.0 - 2.0;
+        assert!((mesh_volume(&mesh) - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_extrude_with_void_crossing_boundary() {
+        use crate::quantities::mesh_volume;
+
+        // Notch through the edge of the profile over the lower half
+        let profile = Profile2DWithVoids::new(
+            create_rectangle(4.0, 4.0),
+            vec![void(square(1.0, 3.0), 0.0, 1.0, false)],
+        );
+        let mesh = extrude_profile_with_voids(&profile, 2.0, None).unwrap();
+
+        let expected = 32.0 - 1.0;
+        assert!((mesh_volume(&mesh) - expected).abs() < 1e-3);
+    }
 }
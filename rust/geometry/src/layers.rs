@@ -0,0 +1,690 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Material Layer Geometry
+//!
+//! Splits wall and slab bodies into one solid per material layer using the
+//! element's `IfcMaterialLayerSetUsage`:
+//!
+//! ```text
+//! IfcMaterialLayerSetUsage
+//!   ├── LayerSetDirection  AXIS1 / AXIS2 / AXIS3 of the element placement
+//!   ├── DirectionSense     POSITIVE / NEGATIVE stacking of the layers
+//!   └── OffsetFromReferenceLine  start of the first layer
+//! ```
+//!
+//! Each layer occupies a slab between two planes perpendicular to the layer
+//! set direction. The element mesh (openings already subtracted) is clipped at
+//! both planes and the cuts are capped, so layers come out as closed solids that
+//! can be styled and picked separately (see
+//! [`crate::GeometryRouter::process_element_with_layers`]).
+
+use ifc_lite_core::{
+    EntityDecoder, IfcType, MaterialAssociation, MaterialAssociationKind, MaterialResolver,
+};
+use nalgebra::{Matrix4, Point2, Point3, Vector3};
+use rustc_hash::FxHashMap;
+
+use crate::bool2d::{compute_signed_area, point_in_contour};
+use crate::csg::{ClipResult, ClippingProcessor, Plane, Triangle};
+use crate::mesh::Mesh;
+use crate::triangulation::triangulate_polygon_with_holes;
+
+/// Layers thinner than this (in meters) are not split off
+const MIN_LAYER_THICKNESS: f64 = 1e-6;
+
+/// Margin added around the element when building layer slabs (in meters)
+const SLAB_MARGIN: f64 = 0.01;
+
+/// Distance from a cut plane below which a vertex counts as on the plane
+const CUT_EPSILON: f64 = 1e-6;
+
+/// Grid used to match cut segment endpoints into loops (in meters)
+const LOOP_TOLERANCE: f64 = 1e-5;
+
+/// Sine of the angle below which a cut point counts as lying on a cap edge
+const COLLINEAR_TOLERANCE: f64 = 1e-9;
+
+/// Outer boundary of a cap with its holes
+type CapPolygon = (Vec<Point2<f64>>, Vec<Vec<Point2<f64>>>);
+
+/// Axis of the element placement the layers are stacked along
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerAxis {
+    /// Local X
+    Axis1,
+    /// Local Y, the thickness direction of standard walls
+    Axis2,
+    /// Local Z, the thickness direction of slabs
+    Axis3,
+}
+
+impl LayerAxis {
+    /// Parse an IfcLayerSetDirectionEnum value (without dots)
+    pub fn from_ifc(value: &str) -> Option<Self> {
+        match value.to_ascii_uppercase().as_str() {
+            "AXIS1" => Some(LayerAxis::Axis1),
+            "AXIS2" => Some(LayerAxis::Axis2),
+            "AXIS3" => Some(LayerAxis::Axis3),
+            _ => None,
+        }
+    }
+
+    /// Coordinate index in the element's local frame
+    pub fn index(&self) -> usize {
+        match self {
+            LayerAxis::Axis1 => 0,
+            LayerAxis::Axis2 => 1,
+            LayerAxis::Axis3 => 2,
+        }
+    }
+}
+
+/// One material layer as a slab along the layer set direction
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaterialLayerSlice {
+    /// Position within the layer set
+    pub index: usize,
+    /// IfcMaterialLayer entity
+    pub layer_id: Option<u32>,
+    /// IfcMaterial of the layer; `None` for air gaps
+    pub material_id: Option<u32>,
+    /// IfcMaterial.Name
+    pub material_name: Option<String>,
+    /// Lower bound along the layer axis, in meters from the placement origin
+    pub min: f64,
+    /// Upper bound along the layer axis, in meters from the placement origin
+    pub max: f64,
+}
+
+/// Layer slabs of one element
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaterialLayerSplit {
+    /// Axis the layers are stacked along
+    pub axis: LayerAxis,
+    /// Layers in set order
+    pub layers: Vec<MaterialLayerSlice>,
+}
+
+impl MaterialLayerSplit {
+    /// Layer slabs of a resolved material association with lengths in meters
+    ///
+    /// Only layer set usages with at least two layers of non-zero thickness can be
+    /// split; anything else renders as a single mesh.
+    pub fn from_association(association: &MaterialAssociation) -> Option<Self> {
+        if association.kind != MaterialAssociationKind::LayerSetUsage {
+            return None;
+        }
+        let axis = LayerAxis::from_ifc(association.layer_set_direction.as_deref()?)?;
+        let negative = association
+            .direction_sense
+            .as_deref()
+            .is_some_and(|sense| sense.eq_ignore_ascii_case("NEGATIVE"));
+
+        let layers: Vec<MaterialLayerSlice> = association
+            .parts
+            .iter()
+            .filter_map(|part| {
+                let thickness = part.thickness?;
+                if thickness < MIN_LAYER_THICKNESS {
+                    return None;
+                }
+                let start = part.offset?;
+                let end = if negative {
+                    start - thickness
+                } else {
+                    start + thickness
+                };
+                Some(MaterialLayerSlice {
+                    index: part.index,
+                    layer_id: part.part_id,
+                    material_id: part.material_id,
+                    material_name: part.material_name.clone(),
+                    min: start.min(end),
+                    max: start.max(end),
+                })
+            })
+            .collect();
+
+        (layers.len() > 1).then_some(Self { axis, layers })
+    }
+}
+
+/// Whether layers of this element type are split into separate meshes
+pub fn has_layered_geometry(ifc_type: &IfcType) -> bool {
+    ifc_type.is_subtype_of(IfcType::IfcWall) || ifc_type.is_subtype_of(IfcType::IfcSlab)
+}
+
+/// Layer slabs of every wall and slab with a material layer set usage
+///
+/// `length_scale` converts layer thicknesses and offsets to meters (the
+/// router's unit scale). Usages inherited from the type object are included.
+pub fn build_material_layer_index(
+    content: &str,
+    decoder: &mut EntityDecoder,
+    length_scale: f64,
+) -> FxHashMap<u32, MaterialLayerSplit> {
+    let resolver = MaterialResolver::from_content(content, decoder);
+    let mut index = FxHashMap::default();
+
+    let elements: Vec<u32> = resolver.elements().collect();
+    for element_id in elements {
+        let is_layered = decoder
+            .decode_by_id(element_id)
+            .map(|element| has_layered_geometry(&element.ifc_type))
+            .unwrap_or(false);
+        if !is_layered {
+            continue;
+        }
+        for mut association in resolver.materials(element_id, decoder) {
+            association.scale_lengths(length_scale);
+            if let Some(split) = MaterialLayerSplit::from_association(&association) {
+                index.insert(element_id, split);
+                break;
+            }
+        }
+    }
+
+    index
+}
+
+/// Split a mesh into one mesh per layer
+///
+/// `placement` maps the element's local frame to the frame of `mesh`. Layers
+/// the mesh does not reach come back empty.
+pub fn split_mesh_by_layers(
+    mesh: &Mesh,
+    placement: &Matrix4<f64>,
+    split: &MaterialLayerSplit,
+) -> Vec<Mesh> {
+    let Some(inverse) = placement.try_inverse() else {
+        return Vec::new();
+    };
+    let mut local = mesh.clone();
+    transform_mesh(&mut local, &inverse);
+
+    let (min, max) = local.bounds();
+    let lower = Point3::new(min.x as f64, min.y as f64, min.z as f64).map(|v| v - SLAB_MARGIN);
+    let upper = Point3::new(max.x as f64, max.y as f64, max.z as f64).map(|v| v + SLAB_MARGIN);
+    let axis = split.axis.index();
+    let clipper = ClippingProcessor::new();
+
+    split
+        .layers
+        .iter()
+        .map(|layer| {
+            if layer.max <= lower[axis] || layer.min >= upper[axis] {
+                return Mesh::new();
+            }
+            let mut slab_min = lower;
+            let mut slab_max = upper;
+            slab_min[axis] = layer.min;
+            slab_max[axis] = layer.max;
+
+            let mut part = slab_of(&clipper, &local, slab_min, slab_max, axis);
+            transform_mesh(&mut part, placement);
+            part
+        })
+        .collect()
+}
+
+/// Part of a local-frame mesh between two axis-aligned planes
+///
+/// Clips at both planes and closes the cuts with cap faces. Inputs whose cuts
+/// do not form closed loops are intersected with a box instead, and clipped
+/// without caps if that fails as well.
+fn slab_of(
+    clipper: &ClippingProcessor,
+    mesh: &Mesh,
+    min: Point3<f64>,
+    max: Point3<f64>,
+    axis: usize,
+) -> Mesh {
+    let capped = clip_and_cap(clipper, mesh, axis, min[axis], true)
+        .and_then(|lower| clip_and_cap(clipper, &lower, axis, max[axis], false));
+    if let Some(result) = capped {
+        return result;
+    }
+
+    if let Ok(result) = clipper.intersection_mesh(mesh, &box_mesh(min, max)) {
+        if !result.is_empty() {
+            return result;
+        }
+    }
+
+    let mut normal = Vector3::zeros();
+    normal[axis] = 1.0;
+    let clipped = clipper
+        .clip_mesh(mesh, &Plane::new(min, normal))
+        .and_then(|lower| clipper.clip_mesh(&lower, &Plane::new(max, -normal)));
+    clipped.unwrap_or_default()
+}
+
+/// Keep the part of a local-frame mesh on one side of the plane `axis = at`
+/// and close the cut with cap faces
+///
+/// `keep_above` keeps coordinates at or above `at`. Returns `None` when the cut
+/// edges do not form closed loops (open or non-manifold input).
+fn clip_and_cap(
+    clipper: &ClippingProcessor,
+    mesh: &Mesh,
+    axis: usize,
+    at: f64,
+    keep_above: bool,
+) -> Option<Mesh> {
+    let sign = if keep_above { 1.0 } else { -1.0 };
+    let mut normal = Vector3::zeros();
+    normal[axis] = sign;
+    let mut origin = Point3::origin();
+    origin[axis] = at;
+    let plane = Plane::new(origin, normal);
+
+    // Cap coordinates are the two remaining axes
+    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+    let mut kept = Mesh::with_capacity(mesh.vertex_count(), mesh.indices.len());
+    let mut segments: Vec<(Point2<f64>, Point2<f64>)> = Vec::new();
+
+    for tri in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [tri[0], tri[1], tri[2]].map(|i| {
+            let i = i as usize * 3;
+            Point3::new(
+                mesh.positions[i] as f64,
+                mesh.positions[i + 1] as f64,
+                mesh.positions[i + 2] as f64,
+            )
+        });
+        let triangle = Triangle::new(a, b, c);
+        match clipper.clip_triangle(&triangle, &plane) {
+            ClipResult::AllFront(t) => add_triangle(&mut kept, &t, triangle.normal()),
+            ClipResult::AllBehind => {}
+            ClipResult::Split(parts) => {
+                for t in parts.iter() {
+                    add_triangle(&mut kept, t, triangle.normal());
+                }
+            }
+        }
+
+        // Crossing points of triangles with vertices strictly on both sides
+        let points = [a, b, c];
+        let distances = points.map(|p| plane.signed_distance(&p));
+        if !(distances.iter().any(|&d| d > CUT_EPSILON)
+            && distances.iter().any(|&d| d < -CUT_EPSILON))
+        {
+            continue;
+        }
+        let mut crossing: Vec<Point2<f64>> = Vec::with_capacity(2);
+        for i in 0..3 {
+            let j = (i + 1) % 3;
+            let (di, dj) = (distances[i], distances[j]);
+            if di.abs() <= CUT_EPSILON {
+                crossing.push(Point2::new(points[i][u], points[i][v]));
+            } else if (di > CUT_EPSILON && dj < -CUT_EPSILON)
+                || (di < -CUT_EPSILON && dj > CUT_EPSILON)
+            {
+                let p = points[i] + (points[j] - points[i]) * (di / (di - dj));
+                crossing.push(Point2::new(p[u], p[v]));
+            }
+        }
+        if crossing.len() != 2 {
+            return None;
+        }
+        segments.push((crossing[0], crossing[1]));
+    }
+
+    if segments.is_empty() {
+        return Some(kept);
+    }
+
+    let loops = chain_loops(&segments)?;
+    let cap_normal = -normal;
+    let to_3d = |p: &Point2<f64>| {
+        let mut point = origin;
+        point[u] = p.x;
+        point[v] = p.y;
+        point
+    };
+
+    for (outer, holes) in nest_loops(loops) {
+        let triangles: Vec<[Point3<f64>; 3]> = cap_triangles(&outer, &holes)?
+            .iter()
+            .map(|tri| tri.map(|p| to_3d(&p)))
+            .collect();
+        // Triangulations are consistently wound, so orient the polygon as a whole
+        let winding: f64 = triangles
+            .iter()
+            .map(|[p0, p1, p2]| (p1 - p0).cross(&(p2 - p0)).dot(&cap_normal))
+            .sum();
+        for [p0, p1, p2] in triangles {
+            let triangle = if winding < 0.0 {
+                Triangle::new(p2, p1, p0)
+            } else {
+                Triangle::new(p0, p1, p2)
+            };
+            add_triangle(&mut kept, &triangle, cap_normal);
+        }
+    }
+
+    Some(kept)
+}
+
+/// Triangulate a cap polygon without T-junctions
+///
+/// Collinear cut points (where the clipped side faces have vertices) are left
+/// out of the triangulation and put back on the edges they lie on by fanning
+/// the affected triangles from their centroid.
+fn cap_triangles(
+    outer: &[Point2<f64>],
+    holes: &[Vec<Point2<f64>>],
+) -> Option<Vec<[Point2<f64>; 3]>> {
+    // Corner points of every ring, the collinear points following each corner,
+    // and the index of the next corner in the same ring
+    let mut corners: Vec<Point2<f64>> = Vec::new();
+    let mut following: Vec<Vec<Point2<f64>>> = Vec::new();
+    let mut next: Vec<usize> = Vec::new();
+    let mut simplified: Vec<Vec<Point2<f64>>> = Vec::new();
+
+    for ring in std::iter::once(outer).chain(holes.iter().map(Vec::as_slice)) {
+        let n = ring.len();
+        let is_corner = |i: usize| {
+            let (prev, p, after) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+            let (d0, d1) = (p - prev, after - p);
+            d0.perp(&d1).abs() > COLLINEAR_TOLERANCE * d0.norm() * d1.norm()
+        };
+        let Some(first) = (0..n).find(|&i| is_corner(i)) else {
+            continue;
+        };
+
+        let start = corners.len();
+        let mut ring_corners = Vec::new();
+        for k in 0..n {
+            let i = (first + k) % n;
+            if is_corner(i) {
+                ring_corners.push(ring[i]);
+                corners.push(ring[i]);
+                following.push(Vec::new());
+                next.push(corners.len());
+            } else if let Some(last) = following.last_mut() {
+                last.push(ring[i]);
+            }
+        }
+        // Close the ring
+        if let Some(last) = next.last_mut() {
+            *last = start;
+        }
+        simplified.push(ring_corners);
+    }
+
+    let (simple_outer, simple_holes) = simplified.split_first()?;
+    let indices = triangulate_polygon_with_holes(simple_outer, simple_holes).ok()?;
+
+    let mut triangles = Vec::with_capacity(indices.len() / 3);
+    for tri in indices.chunks_exact(3) {
+        let mut boundary: Vec<Point2<f64>> = Vec::with_capacity(3);
+        for k in 0..3 {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            boundary.push(corners[a]);
+            if next[a] == b {
+                boundary.extend(following[a].iter().copied());
+            } else if next[b] == a {
+                boundary.extend(following[b].iter().rev().copied());
+            }
+        }
+        if boundary.len() == 3 {
+            triangles.push([boundary[0], boundary[1], boundary[2]]);
+        } else {
+            let centroid = Point2::from(
+                (corners[tri[0]].coords + corners[tri[1]].coords + corners[tri[2]].coords) / 3.0,
+            );
+            for k in 0..boundary.len() {
+                triangles.push([boundary[k], boundary[(k + 1) % boundary.len()], centroid]);
+            }
+        }
+    }
+    Some(triangles)
+}
+
+/// Join cut segments into closed loops, `None` if any loop stays open
+fn chain_loops(segments: &[(Point2<f64>, Point2<f64>)]) -> Option<Vec<Vec<Point2<f64>>>> {
+    let key = |p: &Point2<f64>| {
+        (
+            (p.x / LOOP_TOLERANCE).round() as i64,
+            (p.y / LOOP_TOLERANCE).round() as i64,
+        )
+    };
+
+    // Endpoint → segments touching it; degenerate segments are dropped
+    let mut incident: FxHashMap<(i64, i64), Vec<usize>> = FxHashMap::default();
+    let mut live = vec![false; segments.len()];
+    for (i, (a, b)) in segments.iter().enumerate() {
+        if key(a) == key(b) {
+            continue;
+        }
+        live[i] = true;
+        incident.entry(key(a)).or_default().push(i);
+        incident.entry(key(b)).or_default().push(i);
+    }
+    if incident.values().any(|touching| touching.len() != 2) {
+        return None;
+    }
+
+    let mut used = vec![false; segments.len()];
+    let mut loops = Vec::new();
+    for first in 0..segments.len() {
+        if used[first] || !live[first] {
+            continue;
+        }
+        used[first] = true;
+        let (start, mut current) = segments[first];
+        let mut ring = vec![start];
+        while key(&current) != key(&start) {
+            ring.push(current);
+            let next = incident[&key(&current)]
+                .iter()
+                .copied()
+                .find(|&s| !used[s])?;
+            used[next] = true;
+            let (a, b) = segments[next];
+            current = if key(&a) == key(&current) { b } else { a };
+        }
+        if ring.len() >= 3 {
+            loops.push(ring);
+        }
+    }
+    Some(loops)
+}
+
+/// Group loops into outer boundaries with their holes by containment depth
+fn nest_loops(mut loops: Vec<Vec<Point2<f64>>>) -> Vec<CapPolygon> {
+    loops.sort_by(|a, b| {
+        compute_signed_area(b)
+            .abs()
+            .partial_cmp(&compute_signed_area(a).abs())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut polygons: Vec<CapPolygon> = Vec::new();
+    // Index into `polygons` of each outer loop, by position in `loops`
+    let mut outer_of: Vec<Option<usize>> = Vec::with_capacity(loops.len());
+    for (i, ring) in loops.iter().enumerate() {
+        let containing: Vec<usize> = (0..i)
+            .filter(|&j| point_in_contour(&ring[0], &loops[j]))
+            .collect();
+        if containing.len() % 2 == 0 {
+            outer_of.push(Some(polygons.len()));
+            polygons.push((ring.clone(), Vec::new()));
+        } else {
+            outer_of.push(None);
+            // Innermost containing outer loop
+            if let Some(&owner) = containing.iter().rev().find_map(|&j| outer_of[j].as_ref()) {
+                polygons[owner].1.push(ring.clone());
+            }
+        }
+    }
+    polygons
+}
+
+/// Append a triangle with a flat normal
+fn add_triangle(mesh: &mut Mesh, triangle: &Triangle, normal: Vector3<f64>) {
+    let base = mesh.vertex_count() as u32;
+    mesh.add_vertex(triangle.v0, normal);
+    mesh.add_vertex(triangle.v1, normal);
+    mesh.add_vertex(triangle.v2, normal);
+    mesh.add_triangle(base, base + 1, base + 2);
+}
+
+/// Closed box mesh with outward-facing triangles
+fn box_mesh(min: Point3<f64>, max: Point3<f64>) -> Mesh {
+    let corners = [
+        Point3::new(min.x, min.y, min.z),
+        Point3::new(max.x, min.y, min.z),
+        Point3::new(max.x, max.y, min.z),
+        Point3::new(min.x, max.y, min.z),
+        Point3::new(min.x, min.y, max.z),
+        Point3::new(max.x, min.y, max.z),
+        Point3::new(max.x, max.y, max.z),
+        Point3::new(min.x, max.y, max.z),
+    ];
+    // Faces as corner quads, counter-clockwise seen from outside
+    let faces: [([usize; 4], Vector3<f64>); 6] = [
+        ([0, 3, 2, 1], Vector3::new(0.0, 0.0, -1.0)),
+        ([4, 5, 6, 7], Vector3::new(0.0, 0.0, 1.0)),
+        ([0, 1, 5, 4], Vector3::new(0.0, -1.0, 0.0)),
+        ([2, 3, 7, 6], Vector3::new(0.0, 1.0, 0.0)),
+        ([0, 4, 7, 3], Vector3::new(-1.0, 0.0, 0.0)),
+        ([1, 2, 6, 5], Vector3::new(1.0, 0.0, 0.0)),
+    ];
+
+    let mut mesh = Mesh::with_capacity(24, 36);
+    for (quad, normal) in faces {
+        let base = mesh.vertex_count() as u32;
+        for corner in quad {
+            mesh.add_vertex(corners[corner], normal);
+        }
+        mesh.add_triangle(base, base + 1, base + 2);
+        mesh.add_triangle(base, base + 2, base + 3);
+    }
+    mesh
+}
+
+/// Apply an affine transform to positions and normals
+fn transform_mesh(mesh: &mut Mesh, transform: &Matrix4<f64>) {
+    mesh.positions.chunks_exact_mut(3).for_each(|chunk| {
+        let point = Point3::new(chunk[0] as f64, chunk[1] as f64, chunk[2] as f64);
+        let t = transform.transform_point(&point);
+        chunk[0] = t.x as f32;
+        chunk[1] = t.y as f32;
+        chunk[2] = t.z as f32;
+    });
+    let rotation = transform.fixed_view::<3, 3>(0, 0);
+    mesh.normals.chunks_exact_mut(3).for_each(|chunk| {
+        let normal = Vector3::new(chunk[0] as f64, chunk[1] as f64, chunk[2] as f64);
+        let t = (rotation * normal).normalize();
+        chunk[0] = t.x as f32;
+        chunk[1] = t.y as f32;
+        chunk[2] = t.z as f32;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extrusion::extrude_profile;
+    use crate::profile::Profile2D;
+    use crate::quantities::mesh_volume;
+
+    fn split(direction: &str, sense: &str, offset: f64, thicknesses: &[f64]) -> MaterialLayerSplit {
+        let content = format!(
+            "#1=IFCMATERIAL('A',$,$);\n{}\n#90=IFCMATERIALLAYERSET(({}),'Set',$);\n#91=IFCMATERIALLAYERSETUSAGE(#90,.{}.,.{}.,{},$);\n",
+            thicknesses
+                .iter()
+                .enumerate()
+                .map(|(i, t)| format!("#{}=IFCMATERIALLAYER(#1,{:?},$,$,$,$,$);", 10 + i, t))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            (0..thicknesses.len())
+                .map(|i| format!("#{}", 10 + i))
+                .collect::<Vec<_>>()
+                .join(","),
+            direction,
+            sense,
+            offset,
+        );
+        let mut decoder = EntityDecoder::new(&content);
+        let association = ifc_lite_core::materials::resolve_material(
+            91,
+            ifc_lite_core::PropertySource::Occurrence,
+            &mut decoder,
+        )
+        .unwrap();
+        MaterialLayerSplit::from_association(&association).unwrap()
+    }
+
+    #[test]
+    fn test_layer_slabs_follow_direction_sense() {
+        let positive = split("AXIS2", "POSITIVE", -0.15, &[0.2, 0.1]);
+        assert_eq!(positive.axis, LayerAxis::Axis2);
+        assert!((positive.layers[0].min + 0.15).abs() < 1e-9);
+        assert!((positive.layers[0].max - 0.05).abs() < 1e-9);
+        assert!((positive.layers[1].max - 0.15).abs() < 1e-9);
+
+        let negative = split("AXIS3", "NEGATIVE", 0.0, &[0.05, 0.25]);
+        assert_eq!(negative.axis, LayerAxis::Axis3);
+        assert!((negative.layers[0].min + 0.05).abs() < 1e-9);
+        assert!(negative.layers[0].max.abs() < 1e-9);
+        assert!((negative.layers[1].min + 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_split_box_into_layers() {
+        // 4m x 0.3m x 3m wall centred on its axis, translated away from the origin
+        let layers = split("AXIS2", "POSITIVE", -0.15, &[0.2, 0.1]);
+        let wall = box_mesh(Point3::new(0.0, -0.15, 0.0), Point3::new(4.0, 0.15, 3.0));
+        let placement = Matrix4::new_translation(&Vector3::new(10.0, 5.0, 0.0));
+        let mut world = wall.clone();
+        transform_mesh(&mut world, &placement);
+
+        let parts = split_mesh_by_layers(&world, &placement, &layers);
+        assert_eq!(parts.len(), 2);
+        assert!((mesh_volume(&parts[0]) - 4.0 * 0.2 * 3.0).abs() < 1e-3);
+        assert!((mesh_volume(&parts[1]) - 4.0 * 0.1 * 3.0).abs() < 1e-3);
+
+        // Layers stay in the world frame of the input mesh
+        let (min, max) = parts[1].bounds();
+        assert!((min.y - 5.05).abs() < 1e-4 && (max.y - 5.15).abs() < 1e-4);
+        assert!((min.x - 10.0).abs() < 1e-4);
+
+        // Cut faces are capped, so each layer is a closed solid
+        for mut part in parts {
+            part.weld(crate::DEFAULT_WELD_TOLERANCE);
+            assert!(part.is_solid());
+        }
+    }
+
+    #[test]
+    fn test_layer_caps_keep_openings() {
+        // 4m x 3m panel with a 1m x 1m opening, 0.3m thick along Z
+        let mut profile = Profile2D::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(4.0, 0.0),
+            Point2::new(4.0, 3.0),
+            Point2::new(0.0, 3.0),
+        ]);
+        profile.add_hole(vec![
+            Point2::new(1.0, 1.0),
+            Point2::new(1.0, 2.0),
+            Point2::new(2.0, 2.0),
+            Point2::new(2.0, 1.0),
+        ]);
+        let panel = extrude_profile(&profile, 0.3, None).unwrap();
+        let layers = split("AXIS3", "POSITIVE", 0.0, &[0.2, 0.1]);
+
+        let parts = split_mesh_by_layers(&panel, &Matrix4::identity(), &layers);
+        assert!((mesh_volume(&parts[0]) - 11.0 * 0.2).abs() < 1e-3);
+        assert!((mesh_volume(&parts[1]) - 11.0 * 0.1).abs() < 1e-3);
+        for mut part in parts {
+            part.weld(crate::DEFAULT_WELD_TOLERANCE);
+            assert!(part.is_solid());
+        }
+    }
+}
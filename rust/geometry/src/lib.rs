@@ -15,7 +15,19 @@
 //! - **Extrusion**: Generate 3D meshes from extruded profiles
 //! - **Triangulation**: Polygon triangulation with hole support via earcutr
 //! - **CSG Operations**: Full boolean operations (difference, union, intersection)
-//! - **Mesh Processing**: Normal calculation and coordinate transformations
+//! - **Mesh Processing**: Vertex welding, crease-angle normals and coordinate transformations
+//! - **Mesh Validation**: Watertightness and manifold checks for take-off and printing
+//! - **Oriented Bounding Boxes**: PCA-seeded, calipers-refined boxes per element
+//! - **Computed Quantities**: Volume, surface, footprint and bounding box take-off
+//! - **Surface Materials**: Rendering styles, transparency, specular and texture coordinates
+//! - **Representation Selection**: Body, Axis, FootPrint, Box or Clearance with fallback order
+//! - **Style Index**: Shared colour resolution (styled item, material, type object, defaults)
+//! - **Polylines**: Line output for axes, curve sets, annotations and grid axes with tags
+//! - **Plan Cuts**: Section polygons and projected lines for quick floor plans
+//! - **Drawing Export**: SVG and DXF writers for plan cuts and elevations
+//! - **glTF Export**: GLB with `EXT_mesh_gpu_instancing` and per-element node extras
+//! - **3D Tiles Export**: KD-tree tilesets with per-element feature ids and georeferenced placement
+//! - **Mesh File Export**: STL (binary/ASCII), OBJ with MTL materials and PLY with unit conversion
 //!
 //! ## Supported Geometry Types
 //!
@@ -70,27 +82,63 @@
 pub mod bool2d;
 pub mod csg;
 pub mod error;
+pub mod export;
 pub mod extrusion;
+pub mod layers;
+pub mod material;
 pub mod mesh;
+pub mod obb;
+pub mod plan_cut;
+pub mod polyline;
 pub mod processors;
 pub mod profile;
 pub mod profiles;
+pub mod quantities;
+pub mod representation;
 pub mod router;
+pub mod style;
 pub mod triangulation;
+pub mod validation;
 pub mod void_analysis;
 pub mod void_index;
 
 // Re-export nalgebra types for convenience
-pub use nalgebra::{Point2, Point3, Vector2, Vector3};
+pub use nalgebra::{Matrix4, Point2, Point3, Vector2, Vector3};
 
 pub use bool2d::{
     compute_signed_area, ensure_ccw, ensure_cw, is_valid_contour, point_in_contour,
     subtract_2d, subtract_multiple_2d, union_contours,
 };
-pub use csg::{calculate_normals, ClippingProcessor, Plane, Triangle};
+pub use csg::{
+    calculate_crease_normals, calculate_normals, ClippingProcessor, Plane, Triangle,
+    DEFAULT_CREASE_ANGLE,
+};
 pub use error::{Error, Result};
+pub use export::{
+    detect_rtc_offset, draw, georeferenced_transform, mesh_elements_local,
+    mesh_elements_local_with_rtc, write_dxf, write_mesh_files, write_obj, write_ply,
+    write_ply_ascii, write_stl, write_stl_ascii, write_svg, DrawingFormat, DrawingRequest,
+    DrawingView, DxfOptions, ElementInfo, ElementMesh, ExportUnit, GlbBuilder, GlbOptions,
+    MeshExportOptions, MeshFormat, MeshPart, ObjFile, SvgOptions, TileContent, Tileset,
+    TilesetBuilder, TilesetOptions,
+};
 pub use extrusion::{extrude_profile, extrude_profile_with_voids};
-pub use mesh::{CoordinateShift, Mesh, SubMesh, SubMeshCollection};
+pub use layers::{
+    build_material_layer_index, has_layered_geometry, split_mesh_by_layers, LayerAxis,
+    MaterialLayerSlice, MaterialLayerSplit,
+};
+pub use material::{
+    build_texture_map_index, extract_material_from_styles, extract_styled_item_material,
+    extract_surface_material, IndexedTextureMap, ReflectanceMethod, SpecularHighlight,
+    SurfaceMaterial, SurfaceSide, SurfaceTexture, TextureSource,
+};
+pub use mesh::{CoordinateShift, Mesh, SubMesh, SubMeshCollection, DEFAULT_WELD_TOLERANCE};
+pub use obb::OrientedBoundingBox;
+pub use plan_cut::{
+    is_drawn, CutElement, ElevationDirection, PlanCut, PlanCutter, ProjectedElement, StoreyLevel,
+    DEFAULT_CUT_HEIGHT, DEFAULT_VIEW_DEPTH,
+};
+pub use polyline::{CurveLabel, CurveSet, Polyline3D};
 pub use processors::{
     AdvancedBrepProcessor, BooleanClippingProcessor, ExtrudedAreaSolidProcessor,
     FaceBasedSurfaceModelProcessor, FacetedBrepProcessor, MappedItemProcessor,
@@ -99,8 +147,20 @@ pub use processors::{
 };
 pub use profile::{Profile2D, Profile2DWithVoids, ProfileType, VoidInfo};
 pub use profiles::ProfileProcessor;
+pub use quantities::{
+    mesh_surface_area, mesh_volume, projected_area, ComputedQuantities, ProfileQuantities,
+    QuantitySource,
+};
+pub use representation::{
+    RepresentationCriterion, RepresentationIdentifier, RepresentationSelection, TargetView,
+};
 pub use router::{GeometryProcessor, GeometryRouter};
+pub use style::{default_color, StyleIndex, StyleSource};
 pub use triangulation::triangulate_polygon;
+pub use validation::{
+    count_self_intersections, validate_mesh, validate_mesh_buffers,
+    validate_mesh_with_intersections, MeshValidationReport,
+};
 pub use void_analysis::{
     classify_voids_batch, extract_coplanar_voids, extract_nonplanar_voids, VoidAnalyzer,
     VoidClassification,
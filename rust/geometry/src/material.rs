@@ -0,0 +1,694 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Surface Materials
+//!
+//! Resolves IFC surface presentation styles into a renderer-neutral material:
+//!
+//! ```text
+//! IfcStyledItem → [IfcPresentationStyleAssignment] → IfcSurfaceStyle
+//!   ├── IfcSurfaceStyleShading / IfcSurfaceStyleRendering → colors, transparency, specular
+//!   └── IfcSurfaceStyleWithTextures → IfcImageTexture / IfcPixelTexture / IfcBlobTexture
+//! ```
+//!
+//! Texture coordinates come from `IfcIndexedTriangleTextureMap`, which the
+//! router applies to the triangulated face set it is mapped to (see
+//! [`crate::GeometryRouter::set_texture_maps`]).
+
+use ifc_lite_core::{AttributeValue, DecodedEntity, EntityDecoder, EntityScanner, IfcType};
+use rustc_hash::FxHashMap;
+
+use crate::mesh::Mesh;
+
+/// Color used when a style references a color that cannot be resolved
+const FALLBACK_COLOR: [f32; 3] = [0.8, 0.8, 0.8];
+
+/// Lighting model requested by IfcSurfaceStyleRendering.ReflectanceMethod
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReflectanceMethod {
+    Blinn,
+    Flat,
+    Glass,
+    Matt,
+    Metal,
+    Mirror,
+    Phong,
+    Plastic,
+    Strauss,
+    #[default]
+    NotDefined,
+}
+
+impl ReflectanceMethod {
+    /// Parse an IfcReflectanceMethodEnum value (without dots)
+    pub fn from_ifc(value: &str) -> Self {
+        match value.to_ascii_uppercase().as_str() {
+            "BLINN" => ReflectanceMethod::Blinn,
+            "FLAT" => ReflectanceMethod::Flat,
+            "GLASS" => ReflectanceMethod::Glass,
+            "MATT" => ReflectanceMethod::Matt,
+            "METAL" => ReflectanceMethod::Metal,
+            "MIRROR" => ReflectanceMethod::Mirror,
+            "PHONG" => ReflectanceMethod::Phong,
+            "PLASTIC" => ReflectanceMethod::Plastic,
+            "STRAUSS" => ReflectanceMethod::Strauss,
+            _ => ReflectanceMethod::NotDefined,
+        }
+    }
+
+    /// Lowercase name for serialization
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReflectanceMethod::Blinn => "blinn",
+            ReflectanceMethod::Flat => "flat",
+            ReflectanceMethod::Glass => "glass",
+            ReflectanceMethod::Matt => "matt",
+            ReflectanceMethod::Metal => "metal",
+            ReflectanceMethod::Mirror => "mirror",
+            ReflectanceMethod::Phong => "phong",
+            ReflectanceMethod::Plastic => "plastic",
+            ReflectanceMethod::Strauss => "strauss",
+            ReflectanceMethod::NotDefined => "notdefined",
+        }
+    }
+}
+
+/// Which side of a surface a style applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SurfaceSide {
+    Positive,
+    Negative,
+    #[default]
+    Both,
+}
+
+impl SurfaceSide {
+    /// Parse an IfcSurfaceSide value (without dots)
+    pub fn from_ifc(value: &str) -> Self {
+        match value.to_ascii_uppercase().as_str() {
+            "POSITIVE" => SurfaceSide::Positive,
+            "NEGATIVE" => SurfaceSide::Negative,
+            _ => SurfaceSide::Both,
+        }
+    }
+}
+
+/// Specular highlight control of IfcSurfaceStyleRendering
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpecularHighlight {
+    /// Phong/Blinn exponent (IfcSpecularExponent)
+    Exponent(f64),
+    /// Roughness in [0, 1] (IfcSpecularRoughness)
+    Roughness(f64),
+}
+
+/// Where the pixels of a texture come from
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextureSource {
+    /// IfcImageTexture: external image file
+    Image { url: String },
+    /// IfcPixelTexture: inline pixels (not decoded)
+    Pixel {
+        width: u32,
+        height: u32,
+        components: u32,
+    },
+    /// IfcBlobTexture: inline encoded image (not decoded)
+    Blob { raster_format: String },
+}
+
+/// A texture attached to a surface style
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceTexture {
+    /// IfcSurfaceTexture entity ID (referenced by texture maps)
+    pub id: u32,
+    /// Repeat in the S (u) direction
+    pub repeat_s: bool,
+    /// Repeat in the T (v) direction
+    pub repeat_t: bool,
+    /// Texture mode (IFC4 Mode string or IFC2x3 TextureType enum)
+    pub mode: Option<String>,
+    /// Pixel source
+    pub source: TextureSource,
+}
+
+impl SurfaceTexture {
+    /// Image URL, if this is an IfcImageTexture
+    pub fn url(&self) -> Option<&str> {
+        match &self.source {
+            TextureSource::Image { url } => Some(url),
+            _ => None,
+        }
+    }
+}
+
+/// Resolved IfcSurfaceStyle
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurfaceMaterial {
+    /// IfcSurfaceStyle entity ID
+    pub style_id: u32,
+    /// Style name
+    pub name: Option<String>,
+    /// Side(s) the style applies to
+    pub side: SurfaceSide,
+    /// Base surface color (linear RGB, 0-1)
+    pub surface_color: [f32; 3],
+    /// Transparency (0 = opaque, 1 = fully transparent)
+    pub transparency: f32,
+    /// Diffuse color, already multiplied out when given as a factor
+    pub diffuse_color: Option<[f32; 3]>,
+    /// Specular color, already multiplied out when given as a factor
+    pub specular_color: Option<[f32; 3]>,
+    /// Specular exponent or roughness
+    pub specular_highlight: Option<SpecularHighlight>,
+    /// Requested lighting model
+    pub reflectance_method: ReflectanceMethod,
+    /// Textures from IfcSurfaceStyleWithTextures
+    pub textures: Vec<SurfaceTexture>,
+}
+
+impl SurfaceMaterial {
+    fn new(style_id: u32) -> Self {
+        Self {
+            style_id,
+            name: None,
+            side: SurfaceSide::Both,
+            surface_color: FALLBACK_COLOR,
+            transparency: 0.0,
+            diffuse_color: None,
+            specular_color: None,
+            specular_highlight: None,
+            reflectance_method: ReflectanceMethod::NotDefined,
+            textures: Vec::new(),
+        }
+    }
+
+    /// Display color as RGBA: diffuse color if given, else surface color,
+    /// with alpha = 1 - transparency
+    pub fn color(&self) -> [f32; 4] {
+        let [r, g, b] = self.diffuse_color.unwrap_or(self.surface_color);
+        [r, g, b, (1.0 - self.transparency).clamp(0.0, 1.0)]
+    }
+
+    /// PBR roughness approximation in [0, 1]
+    ///
+    /// Uses the specular highlight when present (exponents are mapped with
+    /// the usual Blinn-Phong ↔ GGX relation), otherwise a per-method default.
+    pub fn roughness(&self) -> f32 {
+        match self.specular_highlight {
+            Some(SpecularHighlight::Roughness(r)) => (r as f32).clamp(0.0, 1.0),
+            Some(SpecularHighlight::Exponent(e)) if e >= 0.0 => {
+                ((2.0 / (e + 2.0)).sqrt() as f32).clamp(0.0, 1.0)
+            }
+            _ => match self.reflectance_method {
+                ReflectanceMethod::Mirror | ReflectanceMethod::Glass => 0.05,
+                ReflectanceMethod::Metal => 0.3,
+                ReflectanceMethod::Plastic => 0.4,
+                ReflectanceMethod::Phong | ReflectanceMethod::Blinn => 0.5,
+                ReflectanceMethod::Strauss => 0.6,
+                ReflectanceMethod::Matt | ReflectanceMethod::Flat => 1.0,
+                ReflectanceMethod::NotDefined => 0.8,
+            },
+        }
+    }
+
+    /// PBR metallic approximation (1 for METAL/MIRROR, else 0)
+    pub fn metallic(&self) -> f32 {
+        match self.reflectance_method {
+            ReflectanceMethod::Metal | ReflectanceMethod::Mirror => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Check if the material needs alpha blending
+    #[inline]
+    pub fn is_transparent(&self) -> bool {
+        self.transparency > 0.0
+    }
+
+    /// URL of the first image texture
+    pub fn texture_url(&self) -> Option<&str> {
+        self.textures.iter().find_map(|t| t.url())
+    }
+}
+
+/// Resolve the material of an IfcStyledItem (Styles at attribute 1)
+pub fn extract_styled_item_material(
+    styled_item: &DecodedEntity,
+    decoder: &mut EntityDecoder,
+) -> Option<SurfaceMaterial> {
+    extract_material_from_styles(styled_item.get(1)?, decoder)
+}
+
+/// Resolve the first surface style in a styles attribute (list or single ref)
+///
+/// Entries may be IfcSurfaceStyle directly (IFC4) or an
+/// IfcPresentationStyleAssignment wrapping a Styles list (IFC2x3).
+pub fn extract_material_from_styles(
+    styles: &AttributeValue,
+    decoder: &mut EntityDecoder,
+) -> Option<SurfaceMaterial> {
+    let style_ids: Vec<u32> = match styles.as_list() {
+        Some(list) => list
+            .iter()
+            .filter_map(|item| item.as_entity_ref())
+            .collect(),
+        None => vec![styles.as_entity_ref()?],
+    };
+
+    for style_id in style_ids {
+        let style = match decoder.decode_by_id(style_id) {
+            Ok(style) => style,
+            Err(_) => continue,
+        };
+
+        if style.ifc_type == IfcType::IfcSurfaceStyle {
+            if let Some(material) = surface_material_from_entity(&style, decoder) {
+                return Some(material);
+            }
+            continue;
+        }
+
+        // IfcPresentationStyleAssignment (IFC2x3, decoded as Unknown): Styles at attr 0
+        let inner_ids: Vec<u32> = match style.get_list(0) {
+            Some(list) => list
+                .iter()
+                .filter_map(|item| item.as_entity_ref())
+                .collect(),
+            None => continue,
+        };
+        for inner_id in inner_ids {
+            if let Some(material) = extract_surface_material(inner_id, decoder) {
+                return Some(material);
+            }
+        }
+    }
+
+    None
+}
+
+/// Resolve an IfcSurfaceStyle by ID
+pub fn extract_surface_material(
+    style_id: u32,
+    decoder: &mut EntityDecoder,
+) -> Option<SurfaceMaterial> {
+    let style = decoder.decode_by_id(style_id).ok()?;
+    if style.ifc_type != IfcType::IfcSurfaceStyle {
+        return None;
+    }
+    surface_material_from_entity(&style, decoder)
+}
+
+fn surface_material_from_entity(
+    style: &DecodedEntity,
+    decoder: &mut EntityDecoder,
+) -> Option<SurfaceMaterial> {
+    // IfcSurfaceStyle: Name, Side, Styles
+    let mut material = SurfaceMaterial::new(style.id);
+    material.name = style.get_string(0).map(str::to_string);
+    material.side = style
+        .get(1)
+        .and_then(|a| a.as_enum())
+        .map(SurfaceSide::from_ifc)
+        .unwrap_or_default();
+
+    let element_ids: Vec<u32> = style
+        .get_list(2)?
+        .iter()
+        .filter_map(|item| item.as_entity_ref())
+        .collect();
+
+    let mut has_shading = false;
+    for element_id in element_ids {
+        let element = match decoder.decode_by_id(element_id) {
+            Ok(element) => element,
+            Err(_) => continue,
+        };
+        match element.ifc_type {
+            IfcType::IfcSurfaceStyleShading | IfcType::IfcSurfaceStyleRendering if !has_shading => {
+                has_shading = apply_shading(&mut material, &element, decoder);
+            }
+            IfcType::IfcSurfaceStyleWithTextures => {
+                // IfcSurfaceStyleWithTextures: Textures
+                let texture_ids: Vec<u32> = element
+                    .get_list(0)
+                    .map(|list| list.iter().filter_map(|t| t.as_entity_ref()).collect())
+                    .unwrap_or_default();
+                for texture_id in texture_ids {
+                    if let Some(texture) = extract_texture(texture_id, decoder) {
+                        material.textures.push(texture);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !has_shading {
+        if material.textures.is_empty() {
+            return None;
+        }
+        // Texture-only styles: show the texture unmodulated
+        material.surface_color = [1.0, 1.0, 1.0];
+    }
+
+    Some(material)
+}
+
+/// Read IfcSurfaceStyleShading / IfcSurfaceStyleRendering attributes
+///
+/// 0 SurfaceColour, 1 Transparency, 2 DiffuseColour, 3 TransmissionColour,
+/// 4 DiffuseTransmissionColour, 5 ReflectionColour, 6 SpecularColour,
+/// 7 SpecularHighlight, 8 ReflectanceMethod
+fn apply_shading(
+    material: &mut SurfaceMaterial,
+    shading: &DecodedEntity,
+    decoder: &mut EntityDecoder,
+) -> bool {
+    let Some(color_id) = shading.get_ref(0) else {
+        return false;
+    };
+    let Some(surface_color) = extract_color_rgb(color_id, decoder) else {
+        return false;
+    };
+    material.surface_color = surface_color;
+    material.transparency = shading.get_float(1).unwrap_or(0.0).clamp(0.0, 1.0) as f32;
+
+    if shading.ifc_type != IfcType::IfcSurfaceStyleRendering {
+        return true;
+    }
+
+    material.diffuse_color = shading
+        .get(2)
+        .and_then(|a| color_or_factor(a, surface_color, decoder));
+    material.specular_color = shading
+        .get(6)
+        .and_then(|a| color_or_factor(a, surface_color, decoder));
+    material.specular_highlight = shading.get(7).and_then(parse_specular_highlight);
+    material.reflectance_method = shading
+        .get(8)
+        .and_then(|a| a.as_enum())
+        .map(ReflectanceMethod::from_ifc)
+        .unwrap_or_default();
+    true
+}
+
+/// IfcColourOrFactor: an IfcColourRgb reference or a factor applied to the surface color
+fn color_or_factor(
+    attr: &AttributeValue,
+    surface_color: [f32; 3],
+    decoder: &mut EntityDecoder,
+) -> Option<[f32; 3]> {
+    if let Some(color_id) = attr.as_entity_ref() {
+        return extract_color_rgb(color_id, decoder);
+    }
+    let factor = attr.as_float()? as f32;
+    Some(surface_color.map(|c| (c * factor).clamp(0.0, 1.0)))
+}
+
+/// IfcSpecularHighlightSelect: IFCSPECULAREXPONENT(..) or IFCSPECULARROUGHNESS(..)
+fn parse_specular_highlight(attr: &AttributeValue) -> Option<SpecularHighlight> {
+    let value = attr.as_float()?;
+    let type_name = attr
+        .as_list()
+        .and_then(|items| items.first())
+        .and_then(|t| t.as_string())
+        .unwrap_or("");
+    if type_name.eq_ignore_ascii_case("IFCSPECULARROUGHNESS") {
+        Some(SpecularHighlight::Roughness(value))
+    } else {
+        Some(SpecularHighlight::Exponent(value))
+    }
+}
+
+/// Read an IfcColourRgb (Name, Red, Green, Blue)
+fn extract_color_rgb(color_id: u32, decoder: &mut EntityDecoder) -> Option<[f32; 3]> {
+    let color = decoder.decode_by_id(color_id).ok()?;
+    if color.ifc_type != IfcType::IfcColourRgb {
+        return None;
+    }
+    Some([
+        color.get_float(1).unwrap_or(0.8) as f32,
+        color.get_float(2).unwrap_or(0.8) as f32,
+        color.get_float(3).unwrap_or(0.8) as f32,
+    ])
+}
+
+/// Read an IfcImageTexture, IfcPixelTexture or IfcBlobTexture
+fn extract_texture(texture_id: u32, decoder: &mut EntityDecoder) -> Option<SurfaceTexture> {
+    let texture = decoder.decode_by_id(texture_id).ok()?;
+
+    // IfcSurfaceTexture: RepeatS, RepeatT, Mode (IFC4) / TextureType (IFC2x3),
+    // TextureTransform, Parameter (IFC4 only)
+    let flag = |index: usize| {
+        texture
+            .get(index)
+            .and_then(|a| a.as_enum())
+            .map(|v| v == "T")
+            .unwrap_or(true)
+    };
+    let mode = texture
+        .get(2)
+        .and_then(|a| a.as_string().or_else(|| a.as_enum()))
+        .map(str::to_string);
+
+    let source = match texture.ifc_type {
+        IfcType::IfcImageTexture => {
+            // URLReference: attr 5 in IFC4, attr 4 in IFC2x3 (no Parameter list)
+            let url = texture
+                .get_string(5)
+                .or_else(|| texture.get_string(4))?
+                .to_string();
+            TextureSource::Image { url }
+        }
+        IfcType::IfcPixelTexture => TextureSource::Pixel {
+            width: texture.get(5).and_then(|a| a.as_int()).unwrap_or(0) as u32,
+            height: texture.get(6).and_then(|a| a.as_int()).unwrap_or(0) as u32,
+            components: texture.get(7).and_then(|a| a.as_int()).unwrap_or(0) as u32,
+        },
+        IfcType::IfcBlobTexture => TextureSource::Blob {
+            raster_format: texture.get_string(5).unwrap_or_default().to_string(),
+        },
+        _ => return None,
+    };
+
+    Some(SurfaceTexture {
+        id: texture_id,
+        repeat_s: flag(0),
+        repeat_t: flag(1),
+        mode,
+        source,
+    })
+}
+
+/// Texture coordinates of an IfcIndexedTriangleTextureMap
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedTextureMap {
+    /// IfcIndexedTriangleTextureMap entity ID
+    pub id: u32,
+    /// IfcTriangulatedFaceSet the map applies to (MappedTo)
+    pub face_set_id: u32,
+    /// IfcSurfaceTexture IDs (Maps)
+    pub texture_ids: Vec<u32>,
+    /// Texture vertices from IfcTextureVertexList
+    pub tex_coords: Vec<[f32; 2]>,
+    /// Zero-based texture vertex indices per triangle (empty = use CoordIndex)
+    pub tex_coord_index: Vec<u32>,
+}
+
+impl IndexedTextureMap {
+    /// Read an IfcIndexedTriangleTextureMap
+    ///
+    /// Attributes: 0 Maps, 1 MappedTo, 2 TexCoords, 3 TexCoordIndex (optional)
+    pub fn from_entity(entity: &DecodedEntity, decoder: &mut EntityDecoder) -> Option<Self> {
+        if entity.ifc_type != IfcType::IfcIndexedTriangleTextureMap {
+            return None;
+        }
+
+        let face_set_id = entity.get_ref(1)?;
+        let texture_ids = entity
+            .get_list(0)
+            .map(|list| list.iter().filter_map(|t| t.as_entity_ref()).collect())
+            .unwrap_or_default();
+
+        // IfcTextureVertexList: TexCoordsList
+        let vertex_list = decoder.decode_by_id(entity.get_ref(2)?).ok()?;
+        let tex_coords = vertex_list
+            .get_list(0)?
+            .iter()
+            .filter_map(|uv| {
+                let uv = uv.as_list()?;
+                Some([
+                    uv.first()?.as_float()? as f32,
+                    uv.get(1)?.as_float()? as f32,
+                ])
+            })
+            .collect();
+
+        let tex_coord_index = entity
+            .get_list(3)
+            .map(AttributeValue::parse_index_list)
+            .unwrap_or_default();
+
+        Some(Self {
+            id: entity.id,
+            face_set_id,
+            texture_ids,
+            tex_coords,
+            tex_coord_index,
+        })
+    }
+
+    /// Give each triangle corner of a face set mesh its texture coordinate
+    ///
+    /// `mesh` must be the unscaled output of the triangulated face set
+    /// (triangles in CoordIndex order). Corners are split so vertices
+    /// shared between triangles can carry different UVs; normals are
+    /// dropped and recomputed downstream. Returns `None` if the map does
+    /// not fit the mesh.
+    pub fn apply(&self, mesh: &Mesh) -> Option<Mesh> {
+        let index_source = if self.tex_coord_index.is_empty() {
+            &mesh.indices
+        } else {
+            &self.tex_coord_index
+        };
+        if index_source.len() != mesh.indices.len() {
+            return None;
+        }
+
+        let vertex_count = mesh.vertex_count();
+        let mut result = Mesh::with_capacity(mesh.indices.len(), mesh.indices.len());
+        result.normals.clear();
+        result.uvs.reserve(mesh.indices.len() * 2);
+
+        for (&vertex, &tex_index) in mesh.indices.iter().zip(index_source) {
+            let vertex = vertex as usize;
+            if vertex >= vertex_count {
+                return None;
+            }
+            let uv = self.tex_coords.get(tex_index as usize)?;
+            result
+                .positions
+                .extend_from_slice(&mesh.positions[vertex * 3..vertex * 3 + 3]);
+            result.uvs.extend_from_slice(uv);
+            result.indices.push(result.indices.len() as u32);
+        }
+
+        Some(result)
+    }
+}
+
+/// Index every IfcIndexedTriangleTextureMap by the face set it maps to
+pub fn build_texture_map_index(
+    content: &str,
+    decoder: &mut EntityDecoder,
+) -> FxHashMap<u32, IndexedTextureMap> {
+    let mut index = FxHashMap::default();
+    let mut scanner = EntityScanner::new(content);
+
+    while let Some((id, type_name, start, end)) = scanner.next_entity() {
+        if type_name != "IFCINDEXEDTRIANGLETEXTUREMAP" {
+            continue;
+        }
+        if let Ok(entity) = decoder.decode_at_with_id(id, start, end) {
+            if let Some(map) = IndexedTextureMap::from_entity(&entity, decoder) {
+                // First map wins when several textures share a face set
+                index.entry(map.face_set_id).or_insert(map);
+            }
+        }
+    }
+
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STYLES: &str = r#"
+#1=IFCCOLOURRGB($,0.5,0.25,1.);
+#2=IFCCOLOURRGB($,1.,1.,1.);
+#3=IFCSURFACESTYLERENDERING(#1,0.25,IFCNORMALISEDRATIOMEASURE(0.5),$,$,$,#2,IFCSPECULAREXPONENT(64.),.METAL.);
+#4=IFCIMAGETEXTURE(.T.,.F.,'DIFFUSE',$,$,'brick.png');
+#5=IFCSURFACESTYLEWITHTEXTURES((#4));
+#6=IFCSURFACESTYLE('Brick',.BOTH.,(#3,#5));
+#7=IFCPRESENTATIONSTYLEASSIGNMENT((#6));
+#8=IFCSTYLEDITEM(#20,(#7),$);
+#9=IFCSURFACESTYLESHADING(#1,$);
+#10=IFCSURFACESTYLE($,.POSITIVE.,(#9));
+#11=IFCSTYLEDITEM(#20,(#10),$);
+"#;
+
+    #[test]
+    fn test_rendering_with_textures() {
+        let mut decoder = EntityDecoder::new(STYLES);
+        let styled_item = decoder.decode_by_id(8).unwrap();
+        let material = extract_styled_item_material(&styled_item, &mut decoder).unwrap();
+
+        assert_eq!(material.style_id, 6);
+        assert_eq!(material.name.as_deref(), Some("Brick"));
+        assert_eq!(material.surface_color, [0.5, 0.25, 1.0]);
+        assert_eq!(material.diffuse_color, Some([0.25, 0.125, 0.5]));
+        assert_eq!(material.specular_color, Some([1.0, 1.0, 1.0]));
+        assert_eq!(
+            material.specular_highlight,
+            Some(SpecularHighlight::Exponent(64.0))
+        );
+        assert_eq!(material.reflectance_method, ReflectanceMethod::Metal);
+        assert_eq!(material.color(), [0.25, 0.125, 0.5, 0.75]);
+        assert_eq!(material.metallic(), 1.0);
+        assert!(material.roughness() < 0.2);
+
+        assert_eq!(material.textures.len(), 1);
+        let texture = &material.textures[0];
+        assert!(texture.repeat_s && !texture.repeat_t);
+        assert_eq!(texture.mode.as_deref(), Some("DIFFUSE"));
+        assert_eq!(material.texture_url(), Some("brick.png"));
+    }
+
+    #[test]
+    fn test_shading_only() {
+        let mut decoder = EntityDecoder::new(STYLES);
+        let styled_item = decoder.decode_by_id(11).unwrap();
+        let material = extract_styled_item_material(&styled_item, &mut decoder).unwrap();
+
+        assert_eq!(material.side, SurfaceSide::Positive);
+        assert_eq!(material.color(), [0.5, 0.25, 1.0, 1.0]);
+        assert!(material.diffuse_color.is_none());
+        assert!(!material.is_transparent());
+        assert!(material.textures.is_empty());
+    }
+
+    #[test]
+    fn test_indexed_triangle_texture_map() {
+        let content = r#"
+#1=IFCCARTESIANPOINTLIST3D(((0.,0.,0.),(1.,0.,0.),(1.,1.,0.),(0.,1.,0.)));
+#2=IFCTRIANGULATEDFACESET(#1,$,$,((1,2,3),(1,3,4)),$);
+#3=IFCTEXTUREVERTEXLIST(((0.,0.),(1.,0.),(1.,1.),(0.,1.)));
+#4=IFCIMAGETEXTURE(.T.,.T.,$,$,$,'tile.png');
+#5=IFCINDEXEDTRIANGLETEXTUREMAP((#4),#2,#3,((1,2,3),(1,3,4)));
+"#;
+        let mut decoder = EntityDecoder::new(content);
+        let maps = build_texture_map_index(content, &mut decoder);
+        let map = maps.get(&2).unwrap();
+        assert_eq!(map.texture_ids, vec![4]);
+        assert_eq!(map.tex_coords.len(), 4);
+
+        let mut mesh = Mesh::new();
+        mesh.positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+        mesh.indices = vec![0, 1, 2, 0, 2, 3];
+
+        let textured = map.apply(&mesh).unwrap();
+        assert_eq!(textured.vertex_count(), 6);
+        assert!(textured.has_uvs());
+        assert_eq!(&textured.uvs[4..6], &[1.0, 1.0]);
+        assert_eq!(&textured.positions[6..9], &[1.0, 1.0, 0.0]);
+
+        // Welding merges shared corners again since their UVs agree
+        let mut welded = textured.clone();
+        welded.weld(crate::DEFAULT_WELD_TOLERANCE);
+        assert_eq!(welded.vertex_count(), 4);
+        assert!(welded.has_uvs());
+    }
+}
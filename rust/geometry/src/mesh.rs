@@ -5,6 +5,10 @@
 //! Mesh data structures
 
 use nalgebra::{Point3, Vector3};
+use rustc_hash::FxHashMap;
+
+/// Default tolerance for [`Mesh::weld`] in meters (0.01mm)
+pub const DEFAULT_WELD_TOLERANCE: f32 = 1e-5;
 
 /// Coordinate shift for RTC (Relative-to-Center) rendering
 /// Stores the offset subtracted from coordinates to improve Float32 precision
@@ -58,6 +62,8 @@ pub struct Mesh {
     pub normals: Vec<f32>,
     /// Triangle indices (i0, i1, i2)
     pub indices: Vec<u32>,
+    /// Texture coordinates (u, v), empty for untextured meshes
+    pub uvs: Vec<f32>,
 }
 
 /// A sub-mesh with its source geometry item ID.
@@ -65,16 +71,26 @@ pub struct Mesh {
 /// allowing per-item color/style lookup.
 #[derive(Debug, Clone)]
 pub struct SubMesh {
-    /// The geometry item ID (e.g., IfcFacetedBrep ID) for style lookup
+    /// The geometry item ID (e.g., IfcFacetedBrep ID) for style lookup,
+    /// or the IfcMaterialLayer ID for per-layer sub-meshes
     pub geometry_id: u32,
     /// The triangulated mesh data
     pub mesh: Mesh,
+    /// Position within the material layer set, for per-layer sub-meshes
+    pub layer_index: Option<usize>,
+    /// IfcMaterial of the layer, for per-layer sub-meshes
+    pub material_id: Option<u32>,
 }
 
 impl SubMesh {
     /// Create a new sub-mesh
     pub fn new(geometry_id: u32, mesh: Mesh) -> Self {
-        Self { geometry_id, mesh }
+        Self {
+            geometry_id,
+            mesh,
+            layer_index: None,
+            material_id: None,
+        }
     }
 }
 
@@ -97,6 +113,24 @@ impl SubMeshCollection {
         }
     }
 
+    /// Add the sub-mesh of one material layer
+    pub fn add_layer(
+        &mut self,
+        layer_id: u32,
+        layer_index: usize,
+        material_id: Option<u32>,
+        mesh: Mesh,
+    ) {
+        if !mesh.is_empty() {
+            self.sub_meshes.push(SubMesh {
+                geometry_id: layer_id,
+                mesh,
+                layer_index: Some(layer_index),
+                material_id,
+            });
+        }
+    }
+
     /// Check if collection is empty
     pub fn is_empty(&self) -> bool {
         self.sub_meshes.is_empty()
@@ -129,6 +163,7 @@ impl Mesh {
             positions: Vec::new(),
             normals: Vec::new(),
             indices: Vec::new(),
+            uvs: Vec::new(),
         }
     }
 
@@ -138,6 +173,7 @@ impl Mesh {
             positions: Vec::with_capacity(vertex_count * 3),
             normals: Vec::with_capacity(vertex_count * 3),
             indices: Vec::with_capacity(index_count),
+            uvs: Vec::new(),
         }
     }
 
@@ -224,6 +260,7 @@ impl Mesh {
         self.normals.reserve(other.normals.len());
         self.indices.reserve(other.indices.len());
 
+        self.merge_uvs(other);
         self.positions.extend_from_slice(&other.positions);
         self.normals.extend_from_slice(&other.normals);
 
@@ -248,6 +285,7 @@ impl Mesh {
         for mesh in meshes {
             if !mesh.is_empty() {
                 let vertex_offset = (self.positions.len() / 3) as u32;
+                self.merge_uvs(mesh);
                 self.positions.extend_from_slice(&mesh.positions);
                 self.normals.extend_from_slice(&mesh.normals);
                 self.indices
@@ -256,6 +294,27 @@ impl Mesh {
         }
     }
 
+    /// Append `other`'s texture coordinates, padding with zeros when only
+    /// one side is textured (must run before positions are extended)
+    fn merge_uvs(&mut self, other: &Mesh) {
+        if self.uvs.is_empty() && !other.has_uvs() {
+            return;
+        }
+        let self_vertices = self.positions.len() / 3;
+        self.uvs.resize(self_vertices * 2, 0.0);
+        if other.has_uvs() {
+            self.uvs.extend_from_slice(&other.uvs);
+        } else {
+            self.uvs.resize((self_vertices + other.vertex_count()) * 2, 0.0);
+        }
+    }
+
+    /// Check if every vertex has a texture coordinate
+    #[inline]
+    pub fn has_uvs(&self) -> bool {
+        !self.uvs.is_empty() && self.uvs.len() / 2 == self.positions.len() / 3
+    }
+
     /// Get vertex count
     #[inline]
     pub fn vertex_count(&self) -> usize {
@@ -322,12 +381,128 @@ impl Mesh {
         )
     }
 
+    /// Merge coincident vertices and drop triangles that collapse as a result
+    ///
+    /// Vertices are snapped to a grid with cell size `tolerance` and every
+    /// vertex falling into the same cell is merged into the first one seen.
+    /// Normals of merged vertices are averaged, so sharp edges become smooth;
+    /// call [`crate::calculate_crease_normals`] afterwards to restore them.
+    ///
+    /// Returns the number of vertices removed.
+    pub fn weld(&mut self, tolerance: f32) -> usize {
+        let vertex_count = self.vertex_count();
+        if vertex_count == 0 {
+            return 0;
+        }
+
+        let inv_cell = if tolerance > 0.0 {
+            1.0 / tolerance as f64
+        } else {
+            1.0 / DEFAULT_WELD_TOLERANCE as f64
+        };
+        let has_normals = self.normals.len() == self.positions.len();
+        let has_uvs = self.has_uvs();
+
+        // Texture coordinates are part of the key so UV seams stay split
+        let mut cells: FxHashMap<(i64, i64, i64, u32, u32), u32> = FxHashMap::default();
+        cells.reserve(vertex_count);
+        let mut remap: Vec<u32> = Vec::with_capacity(vertex_count);
+        let mut positions: Vec<f32> = Vec::with_capacity(self.positions.len());
+        let mut uvs: Vec<f32> = Vec::new();
+        let mut normal_sums: Vec<Vector3<f64>> = Vec::with_capacity(vertex_count);
+
+        for (i, chunk) in self.positions.chunks_exact(3).enumerate() {
+            let uv = if has_uvs {
+                [self.uvs[i * 2], self.uvs[i * 2 + 1]]
+            } else {
+                [0.0, 0.0]
+            };
+            let key = (
+                (chunk[0] as f64 * inv_cell).round() as i64,
+                (chunk[1] as f64 * inv_cell).round() as i64,
+                (chunk[2] as f64 * inv_cell).round() as i64,
+                uv[0].to_bits(),
+                uv[1].to_bits(),
+            );
+            let normal = if has_normals {
+                Vector3::new(
+                    self.normals[i * 3] as f64,
+                    self.normals[i * 3 + 1] as f64,
+                    self.normals[i * 3 + 2] as f64,
+                )
+            } else {
+                Vector3::zeros()
+            };
+
+            let index = *cells.entry(key).or_insert_with(|| {
+                positions.extend_from_slice(chunk);
+                if has_uvs {
+                    uvs.extend_from_slice(&uv);
+                }
+                normal_sums.push(Vector3::zeros());
+                (normal_sums.len() - 1) as u32
+            });
+            normal_sums[index as usize] += normal;
+            remap.push(index);
+        }
+
+        let mut indices = Vec::with_capacity(self.indices.len());
+        for tri in self.indices.chunks_exact(3) {
+            let (Some(&a), Some(&b), Some(&c)) = (
+                remap.get(tri[0] as usize),
+                remap.get(tri[1] as usize),
+                remap.get(tri[2] as usize),
+            ) else {
+                continue;
+            };
+            // Skip triangles that collapsed to a line or point
+            if a != b && b != c && a != c {
+                indices.extend_from_slice(&[a, b, c]);
+            }
+        }
+
+        let mut normals = Vec::with_capacity(positions.len());
+        for sum in normal_sums {
+            let n = sum
+                .try_normalize(1e-12)
+                .unwrap_or_else(|| Vector3::new(0.0, 0.0, 1.0));
+            normals.extend_from_slice(&[n.x as f32, n.y as f32, n.z as f32]);
+        }
+
+        let removed = vertex_count - positions.len() / 3;
+        self.positions = positions;
+        self.normals = normals;
+        self.indices = indices;
+        self.uvs = uvs;
+        removed
+    }
+
+    /// Check the mesh for open edges, non-manifold edges, degenerate
+    /// triangles and inconsistent winding
+    #[inline]
+    pub fn validate(&self) -> crate::validation::MeshValidationReport {
+        crate::validation::validate_mesh(self)
+    }
+
+    /// Check if the mesh is a closed, manifold, consistently wound solid
+    #[inline]
+    pub fn is_solid(&self) -> bool {
+        self.validate().is_solid
+    }
+
+    /// Compute a near-minimal oriented bounding box (`None` if empty)
+    #[inline]
+    pub fn oriented_bounding_box(&self) -> Option<crate::obb::OrientedBoundingBox> {
+        crate::obb::OrientedBoundingBox::from_positions(&self.positions)
+    }
+
     /// Clear the mesh
     #[inline]
     pub fn clear(&mut self) {
         self.positions.clear();
         self.normals.clear();
         self.indices.clear();
+        self.uvs.clear();
     }
 }
 
@@ -373,6 +548,37 @@ mod tests {
         assert_eq!(mesh1.triangle_count(), 2);
     }
 
+    #[test]
+    fn test_weld_merges_coincident_vertices() {
+        // Two triangles forming a quad with split (duplicated) vertices
+        let mut mesh = Mesh::new();
+        mesh.positions = vec![
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, // tri 1
+            0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0, // tri 2
+        ];
+        mesh.normals = [0.0, 0.0, 1.0].repeat(6);
+        mesh.indices = vec![0, 1, 2, 3, 4, 5];
+
+        let removed = mesh.weld(DEFAULT_WELD_TOLERANCE);
+        assert_eq!(removed, 2);
+        assert_eq!(mesh.vertex_count(), 4);
+        assert_eq!(mesh.triangle_count(), 2);
+        assert_eq!(mesh.indices, vec![0, 1, 2, 0, 2, 3]);
+        assert_eq!(&mesh.normals[0..3], &[0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_weld_drops_collapsed_triangles() {
+        let mut mesh = Mesh::new();
+        mesh.positions = vec![0.0, 0.0, 0.0, 0.000001, 0.0, 0.0, 1.0, 1.0, 0.0];
+        mesh.normals = vec![0.0; 9];
+        mesh.indices = vec![0, 1, 2];
+
+        mesh.weld(0.001);
+        assert_eq!(mesh.vertex_count(), 2);
+        assert_eq!(mesh.triangle_count(), 0);
+    }
+
     #[test]
     fn test_coordinate_shift_creation() {
         let shift = CoordinateShift::new(500000.0, 5000000.0, 100.0);
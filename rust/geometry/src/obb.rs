@@ -0,0 +1,363 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Oriented Bounding Boxes
+//!
+//! Tight, arbitrarily rotated boxes around element geometry, used for clash
+//! pre-filtering, label placement and camera framing on rotated buildings
+//! where axis-aligned boxes are far too loose.
+//!
+//! The initial frame comes from principal component analysis of the points.
+//! It is then refined by fixing each candidate axis in turn (PCA axes and the
+//! world axes) and finding the minimum-area rectangle of the projected convex
+//! hull with rotating calipers. The candidate with the smallest volume wins.
+
+use nalgebra::{
+    Matrix3, Point2, Point3, Rotation3, SymmetricEigen, UnitQuaternion, Vector2, Vector3,
+};
+
+/// Bounding box with arbitrary orientation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrientedBoundingBox {
+    /// Box center in world coordinates
+    pub center: Point3<f64>,
+    /// Orthonormal, right-handed box axes, ordered by descending extent
+    pub axes: [Vector3<f64>; 3],
+    /// Half the box size along each axis
+    pub half_extents: Vector3<f64>,
+}
+
+impl OrientedBoundingBox {
+    /// Compute a near-minimal oriented box around a set of points
+    ///
+    /// Returns `None` for an empty point set.
+    pub fn from_points(points: &[Point3<f64>]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+
+        let mut candidates: Vec<[Vector3<f64>; 3]> = Vec::with_capacity(8);
+        let world = [Vector3::x(), Vector3::y(), Vector3::z()];
+        candidates.push(world);
+
+        let pca = principal_axes(points);
+        if let Some(pca) = pca {
+            candidates.push(pca);
+        }
+
+        // Refine: keep one axis fixed, fit the tightest rectangle around the rest
+        let mut fixed_axes: Vec<Vector3<f64>> = world.to_vec();
+        if let Some(pca) = pca {
+            fixed_axes.extend_from_slice(&pca);
+        }
+        for axis in fixed_axes {
+            if let Some(frame) = min_area_frame(points, &axis) {
+                candidates.push(frame);
+            }
+        }
+
+        candidates
+            .iter()
+            .map(|frame| fit_to_frame(points, frame))
+            // Surface area breaks ties between flat (zero-volume) candidates
+            .min_by(|a, b| {
+                a.volume()
+                    .total_cmp(&b.volume())
+                    .then(a.surface_area().total_cmp(&b.surface_area()))
+            })
+            .map(|obb| obb.normalized())
+    }
+
+    /// Compute an oriented box from interleaved xyz positions
+    pub fn from_positions(positions: &[f32]) -> Option<Self> {
+        let points: Vec<Point3<f64>> = positions
+            .chunks_exact(3)
+            .map(|p| Point3::new(p[0] as f64, p[1] as f64, p[2] as f64))
+            .collect();
+        Self::from_points(&points)
+    }
+
+    /// Box volume
+    #[inline]
+    pub fn volume(&self) -> f64 {
+        8.0 * self.half_extents.x * self.half_extents.y * self.half_extents.z
+    }
+
+    /// Box surface area
+    #[inline]
+    pub fn surface_area(&self) -> f64 {
+        let s = self.size();
+        2.0 * (s.x * s.y + s.y * s.z + s.z * s.x)
+    }
+
+    /// Full box size along each axis
+    #[inline]
+    pub fn size(&self) -> Vector3<f64> {
+        self.half_extents * 2.0
+    }
+
+    /// Rotation from box-local to world coordinates
+    pub fn rotation(&self) -> UnitQuaternion<f64> {
+        let matrix = Matrix3::from_columns(&self.axes);
+        UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(matrix))
+    }
+
+    /// The eight corners of the box
+    pub fn corners(&self) -> [Point3<f64>; 8] {
+        let [a, b, c] = self.axes;
+        let h = self.half_extents;
+        let mut corners = [self.center; 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let sx = if i & 1 == 0 { -h.x } else { h.x };
+            let sy = if i & 2 == 0 { -h.y } else { h.y };
+            let sz = if i & 4 == 0 { -h.z } else { h.z };
+            *corner += a * sx + b * sy + c * sz;
+        }
+        corners
+    }
+
+    /// Check if a point lies inside the box (with tolerance)
+    pub fn contains_point(&self, point: &Point3<f64>, tolerance: f64) -> bool {
+        let d = point - self.center;
+        (0..3).all(|i| d.dot(&self.axes[i]).abs() <= self.half_extents[i] + tolerance)
+    }
+
+    /// Sort axes by descending extent and make the frame right-handed
+    fn normalized(mut self) -> Self {
+        let mut order = [0usize, 1, 2];
+        order.sort_by(|&i, &j| self.half_extents[j].total_cmp(&self.half_extents[i]));
+        let axes = [
+            self.axes[order[0]],
+            self.axes[order[1]],
+            self.axes[order[2]],
+        ];
+        self.half_extents = Vector3::new(
+            self.half_extents[order[0]],
+            self.half_extents[order[1]],
+            self.half_extents[order[2]],
+        );
+        // Flipping an axis does not change the box, only its handedness
+        self.axes = [axes[0], axes[1], axes[0].cross(&axes[1])];
+        self
+    }
+}
+
+/// Eigenvectors of the point covariance matrix
+fn principal_axes(points: &[Point3<f64>]) -> Option<[Vector3<f64>; 3]> {
+    if points.len() < 3 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean = points
+        .iter()
+        .fold(Vector3::zeros(), |acc, p| acc + p.coords)
+        / n;
+    let mut covariance = Matrix3::zeros();
+    for p in points {
+        let d = p.coords - mean;
+        covariance += d * d.transpose();
+    }
+    covariance /= n;
+
+    let eigen = SymmetricEigen::new(covariance);
+    let a = eigen.eigenvectors.column(0).into_owned();
+    let b = eigen.eigenvectors.column(1).into_owned();
+    if a.norm() < 1e-9 || b.norm() < 1e-9 {
+        return None;
+    }
+    let a = a.normalize();
+    let b = (b - a * a.dot(&b)).try_normalize(1e-9)?;
+    Some([a, b, a.cross(&b)])
+}
+
+/// Frame whose third axis is `axis` and whose first two axes bound the
+/// projected points with a minimum-area rectangle
+fn min_area_frame(points: &[Point3<f64>], axis: &Vector3<f64>) -> Option<[Vector3<f64>; 3]> {
+    let w = axis.try_normalize(1e-9)?;
+    let helper = if w.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let u = w.cross(&helper).normalize();
+    let v = w.cross(&u);
+
+    let projected: Vec<Point2<f64>> = points
+        .iter()
+        .map(|p| Point2::new(p.coords.dot(&u), p.coords.dot(&v)))
+        .collect();
+    let hull = convex_hull(projected);
+
+    let direction = match hull.len() {
+        0 | 1 => return Some([u, v, w]),
+        2 => hull[1] - hull[0],
+        _ => min_area_rectangle_direction(&hull),
+    };
+    let direction = direction.try_normalize(1e-12)?;
+
+    let a = u * direction.x + v * direction.y;
+    let b = w.cross(&a);
+    Some([a, b, w])
+}
+
+/// Edge direction of the minimum-area enclosing rectangle (rotating calipers)
+///
+/// The optimal rectangle has one side collinear with a hull edge, so each
+/// edge direction is tried and the smallest area kept.
+fn min_area_rectangle_direction(hull: &[Point2<f64>]) -> Vector2<f64> {
+    let n = hull.len();
+    let mut best_area = f64::INFINITY;
+    let mut best_dir = Vector2::x();
+
+    for i in 0..n {
+        let edge = hull[(i + 1) % n] - hull[i];
+        let Some(dir) = edge.try_normalize(1e-12) else {
+            continue;
+        };
+        let perp = Vector2::new(-dir.y, dir.x);
+
+        let (mut min_a, mut max_a) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut min_b, mut max_b) = (f64::INFINITY, f64::NEG_INFINITY);
+        for p in hull {
+            let a = p.coords.dot(&dir);
+            let b = p.coords.dot(&perp);
+            min_a = min_a.min(a);
+            max_a = max_a.max(a);
+            min_b = min_b.min(b);
+            max_b = max_b.max(b);
+        }
+
+        let area = (max_a - min_a) * (max_b - min_b);
+        if area < best_area {
+            best_area = area;
+            best_dir = dir;
+        }
+    }
+
+    best_dir
+}
+
+/// Convex hull (Andrew's monotone chain), counter-clockwise without repeated endpoint
+fn convex_hull(mut points: Vec<Point2<f64>>) -> Vec<Point2<f64>> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup_by(|a, b| (a.x - b.x).abs() < 1e-12 && (a.y - b.y).abs() < 1e-12);
+    if points.len() < 3 {
+        return points;
+    }
+
+    let cross = |o: &Point2<f64>, a: &Point2<f64>, b: &Point2<f64>| {
+        (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+    };
+
+    let mut hull: Vec<Point2<f64>> = Vec::with_capacity(points.len() * 2);
+    for p in points.iter() {
+        while hull.len() >= 2 && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+    let lower_len = hull.len() + 1;
+    for p in points.iter().rev().skip(1) {
+        while hull.len() >= lower_len
+            && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(*p);
+    }
+    hull.pop();
+    hull
+}
+
+/// Tightest box around the points in a given orthonormal frame
+fn fit_to_frame(points: &[Point3<f64>], frame: &[Vector3<f64>; 3]) -> OrientedBoundingBox {
+    let mut min = Vector3::repeat(f64::INFINITY);
+    let mut max = Vector3::repeat(f64::NEG_INFINITY);
+    for p in points {
+        for i in 0..3 {
+            let d = p.coords.dot(&frame[i]);
+            min[i] = min[i].min(d);
+            max[i] = max[i].max(d);
+        }
+    }
+    let mid = (min + max) * 0.5;
+    OrientedBoundingBox {
+        center: Point3::from(frame[0] * mid.x + frame[1] * mid.y + frame[2] * mid.z),
+        axes: *frame,
+        half_extents: (max - min) * 0.5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Corners of a box with the given size, rotated about Z and translated
+    fn rotated_box(size: Vector3<f64>, angle: f64, offset: Vector3<f64>) -> Vec<Point3<f64>> {
+        let rotation = Rotation3::from_axis_angle(&Vector3::z_axis(), angle);
+        let mut points = Vec::new();
+        for i in 0..8 {
+            let local = Vector3::new(
+                if i & 1 == 0 { 0.0 } else { size.x },
+                if i & 2 == 0 { 0.0 } else { size.y },
+                if i & 4 == 0 { 0.0 } else { size.z },
+            );
+            points.push(Point3::from(rotation * local + offset));
+        }
+        points
+    }
+
+    #[test]
+    fn test_obb_of_rotated_box() {
+        let points = rotated_box(
+            Vector3::new(10.0, 0.3, 3.0),
+            0.4,
+            Vector3::new(100.0, 50.0, 0.0),
+        );
+        let obb = OrientedBoundingBox::from_points(&points).unwrap();
+
+        assert!((obb.volume() - 9.0).abs() < 1e-6);
+        assert!((obb.size().x - 10.0).abs() < 1e-6);
+        assert!((obb.size().y - 3.0).abs() < 1e-6);
+        assert!((obb.size().z - 0.3).abs() < 1e-6);
+        for p in &points {
+            assert!(obb.contains_point(p, 1e-6));
+        }
+        // Longest axis follows the rotated wall direction
+        assert!((obb.axes[0].x.abs() - 0.4f64.cos()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_obb_frame_is_right_handed() {
+        let points = rotated_box(Vector3::new(2.0, 1.0, 4.0), 1.1, Vector3::zeros());
+        let obb = OrientedBoundingBox::from_points(&points).unwrap();
+        let [a, b, c] = obb.axes;
+        assert!((a.cross(&b) - c).norm() < 1e-9);
+
+        // Rotation maps local X onto the first axis
+        let mapped = obb.rotation() * Vector3::x();
+        assert!((mapped - a).norm() < 1e-9);
+
+        let corners = obb.corners();
+        for p in &points {
+            assert!(corners.iter().any(|c| (c - p).norm() < 1e-6));
+        }
+    }
+
+    #[test]
+    fn test_obb_degenerate_inputs() {
+        assert!(OrientedBoundingBox::from_points(&[]).is_none());
+
+        let single = OrientedBoundingBox::from_points(&[Point3::new(1.0, 2.0, 3.0)]).unwrap();
+        assert_eq!(single.volume(), 0.0);
+        assert_eq!(single.center, Point3::new(1.0, 2.0, 3.0));
+
+        // Flat quad: zero thickness, correct footprint
+        let flat = rotated_box(Vector3::new(2.0, 1.0, 0.0), 0.3, Vector3::zeros());
+        let obb = OrientedBoundingBox::from_points(&flat).unwrap();
+        assert!((obb.size().x - 2.0).abs() < 1e-6);
+        assert!((obb.size().y - 1.0).abs() < 1e-6);
+        assert!(obb.size().z.abs() < 1e-9);
+    }
+}
@@ -0,0 +1,713 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Plan Cuts
+//!
+//! Horizontal (or arbitrary) sections through element meshes for quick floor
+//! plans. Elements crossing the cut plane give closed 2D polygons for the cut
+//! fill; elements below the plane, within the view depth, give projected
+//! feature edges. Coordinates are in the plane's 2D frame, which for a
+//! horizontal cut is plain plan X/Y.
+//!
+//! Elevations use a vertical plane outside the model, so everything is
+//! projected and nothing is cut. Projected lines are not hidden-line processed.
+//!
+//! ```rust,ignore
+//! // Storey at 3.0 m, cut at 1.2 m above it
+//! let cutter = PlanCutter::at_elevation(4.2).with_view_depth(1.2);
+//! let plan = cutter.cut(elements.iter().map(|e| (e.id, e.ifc_type, &e.mesh)))?;
+//! for element in &plan.cut {
+//!     svg.fill(element.express_id, &element.polygons);
+//! }
+//! ```
+
+use crate::bool2d::{
+    compute_signed_area, ensure_ccw, ensure_cw, point_in_contour, simplify_contour,
+};
+use crate::csg::{ClippingProcessor, Plane};
+use crate::error::Result;
+use crate::mesh::Mesh;
+use crate::profile::Profile2D;
+use crate::router::GeometryRouter;
+use ifc_lite_core::{EntityDecoder, EntityScanner, IfcType};
+use nalgebra::{Point2, Point3, Vector3};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Default distance below the cut plane that is still drawn (meters)
+pub const DEFAULT_VIEW_DEPTH: f64 = 1.2;
+
+/// Default cut height above the storey elevation (meters)
+pub const DEFAULT_CUT_HEIGHT: f64 = 1.2;
+
+/// Check if elements of this type belong in a drawing
+///
+/// Openings, spaces and virtual elements would paint over the building fabric.
+pub fn is_drawn(ifc_type: &IfcType) -> bool {
+    !matches!(
+        ifc_type,
+        IfcType::IfcOpeningElement | IfcType::IfcSpace | IfcType::IfcVirtualElement
+    )
+}
+
+/// Facade an elevation looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElevationDirection {
+    /// North facade (+Y side), viewed looking south
+    North,
+    /// South facade (-Y side), viewed looking north
+    South,
+    /// East facade (+X side), viewed looking west
+    East,
+    /// West facade (-X side), viewed looking east
+    West,
+}
+
+impl ElevationDirection {
+    /// Parse `north`, `south`, `east` or `west`, case-insensitive
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "north" | "n" => Some(Self::North),
+            "south" | "s" => Some(Self::South),
+            "east" | "e" => Some(Self::East),
+            "west" | "w" => Some(Self::West),
+            _ => None,
+        }
+    }
+
+    /// Outward normal of the facade
+    pub fn normal(&self) -> Vector3<f64> {
+        match self {
+            Self::North => Vector3::new(0.0, 1.0, 0.0),
+            Self::South => Vector3::new(0.0, -1.0, 0.0),
+            Self::East => Vector3::new(1.0, 0.0, 0.0),
+            Self::West => Vector3::new(-1.0, 0.0, 0.0),
+        }
+    }
+}
+
+/// Cut fill of one element
+#[derive(Debug, Clone)]
+pub struct CutElement {
+    /// Express ID of the element
+    pub express_id: u32,
+    /// IFC type of the element
+    pub ifc_type: IfcType,
+    /// Closed section polygons (outer counter-clockwise, holes clockwise)
+    pub polygons: Vec<Profile2D>,
+}
+
+/// Projected outline of one element below the cut plane
+#[derive(Debug, Clone)]
+pub struct ProjectedElement {
+    /// Express ID of the element
+    pub express_id: u32,
+    /// IFC type of the element
+    pub ifc_type: IfcType,
+    /// Projected feature edges
+    pub lines: Vec<[Point2<f64>; 2]>,
+}
+
+/// Result of cutting a set of elements
+#[derive(Debug, Clone, Default)]
+pub struct PlanCut {
+    /// Elements intersecting the cut plane
+    pub cut: Vec<CutElement>,
+    /// Elements below the cut plane within the view depth
+    pub projected: Vec<ProjectedElement>,
+}
+
+impl PlanCut {
+    /// Check if nothing was cut or projected
+    pub fn is_empty(&self) -> bool {
+        self.cut.is_empty() && self.projected.is_empty()
+    }
+
+    /// 2D bounding box of all polygons and lines
+    pub fn bounds(&self) -> Option<(Point2<f64>, Point2<f64>)> {
+        let cut_points = self.cut.iter().flat_map(|e| {
+            e.polygons
+                .iter()
+                .flat_map(|p| p.outer.iter().chain(p.holes.iter().flatten()))
+        });
+        let line_points = self.projected.iter().flat_map(|e| e.lines.iter().flatten());
+
+        cut_points.chain(line_points).fold(None, |bounds, p| {
+            let (min, max) = bounds.unwrap_or((*p, *p));
+            Some((
+                Point2::new(min.x.min(p.x), min.y.min(p.y)),
+                Point2::new(max.x.max(p.x), max.y.max(p.y)),
+            ))
+        })
+    }
+}
+
+/// Cuts meshes with a plane, looking against the plane normal
+#[derive(Debug, Clone)]
+pub struct PlanCutter {
+    plane: Plane,
+    /// In-plane X axis
+    u: Vector3<f64>,
+    /// In-plane Y axis
+    v: Vector3<f64>,
+    view_depth: f64,
+    /// Cosine of the angle above which shared edges are drawn
+    crease_cos: f64,
+    /// Snapping distance for joining section segments
+    tolerance: f64,
+}
+
+impl PlanCutter {
+    /// Create a cutter for an arbitrary plane
+    ///
+    /// Elements behind the plane (against the normal) are projected.
+    pub fn new(plane: Plane) -> Self {
+        let n = plane.normal;
+        // Horizontal cuts keep plan X/Y; vertical sections get a horizontal X and upward Y
+        let u = if n.z.abs() > 0.999 {
+            Vector3::new(1.0, 0.0, 0.0)
+        } else {
+            Vector3::z().cross(&n).normalize()
+        };
+        let v = n.cross(&u);
+        Self {
+            plane,
+            u,
+            v,
+            view_depth: DEFAULT_VIEW_DEPTH,
+            crease_cos: 30f64.to_radians().cos(),
+            tolerance: 1e-6,
+        }
+    }
+
+    /// Horizontal cut at an absolute elevation, looking down
+    pub fn at_elevation(elevation: f64) -> Self {
+        Self::new(Plane::new(
+            Point3::new(0.0, 0.0, elevation),
+            Vector3::new(0.0, 0.0, 1.0),
+        ))
+    }
+
+    /// Elevation of a model within the given bounds, projecting everything
+    pub fn elevation(direction: ElevationDirection, min: Point3<f64>, max: Point3<f64>) -> Self {
+        const MARGIN: f64 = 1.0;
+        let normal = direction.normal();
+        let center = nalgebra::center(&min, &max);
+        let half_depth = (max - min).abs().dot(&normal.abs()) / 2.0;
+        let point = center + normal * (half_depth + MARGIN);
+        Self::new(Plane::new(point, normal)).with_view_depth(2.0 * (half_depth + MARGIN))
+    }
+
+    /// Set how far below the cut plane elements are projected
+    pub fn with_view_depth(mut self, view_depth: f64) -> Self {
+        self.view_depth = view_depth.max(0.0);
+        self
+    }
+
+    /// Cut plane
+    pub fn plane(&self) -> &Plane {
+        &self.plane
+    }
+
+    /// View depth below the cut plane
+    pub fn view_depth(&self) -> f64 {
+        self.view_depth
+    }
+
+    /// Project a 3D point into the plane's 2D frame
+    #[inline]
+    pub fn to_2d(&self, point: &Point3<f64>) -> Point2<f64> {
+        let d = point - self.plane.point;
+        Point2::new(d.dot(&self.u), d.dot(&self.v))
+    }
+
+    /// Cut and project elements given as (express id, type, mesh)
+    ///
+    /// Elements above the plane or deeper than the view depth are skipped.
+    pub fn cut<'a, I>(&self, elements: I) -> Result<PlanCut>
+    where
+        I: IntoIterator<Item = (u32, IfcType, &'a Mesh)>,
+    {
+        let mut plan = PlanCut::default();
+
+        for (express_id, ifc_type, mesh) in elements {
+            let Some((min, max)) = self.distance_range(mesh) else {
+                continue;
+            };
+            if min >= 0.0 || max < -self.view_depth {
+                continue;
+            }
+
+            if max >= 0.0 {
+                let polygons = self.section_polygons(mesh);
+                if !polygons.is_empty() {
+                    plan.cut.push(CutElement {
+                        express_id,
+                        ifc_type,
+                        polygons,
+                    });
+                    continue;
+                }
+            }
+
+            let lines = self.projected_lines(mesh)?;
+            if !lines.is_empty() {
+                plan.projected.push(ProjectedElement {
+                    express_id,
+                    ifc_type,
+                    lines,
+                });
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Closed section polygons of a mesh
+    ///
+    /// Open chains from non-watertight meshes are dropped. Loops nested an odd
+    /// number of times become holes of the loop around them.
+    pub fn section_polygons(&self, mesh: &Mesh) -> Vec<Profile2D> {
+        let mut segments = Vec::new();
+
+        for tri in mesh.indices.chunks_exact(3) {
+            let p = [
+                vertex(mesh, tri[0]),
+                vertex(mesh, tri[1]),
+                vertex(mesh, tri[2]),
+            ];
+            let d = p.map(|point| self.plane.signed_distance(&point));
+
+            let mut crossing = [Point2::origin(); 2];
+            let mut count = 0;
+            for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+                // Same rule as Plane::is_front, so shared edges agree
+                if (d[a] >= 0.0) != (d[b] >= 0.0) && count < 2 {
+                    crossing[count] = self.to_2d(&edge_crossing(p[a], d[a], p[b], d[b]));
+                    count += 1;
+                }
+            }
+            if count == 2 && (crossing[0] - crossing[1]).norm() > self.tolerance {
+                segments.push(crossing);
+            }
+        }
+
+        let loops = self.join_segments(&segments);
+        nest_loops(loops)
+    }
+
+    /// Feature edges of the part of a mesh between the cut plane and the view depth,
+    /// projected onto the plane
+    pub fn projected_lines(&self, mesh: &Mesh) -> Result<Vec<[Point2<f64>; 2]>> {
+        let clipper = ClippingProcessor::new();
+        let below = clipper.clip_mesh(mesh, &Plane::new(self.plane.point, -self.plane.normal))?;
+        let bottom = Plane::new(
+            self.plane.point - self.plane.normal * self.view_depth,
+            self.plane.normal,
+        );
+        let visible = clipper.clip_mesh(&below, &bottom)?;
+
+        // Face normals per edge, keyed by welded end points
+        let mut edges: FxHashMap<EdgeKey, EdgeFaces> = FxHashMap::default();
+        for tri in visible.indices.chunks_exact(3) {
+            let p = [
+                vertex(&visible, tri[0]),
+                vertex(&visible, tri[1]),
+                vertex(&visible, tri[2]),
+            ];
+            let cross = (p[1] - p[0]).cross(&(p[2] - p[0]));
+            let Some(normal) = cross.try_normalize(1e-12) else {
+                continue;
+            };
+            for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+                let (ka, kb) = (self.key_3d(&p[a]), self.key_3d(&p[b]));
+                let key = if ka <= kb { (ka, kb) } else { (kb, ka) };
+                edges
+                    .entry(key)
+                    .or_insert_with(|| (p[a], p[b], Vec::new()))
+                    .2
+                    .push(normal);
+            }
+        }
+
+        let mut seen: FxHashSet<EdgeKey> = FxHashSet::default();
+        let mut lines = Vec::new();
+        for (a, b, normals) in edges.into_values() {
+            let is_feature = match normals.as_slice() {
+                [n0, n1] => n0.dot(n1) < self.crease_cos,
+                // Boundary or non-manifold edge
+                _ => true,
+            };
+            if !is_feature {
+                continue;
+            }
+
+            let (a, b) = (self.to_2d(&a), self.to_2d(&b));
+            if (b - a).norm() <= self.tolerance {
+                continue; // Edge seen end-on
+            }
+            let (ka, kb) = (self.key_2d(&a), self.key_2d(&b));
+            let key = if ka <= kb { (ka, kb) } else { (kb, ka) };
+            if seen.insert(key) {
+                lines.push([a, b]);
+            }
+        }
+
+        Ok(lines)
+    }
+
+    /// Chain section segments into closed loops
+    fn join_segments(&self, segments: &[[Point2<f64>; 2]]) -> Vec<Vec<Point2<f64>>> {
+        let mut by_end: FxHashMap<VertexKey, Vec<usize>> = FxHashMap::default();
+        for (i, segment) in segments.iter().enumerate() {
+            for point in segment {
+                by_end.entry(self.key_2d(point)).or_default().push(i);
+            }
+        }
+
+        let mut used = vec![false; segments.len()];
+        let mut loops = Vec::new();
+
+        for start in 0..segments.len() {
+            if used[start] {
+                continue;
+            }
+            used[start] = true;
+
+            let start_key = self.key_2d(&segments[start][0]);
+            let mut contour = vec![segments[start][0]];
+            let mut end = segments[start][1];
+            let mut closed = false;
+
+            loop {
+                let end_key = self.key_2d(&end);
+                if end_key == start_key {
+                    closed = true;
+                    break;
+                }
+                contour.push(end);
+
+                let next = by_end
+                    .get(&end_key)
+                    .and_then(|candidates| candidates.iter().copied().find(|&i| !used[i]));
+                let Some(next) = next else {
+                    break;
+                };
+                used[next] = true;
+                let [a, b] = segments[next];
+                end = if self.key_2d(&a) == end_key { b } else { a };
+            }
+
+            if closed && contour.len() >= 3 {
+                let contour = simplify_contour(&contour, 1e-12);
+                if compute_signed_area(&contour).abs() > self.tolerance * self.tolerance {
+                    loops.push(contour);
+                }
+            }
+        }
+
+        loops
+    }
+
+    #[inline]
+    fn distance_range(&self, mesh: &Mesh) -> Option<(f64, f64)> {
+        mesh.positions
+            .chunks_exact(3)
+            .map(|p| {
+                self.plane
+                    .signed_distance(&Point3::new(p[0] as f64, p[1] as f64, p[2] as f64))
+            })
+            .fold(None, |range, d| match range {
+                None => Some((d, d)),
+                Some((min, max)) => Some((f64::min(min, d), f64::max(max, d))),
+            })
+    }
+
+    #[inline]
+    fn key_2d(&self, point: &Point2<f64>) -> VertexKey {
+        (
+            (point.x / self.tolerance).round() as i64,
+            (point.y / self.tolerance).round() as i64,
+            0,
+        )
+    }
+
+    #[inline]
+    fn key_3d(&self, point: &Point3<f64>) -> VertexKey {
+        (
+            (point.x / self.tolerance).round() as i64,
+            (point.y / self.tolerance).round() as i64,
+            (point.z / self.tolerance).round() as i64,
+        )
+    }
+}
+
+/// A building storey and the elevation of its placement
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreyLevel {
+    /// Express ID of the IfcBuildingStorey
+    pub express_id: u32,
+    /// GlobalId
+    pub global_id: String,
+    /// Name
+    pub name: String,
+    /// Elevation in meters, in the same frame as the router's meshes
+    pub elevation: f64,
+}
+
+impl StoreyLevel {
+    /// Collect all storeys, lowest first
+    ///
+    /// The elevation is taken from the storey placement, falling back to the
+    /// Elevation attribute when the storey is not placed.
+    pub fn collect(
+        content: &str,
+        decoder: &mut EntityDecoder,
+        router: &GeometryRouter,
+    ) -> Vec<StoreyLevel> {
+        let mut levels = Vec::new();
+        let mut scanner = EntityScanner::new(content);
+
+        while let Some((id, type_name, start, end)) = scanner.next_entity() {
+            if type_name != "IFCBUILDINGSTOREY" {
+                continue;
+            }
+            let Ok(storey) = decoder.decode_at_with_id(id, start, end) else {
+                continue;
+            };
+
+            // Attribute 5: ObjectPlacement, attribute 9: Elevation
+            let placed = storey.get(5).is_some_and(|attr| !attr.is_null());
+            let elevation = if placed {
+                router
+                    .element_placement(&storey, decoder)
+                    .ok()
+                    .map(|transform| transform[(2, 3)])
+            } else {
+                None
+            }
+            .or_else(|| storey.get_float(9).map(|e| e * router.unit_scale()))
+            .unwrap_or(0.0);
+
+            levels.push(StoreyLevel {
+                express_id: id,
+                global_id: storey.get_string(0).unwrap_or_default().to_string(),
+                name: storey.get_string(2).unwrap_or_default().to_string(),
+                elevation,
+            });
+        }
+
+        levels.sort_by(|a, b| a.elevation.total_cmp(&b.elevation));
+        levels
+    }
+
+    /// Find a storey by express id (`123` or `#123`), GlobalId or name
+    pub fn find<'a>(levels: &'a [StoreyLevel], key: &str) -> Option<&'a StoreyLevel> {
+        let key = key.trim();
+        let id = key.trim_start_matches('#').parse::<u32>().ok();
+        levels
+            .iter()
+            .find(|level| Some(level.express_id) == id || level.global_id == key)
+            .or_else(|| levels.iter().find(|level| level.name.eq_ignore_ascii_case(key)))
+    }
+}
+
+type VertexKey = (i64, i64, i64);
+type EdgeKey = (VertexKey, VertexKey);
+/// Edge end points and the normals of the faces sharing it
+type EdgeFaces = (Point3<f64>, Point3<f64>, Vec<Vector3<f64>>);
+
+#[inline]
+fn vertex(mesh: &Mesh, index: u32) -> Point3<f64> {
+    let i = index as usize * 3;
+    Point3::new(
+        mesh.positions[i] as f64,
+        mesh.positions[i + 1] as f64,
+        mesh.positions[i + 2] as f64,
+    )
+}
+
+/// Point where an edge crosses the plane
+///
+/// End points are ordered first so both triangles sharing the edge compute
+/// bit-identical crossings.
+#[inline]
+fn edge_crossing(a: Point3<f64>, da: f64, b: Point3<f64>, db: f64) -> Point3<f64> {
+    let ((a, da), (b, db)) = if (a.x, a.y, a.z) <= (b.x, b.y, b.z) {
+        ((a, da), (b, db))
+    } else {
+        ((b, db), (a, da))
+    };
+    let t = da / (da - db);
+    a + (b - a) * t
+}
+
+/// Sort loops into outer boundaries and holes by nesting depth
+fn nest_loops(mut loops: Vec<Vec<Point2<f64>>>) -> Vec<Profile2D> {
+    // Largest first, so every container precedes what it contains
+    loops.sort_by(|a, b| {
+        compute_signed_area(b)
+            .abs()
+            .total_cmp(&compute_signed_area(a).abs())
+    });
+
+    let mut profiles: Vec<Profile2D> = Vec::new();
+    // (loop index, depth, owning profile)
+    let mut placed: Vec<(usize, usize, usize)> = Vec::new();
+
+    for i in 0..loops.len() {
+        let probe = loops[i][0];
+        // Innermost containing loop is the last (smallest) one containing the probe
+        let parent = placed
+            .iter()
+            .rev()
+            .find(|(j, _, _)| point_in_contour(&probe, &loops[*j]))
+            .copied();
+
+        match parent {
+            Some((_, depth, profile)) if depth % 2 == 0 => {
+                profiles[profile].add_hole(ensure_cw(&loops[i]));
+                placed.push((i, depth + 1, profile));
+            }
+            Some((_, depth, _)) => {
+                profiles.push(Profile2D::new(ensure_ccw(&loops[i])));
+                placed.push((i, depth + 1, profiles.len() - 1));
+            }
+            None => {
+                profiles.push(Profile2D::new(ensure_ccw(&loops[i])));
+                placed.push((i, 0, profiles.len() - 1));
+            }
+        }
+    }
+
+    profiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extrusion::extrude_profile;
+
+    fn box_mesh(min: [f64; 3], size: [f64; 3]) -> Mesh {
+        let profile = Profile2D::new(vec![
+            Point2::new(min[0], min[1]),
+            Point2::new(min[0] + size[0], min[1]),
+            Point2::new(min[0] + size[0], min[1] + size[1]),
+            Point2::new(min[0], min[1] + size[1]),
+        ]);
+        let mut mesh = extrude_profile(&profile, size[2], None).unwrap();
+        for z in mesh.positions.iter_mut().skip(2).step_by(3) {
+            *z += min[2] as f32;
+        }
+        mesh
+    }
+
+    #[test]
+    fn test_section_of_box() {
+        let wall = box_mesh([0.0, 0.0, 0.0], [5.0, 0.2, 3.0]);
+        let cutter = PlanCutter::at_elevation(1.2);
+
+        let polygons = cutter.section_polygons(&wall);
+        assert_eq!(polygons.len(), 1);
+        assert!(polygons[0].holes.is_empty());
+        assert_eq!(polygons[0].outer.len(), 4);
+        assert!((compute_signed_area(&polygons[0].outer) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_section_with_hole() {
+        let mut profile = Profile2D::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(4.0, 0.0),
+            Point2::new(4.0, 4.0),
+            Point2::new(0.0, 4.0),
+        ]);
+        profile.add_hole(vec![
+            Point2::new(1.0, 1.0),
+            Point2::new(1.0, 3.0),
+            Point2::new(3.0, 3.0),
+            Point2::new(3.0, 1.0),
+        ]);
+        let shaft = extrude_profile(&profile, 3.0, None).unwrap();
+
+        let polygons = PlanCutter::at_elevation(1.0).section_polygons(&shaft);
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].holes.len(), 1);
+        assert!((compute_signed_area(&polygons[0].outer) - 16.0).abs() < 1e-6);
+        assert!((compute_signed_area(&polygons[0].holes[0]) + 4.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_elevation_projects_everything() {
+        let wall = box_mesh([0.0, 0.0, 0.0], [5.0, 0.2, 3.0]);
+        let cutter = PlanCutter::elevation(
+            ElevationDirection::South,
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(5.0, 0.2, 3.0),
+        );
+        let plan = cutter.cut([(1, IfcType::IfcWall, &wall)]).unwrap();
+
+        assert!(plan.cut.is_empty());
+        assert_eq!(plan.projected.len(), 1);
+        // Facade rectangle: 5 m wide, 3 m high, drawn left to right
+        let lines = &plan.projected[0].lines;
+        assert_eq!(lines.len(), 4);
+        let max_y = lines
+            .iter()
+            .flat_map(|l| l.iter().map(|p| p.y))
+            .fold(f64::MIN, f64::max);
+        let min_y = lines
+            .iter()
+            .flat_map(|l| l.iter().map(|p| p.y))
+            .fold(f64::MAX, f64::min);
+        assert!((max_y - min_y - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_storey_levels() {
+        let content = r#"
+#1=IFCCARTESIANPOINT((0.,0.,3000.));
+#2=IFCAXIS2PLACEMENT3D(#1,$,$);
+#3=IFCLOCALPLACEMENT($,#2);
+#4=IFCBUILDINGSTOREY('2hF2b$7X5Dy8Y9tY0OK2ez',$,'Level 1',$,$,#3,$,$,.ELEMENT.,3000.);
+#5=IFCBUILDINGSTOREY('0lQ$X2b8z1ihJ8Ffd1Jb8G',$,'Ground',$,$,$,$,$,.ELEMENT.,0.);
+"#;
+        let mut decoder = EntityDecoder::new(content);
+        let router = GeometryRouter::with_scale(0.001);
+        let levels = StoreyLevel::collect(content, &mut decoder, &router);
+
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels[0].name, "Ground");
+        assert!((levels[1].elevation - 3.0).abs() < 1e-9);
+        assert_eq!(StoreyLevel::find(&levels, "#4").unwrap().name, "Level 1");
+        assert_eq!(StoreyLevel::find(&levels, "ground").unwrap().express_id, 5);
+        assert!(StoreyLevel::find(&levels, "Roof").is_none());
+    }
+
+    #[test]
+    fn test_cut_separates_fill_and_projection() {
+        let wall = box_mesh([0.0, 0.0, 0.0], [5.0, 0.2, 3.0]);
+        let table = box_mesh([1.0, 1.0, 0.0], [2.0, 1.0, 0.75]);
+        let lamp = box_mesh([1.0, 1.0, 2.5], [0.2, 0.2, 0.2]);
+        let deep = box_mesh([1.0, 1.0, -5.0], [1.0, 1.0, 1.0]);
+
+        let cutter = PlanCutter::at_elevation(1.2);
+        let plan = cutter
+            .cut([
+                (1, IfcType::IfcWall, &wall),
+                (2, IfcType::IfcFurniture, &table),
+                (3, IfcType::IfcLightFixture, &lamp),
+                (4, IfcType::IfcSlab, &deep),
+            ])
+            .unwrap();
+
+        assert_eq!(plan.cut.len(), 1);
+        assert_eq!(plan.cut[0].express_id, 1);
+        assert_eq!(plan.cut[0].ifc_type, IfcType::IfcWall);
+
+        assert_eq!(plan.projected.len(), 1);
+        assert_eq!(plan.projected[0].express_id, 2);
+        // Table top outline: four edges, vertical edges are seen end-on
+        assert_eq!(plan.projected[0].lines.len(), 4);
+    }
+}
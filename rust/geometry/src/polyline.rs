@@ -0,0 +1,250 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Polyline Output
+//!
+//! Line geometry for curve-only representations that have no triangles:
+//! wall axes, `IfcGeometricCurveSet` footprints, `IfcAnnotation` linework and
+//! `IfcGrid` axes. Produced by `GeometryRouter::process_element_curves`
+//! alongside the `Mesh` output and rendered as line segments.
+
+use nalgebra::{Matrix4, Point3};
+
+/// A connected 3D line strip
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Polyline3D {
+    /// Vertex positions (x, y, z)
+    pub positions: Vec<f32>,
+    /// Whether the last point connects back to the first
+    pub closed: bool,
+}
+
+impl Polyline3D {
+    /// Build from points, dropping consecutive duplicates
+    ///
+    /// A repeated end point marks the polyline as closed and is removed.
+    pub fn from_points(points: &[Point3<f64>]) -> Self {
+        let mut kept: Vec<Point3<f64>> = Vec::with_capacity(points.len());
+        for point in points {
+            if kept.last().is_none_or(|last| (point - last).norm() > 1e-9) {
+                kept.push(*point);
+            }
+        }
+
+        let closed = kept.len() > 2 && (kept[0] - kept[kept.len() - 1]).norm() < 1e-6;
+        if closed {
+            kept.pop();
+        }
+
+        Self {
+            positions: kept
+                .iter()
+                .flat_map(|p| [p.x as f32, p.y as f32, p.z as f32])
+                .collect(),
+            closed,
+        }
+    }
+
+    /// Number of points
+    #[inline]
+    pub fn point_count(&self) -> usize {
+        self.positions.len() / 3
+    }
+
+    /// Number of line segments, including the closing one
+    pub fn segment_count(&self) -> usize {
+        match self.point_count() {
+            0 | 1 => 0,
+            n if self.closed => n,
+            n => n - 1,
+        }
+    }
+
+    /// Check if there is nothing to draw
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segment_count() == 0
+    }
+
+    /// Point by index
+    pub fn point(&self, index: usize) -> Point3<f64> {
+        let i = index * 3;
+        Point3::new(
+            self.positions[i] as f64,
+            self.positions[i + 1] as f64,
+            self.positions[i + 2] as f64,
+        )
+    }
+
+    /// Total length of all segments
+    pub fn length(&self) -> f64 {
+        self.segment_indices()
+            .map(|(a, b)| (self.point(b) - self.point(a)).norm())
+            .sum()
+    }
+
+    /// Index pairs of the segments
+    pub fn segment_indices(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let n = self.point_count();
+        (0..self.segment_count()).map(move |i| (i, (i + 1) % n))
+    }
+
+    /// Apply a transformation matrix to all points
+    pub fn transform(&mut self, transform: &Matrix4<f64>) {
+        self.positions.chunks_exact_mut(3).for_each(|chunk| {
+            let point = Point3::new(chunk[0] as f64, chunk[1] as f64, chunk[2] as f64);
+            let t = transform.transform_point(&point);
+            chunk[0] = t.x as f32;
+            chunk[1] = t.y as f32;
+            chunk[2] = t.z as f32;
+        });
+    }
+}
+
+/// Text anchored at a point, e.g. a grid axis tag
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveLabel {
+    /// Label text
+    pub text: String,
+    /// Anchor position (x, y, z)
+    pub position: [f32; 3],
+}
+
+/// Line geometry and labels of one element
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CurveSet {
+    /// Line strips
+    pub polylines: Vec<Polyline3D>,
+    /// Text labels
+    pub labels: Vec<CurveLabel>,
+}
+
+impl CurveSet {
+    /// Create an empty curve set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if there are no lines and no labels
+    pub fn is_empty(&self) -> bool {
+        self.polylines.is_empty() && self.labels.is_empty()
+    }
+
+    /// Add a polyline, skipping degenerate ones
+    pub fn add_polyline(&mut self, polyline: Polyline3D) {
+        if !polyline.is_empty() {
+            self.polylines.push(polyline);
+        }
+    }
+
+    /// Add a label
+    pub fn add_label(&mut self, text: impl Into<String>, position: Point3<f64>) {
+        self.labels.push(CurveLabel {
+            text: text.into(),
+            position: [position.x as f32, position.y as f32, position.z as f32],
+        });
+    }
+
+    /// Append another curve set
+    pub fn merge(&mut self, other: CurveSet) {
+        self.polylines.extend(other.polylines);
+        self.labels.extend(other.labels);
+    }
+
+    /// Total number of line segments
+    pub fn segment_count(&self) -> usize {
+        self.polylines.iter().map(Polyline3D::segment_count).sum()
+    }
+
+    /// Apply a transformation matrix to all lines and labels
+    pub fn transform(&mut self, transform: &Matrix4<f64>) {
+        for polyline in &mut self.polylines {
+            polyline.transform(transform);
+        }
+        for label in &mut self.labels {
+            let p = Point3::new(
+                label.position[0] as f64,
+                label.position[1] as f64,
+                label.position[2] as f64,
+            );
+            let t = transform.transform_point(&p);
+            label.position = [t.x as f32, t.y as f32, t.z as f32];
+        }
+    }
+
+    /// Flatten into a shared vertex buffer and line-list indices (two per segment)
+    pub fn to_line_segments(&self) -> (Vec<f32>, Vec<u32>) {
+        let vertex_count: usize = self.polylines.iter().map(|p| p.positions.len()).sum();
+        let mut positions = Vec::with_capacity(vertex_count);
+        let mut indices = Vec::with_capacity(self.segment_count() * 2);
+
+        for polyline in &self.polylines {
+            let base = (positions.len() / 3) as u32;
+            positions.extend_from_slice(&polyline.positions);
+            for (a, b) in polyline.segment_indices() {
+                indices.push(base + a as u32);
+                indices.push(base + b as u32);
+            }
+        }
+
+        (positions, indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polyline_from_points() {
+        let open = Polyline3D::from_points(&[
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(3.0, 0.0, 0.0),
+            Point3::new(3.0, 4.0, 0.0),
+        ]);
+        assert!(!open.closed);
+        assert_eq!(open.point_count(), 3);
+        assert_eq!(open.segment_count(), 2);
+        assert!((open.length() - 7.0).abs() < 1e-6);
+
+        let closed = Polyline3D::from_points(&[
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(3.0, 0.0, 0.0),
+            Point3::new(3.0, 4.0, 0.0),
+            Point3::new(0.0, 0.0, 0.0),
+        ]);
+        assert!(closed.closed);
+        assert_eq!(closed.point_count(), 3);
+        assert_eq!(closed.segment_count(), 3);
+        assert!((closed.length() - 12.0).abs() < 1e-6);
+
+        assert!(Polyline3D::from_points(&[Point3::new(1.0, 1.0, 1.0)]).is_empty());
+    }
+
+    #[test]
+    fn test_curve_set_line_segments() {
+        let mut curves = CurveSet::new();
+        curves.add_polyline(Polyline3D::from_points(&[
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+        ]));
+        curves.add_polyline(Polyline3D::from_points(&[
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(0.0, 0.0, 0.0),
+        ]));
+        curves.add_label("A", Point3::new(0.0, 0.0, 0.0));
+
+        let translation = Matrix4::new_translation(&nalgebra::Vector3::new(10.0, 0.0, 0.0));
+        curves.transform(&translation);
+        assert_eq!(curves.labels[0].position, [10.0, 0.0, 0.0]);
+
+        let (positions, indices) = curves.to_line_segments();
+        assert_eq!(positions.len(), 5 * 3);
+        assert_eq!(indices, vec![0, 1, 2, 3, 3, 4, 4, 2]);
+        assert_eq!(curves.segment_count(), 4);
+    }
+}
@@ -549,6 +549,7 @@ impl GeometryProcessor for TriangulatedFaceSetProcessor {
             positions,
             normals: Vec::new(),
             indices,
+            uvs: Vec::new(),
         })
     }
 
@@ -678,6 +679,7 @@ impl GeometryProcessor for PolygonalFaceSetProcessor {
             positions,
             normals: Vec::new(), // Will be computed later
             indices,
+            uvs: Vec::new(),
         })
     }
 
@@ -1068,6 +1070,7 @@ impl FacetedBrepProcessor {
                         positions,
                         normals: Vec::new(),
                         indices,
+                        uvs: Vec::new(),
                     },
                 )
             })
@@ -1192,6 +1195,7 @@ impl GeometryProcessor for FacetedBrepProcessor {
             positions,
             normals: Vec::new(),
             indices,
+            uvs: Vec::new(),
         })
     }
 
@@ -1314,6 +1318,7 @@ impl GeometryProcessor for FaceBasedSurfaceModelProcessor {
             positions: all_positions,
             normals: Vec::new(),
             indices: all_indices,
+            uvs: Vec::new(),
         })
     }
 
@@ -1455,6 +1460,7 @@ impl GeometryProcessor for ShellBasedSurfaceModelProcessor {
             positions: all_positions,
             normals: Vec::new(),
             indices: all_indices,
+            uvs: Vec::new(),
         })
     }
 
@@ -1599,6 +1605,7 @@ impl GeometryProcessor for SurfaceOfLinearExtrusionProcessor {
             positions,
             normals: Vec::new(),
             indices,
+            uvs: Vec::new(),
         })
     }
 
@@ -2460,6 +2467,7 @@ impl GeometryProcessor for SweptDiskSolidProcessor {
             positions,
             normals: Vec::new(),
             indices,
+            uvs: Vec::new(),
         })
     }
 
@@ -2499,7 +2507,7 @@ impl GeometryProcessor for RevolvedAreaSolidProcessor {
         // 0: SweptArea (IfcProfileDef) - the 2D profile to revolve
         // 1: Position (IfcAxis2Placement3D) - placement of the solid
         // 2: Axis (IfcAxis1Placement) - the axis of revolution
-        // 3: Angle (IfcPlaneAngleMeasure) - revolution angle in radians
+        // 3: Angle (IfcPlaneAngleMeasure) - revolution angle in project angle units
 
         let profile_attr = entity
             .get(0)
@@ -2521,7 +2529,8 @@ impl GeometryProcessor for RevolvedAreaSolidProcessor {
         // Get angle (attribute 3)
         let angle = entity
             .get_float(3)
-            .ok_or_else(|| Error::geometry("RevolvedAreaSolid missing Angle".to_string()))?;
+            .ok_or_else(|| Error::geometry("RevolvedAreaSolid missing Angle".to_string()))?
+            * decoder.plane_angle_scale();
 
         // Get the 2D profile points
         let profile_2d = self.profile_processor.process(&profile, decoder)?;
@@ -2688,6 +2697,7 @@ impl GeometryProcessor for RevolvedAreaSolidProcessor {
             positions,
             normals: Vec::new(),
             indices,
+            uvs: Vec::new(),
         })
     }
 
@@ -3422,6 +3432,7 @@ impl GeometryProcessor for AdvancedBrepProcessor {
             positions: all_positions,
             normals: Vec::new(),
             indices: all_indices,
+            uvs: Vec::new(),
         })
     }
 
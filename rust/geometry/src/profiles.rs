@@ -897,9 +897,10 @@ impl ProfileProcessor {
 
         let (center, rotation) = self.get_placement_2d(basis, decoder)?;
 
-        // Convert trim parameters to angles (in degrees usually)
-        let start_angle = trim1.unwrap_or(0.0).to_radians();
-        let end_angle = trim2.unwrap_or(360.0).to_radians();
+        // Conic trim parameters are angles in the project's plane angle unit
+        let angle_scale = decoder.plane_angle_scale();
+        let start_angle = trim1.map_or(0.0, |t| t * angle_scale);
+        let end_angle = trim2.map_or(2.0 * PI, |t| t * angle_scale);
 
         // Calculate arc angle and adaptive segment count
         // Use ~8 segments per 90° (quarter circle), minimum 2
@@ -1447,4 +1448,27 @@ mod tests {
         assert_eq!(profile.outer.len(), 5); // 4 corners + closing point
         assert!(!profile.outer.is_empty());
     }
+
+    #[test]
+    fn test_trimmed_circle_plane_angle_unit() {
+        let content = r#"
+#1=IFCCARTESIANPOINT((0.0,0.0));
+#2=IFCAXIS2PLACEMENT2D(#1,$);
+#3=IFCCIRCLE(#2,10.0);
+#4=IFCTRIMMEDCURVE(#3,(IFCPARAMETERVALUE(0.0)),(IFCPARAMETERVALUE(90.0)),.T.,.PARAMETER.);
+"#;
+
+        let mut decoder = EntityDecoder::new(content);
+        decoder.set_plane_angle_scale(PI / 180.0);
+        let processor = ProfileProcessor::new(IfcSchema::new());
+
+        let curve = decoder.decode_by_id(4).unwrap();
+        let points = processor.process_curve(&curve, &mut decoder).unwrap();
+
+        // Quarter arc in degrees ends on the Y axis
+        let first = points.first().unwrap();
+        let last = points.last().unwrap();
+        assert!((first.x - 10.0).abs() < 1e-9 && first.y.abs() < 1e-9);
+        assert!(last.x.abs() < 1e-9 && (last.y - 10.0).abs() < 1e-9);
+    }
 }
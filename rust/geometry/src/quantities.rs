@@ -0,0 +1,342 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Computed Geometric Quantities
+//!
+//! Derives take-off quantities (volume, surface area, footprint, side areas
+//! and bounding box) from generated geometry, for files where the exporter
+//! did not author `IfcElementQuantity` values.
+//!
+//! Mesh-based values are always available; volume is only reported for
+//! closed solids. When an element is a single perpendicular extrusion the
+//! router can supply exact values from the profile and depth instead
+//! (see [`crate::GeometryRouter::extrusion_quantities`]).
+
+use crate::mesh::Mesh;
+use crate::validation::validate_mesh;
+use i_overlay::core::fill_rule::FillRule;
+use i_overlay::float::simplify::SimplifyShape;
+use nalgebra::{Matrix4, Point3, Vector3};
+
+/// How a computed quantity was derived
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantitySource {
+    /// Exact, from profile parameters and extrusion depth
+    Profile,
+    /// Approximate, from the tessellated mesh
+    Mesh,
+}
+
+impl QuantitySource {
+    /// Lowercase name for serialization
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuantitySource::Profile => "profile",
+            QuantitySource::Mesh => "mesh",
+        }
+    }
+}
+
+/// Exact quantities of a perpendicular extrusion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfileQuantities {
+    /// Net profile area (outer minus holes)
+    pub profile_area: f64,
+    /// Profile perimeter including holes
+    pub profile_perimeter: f64,
+    /// Extrusion depth
+    pub depth: f64,
+}
+
+impl ProfileQuantities {
+    /// Swept volume (area x depth)
+    #[inline]
+    pub fn volume(&self) -> f64 {
+        self.profile_area * self.depth
+    }
+
+    /// Total surface area (both caps plus the lateral faces)
+    #[inline]
+    pub fn surface_area(&self) -> f64 {
+        2.0 * self.profile_area + self.profile_perimeter * self.depth
+    }
+}
+
+/// Geometric quantities of one element, in meters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComputedQuantities {
+    /// Gross volume (`None` if the mesh is not a closed solid)
+    pub volume: Option<f64>,
+    /// Total surface area
+    pub surface_area: f64,
+    /// Area of the shadow cast onto the XY plane
+    pub footprint_area: f64,
+    /// Area of the shadow cast onto the YZ plane (viewed along X)
+    pub side_area_x: f64,
+    /// Area of the shadow cast onto the XZ plane (viewed along Y)
+    pub side_area_y: f64,
+    /// Axis-aligned bounding box minimum
+    pub bbox_min: Point3<f64>,
+    /// Axis-aligned bounding box maximum
+    pub bbox_max: Point3<f64>,
+    /// Source of `volume` and `surface_area`; the other values always come from the mesh
+    pub source: QuantitySource,
+}
+
+impl ComputedQuantities {
+    /// Compute quantities from a mesh
+    pub fn from_mesh(mesh: &Mesh) -> Self {
+        let (min, max) = mesh.bounds();
+        let volume = if validate_mesh(mesh).is_solid {
+            Some(mesh_volume(mesh).abs())
+        } else {
+            None
+        };
+
+        Self {
+            volume,
+            surface_area: mesh_surface_area(mesh),
+            footprint_area: projected_area(mesh, 2),
+            side_area_x: projected_area(mesh, 0),
+            side_area_y: projected_area(mesh, 1),
+            bbox_min: Point3::new(min.x as f64, min.y as f64, min.z as f64),
+            bbox_max: Point3::new(max.x as f64, max.y as f64, max.z as f64),
+            source: QuantitySource::Mesh,
+        }
+    }
+
+    /// Compute quantities from a mesh in its own coordinates and its placement
+    ///
+    /// Only the rotation is applied to the vertices, so a placement far from
+    /// the origin costs no f32 precision; the bounding box is moved in f64.
+    pub fn from_placed_mesh(mesh: &Mesh, placement: &Matrix4<f64>) -> Self {
+        let rotation = placement.fixed_view::<3, 3>(0, 0);
+        let mut oriented = mesh.clone();
+        for p in oriented.positions.chunks_exact_mut(3) {
+            let v = rotation * Vector3::new(p[0] as f64, p[1] as f64, p[2] as f64);
+            p.copy_from_slice(&[v.x as f32, v.y as f32, v.z as f32]);
+        }
+
+        let mut quantities = Self::from_mesh(&oriented);
+        let offset = Vector3::new(placement[(0, 3)], placement[(1, 3)], placement[(2, 3)]);
+        quantities.bbox_min += offset;
+        quantities.bbox_max += offset;
+        quantities
+    }
+
+    /// Replace volume and surface area with exact extrusion values
+    pub fn with_profile_quantities(mut self, profile: &ProfileQuantities) -> Self {
+        self.volume = Some(profile.volume());
+        self.surface_area = profile.surface_area();
+        self.source = QuantitySource::Profile;
+        self
+    }
+
+    /// Bounding box extents (x, y, z)
+    #[inline]
+    pub fn bbox_size(&self) -> Vector3<f64> {
+        self.bbox_max - self.bbox_min
+    }
+}
+
+/// Signed volume of a closed mesh (divergence theorem)
+///
+/// Positive for outward-facing triangles. Meaningless for open meshes.
+pub fn mesh_volume(mesh: &Mesh) -> f64 {
+    let vertex_count = mesh.vertex_count();
+    let mut volume = 0.0;
+    for tri in mesh.indices.chunks_exact(3) {
+        if tri.iter().any(|&i| i as usize >= vertex_count) {
+            continue;
+        }
+        let a = vertex(mesh, tri[0]);
+        let b = vertex(mesh, tri[1]);
+        let c = vertex(mesh, tri[2]);
+        volume += a.dot(&b.cross(&c));
+    }
+    volume / 6.0
+}
+
+/// Total area of all triangles
+pub fn mesh_surface_area(mesh: &Mesh) -> f64 {
+    let vertex_count = mesh.vertex_count();
+    let mut area = 0.0;
+    for tri in mesh.indices.chunks_exact(3) {
+        if tri.iter().any(|&i| i as usize >= vertex_count) {
+            continue;
+        }
+        let a = vertex(mesh, tri[0]);
+        let b = vertex(mesh, tri[1]);
+        let c = vertex(mesh, tri[2]);
+        area += (b - a).cross(&(c - a)).norm() * 0.5;
+    }
+    area
+}
+
+/// Area of the mesh projected along a coordinate axis (0 = X, 1 = Y, 2 = Z)
+///
+/// Projected triangles are unioned, so overlapping faces (top and bottom of
+/// a slab, overhangs) are only counted once.
+pub fn projected_area(mesh: &Mesh, axis: usize) -> f64 {
+    let (u, v) = match axis {
+        0 => (1, 2),
+        1 => (0, 2),
+        _ => (0, 1),
+    };
+    let vertex_count = mesh.vertex_count();
+
+    let mut triangles: Vec<Vec<[f64; 2]>> = Vec::with_capacity(mesh.triangle_count());
+    for tri in mesh.indices.chunks_exact(3) {
+        if tri.iter().any(|&i| i as usize >= vertex_count) {
+            continue;
+        }
+        let p = |i: u32| {
+            let base = i as usize * 3;
+            [
+                mesh.positions[base + u] as f64,
+                mesh.positions[base + v] as f64,
+            ]
+        };
+        let (a, b, c) = (p(tri[0]), p(tri[1]), p(tri[2]));
+        let signed = (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+        if signed.abs() < 1e-12 {
+            continue; // Edge-on to the projection direction
+        }
+        // Orient all triangles counter-clockwise so NonZero fill unions them
+        if signed > 0.0 {
+            triangles.push(vec![a, b, c]);
+        } else {
+            triangles.push(vec![a, c, b]);
+        }
+    }
+
+    if triangles.is_empty() {
+        return 0.0;
+    }
+
+    let shapes = triangles.simplify_shape(FillRule::NonZero, 0.0);
+    shapes
+        .iter()
+        .map(|shape| {
+            let mut contours = shape.iter().map(|c| contour_area(c).abs());
+            let outer = contours.next().unwrap_or(0.0);
+            outer - contours.sum::<f64>()
+        })
+        .sum()
+}
+
+#[inline]
+fn vertex(mesh: &Mesh, i: u32) -> Vector3<f64> {
+    let base = i as usize * 3;
+    Vector3::new(
+        mesh.positions[base] as f64,
+        mesh.positions[base + 1] as f64,
+        mesh.positions[base + 2] as f64,
+    )
+}
+
+/// Signed area of a 2D contour (shoelace formula)
+fn contour_area(contour: &[[f64; 2]]) -> f64 {
+    let n = contour.len();
+    let mut area = 0.0;
+    for i in 0..n {
+        let j = (i + 1) % n;
+        area += contour[i][0] * contour[j][1] - contour[j][0] * contour[i][1];
+    }
+    area * 0.5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extrusion::extrude_profile;
+    use crate::profile::{create_rectangle, Profile2D};
+    use nalgebra::Point2;
+
+    #[test]
+    fn test_box_quantities() {
+        // 2 x 1 x 3 box
+        let profile = create_rectangle(2.0, 1.0);
+        let mesh = extrude_profile(&profile, 3.0, None).unwrap();
+        let q = ComputedQuantities::from_mesh(&mesh);
+
+        assert!((q.volume.unwrap() - 6.0).abs() < 1e-4);
+        assert!((q.surface_area - 22.0).abs() < 1e-4);
+        assert!((q.footprint_area - 2.0).abs() < 1e-4);
+        assert!((q.side_area_x - 3.0).abs() < 1e-4);
+        assert!((q.side_area_y - 6.0).abs() < 1e-4);
+        let size = q.bbox_size();
+        assert!((size.x - 2.0).abs() < 1e-6 && (size.y - 1.0).abs() < 1e-6);
+        assert_eq!(q.source, QuantitySource::Mesh);
+    }
+
+    #[test]
+    fn test_placed_box_quantities() {
+        // 2 x 1 x 3 box turned 90 degrees about Z, placed on LV95 coordinates
+        let profile = create_rectangle(2.0, 1.0);
+        let mesh = extrude_profile(&profile, 3.0, None).unwrap();
+        let placement = Matrix4::new_translation(&Vector3::new(2_600_000.0, 1_200_000.0, 400.0))
+            * Matrix4::from_axis_angle(&Vector3::z_axis(), std::f64::consts::FRAC_PI_2);
+        let q = ComputedQuantities::from_placed_mesh(&mesh, &placement);
+
+        assert!((q.volume.unwrap() - 6.0).abs() < 1e-4);
+        assert!((q.side_area_x - 6.0).abs() < 1e-4);
+        assert!((q.side_area_y - 3.0).abs() < 1e-4);
+        let size = q.bbox_size();
+        assert!((size.x - 1.0).abs() < 1e-6 && (size.y - 2.0).abs() < 1e-6);
+        assert!((q.bbox_min.x - 2_599_999.5).abs() < 1e-6);
+        assert!((q.bbox_max.z - 403.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_footprint_excludes_hole() {
+        let mut profile = Profile2D::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(4.0, 0.0),
+            Point2::new(4.0, 4.0),
+            Point2::new(0.0, 4.0),
+        ]);
+        profile.add_hole(vec![
+            Point2::new(1.0, 1.0),
+            Point2::new(1.0, 3.0),
+            Point2::new(3.0, 3.0),
+            Point2::new(3.0, 1.0),
+        ]);
+        let mesh = extrude_profile(&profile, 1.0, None).unwrap();
+        let q = ComputedQuantities::from_mesh(&mesh);
+
+        assert!((q.footprint_area - 12.0).abs() < 1e-4);
+        assert!((q.volume.unwrap() - 12.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_open_mesh_has_no_volume() {
+        let mut mesh = Mesh::new();
+        mesh.positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        mesh.normals = vec![0.0; 9];
+        mesh.indices = vec![0, 1, 2];
+        let q = ComputedQuantities::from_mesh(&mesh);
+        assert!(q.volume.is_none());
+        assert!((q.surface_area - 0.5).abs() < 1e-6);
+        assert!((q.footprint_area - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_profile_quantities_override() {
+        let profile = ProfileQuantities {
+            profile_area: 2.0,
+            profile_perimeter: 6.0,
+            depth: 3.0,
+        };
+        assert_eq!(profile.volume(), 6.0);
+        assert_eq!(profile.surface_area(), 22.0);
+
+        let mut mesh = Mesh::new();
+        mesh.positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        mesh.indices = vec![0, 1, 2];
+        let q = ComputedQuantities::from_mesh(&mesh).with_profile_quantities(&profile);
+        assert_eq!(q.volume, Some(6.0));
+        assert_eq!(q.source, QuantitySource::Profile);
+    }
+}
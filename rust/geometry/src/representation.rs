@@ -0,0 +1,559 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Representation Selection
+//!
+//! Chooses which `IfcShapeRepresentation`s of a product the router meshes.
+//!
+//! By default only solid body geometry is used, selected by RepresentationType
+//! (`SweptSolid`, `Brep`, `Tessellation`, ...). An explicit selection lists
+//! RepresentationIdentifiers in preference order; the first identifier an
+//! element carries wins and all of its representations with that identifier
+//! are meshed. The identifier falls back to the ContextIdentifier of the
+//! `IfcGeometricRepresentationSubContext` when the representation has none.
+//!
+//! An entry may also require a TargetView of the sub-context, written after
+//! `@`; `@PLAN_VIEW` alone matches any identifier in that view.
+//!
+//! ```rust,ignore
+//! // Wall axes where present, otherwise the body
+//! router.set_representation_selection(RepresentationSelection::parse("Axis,Body"));
+//! // Plan view footprints, otherwise the model view body
+//! router.set_representation_selection(RepresentationSelection::parse(
+//!     "FootPrint@PLAN_VIEW,Body@MODEL_VIEW",
+//! ));
+//! ```
+
+use std::fmt;
+
+use ifc_lite_core::{DecodedEntity, EntityDecoder, IfcType};
+
+/// IfcShapeRepresentation.RepresentationIdentifier
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RepresentationIdentifier {
+    /// 3D body of the element
+    Body,
+    /// Axis line (walls, beams, members)
+    Axis,
+    /// 2D footprint, typically for plan views of slabs and spaces
+    FootPrint,
+    /// Bounding box placeholder (IfcBoundingBox)
+    Box,
+    /// Clearance or maintenance volume
+    Clearance,
+    /// Any other identifier, matched case-insensitively
+    Other(String),
+}
+
+impl RepresentationIdentifier {
+    /// Parse an identifier, case-insensitive for the known values
+    pub fn parse(s: &str) -> Self {
+        let s = s.trim();
+        match s.to_ascii_lowercase().as_str() {
+            "body" => Self::Body,
+            "axis" => Self::Axis,
+            "footprint" => Self::FootPrint,
+            "box" => Self::Box,
+            "clearance" => Self::Clearance,
+            _ => Self::Other(s.to_string()),
+        }
+    }
+
+    /// Identifier as written in IFC
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Body => "Body",
+            Self::Axis => "Axis",
+            Self::FootPrint => "FootPrint",
+            Self::Box => "Box",
+            Self::Clearance => "Clearance",
+            Self::Other(s) => s,
+        }
+    }
+
+    /// Identifier of a shape representation, falling back to its sub-context identifier
+    pub fn of(shape_rep: &DecodedEntity, decoder: &mut EntityDecoder) -> Option<Self> {
+        // Attribute 1: RepresentationIdentifier
+        if let Some(identifier) = shape_rep.get_string(1) {
+            if !identifier.is_empty() {
+                return Some(Self::parse(identifier));
+            }
+        }
+
+        // Attribute 0: ContextOfItems → IfcGeometricRepresentationSubContext.ContextIdentifier
+        let context = decoder.decode_by_id(shape_rep.get_ref(0)?).ok()?;
+        if context.ifc_type != IfcType::IfcGeometricRepresentationSubContext {
+            return None;
+        }
+        context
+            .get_string(0)
+            .filter(|s| !s.is_empty())
+            .map(Self::parse)
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Other(a), Self::Other(b)) => a.eq_ignore_ascii_case(b),
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for RepresentationIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// IfcGeometricRepresentationSubContext.TargetView
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TargetView {
+    GraphView,
+    SketchView,
+    ModelView,
+    PlanView,
+    ReflectedPlanView,
+    SectionView,
+    ElevationView,
+    NotDefined,
+    /// USERDEFINED, matched case-insensitively against UserDefinedTargetView
+    UserDefined(String),
+}
+
+impl TargetView {
+    /// Parse a view such as `PLAN_VIEW`, case-insensitive, dots optional
+    ///
+    /// Unknown names are taken as a user-defined view.
+    pub fn parse(s: &str) -> Self {
+        let s = s.trim().trim_matches('.');
+        match s.to_ascii_uppercase().as_str() {
+            "GRAPH_VIEW" => Self::GraphView,
+            "SKETCH_VIEW" => Self::SketchView,
+            "MODEL_VIEW" => Self::ModelView,
+            "PLAN_VIEW" => Self::PlanView,
+            "REFLECTED_PLAN_VIEW" => Self::ReflectedPlanView,
+            "SECTION_VIEW" => Self::SectionView,
+            "ELEVATION_VIEW" => Self::ElevationView,
+            "NOTDEFINED" => Self::NotDefined,
+            _ => Self::UserDefined(s.to_string()),
+        }
+    }
+
+    /// View as written in IFC, or the user-defined name
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::GraphView => "GRAPH_VIEW",
+            Self::SketchView => "SKETCH_VIEW",
+            Self::ModelView => "MODEL_VIEW",
+            Self::PlanView => "PLAN_VIEW",
+            Self::ReflectedPlanView => "REFLECTED_PLAN_VIEW",
+            Self::SectionView => "SECTION_VIEW",
+            Self::ElevationView => "ELEVATION_VIEW",
+            Self::NotDefined => "NOTDEFINED",
+            Self::UserDefined(s) => s,
+        }
+    }
+
+    /// Target view of a shape representation's context
+    ///
+    /// Representations directly in a top-level context count as MODEL_VIEW
+    /// for a `Model` context and PLAN_VIEW for a `Plan` context.
+    pub fn of(shape_rep: &DecodedEntity, decoder: &mut EntityDecoder) -> Option<Self> {
+        // Attribute 0: ContextOfItems
+        let context = decoder.decode_by_id(shape_rep.get_ref(0)?).ok()?;
+        match context.ifc_type {
+            IfcType::IfcGeometricRepresentationSubContext => {
+                // Attribute 8: TargetView, 9: UserDefinedTargetView
+                match context.get(8)?.as_enum()? {
+                    "USERDEFINED" => context
+                        .get_string(9)
+                        .map(|name| Self::UserDefined(name.to_string())),
+                    view => Some(Self::parse(view)),
+                }
+            }
+            IfcType::IfcGeometricRepresentationContext => {
+                // Attribute 1: ContextType
+                match context.get_string(1)?.to_ascii_lowercase().as_str() {
+                    "model" => Some(Self::ModelView),
+                    "plan" => Some(Self::PlanView),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::UserDefined(a), Self::UserDefined(b)) => a.eq_ignore_ascii_case(b),
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for TargetView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One entry of a selection: an identifier, a target view, or both
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepresentationCriterion {
+    /// Required identifier (`None` matches any)
+    pub identifier: Option<RepresentationIdentifier>,
+    /// Required target view (`None` matches any)
+    pub target_view: Option<TargetView>,
+}
+
+impl RepresentationCriterion {
+    /// Parse `Identifier`, `Identifier@VIEW` or `@VIEW`
+    pub fn parse(s: &str) -> Self {
+        let (identifier, view) = match s.split_once('@') {
+            Some((identifier, view)) => (identifier, Some(view)),
+            None => (s, None),
+        };
+        Self {
+            identifier: Some(identifier.trim())
+                .filter(|s| !s.is_empty())
+                .map(RepresentationIdentifier::parse),
+            target_view: view
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(TargetView::parse),
+        }
+    }
+
+    fn is_body(&self) -> bool {
+        self.identifier == Some(RepresentationIdentifier::Body)
+    }
+}
+
+impl From<RepresentationIdentifier> for RepresentationCriterion {
+    fn from(identifier: RepresentationIdentifier) -> Self {
+        Self {
+            identifier: Some(identifier),
+            target_view: None,
+        }
+    }
+}
+
+impl fmt::Display for RepresentationCriterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(identifier) = &self.identifier {
+            f.write_str(identifier.as_str())?;
+        }
+        if let Some(view) = &self.target_view {
+            write!(f, "@{}", view)?;
+        }
+        Ok(())
+    }
+}
+
+/// Which representations the router meshes, in preference order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepresentationSelection {
+    /// Empty means the default solid-body selection
+    criteria: Vec<RepresentationCriterion>,
+}
+
+impl RepresentationSelection {
+    /// Select by identifier or target view, first match per element wins
+    pub fn new<C: Into<RepresentationCriterion>>(criteria: impl IntoIterator<Item = C>) -> Self {
+        Self {
+            criteria: criteria.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Parse a comma-separated list such as `"Axis,Body"` or
+    /// `"FootPrint@PLAN_VIEW,Body"`
+    ///
+    /// An empty string gives the default selection.
+    pub fn parse(spec: &str) -> Self {
+        Self::new(
+            spec.split(',')
+                .filter(|s| !s.trim().is_empty())
+                .map(RepresentationCriterion::parse),
+        )
+    }
+
+    /// Criteria in preference order (empty for the default selection)
+    pub fn criteria(&self) -> &[RepresentationCriterion] {
+        &self.criteria
+    }
+
+    /// Check if this is the default solid-body selection
+    pub fn is_default(&self) -> bool {
+        self.criteria.is_empty()
+    }
+
+    /// Check if body geometry is preferred, so openings should be subtracted
+    pub fn prefers_body(&self) -> bool {
+        self.criteria
+            .first()
+            .is_none_or(RepresentationCriterion::is_body)
+    }
+
+    /// Pick the shape representations of a product to mesh
+    pub fn select(
+        &self,
+        representations: Vec<DecodedEntity>,
+        decoder: &mut EntityDecoder,
+    ) -> Vec<DecodedEntity> {
+        let shape_reps: Vec<DecodedEntity> = representations
+            .into_iter()
+            .filter(|rep| rep.ifc_type == IfcType::IfcShapeRepresentation)
+            .collect();
+
+        if self.is_default() {
+            return select_body(shape_reps);
+        }
+
+        let with_views = self.criteria.iter().any(|c| c.target_view.is_some());
+        let tagged: Vec<(
+            Option<RepresentationIdentifier>,
+            Option<TargetView>,
+            DecodedEntity,
+        )> = shape_reps
+            .into_iter()
+            .map(|rep| {
+                let view = if with_views {
+                    TargetView::of(&rep, decoder)
+                } else {
+                    None
+                };
+                (RepresentationIdentifier::of(&rep, decoder), view, rep)
+            })
+            .collect();
+
+        for wanted in &self.criteria {
+            let chosen: Vec<DecodedEntity> = tagged
+                .iter()
+                .filter(|(identifier, view, rep)| {
+                    let view_matches = match (&wanted.target_view, view) {
+                        (None, _) => true,
+                        (Some(wanted), Some(view)) => view.matches(wanted),
+                        (Some(_), None) => false,
+                    };
+                    let identifier_matches = match (&wanted.identifier, identifier) {
+                        (None, _) => true,
+                        (Some(wanted), Some(identifier)) => identifier.matches(wanted),
+                        // Untagged solids count as body geometry
+                        (Some(wanted), None) => {
+                            *wanted == RepresentationIdentifier::Body
+                                && is_body_type(representation_type(rep))
+                        }
+                    };
+                    view_matches && identifier_matches
+                })
+                .map(|(_, _, rep)| rep.clone())
+                .collect();
+
+            if !chosen.is_empty() {
+                return if wanted.is_body() {
+                    select_body(chosen)
+                } else {
+                    chosen
+                };
+            }
+        }
+
+        Vec::new()
+    }
+}
+
+impl fmt::Display for RepresentationSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_default() {
+            return f.write_str("Body");
+        }
+        for (i, criterion) in self.criteria.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", criterion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Attribute 2: RepresentationType
+fn representation_type(rep: &DecodedEntity) -> &str {
+    rep.get_string(2).unwrap_or("")
+}
+
+/// Solid representation types meshed as body geometry
+fn is_direct_body_type(rep_type: &str) -> bool {
+    matches!(
+        rep_type,
+        "Body"
+            | "SweptSolid"
+            | "Brep"
+            | "CSG"
+            | "Clipping"
+            | "SurfaceModel"
+            | "Tessellation"
+            | "AdvancedSweptSolid"
+            | "AdvancedBrep"
+    )
+}
+
+/// Solid representation types, including mapped ones
+pub(crate) fn is_body_type(rep_type: &str) -> bool {
+    rep_type == "MappedRepresentation" || is_direct_body_type(rep_type)
+}
+
+/// Solid body representations, skipping MappedRepresentation when direct
+/// geometry exists so elements carrying both are not duplicated
+fn select_body(shape_reps: Vec<DecodedEntity>) -> Vec<DecodedEntity> {
+    let has_direct_geometry = shape_reps
+        .iter()
+        .any(|rep| is_direct_body_type(representation_type(rep)));
+
+    shape_reps
+        .into_iter()
+        .filter(|rep| {
+            // Representations without a type are kept, as before
+            let Some(rep_type) = rep.get_string(2) else {
+                return true;
+            };
+            if rep_type == "MappedRepresentation" {
+                return !has_direct_geometry;
+            }
+            is_direct_body_type(rep_type)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPS: &str = r#"
+#1=IFCGEOMETRICREPRESENTATIONCONTEXT($,'Model',3,1.E-05,#9,$);
+#2=IFCGEOMETRICREPRESENTATIONSUBCONTEXT('Axis','Model',*,*,*,*,#1,$,.GRAPH_VIEW.,$);
+#3=IFCSHAPEREPRESENTATION(#1,'Body','SweptSolid',(#20));
+#4=IFCSHAPEREPRESENTATION(#2,$,'Curve2D',(#21));
+#5=IFCSHAPEREPRESENTATION(#1,'Box','BoundingBox',(#22));
+#6=IFCSHAPEREPRESENTATION(#1,'Body','MappedRepresentation',(#23));
+#7=IFCGEOMETRICREPRESENTATIONSUBCONTEXT('FootPrint','Plan',*,*,*,*,#1,$,.PLAN_VIEW.,$);
+#8=IFCSHAPEREPRESENTATION(#7,'FootPrint','GeometricCurveSet',(#24));
+#10=IFCSHAPEREPRESENTATION(#1,'FootPrint','GeometricCurveSet',(#25));
+#11=IFCGEOMETRICREPRESENTATIONSUBCONTEXT('Annotation','Plan',*,*,*,*,#1,$,.USERDEFINED.,'Detail');
+#12=IFCSHAPEREPRESENTATION(#11,'Annotation','Annotation2D',(#26));
+"#;
+
+    fn reps(decoder: &mut EntityDecoder, ids: &[u32]) -> Vec<DecodedEntity> {
+        ids.iter().map(|&id| decoder.decode_by_id(id).unwrap()).collect()
+    }
+
+    fn ids(reps: &[DecodedEntity]) -> Vec<u32> {
+        reps.iter().map(|rep| rep.id).collect()
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        let selection = RepresentationSelection::parse("axis, FootPrint,Body,Reference");
+        assert_eq!(
+            selection.criteria(),
+            &[
+                RepresentationIdentifier::Axis.into(),
+                RepresentationIdentifier::FootPrint.into(),
+                RepresentationIdentifier::Body.into(),
+                RepresentationIdentifier::Other("Reference".to_string()).into(),
+            ]
+        );
+        assert_eq!(selection.to_string(), "Axis,FootPrint,Body,Reference");
+        assert!(!selection.prefers_body());
+        assert!(RepresentationSelection::parse("").is_default());
+
+        let selection =
+            RepresentationSelection::parse("FootPrint@.plan_view., @Detail,Body@MODEL_VIEW");
+        assert_eq!(
+            selection.criteria(),
+            &[
+                RepresentationCriterion {
+                    identifier: Some(RepresentationIdentifier::FootPrint),
+                    target_view: Some(TargetView::PlanView),
+                },
+                RepresentationCriterion {
+                    identifier: None,
+                    target_view: Some(TargetView::UserDefined("Detail".to_string())),
+                },
+                RepresentationCriterion {
+                    identifier: Some(RepresentationIdentifier::Body),
+                    target_view: Some(TargetView::ModelView),
+                },
+            ]
+        );
+        assert_eq!(
+            selection.to_string(),
+            "FootPrint@PLAN_VIEW,@Detail,Body@MODEL_VIEW"
+        );
+        assert!(RepresentationSelection::parse("Body@MODEL_VIEW").prefers_body());
+    }
+
+    #[test]
+    fn test_default_selects_body_only() {
+        let mut decoder = EntityDecoder::new(REPS);
+        let all = reps(&mut decoder, &[3, 4, 5, 6]);
+        let selected = RepresentationSelection::default().select(all, &mut decoder);
+        // Mapped body skipped because direct geometry exists
+        assert_eq!(ids(&selected), vec![3]);
+    }
+
+    #[test]
+    fn test_identifier_fallback_order() {
+        let mut decoder = EntityDecoder::new(REPS);
+
+        // Axis comes from the sub-context identifier
+        let selection = RepresentationSelection::parse("Axis,Body");
+        let selected = selection.select(reps(&mut decoder, &[3, 4, 5]), &mut decoder);
+        assert_eq!(ids(&selected), vec![4]);
+
+        // No axis, fall back to body
+        let selected = selection.select(reps(&mut decoder, &[3, 5]), &mut decoder);
+        assert_eq!(ids(&selected), vec![3]);
+
+        // Nothing matches
+        let selection = RepresentationSelection::parse("Clearance");
+        assert!(selection
+            .select(reps(&mut decoder, &[3, 4, 5]), &mut decoder)
+            .is_empty());
+
+        let selection = RepresentationSelection::parse("Box");
+        let selected = selection.select(reps(&mut decoder, &[3, 5]), &mut decoder);
+        assert_eq!(ids(&selected), vec![5]);
+    }
+
+    #[test]
+    fn test_target_view_selection() {
+        let mut decoder = EntityDecoder::new(REPS);
+
+        // Without a view, footprints of every context match
+        let selection = RepresentationSelection::parse("FootPrint");
+        let selected = selection.select(reps(&mut decoder, &[3, 8, 10]), &mut decoder);
+        assert_eq!(ids(&selected), vec![8, 10]);
+
+        // Only the plan view footprint, otherwise the body of the 'Model' context
+        let selection = RepresentationSelection::parse("FootPrint@PLAN_VIEW,Body@MODEL_VIEW");
+        let selected = selection.select(reps(&mut decoder, &[3, 8, 10]), &mut decoder);
+        assert_eq!(ids(&selected), vec![8]);
+        let selected = selection.select(reps(&mut decoder, &[3, 10]), &mut decoder);
+        assert_eq!(ids(&selected), vec![3]);
+
+        // A view alone matches any identifier; the axis sub-context is a graph view
+        let selection = RepresentationSelection::parse("@PLAN_VIEW");
+        let selected = selection.select(reps(&mut decoder, &[3, 4, 8, 12]), &mut decoder);
+        assert_eq!(ids(&selected), vec![8]);
+        let selection = RepresentationSelection::parse("@graph_view");
+        let selected = selection.select(reps(&mut decoder, &[3, 4, 8, 12]), &mut decoder);
+        assert_eq!(ids(&selected), vec![4]);
+
+        // User-defined views match UserDefinedTargetView
+        let selection = RepresentationSelection::parse("Annotation@detail");
+        let selected = selection.select(reps(&mut decoder, &[8, 12]), &mut decoder);
+        assert_eq!(ids(&selected), vec![12]);
+    }
+}
@@ -6,7 +6,8 @@
 //!
 //! Routes IFC representation entities to appropriate processors based on type.
 
-use crate::bool2d::subtract_multiple_2d;
+use crate::layers::{has_layered_geometry, split_mesh_by_layers, MaterialLayerSplit};
+use crate::material::IndexedTextureMap;
 use crate::csg::{ClippingProcessor, Triangle, TriangleVec};
 use crate::processors::{
     AdvancedBrepProcessor, BooleanClippingProcessor, ExtrudedAreaSolidProcessor,
@@ -14,7 +15,12 @@ use crate::processors::{
     PolygonalFaceSetProcessor, RevolvedAreaSolidProcessor, ShellBasedSurfaceModelProcessor,
     SweptDiskSolidProcessor, TriangulatedFaceSetProcessor,
 };
-use crate::profile::{Profile2D, Profile2DWithVoids, VoidInfo};
+use crate::polyline::{CurveSet, Polyline3D};
+use crate::profile::{Profile2D, Profile2DWithVoids};
+use crate::profiles::ProfileProcessor;
+use crate::quantities::{mesh_volume, ProfileQuantities};
+use crate::representation::{is_body_type, RepresentationSelection};
+use crate::triangulation::triangulate_polygon;
 use crate::void_analysis::{
     extract_coplanar_voids, extract_nonplanar_voids, VoidAnalyzer, VoidClassification,
 };
@@ -28,6 +34,11 @@ use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
+/// Maximum number of openings per element subtracted with 3D mesh CSG
+///
+/// Box openings and openings subtracted at the profile level are not limited.
+const MAX_CSG_OPENINGS: usize = 15;
+
 /// Reusable buffers for triangle clipping operations
 ///
 /// This struct eliminates per-triangle allocations in clip_triangle_against_box
@@ -86,6 +97,9 @@ pub struct GeometryRouter {
     /// Key: FacetedBrep entity ID, Value: Processed mesh
     /// Uses Box to avoid copying large meshes, entries are taken (removed) when used
     faceted_brep_cache: RefCell<FxHashMap<u32, Mesh>>,
+    /// Batch-triangulated FacetedBreps by entity ID
+    /// Shared across per-thread routers; empty unless set
+    faceted_breps: Arc<FxHashMap<u32, Mesh>>,
     /// Cache for geometry deduplication by content hash
     /// Buildings with repeated floors have 99% identical geometry
     /// Key: Hash of mesh content, Value: Processed mesh
@@ -97,6 +111,14 @@ pub struct GeometryRouter {
     /// Subtracted from all world positions in f64 before converting to f32
     /// This preserves precision for georeferenced models (e.g., Swiss UTM)
     rtc_offset: (f64, f64, f64),
+    /// IfcIndexedTriangleTextureMap by IfcTriangulatedFaceSet ID
+    /// Shared across per-thread routers; empty unless set
+    texture_maps: Arc<FxHashMap<u32, IndexedTextureMap>>,
+    /// Material layer slabs by wall/slab ID
+    /// Shared across per-thread routers; empty unless set
+    material_layers: Arc<FxHashMap<u32, MaterialLayerSplit>>,
+    /// Which shape representations to mesh (default: solid body)
+    representation_selection: RepresentationSelection,
 }
 
 impl GeometryRouter {
@@ -109,9 +131,13 @@ impl GeometryRouter {
             processors: HashMap::new(),
             mapped_item_cache: RefCell::new(FxHashMap::default()),
             faceted_brep_cache: RefCell::new(FxHashMap::default()),
+            faceted_breps: Arc::new(FxHashMap::default()),
             geometry_hash_cache: RefCell::new(FxHashMap::default()),
             unit_scale: 1.0, // Default to base meters
             rtc_offset: (0.0, 0.0, 0.0), // Default to no offset
+            texture_maps: Arc::new(FxHashMap::default()),
+            material_layers: Arc::new(FxHashMap::default()),
+            representation_selection: RepresentationSelection::default(),
         };
 
         // Register default P0 processors
@@ -135,22 +161,14 @@ impl GeometryRouter {
     }
 
     /// Create router and extract unit scale from IFC file
-    /// Automatically finds IFCPROJECT and extracts length unit conversion
+    /// Automatically finds IFCPROJECT and extracts length unit conversion.
+    /// The plane angle unit is set on `decoder`; decoders created later for
+    /// the same file need [`EntityDecoder::set_plane_angle_scale`] as well.
     pub fn with_units(content: &str, decoder: &mut EntityDecoder) -> Self {
-        let mut scanner = ifc_lite_core::EntityScanner::new(content);
-        let mut scale = 1.0;
-
-        // Scan through file to find IFCPROJECT
-        while let Some((id, type_name, _, _)) = scanner.next_entity() {
-            if type_name == "IFCPROJECT" {
-                if let Ok(s) = ifc_lite_core::extract_length_unit_scale(decoder, id) {
-                    scale = s;
-                }
-                break;
-            }
-        }
+        let units = ifc_lite_core::UnitContext::from_content(content, decoder);
+        decoder.set_plane_angle_scale(units.plane_angle_scale());
 
-        Self::with_scale(scale)
+        Self::with_scale(units.length_scale())
     }
 
     /// Create router with unit scale extracted from IFC file AND RTC offset for large coordinates
@@ -165,20 +183,10 @@ impl GeometryRouter {
         decoder: &mut ifc_lite_core::EntityDecoder,
         rtc_offset: (f64, f64, f64),
     ) -> Self {
-        let mut scanner = ifc_lite_core::EntityScanner::new(content);
-        let mut scale = 1.0;
-
-        // Scan through file to find IFCPROJECT
-        while let Some((id, type_name, _, _)) = scanner.next_entity() {
-            if type_name == "IFCPROJECT" {
-                if let Ok(s) = ifc_lite_core::extract_length_unit_scale(decoder, id) {
-                    scale = s;
-                }
-                break;
-            }
-        }
+        let units = ifc_lite_core::UnitContext::from_content(content, decoder);
+        decoder.set_plane_angle_scale(units.plane_angle_scale());
 
-        Self::with_scale_and_rtc(scale, rtc_offset)
+        Self::with_scale_and_rtc(units.length_scale(), rtc_offset)
     }
 
     /// Create router with pre-calculated unit scale
@@ -209,6 +217,34 @@ impl GeometryRouter {
         self.rtc_offset = offset;
     }
 
+    /// Set the texture maps used to give triangulated face sets UVs
+    /// (see [`crate::build_texture_map_index`])
+    pub fn set_texture_maps(&mut self, texture_maps: Arc<FxHashMap<u32, IndexedTextureMap>>) {
+        self.texture_maps = texture_maps;
+    }
+
+    /// Set the material layers used to split walls and slabs per layer
+    /// (see [`crate::build_material_layer_index`])
+    pub fn set_material_layers(&mut self, material_layers: Arc<FxHashMap<u32, MaterialLayerSplit>>) {
+        self.material_layers = material_layers;
+    }
+
+    /// Set the FacetedBreps triangulated up front for every router
+    /// (see [`Self::triangulate_faceted_breps`])
+    pub fn set_faceted_breps(&mut self, faceted_breps: Arc<FxHashMap<u32, Mesh>>) {
+        self.faceted_breps = faceted_breps;
+    }
+
+    /// Set which representation identifiers to mesh, in preference order
+    pub fn set_representation_selection(&mut self, selection: RepresentationSelection) {
+        self.representation_selection = selection;
+    }
+
+    /// Get the current representation selection
+    pub fn representation_selection(&self) -> &RepresentationSelection {
+        &self.representation_selection
+    }
+
     /// Get the current RTC offset
     pub fn rtc_offset(&self) -> (f64, f64, f64) {
         self.rtc_offset
@@ -354,17 +390,25 @@ impl GeometryRouter {
             return;
         }
 
+        let results = Self::triangulate_faceted_breps(brep_ids, decoder);
+        self.faceted_brep_cache.borrow_mut().extend(results);
+    }
+
+    /// Batch triangulate FacetedBrep entities, keyed by entity ID
+    ///
+    /// Meshes are in file units. Share the result between per-thread routers
+    /// with [`Self::set_faceted_breps`].
+    pub fn triangulate_faceted_breps(
+        brep_ids: &[u32],
+        decoder: &mut EntityDecoder,
+    ) -> FxHashMap<u32, Mesh> {
         // Use batch processing for parallel triangulation
         let processor = FacetedBrepProcessor::new();
-        let results = processor.process_batch(brep_ids, decoder);
-
-        // Store results in cache (preallocate to avoid rehashing)
-        let mut cache = self.faceted_brep_cache.borrow_mut();
-        cache.reserve(results.len());
-        for (brep_idx, mesh) in results {
-            let brep_id = brep_ids[brep_idx];
-            cache.insert(brep_id, mesh);
-        }
+        processor
+            .process_batch(brep_ids, decoder)
+            .into_iter()
+            .map(|(brep_idx, mesh)| (brep_ids[brep_idx], mesh))
+            .collect()
     }
 
     /// Take FacetedBrep from cache (removes entry since each BREP is only used once)
@@ -396,6 +440,11 @@ impl GeometryRouter {
             idx.hash(&mut hasher);
         }
 
+        // Hash texture coordinates so textured and untextured copies stay apart
+        for uv in &mesh.uvs {
+            uv.to_bits().hash(&mut hasher);
+        }
+
         hasher.finish()
     }
 
@@ -464,73 +513,14 @@ impl GeometryRouter {
             Error::geometry("IfcProductDefinitionShape missing Representations".to_string())
         })?;
 
+        // Representations chosen by the selection (solid body by default)
         let representations = decoder.resolve_ref_list(representations_attr)?;
+        let representations = self.representation_selection.select(representations, decoder);
 
         // Process all representations and merge meshes
         let mut combined_mesh = Mesh::new();
 
-        // First pass: check if we have any direct geometry representations
-        // This prevents duplication when both direct and MappedRepresentation exist
-        let has_direct_geometry = representations.iter().any(|rep| {
-            if rep.ifc_type != IfcType::IfcShapeRepresentation {
-                return false;
-            }
-            if let Some(rep_type_attr) = rep.get(2) {
-                if let Some(rep_type) = rep_type_attr.as_string() {
-                    matches!(
-                        rep_type,
-                        "Body"
-                            | "SweptSolid"
-                            | "Brep"
-                            | "CSG"
-                            | "Clipping"
-                            | "SurfaceModel"
-                            | "Tessellation"
-                            | "AdvancedSweptSolid"
-                            | "AdvancedBrep"
-                    )
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        });
-
         for shape_rep in representations {
-            if shape_rep.ifc_type != IfcType::IfcShapeRepresentation {
-                continue;
-            }
-
-            // Check RepresentationType (attribute 2) - only process geometric representations
-            // Skip 'Axis', 'Curve2D', 'FootPrint', etc. - only process 'Body', 'SweptSolid', 'Brep', etc.
-            if let Some(rep_type_attr) = shape_rep.get(2) {
-                if let Some(rep_type) = rep_type_attr.as_string() {
-                    // Skip MappedRepresentation if we already have direct geometry
-                    // This prevents duplication when an element has both direct and mapped representations
-                    if rep_type == "MappedRepresentation" && has_direct_geometry {
-                        continue;
-                    }
-
-                    // Only process solid geometry representations
-                    if !matches!(
-                        rep_type,
-                        "Body"
-                            | "SweptSolid"
-                            | "Brep"
-                            | "CSG"
-                            | "Clipping"
-                            | "SurfaceModel"
-                            | "Tessellation"
-                            | "MappedRepresentation"
-                            | "AdvancedSweptSolid"
-                            | "AdvancedBrep"
-                    ) {
-                        continue; // Skip non-solid representations like 'Axis', 'Curve2D', etc.
-                    }
-                }
-            }
-
             // Get items list (attribute 3)
             let items_attr = shape_rep.get(3).ok_or_else(|| {
                 Error::geometry("IfcShapeRepresentation missing Items".to_string())
@@ -551,14 +541,118 @@ impl GeometryRouter {
         Ok(combined_mesh)
     }
 
-    /// Get individual bounding boxes for each representation item in an opening element.
+    /// Exact quantities for an element whose body is a single perpendicular extrusion
+    ///
+    /// Returns `None` when the body is anything else (multiple items, mapped,
+    /// boolean, slanted extrusion), in which case mesh-based values should be
+    /// used. Values are in meters. Openings are not subtracted.
+    pub fn extrusion_quantities(
+        &self,
+        element: &DecodedEntity,
+        decoder: &mut EntityDecoder,
+    ) -> Result<Option<ProfileQuantities>> {
+        let representation_attr = match element.get(6) {
+            Some(attr) if !attr.is_null() => attr,
+            _ => return Ok(None),
+        };
+        let representation = match decoder.resolve_ref(representation_attr)? {
+            Some(rep) if rep.ifc_type == IfcType::IfcProductDefinitionShape => rep,
+            _ => return Ok(None),
+        };
+        let representations = match representation.get(2) {
+            Some(attr) => decoder.resolve_ref_list(attr)?,
+            None => return Ok(None),
+        };
+
+        // Exactly one solid body item, which must be an IfcExtrudedAreaSolid
+        let mut body_items = Vec::new();
+        for shape_rep in &representations {
+            if shape_rep.ifc_type != IfcType::IfcShapeRepresentation {
+                continue;
+            }
+            let rep_type = shape_rep.get(2).and_then(|a| a.as_string()).unwrap_or("");
+            if !is_body_type(rep_type) {
+                continue;
+            }
+            if let Some(items_attr) = shape_rep.get(3) {
+                body_items.extend(decoder.resolve_ref_list(items_attr)?);
+            }
+        }
+        if body_items.len() != 1 || body_items[0].ifc_type != IfcType::IfcExtrudedAreaSolid {
+            return Ok(None);
+        }
+        let solid = &body_items[0];
+
+        // IfcExtrudedAreaSolid: SweptArea, Position, ExtrudedDirection, Depth
+        let depth = match solid.get_float(3) {
+            Some(d) if d > 0.0 => d,
+            _ => return Ok(None),
+        };
+        let direction = match solid.get(2) {
+            Some(attr) => match decoder.resolve_ref(attr)? {
+                Some(dir) => self.parse_direction(&dir)?,
+                None => return Ok(None),
+            },
+            None => return Ok(None),
+        };
+        // Slanted extrusions have a sheared volume; leave those to the mesh
+        if direction.norm() < 1e-12 || (direction.z.abs() / direction.norm()) < 1.0 - 1e-9 {
+            return Ok(None);
+        }
+
+        let profile_entity = match solid.get(0) {
+            Some(attr) => match decoder.resolve_ref(attr)? {
+                Some(profile) => profile,
+                None => return Ok(None),
+            },
+            None => return Ok(None),
+        };
+
+        let (area, perimeter) = match profile_entity.ifc_type {
+            IfcType::IfcRectangleProfileDef => {
+                let x = profile_entity.get_float(3).unwrap_or(0.0);
+                let y = profile_entity.get_float(4).unwrap_or(0.0);
+                (x * y, 2.0 * (x + y))
+            }
+            IfcType::IfcCircleProfileDef => {
+                let r = profile_entity.get_float(3).unwrap_or(0.0);
+                (std::f64::consts::PI * r * r, 2.0 * std::f64::consts::PI * r)
+            }
+            _ => {
+                let profile = ProfileProcessor::new(self.schema.clone())
+                    .process(&profile_entity, decoder)?;
+                if profile.outer.len() < 3 {
+                    return Ok(None);
+                }
+                let area = polygon_area(&profile.outer).abs()
+                    - profile.holes.iter().map(|h| polygon_area(h).abs()).sum::<f64>();
+                let perimeter = polygon_perimeter(&profile.outer)
+                    + profile.holes.iter().map(|h| polygon_perimeter(h)).sum::<f64>();
+                (area, perimeter)
+            }
+        };
+
+        if area <= 0.0 {
+            return Ok(None);
+        }
+
+        let s = self.unit_scale;
+        Ok(Some(ProfileQuantities {
+            profile_area: area * s * s,
+            profile_perimeter: perimeter * s,
+            depth: depth * s,
+        }))
+    }
+
+    /// World-space meshes for each body item of an opening element.
     /// This handles disconnected geometry (e.g., two separate window openings in one IfcOpeningElement)
-    /// by returning separate bounds for each item instead of one combined bounding box.
-    fn get_opening_item_bounds(
+    /// by returning one mesh per item instead of one combined mesh, so each item can be cut
+    /// with the cheapest exact method.
+    fn opening_item_meshes(
         &self,
         element: &DecodedEntity,
         decoder: &mut EntityDecoder,
-    ) -> Result<Vec<(Point3<f64>, Point3<f64>)>> {
+    ) -> Result<Vec<Mesh>> {
         // Get representation (attribute 6 for most building elements)
         let representation_attr = element.get(6).ok_or_else(|| {
             Error::geometry("Element has no representation attribute".to_string())
@@ -579,12 +673,7 @@ impl GeometryRouter {
 
         let representations = decoder.resolve_ref_list(representations_attr)?;
 
-        // Get placement transform
-        let mut placement_transform = self.get_placement_transform_from_element(element, decoder)
-            .unwrap_or_else(|_| Matrix4::identity());
-        self.scale_transform(&mut placement_transform);
-
-        let mut bounds_list = Vec::new();
+        let mut meshes = Vec::new();
 
         for shape_rep in representations {
             if shape_rep.ifc_type != IfcType::IfcShapeRepresentation {
@@ -611,49 +700,46 @@ impl GeometryRouter {
                 Err(_) => continue,
             };
 
-            // Process each item separately to get individual bounds
+            // Process each item separately and place it in world coordinates
             for item in items {
-                let mesh = match self.process_representation_item(&item, decoder) {
+                let mut mesh = match self.process_representation_item(&item, decoder) {
                     Ok(m) if !m.is_empty() => m,
                     _ => continue,
                 };
+                self.apply_placement(element, decoder, &mut mesh)?;
+                meshes.push(mesh);
+            }
+        }
 
-                // Get bounds and transform to world coordinates
-                let (mesh_min, mesh_max) = mesh.bounds();
-
-                // Transform corner points to world coordinates
-                let corners = [
-                    Point3::new(mesh_min.x as f64, mesh_min.y as f64, mesh_min.z as f64),
-                    Point3::new(mesh_max.x as f64, mesh_min.y as f64, mesh_min.z as f64),
-                    Point3::new(mesh_min.x as f64, mesh_max.y as f64, mesh_min.z as f64),
-                    Point3::new(mesh_max.x as f64, mesh_max.y as f64, mesh_min.z as f64),
-                    Point3::new(mesh_min.x as f64, mesh_min.y as f64, mesh_max.z as f64),
-                    Point3::new(mesh_max.x as f64, mesh_min.y as f64, mesh_max.z as f64),
-                    Point3::new(mesh_min.x as f64, mesh_max.y as f64, mesh_max.z as f64),
-                    Point3::new(mesh_max.x as f64, mesh_max.y as f64, mesh_max.z as f64),
-                ];
-
-                // Transform all corners and compute new AABB
-                let transformed: Vec<Point3<f64>> = corners.iter()
-                    .map(|p| placement_transform.transform_point(p))
-                    .collect();
-
-                let world_min = Point3::new(
-                    transformed.iter().map(|p| p.x).fold(f64::INFINITY, f64::min),
-                    transformed.iter().map(|p| p.y).fold(f64::INFINITY, f64::min),
-                    transformed.iter().map(|p| p.z).fold(f64::INFINITY, f64::min),
-                );
-                let world_max = Point3::new(
-                    transformed.iter().map(|p| p.x).fold(f64::NEG_INFINITY, f64::max),
-                    transformed.iter().map(|p| p.y).fold(f64::NEG_INFINITY, f64::max),
-                    transformed.iter().map(|p| p.z).fold(f64::NEG_INFINITY, f64::max),
-                );
+        Ok(meshes)
+    }
+
+    /// World-space opening meshes for a host, one per opening body item
+    fn collect_opening_meshes(
+        &self,
+        opening_ids: &[u32],
+        decoder: &mut EntityDecoder,
+    ) -> Vec<Mesh> {
+        let mut meshes = Vec::new();
+        for &opening_id in opening_ids {
+            let opening_entity = match decoder.decode_by_id(opening_id) {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
 
-                bounds_list.push((world_min, world_max));
+            match self.opening_item_meshes(&opening_entity, decoder) {
+                Ok(items) if !items.is_empty() => meshes.extend(items),
+                // Fall back to the whole element mesh (e.g. non-standard representation types)
+                _ => {
+                    if let Ok(mesh) = self.process_element(&opening_entity, decoder) {
+                        if !mesh.is_empty() {
+                            meshes.push(mesh);
+                        }
+                    }
+                }
             }
         }
-
-        Ok(bounds_list)
+        meshes
     }
 
     /// Process element and return sub-meshes with their geometry item IDs.
@@ -694,68 +780,13 @@ impl GeometryRouter {
             Error::geometry("IfcProductDefinitionShape missing Representations".to_string())
         })?;
 
+        // Representations chosen by the selection (solid body by default)
         let representations = decoder.resolve_ref_list(representations_attr)?;
+        let representations = self.representation_selection.select(representations, decoder);
 
         let mut sub_meshes = SubMeshCollection::new();
 
-        // Check if we have direct geometry
-        let has_direct_geometry = representations.iter().any(|rep| {
-            if rep.ifc_type != IfcType::IfcShapeRepresentation {
-                return false;
-            }
-            if let Some(rep_type_attr) = rep.get(2) {
-                if let Some(rep_type) = rep_type_attr.as_string() {
-                    matches!(
-                        rep_type,
-                        "Body"
-                            | "SweptSolid"
-                            | "Brep"
-                            | "CSG"
-                            | "Clipping"
-                            | "SurfaceModel"
-                            | "Tessellation"
-                            | "AdvancedSweptSolid"
-                            | "AdvancedBrep"
-                    )
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        });
-
         for shape_rep in representations {
-            if shape_rep.ifc_type != IfcType::IfcShapeRepresentation {
-                continue;
-            }
-
-            if let Some(rep_type_attr) = shape_rep.get(2) {
-                if let Some(rep_type) = rep_type_attr.as_string() {
-                    // Skip MappedRepresentation if we have direct geometry
-                    if rep_type == "MappedRepresentation" && has_direct_geometry {
-                        continue;
-                    }
-
-                    // Only process solid geometry representations
-                    if !matches!(
-                        rep_type,
-                        "Body"
-                            | "SweptSolid"
-                            | "Brep"
-                            | "CSG"
-                            | "Clipping"
-                            | "SurfaceModel"
-                            | "Tessellation"
-                            | "MappedRepresentation"
-                            | "AdvancedSweptSolid"
-                            | "AdvancedBrep"
-                    ) {
-                        continue;
-                    }
-                }
-            }
-
             // Get items list (attribute 3)
             let items_attr = shape_rep.get(3).ok_or_else(|| {
                 Error::geometry("IfcShapeRepresentation missing Items".to_string())
@@ -857,13 +888,65 @@ impl GeometryRouter {
         Ok(())
     }
 
+    /// Process element with openings subtracted, split into one sub-mesh per material layer
+    ///
+    /// Walls and slabs with a material layer set usage (see
+    /// [`Self::set_material_layers`]) are cut along the layer set direction of
+    /// their placement; each sub-mesh carries the IfcMaterialLayer as its
+    /// geometry ID plus the layer index and material. Other elements, and
+    /// non-body representations, come back as a single sub-mesh keyed by the
+    /// element ID.
+    pub fn process_element_with_layers(
+        &self,
+        element: &DecodedEntity,
+        decoder: &mut EntityDecoder,
+        void_index: &rustc_hash::FxHashMap<u32, Vec<u32>>,
+    ) -> Result<SubMeshCollection> {
+        let mesh = self.process_element_with_voids(element, decoder, void_index)?;
+        let mut sub_meshes = SubMeshCollection::new();
+
+        let split = match self.material_layers.get(&element.id) {
+            Some(split)
+                if self.representation_selection.prefers_body()
+                    && has_layered_geometry(&element.ifc_type)
+                    && !mesh.is_empty() =>
+            {
+                split
+            }
+            _ => {
+                sub_meshes.add(element.id, mesh);
+                return Ok(sub_meshes);
+            }
+        };
+
+        // Same frame as the mesh: scaled placement with the RTC offset applied
+        let placement = self.rtc_adjusted(&self.element_placement(element, decoder)?);
+        let layer_meshes = split_mesh_by_layers(&mesh, &placement, split);
+        for (layer, layer_mesh) in split.layers.iter().zip(layer_meshes) {
+            sub_meshes.add_layer(
+                layer.layer_id.unwrap_or(element.id),
+                layer.index,
+                layer.material_id,
+                layer_mesh,
+            );
+        }
+
+        // Keep the element visible if the layers do not match its geometry
+        if sub_meshes.is_empty() {
+            sub_meshes.add(element.id, mesh);
+        }
+        Ok(sub_meshes)
+    }
+
     /// Process element with void subtraction (openings)
-    /// Process element with voids using optimized plane clipping
     ///
-    /// This approach is more efficient than full 3D CSG for rectangular openings:
-    /// 1. Get chamfered wall mesh (preserves chamfered corners)
-    /// 2. For each opening, use optimized box cutting with internal face generation
-    /// 3. Apply any clipping operations (roof clips) from original representation
+    /// Each opening is cut with the most exact method the geometry allows:
+    /// 1. Hosts whose body is a single extrusion subtract prismatic openings from the
+    ///    profile before extrusion, with 3D CSG only for the remaining openings
+    /// 2. Otherwise, axis-aligned boxes through the wall use optimized box cutting
+    ///    with internal face generation
+    /// 3. Any other opening (rotated, circular, arched, B-rep) is subtracted with mesh CSG
+    /// 4. Clipping operations (roof clips) from the original representation are applied last
     #[inline]
     pub fn process_element_with_voids(
         &self,
@@ -871,6 +954,11 @@ impl GeometryRouter {
         decoder: &mut EntityDecoder,
         void_index: &rustc_hash::FxHashMap<u32, Vec<u32>>,
     ) -> Result<Mesh> {
+        // Openings only cut body geometry, not axes, footprints or boxes
+        if !self.representation_selection.prefers_body() {
+            return self.process_element(element, decoder);
+        }
+
         // Check if this element has any openings
         let opening_ids = match void_index.get(&element.id) {
             Some(ids) if !ids.is_empty() => ids,
@@ -879,27 +967,19 @@ impl GeometryRouter {
                 return self.process_element(element, decoder);
             }
         };
-        
-        // SAFETY: Skip void subtraction for elements with too many openings
-        // This prevents CSG operations from causing panics or excessive processing time
-        // Elements with many openings (like curtain walls) are better handled without CSG
-        const MAX_OPENINGS: usize = 15;
-        if opening_ids.len() > MAX_OPENINGS {
-            // Just return the base mesh without void subtraction
-            return self.process_element(element, decoder);
+
+        // STEP 1: Exact profile-level subtraction for extruded hosts
+        if let Ok(Some(mesh)) =
+            self.try_process_extrusion_with_voids_2d(element, decoder, opening_ids)
+        {
+            return Ok(mesh);
         }
 
-        // STEP 1: Get chamfered wall mesh (preserves chamfered corners at intersections)
-        let wall_mesh = match self.process_element(element, decoder) {
-            Ok(m) => m,
-            Err(_) => {
-                return self.process_element(element, decoder);
-            }
-        };
+        // STEP 2: Get chamfered wall mesh (preserves chamfered corners at intersections)
+        let wall_mesh = self.process_element(element, decoder)?;
 
         // OPTIMIZATION: Only extract clipping planes if element actually has them
         // This skips expensive profile extraction for ~95% of elements
-        use nalgebra::Vector3;
         let world_clipping_planes: Vec<(Point3<f64>, Vector3<f64>, bool)> =
             if self.has_clipping_planes(element, decoder) {
                 // Get element's ObjectPlacement transform (for clipping planes)
@@ -928,60 +1008,7 @@ impl GeometryRouter {
             } else {
                 Vec::new()
             };
-        
-        // STEP 5: Collect opening info (bounds for rectangular, full mesh for non-rectangular)
-        // For rectangular openings, get individual bounds per representation item to handle
-        // disconnected geometry (e.g., two separate window openings in one IfcOpeningElement)
-        enum OpeningType {
-            Rectangular(Point3<f64>, Point3<f64>),  // min, max bounds
-            NonRectangular(Mesh),                    // full mesh for CSG
-        }
-
-        let mut openings: Vec<OpeningType> = Vec::new();
-        for &opening_id in opening_ids.iter() {
-            let opening_entity = match decoder.decode_by_id(opening_id) {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-
-            let opening_mesh = match self.process_element(&opening_entity, decoder) {
-                Ok(m) if !m.is_empty() => m,
-                _ => continue,
-            };
-
-            let vertex_count = opening_mesh.positions.len() / 3;
-
-            if vertex_count > 100 {
-                // Non-rectangular (circular, arched, etc.) - use full CSG
-                openings.push(OpeningType::NonRectangular(opening_mesh));
-            } else {
-                // Rectangular - get individual bounds for each representation item
-                // This handles disconnected geometry (multiple boxes with gaps between them)
-                let item_bounds = self.get_opening_item_bounds(&opening_entity, decoder)
-                    .unwrap_or_default();
-
-                if !item_bounds.is_empty() {
-                    // Use individual item bounds for disconnected geometry
-                    for (min_pt, max_pt) in item_bounds {
-                        openings.push(OpeningType::Rectangular(min_pt, max_pt));
-                    }
-                } else {
-                    // Fallback to combined mesh bounds when individual bounds unavailable
-                    let (open_min, open_max) = opening_mesh.bounds();
-                    let min_f64 = Point3::new(open_min.x as f64, open_min.y as f64, open_min.z as f64);
-                    let max_f64 = Point3::new(open_max.x as f64, open_max.y as f64, open_max.z as f64);
-                    openings.push(OpeningType::Rectangular(min_f64, max_f64));
-                }
-            }
-        }
-
-        if openings.is_empty() {
-            return self.process_element(element, decoder);
-        }
 
-        // STEP 6: Cut openings using appropriate method
-        use crate::csg::ClippingProcessor;
-        let clipper = ClippingProcessor::new();
         let mut result = wall_mesh;
 
         // Get wall bounds for clamping opening faces (from result before cutting)
@@ -997,8 +1024,24 @@ impl GeometryRouter {
             wall_max_f32.z as f64,
         );
 
-        // Validate wall mesh ONCE before CSG operations (not per-iteration)
-        // This avoids O(n) validation on every loop iteration
+        // STEP 3: Split opening items into boxes cutting straight through the wall
+        // and general solids
+        let mut box_openings: Vec<(Point3<f64>, Point3<f64>)> = Vec::new();
+        let mut csg_openings: Vec<Mesh> = Vec::new();
+        for opening_mesh in self.collect_opening_meshes(opening_ids, decoder) {
+            match axis_aligned_box(&opening_mesh) {
+                Some((min, max)) if cuts_through(&min, &max, &wall_min, &wall_max) => {
+                    box_openings.push((min, max))
+                }
+                _ => csg_openings.push(opening_mesh),
+            }
+        }
+
+        if box_openings.is_empty() && csg_openings.is_empty() {
+            return Ok(result);
+        }
+
+        // Validate wall mesh ONCE before cutting (not per-iteration)
         let wall_valid = !result.is_empty()
             && result.positions.iter().all(|&v| v.is_finite())
             && result.triangle_count() >= 4;
@@ -1008,64 +1051,30 @@ impl GeometryRouter {
             return Ok(result);
         }
 
-        // Track CSG operations to prevent excessive complexity
-        let mut csg_operation_count = 0;
-        const MAX_CSG_OPERATIONS: usize = 10; // Limit to prevent runaway CSG
-
-        for (_idx, opening) in openings.iter().enumerate() {
-            match opening {
-                OpeningType::Rectangular(open_min, open_max) => {
-                    // Use optimized rectangular opening cut (safe, doesn't use csgrs)
-                    result = self.cut_rectangular_opening(&result, *open_min, *open_max, wall_min, wall_max);
-                }
-                OpeningType::NonRectangular(opening_mesh) => {
-                    // Safety: limit total CSG operations to prevent crashes on complex geometry
-                    if csg_operation_count >= MAX_CSG_OPERATIONS {
-                        // Skip remaining CSG operations
-                        continue;
-                    }
-
-                    // Validate opening mesh before CSG (only once per opening)
-                    let opening_valid = !opening_mesh.is_empty()
-                        && opening_mesh.positions.iter().all(|&v| v.is_finite())
-                        && opening_mesh.positions.len() >= 9; // At least 3 vertices
-
-                    if !opening_valid {
-                        // Skip invalid opening
-                        continue;
-                    }
+        // STEP 4: Cut box openings (safe, doesn't use csgrs)
+        for (open_min, open_max) in box_openings {
+            result = self.cut_rectangular_opening(&result, open_min, open_max, wall_min, wall_max);
+        }
 
-                    // Use full CSG subtraction for non-rectangular shapes
-                    // Note: mesh_to_csgrs validates and filters invalid triangles internally
-                    match clipper.subtract_mesh(&result, opening_mesh) {
-                        Ok(csg_result) => {
-                            // Validate result is not degenerate
-                            if !csg_result.is_empty() && csg_result.triangle_count() >= 4 {
-                                result = csg_result;
-                            }
-                            // If result is degenerate, keep previous result
-                        }
-                        Err(_) => {
-                            // Keep original result if CSG fails
-                        }
-                    }
-                    csg_operation_count += 1;
-                }
-            }
+        // STEP 5: Subtract all other openings with mesh CSG in one batch
+        // Elements with very many non-box openings keep those uncut to bound processing time
+        if !csg_openings.is_empty() && csg_openings.len() <= MAX_CSG_OPENINGS {
+            let clipper = ClippingProcessor::new();
+            result = clipper.subtract_meshes_with_fallback(&result, &csg_openings);
         }
 
-        // STEP 7: Apply clipping planes (roof clips) if any
+        // STEP 6: Apply clipping planes (roof clips) if any
         if !world_clipping_planes.is_empty() {
-            use crate::csg::{ClippingProcessor, Plane};
+            use crate::csg::Plane;
             let clipper = ClippingProcessor::new();
-            
-            for (_clip_idx, (plane_point, plane_normal, agreement)) in world_clipping_planes.iter().enumerate() {
+
+            for (plane_point, plane_normal, agreement) in world_clipping_planes.iter() {
                 let clip_normal = if *agreement {
                     *plane_normal
                 } else {
                     -*plane_normal
                 };
-                
+
                 let plane = Plane::new(*plane_point, clip_normal);
                 if let Ok(clipped) = clipper.clip_mesh(&result, &plane) {
                     if !clipped.is_empty() {
@@ -1077,7 +1086,7 @@ impl GeometryRouter {
 
         Ok(result)
     }
-    
+
     /// Cut a rectangular opening from a mesh using optimized plane clipping
     /// 
     /// This is more efficient than full CSG because:
@@ -1934,14 +1943,17 @@ impl GeometryRouter {
     /// Process element with voids using 2D profile-level operations
     ///
     /// This is a smarter and more efficient approach that:
-    /// 1. Classifies voids as coplanar (can subtract in 2D) or non-planar (need 3D CSG)
-    /// 2. Subtracts coplanar voids at the 2D profile level before extrusion
-    /// 3. Falls back to 3D CSG only for non-planar voids
+    /// 1. Classifies voids as prisms along the extrusion (can subtract in 2D) or not (need 3D CSG)
+    /// 2. Subtracts prismatic voids at the 2D profile level, slab by slab over their depth range
+    /// 3. Falls back to 3D CSG only for the remaining voids
     ///
     /// Benefits:
     /// - 10-25x faster than full 3D CSG for most openings
     /// - More reliable, especially for floors/slabs with many penetrations
     /// - Cleaner geometry with fewer degenerate triangles
+    ///
+    /// Hosts whose body is not a single extrusion use the 3D path of
+    /// [`Self::process_element_with_voids`].
     #[inline]
     pub fn process_element_with_voids_2d(
         &self,
@@ -1949,7 +1961,6 @@ impl GeometryRouter {
         decoder: &mut EntityDecoder,
         void_index: &VoidIndex,
     ) -> Result<Mesh> {
-        // Check if this element has any openings
         let opening_ids = void_index.get_voids(element.id);
 
         if opening_ids.is_empty() {
@@ -1957,18 +1968,10 @@ impl GeometryRouter {
             return self.process_element(element, decoder);
         }
 
-        // Try to extract extrusion parameters for 2D void processing
-        // If the element isn't an extrusion, fall back to 3D CSG
-        match self.try_process_extrusion_with_voids_2d(element, decoder, opening_ids) {
-            Ok(Some(mesh)) => Ok(mesh),
-            Ok(None) | Err(_) => {
-                // Fall back to traditional 3D CSG approach
-                let void_map: FxHashMap<u32, Vec<u32>> = [(element.id, opening_ids.to_vec())]
-                    .into_iter()
-                    .collect();
-                self.process_element_with_voids(element, decoder, &void_map)
-            }
-        }
+        let void_map: FxHashMap<u32, Vec<u32>> = [(element.id, opening_ids.to_vec())]
+            .into_iter()
+            .collect();
+        self.process_element_with_voids(element, decoder, &void_map)
     }
 
     /// Try to process an extrusion with 2D void subtraction
@@ -1997,44 +2000,36 @@ impl GeometryRouter {
             return Ok(None);
         }
 
-        // Find an IfcExtrudedAreaSolid in the representations
         let representations_attr = match representation.get(2) {
             Some(attr) => attr,
             None => return Ok(None),
         };
 
         let representations = decoder.resolve_ref_list(representations_attr)?;
+        let representations = self.representation_selection.select(representations, decoder);
 
-        // Look for extruded area solid
+        // The body must be exactly one extruded area solid; additional items,
+        // boolean clippings or B-reps need the mesh path
+        let mut items = Vec::new();
         for shape_rep in &representations {
-            if shape_rep.ifc_type != IfcType::IfcShapeRepresentation {
-                continue;
+            if let Some(items_attr) = shape_rep.get(3) {
+                items.extend(decoder.resolve_ref_list(items_attr)?);
             }
+        }
 
-            let items_attr = match shape_rep.get(3) {
-                Some(attr) => attr,
-                None => continue,
-            };
-
-            let items = decoder.resolve_ref_list(items_attr)?;
-
-            for item in &items {
-                if item.ifc_type == IfcType::IfcExtrudedAreaSolid {
-                    // Found an extrusion - try 2D void processing
-                    return self.process_extrusion_with_voids_2d_impl(
-                        element,
-                        item,
-                        decoder,
-                        opening_ids,
-                    );
-                }
+        match items.as_slice() {
+            [extrusion] if extrusion.ifc_type == IfcType::IfcExtrudedAreaSolid => {
+                self.process_extrusion_with_voids_2d_impl(element, extrusion, decoder, opening_ids)
             }
+            _ => Ok(None),
         }
-
-        Ok(None)
     }
 
     /// Implementation of 2D void processing for extrusions
+    ///
+    /// Works in the extrusion's swept space (profile in XY, extrusion along Z, file units),
+    /// mapped to world space by the same Position, unit scale, placement and RTC offset
+    /// that `process_element` applies, so the result matches the plain element mesh.
     fn process_extrusion_with_voids_2d_impl(
         &self,
         element: &DecodedEntity,
@@ -2062,7 +2057,10 @@ impl GeometryRouter {
             None => return Ok(None),
         };
 
-        let local_extrusion_direction = self.parse_direction(&direction_entity)?;
+        let direction = match self.parse_direction(&direction_entity)?.try_normalize(1e-12) {
+            Some(d) => d,
+            None => return Ok(None),
+        };
 
         // Get position transform (attribute 1)
         let position_transform = if let Some(pos_attr) = extrusion.get(1) {
@@ -2079,33 +2077,31 @@ impl GeometryRouter {
             Matrix4::identity()
         };
 
-        // Transform extrusion direction from local to world coordinates
-        // ExtrudedDirection is specified in Position's local coordinate system
-        let extrusion_direction = {
-            let rot_x = Vector3::new(
-                position_transform[(0, 0)],
-                position_transform[(1, 0)],
-                position_transform[(2, 0)],
-            );
-            let rot_y = Vector3::new(
-                position_transform[(0, 1)],
-                position_transform[(1, 1)],
-                position_transform[(2, 1)],
-            );
-            let rot_z = Vector3::new(
-                position_transform[(0, 2)],
-                position_transform[(1, 2)],
-                position_transform[(2, 2)],
-            );
-            (rot_x * local_extrusion_direction.x
-                + rot_y * local_extrusion_direction.y
-                + rot_z * local_extrusion_direction.z)
-                .normalize()
-        };
+        // Sweep matrix matching ExtrudedAreaSolidProcessor: downward extrusions are
+        // shifted, slanted ones shear the extrusion axis
+        let sweep = if direction.x.abs() < 0.001 && direction.y.abs() < 0.001 {
+            if direction.z < 0.0 {
+                Matrix4::new_translation(&Vector3::new(0.0, 0.0, -depth))
+            } else {
+                Matrix4::identity()
+            }
+        } else if direction.z > 1e-6 {
+            let mut shear = Matrix4::identity();
+            shear[(0, 2)] = direction.x;
+            shear[(1, 2)] = direction.y;
+            shear[(2, 2)] = direction.z;
+            shear
+        } else {
+            return Ok(None);
+        };
 
-        // Get element placement transform
-        let element_transform = self.get_placement_transform_from_element(element, decoder)?;
-        let combined_transform = element_transform * position_transform;
+        // Swept space to world space, including unit scale and RTC offset
+        let mut element_transform = self.get_placement_transform_from_element(element, decoder)?;
+        self.scale_transform(&mut element_transform);
+        let swept_to_world = self.rtc_adjusted(&element_transform)
+            * Matrix4::new_scaling(self.unit_scale)
+            * position_transform
+            * sweep;
 
         // Get swept area (profile) - attribute 0
         let profile_attr = match extrusion.get(0) {
@@ -2118,126 +2114,40 @@ impl GeometryRouter {
             None => return Ok(None),
         };
 
-        // Extract base 2D profile
-        let base_profile = match self.extract_profile_2d(&profile_entity, decoder) {
-            Ok(p) => p,
-            Err(_) => return Ok(None),
+        // Same profile the extrusion processor would sweep (including profile Position)
+        let profiles = ProfileProcessor::new(self.schema.clone());
+        let base_profile = match profiles.process(&profile_entity, decoder) {
+            Ok(p) if p.outer.len() >= 3 => p,
+            _ => return Ok(None),
         };
 
-        // Process opening meshes and classify them
-        let mut void_meshes: Vec<Mesh> = Vec::new();
-
-        for &opening_id in opening_ids {
-            let opening_entity = match decoder.decode_by_id(opening_id) {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-
-            let opening_mesh = match self.process_element(&opening_entity, decoder) {
-                Ok(m) if !m.is_empty() => m,
-                _ => continue,
-            };
-
-            void_meshes.push(opening_mesh);
-        }
-
-        if void_meshes.is_empty() {
-            // No valid openings - just process the extrusion normally
-            let processor = self.processors.get(&IfcType::IfcExtrudedAreaSolid);
-            if let Some(proc) = processor {
-                let mut mesh = proc.process(extrusion, decoder, &self.schema)?;
-                self.scale_mesh(&mut mesh);
-                self.apply_placement(element, decoder, &mut mesh)?;
-                return Ok(Some(mesh));
-            }
-            return Ok(None);
-        }
-
-        // Classify voids
-        // Use unscaled depth since void_meshes are in file units (not yet scaled)
+        // Classify world-space opening items against the swept space
+        let void_meshes = self.collect_opening_meshes(opening_ids, decoder);
+        let extrusion_direction = swept_to_world
+            .transform_vector(&Vector3::new(0.0, 0.0, 1.0))
+            .normalize();
         let analyzer = VoidAnalyzer::new();
 
         let classifications: Vec<VoidClassification> = void_meshes
             .iter()
-            .map(|mesh| {
-                analyzer.classify_void(
-                    mesh,
-                    &combined_transform,
-                    &extrusion_direction.normalize(),
-                    depth,
-                )
-            })
+            .map(|mesh| analyzer.classify_void(mesh, &swept_to_world, &extrusion_direction, depth))
             .collect();
 
-        // Extract coplanar and non-planar voids
         let coplanar_voids = extract_coplanar_voids(&classifications);
         let nonplanar_voids = extract_nonplanar_voids(classifications);
 
-        // Process coplanar voids at 2D level
-        let profile_with_voids = if !coplanar_voids.is_empty() {
-            // Collect through-void contours for 2D subtraction
-            let through_contours: Vec<Vec<Point2<f64>>> = coplanar_voids
-                .iter()
-                .filter(|v| v.is_through)
-                .map(|v| v.contour.clone())
-                .collect();
-
-            // Subtract voids from profile
-            let modified_profile = if !through_contours.is_empty() {
-                match subtract_multiple_2d(&base_profile, &through_contours) {
-                    Ok(p) => p,
-                    Err(_) => base_profile.clone(),
-                }
-            } else {
-                base_profile.clone()
-            };
-
-            // Create profile with partial-depth voids
-            let partial_voids: Vec<VoidInfo> = coplanar_voids
-                .into_iter()
-                .filter(|v| !v.is_through)
-                .map(|v| VoidInfo {
-                    contour: v.contour,
-                    depth_start: v.depth_start,
-                    depth_end: v.depth_end,
-                    is_through: false,
-                })
-                .collect();
-
-            Profile2DWithVoids::new(modified_profile, partial_voids)
-        } else {
-            Profile2DWithVoids::from_profile(base_profile)
-        };
-
-        // Extrude with voids
+        // Extrude with prismatic voids removed slab by slab
         use crate::extrusion::extrude_profile_with_voids;
 
-        let mut mesh = match extrude_profile_with_voids(&profile_with_voids, depth, None) {
-            Ok(m) => m,
-            Err(_) => {
-                // Fall back to normal extrusion
-                let processor = self.processors.get(&IfcType::IfcExtrudedAreaSolid);
-                if let Some(proc) = processor {
-                    proc.process(extrusion, decoder, &self.schema)?
-                } else {
-                    return Ok(None);
-                }
-            }
-        };
-
-        // Apply extrusion position transform (with RTC offset)
-        if position_transform != Matrix4::identity() {
-            self.transform_mesh(&mut mesh, &position_transform);
-        }
-
-        // Scale mesh
-        self.scale_mesh(&mut mesh);
-
-        // Apply element placement
-        self.apply_placement(element, decoder, &mut mesh)?;
+        let profile_with_voids = Profile2DWithVoids::new(base_profile, coplanar_voids);
+        let mut mesh =
+            match extrude_profile_with_voids(&profile_with_voids, depth, Some(swept_to_world)) {
+                Ok(m) => m,
+                Err(_) => return Ok(None),
+            };
 
-        // Handle non-planar voids with 3D CSG
-        if !nonplanar_voids.is_empty() {
+        // Handle remaining voids with 3D CSG
+        if !nonplanar_voids.is_empty() && nonplanar_voids.len() <= MAX_CSG_OPENINGS {
             let clipper = ClippingProcessor::new();
             mesh = clipper.subtract_meshes_with_fallback(&mesh, &nonplanar_voids);
         }
@@ -2445,67 +2355,14 @@ impl GeometryRouter {
             Error::geometry("IfcProductDefinitionShape missing Representations".to_string())
         })?;
 
+        // Representations chosen by the selection (solid body by default)
         let representations = decoder.resolve_ref_list(representations_attr)?;
+        let representations = self.representation_selection.select(representations, decoder);
 
         // Process all representations and merge meshes
         let mut combined_mesh = Mesh::new();
 
-        // Check for direct geometry
-        let has_direct_geometry = representations.iter().any(|rep| {
-            if rep.ifc_type != IfcType::IfcShapeRepresentation {
-                return false;
-            }
-            if let Some(rep_type_attr) = rep.get(2) {
-                if let Some(rep_type) = rep_type_attr.as_string() {
-                    matches!(
-                        rep_type,
-                        "Body"
-                            | "SweptSolid"
-                            | "Brep"
-                            | "CSG"
-                            | "Clipping"
-                            | "SurfaceModel"
-                            | "Tessellation"
-                            | "AdvancedSweptSolid"
-                            | "AdvancedBrep"
-                    )
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        });
-
         for shape_rep in representations {
-            if shape_rep.ifc_type != IfcType::IfcShapeRepresentation {
-                continue;
-            }
-
-            if let Some(rep_type_attr) = shape_rep.get(2) {
-                if let Some(rep_type) = rep_type_attr.as_string() {
-                    if rep_type == "MappedRepresentation" && has_direct_geometry {
-                        continue;
-                    }
-
-                    if !matches!(
-                        rep_type,
-                        "Body"
-                            | "SweptSolid"
-                            | "Brep"
-                            | "CSG"
-                            | "Clipping"
-                            | "SurfaceModel"
-                            | "Tessellation"
-                            | "MappedRepresentation"
-                            | "AdvancedSweptSolid"
-                            | "AdvancedBrep"
-                    ) {
-                        continue;
-                    }
-                }
-            }
-
             let items_attr = shape_rep.get(3).ok_or_else(|| {
                 Error::geometry("IfcShapeRepresentation missing Items".to_string())
             })?;
@@ -2524,6 +2381,41 @@ impl GeometryRouter {
         Ok((combined_mesh, transform))
     }
 
+    /// Element mesh in its own coordinates plus placement, both in meters
+    ///
+    /// Used for instanced export. The placement is relative to the RTC
+    /// offset. Elements with openings cannot share their geometry, so they
+    /// come back as a world-space mesh from `process_element_with_voids` with
+    /// an identity placement.
+    pub fn process_element_local(
+        &self,
+        element: &DecodedEntity,
+        decoder: &mut EntityDecoder,
+        void_index: &rustc_hash::FxHashMap<u32, Vec<u32>>,
+    ) -> Result<(Mesh, Matrix4<f64>)> {
+        if void_index.contains_key(&element.id) {
+            let mesh = self.process_element_with_voids(element, decoder, void_index)?;
+            return Ok((mesh, Matrix4::identity()));
+        }
+        // Item meshes are already in meters, only the placement is in file units
+        let (mesh, mut transform) = self.process_element_with_transform(element, decoder)?;
+        self.scale_transform(&mut transform);
+        Ok((mesh, self.rtc_adjusted(&transform)))
+    }
+
+    /// Placement of an element with its translation in meters
+    ///
+    /// Identity when the element has no ObjectPlacement.
+    pub fn element_placement(
+        &self,
+        element: &DecodedEntity,
+        decoder: &mut EntityDecoder,
+    ) -> Result<Matrix4<f64>> {
+        let mut transform = self.get_placement_transform_from_element(element, decoder)?;
+        self.scale_transform(&mut transform);
+        Ok(transform)
+    }
+
     /// Get placement transform from element without applying it
     fn get_placement_transform_from_element(
         &self,
@@ -2560,16 +2452,51 @@ impl GeometryRouter {
 
         // Check FacetedBrep cache first (from batch preprocessing)
         if item.ifc_type == IfcType::IfcFacetedBrep {
-            if let Some(mut mesh) = self.take_cached_faceted_brep(item.id) {
+            let cached = self
+                .take_cached_faceted_brep(item.id)
+                .or_else(|| self.faceted_breps.get(&item.id).cloned());
+            if let Some(mut mesh) = cached {
                 self.scale_mesh(&mut mesh);
                 let cached = self.get_or_cache_by_hash(mesh);
                 return Ok((*cached).clone());
             }
         }
 
+        // Non-body representations: bounding boxes, footprint and axis curves
+        match item.ifc_type {
+            IfcType::IfcBoundingBox => {
+                let mut mesh = self.process_bounding_box(item, decoder)?;
+                self.scale_mesh(&mut mesh);
+                return Ok(mesh);
+            }
+            IfcType::IfcGeometricCurveSet
+            | IfcType::IfcGeometricSet
+            | IfcType::IfcPolyline
+            | IfcType::IfcIndexedPolyCurve
+            | IfcType::IfcCompositeCurve
+            | IfcType::IfcTrimmedCurve
+            | IfcType::IfcLine
+            | IfcType::IfcCircle
+            | IfcType::IfcCartesianPoint => {
+                let mut mesh = self.process_curve_fill(item, decoder, 0)?;
+                self.scale_mesh(&mut mesh);
+                return Ok(mesh);
+            }
+            _ => {}
+        }
+
         // Check if we have a processor for this type
         if let Some(processor) = self.processors.get(&item.ifc_type) {
             let mut mesh = processor.process(item, decoder, &self.schema)?;
+            if item.ifc_type == IfcType::IfcTriangulatedFaceSet {
+                if let Some(textured) = self
+                    .texture_maps
+                    .get(&item.id)
+                    .and_then(|map| map.apply(&mesh))
+                {
+                    mesh = textured;
+                }
+            }
             self.scale_mesh(&mut mesh);
 
             // Deduplicate by hash - buildings with repeated floors have identical geometry
@@ -2605,6 +2532,318 @@ impl GeometryRouter {
         }
     }
 
+    /// Mesh an IfcBoundingBox (Corner, XDim, YDim, ZDim) as a closed box
+    fn process_bounding_box(&self, item: &DecodedEntity, decoder: &mut EntityDecoder) -> Result<Mesh> {
+        let corner = self.parse_cartesian_point(item, decoder, 0)?;
+        let dims = Vector3::new(
+            item.get_float(1).unwrap_or(0.0),
+            item.get_float(2).unwrap_or(0.0),
+            item.get_float(3).unwrap_or(0.0),
+        );
+        if dims.x <= 0.0 || dims.y <= 0.0 || dims.z <= 0.0 {
+            return Ok(Mesh::new());
+        }
+
+        let max = corner + dims;
+        let mut mesh = Mesh::with_capacity(24, 36);
+        // (normal, four corners counter-clockwise seen from outside)
+        let faces = [
+            (Vector3::new(-1.0, 0.0, 0.0), [(0, 0, 0), (0, 0, 1), (0, 1, 1), (0, 1, 0)]),
+            (Vector3::new(1.0, 0.0, 0.0), [(1, 0, 0), (1, 1, 0), (1, 1, 1), (1, 0, 1)]),
+            (Vector3::new(0.0, -1.0, 0.0), [(0, 0, 0), (1, 0, 0), (1, 0, 1), (0, 0, 1)]),
+            (Vector3::new(0.0, 1.0, 0.0), [(0, 1, 0), (0, 1, 1), (1, 1, 1), (1, 1, 0)]),
+            (Vector3::new(0.0, 0.0, -1.0), [(0, 0, 0), (0, 1, 0), (1, 1, 0), (1, 0, 0)]),
+            (Vector3::new(0.0, 0.0, 1.0), [(0, 0, 1), (1, 0, 1), (1, 1, 1), (0, 1, 1)]),
+        ];
+        for (normal, corners) in faces {
+            let base = mesh.vertex_count() as u32;
+            for (x, y, z) in corners {
+                let pick = |i: i32, lo: f64, hi: f64| if i == 0 { lo } else { hi };
+                mesh.add_vertex(
+                    Point3::new(
+                        pick(x, corner.x, max.x),
+                        pick(y, corner.y, max.y),
+                        pick(z, corner.z, max.z),
+                    ),
+                    normal,
+                );
+            }
+            mesh.add_triangle(base, base + 1, base + 2);
+            mesh.add_triangle(base, base + 2, base + 3);
+        }
+        Ok(mesh)
+    }
+
+    /// Mesh closed 2D curves (footprints) as flat fills in the XY plane
+    ///
+    /// Open curves and points carry no area and give an empty mesh.
+    fn process_curve_fill(
+        &self,
+        item: &DecodedEntity,
+        decoder: &mut EntityDecoder,
+        depth: usize,
+    ) -> Result<Mesh> {
+        match item.ifc_type {
+            IfcType::IfcGeometricCurveSet | IfcType::IfcGeometricSet => {
+                let mut combined = Mesh::new();
+                if depth >= 4 {
+                    return Ok(combined);
+                }
+                // Attribute 0: Elements
+                let elements = match item.get(0) {
+                    Some(attr) => decoder.resolve_ref_list(attr)?,
+                    None => Vec::new(),
+                };
+                for element in elements {
+                    combined.merge(&self.process_curve_fill(&element, decoder, depth + 1)?);
+                }
+                Ok(combined)
+            }
+            IfcType::IfcPolyline | IfcType::IfcIndexedPolyCurve | IfcType::IfcCompositeCurve => {
+                let mut points = self.extract_curve_points(item, decoder)?;
+                points.dedup_by(|a, b| (*a - *b).norm() < 1e-9);
+                let closed = points.len() >= 4
+                    && (points[0] - points[points.len() - 1]).norm() < 1e-6;
+                if !closed {
+                    return Ok(Mesh::new());
+                }
+                points.pop();
+
+                let Ok(indices) = triangulate_polygon(&points) else {
+                    return Ok(Mesh::new());
+                };
+                let mut mesh = Mesh::with_capacity(points.len(), indices.len());
+                for point in &points {
+                    mesh.add_vertex(Point3::new(point.x, point.y, 0.0), Vector3::new(0.0, 0.0, 1.0));
+                }
+                for triangle in indices.chunks_exact(3) {
+                    mesh.add_triangle(triangle[0] as u32, triangle[1] as u32, triangle[2] as u32);
+                }
+                Ok(mesh)
+            }
+            _ => Ok(Mesh::new()),
+        }
+    }
+
+    /// Process the line geometry of an element
+    ///
+    /// `IfcGrid` gives its U, V and W axes with the axis tags as labels at both
+    /// ends. Other elements give the curves of their curve representations
+    /// (`Curve2D`, `GeometricCurveSet`, `Annotation2D`, ...) or, with an
+    /// explicit selection, of the representations it picks. Items without
+    /// line geometry are ignored, so solids only ever come from `process_element`.
+    pub fn process_element_curves(
+        &self,
+        element: &DecodedEntity,
+        decoder: &mut EntityDecoder,
+    ) -> Result<CurveSet> {
+        let mut curves = CurveSet::new();
+
+        if element.ifc_type == IfcType::IfcGrid {
+            let profiles = ProfileProcessor::new(self.schema.clone());
+            self.collect_grid_axes(element, decoder, &profiles, &mut curves)?;
+        } else {
+            let representation = match element.get(6) {
+                Some(attr) if !attr.is_null() => decoder.resolve_ref(attr)?,
+                _ => None,
+            };
+            let Some(representation) = representation
+                .filter(|rep| rep.ifc_type == IfcType::IfcProductDefinitionShape)
+            else {
+                return Ok(curves);
+            };
+
+            let representations = match representation.get(2) {
+                Some(attr) => decoder.resolve_ref_list(attr)?,
+                None => Vec::new(),
+            };
+            let representations = if self.representation_selection.is_default() {
+                representations
+                    .into_iter()
+                    .filter(|rep| {
+                        rep.ifc_type == IfcType::IfcShapeRepresentation
+                            && rep.get_string(2).is_some_and(is_curve_representation_type)
+                    })
+                    .collect()
+            } else {
+                self.representation_selection.select(representations, decoder)
+            };
+            if representations.is_empty() {
+                return Ok(curves);
+            }
+
+            let profiles = ProfileProcessor::new(self.schema.clone());
+            for shape_rep in representations {
+                let items = match shape_rep.get(3) {
+                    Some(attr) => decoder.resolve_ref_list(attr)?,
+                    None => continue,
+                };
+                for item in items {
+                    self.collect_item_curves(&item, decoder, &profiles, &mut curves, 0)?;
+                }
+            }
+        }
+
+        if curves.is_empty() {
+            return Ok(curves);
+        }
+
+        // Same order as meshes: scale to meters, then place
+        if self.unit_scale != 1.0 {
+            curves.transform(&Matrix4::new_scaling(self.unit_scale));
+        }
+        let transform = self.element_placement(element, decoder)?;
+        self.transform_curves(&mut curves, &transform);
+
+        Ok(curves)
+    }
+
+    /// Collect IfcGrid axes (attributes 7-9: UAxes, VAxes, WAxes)
+    fn collect_grid_axes(
+        &self,
+        grid: &DecodedEntity,
+        decoder: &mut EntityDecoder,
+        profiles: &ProfileProcessor,
+        curves: &mut CurveSet,
+    ) -> Result<()> {
+        for attr_index in 7..=9 {
+            let axes = match grid.get(attr_index) {
+                Some(attr) if !attr.is_null() => decoder.resolve_ref_list(attr)?,
+                _ => continue,
+            };
+
+            for axis in axes {
+                // IfcGridAxis: AxisTag, AxisCurve, SameSense
+                let Some(curve_id) = axis.get_ref(1) else {
+                    continue;
+                };
+                let curve = decoder.decode_by_id(curve_id)?;
+                let Ok(points) = profiles.get_curve_points(&curve, decoder) else {
+                    continue;
+                };
+                let polyline = Polyline3D::from_points(&points);
+                if polyline.is_empty() {
+                    continue;
+                }
+
+                if let Some(tag) = axis.get_string(0).filter(|tag| !tag.is_empty()) {
+                    curves.add_label(tag, polyline.point(0));
+                    if !polyline.closed {
+                        curves.add_label(tag, polyline.point(polyline.point_count() - 1));
+                    }
+                }
+                curves.add_polyline(polyline);
+            }
+        }
+        Ok(())
+    }
+
+    /// Collect the line geometry of a representation item, in file units
+    fn collect_item_curves(
+        &self,
+        item: &DecodedEntity,
+        decoder: &mut EntityDecoder,
+        profiles: &ProfileProcessor,
+        curves: &mut CurveSet,
+        depth: usize,
+    ) -> Result<()> {
+        if depth >= 4 {
+            return Ok(());
+        }
+
+        match item.ifc_type {
+            IfcType::IfcGeometricCurveSet | IfcType::IfcGeometricSet => {
+                // Attribute 0: Elements
+                let elements = match item.get(0) {
+                    Some(attr) => decoder.resolve_ref_list(attr)?,
+                    None => Vec::new(),
+                };
+                for element in elements {
+                    self.collect_item_curves(&element, decoder, profiles, curves, depth + 1)?;
+                }
+            }
+            IfcType::IfcMappedItem => {
+                // MappingSource → MappedRepresentation → Items, placed by MappingTarget
+                let Some(source_id) = item.get_ref(0) else {
+                    return Ok(());
+                };
+                let source = decoder.decode_by_id(source_id)?;
+                let Some(mapped_rep_id) = source.get_ref(1) else {
+                    return Ok(());
+                };
+                let mapped_rep = decoder.decode_by_id(mapped_rep_id)?;
+                let items = match mapped_rep.get(3) {
+                    Some(attr) => decoder.resolve_ref_list(attr)?,
+                    None => Vec::new(),
+                };
+
+                let mut mapped = CurveSet::new();
+                for sub_item in items {
+                    self.collect_item_curves(&sub_item, decoder, profiles, &mut mapped, depth + 1)?;
+                }
+                if let Some(target_id) = item.get_ref(1) {
+                    let target = decoder.decode_by_id(target_id)?;
+                    let transform = self.parse_cartesian_transformation_operator(&target, decoder)?;
+                    mapped.transform(&transform);
+                }
+                curves.merge(mapped);
+            }
+            IfcType::IfcAnnotationFillArea => {
+                // OuterBoundary, InnerBoundaries
+                let mut boundaries = Vec::new();
+                if let Some(outer) = item.get(0) {
+                    boundaries.extend(decoder.resolve_ref(outer)?);
+                }
+                if let Some(inner) = item.get(1).filter(|attr| !attr.is_null()) {
+                    boundaries.extend(decoder.resolve_ref_list(inner)?);
+                }
+                for boundary in boundaries {
+                    self.collect_item_curves(&boundary, decoder, profiles, curves, depth + 1)?;
+                }
+            }
+            IfcType::IfcTextLiteral | IfcType::IfcTextLiteralWithExtent => {
+                // Literal, Placement (IfcAxis2Placement), Path
+                let Some(text) = item.get_string(0).filter(|text| !text.is_empty()) else {
+                    return Ok(());
+                };
+                let Some(placement_id) = item.get_ref(1) else {
+                    return Ok(());
+                };
+                let placement = decoder.decode_by_id(placement_id)?;
+                let position = self.parse_cartesian_point(&placement, decoder, 0)?;
+                curves.add_label(text, position);
+            }
+            IfcType::IfcPolyline
+            | IfcType::IfcIndexedPolyCurve
+            | IfcType::IfcCompositeCurve
+            | IfcType::IfcTrimmedCurve
+            | IfcType::IfcCircle
+            | IfcType::IfcEllipse => {
+                // Unsupported curve parameterisations are skipped, not fatal
+                if let Ok(points) = profiles.get_curve_points(item, decoder) {
+                    curves.add_polyline(Polyline3D::from_points(&points));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Apply a placement to curves, with the same RTC handling as `transform_mesh`
+    fn transform_curves(&self, curves: &mut CurveSet, transform: &Matrix4<f64>) {
+        const LARGE_COORD_THRESHOLD: f64 = 1000.0;
+
+        let needs_rtc = self.has_rtc_offset()
+            && (0..3).any(|row| transform[(row, 3)].abs() > LARGE_COORD_THRESHOLD);
+        if needs_rtc {
+            let rtc = self.rtc_offset;
+            let shift = Matrix4::new_translation(&Vector3::new(-rtc.0, -rtc.1, -rtc.2));
+            curves.transform(&(shift * transform));
+        } else {
+            curves.transform(transform);
+        }
+    }
+
     /// Process MappedItem with caching for repeated geometry
     #[inline]
     fn process_mapped_item_cached(
@@ -3025,17 +3264,10 @@ impl GeometryRouter {
     #[inline]
     fn transform_mesh(&self, mesh: &mut Mesh, transform: &Matrix4<f64>) {
         let rtc = self.rtc_offset;
-        const LARGE_COORD_THRESHOLD: f64 = 1000.0;
 
         // Determine RTC need ONCE for the whole mesh based on transform's translation component
         // This ensures all vertices in the mesh use consistent RTC subtraction
-        let tx = transform[(0, 3)];
-        let ty = transform[(1, 3)];
-        let tz = transform[(2, 3)];
-        let needs_rtc = self.has_rtc_offset() &&
-            (tx.abs() > LARGE_COORD_THRESHOLD || ty.abs() > LARGE_COORD_THRESHOLD || tz.abs() > LARGE_COORD_THRESHOLD);
-
-        if needs_rtc {
+        if self.needs_rtc(transform) {
             // Apply RTC offset to all vertices uniformly
             mesh.positions.chunks_exact_mut(3).for_each(|chunk| {
                 let point = Point3::new(chunk[0] as f64, chunk[1] as f64, chunk[2] as f64);
@@ -3066,6 +3298,24 @@ impl GeometryRouter {
         });
     }
 
+    /// Whether `transform_mesh` subtracts the RTC offset for this transform
+    fn needs_rtc(&self, transform: &Matrix4<f64>) -> bool {
+        const LARGE_COORD_THRESHOLD: f64 = 1000.0;
+        self.has_rtc_offset()
+            && (transform[(0, 3)].abs() > LARGE_COORD_THRESHOLD
+                || transform[(1, 3)].abs() > LARGE_COORD_THRESHOLD
+                || transform[(2, 3)].abs() > LARGE_COORD_THRESHOLD)
+    }
+
+    /// Transform with the RTC offset folded in, equivalent to `transform_mesh`
+    fn rtc_adjusted(&self, transform: &Matrix4<f64>) -> Matrix4<f64> {
+        if !self.needs_rtc(transform) {
+            return *transform;
+        }
+        let (x, y, z) = self.rtc_offset;
+        Matrix4::new_translation(&Vector3::new(-x, -y, -z)) * transform
+    }
+
     /// Get schema reference
     pub fn schema(&self) -> &IfcSchema {
         &self.schema
@@ -3078,6 +3328,88 @@ impl Default for GeometryRouter {
     }
 }
 
+/// RepresentationTypes made of curves rather than solids
+fn is_curve_representation_type(rep_type: &str) -> bool {
+    matches!(
+        rep_type,
+        "Curve" | "Curve2D" | "Curve3D" | "GeometricCurveSet" | "GeometricSet" | "Annotation2D"
+    )
+}
+
+/// World bounds of a mesh that is exactly an axis-aligned box
+///
+/// Every vertex must lie on a corner of the bounds and the enclosed volume must
+/// match the bounds' volume, which rules out wedges and other solids built from
+/// box corners.
+fn axis_aligned_box(mesh: &Mesh) -> Option<(Point3<f64>, Point3<f64>)> {
+    if mesh.is_empty() {
+        return None;
+    }
+
+    let (min, max) = mesh.bounds();
+    let min = Point3::new(min.x as f64, min.y as f64, min.z as f64);
+    let max = Point3::new(max.x as f64, max.y as f64, max.z as f64);
+    let size = max - min;
+    let tolerance = size.norm() * 1e-5 + 1e-4;
+    if size.min() <= tolerance {
+        return None;
+    }
+
+    let on_corners = mesh.positions.chunks_exact(3).all(|p| {
+        (0..3).all(|axis| {
+            let v = p[axis] as f64;
+            (v - min[axis]).abs() <= tolerance || (v - max[axis]).abs() <= tolerance
+        })
+    });
+    if !on_corners {
+        return None;
+    }
+
+    let box_volume = size.x * size.y * size.z;
+    let volume = mesh_volume(mesh).abs();
+    ((volume - box_volume).abs() <= box_volume * 1e-3).then_some((min, max))
+}
+
+/// Whether a box opening passes through the wall along its thinnest axis
+///
+/// This is the case `cut_rectangular_opening` handles exactly; recesses and
+/// boxes running along the wall need CSG.
+fn cuts_through(
+    open_min: &Point3<f64>,
+    open_max: &Point3<f64>,
+    wall_min: &Point3<f64>,
+    wall_max: &Point3<f64>,
+) -> bool {
+    let size = open_max - open_min;
+    // Same through-axis choice as generate_opening_faces
+    let axis = if size.y <= size.x && size.y <= size.z {
+        1
+    } else if size.x <= size.y && size.x <= size.z {
+        0
+    } else {
+        2
+    };
+    let tolerance = (wall_max - wall_min).norm() * 1e-6;
+    open_min[axis] <= wall_min[axis] + tolerance && open_max[axis] >= wall_max[axis] - tolerance
+}
+
+/// Signed area of a 2D polygon (shoelace formula)
+fn polygon_area(points: &[Point2<f64>]) -> f64 {
+    let n = points.len();
+    let mut area = 0.0;
+    for i in 0..n {
+        let j = (i + 1) % n;
+        area += points[i].x * points[j].y - points[j].x * points[i].y;
+    }
+    area * 0.5
+}
+
+/// Closed perimeter of a 2D polygon
+fn polygon_perimeter(points: &[Point2<f64>]) -> f64 {
+    let n = points.len();
+    (0..n).map(|i| (points[(i + 1) % n] - points[i]).norm()).sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3125,6 +3457,251 @@ mod tests {
         assert_eq!(vec.y, 0.0);
         assert_eq!(vec.z, 0.0);
     }
+
+    #[test]
+    fn test_extrusion_quantities() {
+        let content = r#"
+#1=IFCCARTESIANPOINT((0.,0.,0.));
+#2=IFCAXIS2PLACEMENT3D(#1,$,$);
+#3=IFCDIRECTION((0.,0.,1.));
+#4=IFCRECTANGLEPROFILEDEF(.AREA.,$,$,2000.,300.);
+#5=IFCEXTRUDEDAREASOLID(#4,#2,#3,3000.);
+#6=IFCSHAPEREPRESENTATION($,'Body','SweptSolid',(#5));
+#7=IFCPRODUCTDEFINITIONSHAPE($,$,(#6));
+#8=IFCWALL('guid',$,$,$,$,$,#7,$);
+#9=IFCDIRECTION((0.,0.5,1.));
+#10=IFCEXTRUDEDAREASOLID(#4,#2,#9,3000.);
+#11=IFCSHAPEREPRESENTATION($,'Body','SweptSolid',(#10));
+#12=IFCPRODUCTDEFINITIONSHAPE($,$,(#11));
+#13=IFCWALL('guid2',$,$,$,$,$,#12,$);
+"#;
+
+        let mut decoder = EntityDecoder::new(content);
+        let router = GeometryRouter::with_scale(0.001);
+
+        let wall = decoder.decode_by_id(8).unwrap();
+        let q = router
+            .extrusion_quantities(&wall, &mut decoder)
+            .unwrap()
+            .unwrap();
+        assert!((q.volume() - 1.8).abs() < 1e-9);
+        assert!((q.surface_area() - (2.0 * 0.6 + 4.6 * 3.0)).abs() < 1e-9);
+
+        // Slanted extrusion falls back to mesh quantities
+        let slanted = decoder.decode_by_id(13).unwrap();
+        assert!(router
+            .extrusion_quantities(&slanted, &mut decoder)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_representation_selection() {
+        let content = r#"
+#1=IFCCARTESIANPOINT((0.,0.,0.));
+#2=IFCAXIS2PLACEMENT3D(#1,$,$);
+#3=IFCDIRECTION((0.,0.,1.));
+#4=IFCRECTANGLEPROFILEDEF(.AREA.,$,$,2.,1.);
+#5=IFCEXTRUDEDAREASOLID(#4,#2,#3,3.);
+#6=IFCSHAPEREPRESENTATION($,'Body','SweptSolid',(#5));
+#7=IFCCARTESIANPOINT((0.,0.));
+#8=IFCCARTESIANPOINT((4.,0.));
+#9=IFCCARTESIANPOINT((4.,2.));
+#10=IFCCARTESIANPOINT((0.,2.));
+#11=IFCPOLYLINE((#7,#8,#9,#10,#7));
+#12=IFCGEOMETRICCURVESET((#11));
+#13=IFCSHAPEREPRESENTATION($,'FootPrint','GeometricCurveSet',(#12));
+#14=IFCBOUNDINGBOX(#1,2.,1.,3.);
+#15=IFCSHAPEREPRESENTATION($,'Box','BoundingBox',(#14));
+#16=IFCPOLYLINE((#7,#8));
+#17=IFCSHAPEREPRESENTATION($,'Axis','Curve2D',(#16));
+#18=IFCPRODUCTDEFINITIONSHAPE($,$,(#6,#13,#15,#17));
+#19=IFCSLAB('guid',$,$,$,$,$,#18,$,$);
+"#;
+
+        let mut decoder = EntityDecoder::new(content);
+        let slab = decoder.decode_by_id(19).unwrap();
+        let mut router = GeometryRouter::new();
+
+        // Default: body only
+        let body = router.process_element(&slab, &mut decoder).unwrap();
+        assert_eq!(body.triangle_count(), 12);
+
+        // Closed footprint curves are filled
+        router.set_representation_selection(RepresentationSelection::parse("FootPrint,Body"));
+        let footprint = router.process_element(&slab, &mut decoder).unwrap();
+        assert_eq!(footprint.triangle_count(), 2);
+        assert!((crate::projected_area(&footprint, 2) - 8.0).abs() < 1e-6);
+
+        router.set_representation_selection(RepresentationSelection::parse("Box"));
+        let bounding_box = router.process_element(&slab, &mut decoder).unwrap();
+        assert_eq!(bounding_box.triangle_count(), 12);
+        assert!((crate::mesh_volume(&bounding_box) - 6.0).abs() < 1e-6);
+
+        // Open axis curves carry no area; missing identifiers fall back
+        router.set_representation_selection(RepresentationSelection::parse("Axis"));
+        assert!(router.process_element(&slab, &mut decoder).unwrap().is_empty());
+        router.set_representation_selection(RepresentationSelection::parse("Clearance,Body"));
+        let fallback = router.process_element(&slab, &mut decoder).unwrap();
+        assert_eq!(fallback.triangle_count(), 12);
+    }
+
+    #[test]
+    fn test_element_curves() {
+        let content = r#"
+#1=IFCCARTESIANPOINT((0.,0.,0.));
+#2=IFCAXIS2PLACEMENT3D(#1,$,$);
+#3=IFCLOCALPLACEMENT($,#2);
+#4=IFCCARTESIANPOINT((0.,0.));
+#5=IFCCARTESIANPOINT((0.,6000.));
+#6=IFCCARTESIANPOINT((5000.,0.));
+#7=IFCCARTESIANPOINT((5000.,6000.));
+#8=IFCCARTESIANPOINT((-1000.,0.));
+#9=IFCCARTESIANPOINT((6000.,0.));
+#10=IFCPOLYLINE((#4,#5));
+#11=IFCPOLYLINE((#6,#7));
+#12=IFCPOLYLINE((#8,#9));
+#13=IFCGRIDAXIS('A',#10,.T.);
+#14=IFCGRIDAXIS('B',#11,.T.);
+#15=IFCGRIDAXIS('1',#12,.T.);
+#16=IFCGRID('guid',$,$,$,$,#3,$,(#13,#14),(#15),$,$);
+#20=IFCPOLYLINE((#4,#6));
+#21=IFCSHAPEREPRESENTATION($,'Axis','Curve2D',(#20));
+#22=IFCRECTANGLEPROFILEDEF(.AREA.,$,$,2.,1.);
+#23=IFCDIRECTION((0.,0.,1.));
+#24=IFCEXTRUDEDAREASOLID(#22,#2,#23,3.);
+#25=IFCSHAPEREPRESENTATION($,'Body','SweptSolid',(#24));
+#26=IFCPRODUCTDEFINITIONSHAPE($,$,(#21,#25));
+#27=IFCWALL('guid',$,$,$,$,#3,#26,$,$);
+"#;
+
+        let mut decoder = EntityDecoder::new(content);
+        let router = GeometryRouter::with_scale(0.001);
+
+        // Grid axes in meters, tagged at both ends
+        let grid = decoder.decode_by_id(16).unwrap();
+        let curves = router.process_element_curves(&grid, &mut decoder).unwrap();
+        assert_eq!(curves.polylines.len(), 3);
+        assert_eq!(curves.segment_count(), 3);
+        assert!((curves.polylines[0].length() - 6.0).abs() < 1e-6);
+        let tags: Vec<&str> = curves.labels.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(tags, vec!["A", "A", "B", "B", "1", "1"]);
+        assert_eq!(curves.labels[3].position, [5.0, 6.0, 0.0]);
+
+        // Wall axis is a curve representation, the body is not
+        let wall = decoder.decode_by_id(27).unwrap();
+        let curves = router.process_element_curves(&wall, &mut decoder).unwrap();
+        assert_eq!(curves.polylines.len(), 1);
+        assert!((curves.polylines[0].length() - 5.0).abs() < 1e-6);
+
+        let mut body_only = GeometryRouter::with_scale(0.001);
+        body_only.set_representation_selection(RepresentationSelection::parse("Body"));
+        assert!(body_only
+            .process_element_curves(&wall, &mut decoder)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_element_local() {
+        let content = r#"
+#1=IFCCARTESIANPOINT((0.,0.,0.));
+#2=IFCAXIS2PLACEMENT3D(#1,$,$);
+#3=IFCCARTESIANPOINT((10000.,0.,3000.));
+#4=IFCAXIS2PLACEMENT3D(#3,$,$);
+#5=IFCLOCALPLACEMENT($,#4);
+#6=IFCRECTANGLEPROFILEDEF(.AREA.,$,$,400.,400.);
+#7=IFCDIRECTION((0.,0.,1.));
+#8=IFCEXTRUDEDAREASOLID(#6,#2,#7,3000.);
+#9=IFCSHAPEREPRESENTATION($,'Body','SweptSolid',(#8));
+#10=IFCPRODUCTDEFINITIONSHAPE($,$,(#9));
+#11=IFCCOLUMN('guid',$,$,$,$,#5,#10,$,$);
+"#;
+
+        let mut decoder = EntityDecoder::new(content);
+        let router = GeometryRouter::with_scale(0.001);
+        let column = decoder.decode_by_id(11).unwrap();
+
+        // Mesh stays around the local origin, placement carries the offset
+        let (mesh, transform) = router
+            .process_element_local(&column, &mut decoder, &FxHashMap::default())
+            .unwrap();
+        let (min, max) = mesh.bounds();
+        assert!((max.z - min.z - 3.0).abs() < 1e-5);
+        assert!((max.x - 0.2).abs() < 1e-5);
+        assert!((transform[(0, 3)] - 10.0).abs() < 1e-9);
+        assert!((transform[(2, 3)] - 3.0).abs() < 1e-9);
+
+        // Hosts of openings come back in world space
+        let mut voids = FxHashMap::default();
+        voids.insert(11, Vec::new());
+        let (world, identity) = router
+            .process_element_local(&column, &mut decoder, &voids)
+            .unwrap();
+        assert_eq!(identity, Matrix4::identity());
+        assert!((world.bounds().0.x - 9.8).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_element_layers() {
+        let content = r#"
+#1=IFCCARTESIANPOINT((0.,0.,0.));
+#2=IFCAXIS2PLACEMENT3D(#1,$,$);
+#3=IFCCARTESIANPOINT((10000.,0.,0.));
+#4=IFCDIRECTION((0.,0.,1.));
+#5=IFCDIRECTION((0.,1.,0.));
+#6=IFCAXIS2PLACEMENT3D(#3,#4,#5);
+#7=IFCLOCALPLACEMENT($,#6);
+#8=IFCCARTESIANPOINT((2000.,0.));
+#9=IFCAXIS2PLACEMENT2D(#8,$);
+#10=IFCRECTANGLEPROFILEDEF(.AREA.,$,#9,4000.,300.);
+#11=IFCEXTRUDEDAREASOLID(#10,#2,#4,3000.);
+#12=IFCSHAPEREPRESENTATION($,'Body','SweptSolid',(#11));
+#13=IFCPRODUCTDEFINITIONSHAPE($,$,(#12));
+#14=IFCWALL('guid',$,$,$,$,#7,#13,$,$);
+#15=IFCCOLUMN('guid2',$,$,$,$,#7,#13,$,$);
+#20=IFCMATERIAL('Concrete',$,$);
+#21=IFCMATERIAL('Plaster',$,$);
+#22=IFCMATERIALLAYER(#20,200.,$,$,$,$,$);
+#23=IFCMATERIALLAYER(#21,100.,$,$,$,$,$);
+#24=IFCMATERIALLAYERSET((#22,#23),$,$);
+#25=IFCMATERIALLAYERSETUSAGE(#24,.AXIS2.,.POSITIVE.,-150.,$);
+#26=IFCRELASSOCIATESMATERIAL('rel',$,$,$,(#14,#15),#25);
+"#;
+
+        let mut decoder = EntityDecoder::new(content);
+        let mut router = GeometryRouter::with_scale(0.001);
+        let layers = crate::build_material_layer_index(content, &mut decoder, 0.001);
+        // Only walls and slabs are split
+        assert_eq!(layers.len(), 1);
+        router.set_material_layers(Arc::new(layers));
+
+        let wall = decoder.decode_by_id(14).unwrap();
+        let sub_meshes = router
+            .process_element_with_layers(&wall, &mut decoder, &FxHashMap::default())
+            .unwrap();
+        assert_eq!(sub_meshes.len(), 2);
+
+        // Placement turns local Y into world -X
+        let core = &sub_meshes.sub_meshes[0];
+        assert_eq!((core.geometry_id, core.layer_index, core.material_id), (22, Some(0), Some(20)));
+        let (min, max) = core.mesh.bounds();
+        assert!((min.x - 9.95).abs() < 1e-4 && (max.x - 10.15).abs() < 1e-4);
+        assert!((crate::mesh_volume(&core.mesh) - 4.0 * 0.2 * 3.0).abs() < 1e-3);
+
+        let finish = &sub_meshes.sub_meshes[1];
+        assert_eq!((finish.geometry_id, finish.material_id), (23, Some(21)));
+        let (min, max) = finish.mesh.bounds();
+        assert!((min.x - 9.85).abs() < 1e-4 && (max.x - 9.95).abs() < 1e-4);
+
+        // Elements without layers come back whole
+        let column = decoder.decode_by_id(15).unwrap();
+        let whole = router
+            .process_element_with_layers(&column, &mut decoder, &FxHashMap::default())
+            .unwrap();
+        assert_eq!(whole.len(), 1);
+        assert_eq!((whole.sub_meshes[0].geometry_id, whole.sub_meshes[0].layer_index), (15, None));
+    }
 }
 
 /// Wall Profile Research Tests
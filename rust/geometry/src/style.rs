@@ -0,0 +1,504 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Style Index
+//!
+//! Resolves the surface material of every geometry-bearing product in a single
+//! scan, so the server, desktop and browser frontends all render the same colours.
+//! Sources are tried in priority order:
+//!
+//! 1. `IfcStyledItem` on the product's representation items (following mapped items)
+//! 2. `IfcMaterialDefinitionRepresentation` of the product's associated material
+//! 3. Styles of the type object: styled representation maps, then its material
+//! 4. Per-type defaults ([`default_color`])
+//!
+//! Materials are interned by style id, so elements sharing a style share one entry.
+
+use ifc_lite_core::{DecodedEntity, EntityDecoder, EntityScanner, IfcType};
+use rustc_hash::FxHashMap;
+
+use crate::material::{extract_styled_item_material, SurfaceMaterial};
+
+/// Maximum nesting followed through mapped items and material sets
+const MAX_DEPTH: usize = 8;
+
+/// Where an element's resolved style came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StyleSource {
+    /// IfcStyledItem on the element's own geometry
+    StyledItem,
+    /// Presentation of the element's associated IfcMaterial
+    Material,
+    /// Styled representation maps or material of the element's type object
+    TypeObject,
+    /// No style found, per-type default colour
+    Default,
+}
+
+/// Per-file index of surface materials for geometry items and elements
+#[derive(Debug, Clone, Default)]
+pub struct StyleIndex {
+    /// Interned materials, one per distinct surface style
+    materials: Vec<SurfaceMaterial>,
+    /// Surface style id → slot in `materials`
+    slots: FxHashMap<u32, usize>,
+    /// Styled representation item id → material slot
+    geometry: FxHashMap<u32, usize>,
+    /// IfcMaterial id → material slot (from IfcMaterialDefinitionRepresentation)
+    material_presentations: FxHashMap<u32, usize>,
+    /// Element id → resolved material slot and its source
+    elements: FxHashMap<u32, (usize, StyleSource)>,
+}
+
+impl StyleIndex {
+    /// Build the index with one scan over the file content
+    pub fn build(content: &str, decoder: &mut EntityDecoder) -> Self {
+        let mut index = Self::default();
+
+        // Phase 1: collect styles, associations and geometry-bearing elements
+        let mut element_repr_ids: Vec<(u32, u32)> = Vec::with_capacity(2000);
+        let mut material_of: FxHashMap<u32, u32> = FxHashMap::default();
+        let mut type_of: FxHashMap<u32, u32> = FxHashMap::default();
+        let mut scanner = EntityScanner::new(content);
+
+        while let Some((id, type_name, start, end)) = scanner.next_entity() {
+            match type_name {
+                "IFCSTYLEDITEM" => {
+                    let Ok(styled_item) = decoder.decode_at(start, end) else {
+                        continue;
+                    };
+                    // Styled items inside IfcStyledRepresentation have no Item
+                    let Some(geometry_id) = styled_item.get_ref(0) else {
+                        continue;
+                    };
+                    if index.geometry.contains_key(&geometry_id) {
+                        continue;
+                    }
+                    if let Some(material) = extract_styled_item_material(&styled_item, decoder) {
+                        let slot = index.intern(material);
+                        index.geometry.insert(geometry_id, slot);
+                    }
+                }
+                "IFCMATERIALDEFINITIONREPRESENTATION" => {
+                    let Ok(definition) = decoder.decode_at(start, end) else {
+                        continue;
+                    };
+                    // Attribute 3: RepresentedMaterial
+                    let Some(material_id) = definition.get_ref(3) else {
+                        continue;
+                    };
+                    if let Some(material) = material_presentation(&definition, decoder) {
+                        let slot = index.intern(material);
+                        index
+                            .material_presentations
+                            .entry(material_id)
+                            .or_insert(slot);
+                    }
+                }
+                "IFCRELASSOCIATESMATERIAL" => {
+                    if let Ok(rel) = decoder.decode_at(start, end) {
+                        // Attribute 4: RelatedObjects, 5: RelatingMaterial
+                        if let Some(material_id) = rel.get_ref(5) {
+                            for object_id in refs(&rel, 4) {
+                                material_of.entry(object_id).or_insert(material_id);
+                            }
+                        }
+                    }
+                }
+                "IFCRELDEFINESBYTYPE" => {
+                    if let Ok(rel) = decoder.decode_at(start, end) {
+                        // Attribute 4: RelatedObjects, 5: RelatingType
+                        if let Some(type_id) = rel.get_ref(5) {
+                            for object_id in refs(&rel, 4) {
+                                type_of.entry(object_id).or_insert(type_id);
+                            }
+                        }
+                    }
+                }
+                _ if ifc_lite_core::has_geometry_by_name(type_name) => {
+                    if let Ok(element) = decoder.decode_at(start, end) {
+                        // Attribute 6: Representation
+                        if let Some(repr_id) = element.get_ref(6) {
+                            element_repr_ids.push((id, repr_id));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Phase 2: resolve each element in priority order
+        for (element_id, repr_id) in element_repr_ids {
+            let resolved = index
+                .find_in_product_shape(repr_id, decoder)
+                .map(|slot| (slot, StyleSource::StyledItem))
+                .or_else(|| {
+                    let material_id = *material_of.get(&element_id)?;
+                    index
+                        .find_in_material(material_id, decoder, 0)
+                        .map(|slot| (slot, StyleSource::Material))
+                })
+                .or_else(|| {
+                    let type_id = *type_of.get(&element_id)?;
+                    index
+                        .find_in_type_object(type_id, decoder)
+                        .or_else(|| {
+                            let material_id = *material_of.get(&type_id)?;
+                            index.find_in_material(material_id, decoder, 0)
+                        })
+                        .map(|slot| (slot, StyleSource::TypeObject))
+                });
+
+            if let Some(resolved) = resolved {
+                index.elements.insert(element_id, resolved);
+            }
+        }
+
+        index
+    }
+
+    /// Resolved material of an element, if any source provided one
+    pub fn element_material(&self, element_id: u32) -> Option<&SurfaceMaterial> {
+        self.elements
+            .get(&element_id)
+            .map(|&(slot, _)| &self.materials[slot])
+    }
+
+    /// Source the element's style was resolved from
+    pub fn element_source(&self, element_id: u32) -> StyleSource {
+        self.elements
+            .get(&element_id)
+            .map_or(StyleSource::Default, |&(_, source)| source)
+    }
+
+    /// RGBA colour of an element, falling back to the per-type default
+    pub fn element_color(&self, element_id: u32, ifc_type: &IfcType) -> [f32; 4] {
+        self.element_material(element_id)
+            .map(SurfaceMaterial::color)
+            .unwrap_or_else(|| default_color(ifc_type))
+    }
+
+    /// Material of a directly styled representation item
+    pub fn geometry_material(&self, geometry_id: u32) -> Option<&SurfaceMaterial> {
+        self.geometry
+            .get(&geometry_id)
+            .map(|&slot| &self.materials[slot])
+    }
+
+    /// Material of a representation item, following IfcMappedItem to the mapped geometry
+    pub fn find_geometry_material(
+        &self,
+        geometry_id: u32,
+        decoder: &mut EntityDecoder,
+    ) -> Option<&SurfaceMaterial> {
+        self.find_in_item(geometry_id, decoder, 0)
+            .map(|slot| &self.materials[slot])
+    }
+
+    /// Presentation material of an IfcMaterial
+    pub fn material_presentation(&self, material_id: u32) -> Option<&SurfaceMaterial> {
+        self.material_presentations
+            .get(&material_id)
+            .map(|&slot| &self.materials[slot])
+    }
+
+    /// Number of elements with a resolved (non-default) style
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Check if no element has a resolved style
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    fn intern(&mut self, material: SurfaceMaterial) -> usize {
+        if let Some(&slot) = self.slots.get(&material.style_id) {
+            return slot;
+        }
+        let slot = self.materials.len();
+        self.slots.insert(material.style_id, slot);
+        self.materials.push(material);
+        slot
+    }
+
+    /// Search an IfcProductDefinitionShape's representations for a styled item
+    fn find_in_product_shape(&self, repr_id: u32, decoder: &mut EntityDecoder) -> Option<usize> {
+        let shape = decoder.decode_by_id(repr_id).ok()?;
+        // Attribute 2: Representations
+        refs(&shape, 2)
+            .into_iter()
+            .find_map(|shape_repr_id| self.find_in_shape_representation(shape_repr_id, decoder, 0))
+    }
+
+    /// Search an IfcShapeRepresentation's items for a styled item
+    fn find_in_shape_representation(
+        &self,
+        repr_id: u32,
+        decoder: &mut EntityDecoder,
+        depth: usize,
+    ) -> Option<usize> {
+        let repr = decoder.decode_by_id(repr_id).ok()?;
+        // Attribute 3: Items
+        let items = refs(&repr, 3);
+
+        // Direct styles win over styles reached through mapped items
+        if let Some(slot) = items.iter().find_map(|id| self.geometry.get(id).copied()) {
+            return Some(slot);
+        }
+        items
+            .into_iter()
+            .find_map(|item_id| self.find_in_item(item_id, decoder, depth))
+    }
+
+    fn find_in_item(
+        &self,
+        item_id: u32,
+        decoder: &mut EntityDecoder,
+        depth: usize,
+    ) -> Option<usize> {
+        if let Some(&slot) = self.geometry.get(&item_id) {
+            return Some(slot);
+        }
+        if depth >= MAX_DEPTH {
+            return None;
+        }
+
+        let item = decoder.decode_by_id(item_id).ok()?;
+        if item.ifc_type != IfcType::IfcMappedItem {
+            return None;
+        }
+        // IfcMappedItem.MappingSource → IfcRepresentationMap.MappedRepresentation
+        let source = decoder.decode_by_id(item.get_ref(0)?).ok()?;
+        let mapped_repr_id = source.get_ref(1)?;
+        self.find_in_shape_representation(mapped_repr_id, decoder, depth + 1)
+    }
+
+    /// Search a type object's RepresentationMaps for a styled item
+    fn find_in_type_object(&self, type_id: u32, decoder: &mut EntityDecoder) -> Option<usize> {
+        let type_object = decoder.decode_by_id(type_id).ok()?;
+        // Attribute 6: RepresentationMaps (IfcTypeProduct)
+        refs(&type_object, 6).into_iter().find_map(|map_id| {
+            let map = decoder.decode_by_id(map_id).ok()?;
+            self.find_in_shape_representation(map.get_ref(1)?, decoder, 1)
+        })
+    }
+
+    /// Resolve a material select to the first IfcMaterial with a presentation
+    fn find_in_material(
+        &self,
+        select_id: u32,
+        decoder: &mut EntityDecoder,
+        depth: usize,
+    ) -> Option<usize> {
+        if let Some(&slot) = self.material_presentations.get(&select_id) {
+            return Some(slot);
+        }
+        if depth >= MAX_DEPTH {
+            return None;
+        }
+
+        let select = decoder.decode_by_id(select_id).ok()?;
+        let children = match select.ifc_type {
+            // ForLayerSet / ForProfileSet
+            IfcType::IfcMaterialLayerSetUsage | IfcType::IfcMaterialProfileSetUsage => {
+                select.get_ref(0).into_iter().collect()
+            }
+            // MaterialLayers / Materials
+            IfcType::IfcMaterialLayerSet | IfcType::IfcMaterialList => refs(&select, 0),
+            // MaterialProfiles / MaterialConstituents
+            IfcType::IfcMaterialProfileSet | IfcType::IfcMaterialConstituentSet => refs(&select, 2),
+            // Material
+            IfcType::IfcMaterialLayer => select.get_ref(0).into_iter().collect(),
+            IfcType::IfcMaterialProfile | IfcType::IfcMaterialConstituent => {
+                select.get_ref(2).into_iter().collect()
+            }
+            _ => Vec::new(),
+        };
+
+        children
+            .into_iter()
+            .find_map(|child_id| self.find_in_material(child_id, decoder, depth + 1))
+    }
+}
+
+/// Extract the surface material from an IfcMaterialDefinitionRepresentation
+fn material_presentation(
+    definition: &DecodedEntity,
+    decoder: &mut EntityDecoder,
+) -> Option<SurfaceMaterial> {
+    // Attribute 2: Representations (IfcStyledRepresentation), whose Items are IfcStyledItem
+    for repr_id in refs(definition, 2) {
+        let Ok(repr) = decoder.decode_by_id(repr_id) else {
+            continue;
+        };
+        for item_id in refs(&repr, 3) {
+            let Ok(styled_item) = decoder.decode_by_id(item_id) else {
+                continue;
+            };
+            if let Some(material) = extract_styled_item_material(&styled_item, decoder) {
+                return Some(material);
+            }
+        }
+    }
+    None
+}
+
+/// Entity references in a list attribute
+fn refs(entity: &DecodedEntity, index: usize) -> Vec<u32> {
+    entity
+        .get_list(index)
+        .map(|list| list.iter().filter_map(|v| v.as_entity_ref()).collect())
+        .unwrap_or_default()
+}
+
+/// Default RGBA colour for elements without any resolvable style
+pub fn default_color(ifc_type: &IfcType) -> [f32; 4] {
+    match ifc_type {
+        // Walls - light gray
+        IfcType::IfcWall | IfcType::IfcWallStandardCase => [0.85, 0.85, 0.85, 1.0],
+
+        // Slabs - darker gray
+        IfcType::IfcSlab => [0.7, 0.7, 0.7, 1.0],
+
+        // Roofs - brown-ish
+        IfcType::IfcRoof => [0.6, 0.5, 0.4, 1.0],
+
+        // Columns/Beams - steel gray
+        IfcType::IfcColumn | IfcType::IfcBeam | IfcType::IfcMember => [0.6, 0.65, 0.7, 1.0],
+
+        // Windows - light blue transparent
+        IfcType::IfcWindow => [0.6, 0.8, 1.0, 0.4],
+
+        // Doors - wood brown
+        IfcType::IfcDoor => [0.6, 0.45, 0.3, 1.0],
+
+        // Stairs
+        IfcType::IfcStair | IfcType::IfcStairFlight => [0.75, 0.75, 0.75, 1.0],
+
+        // Railings
+        IfcType::IfcRailing => [0.4, 0.4, 0.45, 1.0],
+
+        // Plates/Coverings
+        IfcType::IfcPlate | IfcType::IfcCovering => [0.8, 0.8, 0.8, 1.0],
+
+        // Curtain walls - glass blue
+        IfcType::IfcCurtainWall => [0.5, 0.7, 0.9, 0.5],
+
+        // Furniture - wood
+        IfcType::IfcFurniture | IfcType::IfcFurnishingElement => [0.7, 0.55, 0.4, 1.0],
+
+        // Spaces - cyan transparent (matches MainToolbar)
+        IfcType::IfcSpace => [0.2, 0.85, 1.0, 0.3],
+
+        // Opening elements - red-orange transparent
+        IfcType::IfcOpeningElement => [1.0, 0.42, 0.29, 0.4],
+
+        // Site - green
+        IfcType::IfcSite => [0.4, 0.8, 0.3, 1.0],
+
+        // Grids and annotations - dark linework
+        IfcType::IfcGrid | IfcType::IfcAnnotation => [0.25, 0.25, 0.3, 1.0],
+
+        // Building element proxy - generic gray
+        IfcType::IfcBuildingElementProxy => [0.6, 0.6, 0.6, 1.0],
+
+        // Default - neutral gray
+        _ => [0.8, 0.8, 0.8, 1.0],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODEL: &str = r#"
+#1=IFCCOLOURRGB($,1.,0.,0.);
+#2=IFCSURFACESTYLESHADING(#1,$);
+#3=IFCSURFACESTYLE('Red',.BOTH.,(#2));
+#4=IFCCOLOURRGB($,0.,1.,0.);
+#5=IFCSURFACESTYLESHADING(#4,$);
+#6=IFCSURFACESTYLE('Green',.BOTH.,(#5));
+#7=IFCCOLOURRGB($,0.,0.,1.);
+#8=IFCSURFACESTYLESHADING(#7,$);
+#9=IFCSURFACESTYLE('Blue',.BOTH.,(#8));
+#10=IFCBLOCK($,1.,1.,1.);
+#11=IFCBLOCK($,2.,2.,2.);
+#12=IFCSTYLEDITEM(#10,(#3),$);
+#13=IFCSHAPEREPRESENTATION($,'Body','CSG',(#10));
+#14=IFCPRODUCTDEFINITIONSHAPE($,$,(#13));
+#15=IFCSHAPEREPRESENTATION($,'Body','CSG',(#11));
+#16=IFCPRODUCTDEFINITIONSHAPE($,$,(#15));
+#17=IFCWALL('0a',$,$,$,$,$,#14,$,$);
+#18=IFCWALL('0b',$,$,$,$,$,#16,$,$);
+#19=IFCWALL('0c',$,$,$,$,$,#16,$,$);
+#20=IFCWALL('0d',$,$,$,$,$,#16,$,$);
+#21=IFCMATERIAL('Concrete',$,$);
+#22=IFCSTYLEDITEM($,(#6),$);
+#23=IFCSTYLEDREPRESENTATION($,'Style','Material',(#22));
+#24=IFCMATERIALDEFINITIONREPRESENTATION($,$,(#23),#21);
+#25=IFCMATERIALLAYER(#21,0.2,$,$,$,$,$);
+#26=IFCMATERIALLAYERSET((#25),$,$);
+#27=IFCMATERIALLAYERSETUSAGE(#26,.AXIS2.,.POSITIVE.,0.,$);
+#28=IFCRELASSOCIATESMATERIAL('1a',$,$,$,(#17,#18),#27);
+#29=IFCBLOCK($,3.,3.,3.);
+#30=IFCSTYLEDITEM(#29,(#9),$);
+#31=IFCSHAPEREPRESENTATION($,'Body','CSG',(#29));
+#32=IFCREPRESENTATIONMAP($,#31);
+#33=IFCWALLTYPE('2a',$,$,$,$,$,(#32),$,$,.STANDARD.);
+#34=IFCRELDEFINESBYTYPE('3a',$,$,$,(#18,#19),#33);
+#35=IFCMAPPEDITEM(#32,$);
+"#;
+
+    #[test]
+    fn test_priority_order() {
+        let mut decoder = EntityDecoder::new(MODEL);
+        let index = StyleIndex::build(MODEL, &mut decoder);
+
+        // Styled item beats the associated material
+        assert_eq!(index.element_source(17), StyleSource::StyledItem);
+        assert_eq!(
+            index.element_color(17, &IfcType::IfcWall),
+            [1.0, 0.0, 0.0, 1.0]
+        );
+
+        // Material presentation beats the type object
+        assert_eq!(index.element_source(18), StyleSource::Material);
+        assert_eq!(
+            index.element_color(18, &IfcType::IfcWall),
+            [0.0, 1.0, 0.0, 1.0]
+        );
+
+        // Type object styles when the occurrence has nothing of its own
+        assert_eq!(index.element_source(19), StyleSource::TypeObject);
+        assert_eq!(
+            index.element_color(19, &IfcType::IfcWall),
+            [0.0, 0.0, 1.0, 1.0]
+        );
+
+        // Per-type default
+        assert_eq!(index.element_source(20), StyleSource::Default);
+        assert_eq!(
+            index.element_color(20, &IfcType::IfcWall),
+            default_color(&IfcType::IfcWall)
+        );
+        assert_eq!(index.len(), 3);
+    }
+
+    #[test]
+    fn test_geometry_lookup_follows_mapped_items() {
+        let mut decoder = EntityDecoder::new(MODEL);
+        let index = StyleIndex::build(MODEL, &mut decoder);
+
+        assert!(index.geometry_material(35).is_none());
+        let material = index.find_geometry_material(35, &mut decoder).unwrap();
+        assert_eq!(material.name.as_deref(), Some("Blue"));
+        assert_eq!(
+            index
+                .material_presentation(21)
+                .and_then(|m| m.name.as_deref()),
+            Some("Green")
+        );
+    }
+}
@@ -0,0 +1,363 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Mesh Validation Module
+//!
+//! Checks whether a generated mesh describes a closed, manifold solid.
+//! This matters for volume take-off and 3D printing, where open shells or
+//! non-manifold edges give wrong or unusable results.
+//!
+//! Vertices are compared by position (snapped to a small grid), so meshes
+//! with per-face split vertices are analysed the same as welded ones.
+
+use crate::mesh::{Mesh, DEFAULT_WELD_TOLERANCE};
+use nalgebra::{Point3, Vector3};
+use rustc_hash::FxHashMap;
+
+/// Result of validating a mesh
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MeshValidationReport {
+    /// Number of triangles checked
+    pub triangle_count: usize,
+    /// Triangles with zero area (collinear or repeated vertices)
+    pub degenerate_triangles: usize,
+    /// Edges used by exactly one triangle (holes in the shell)
+    pub open_edges: usize,
+    /// Edges shared by more than two triangles
+    pub non_manifold_edges: usize,
+    /// Edges shared by two triangles that traverse it in the same direction
+    pub inconsistent_winding_edges: usize,
+    /// Pairs of triangles crossing each other (`None` unless checked with
+    /// [`validate_mesh_with_intersections`])
+    pub self_intersections: Option<usize>,
+    /// Closed, manifold, consistently wound and, if checked, free of
+    /// self-intersections
+    pub is_solid: bool,
+}
+
+impl MeshValidationReport {
+    /// Check if the shell has no open edges
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.open_edges == 0
+    }
+
+    /// Check if every edge is shared by at most two triangles
+    #[inline]
+    pub fn is_manifold(&self) -> bool {
+        self.non_manifold_edges == 0
+    }
+}
+
+/// Validate raw mesh buffers (positions as xyz triplets, triangle indices)
+pub fn validate_mesh_buffers(positions: &[f32], indices: &[u32]) -> MeshValidationReport {
+    let vertex_count = positions.len() / 3;
+    let mut report = MeshValidationReport {
+        triangle_count: indices.len() / 3,
+        ..Default::default()
+    };
+
+    let welded = weld_indices(positions, DEFAULT_WELD_TOLERANCE);
+
+    // Undirected edge -> (uses in low->high direction, uses in high->low direction)
+    let mut edges: FxHashMap<(u32, u32), (u32, u32)> = FxHashMap::default();
+    edges.reserve(indices.len());
+
+    for tri in indices.chunks_exact(3) {
+        if tri.iter().any(|&i| i as usize >= vertex_count) {
+            report.degenerate_triangles += 1;
+            continue;
+        }
+
+        let (a, b, c) = (
+            welded[tri[0] as usize],
+            welded[tri[1] as usize],
+            welded[tri[2] as usize],
+        );
+        if a == b || b == c || a == c {
+            report.degenerate_triangles += 1;
+            continue;
+        }
+
+        let p = |i: u32| point_at(positions, i as usize);
+        let cross = (p(tri[1]) - p(tri[0])).cross(&(p(tri[2]) - p(tri[0])));
+        if cross.norm_squared() < 1e-20 {
+            report.degenerate_triangles += 1;
+            continue;
+        }
+
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            let entry = edges.entry((from.min(to), from.max(to))).or_insert((0, 0));
+            if from < to {
+                entry.0 += 1;
+            } else {
+                entry.1 += 1;
+            }
+        }
+    }
+
+    for &(forward, backward) in edges.values() {
+        match forward + backward {
+            1 => report.open_edges += 1,
+            2 if forward != backward => report.inconsistent_winding_edges += 1,
+            2 => {}
+            _ => report.non_manifold_edges += 1,
+        }
+    }
+
+    report.is_solid = !edges.is_empty()
+        && report.open_edges == 0
+        && report.non_manifold_edges == 0
+        && report.inconsistent_winding_edges == 0;
+
+    report
+}
+
+/// Validate a mesh
+#[inline]
+pub fn validate_mesh(mesh: &Mesh) -> MeshValidationReport {
+    validate_mesh_buffers(&mesh.positions, &mesh.indices)
+}
+
+/// Validate a mesh, including a check for self-intersections
+///
+/// A shell that crosses itself is not reported as solid. See
+/// [`count_self_intersections`] for the cost of the extra check.
+pub fn validate_mesh_with_intersections(mesh: &Mesh) -> MeshValidationReport {
+    let mut report = validate_mesh(mesh);
+    let intersections = count_self_intersections(mesh);
+    report.self_intersections = Some(intersections);
+    report.is_solid &= intersections == 0;
+    report
+}
+
+/// Count pairs of triangles that intersect each other
+///
+/// Triangles sharing a vertex are not tested against each other, so only
+/// genuine self-intersections are reported. Uses a sweep over X bounds to
+/// skip distant pairs, but is still considerably more expensive than
+/// [`validate_mesh`] and meant for on-demand checks.
+pub fn count_self_intersections(mesh: &Mesh) -> usize {
+    let vertex_count = mesh.vertex_count();
+    let welded = weld_indices(&mesh.positions, DEFAULT_WELD_TOLERANCE);
+
+    struct Tri {
+        ids: [u32; 3],
+        v: [Point3<f64>; 3],
+        min: Point3<f64>,
+        max: Point3<f64>,
+    }
+
+    let mut tris: Vec<Tri> = mesh
+        .indices
+        .chunks_exact(3)
+        .filter(|tri| tri.iter().all(|&i| (i as usize) < vertex_count))
+        .map(|tri| {
+            let v = [
+                point_at(&mesh.positions, tri[0] as usize),
+                point_at(&mesh.positions, tri[1] as usize),
+                point_at(&mesh.positions, tri[2] as usize),
+            ];
+            let min = Point3::new(
+                v[0].x.min(v[1].x).min(v[2].x),
+                v[0].y.min(v[1].y).min(v[2].y),
+                v[0].z.min(v[1].z).min(v[2].z),
+            );
+            let max = Point3::new(
+                v[0].x.max(v[1].x).max(v[2].x),
+                v[0].y.max(v[1].y).max(v[2].y),
+                v[0].z.max(v[1].z).max(v[2].z),
+            );
+            Tri {
+                ids: [
+                    welded[tri[0] as usize],
+                    welded[tri[1] as usize],
+                    welded[tri[2] as usize],
+                ],
+                v,
+                min,
+                max,
+            }
+        })
+        .collect();
+
+    tris.sort_by(|a, b| a.min.x.total_cmp(&b.min.x));
+
+    let mut count = 0;
+    for i in 0..tris.len() {
+        for j in (i + 1)..tris.len() {
+            let (a, b) = (&tris[i], &tris[j]);
+            if b.min.x > a.max.x {
+                break;
+            }
+            if b.min.y > a.max.y || b.max.y < a.min.y || b.min.z > a.max.z || b.max.z < a.min.z {
+                continue;
+            }
+            if a.ids.iter().any(|id| b.ids.contains(id)) {
+                continue;
+            }
+            if triangles_intersect(&a.v, &b.v) {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+/// Map every vertex to the first vertex at the same (snapped) position
+fn weld_indices(positions: &[f32], tolerance: f32) -> Vec<u32> {
+    let inv_cell = 1.0 / tolerance as f64;
+    let mut cells: FxHashMap<(i64, i64, i64), u32> = FxHashMap::default();
+    positions
+        .chunks_exact(3)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let key = (
+                (chunk[0] as f64 * inv_cell).round() as i64,
+                (chunk[1] as f64 * inv_cell).round() as i64,
+                (chunk[2] as f64 * inv_cell).round() as i64,
+            );
+            *cells.entry(key).or_insert(i as u32)
+        })
+        .collect()
+}
+
+#[inline]
+fn point_at(positions: &[f32], i: usize) -> Point3<f64> {
+    Point3::new(
+        positions[i * 3] as f64,
+        positions[i * 3 + 1] as f64,
+        positions[i * 3 + 2] as f64,
+    )
+}
+
+/// Test whether two non-adjacent triangles intersect (edge-vs-triangle in both directions)
+fn triangles_intersect(a: &[Point3<f64>; 3], b: &[Point3<f64>; 3]) -> bool {
+    (0..3).any(|k| segment_hits_triangle(a[k], a[(k + 1) % 3], b))
+        || (0..3).any(|k| segment_hits_triangle(b[k], b[(k + 1) % 3], a))
+}
+
+/// Möller–Trumbore segment/triangle test
+fn segment_hits_triangle(p0: Point3<f64>, p1: Point3<f64>, tri: &[Point3<f64>; 3]) -> bool {
+    const EPS: f64 = 1e-9;
+    let dir: Vector3<f64> = p1 - p0;
+    let e1 = tri[1] - tri[0];
+    let e2 = tri[2] - tri[0];
+    let h = dir.cross(&e2);
+    let det = e1.dot(&h);
+    if det.abs() < EPS {
+        return false; // Parallel or coplanar - ignored
+    }
+    let inv_det = 1.0 / det;
+    let s = p0 - tri[0];
+    let u = inv_det * s.dot(&h);
+    if !(EPS..=1.0 - EPS).contains(&u) {
+        return false;
+    }
+    let q = s.cross(&e1);
+    let v = inv_det * dir.dot(&q);
+    if v < EPS || u + v > 1.0 - EPS {
+        return false;
+    }
+    let t = inv_det * e2.dot(&q);
+    t > EPS && t < 1.0 - EPS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Closed unit tetrahedron with outward winding
+    fn tetrahedron() -> Mesh {
+        #[rustfmt::skip]
+        let positions = vec![
+            0.0, 0.0, 0.0,
+            1.0, 0.0, 0.0,
+            0.0, 1.0, 0.0,
+            0.0, 0.0, 1.0,
+        ];
+        let mut mesh = Mesh::new();
+        mesh.positions = positions;
+        mesh.normals = vec![0.0; 12];
+        mesh.indices = vec![0, 2, 1, 0, 1, 3, 1, 2, 3, 0, 3, 2];
+        mesh
+    }
+
+    #[test]
+    fn test_closed_tetrahedron_is_solid() {
+        let report = validate_mesh(&tetrahedron());
+        assert_eq!(report.triangle_count, 4);
+        assert!(report.is_closed());
+        assert!(report.is_manifold());
+        assert_eq!(report.inconsistent_winding_edges, 0);
+        assert!(report.is_solid);
+    }
+
+    #[test]
+    fn test_open_and_flipped_faces_detected() {
+        let mut open = tetrahedron();
+        open.indices.truncate(9);
+        let report = validate_mesh(&open);
+        assert_eq!(report.open_edges, 3);
+        assert!(!report.is_solid);
+
+        let mut flipped = tetrahedron();
+        flipped.indices.swap(10, 11);
+        let report = validate_mesh(&flipped);
+        assert_eq!(report.inconsistent_winding_edges, 3);
+        assert!(!report.is_solid);
+    }
+
+    #[test]
+    fn test_degenerate_and_non_manifold() {
+        let mut mesh = tetrahedron();
+        // Degenerate: repeated vertex
+        mesh.indices.extend_from_slice(&[0, 0, 1]);
+        // Third triangle on edge 0-1
+        mesh.positions.extend_from_slice(&[0.5, -1.0, 0.5]);
+        mesh.indices.extend_from_slice(&[0, 1, 4]);
+        let report = validate_mesh(&mesh);
+        assert_eq!(report.degenerate_triangles, 1);
+        assert_eq!(report.non_manifold_edges, 1);
+        assert!(!report.is_solid);
+    }
+
+    #[test]
+    fn test_self_intersections() {
+        assert_eq!(count_self_intersections(&tetrahedron()), 0);
+
+        // Two crossing triangles that share no vertices
+        #[rustfmt::skip]
+        let positions = vec![
+            0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 1.0, 2.0, 0.0,
+            1.0, 1.0, -1.0, 1.0, 1.0, 1.0, 1.0, -1.0, 0.0,
+        ];
+        let mut mesh = Mesh::new();
+        mesh.positions = positions;
+        mesh.indices = vec![0, 1, 2, 3, 4, 5];
+        assert_eq!(count_self_intersections(&mesh), 1);
+    }
+
+    #[test]
+    fn test_self_intersecting_shell_is_not_solid() {
+        let report = validate_mesh_with_intersections(&tetrahedron());
+        assert_eq!(report.self_intersections, Some(0));
+        assert!(report.is_solid);
+
+        // Two closed tetrahedra pushed into each other
+        let mut mesh = tetrahedron();
+        let mut other = tetrahedron();
+        for (i, p) in other.positions.iter_mut().enumerate() {
+            *p += if i % 3 == 0 { 0.25 } else { 0.1 };
+        }
+        mesh.merge(&other);
+
+        assert_eq!(validate_mesh(&mesh).self_intersections, None);
+        assert!(validate_mesh(&mesh).is_solid);
+        let report = validate_mesh_with_intersections(&mesh);
+        assert!(report.self_intersections.unwrap() > 0);
+        assert!(!report.is_solid);
+    }
+}
@@ -11,9 +11,10 @@
 //! This module implements the detection and projection logic needed to
 //! efficiently route voids to the appropriate processing path.
 
-use crate::bool2d::{ensure_ccw, is_valid_contour};
+use crate::bool2d::{compute_signed_area, ensure_ccw, is_valid_contour};
 use crate::mesh::Mesh;
 use crate::profile::VoidInfo;
+use crate::quantities::mesh_volume;
 use nalgebra::{Matrix4, Point2, Point3, Vector3};
 use rustc_hash::FxHashMap;
 
@@ -23,6 +24,9 @@ const DEFAULT_PLANARITY_EPSILON: f64 = 0.02;
 /// Maximum depth tolerance for considering a void as "through"
 const THROUGH_VOID_TOLERANCE: f64 = 0.01;
 
+/// Relative volume difference allowed between a void and its footprint prism
+const PRISM_VOLUME_TOLERANCE: f64 = 1e-3;
+
 /// Classification of a void relative to a host extrusion
 #[derive(Debug, Clone)]
 pub enum VoidClassification {
@@ -115,7 +119,7 @@ impl VoidAnalyzer {
         };
 
         match self.extract_footprint(void_mesh, &inverse_transform, extrusion_direction) {
-            Some((footprint, depth_start, depth_end)) => {
+            Some((footprint, min_z, max_z)) => {
                 // Check if footprint is valid
                 if !is_valid_contour(&footprint) {
                     return VoidClassification::NonPlanar {
@@ -123,6 +127,22 @@ impl VoidAnalyzer {
                     };
                 }
 
+                // Only a prism over its footprint fills footprint x depth range;
+                // anything else (tilted, tapered, non-convex) needs 3D CSG
+                let scale = profile_transform.fixed_view::<3, 3>(0, 0).determinant().abs();
+                let prism_volume = compute_signed_area(&footprint).abs() * (max_z - min_z);
+                let void_volume = mesh_volume(void_mesh).abs() / scale;
+                if scale <= f64::EPSILON
+                    || (void_volume - prism_volume).abs() > PRISM_VOLUME_TOLERANCE * prism_volume
+                {
+                    return VoidClassification::NonPlanar {
+                        mesh: void_mesh.clone(),
+                    };
+                }
+
+                let depth_start = min_z.max(0.0);
+                let depth_end = max_z;
+
                 // Check if it's a through void
                 let is_through = depth_start <= THROUGH_VOID_TOLERANCE
                     && depth_end >= extrusion_depth - THROUGH_VOID_TOLERANCE;
@@ -271,7 +291,7 @@ impl VoidAnalyzer {
     /// Extract 2D footprint from void mesh
     ///
     /// Projects the void mesh onto the profile plane and extracts the
-    /// boundary contour.
+    /// boundary contour together with the unclamped depth range.
     fn extract_footprint(
         &self,
         void_mesh: &Mesh,
@@ -319,11 +339,7 @@ impl VoidAnalyzer {
         // Ensure counter-clockwise winding
         let footprint = ensure_ccw(&hull);
 
-        // Clamp depth values
-        let depth_start = min_z.max(0.0);
-        let depth_end = max_z;
-
-        Some((footprint, depth_start, depth_end))
+        Some((footprint, min_z, max_z))
     }
 
     /// Compute convex hull of 2D points using Graham scan
@@ -587,6 +603,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_void_analyzer_tilted_box_is_nonplanar() {
+        let analyzer = VoidAnalyzer::new();
+
+        // Box rotated 30 degrees about X: side faces stay perpendicular to the
+        // extrusion, but its footprint prism is larger than the box
+        let mut void_mesh = create_box_mesh(
+            Point3::new(2.0, 2.0, 2.0),
+            Point3::new(4.0, 4.0, 8.0),
+        );
+        let rotation = Matrix4::from_axis_angle(&Vector3::x_axis(), 30f64.to_radians());
+        void_mesh.positions.chunks_exact_mut(3).for_each(|chunk| {
+            let p = rotation.transform_point(&Point3::new(
+                chunk[0] as f64,
+                chunk[1] as f64,
+                chunk[2] as f64,
+            ));
+            chunk[0] = p.x as f32;
+            chunk[1] = p.y as f32;
+            chunk[2] = p.z as f32;
+        });
+
+        let classification = analyzer.classify_void(
+            &void_mesh,
+            &Matrix4::identity(),
+            &Vector3::new(0.0, 0.0, 1.0),
+            10.0,
+        );
+
+        assert!(matches!(classification, VoidClassification::NonPlanar { .. }));
+    }
+
     #[test]
     fn test_extract_coplanar_voids() {
         let classifications = vec![
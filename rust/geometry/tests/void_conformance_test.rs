@@ -0,0 +1,528 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Opening conformance tests using inline IFC content.
+//! Each case combines a host body (extruded wall or slab, rotated, millimetre
+//! units, faceted B-rep) with an opening shape (box, circle, L-shape,
+//! partial depth, multi-item) and checks the cut volume against the
+//! analytic result.
+
+use ifc_lite_core::EntityDecoder;
+use ifc_lite_geometry::{mesh_volume, GeometryRouter, Mesh, VoidIndex};
+use rustc_hash::FxHashMap;
+
+const HOST_ID: u32 = 100;
+
+/// Wrap DATA entities in a minimal IFC4 file with the given length unit prefix
+fn ifc_file(length_prefix: &str, data: &str) -> String {
+    format!(
+        r#"ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('ViewDefinition [CoordinationView]'),'2;1');
+FILE_NAME('test.ifc','2024-01-01T00:00:00',(''),(''),'','','');
+FILE_SCHEMA(('IFC4'));
+ENDSEC;
+DATA;
+#1=IFCPROJECT('1234567890123456789012',#2,'Test',$,$,$,$,(#10),#7);
+#2=IFCOWNERHISTORY(#3,#4,$,.ADDED.,$,$,$,0);
+#3=IFCPERSONANDORGANIZATION(#5,#6,$);
+#4=IFCAPPLICATION(#6,'1.0','Test','Test');
+#5=IFCPERSON($,'Test',$,$,$,$,$,$);
+#6=IFCORGANIZATION($,'Test',$,$,$);
+#7=IFCUNITASSIGNMENT((#8));
+#8=IFCSIUNIT(*,.LENGTHUNIT.,{length_prefix},.METRE.);
+#10=IFCGEOMETRICREPRESENTATIONCONTEXT($,'Model',3,1.E-5,#11,$);
+#11=IFCAXIS2PLACEMENT3D(#12,$,$);
+#12=IFCCARTESIANPOINT((0.,0.,0.));
+#13=IFCGEOMETRICREPRESENTATIONSUBCONTEXT('Body','Model',*,*,*,*,#10,$,.MODEL_VIEW.,$);
+{data}ENDSEC;
+END-ISO-10303-21;
+"#
+    )
+}
+
+/// Local placement at `origin`, rotated `angle` degrees about Z (uses ids id..id+4)
+fn placement(id: u32, parent: Option<u32>, origin: [f64; 3], angle: f64) -> String {
+    let parent = parent.map_or("$".to_string(), |p| format!("#{}", p));
+    let (sin, cos) = angle.to_radians().sin_cos();
+    format!(
+        "#{id}=IFCLOCALPLACEMENT({parent},#{a});\n\
+         #{a}=IFCAXIS2PLACEMENT3D(#{b},#{c},#{d});\n\
+         #{b}=IFCCARTESIANPOINT(({:?},{:?},{:?}));\n\
+         #{c}=IFCDIRECTION((0.,0.,1.));\n\
+         #{d}=IFCDIRECTION(({:?},{:?},0.));\n",
+        origin[0],
+        origin[1],
+        origin[2],
+        cos,
+        sin,
+        a = id + 1,
+        b = id + 2,
+        c = id + 3,
+        d = id + 4,
+    )
+}
+
+/// Rectangle profile centred at `center` (uses ids id..id+2)
+fn rectangle_profile(id: u32, size: [f64; 2], center: [f64; 2]) -> String {
+    format!(
+        "#{id}=IFCRECTANGLEPROFILEDEF(.AREA.,$,#{a},{:?},{:?});\n\
+         #{a}=IFCAXIS2PLACEMENT2D(#{b},$);\n\
+         #{b}=IFCCARTESIANPOINT(({:?},{:?}));\n",
+        size[0],
+        size[1],
+        center[0],
+        center[1],
+        a = id + 1,
+        b = id + 2,
+    )
+}
+
+/// Circle profile centred at `center` (uses ids id..id+2)
+fn circle_profile(id: u32, radius: f64, center: [f64; 2]) -> String {
+    format!(
+        "#{id}=IFCCIRCLEPROFILEDEF(.AREA.,$,#{a},{:?});\n\
+         #{a}=IFCAXIS2PLACEMENT2D(#{b},$);\n\
+         #{b}=IFCCARTESIANPOINT(({:?},{:?}));\n",
+        radius,
+        center[0],
+        center[1],
+        a = id + 1,
+        b = id + 2,
+    )
+}
+
+/// Closed polyline profile (uses ids id..id+1+points)
+fn polyline_profile(id: u32, points: &[[f64; 2]]) -> String {
+    let point_ids: Vec<String> = (0..=points.len())
+        .map(|i| format!("#{}", id + 2 + (i % points.len()) as u32))
+        .collect();
+    let mut out = format!(
+        "#{id}=IFCARBITRARYCLOSEDPROFILEDEF(.AREA.,$,#{a});\n#{a}=IFCPOLYLINE(({}));\n",
+        point_ids.join(","),
+        a = id + 1,
+    );
+    for (i, p) in points.iter().enumerate() {
+        out += &format!(
+            "#{}=IFCCARTESIANPOINT(({:?},{:?}));\n",
+            id + 2 + i as u32,
+            p[0],
+            p[1]
+        );
+    }
+    out
+}
+
+/// Extrusion of `profile_id` from `origin` along the local Z of `axis`/`ref_dir`
+/// (uses ids id..id+5)
+fn extrusion(
+    id: u32,
+    profile_id: u32,
+    origin: [f64; 3],
+    axis: [f64; 3],
+    ref_dir: [f64; 3],
+    depth: f64,
+) -> String {
+    format!(
+        "#{id}=IFCEXTRUDEDAREASOLID(#{profile_id},#{a},#{e},{:?});\n\
+         #{a}=IFCAXIS2PLACEMENT3D(#{b},#{c},#{d});\n\
+         #{b}=IFCCARTESIANPOINT(({:?},{:?},{:?}));\n\
+         #{c}=IFCDIRECTION(({:?},{:?},{:?}));\n\
+         #{d}=IFCDIRECTION(({:?},{:?},{:?}));\n\
+         #{e}=IFCDIRECTION((0.,0.,1.));\n",
+        depth,
+        origin[0],
+        origin[1],
+        origin[2],
+        axis[0],
+        axis[1],
+        axis[2],
+        ref_dir[0],
+        ref_dir[1],
+        ref_dir[2],
+        a = id + 1,
+        b = id + 2,
+        c = id + 3,
+        d = id + 4,
+        e = id + 5,
+    )
+}
+
+/// Vertical extrusion starting at `z`
+fn vertical_extrusion(id: u32, profile_id: u32, z: f64, depth: f64) -> String {
+    extrusion(
+        id,
+        profile_id,
+        [0.0, 0.0, z],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 0.0],
+        depth,
+    )
+}
+
+/// Extrusion through a wall along -Y, profile Y pointing up
+fn horizontal_extrusion(id: u32, profile_id: u32, y: f64, depth: f64) -> String {
+    extrusion(
+        id,
+        profile_id,
+        [0.0, y, 0.0],
+        [0.0, -1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        depth,
+    )
+}
+
+/// Faceted B-rep box from `min` to `max` with outward faces (uses ids id..id+27)
+fn brep_box(id: u32, min: [f64; 3], max: [f64; 3]) -> String {
+    let mut out = String::new();
+    for i in 0..8u32 {
+        let x = if i & 1 == 0 { min[0] } else { max[0] };
+        let y = if i & 2 == 0 { min[1] } else { max[1] };
+        let z = if i & 4 == 0 { min[2] } else { max[2] };
+        out += &format!(
+            "#{}=IFCCARTESIANPOINT(({:?},{:?},{:?}));\n",
+            id + 2 + i,
+            x,
+            y,
+            z
+        );
+    }
+    // Corner indices per face, counter-clockwise seen from outside
+    let faces: [[u32; 4]; 6] = [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ];
+    let mut face_ids = Vec::new();
+    for (f, corners) in faces.iter().enumerate() {
+        let face = id + 10 + f as u32 * 3;
+        let bound = face + 1;
+        let polyloop = face + 2;
+        let points: Vec<String> = corners.iter().map(|c| format!("#{}", id + 2 + c)).collect();
+        out += &format!(
+            "#{face}=IFCFACE((#{bound}));\n#{bound}=IFCFACEOUTERBOUND(#{polyloop},.T.);\n#{polyloop}=IFCPOLYLOOP(({}));\n",
+            points.join(",")
+        );
+        face_ids.push(format!("#{}", face));
+    }
+    out += &format!(
+        "#{id}=IFCFACETEDBREP(#{a});\n#{a}=IFCCLOSEDSHELL(({}));\n",
+        face_ids.join(","),
+        a = id + 1,
+    );
+    out
+}
+
+/// Product shape with a single body representation (uses ids id..id+1)
+fn body(id: u32, rep_type: &str, items: &[u32]) -> String {
+    let items: Vec<String> = items.iter().map(|i| format!("#{}", i)).collect();
+    format!(
+        "#{id}=IFCSHAPEREPRESENTATION(#13,'Body','{rep_type}',({}));\n\
+         #{a}=IFCPRODUCTDEFINITIONSHAPE($,$,(#{id}));\n",
+        items.join(","),
+        a = id + 1,
+    )
+}
+
+/// Host wall #100 with shape #{shape}+1 and opening elements voiding it
+fn host_with_openings(
+    host_placement: u32,
+    host_shape: u32,
+    openings: &[(u32, u32, u32)],
+) -> String {
+    let mut out = format!(
+        "#{HOST_ID}=IFCWALL('0001234567890123456789',#2,'Host',$,$,#{host_placement},#{},$,$);\n",
+        host_shape + 1
+    );
+    for (n, (opening_id, opening_placement, opening_shape)) in openings.iter().enumerate() {
+        out += &format!(
+            "#{opening_id}=IFCOPENINGELEMENT('00012345678901234567{:02}',#2,'Opening',$,$,#{opening_placement},#{},$,.OPENING.);\n\
+             #{}=IFCRELVOIDSELEMENT('00012345678901234568{:02}',#2,$,$,#{HOST_ID},#{opening_id});\n",
+            n,
+            opening_shape + 1,
+            opening_id + 1,
+            n,
+        );
+    }
+    out
+}
+
+/// Standard wall: 4 m long, 0.2 m thick, 3 m high, along X from the placement origin
+fn wall(scale: f64, angle: f64) -> String {
+    let mut data = placement(20, None, [10.0 * scale, 5.0 * scale, 0.0], angle);
+    data += &rectangle_profile(30, [4.0 * scale, 0.2 * scale], [2.0 * scale, 0.1 * scale]);
+    data += &vertical_extrusion(40, 30, 0.0, 3.0 * scale);
+    data += &body(50, "SweptSolid", &[40]);
+    data
+}
+
+/// Standard slab: 4 m x 3 m, 0.3 m thick
+fn slab() -> String {
+    let mut data = placement(20, None, [0.0, 0.0, 0.0], 0.0);
+    data += &rectangle_profile(30, [4.0, 3.0], [2.0, 1.5]);
+    data += &vertical_extrusion(40, 30, 0.0, 0.3);
+    data += &body(50, "SweptSolid", &[40]);
+    data
+}
+
+/// Faceted B-rep host: 2 m cube
+fn brep_host() -> String {
+    let mut data = placement(20, None, [0.0, 0.0, 0.0], 0.0);
+    data += &brep_box(60, [0.0, 0.0, 0.0], [2.0, 2.0, 2.0]);
+    data += &body(50, "Brep", &[60]);
+    data
+}
+
+/// Door-sized box through the wall at x 1.5..2.5, z 0.9..2.4 (in placement units)
+fn wall_box_opening(scale: f64) -> String {
+    let mut data = placement(200, Some(20), [0.0, 0.0, 0.0], 0.0);
+    data += &rectangle_profile(210, [1.0 * scale, 1.5 * scale], [2.0 * scale, 1.65 * scale]);
+    data += &horizontal_extrusion(220, 210, 0.3 * scale, 0.4 * scale);
+    data += &body(230, "SweptSolid", &[220]);
+    data
+}
+
+/// Process the host with the router's opening pipeline
+fn cut_host(content: &str) -> Mesh {
+    let mut decoder = EntityDecoder::new(content);
+    let router = GeometryRouter::with_units(content, &mut decoder);
+    let void_index = VoidIndex::from_content(content, &mut decoder);
+    let void_map: FxHashMap<u32, Vec<u32>> = void_index
+        .iter()
+        .map(|(host, voids)| (host, voids.to_vec()))
+        .collect();
+
+    let host = decoder
+        .decode_by_id(HOST_ID)
+        .expect("Failed to decode host");
+    let mesh = router
+        .process_element_with_voids(&host, &mut decoder, &void_map)
+        .expect("Failed to process host with voids");
+
+    // The VoidIndex entry point must agree
+    let mesh_2d = router
+        .process_element_with_voids_2d(&host, &mut decoder, &void_index)
+        .expect("Failed to process host with 2D voids");
+    assert_eq!(mesh.triangle_count(), mesh_2d.triangle_count());
+
+    assert!(!mesh.is_empty(), "Cut mesh should not be empty");
+    assert!(
+        mesh.positions.iter().all(|v| v.is_finite()),
+        "All positions should be finite"
+    );
+    assert!(
+        mesh.normals.iter().all(|v| v.is_finite()),
+        "All normals should be finite"
+    );
+    mesh
+}
+
+fn assert_volume(mesh: &Mesh, expected: f64, tolerance: f64) {
+    let volume = mesh_volume(mesh).abs();
+    assert!(
+        (volume - expected).abs() <= tolerance,
+        "Expected volume {:.4} m³, got {:.4} m³",
+        expected,
+        volume
+    );
+}
+
+/// Area of a regular polygon approximating a circle (24 segments, as profiles are generated)
+fn circle_polygon_area(radius: f64) -> f64 {
+    let segments = 24.0;
+    0.5 * segments * radius * radius * (2.0 * std::f64::consts::PI / segments).sin()
+}
+
+const WALL_VOLUME: f64 = 4.0 * 0.2 * 3.0;
+const DOOR_VOLUME: f64 = 1.0 * 0.2 * 1.5;
+
+#[test]
+fn test_extruded_wall_box_opening() {
+    let data =
+        wall(1.0, 0.0) + &wall_box_opening(1.0) + &host_with_openings(20, 50, &[(300, 200, 230)]);
+    let mesh = cut_host(&ifc_file("$", &data));
+    assert_volume(&mesh, WALL_VOLUME - DOOR_VOLUME, 1e-3);
+
+    // Wall stays in place: x 10..14 in world coordinates
+    let (min, max) = mesh.bounds();
+    assert!((min.x - 10.0).abs() < 1e-3 && (max.x - 14.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_rotated_wall_box_opening() {
+    // An axis-aligned cut would remove far more than the opening from a rotated wall
+    let data =
+        wall(1.0, 30.0) + &wall_box_opening(1.0) + &host_with_openings(20, 50, &[(300, 200, 230)]);
+    let mesh = cut_host(&ifc_file("$", &data));
+    assert_volume(&mesh, WALL_VOLUME - DOOR_VOLUME, 1e-3);
+}
+
+#[test]
+fn test_millimetre_wall_box_opening() {
+    let data = wall(1000.0, 0.0)
+        + &wall_box_opening(1000.0)
+        + &host_with_openings(20, 50, &[(300, 200, 230)]);
+    let mesh = cut_host(&ifc_file(".MILLI.", &data));
+    assert_volume(&mesh, WALL_VOLUME - DOOR_VOLUME, 1e-3);
+}
+
+#[test]
+fn test_extruded_wall_circular_opening() {
+    let mut data = wall(1.0, 15.0);
+    data += &placement(200, Some(20), [0.0, 0.0, 0.0], 0.0);
+    data += &circle_profile(210, 0.3, [2.0, 1.5]);
+    data += &horizontal_extrusion(220, 210, 0.3, 0.4);
+    data += &body(230, "SweptSolid", &[220]);
+    data += &host_with_openings(20, 50, &[(300, 200, 230)]);
+
+    let mesh = cut_host(&ifc_file("$", &data));
+    assert_volume(&mesh, WALL_VOLUME - circle_polygon_area(0.3) * 0.2, 2e-3);
+}
+
+#[test]
+fn test_slab_partial_depth_opening() {
+    // Recess from the top face down to 0.1 m above the bottom
+    let mut data = slab();
+    data += &placement(200, Some(20), [0.0, 0.0, 0.0], 0.0);
+    data += &rectangle_profile(210, [1.0, 1.0], [1.0, 1.0]);
+    data += &vertical_extrusion(220, 210, 0.1, 0.5);
+    data += &body(230, "SweptSolid", &[220]);
+    data += &host_with_openings(20, 50, &[(300, 200, 230)]);
+
+    let mesh = cut_host(&ifc_file("$", &data));
+    assert_volume(&mesh, 4.0 * 3.0 * 0.3 - 1.0 * 1.0 * 0.2, 1e-3);
+}
+
+#[test]
+fn test_slab_opening_crossing_edge() {
+    // Notch overlapping the slab edge: only the inside part is removed
+    let mut data = slab();
+    data += &placement(200, Some(20), [0.0, 0.0, 0.0], 0.0);
+    data += &rectangle_profile(210, [1.0, 1.0], [4.0, 1.5]);
+    data += &vertical_extrusion(220, 210, -0.1, 0.5);
+    data += &body(230, "SweptSolid", &[220]);
+    data += &host_with_openings(20, 50, &[(300, 200, 230)]);
+
+    let mesh = cut_host(&ifc_file("$", &data));
+    assert_volume(&mesh, 4.0 * 3.0 * 0.3 - 0.5 * 1.0 * 0.3, 1e-3);
+
+    let (_, max) = mesh.bounds();
+    assert!((max.x - 4.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_slab_l_shaped_opening() {
+    // Non-convex opening: its convex hull must not be cut
+    let mut data = slab();
+    data += &placement(200, Some(20), [0.0, 0.0, 0.0], 0.0);
+    data += &polyline_profile(
+        210,
+        &[
+            [1.0, 1.0],
+            [2.0, 1.0],
+            [2.0, 1.5],
+            [1.5, 1.5],
+            [1.5, 2.0],
+            [1.0, 2.0],
+        ],
+    );
+    data += &vertical_extrusion(220, 210, -0.1, 0.5);
+    data += &body(230, "SweptSolid", &[220]);
+    data += &host_with_openings(20, 50, &[(300, 200, 230)]);
+
+    let mesh = cut_host(&ifc_file("$", &data));
+    assert_volume(&mesh, 4.0 * 3.0 * 0.3 - 0.75 * 0.3, 2e-3);
+}
+
+#[test]
+fn test_slab_multiple_openings() {
+    // One opening element with two disjoint items plus a second opening element
+    let mut data = slab();
+    data += &placement(200, Some(20), [0.0, 0.0, 0.0], 0.0);
+    data += &rectangle_profile(210, [0.5, 0.5], [0.5, 0.5]);
+    data += &vertical_extrusion(220, 210, -0.1, 0.5);
+    data += &rectangle_profile(240, [0.5, 0.5], [3.5, 2.5]);
+    data += &vertical_extrusion(250, 240, -0.1, 0.5);
+    data += &body(230, "SweptSolid", &[220, 250]);
+    data += &circle_profile(410, 0.25, [2.0, 1.5]);
+    data += &vertical_extrusion(420, 410, -0.1, 0.5);
+    data += &body(430, "SweptSolid", &[420]);
+    data += &host_with_openings(20, 50, &[(300, 200, 230), (310, 200, 430)]);
+
+    let mesh = cut_host(&ifc_file("$", &data));
+    let removed = (0.25 + 0.25 + circle_polygon_area(0.25)) * 0.3;
+    assert_volume(&mesh, 4.0 * 3.0 * 0.3 - removed, 2e-3);
+}
+
+#[test]
+fn test_brep_host_box_opening() {
+    let mut data = brep_host();
+    data += &placement(200, Some(20), [0.0, 0.0, 0.0], 0.0);
+    data += &rectangle_profile(210, [1.0, 1.0], [1.0, 1.0]);
+    data += &vertical_extrusion(220, 210, -0.5, 3.0);
+    data += &body(230, "SweptSolid", &[220]);
+    data += &host_with_openings(20, 50, &[(300, 200, 230)]);
+
+    let mesh = cut_host(&ifc_file("$", &data));
+    assert_volume(&mesh, 8.0 - 2.0, 1e-3);
+}
+
+#[test]
+fn test_brep_wall_box_opening() {
+    // Box cut straight through a non-extruded wall
+    let mut data = placement(20, None, [0.0, 0.0, 0.0], 0.0);
+    data += &brep_box(60, [0.0, 0.0, 0.0], [4.0, 0.2, 3.0]);
+    data += &body(50, "Brep", &[60]);
+    data += &wall_box_opening(1.0);
+    data += &host_with_openings(20, 50, &[(300, 200, 230)]);
+
+    let mesh = cut_host(&ifc_file("$", &data));
+    assert_volume(&mesh, WALL_VOLUME - DOOR_VOLUME, 1e-3);
+}
+
+#[test]
+fn test_brep_wall_recess() {
+    // Box reaching half way into a non-extruded wall from the back
+    let mut data = placement(20, None, [0.0, 0.0, 0.0], 0.0);
+    data += &brep_box(60, [0.0, 0.0, 0.0], [4.0, 0.2, 3.0]);
+    data += &body(50, "Brep", &[60]);
+    data += &placement(200, Some(20), [0.0, 0.0, 0.0], 0.0);
+    data += &rectangle_profile(210, [1.0, 1.5], [2.0, 1.65]);
+    data += &horizontal_extrusion(220, 210, 0.3, 0.2);
+    data += &body(230, "SweptSolid", &[220]);
+    data += &host_with_openings(20, 50, &[(300, 200, 230)]);
+
+    let mesh = cut_host(&ifc_file("$", &data));
+    assert_volume(&mesh, WALL_VOLUME - 1.0 * 0.1 * 1.5, 1e-3);
+}
+
+#[test]
+fn test_brep_host_rotated_opening() {
+    // Opening rotated 45 degrees: a square hole of 0.5 m² across the cube
+    let mut data = brep_host();
+    data += &placement(200, Some(20), [1.0, 1.0, 0.0], 45.0);
+    data += &rectangle_profile(210, [0.5f64.sqrt(), 0.5f64.sqrt()], [0.0, 0.0]);
+    data += &vertical_extrusion(220, 210, -0.5, 3.0);
+    data += &body(230, "SweptSolid", &[220]);
+    data += &host_with_openings(20, 50, &[(300, 200, 230)]);
+
+    let mesh = cut_host(&ifc_file("$", &data));
+    assert_volume(&mesh, 8.0 - 0.5 * 2.0, 2e-3);
+}
+
+#[test]
+fn test_brep_host_circular_opening() {
+    let mut data = brep_host();
+    data += &placement(200, Some(20), [0.0, 0.0, 0.0], 0.0);
+    data += &circle_profile(210, 0.5, [1.0, 1.0]);
+    data += &vertical_extrusion(220, 210, -0.5, 3.0);
+    data += &body(230, "SweptSolid", &[220]);
+    data += &host_with_openings(20, 50, &[(300, 200, 230)]);
+
+    let mesh = cut_host(&ifc_file("$", &data));
+    assert_volume(&mesh, 8.0 - circle_polygon_area(0.5) * 2.0, 5e-3);
+}
@@ -11,7 +11,13 @@ use crate::zero_copy::{
     InstanceData, InstancedGeometry, InstancedMeshCollection, MeshCollection, MeshDataJs,
     ZeroCopyMesh,
 };
-use ifc_lite_core::{EntityScanner, GeoReference, ParseEvent, RtcOffset, StreamConfig};
+use ifc_lite_core::{
+    ElementFilter, EntityIndex, EntityScanner, GeoReference, ModelIndex, ParseEvent, RtcOffset,
+    StreamConfig,
+};
+use ifc_lite_geometry::{
+    build_texture_map_index, default_color, RepresentationSelection, StyleIndex, SurfaceMaterial,
+};
 use js_sys::{Function, Promise};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
@@ -211,10 +217,71 @@ impl MeshCollectionWithRtc {
     }
 }
 
+/// Element query index over a model, built once and queried many times
+///
+/// Example:
+/// ```javascript
+/// const model = new ModelQuery(ifcData);
+/// const ids = model.query('IfcDoor where storey = "Level 2" and Pset_DoorCommon.FireRating = EI30');
+/// ```
+#[wasm_bindgen]
+pub struct ModelQuery {
+    content: String,
+    entity_index: std::sync::Arc<EntityIndex>,
+    index: ModelIndex,
+}
+
+#[wasm_bindgen]
+impl ModelQuery {
+    /// Index a model for querying
+    #[wasm_bindgen(constructor)]
+    pub fn new(content: String) -> Self {
+        use ifc_lite_core::{build_entity_index, EntityDecoder};
+
+        let entity_index = std::sync::Arc::new(build_entity_index(&content));
+        let mut decoder = EntityDecoder::with_arc_index(&content, entity_index.clone());
+        let index = ModelIndex::build(&content, &mut decoder);
+        Self {
+            content,
+            entity_index,
+            index,
+        }
+    }
+
+    /// Run a query, returning matching express ids in ascending order
+    pub fn query(&self, text: &str) -> Result<Vec<u32>, JsValue> {
+        use ifc_lite_core::EntityDecoder;
+
+        let mut decoder = EntityDecoder::with_arc_index(&self.content, self.entity_index.clone());
+        self.index
+            .query(text, &mut decoder)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Get number of indexed products
+    #[wasm_bindgen(getter, js_name = productCount)]
+    pub fn product_count(&self) -> usize {
+        self.index.product_count()
+    }
+}
+
+/// Element filter as passed to `setElementFilter`
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ElementFilterOptions {
+    types: Vec<String>,
+    exclude_types: Vec<String>,
+    global_ids: Vec<String>,
+    storeys: Vec<String>,
+    properties: Vec<String>,
+}
+
 /// Main IFC-Lite API
 #[wasm_bindgen]
 pub struct IfcAPI {
     initialized: bool,
+    representation_selection: RepresentationSelection,
+    element_filter: ElementFilter,
 }
 
 #[wasm_bindgen]
@@ -225,7 +292,11 @@ impl IfcAPI {
         #[cfg(feature = "console_error_panic_hook")]
         console_error_panic_hook::set_once();
 
-        Self { initialized: true }
+        Self {
+            initialized: true,
+            representation_selection: RepresentationSelection::default(),
+            element_filter: ElementFilter::default(),
+        }
     }
 
     /// Check if API is initialized
@@ -234,6 +305,57 @@ impl IfcAPI {
         self.initialized
     }
 
+    /// Choose which shape representations are meshed by all parse methods
+    /// Comma-separated RepresentationIdentifiers in preference order; the first
+    /// one an element has wins. An entry can require a sub-context TargetView
+    /// after `@`. An empty string restores solid body geometry.
+    ///
+    /// Example:
+    /// ```javascript
+    /// api.setRepresentations('FootPrint,Body'); // slab footprints for plan views
+    /// api.setRepresentations('FootPrint@PLAN_VIEW,Body@MODEL_VIEW');
+    /// ```
+    #[wasm_bindgen(js_name = setRepresentations)]
+    pub fn set_representations(&mut self, identifiers: &str) {
+        self.representation_selection = RepresentationSelection::parse(identifiers);
+    }
+
+    /// Current representation selection, e.g. "Body" or "Axis,Body"
+    #[wasm_bindgen(getter)]
+    pub fn representations(&self) -> String {
+        self.representation_selection.to_string()
+    }
+
+    /// Choose which products are meshed by all parse methods
+    /// Unselected products are skipped before any geometry work. All given
+    /// criteria must hold; types include their subtypes. Pass `null` to mesh
+    /// everything again.
+    ///
+    /// Example:
+    /// ```javascript
+    /// api.setElementFilter({
+    ///   types: ['IfcWall', 'IfcSlab'],
+    ///   storeys: ['Level 1'],
+    ///   properties: ['Pset_WallCommon.IsExternal=true'],
+    /// });
+    /// ```
+    #[wasm_bindgen(js_name = setElementFilter)]
+    pub fn set_element_filter(&mut self, filter: JsValue) -> Result<(), JsValue> {
+        if filter.is_null() || filter.is_undefined() {
+            self.element_filter = ElementFilter::default();
+            return Ok(());
+        }
+        let options: ElementFilterOptions = serde_wasm_bindgen::from_value(filter)?;
+        self.element_filter = ElementFilter::new()
+            .with_types(&options.types)
+            .and_then(|f| f.with_excluded_types(&options.exclude_types))
+            .and_then(|f| f.with_properties(&options.properties))
+            .map_err(|e| JsValue::from_str(&e.to_string()))?
+            .with_global_ids(&options.global_ids)
+            .with_storeys(&options.storeys);
+        Ok(())
+    }
+
     /// Parse IFC file with streaming events
     /// Calls the callback function for each parse event
     ///
@@ -347,9 +469,11 @@ impl IfcAPI {
         // Create scanner and decoder with pre-built index
         let mut scanner = EntityScanner::new(&content);
         let mut decoder = EntityDecoder::with_index(&content, entity_index);
+        let matcher = self.element_filter.compile(&content, &mut decoder);
 
         // Create geometry router (reuses processor instances)
-        let router = GeometryRouter::with_units(&content, &mut decoder);
+        let mut router = GeometryRouter::with_units(&content, &mut decoder);
+        router.set_representation_selection(self.representation_selection.clone());
 
         // Collect all meshes first (better for batch merge)
         let mut meshes: Vec<Mesh> = Vec::with_capacity(2000);
@@ -357,7 +481,9 @@ impl IfcAPI {
         // Process all building elements
         while let Some((id, type_name, start, end)) = scanner.next_entity() {
             // Check if this is a building element type
-            if !ifc_lite_core::has_geometry_by_name(type_name) {
+            if !ifc_lite_core::has_geometry_by_name(type_name)
+                || !matcher.matches_name(id, type_name)
+            {
                 continue;
             }
 
@@ -407,10 +533,10 @@ impl IfcAPI {
 
         // Create decoder with pre-built index
         let mut decoder = EntityDecoder::with_index(&content, entity_index.clone());
+        let matcher = self.element_filter.compile(&content, &mut decoder);
 
         // Build style index: first map geometry IDs to colors, then map element IDs to colors
-        let geometry_styles = build_geometry_style_index(&content, &mut decoder);
-        let style_index = build_element_style_index(&content, &geometry_styles, &mut decoder);
+        let style_index = StyleIndex::build(&content, &mut decoder);
 
         // OPTIMIZATION: Collect all FacetedBrep IDs for batch processing
         // Also build void relationship index (host → openings)
@@ -435,6 +561,7 @@ impl IfcAPI {
 
         // Create geometry router (without RTC offset initially)
         let mut router = GeometryRouter::with_units(&content, &mut decoder);
+        router.set_representation_selection(self.representation_selection.clone());
 
         // DETECT RTC OFFSET from actual building element transforms
         // This is more reliable than scanning cartesian points because it uses
@@ -450,7 +577,7 @@ impl IfcAPI {
 
         // Batch preprocess FacetedBrep entities for maximum parallelism
         // This triangulates ALL faces from ALL BREPs in one parallel batch
-        if !faceted_brep_ids.is_empty() {
+        if !faceted_brep_ids.is_empty() && matcher.is_all() {
             router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
         }
 
@@ -472,7 +599,9 @@ impl IfcAPI {
         // Process all building elements
         while let Some((id, type_name, start, end)) = scanner.next_entity() {
             // Check if this is a building element type
-            if !ifc_lite_core::has_geometry_by_name(type_name) {
+            if !ifc_lite_core::has_geometry_by_name(type_name)
+                || !matcher.matches_name(id, type_name)
+            {
                 continue;
             }
 
@@ -500,10 +629,7 @@ impl IfcAPI {
                         }
 
                         // Try to get color from style index, otherwise use default
-                        let color = style_index
-                            .get(&id)
-                            .copied()
-                            .unwrap_or_else(|| get_default_color_for_type(&entity.ifc_type));
+                        let color = style_index.element_color(id, &entity.ifc_type);
 
                         // Safety filter: exclude meshes with unreasonable coordinates after RTC
                         const MAX_REASONABLE_OFFSET: f32 = 50_000.0; // 50km from RTC center
@@ -620,10 +746,10 @@ impl IfcAPI {
 
         // Create decoder with pre-built index
         let mut decoder = EntityDecoder::with_index(&content, entity_index.clone());
+        let matcher = self.element_filter.compile(&content, &mut decoder);
 
         // Build style index: first map geometry IDs to colors, then map element IDs to colors
-        let geometry_styles = build_geometry_style_index(&content, &mut decoder);
-        let style_index = build_element_style_index(&content, &geometry_styles, &mut decoder);
+        let style_index = StyleIndex::build(&content, &mut decoder);
 
         // OPTIMIZATION: Collect all FacetedBrep IDs for batch processing
         let mut scanner = EntityScanner::new(&content);
@@ -635,10 +761,11 @@ impl IfcAPI {
         }
 
         // Create geometry router (reuses processor instances)
-        let router = GeometryRouter::with_units(&content, &mut decoder);
+        let mut router = GeometryRouter::with_units(&content, &mut decoder);
+        router.set_representation_selection(self.representation_selection.clone());
 
         // Batch preprocess FacetedBrep entities for maximum parallelism
-        if !faceted_brep_ids.is_empty() {
+        if !faceted_brep_ids.is_empty() && matcher.is_all() {
             router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
         }
 
@@ -655,7 +782,9 @@ impl IfcAPI {
         // Process all building elements
         while let Some((id, type_name, start, end)) = scanner.next_entity() {
             // Check if this is a building element type
-            if !ifc_lite_core::has_geometry_by_name(type_name) {
+            if !ifc_lite_core::has_geometry_by_name(type_name)
+                || !matcher.matches_name(id, type_name)
+            {
                 continue;
             }
 
@@ -684,10 +813,7 @@ impl IfcAPI {
                         let geometry_hash = hasher.finish();
 
                         // Try to get color from style index, otherwise use default
-                        let color = style_index
-                            .get(&id)
-                            .copied()
-                            .unwrap_or_else(|| get_default_color_for_type(&entity.ifc_type));
+                        let color = style_index.element_color(id, &entity.ifc_type);
 
                         // Convert Matrix4<f64> to [f64; 16] array (column-major for WebGPU)
                         let mut transform_array = [0.0; 16];
@@ -761,9 +887,14 @@ impl IfcAPI {
         use rustc_hash::{FxHashMap, FxHasher};
         use std::hash::{Hash, Hasher};
 
+        let selection = self.selection_for(&options);
+        let filter = self.element_filter.clone();
+
         let promise = Promise::new(&mut |resolve, _reject| {
             let content = content.clone();
             let options = options.clone();
+            let selection = selection.clone();
+            let filter = filter.clone();
 
             spawn_local(async move {
                 // Parse options
@@ -784,11 +915,10 @@ impl IfcAPI {
                 // Build entity index once upfront for O(1) lookups
                 let entity_index = build_entity_index(&content);
                 let mut decoder = EntityDecoder::with_index(&content, entity_index.clone());
+                let matcher = filter.compile(&content, &mut decoder);
 
                 // Build style index
-                let geometry_styles = build_geometry_style_index(&content, &mut decoder);
-                let style_index =
-                    build_element_style_index(&content, &geometry_styles, &mut decoder);
+                let style_index = StyleIndex::build(&content, &mut decoder);
 
                 // Collect FacetedBrep IDs for batch preprocessing
                 let mut scanner = EntityScanner::new(&content);
@@ -800,10 +930,11 @@ impl IfcAPI {
                 }
 
                 // Create geometry router
-                let router = GeometryRouter::with_units(&content, &mut decoder);
+                let mut router = GeometryRouter::with_units(&content, &mut decoder);
+                router.set_representation_selection(selection);
 
                 // Batch preprocess FacetedBreps
-                if !faceted_brep_ids.is_empty() {
+                if !faceted_brep_ids.is_empty() && matcher.is_all() {
                     router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
                 }
 
@@ -825,7 +956,9 @@ impl IfcAPI {
 
                 // First pass - process simple geometry immediately
                 while let Some((id, type_name, start, end)) = scanner.next_entity() {
-                    if !ifc_lite_core::has_geometry_by_name(type_name) {
+                    if !ifc_lite_core::has_geometry_by_name(type_name)
+                        || !matcher.matches_name(id, type_name)
+                    {
                         continue;
                     }
 
@@ -871,10 +1004,7 @@ impl IfcAPI {
                                     let geometry_hash = hasher.finish();
 
                                     // Get color
-                                    let color = style_index
-                                        .get(&id)
-                                        .copied()
-                                        .unwrap_or_else(|| get_default_color_for_type(&ifc_type));
+                                    let color = style_index.element_color(id, &ifc_type);
 
                                     // Convert Matrix4<f64> to [f64; 16] array (column-major for WebGPU)
                                     let mut transform_array = [0.0; 16];
@@ -1027,10 +1157,7 @@ impl IfcAPI {
                                 let geometry_hash = hasher.finish();
 
                                 // Get color
-                                let color = style_index
-                                    .get(&id)
-                                    .copied()
-                                    .unwrap_or_else(|| get_default_color_for_type(&ifc_type));
+                                let color = style_index.element_color(id, &ifc_type);
 
                                 // Convert transform (column-major for WebGPU)
                                 let mut transform_array = [0.0; 16];
@@ -1204,9 +1331,14 @@ impl IfcAPI {
         use ifc_lite_core::{EntityDecoder, EntityScanner};
         use ifc_lite_geometry::{calculate_normals, GeometryRouter};
 
+        let selection = self.selection_for(&options);
+        let filter = self.element_filter.clone();
+
         let promise = Promise::new(&mut |resolve, _reject| {
             let content = content.clone();
             let options = options.clone();
+            let selection = selection.clone();
+            let filter = filter.clone();
 
             spawn_local(async move {
                 // Parse options - smaller default batch size for faster first frame
@@ -1235,15 +1367,16 @@ impl IfcAPI {
                 // Build entity index for lookups
                 let entity_index = ifc_lite_core::build_entity_index(&content);
                 let mut decoder = EntityDecoder::with_index(&content, entity_index.clone());
+                let matcher = filter.compile(&content, &mut decoder);
 
                 // OPTIMIZATION: Defer style building for faster first frame
                 // Simple geometry will use default colors initially, styles applied to complex geometry
                 // This trades slightly incorrect initial colors for much faster first render
-                let mut style_index: rustc_hash::FxHashMap<u32, [f32; 4]> =
-                    rustc_hash::FxHashMap::default();
+                let mut style_index = StyleIndex::default();
 
                 // Create geometry router
                 let mut router = GeometryRouter::with_units(&content, &mut decoder);
+                router.set_representation_selection(selection);
 
                 // DETECT RTC OFFSET from actual building element transforms (same as sync version)
                 let rtc_offset = router.detect_rtc_offset_from_first_element(&content, &mut decoder);
@@ -1302,7 +1435,9 @@ impl IfcAPI {
 
                 // Process elements - simple geometry immediately, defer complex
                 while let Some((id, type_name, start, end)) = scanner.next_entity() {
-                    if !ifc_lite_core::has_geometry_by_name(type_name) {
+                    if !ifc_lite_core::has_geometry_by_name(type_name)
+                        || !matcher.matches_name(id, type_name)
+                    {
                         continue;
                     }
 
@@ -1342,10 +1477,7 @@ impl IfcAPI {
                                             calculate_normals(&mut mesh);
                                         }
 
-                                        let color = style_index
-                                            .get(&id)
-                                            .copied()
-                                            .unwrap_or_else(|| get_default_color_for_type(&ifc_type));
+                                        let color = style_index.element_color(id, &ifc_type);
                                         total_vertices += mesh.positions.len() / 3;
                                         total_triangles += mesh.indices.len() / 3;
 
@@ -1408,14 +1540,13 @@ impl IfcAPI {
 
                 // NOW build styles - after first batches are yielded for faster first frame
                 // Complex geometry will have proper IFC colors
-                let geometry_styles = build_geometry_style_index(&content, &mut decoder);
-                style_index = build_element_style_index(&content, &geometry_styles, &mut decoder);
+                style_index = StyleIndex::build(&content, &mut decoder);
 
                 // Send color updates for already-processed simple geometry
                 if let Some(ref callback) = on_color_update {
                     let color_updates = js_sys::Map::new();
                     for &id in &processed_simple_ids {
-                        if let Some(&color) = style_index.get(&id) {
+                        if let Some(color) = style_index.element_material(id).map(SurfaceMaterial::color) {
                             // Convert [f32; 4] to JS array
                             let js_color = js_sys::Array::new();
                             js_color.push(&color[0].into());
@@ -1432,7 +1563,7 @@ impl IfcAPI {
 
                 // CRITICAL: Batch preprocess FacetedBreps BEFORE complex phase
                 // This triangulates ALL faces in parallel - massive speedup for repeated geometry
-                if !faceted_brep_ids.is_empty() {
+                if !faceted_brep_ids.is_empty() && matcher.is_all() {
                     router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
                 }
 
@@ -1442,7 +1573,7 @@ impl IfcAPI {
                     if let Ok(entity) = decoder.decode_at_with_id(id, start, end) {
                         let has_openings = void_index.contains_key(&id);
                         let ifc_type_name = ifc_type.name().to_string();
-                        let default_color = get_default_color_for_type(&ifc_type);
+                        let default_color = default_color(&ifc_type);
 
                         if has_openings {
                             // Element has openings - use void subtraction (merged mesh)
@@ -1457,8 +1588,8 @@ impl IfcAPI {
                                     }
 
                                     let color = style_index
-                                        .get(&id)
-                                        .copied()
+                                        .element_material(id)
+                                        .map(SurfaceMaterial::color)
                                         .unwrap_or(default_color);
 
                                     total_vertices += mesh.positions.len() / 3;
@@ -1499,8 +1630,10 @@ impl IfcAPI {
 
                                     // Look up color by geometry item ID (resolving MappedItem chains),
                                     // then by element ID, then default
-                                    let color = find_color_for_geometry(sub.geometry_id, &geometry_styles, &mut decoder)
-                                        .or_else(|| style_index.get(&id).copied())
+                                    let color = style_index
+                                        .find_geometry_material(sub.geometry_id, &mut decoder)
+                                        .or_else(|| style_index.element_material(id))
+                                        .map(SurfaceMaterial::color)
                                         .unwrap_or(default_color);
 
                                     total_vertices += mesh.positions.len() / 3;
@@ -1521,8 +1654,8 @@ impl IfcAPI {
                                         }
 
                                         let color = style_index
-                                            .get(&id)
-                                            .copied()
+                                            .element_material(id)
+                                            .map(SurfaceMaterial::color)
                                             .unwrap_or(default_color);
 
                                         total_vertices += mesh.positions.len() / 3;
@@ -1756,10 +1889,10 @@ impl IfcAPI {
         // Build entity index once upfront
         let entity_index = build_entity_index(&content);
         let mut decoder = EntityDecoder::with_index(&content, entity_index.clone());
+        let matcher = self.element_filter.compile(&content, &mut decoder);
 
         // Build style indices
-        let geometry_styles = build_geometry_style_index(&content, &mut decoder);
-        let style_index = build_element_style_index(&content, &geometry_styles, &mut decoder);
+        let style_index = StyleIndex::build(&content, &mut decoder);
 
         // OPTIMIZATION: Collect all FacetedBrep IDs for batch processing
         let mut scanner = EntityScanner::new(&content);
@@ -1770,10 +1903,11 @@ impl IfcAPI {
             }
         }
 
-        let router = GeometryRouter::with_units(&content, &mut decoder);
+        let mut router = GeometryRouter::with_units(&content, &mut decoder);
+        router.set_representation_selection(self.representation_selection.clone());
 
         // Batch preprocess FacetedBrep entities for maximum parallelism
-        if !faceted_brep_ids.is_empty() {
+        if !faceted_brep_ids.is_empty() && matcher.is_all() {
             router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
         }
 
@@ -1788,7 +1922,9 @@ impl IfcAPI {
 
         // Process all building elements
         while let Some((id, type_name, start, end)) = scanner.next_entity() {
-            if !ifc_lite_core::has_geometry_by_name(type_name) {
+            if !ifc_lite_core::has_geometry_by_name(type_name)
+                || !matcher.matches_name(id, type_name)
+            {
                 continue;
             }
 
@@ -1808,10 +1944,7 @@ impl IfcAPI {
                         // Collect positions for RTC calculation
                         all_positions.extend_from_slice(&mesh.positions);
 
-                        let color = style_index
-                            .get(&id)
-                            .copied()
-                            .unwrap_or_else(|| get_default_color_for_type(&entity.ifc_type));
+                        let color = style_index.element_color(id, &entity.ifc_type);
 
                         let ifc_type_name = entity.ifc_type.name().to_string();
                         let mesh_data = MeshDataJs::new(id, ifc_type_name, mesh, color);
@@ -1878,10 +2011,10 @@ impl IfcAPI {
         // Build entity index once upfront for O(1) lookups
         let entity_index = build_entity_index(&content);
         let mut decoder = EntityDecoder::with_index(&content, entity_index.clone());
+        let matcher = self.element_filter.compile(&content, &mut decoder);
 
         // Build style index for colors
-        let geometry_styles = build_geometry_style_index(&content, &mut decoder);
-        let style_index = build_element_style_index(&content, &geometry_styles, &mut decoder);
+        let style_index = StyleIndex::build(&content, &mut decoder);
 
         // Collect FacetedBrep IDs for batch preprocessing
         let mut scanner = EntityScanner::new(&content);
@@ -1904,6 +2037,9 @@ impl IfcAPI {
 
         // Create geometry router (without RTC offset initially)
         let mut router = GeometryRouter::with_units(&content, &mut decoder);
+        router.set_representation_selection(self.representation_selection.clone());
+        let texture_maps = build_texture_map_index(&content, &mut decoder);
+        router.set_texture_maps(std::sync::Arc::new(texture_maps));
 
         // DETECT RTC OFFSET from actual building element transforms
         let rtc_offset = router.detect_rtc_offset_from_first_element(&content, &mut decoder);
@@ -1916,7 +2052,7 @@ impl IfcAPI {
         }
 
         // Batch preprocess FacetedBreps
-        if !faceted_brep_ids.is_empty() {
+        if !faceted_brep_ids.is_empty() && matcher.is_all() {
             router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
         }
 
@@ -1930,43 +2066,49 @@ impl IfcAPI {
 
         // Process all building elements
         while let Some((id, type_name, start, end)) = scanner.next_entity() {
-            if !ifc_lite_core::has_geometry_by_name(type_name) {
+            if !ifc_lite_core::has_geometry_by_name(type_name)
+                || !matcher.matches_name(id, type_name)
+            {
                 continue;
             }
 
             if let Ok(entity) = decoder.decode_at_with_id(id, start, end) {
-                // Check if entity has representation
+                // Check if entity has representation (grids are drawn from their axes)
                 let has_representation = entity.get(6).map(|a| !a.is_null()).unwrap_or(false);
-                if !has_representation {
+                if !has_representation && entity.ifc_type != ifc_lite_core::IfcType::IfcGrid {
                     continue;
                 }
 
-                if let Ok(mut mesh) =
-                    router.process_element_with_voids(&entity, &mut decoder, &void_index)
-                {
-                    if !mesh.is_empty() {
+                match router.process_element_with_voids(&entity, &mut decoder, &void_index) {
+                    Ok(mut mesh) if !mesh.is_empty() => {
                         // Calculate normals if not present or incomplete
                         // CSG operations may produce partial normals, so check for matching count
                         if mesh.normals.len() != mesh.positions.len() {
                             calculate_normals(&mut mesh);
                         }
 
-                        // Get color from style index or default
-                        let color = style_index
-                            .get(&id)
-                            .copied()
-                            .unwrap_or_else(|| get_default_color_for_type(&entity.ifc_type));
+                        // Get material and color from style index or default
+                        let material = style_index.element_material(id);
+                        let color = style_index.element_color(id, &entity.ifc_type);
 
                         // Add to GPU geometry (interleaves and converts coordinates)
-                        gpu_geometry.add_mesh(
+                        gpu_geometry.add_mesh_with_material(
                             id,
                             entity.ifc_type.name(),
-                            &mesh.positions,
-                            &mesh.normals,
-                            &mesh.indices,
+                            &mesh,
                             color,
+                            material,
                         );
                     }
+                    _ => {
+                        // Curve-only elements (grids, annotations, axes) render as lines
+                        if let Ok(curves) = router.process_element_curves(&entity, &mut decoder) {
+                            if !curves.is_empty() {
+                                let color = style_index.element_color(id, &entity.ifc_type);
+                                gpu_geometry.add_curves(id, entity.ifc_type.name(), &curves, color);
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -2015,9 +2157,14 @@ impl IfcAPI {
         use ifc_lite_core::{build_entity_index, EntityDecoder, EntityScanner};
         use ifc_lite_geometry::{calculate_normals, GeometryRouter};
 
+        let selection = self.selection_for(&options);
+        let filter = self.element_filter.clone();
+
         let promise = Promise::new(&mut |resolve, _reject| {
             let content = content.clone();
             let options = options.clone();
+            let selection = selection.clone();
+            let filter = filter.clone();
 
             spawn_local(async move {
                 // Parse options
@@ -2038,11 +2185,10 @@ impl IfcAPI {
                 // Build entity index
                 let entity_index = build_entity_index(&content);
                 let mut decoder = EntityDecoder::with_index(&content, entity_index.clone());
+                let matcher = filter.compile(&content, &mut decoder);
 
                 // Build style index
-                let geometry_styles = build_geometry_style_index(&content, &mut decoder);
-                let style_index =
-                    build_element_style_index(&content, &geometry_styles, &mut decoder);
+                let style_index = StyleIndex::build(&content, &mut decoder);
 
                 // Collect FacetedBrep IDs and void relationships
                 let mut scanner = EntityScanner::new(&content);
@@ -2066,6 +2212,9 @@ impl IfcAPI {
 
                 // Create geometry router
                 let mut router = GeometryRouter::with_units(&content, &mut decoder);
+                router.set_representation_selection(selection);
+                let texture_maps = build_texture_map_index(&content, &mut decoder);
+                router.set_texture_maps(std::sync::Arc::new(texture_maps));
 
                 // DETECT RTC OFFSET from actual building element transforms
                 let rtc_offset = router.detect_rtc_offset_from_first_element(&content, &mut decoder);
@@ -2078,7 +2227,7 @@ impl IfcAPI {
                 }
 
                 // Batch preprocess FacetedBreps
-                if !faceted_brep_ids.is_empty() {
+                if !faceted_brep_ids.is_empty() && matcher.is_all() {
                     router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
                 }
 
@@ -2098,7 +2247,7 @@ impl IfcAPI {
                 let flush_batch = |batch: &mut GpuGeometry,
                                    on_batch: &Option<Function>,
                                    progress: &JsValue| {
-                    if batch.mesh_count() == 0 {
+                    if batch.is_empty() {
                         return;
                     }
 
@@ -2117,7 +2266,9 @@ impl IfcAPI {
 
                 // First pass - process simple geometry immediately
                 while let Some((id, type_name, start, end)) = scanner.next_entity() {
-                    if !ifc_lite_core::has_geometry_by_name(type_name) {
+                    if !ifc_lite_core::has_geometry_by_name(type_name)
+                        || !matcher.matches_name(id, type_name)
+                    {
                         continue;
                     }
 
@@ -2155,21 +2306,18 @@ impl IfcAPI {
                                             calculate_normals(&mut mesh);
                                         }
 
-                                        let color = style_index
-                                            .get(&id)
-                                            .copied()
-                                            .unwrap_or_else(|| get_default_color_for_type(&ifc_type));
+                                        let material = style_index.element_material(id);
+                                        let color = style_index.element_color(id, &ifc_type);
 
                                         total_vertices += mesh.positions.len() / 3;
                                         total_triangles += mesh.indices.len() / 3;
 
-                                        current_batch.add_mesh(
+                                        current_batch.add_mesh_with_material(
                                             id,
                                             ifc_type.name(),
-                                            &mesh.positions,
-                                            &mesh.normals,
-                                            &mesh.indices,
+                                            &mesh,
                                             color,
+                                            material,
                                         );
                                         processed += 1;
                                         total_meshes += 1;
@@ -2197,7 +2345,7 @@ impl IfcAPI {
                 }
 
                 // Flush remaining simple geometry
-                if current_batch.mesh_count() > 0 {
+                if !current_batch.is_empty() {
                     let progress = js_sys::Object::new();
                     set_js_prop(&progress, "phase", &"simple_complete".into());
                     flush_batch(&mut current_batch, &on_batch, &progress.into());
@@ -2208,32 +2356,44 @@ impl IfcAPI {
                 let total_elements = processed + deferred_complex.len();
                 for (id, start, end, ifc_type) in deferred_complex {
                     if let Ok(entity) = decoder.decode_at_with_id(id, start, end) {
-                        if let Ok(mut mesh) =
-                            router.process_element_with_voids(&entity, &mut decoder, &void_index)
+                        match router.process_element_with_voids(&entity, &mut decoder, &void_index)
                         {
-                            if !mesh.is_empty() {
+                            Ok(mut mesh) if !mesh.is_empty() => {
                                 if mesh.normals.len() != mesh.positions.len() {
                                     calculate_normals(&mut mesh);
                                 }
 
-                                let color = style_index
-                                    .get(&id)
-                                    .copied()
-                                    .unwrap_or_else(|| get_default_color_for_type(&ifc_type));
+                                let material = style_index.element_material(id);
+                                let color = style_index.element_color(id, &ifc_type);
 
                                 total_vertices += mesh.positions.len() / 3;
                                 total_triangles += mesh.indices.len() / 3;
 
-                                current_batch.add_mesh(
+                                current_batch.add_mesh_with_material(
                                     id,
                                     ifc_type.name(),
-                                    &mesh.positions,
-                                    &mesh.normals,
-                                    &mesh.indices,
+                                    &mesh,
                                     color,
+                                    material,
                                 );
                                 total_meshes += 1;
                             }
+                            _ => {
+                                // Curve-only elements (grids, annotations, axes) render as lines
+                                if let Ok(curves) =
+                                    router.process_element_curves(&entity, &mut decoder)
+                                {
+                                    if !curves.is_empty() {
+                                        let color = style_index.element_color(id, &ifc_type);
+                                        current_batch.add_curves(
+                                            id,
+                                            ifc_type.name(),
+                                            &curves,
+                                            color,
+                                        );
+                                    }
+                                }
+                            }
                         }
                     }
 
@@ -2254,7 +2414,7 @@ impl IfcAPI {
                 }
 
                 // Final flush
-                if current_batch.mesh_count() > 0 {
+                if !current_batch.is_empty() {
                     let progress = js_sys::Object::new();
                     set_js_prop(&progress, "percent", &100u32.into());
                     set_js_prop(&progress, "phase", &"complete".into());
@@ -2302,10 +2462,10 @@ impl IfcAPI {
         // Build entity index
         let entity_index = build_entity_index(&content);
         let mut decoder = EntityDecoder::with_index(&content, entity_index.clone());
+        let matcher = self.element_filter.compile(&content, &mut decoder);
 
         // Build style index
-        let geometry_styles = build_geometry_style_index(&content, &mut decoder);
-        let style_index = build_element_style_index(&content, &geometry_styles, &mut decoder);
+        let style_index = StyleIndex::build(&content, &mut decoder);
 
         // Collect FacetedBrep IDs
         let mut scanner = EntityScanner::new(&content);
@@ -2318,10 +2478,11 @@ impl IfcAPI {
         }
 
         // Create geometry router
-        let router = GeometryRouter::with_units(&content, &mut decoder);
+        let mut router = GeometryRouter::with_units(&content, &mut decoder);
+        router.set_representation_selection(self.representation_selection.clone());
 
         // Batch preprocess FacetedBreps
-        if !faceted_brep_ids.is_empty() {
+        if !faceted_brep_ids.is_empty() && matcher.is_all() {
             router.preprocess_faceted_breps(&faceted_brep_ids, &mut decoder);
         }
 
@@ -2335,7 +2496,9 @@ impl IfcAPI {
 
         // Process all building elements
         while let Some((id, type_name, start, end)) = scanner.next_entity() {
-            if !ifc_lite_core::has_geometry_by_name(type_name) {
+            if !ifc_lite_core::has_geometry_by_name(type_name)
+                || !matcher.matches_name(id, type_name)
+            {
                 continue;
             }
 
@@ -2361,10 +2524,7 @@ impl IfcAPI {
                         let geometry_hash = hasher.finish();
 
                         // Get color
-                        let color = style_index
-                            .get(&id)
-                            .copied()
-                            .unwrap_or_else(|| get_default_color_for_type(&entity.ifc_type));
+                        let color = style_index.element_color(id, &entity.ifc_type);
 
                         // Convert transform to column-major array
                         let mut transform_array = [0.0f64; 16];
@@ -2414,6 +2574,41 @@ impl IfcAPI {
         collection
     }
 
+    /// Export the model as binary glTF (GLB)
+    ///
+    /// Identical geometries are grouped as in `parseToGpuInstancedGeometry`
+    /// and written with EXT_mesh_gpu_instancing. Node extras carry
+    /// `expressId`, `globalId` and `ifcType` for every element.
+    ///
+    /// Example:
+    /// ```javascript
+    /// const api = new IfcAPI();
+    /// const glb = api.exportGlb(ifcData);
+    /// const blob = new Blob([glb], { type: 'model/gltf-binary' });
+    /// ```
+    #[wasm_bindgen(js_name = exportGlb)]
+    pub fn export_glb(&self, content: String) -> Vec<u8> {
+        use ifc_lite_core::EntityDecoder;
+        use ifc_lite_geometry::{detect_rtc_offset, mesh_elements_local_with_rtc, GlbBuilder};
+
+        let mut decoder = EntityDecoder::new(&content);
+        let matcher = self.element_filter.compile(&content, &mut decoder);
+        let rtc = detect_rtc_offset(&content);
+        let elements = mesh_elements_local_with_rtc(
+            &content,
+            &self.representation_selection,
+            &matcher,
+            &rtc,
+        );
+
+        let mut builder = GlbBuilder::new();
+        builder.set_rtc_offset(&rtc);
+        for element in elements {
+            builder.add(element.info, &element.mesh, &element.transform, element.color);
+        }
+        builder.build()
+    }
+
     /// Debug: Test processing entity #953 (FacetedBrep wall)
     #[wasm_bindgen(js_name = debugProcessEntity953)]
     pub fn debug_process_entity_953(&self, content: String) -> String {
@@ -2501,6 +2696,18 @@ impl Default for IfcAPI {
     }
 }
 
+impl IfcAPI {
+    /// Representation selection for an async call: the `representations`
+    /// option if given, otherwise the API-wide setting
+    fn selection_for(&self, options: &JsValue) -> RepresentationSelection {
+        js_sys::Reflect::get(options, &"representations".into())
+            .ok()
+            .and_then(|v| v.as_string())
+            .map(|spec| RepresentationSelection::parse(&spec))
+            .unwrap_or_else(|| self.representation_selection.clone())
+    }
+}
+
 /// Convert ParseEvent to JavaScript object
 fn parse_event_to_js(event: &ParseEvent) -> JsValue {
     let obj = js_sys::Object::new();
@@ -2568,414 +2775,6 @@ fn parse_event_to_js(event: &ParseEvent) -> JsValue {
     obj.into()
 }
 
-/// Build style index: maps geometry express IDs to RGBA colors
-/// Follows the chain: IfcStyledItem → IfcSurfaceStyle → IfcSurfaceStyleRendering → IfcColourRgb
-fn build_geometry_style_index(
-    content: &str,
-    decoder: &mut ifc_lite_core::EntityDecoder,
-) -> rustc_hash::FxHashMap<u32, [f32; 4]> {
-    use ifc_lite_core::EntityScanner;
-    use rustc_hash::FxHashMap;
-
-    let mut style_index: FxHashMap<u32, [f32; 4]> = FxHashMap::default();
-    let mut scanner = EntityScanner::new(content);
-
-    // First pass: find all IfcStyledItem entities
-    while let Some((id, type_name, start, end)) = scanner.next_entity() {
-        if type_name != "IFCSTYLEDITEM" {
-            continue;
-        }
-
-        // Decode the IfcStyledItem
-        let styled_item = match decoder.decode_at_with_id(id, start, end) {
-            Ok(entity) => entity,
-            Err(_) => continue,
-        };
-
-        // IfcStyledItem: Item (ref to geometry), Styles (list of style refs), Name
-        // Attribute 0: Item (geometry reference)
-        let geometry_id = match styled_item.get_ref(0) {
-            Some(id) => id,
-            None => continue,
-        };
-
-        // Skip if we already have a color for this geometry
-        if style_index.contains_key(&geometry_id) {
-            continue;
-        }
-
-        // Attribute 1: Styles (list of style assignment refs)
-        let styles_attr = match styled_item.get(1) {
-            Some(attr) => attr,
-            None => continue,
-        };
-
-        // Extract color from styles list
-        if let Some(color) = extract_color_from_styles(styles_attr, decoder) {
-            style_index.insert(geometry_id, color);
-        }
-    }
-
-    style_index
-}
-
-/// Build element style index: maps building element IDs to RGBA colors
-/// Follows: Element → IfcProductDefinitionShape → IfcShapeRepresentation → geometry items
-fn build_element_style_index(
-    content: &str,
-    geometry_styles: &rustc_hash::FxHashMap<u32, [f32; 4]>,
-    decoder: &mut ifc_lite_core::EntityDecoder,
-) -> rustc_hash::FxHashMap<u32, [f32; 4]> {
-    use ifc_lite_core::EntityScanner;
-    use rustc_hash::FxHashMap;
-
-    let mut element_styles: FxHashMap<u32, [f32; 4]> = FxHashMap::default();
-    let mut scanner = EntityScanner::new(content);
-
-    // Scan all building elements
-    while let Some((element_id, type_name, start, end)) = scanner.next_entity() {
-        // Check if this is a building element type
-        if !ifc_lite_core::has_geometry_by_name(type_name) {
-            continue;
-        }
-
-        // Decode the element
-        let element = match decoder.decode_at_with_id(element_id, start, end) {
-            Ok(entity) => entity,
-            Err(_) => continue,
-        };
-
-        // Building elements have Representation attribute at index 6
-        // IfcProduct: GlobalId, OwnerHistory, Name, Description, ObjectType, ObjectPlacement, Representation
-        let repr_id = match element.get_ref(6) {
-            Some(id) => id,
-            None => continue,
-        };
-
-        // Decode IfcProductDefinitionShape
-        let product_shape = match decoder.decode_by_id(repr_id) {
-            Ok(entity) => entity,
-            Err(_) => continue,
-        };
-
-        // IfcProductDefinitionShape: Name, Description, Representations (list)
-        // Attribute 2: Representations
-        let reprs_attr = match product_shape.get(2) {
-            Some(attr) => attr,
-            None => continue,
-        };
-
-        let reprs_list = match reprs_attr.as_list() {
-            Some(list) => list,
-            None => continue,
-        };
-
-        // Look through representations for geometry with styles
-        for repr_item in reprs_list {
-            let shape_repr_id = match repr_item.as_entity_ref() {
-                Some(id) => id,
-                None => continue,
-            };
-
-            // Decode IfcShapeRepresentation
-            let shape_repr = match decoder.decode_by_id(shape_repr_id) {
-                Ok(entity) => entity,
-                Err(_) => continue,
-            };
-
-            // IfcShapeRepresentation: ContextOfItems, RepresentationIdentifier, RepresentationType, Items
-            // Attribute 3: Items (list of geometry items)
-            let items_attr = match shape_repr.get(3) {
-                Some(attr) => attr,
-                None => continue,
-            };
-
-            let items_list = match items_attr.as_list() {
-                Some(list) => list,
-                None => continue,
-            };
-
-            // Check each geometry item for a style
-            for geom_item in items_list {
-                let geom_id = match geom_item.as_entity_ref() {
-                    Some(id) => id,
-                    None => continue,
-                };
-
-                // Check if this geometry has a style, following MappedItem references if needed
-                if let Some(color) =
-                    find_color_for_geometry(geom_id, geometry_styles, decoder)
-                {
-                    element_styles.insert(element_id, color);
-                    break; // Found a color for this element
-                }
-            }
-
-            // If we found a color, stop looking at more representations
-            if element_styles.contains_key(&element_id) {
-                break;
-            }
-        }
-    }
-
-    element_styles
-}
-
-/// Find color for a geometry item, following MappedItem references if needed.
-/// This handles the case where IfcStyledItem points to geometry inside a MappedRepresentation,
-/// not to the MappedItem itself.
-fn find_color_for_geometry(
-    geom_id: u32,
-    geometry_styles: &rustc_hash::FxHashMap<u32, [f32; 4]>,
-    decoder: &mut ifc_lite_core::EntityDecoder,
-) -> Option<[f32; 4]> {
-    use ifc_lite_core::IfcType;
-
-    // First check if this geometry ID directly has a color
-    if let Some(&color) = geometry_styles.get(&geom_id) {
-        return Some(color);
-    }
-
-    // If not, check if it's an IfcMappedItem and follow the reference
-    let geom = decoder.decode_by_id(geom_id).ok()?;
-
-    if geom.ifc_type == IfcType::IfcMappedItem {
-        // IfcMappedItem: MappingSource (IfcRepresentationMap ref), MappingTarget
-        let map_source_id = geom.get_ref(0)?;
-
-        // Decode the IfcRepresentationMap
-        let rep_map = decoder.decode_by_id(map_source_id).ok()?;
-
-        // IfcRepresentationMap: MappingOrigin (IfcAxis2Placement), MappedRepresentation (IfcShapeRepresentation)
-        let mapped_repr_id = rep_map.get_ref(1)?;
-
-        // Decode the mapped IfcShapeRepresentation
-        let mapped_repr = decoder.decode_by_id(mapped_repr_id).ok()?;
-
-        // IfcShapeRepresentation: ContextOfItems, RepresentationIdentifier, RepresentationType, Items
-        // Attribute 3: Items (list of geometry items)
-        let items_attr = mapped_repr.get(3)?;
-        let items_list = items_attr.as_list()?;
-
-        // Check each underlying geometry item for a color
-        for item in items_list {
-            if let Some(underlying_geom_id) = item.as_entity_ref() {
-                // Recursively find color (handles nested MappedItems)
-                if let Some(color) =
-                    find_color_for_geometry(underlying_geom_id, geometry_styles, decoder)
-                {
-                    return Some(color);
-                }
-            }
-        }
-    }
-
-    None
-}
-
-/// Extract RGBA color from IfcStyledItem.Styles attribute
-fn extract_color_from_styles(
-    styles_attr: &ifc_lite_core::AttributeValue,
-    decoder: &mut ifc_lite_core::EntityDecoder,
-) -> Option<[f32; 4]> {
-    // Styles can be a list or a single reference
-    if let Some(list) = styles_attr.as_list() {
-        for item in list {
-            if let Some(style_id) = item.as_entity_ref() {
-                if let Some(color) = extract_color_from_style_assignment(style_id, decoder) {
-                    return Some(color);
-                }
-            }
-        }
-    } else if let Some(style_id) = styles_attr.as_entity_ref() {
-        return extract_color_from_style_assignment(style_id, decoder);
-    }
-
-    None
-}
-
-/// Extract color from IfcPresentationStyleAssignment or IfcSurfaceStyle
-fn extract_color_from_style_assignment(
-    style_id: u32,
-    decoder: &mut ifc_lite_core::EntityDecoder,
-) -> Option<[f32; 4]> {
-    use ifc_lite_core::IfcType;
-
-    let style = decoder.decode_by_id(style_id).ok()?;
-
-    match style.ifc_type {
-        IfcType::IfcPresentationStyle => {
-            // IfcPresentationStyle has Styles at attr 0
-            let styles_attr = style.get(0)?;
-            if let Some(list) = styles_attr.as_list() {
-                for item in list {
-                    if let Some(inner_id) = item.as_entity_ref() {
-                        if let Some(color) = extract_color_from_surface_style(inner_id, decoder) {
-                            return Some(color);
-                        }
-                    }
-                }
-            }
-        }
-        IfcType::IfcSurfaceStyle => {
-            return extract_color_from_surface_style(style_id, decoder);
-        }
-        _ => {
-            // FIX: Handle IfcPresentationStyleAssignment (IFC2x3 entity not in IFC4 schema)
-            // IfcPresentationStyleAssignment has Styles list at attribute 0
-            // It's decoded as Unknown type, so we check by structure
-            let styles_attr = style.get(0)?;
-            if let Some(list) = styles_attr.as_list() {
-                for item in list {
-                    if let Some(inner_id) = item.as_entity_ref() {
-                        if let Some(color) = extract_color_from_surface_style(inner_id, decoder) {
-                            return Some(color);
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    None
-}
-
-/// Extract color from IfcSurfaceStyle
-fn extract_color_from_surface_style(
-    style_id: u32,
-    decoder: &mut ifc_lite_core::EntityDecoder,
-) -> Option<[f32; 4]> {
-    use ifc_lite_core::IfcType;
-
-    let style = decoder.decode_by_id(style_id).ok()?;
-
-    if style.ifc_type != IfcType::IfcSurfaceStyle {
-        return None;
-    }
-
-    // IfcSurfaceStyle: Name, Side, Styles (list of surface style elements)
-    // Attribute 2: Styles
-    let styles_attr = style.get(2)?;
-
-    if let Some(list) = styles_attr.as_list() {
-        for item in list {
-            if let Some(element_id) = item.as_entity_ref() {
-                if let Some(color) = extract_color_from_rendering(element_id, decoder) {
-                    return Some(color);
-                }
-            }
-        }
-    }
-
-    None
-}
-
-/// Extract color from IfcSurfaceStyleRendering or IfcSurfaceStyleShading
-fn extract_color_from_rendering(
-    rendering_id: u32,
-    decoder: &mut ifc_lite_core::EntityDecoder,
-) -> Option<[f32; 4]> {
-    use ifc_lite_core::IfcType;
-
-    let rendering = decoder.decode_by_id(rendering_id).ok()?;
-
-    match rendering.ifc_type {
-        IfcType::IfcSurfaceStyleRendering | IfcType::IfcSurfaceStyleShading => {
-            // Attr 0: SurfaceColour (inherited from IfcSurfaceStyleShading)
-            // Attr 1: Transparency (inherited, 0.0=opaque, 1.0=transparent)
-            let color_ref = rendering.get_ref(0)?;
-            let [r, g, b, _] = extract_color_rgb(color_ref, decoder)?;
-            
-            // Read transparency and convert to alpha
-            // Transparency: 0.0 = opaque, 1.0 = fully transparent
-            // Alpha: 1.0 = opaque, 0.0 = fully transparent
-            // So: alpha = 1.0 - transparency
-            let transparency = rendering.get_float(1).unwrap_or(0.0);
-            let alpha = 1.0 - transparency as f32;
-            
-            return Some([r, g, b, alpha.max(0.0).min(1.0)]);
-        }
-        _ => {}
-    }
-
-    None
-}
-
-/// Extract RGB color from IfcColourRgb
-fn extract_color_rgb(
-    color_id: u32,
-    decoder: &mut ifc_lite_core::EntityDecoder,
-) -> Option<[f32; 4]> {
-    use ifc_lite_core::IfcType;
-
-    let color = decoder.decode_by_id(color_id).ok()?;
-
-    if color.ifc_type != IfcType::IfcColourRgb {
-        return None;
-    }
-
-    // IfcColourRgb: Name, Red, Green, Blue
-    // Note: In IFC2x3, attributes are at indices 1, 2, 3 (0 is Name)
-    // In IFC4, attributes are also at 1, 2, 3
-    let red = color.get_float(1).unwrap_or(0.8);
-    let green = color.get_float(2).unwrap_or(0.8);
-    let blue = color.get_float(3).unwrap_or(0.8);
-
-    Some([red as f32, green as f32, blue as f32, 1.0])
-}
-
-/// Get default color for IFC type (matches default-materials.ts)
-fn get_default_color_for_type(ifc_type: &ifc_lite_core::IfcType) -> [f32; 4] {
-    use ifc_lite_core::IfcType;
-
-    match ifc_type {
-        // Walls - light gray
-        IfcType::IfcWall | IfcType::IfcWallStandardCase => [0.85, 0.85, 0.85, 1.0],
-
-        // Slabs - darker gray
-        IfcType::IfcSlab => [0.7, 0.7, 0.7, 1.0],
-
-        // Roofs - brown-ish
-        IfcType::IfcRoof => [0.6, 0.5, 0.4, 1.0],
-
-        // Columns/Beams - steel gray
-        IfcType::IfcColumn | IfcType::IfcBeam | IfcType::IfcMember => [0.6, 0.65, 0.7, 1.0],
-
-        // Windows - light blue transparent
-        IfcType::IfcWindow => [0.6, 0.8, 1.0, 0.4],
-
-        // Doors - wood brown
-        IfcType::IfcDoor => [0.6, 0.45, 0.3, 1.0],
-
-        // Stairs
-        IfcType::IfcStair => [0.75, 0.75, 0.75, 1.0],
-
-        // Railings
-        IfcType::IfcRailing => [0.4, 0.4, 0.45, 1.0],
-
-        // Plates/Coverings
-        IfcType::IfcPlate | IfcType::IfcCovering => [0.8, 0.8, 0.8, 1.0],
-
-        // Curtain walls - glass blue
-        IfcType::IfcCurtainWall => [0.5, 0.7, 0.9, 0.5],
-
-        // Furniture - wood
-        IfcType::IfcFurnishingElement => [0.7, 0.55, 0.4, 1.0],
-
-        // Spaces - cyan transparent (matches MainToolbar)
-        IfcType::IfcSpace => [0.2, 0.85, 1.0, 0.3],
-
-        // Opening elements - red-orange transparent
-        IfcType::IfcOpeningElement => [1.0, 0.42, 0.29, 0.4],
-
-        // Site - green
-        IfcType::IfcSite => [0.4, 0.8, 0.3, 1.0],
-
-        // Default gray
-        _ => [0.8, 0.8, 0.8, 1.0],
-    }
-}
-
 /// Safely set a property on a JavaScript object.
 /// Returns true if successful, false otherwise.
 /// This avoids panicking on edge cases like non-extensible objects.
@@ -6,8 +6,11 @@
 //!
 //! This module provides geometry data structures that are pre-processed for direct
 //! GPU upload without intermediate copies. Data is:
-//! - Interleaved (position + normal per vertex)
+//! - Interleaved (position + normal per vertex), with texture coordinates in a
+//!   separate buffer when any mesh is textured
 //! - Coordinate-converted (Z-up to Y-up)
+//! - Accompanied by line segments and text labels for curve-only elements
+//!   (grid axes, annotations, wall axes)
 //! - Stored contiguously for efficient memory access
 //!
 //! # Zero-Copy Pattern
@@ -33,6 +36,9 @@
 //! gpuGeom.free();
 //! ```
 
+use ifc_lite_geometry::{
+    CurveSet, Mesh, OrientedBoundingBox, Point3, SurfaceMaterial, SurfaceSide,
+};
 use wasm_bindgen::prelude::*;
 
 /// Metadata for a single mesh within the GPU geometry buffer
@@ -53,8 +59,19 @@ pub struct GpuMeshMetadata {
     index_count: u32,
     /// RGBA color
     color: [f32; 4],
+    /// PBR roughness approximation (0-1)
+    roughness: f32,
+    /// PBR metallic approximation (0-1)
+    metallic: f32,
+    /// Whether both faces should be rendered
+    double_sided: bool,
+    /// Index into the texture URL table (-1 = untextured)
+    texture_idx: i32,
 }
 
+/// Roughness of meshes without a surface style
+const DEFAULT_ROUGHNESS: f32 = 0.8;
+
 #[wasm_bindgen]
 impl GpuMeshMetadata {
     #[wasm_bindgen(getter, js_name = expressId)]
@@ -91,6 +108,152 @@ impl GpuMeshMetadata {
     pub fn color(&self) -> Vec<f32> {
         self.color.to_vec()
     }
+
+    /// PBR roughness approximation (0-1)
+    #[wasm_bindgen(getter)]
+    pub fn roughness(&self) -> f32 {
+        self.roughness
+    }
+
+    /// PBR metallic approximation (0-1)
+    #[wasm_bindgen(getter)]
+    pub fn metallic(&self) -> f32 {
+        self.metallic
+    }
+
+    /// Whether both faces should be rendered
+    #[wasm_bindgen(getter, js_name = doubleSided)]
+    pub fn double_sided(&self) -> bool {
+        self.double_sided
+    }
+
+    /// Index into the texture URL table (-1 = untextured)
+    #[wasm_bindgen(getter, js_name = textureIdx)]
+    pub fn texture_idx(&self) -> i32 {
+        self.texture_idx
+    }
+}
+
+/// Metadata for the line segments of a single element
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct GpuLineMetadata {
+    /// Express ID of the IFC entity
+    express_id: u32,
+    /// Index into the IFC type string table
+    ifc_type_idx: u16,
+    /// First vertex in line_vertex_data (in vertices, not floats)
+    vertex_offset: u32,
+    /// Number of vertices
+    vertex_count: u32,
+    /// Offset in line_indices array
+    index_offset: u32,
+    /// Number of indices (two per segment)
+    index_count: u32,
+    /// RGBA color
+    color: [f32; 4],
+}
+
+#[wasm_bindgen]
+impl GpuLineMetadata {
+    #[wasm_bindgen(getter, js_name = expressId)]
+    pub fn express_id(&self) -> u32 {
+        self.express_id
+    }
+
+    #[wasm_bindgen(getter, js_name = ifcTypeIdx)]
+    pub fn ifc_type_idx(&self) -> u16 {
+        self.ifc_type_idx
+    }
+
+    #[wasm_bindgen(getter, js_name = vertexOffset)]
+    pub fn vertex_offset(&self) -> u32 {
+        self.vertex_offset
+    }
+
+    #[wasm_bindgen(getter, js_name = vertexCount)]
+    pub fn vertex_count(&self) -> u32 {
+        self.vertex_count
+    }
+
+    #[wasm_bindgen(getter, js_name = indexOffset)]
+    pub fn index_offset(&self) -> u32 {
+        self.index_offset
+    }
+
+    #[wasm_bindgen(getter, js_name = indexCount)]
+    pub fn index_count(&self) -> u32 {
+        self.index_count
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn color(&self) -> Vec<f32> {
+        self.color.to_vec()
+    }
+}
+
+/// Oriented bounding box of a mesh (Y-up), computed on request
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct GpuOrientedBox {
+    /// Center
+    center: [f32; 3],
+    /// Half extents, longest first
+    half_extents: [f32; 3],
+    /// Rotation quaternion [x, y, z, w]
+    rotation: [f32; 4],
+}
+
+#[wasm_bindgen]
+impl GpuOrientedBox {
+    /// Center [x, y, z]
+    #[wasm_bindgen(getter)]
+    pub fn center(&self) -> Vec<f32> {
+        self.center.to_vec()
+    }
+
+    /// Half extents, longest axis first
+    #[wasm_bindgen(getter, js_name = halfExtents)]
+    pub fn half_extents(&self) -> Vec<f32> {
+        self.half_extents.to_vec()
+    }
+
+    /// Rotation quaternion [x, y, z, w]
+    #[wasm_bindgen(getter)]
+    pub fn rotation(&self) -> Vec<f32> {
+        self.rotation.to_vec()
+    }
+}
+
+/// Text label anchored in the model, e.g. a grid axis tag
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct GpuLabel {
+    /// Express ID of the IFC entity
+    express_id: u32,
+    /// Label text
+    text: String,
+    /// Anchor position (Y-up)
+    position: [f32; 3],
+}
+
+#[wasm_bindgen]
+impl GpuLabel {
+    #[wasm_bindgen(getter, js_name = expressId)]
+    pub fn express_id(&self) -> u32 {
+        self.express_id
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    /// Anchor position [x, y, z]
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> Vec<f32> {
+        self.position.to_vec()
+    }
 }
 
 /// GPU-ready geometry stored in WASM linear memory
@@ -98,7 +261,11 @@ impl GpuMeshMetadata {
 /// Data layout:
 /// - vertex_data: Interleaved [px, py, pz, nx, ny, nz, ...] (6 floats per vertex)
 /// - indices: Triangle indices [i0, i1, i2, ...]
+/// - uv_data: [u, v, ...] (2 floats per vertex), empty until a textured mesh is added
 /// - mesh_metadata: Per-mesh metadata for draw calls
+/// - line_vertex_data: [px, py, pz, ...] (3 floats per vertex) for line elements
+/// - line_indices: Line-list indices [a0, b0, a1, b1, ...], relative to line_vertex_data
+/// - line_metadata / labels: Per-element line ranges and text labels
 ///
 /// All coordinates are pre-converted from IFC Z-up to WebGL Y-up
 #[wasm_bindgen]
@@ -110,12 +277,30 @@ pub struct GpuGeometry {
     /// Triangle indices
     indices: Vec<u32>,
 
+    /// Texture coordinates [u, v, ...], parallel to vertex_data once non-empty
+    uv_data: Vec<f32>,
+
     /// Metadata per mesh (for selection, draw call ranges, etc.)
     mesh_metadata: Vec<GpuMeshMetadata>,
 
     /// IFC type names (deduplicated)
     ifc_type_names: Vec<String>,
 
+    /// Texture image URLs (deduplicated)
+    texture_urls: Vec<String>,
+
+    /// Line vertex positions [px, py, pz, ...], already converted to Y-up
+    line_vertex_data: Vec<f32>,
+
+    /// Line-list indices, two per segment
+    line_indices: Vec<u32>,
+
+    /// Metadata per line element
+    line_metadata: Vec<GpuLineMetadata>,
+
+    /// Text labels (grid axis tags, annotation text)
+    labels: Vec<GpuLabel>,
+
     /// RTC (Relative To Center) offset applied to coordinates
     /// Used for models with large world coordinates (>10km from origin)
     rtc_offset_x: f64,
@@ -131,8 +316,14 @@ impl GpuGeometry {
         Self {
             vertex_data: Vec::new(),
             indices: Vec::new(),
+            uv_data: Vec::new(),
             mesh_metadata: Vec::new(),
             ifc_type_names: Vec::new(),
+            texture_urls: Vec::new(),
+            line_vertex_data: Vec::new(),
+            line_indices: Vec::new(),
+            line_metadata: Vec::new(),
+            labels: Vec::new(),
             rtc_offset_x: 0.0,
             rtc_offset_y: 0.0,
             rtc_offset_z: 0.0,
@@ -209,6 +400,30 @@ impl GpuGeometry {
         self.indices.len() * 4 // u32 = 4 bytes
     }
 
+    /// Get pointer to texture coordinate data for zero-copy view
+    #[wasm_bindgen(getter, js_name = uvDataPtr)]
+    pub fn uv_data_ptr(&self) -> *const f32 {
+        self.uv_data.as_ptr()
+    }
+
+    /// Get length of texture coordinate data (in f32 elements, 0 if untextured)
+    #[wasm_bindgen(getter, js_name = uvDataLen)]
+    pub fn uv_data_len(&self) -> usize {
+        self.uv_data.len()
+    }
+
+    /// Check if any mesh in this batch has texture coordinates
+    #[wasm_bindgen(getter, js_name = hasUvs)]
+    pub fn has_uvs(&self) -> bool {
+        !self.uv_data.is_empty()
+    }
+
+    /// Get texture image URL by index
+    #[wasm_bindgen(js_name = getTextureUrl)]
+    pub fn get_texture_url(&self, index: u32) -> Option<String> {
+        self.texture_urls.get(index as usize).cloned()
+    }
+
     /// Get number of meshes in this geometry batch
     #[wasm_bindgen(getter, js_name = meshCount)]
     pub fn mesh_count(&self) -> usize {
@@ -233,16 +448,96 @@ impl GpuGeometry {
         self.mesh_metadata.get(index).cloned()
     }
 
+    /// Compute the oriented bounding box of a mesh
+    ///
+    /// Not stored with the metadata since most callers never need it; each
+    /// call fits the box to the mesh's vertices again.
+    #[wasm_bindgen(js_name = getMeshObb)]
+    pub fn get_mesh_obb(&self, index: usize) -> Option<GpuOrientedBox> {
+        let meta = self.mesh_metadata.get(index)?;
+        let start = meta.vertex_offset as usize * 6;
+        let end = start + meta.vertex_count as usize * 6;
+        let points: Vec<Point3<f64>> = self.vertex_data[start..end]
+            .chunks_exact(6)
+            .map(|v| Point3::new(v[0] as f64, v[1] as f64, v[2] as f64))
+            .collect();
+        let obb = OrientedBoundingBox::from_points(&points)?;
+        let q = obb.rotation();
+        Some(GpuOrientedBox {
+            center: [obb.center.x as f32, obb.center.y as f32, obb.center.z as f32],
+            half_extents: [
+                obb.half_extents.x as f32,
+                obb.half_extents.y as f32,
+                obb.half_extents.z as f32,
+            ],
+            rotation: [q.i as f32, q.j as f32, q.k as f32, q.w as f32],
+        })
+    }
+
     /// Get IFC type name by index
     #[wasm_bindgen(js_name = getIfcTypeName)]
     pub fn get_ifc_type_name(&self, index: u16) -> Option<String> {
         self.ifc_type_names.get(index as usize).cloned()
     }
 
-    /// Check if geometry is empty
+    /// Get pointer to line vertex data for zero-copy view
+    #[wasm_bindgen(getter, js_name = lineVertexDataPtr)]
+    pub fn line_vertex_data_ptr(&self) -> *const f32 {
+        self.line_vertex_data.as_ptr()
+    }
+
+    /// Get length of line vertex data (in f32 elements, 3 per vertex)
+    #[wasm_bindgen(getter, js_name = lineVertexDataLen)]
+    pub fn line_vertex_data_len(&self) -> usize {
+        self.line_vertex_data.len()
+    }
+
+    /// Get pointer to line-list indices for zero-copy view
+    #[wasm_bindgen(getter, js_name = lineIndicesPtr)]
+    pub fn line_indices_ptr(&self) -> *const u32 {
+        self.line_indices.as_ptr()
+    }
+
+    /// Get length of line-list indices (two per segment)
+    #[wasm_bindgen(getter, js_name = lineIndicesLen)]
+    pub fn line_indices_len(&self) -> usize {
+        self.line_indices.len()
+    }
+
+    /// Get number of line elements in this batch
+    #[wasm_bindgen(getter, js_name = lineCount)]
+    pub fn line_count(&self) -> usize {
+        self.line_metadata.len()
+    }
+
+    /// Get total line segment count
+    #[wasm_bindgen(getter, js_name = totalSegmentCount)]
+    pub fn total_segment_count(&self) -> usize {
+        self.line_indices.len() / 2
+    }
+
+    /// Get metadata for a specific line element
+    #[wasm_bindgen(js_name = getLineMetadata)]
+    pub fn get_line_metadata(&self, index: usize) -> Option<GpuLineMetadata> {
+        self.line_metadata.get(index).cloned()
+    }
+
+    /// Get number of text labels
+    #[wasm_bindgen(getter, js_name = labelCount)]
+    pub fn label_count(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Get a text label
+    #[wasm_bindgen(js_name = getLabel)]
+    pub fn get_label(&self, index: usize) -> Option<GpuLabel> {
+        self.labels.get(index).cloned()
+    }
+
+    /// Check if geometry is empty (no meshes, lines or labels)
     #[wasm_bindgen(getter, js_name = isEmpty)]
     pub fn is_empty(&self) -> bool {
-        self.vertex_data.is_empty()
+        self.vertex_data.is_empty() && self.line_vertex_data.is_empty() && self.labels.is_empty()
     }
 }
 
@@ -252,8 +547,14 @@ impl GpuGeometry {
         Self {
             vertex_data: Vec::with_capacity(vertex_capacity),
             indices: Vec::with_capacity(index_capacity),
+            uv_data: Vec::new(),
             mesh_metadata: Vec::with_capacity(256),
             ifc_type_names: Vec::with_capacity(64),
+            texture_urls: Vec::new(),
+            line_vertex_data: Vec::new(),
+            line_indices: Vec::new(),
+            line_metadata: Vec::new(),
+            labels: Vec::new(),
             rtc_offset_x: 0.0,
             rtc_offset_y: 0.0,
             rtc_offset_z: 0.0,
@@ -322,9 +623,115 @@ impl GpuGeometry {
             index_offset,
             index_count: indices.len() as u32,
             color,
+            roughness: DEFAULT_ROUGHNESS,
+            metallic: 0.0,
+            double_sided: false,
+            texture_idx: -1,
+        });
+    }
+
+    /// Add a mesh with its surface material and texture coordinates
+    pub fn add_mesh_with_material(
+        &mut self,
+        express_id: u32,
+        ifc_type: &str,
+        mesh: &Mesh,
+        color: [f32; 4],
+        material: Option<&SurfaceMaterial>,
+    ) {
+        let vertex_start = self.vertex_data.len() / 6;
+        let mesh_count = self.mesh_metadata.len();
+        self.add_mesh(
+            express_id,
+            ifc_type,
+            &mesh.positions,
+            &mesh.normals,
+            &mesh.indices,
+            color,
+        );
+        if self.mesh_metadata.len() == mesh_count {
+            return; // Empty mesh was skipped
+        }
+
+        if mesh.has_uvs() {
+            // Pad earlier untextured meshes so uv_data stays parallel to vertex_data
+            self.uv_data.resize(vertex_start * 2, 0.0);
+            self.uv_data.extend_from_slice(&mesh.uvs);
+        } else if !self.uv_data.is_empty() {
+            self.uv_data.resize(self.vertex_data.len() / 6 * 2, 0.0);
+        }
+
+        let Some(material) = material else {
+            return;
+        };
+        let texture_idx = match material.texture_url() {
+            Some(url) if mesh.has_uvs() => self.get_or_add_texture_url(url) as i32,
+            _ => -1,
+        };
+        if let Some(metadata) = self.mesh_metadata.last_mut() {
+            metadata.roughness = material.roughness();
+            metadata.metallic = material.metallic();
+            metadata.double_sided = material.side == SurfaceSide::Both;
+            metadata.texture_idx = texture_idx;
+        }
+    }
+
+    /// Add the line segments and labels of a curve-only element, converting coordinates
+    pub fn add_curves(
+        &mut self,
+        express_id: u32,
+        ifc_type: &str,
+        curves: &CurveSet,
+        color: [f32; 4],
+    ) {
+        let (positions, indices) = curves.to_line_segments();
+
+        for label in &curves.labels {
+            let [x, y, z] = label.position;
+            self.labels.push(GpuLabel {
+                express_id,
+                text: label.text.clone(),
+                position: [x, z, -y],
+            });
+        }
+
+        let vertex_count = positions.len() / 3;
+        if vertex_count == 0 {
+            return;
+        }
+
+        let ifc_type_idx = self.get_or_add_ifc_type(ifc_type);
+        let vertex_offset = (self.line_vertex_data.len() / 3) as u32;
+        let index_offset = self.line_indices.len() as u32;
+
+        // Convert Z-up to Y-up: (x, y, z) -> (x, z, -y)
+        self.line_vertex_data.reserve(positions.len());
+        for p in positions.chunks_exact(3) {
+            self.line_vertex_data.extend_from_slice(&[p[0], p[2], -p[1]]);
+        }
+        self.line_indices
+            .extend(indices.iter().map(|&idx| idx + vertex_offset));
+
+        self.line_metadata.push(GpuLineMetadata {
+            express_id,
+            ifc_type_idx,
+            vertex_offset,
+            vertex_count: vertex_count as u32,
+            index_offset,
+            index_count: indices.len() as u32,
+            color,
         });
     }
 
+    /// Get or add a texture URL to the string table
+    fn get_or_add_texture_url(&mut self, url: &str) -> u32 {
+        if let Some(i) = self.texture_urls.iter().position(|u| u == url) {
+            return i as u32;
+        }
+        self.texture_urls.push(url.to_string());
+        (self.texture_urls.len() - 1) as u32
+    }
+
     /// Get or add an IFC type name to the string table
     fn get_or_add_ifc_type(&mut self, ifc_type: &str) -> u16 {
         // Check if already exists
@@ -344,8 +751,13 @@ impl GpuGeometry {
     pub fn clear(&mut self) {
         self.vertex_data.clear();
         self.indices.clear();
+        self.uv_data.clear();
         self.mesh_metadata.clear();
-        // Keep ifc_type_names for reuse
+        self.line_vertex_data.clear();
+        self.line_indices.clear();
+        self.line_metadata.clear();
+        self.labels.clear();
+        // Keep ifc_type_names and texture_urls for reuse
     }
 }
 
@@ -720,6 +1132,34 @@ mod tests {
         assert_eq!(geom.vertex_data[5], 0.0); // nz = -old ny
     }
 
+    #[test]
+    fn test_gpu_geometry_add_curves() {
+        use ifc_lite_geometry::Polyline3D;
+
+        let mut curves = CurveSet::new();
+        curves.add_polyline(Polyline3D::from_points(&[
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 6.0, 0.0),
+        ]));
+        curves.add_label("A", Point3::new(0.0, 6.0, 0.0));
+
+        let mut geom = GpuGeometry::new();
+        geom.add_curves(7, "IfcGrid", &curves, [0.0, 0.0, 0.0, 1.0]);
+        geom.add_curves(8, "IfcGrid", &curves, [0.0, 0.0, 0.0, 1.0]);
+
+        assert!(!geom.is_empty());
+        assert_eq!(geom.mesh_count(), 0);
+        assert_eq!(geom.line_count(), 2);
+        assert_eq!(geom.total_segment_count(), 2);
+        assert_eq!(geom.line_indices, vec![0, 1, 2, 3]);
+        assert_eq!(&geom.line_vertex_data[3..6], &[0.0, 0.0, -6.0]);
+
+        let label = geom.get_label(1).unwrap();
+        assert_eq!(label.express_id(), 8);
+        assert_eq!(label.text(), "A");
+        assert_eq!(label.position(), vec![0.0, 0.0, -6.0]);
+    }
+
     #[test]
     fn test_instanced_geometry() {
         let mut geom = GpuInstancedGeometry::new(12345);
@@ -68,7 +68,7 @@ mod gpu_geometry;
 mod utils;
 mod zero_copy;
 
-pub use api::IfcAPI;
+pub use api::{IfcAPI, ModelQuery};
 pub use gpu_geometry::{
     GpuGeometry, GpuInstancedGeometry, GpuInstancedGeometryCollection, GpuInstancedGeometryRef,
     GpuMeshMetadata,